@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE tracks ALTER COLUMN isrc_code DROP NOT NULL;
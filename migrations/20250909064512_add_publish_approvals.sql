@@ -0,0 +1,19 @@
+-- Add migration script here
+ALTER TABLE labels ADD COLUMN require_approval BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS approvals (
+    id BIGSERIAL PRIMARY KEY,
+    release_id BIGINT NOT NULL,
+    requested_by BIGINT NOT NULL,
+    approved_by BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW (),
+    approved_at TIMESTAMPTZ,
+    FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE,
+    FOREIGN KEY (requested_by) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (approved_by) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Only one pending approval per release
+CREATE UNIQUE INDEX IF NOT EXISTS approvals_pending_release_index ON approvals (release_id)
+WHERE
+    approved_at IS NULL;
@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE tracks ADD COLUMN preview_url TEXT;
+ALTER TABLE tracks ADD COLUMN preview_start_ms INT;
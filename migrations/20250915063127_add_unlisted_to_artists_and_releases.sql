@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE artists ADD COLUMN unlisted BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE releases ADD COLUMN unlisted BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE artists ADD COLUMN monthly_listeners INTEGER;
+ALTER TABLE artists ADD COLUMN stats_updated_at TIMESTAMPTZ;
@@ -0,0 +1,11 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS release_credits (
+    id BIGSERIAL PRIMARY KEY,
+    release_id BIGINT NOT NULL,
+    role TEXT NOT NULL,
+    name TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS release_credits_release_id_index ON release_credits (release_id);
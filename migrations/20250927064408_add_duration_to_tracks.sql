@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE tracks ADD COLUMN duration_ms INT;
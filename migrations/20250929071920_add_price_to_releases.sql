@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE labels ADD COLUMN currency TEXT NOT NULL DEFAULT 'GBP';
+ALTER TABLE releases ADD COLUMN price_cents INT;
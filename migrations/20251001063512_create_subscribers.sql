@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS subscribers (
+    id BIGSERIAL PRIMARY KEY,
+    label_id BIGINT NOT NULL,
+    email TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW (),
+    FOREIGN KEY (label_id) REFERENCES labels (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS subscribers_label_id_email_index ON subscribers (label_id, email);
+
+-- Releases that have already been included in a digest, so they are only sent once
+CREATE TABLE IF NOT EXISTS digest_releases (
+    release_id BIGINT PRIMARY KEY,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW (),
+    FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE release_artists ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
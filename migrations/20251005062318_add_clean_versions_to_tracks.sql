@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE tracks ADD COLUMN explicit BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE tracks ADD COLUMN clean_version_of BIGINT;
+ALTER TABLE tracks ADD CONSTRAINT fk_clean_version_of FOREIGN KEY (clean_version_of) REFERENCES tracks(id) ON DELETE SET NULL;
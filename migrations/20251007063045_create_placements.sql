@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TYPE PlacementEntity AS ENUM (
+    'Release',
+    'Track'
+);
+
+-- Playlists a release or track has been featured on, for reporting
+CREATE TABLE IF NOT EXISTS placements (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type PlacementEntity NOT NULL,
+    entity_id BIGINT NOT NULL,
+    playlist_name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW ()
+);
+
+CREATE INDEX IF NOT EXISTS placements_entity_index ON placements (entity_type, entity_id);
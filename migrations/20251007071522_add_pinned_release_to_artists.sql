@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE artists
+ADD COLUMN pinned_release_id BIGINT REFERENCES releases (id) ON DELETE SET NULL;
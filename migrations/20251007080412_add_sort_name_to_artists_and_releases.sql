@@ -0,0 +1,15 @@
+-- Add migration script here
+ALTER TABLE artists
+ADD COLUMN sort_name TEXT;
+
+ALTER TABLE releases
+ADD COLUMN sort_name TEXT;
+
+-- The name used for alphabetical ordering. Falls back to the name with a leading article stripped.
+-- Keep in sync with `utils::sort_name::default_sort_name`.
+CREATE OR REPLACE FUNCTION sort_name_or_default (sort_name TEXT, name TEXT) RETURNS TEXT AS $$
+    SELECT COALESCE(
+        NULLIF(TRIM(sort_name), ''),
+        REGEXP_REPLACE(TRIM(name), '^(the|an|a)\s+', '', 'i')
+    )
+$$ LANGUAGE SQL IMMUTABLE;
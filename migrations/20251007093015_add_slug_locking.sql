@@ -0,0 +1,20 @@
+-- Add migration script here
+ALTER TABLE artists
+ADD COLUMN lock_slug BOOLEAN;
+
+ALTER TABLE releases
+ADD COLUMN lock_slug BOOLEAN;
+
+ALTER TABLE tracks
+ADD COLUMN lock_slug BOOLEAN;
+
+CREATE TABLE slug_redirects (
+    id BIGSERIAL PRIMARY KEY,
+    entity_type TEXT NOT NULL,
+    entity_id BIGINT NOT NULL,
+    old_slug TEXT NOT NULL,
+    new_slug TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX slug_redirects_entity_type_old_slug_idx ON slug_redirects (entity_type, old_slug);
@@ -0,0 +1,3 @@
+-- Add migration script here
+ALTER TABLE labels
+ADD COLUMN release_date_placeholder TEXT NOT NULL DEFAULT 'TBA';
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE releases
+ADD COLUMN embargo_until TIMESTAMPTZ DEFAULT NULL,
+ADD COLUMN preview_token TEXT DEFAULT NULL UNIQUE;
@@ -0,0 +1,9 @@
+-- Add migration script here
+ALTER TABLE releases
+ADD COLUMN first_published_at TIMESTAMPTZ DEFAULT NULL;
+
+ALTER TABLE tracks
+ADD COLUMN first_published_at TIMESTAMPTZ DEFAULT NULL;
+
+UPDATE releases SET first_published_at = published_at WHERE published_at <= NOW();
+UPDATE tracks SET first_published_at = published_at WHERE published_at <= NOW();
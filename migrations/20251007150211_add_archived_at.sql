@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE releases
+ADD COLUMN archived_at TIMESTAMPTZ DEFAULT NULL;
+
+ALTER TABLE tracks
+ADD COLUMN archived_at TIMESTAMPTZ DEFAULT NULL;
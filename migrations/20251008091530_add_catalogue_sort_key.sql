@@ -0,0 +1,25 @@
+-- Add migration script here
+-- The normalised catalogue number, used to catch the same number written in different formats.
+-- Keep in sync with `utils::catalogue_number::catalogue_sort_key`.
+CREATE OR REPLACE FUNCTION catalogue_sort_key (catalogue_number TEXT) RETURNS TEXT AS $$
+    SELECT COALESCE(
+        STRING_AGG(
+            CASE
+                WHEN part[1] ~ '^[0-9]+$' THEN LPAD(LTRIM(part[1], '0'), GREATEST(10, LENGTH(LTRIM(part[1], '0'))), '0')
+                ELSE UPPER(part[1])
+            END,
+            '' ORDER BY position
+        ),
+        ''
+    )
+    FROM REGEXP_MATCHES(
+        REGEXP_REPLACE(COALESCE(catalogue_number, ''), '[^A-Za-z0-9]', '', 'g'),
+        '([0-9]+|[^0-9]+)',
+        'g'
+    ) WITH ORDINALITY AS parts(part, position)
+$$ LANGUAGE SQL IMMUTABLE;
+
+ALTER TABLE releases
+ADD COLUMN catalogue_sort_key TEXT GENERATED ALWAYS AS (catalogue_sort_key(catalogue_number)) STORED;
+
+CREATE INDEX IF NOT EXISTS releases_catalogue_sort_key ON releases (label_id, catalogue_sort_key);
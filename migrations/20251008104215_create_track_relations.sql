@@ -0,0 +1,13 @@
+-- Add migration script here
+-- Works a track covers, samples or remixes, for rights management
+CREATE TABLE IF NOT EXISTS track_relations (
+    id BIGSERIAL PRIMARY KEY,
+    track_id BIGINT NOT NULL,
+    relation_type TEXT NOT NULL CHECK (relation_type IN ('cover_of', 'samples', 'remix_of')),
+    related_title TEXT NOT NULL,
+    related_artist TEXT,
+    related_isrc VARCHAR(12),
+    FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS track_relations_track_id_index ON track_relations (track_id);
@@ -0,0 +1,16 @@
+-- Add migration script here
+-- An ordered gallery of images for each release, e.g. covers, inlay scans and press photos
+CREATE TABLE IF NOT EXISTS release_images (
+    id BIGSERIAL PRIMARY KEY,
+    release_id BIGINT NOT NULL,
+    filename TEXT NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    alt_text TEXT NOT NULL DEFAULT '',
+    FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS release_images_release_id_position_index ON release_images (release_id, position);
+
+-- The first gallery image, used when the release has no primary image
+ALTER TABLE releases
+ADD COLUMN gallery_image TEXT DEFAULT NULL;
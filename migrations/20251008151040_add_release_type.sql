@@ -0,0 +1,13 @@
+-- Add migration script here
+CREATE TYPE ReleaseType AS ENUM (
+    'Album',
+    'Ep',
+    'Single',
+    'Compilation',
+    'Mixtape'
+);
+
+ALTER TABLE releases
+ADD COLUMN release_type ReleaseType NOT NULL DEFAULT 'Album';
+
+CREATE INDEX IF NOT EXISTS releases_release_type_index ON releases (release_type);
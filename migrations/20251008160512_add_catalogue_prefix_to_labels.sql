@@ -0,0 +1,4 @@
+-- Add migration script here
+-- The prefix for generated catalogue numbers, e.g. "CAT-" gives "CAT-0001"
+ALTER TABLE labels
+ADD COLUMN catalogue_prefix TEXT NOT NULL DEFAULT 'CAT-';
@@ -0,0 +1,6 @@
+-- Add migration script here
+-- Slugs identify artists and releases, so names no longer need to be unique, e.g. two releases called "Live"
+DROP INDEX IF EXISTS artists_name_index;
+CREATE INDEX IF NOT EXISTS artists_name_index ON artists (name);
+DROP INDEX IF EXISTS releases_name_index;
+CREATE INDEX IF NOT EXISTS releases_name_index ON releases (name);
@@ -0,0 +1,29 @@
+-- Add migration script here
+-- Genres are shared by every label, so fans can browse artists and releases by genre
+CREATE TABLE IF NOT EXISTS genres (
+    id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    slug VARCHAR(255) NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS genres_slug_index ON genres (slug);
+
+CREATE TABLE IF NOT EXISTS artist_genres (
+    artist_id BIGINT NOT NULL,
+    genre_id BIGINT NOT NULL,
+    PRIMARY KEY (artist_id, genre_id),
+    FOREIGN KEY (artist_id) REFERENCES artists (id) ON DELETE CASCADE,
+    FOREIGN KEY (genre_id) REFERENCES genres (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS artist_genres_genre_id_index ON artist_genres (genre_id);
+
+CREATE TABLE IF NOT EXISTS release_genres (
+    release_id BIGINT NOT NULL,
+    genre_id BIGINT NOT NULL,
+    PRIMARY KEY (release_id, genre_id),
+    FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE,
+    FOREIGN KEY (genre_id) REFERENCES genres (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS release_genres_genre_id_index ON release_genres (genre_id);
@@ -0,0 +1,38 @@
+-- Add migration script here
+CREATE TYPE ArtistRole AS ENUM (
+    'Primary',
+    'Featured',
+    'Remixer'
+);
+
+-- Existing rows are backfilled as primary artists
+ALTER TABLE release_artists
+ADD COLUMN role ArtistRole NOT NULL DEFAULT 'Primary';
+
+-- Where the release's primary artist is listed, the other artists were featured
+UPDATE release_artists
+SET role = 'Featured'
+FROM releases
+WHERE releases.id = release_artists.release_id
+  AND release_artists.artist_id != releases.primary_artist_id
+  AND EXISTS (
+    SELECT 1 FROM release_artists AS primary_artists
+    WHERE primary_artists.release_id = releases.id
+      AND primary_artists.artist_id = releases.primary_artist_id
+  );
+
+-- Where the primary artist isn't listed, every row is still primary, so keep only the first listed artist
+UPDATE release_artists
+SET role = 'Featured'
+WHERE role = 'Primary'
+  AND EXISTS (
+    SELECT 1 FROM release_artists AS earlier_primaries
+    WHERE earlier_primaries.release_id = release_artists.release_id
+      AND earlier_primaries.role = 'Primary'
+      AND (earlier_primaries.sort_order, earlier_primaries.artist_id)
+        < (release_artists.sort_order, release_artists.artist_id)
+  );
+
+-- A release can only have one primary artist
+CREATE UNIQUE INDEX IF NOT EXISTS release_artists_primary_index ON release_artists (release_id)
+WHERE role = 'Primary';
@@ -0,0 +1,5 @@
+-- Add migration script here
+ALTER TABLE users ADD COLUMN email_verified_at TIMESTAMPTZ;
+-- The exact time is unknown for users who have already verified
+UPDATE users SET email_verified_at = updated_at WHERE email_verified;
+ALTER TABLE users DROP COLUMN email_verified;
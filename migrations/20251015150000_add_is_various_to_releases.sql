@@ -0,0 +1,2 @@
+-- Compilations by various artists take their artists from their tracks
+ALTER TABLE releases ADD COLUMN is_various BOOLEAN NOT NULL DEFAULT FALSE;
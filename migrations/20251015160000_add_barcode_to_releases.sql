@@ -0,0 +1,3 @@
+-- UPC-A or EAN-13 barcode, unique to the record label
+ALTER TABLE releases ADD COLUMN barcode TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS releases_barcode ON releases (barcode, label_id);
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- Only a hash of each token is stored, so a leaked table can't be used to verify email addresses
+ALTER TABLE email_verification_tokens RENAME COLUMN token TO token_hash;
+UPDATE email_verification_tokens SET token_hash = encode(sha256(convert_to(token_hash, 'UTF8')), 'hex');
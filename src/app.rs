@@ -16,11 +16,13 @@ use reactive_stores::Store;
 
 use crate::components::{
     admin::{
+        archive::Archive,
         artist::{
             create::CreateArtist,
             edit::EditArtist,
             images::EditArtistImages,
             links::EditArtistLinks,
+            managers::EditArtistManagers,
             release::{
                 create::CreateRelease,
                 edit::EditRelease,
@@ -37,12 +39,23 @@ use crate::components::{
     },
     artist::{home::ArtistPage, list::ArtistsPage, release::ReleasePage},
     auth::{
-        change_password::ChangePassword, login::Login, logout::Logout, profile::EditProfile,
+        change_password::ChangePassword,
+        login::Login,
+        logout::Logout,
+        profile::EditProfile,
         register::Register,
+        reset_password::{ForgotPassword, ResetPassword},
+        verify_email::VerifyEmail,
     },
     page::PageDetails,
     record_label::{footer::LabelFooter, header::LabelHeader, home::RecordLabelHome},
-    utils::{error::ErrorPage, loading::Loading, not_found::NotFound},
+    utils::{
+        analytics_gate::AnalyticsGate, error::ErrorPage, loading::Loading,
+        maintenance_banner::MaintenanceBanner, not_found::NotFound,
+    },
+};
+use crate::config::analytics::{
+    ANALYTICS_META_NAME, analytics_snippet, analytics_snippet_for_path,
 };
 use crate::models::auth::User;
 use crate::routes::auth::get_user;
@@ -51,6 +64,11 @@ use crate::store::GlobalState;
 /// HTML shell for the application.
 #[must_use]
 pub fn shell(options: LeptosOptions) -> impl IntoView {
+    // Analytics are only added to public pages
+    let analytics = use_context::<http::request::Parts>()
+        .and_then(|parts| analytics_snippet_for_path(parts.uri.path(), analytics_snippet()))
+        .map(leptos::tachys::html::InertElement::new);
+
     view! {
         <!DOCTYPE html>
         <html lang="en">
@@ -60,6 +78,10 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <AutoReload options=options.clone() />
                 <HydrationScripts options />
                 <MetaTags />
+                {analytics
+                    .is_some()
+                    .then(|| view! { <meta name=ANALYTICS_META_NAME content="enabled" /> })}
+                {analytics}
             </head>
             <body>
                 <WhiteLabel />
@@ -75,6 +97,7 @@ pub struct UserContext(pub ReadSignal<User>, pub WriteSignal<User>);
 /// Renders the main application.
 #[component]
 #[must_use]
+#[allow(clippy::too_many_lines)] // components are a pain to make smaller
 pub fn WhiteLabel() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
@@ -118,7 +141,10 @@ pub fn WhiteLabel() -> impl IntoView {
             </ErrorBoundary>
         </Transition>
 
+        <MaintenanceBanner />
+
         <Router>
+            <AnalyticsGate />
             <LabelHeader />
 
             <main>
@@ -133,6 +159,9 @@ pub fn WhiteLabel() -> impl IntoView {
 
                     <Route path=path!("login") view=Login />
                     <Route path=path!("register") view=Register />
+                    <Route path=path!("verify-email/:token") view=VerifyEmail />
+                    <Route path=path!("forgot-password") view=ForgotPassword />
+                    <Route path=path!("reset-password/:token") view=ResetPassword />
                     <Route path=path!("logout") view=Logout />
                     <Route path=path!("profile") view=EditProfile />
                     <Route path=path!("profile/change-password") view=ChangePassword />
@@ -140,12 +169,15 @@ pub fn WhiteLabel() -> impl IntoView {
                     <ParentRoute path=path!("admin") view=AdminRoot>
                         <Route path=path!("") view=Dashboard />
                         <Route path=path!("label") view=EditLabel />
+                        <Route path=path!("archive") view=Archive />
                         <ParentRoute path=path!("artist") view=AdminArtistRoot>
                             <Route path=path!("") view=CreateArtist />
                             <Route path=path!(":artist_slug") view=EditArtist />
                             <Route path=path!(":artist_slug/") view=EditArtist />
                             <Route path=path!(":artist_slug/links") view=EditArtistLinks />
                             <Route path=path!(":artist_slug/links/") view=EditArtistLinks />
+                            <Route path=path!(":artist_slug/managers") view=EditArtistManagers />
+                            <Route path=path!(":artist_slug/managers/") view=EditArtistManagers />
                             <Route path=path!(":artist_slug/images") view=EditArtistImages />
                             <Route path=path!(":artist_slug/images/") view=EditArtistImages />
                             <Route path=path!(":artist_slug/releases") view=Releases />
@@ -0,0 +1,161 @@
+//! List the archived releases and tracks.
+//!
+//! Archived items are kept out of public listings, but can still be reached by their slug.
+//! They are kept apart from the trash, and can be unarchived from here.
+
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+use crate::components::utils::{
+    error::{ErrorPage, ServerErrors},
+    loading::Loading,
+    permissions::permission_or_redirect,
+};
+use crate::routes::release::{ArchiveRelease, get_archived_releases};
+use crate::routes::track::{ArchiveTrack, get_archived_tracks};
+
+fn archived_on(archived_at: Option<chrono::DateTime<chrono::Utc>>) -> String {
+    archived_at.map_or_else(String::new, |date| {
+        format!("Archived {}", date.format("%e %B %Y"))
+    })
+}
+
+/// Renders the admin archive page.
+#[component]
+#[allow(clippy::too_many_lines)] // components are a pain to make smaller
+pub fn Archive() -> impl IntoView {
+    Effect::new_isomorphic(move || {
+        permission_or_redirect("label_owner", "/admin");
+    });
+
+    let unarchive_release = ServerAction::<ArchiveRelease>::new();
+    let unarchive_track = ServerAction::<ArchiveTrack>::new();
+    let releases_resource = Resource::new(
+        move || unarchive_release.version().get(),
+        |_| get_archived_releases(),
+    );
+    let tracks_resource = Resource::new(
+        move || unarchive_track.version().get(),
+        |_| get_archived_tracks(),
+    );
+
+    view! {
+        <Title text="Archive" />
+        <h1>"Archive"</h1>
+
+        <div class="grid gap-6">
+            {move || match unarchive_release.value().get() {
+                Some(Err(errors)) => {
+                    view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                }
+                _ => view! { "" }.into_any(),
+            }}
+            {move || match unarchive_track.value().get() {
+                Some(Err(errors)) => {
+                    view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                }
+                _ => view! { "" }.into_any(),
+            }}
+
+            <div class="divider">Releases</div>
+            <Transition fallback=Loading>
+                <ErrorBoundary fallback=|_| {
+                    ErrorPage
+                }>
+                    {move || Suspend::new(async move {
+                        releases_resource
+                            .await
+                            .map(|result| {
+                                if result.releases.is_empty() {
+                                    return view! { <p>"No releases have been archived."</p> }
+                                        .into_any();
+                                }
+                                view! {
+                                    <ul class="list">
+                                        {result
+                                            .releases
+                                            .into_iter()
+                                            .map(|release| {
+                                                view! {
+                                                    <li class="list-row">
+                                                        <span class="list-col-grow">{release.name}</span>
+                                                        <span>{archived_on(release.archived_at)}</span>
+                                                        <ActionForm action=unarchive_release>
+                                                            <input
+                                                                name="slug"
+                                                                type="hidden"
+                                                                value=release.slug
+                                                            />
+                                                            <input
+                                                                name="archived"
+                                                                type="hidden"
+                                                                value="false"
+                                                            />
+                                                            <button class="btn btn-sm btn-secondary">
+                                                                "Unarchive"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            })
+                    })}
+                </ErrorBoundary>
+            </Transition>
+
+            <div class="divider">Tracks</div>
+            <Transition fallback=Loading>
+                <ErrorBoundary fallback=|_| {
+                    ErrorPage
+                }>
+                    {move || Suspend::new(async move {
+                        tracks_resource
+                            .await
+                            .map(|result| {
+                                if result.tracks.is_empty() {
+                                    return view! { <p>"No tracks have been archived."</p> }
+                                        .into_any();
+                                }
+                                view! {
+                                    <ul class="list">
+                                        {result
+                                            .tracks
+                                            .into_iter()
+                                            .map(|track| {
+                                                view! {
+                                                    <li class="list-row">
+                                                        <span class="list-col-grow">{track.name}</span>
+                                                        <span>{archived_on(track.archived_at)}</span>
+                                                        <ActionForm action=unarchive_track>
+                                                            <input
+                                                                name="slug"
+                                                                type="hidden"
+                                                                value=track.slug
+                                                            />
+                                                            <input
+                                                                name="archived"
+                                                                type="hidden"
+                                                                value="false"
+                                                            />
+                                                            <button class="btn btn-sm btn-secondary">
+                                                                "Unarchive"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            })
+                    })}
+                </ErrorBoundary>
+            </Transition>
+        </div>
+    }
+}
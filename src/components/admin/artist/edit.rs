@@ -107,6 +107,15 @@ fn Form(artist: Subfield<Store<GlobalState>, GlobalState, Artist>) -> impl IntoV
                 value=move || artist.get().name
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="text"
+                class="grow"
+                placeholder="Sort name (optional)"
+                name="artist_form[sort_name]"
+                value=move || artist.get().sort_name
+            />
+        </label>
         {move || {
             view! {
                 <MarkdownField
@@ -136,6 +145,26 @@ fn Form(artist: Subfield<Store<GlobalState>, GlobalState, Artist>) -> impl IntoV
                 />
             }
         }}
+        <label class="flex gap-2 items-center input">
+            <input
+                type="number"
+                min="1"
+                class="grow"
+                placeholder="Pinned release (release ID)"
+                name="artist_form[pinned_release_id]"
+                value=move || artist.get().pinned_release_id
+            />
+        </label>
+        <label class="label">
+            <input
+                type="checkbox"
+                class="checkbox"
+                name="artist_form[unlisted]"
+                value="true"
+                checked=move || artist.get().unlisted
+            />
+            Unlisted
+        </label>
         <div class="flex flex-auto gap-6">
             <button class="flex-1 btn btn-primary">Update</button>
             {move || {
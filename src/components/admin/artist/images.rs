@@ -34,7 +34,10 @@ pub fn EditArtistImages() -> impl IntoView {
                     />
                 }
             }}
-            <img src=move || artist.get().primary_image_url() alt=move || artist.get().name />
+            <img
+                src=move || artist.get().primary_image_url(&store.record_label().get().placeholder_images)
+                alt=move || artist.get().name
+            />
         </div>
     }
 }
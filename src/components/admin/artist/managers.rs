@@ -0,0 +1,123 @@
+//! Assign the users who manage an artist.
+//!
+//! Managers can edit the artist's releases, tracks and links, but publishing goes through approval.
+
+use leptos::prelude::*;
+use leptos_meta::Title;
+use reactive_stores::Store;
+
+use crate::components::utils::{
+    error::{ErrorPage, ServerErrors},
+    loading::Loading,
+    permissions::permission_or_redirect,
+};
+use crate::routes::artist::{AddArtistManager, RemoveArtistManager, get_artist_managers};
+use crate::store::{GlobalState, GlobalStateStoreFields};
+
+/// Renders the edit artist managers page.
+#[component]
+pub fn EditArtistManagers() -> impl IntoView {
+    Effect::new_isomorphic(move || {
+        permission_or_redirect("label_owner", "/admin");
+    });
+
+    let store = expect_context::<Store<GlobalState>>();
+    let artist = store.artist();
+
+    let add_manager = ServerAction::<AddArtistManager>::new();
+    let remove_manager = ServerAction::<RemoveArtistManager>::new();
+    let managers_resource = Resource::new(
+        move || {
+            (
+                artist.get().slug,
+                add_manager.version().get(),
+                remove_manager.version().get(),
+            )
+        },
+        |(slug, _, _)| get_artist_managers(slug),
+    );
+
+    view! {
+        <Title text=move || format!("{} Managers", artist.get().name) />
+        <h1>{move || view! { {artist.get().name} }}" Managers"</h1>
+
+        <div class="grid gap-6">
+            {move || match add_manager.value().get() {
+                Some(Err(errors)) => {
+                    view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                }
+                _ => view! { "" }.into_any(),
+            }}
+            {move || match remove_manager.value().get() {
+                Some(Err(errors)) => {
+                    view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                }
+                _ => view! { "" }.into_any(),
+            }}
+            <Transition fallback=Loading>
+                <ErrorBoundary fallback=|_| {
+                    ErrorPage
+                }>
+                    {move || Suspend::new(async move {
+                        managers_resource
+                            .await
+                            .map(|result| {
+                                if result.managers.is_empty() {
+                                    return view! { <p>"No one has been assigned to manage this artist."</p> }
+                                        .into_any();
+                                }
+                                view! {
+                                    <ul class="list">
+                                        {result
+                                            .managers
+                                            .into_iter()
+                                            .map(|manager| {
+                                                view! {
+                                                    <li class="list-row">
+                                                        <span class="list-col-grow">
+                                                            {manager.username}
+                                                        </span>
+                                                        <ActionForm action=remove_manager>
+                                                            <input
+                                                                name="slug"
+                                                                type="hidden"
+                                                                value=move || artist.get().slug
+                                                            />
+                                                            <input
+                                                                name="user_id"
+                                                                type="hidden"
+                                                                value=manager.user_id
+                                                            />
+                                                            <button class="btn btn-sm btn-error">
+                                                                "Remove"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </ul>
+                                }
+                                    .into_any()
+                            })
+                    })}
+                </ErrorBoundary>
+            </Transition>
+
+            <div class="divider">Assign a manager</div>
+            <ActionForm action=add_manager>
+                <div class="flex gap-6">
+                    <input name="slug" type="hidden" value=move || artist.get().slug />
+                    <input
+                        type="text"
+                        name="username"
+                        placeholder="Username"
+                        class="flex-1 input"
+                        required
+                    />
+                    <button class="btn btn-primary">Assign</button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
@@ -4,6 +4,7 @@ pub mod delete;
 pub mod edit;
 pub mod images;
 pub mod links;
+pub mod managers;
 pub mod music_service;
 pub mod release;
 pub mod restore;
@@ -6,14 +6,19 @@ use reactive_stores::{Store, Subfield};
 
 use crate::components::{
     admin::shared::{
-        artist_select::ArtistSelect, date_field::DateField, markdown_field::MarkdownField,
+        artist_select::{ArtistSelect, RemixerSelect},
+        date_field::DateField,
+        markdown_field::MarkdownField,
     },
     utils::{
         error::ErrorPage, error::ServerErrors, loading::Loading,
         permissions::permission_or_redirect,
     },
 };
-use crate::models::{artist::Artist, release::Release};
+use crate::models::{
+    artist::Artist,
+    release::{Release, ReleaseType},
+};
 use crate::routes::release::CreateRelease;
 use crate::store::{GlobalState, GlobalStateStoreFields};
 use crate::utils::redirect::redirect;
@@ -33,6 +38,7 @@ pub fn CreateRelease() -> impl IntoView {
     Effect::new_isomorphic(move || {
         artist_ids.set(vec![artist.get().id]);
     });
+    let remixer_ids = RwSignal::new(vec![]);
 
     let (release, _set_release) = signal(Release::default());
     let create_release = ServerAction::<CreateRelease>::new();
@@ -75,7 +81,7 @@ pub fn CreateRelease() -> impl IntoView {
                             placeholder=""
                             name="form[label_id]"
                             value=move || { record_label.get().id }
-                        /><Form release artist artist_ids />
+                        /><Form release artist artist_ids remixer_ids />
                     </div>
                 </ActionForm>
             </ErrorBoundary>
@@ -88,6 +94,7 @@ fn Form(
     release: ReadSignal<Release>,
     artist: Subfield<Store<GlobalState>, GlobalState, Artist>,
     artist_ids: RwSignal<Vec<i64>>,
+    remixer_ids: RwSignal<Vec<i64>>,
 ) -> impl IntoView {
     view! {
         <label class="flex gap-2 items-center input">
@@ -107,33 +114,46 @@ fn Form(
         {move || {
             view! { <ArtistSelect primary_artist_id=artist.get().id artist_ids=artist_ids /> }
         }}
+        <RemixerSelect remixer_ids=remixer_ids />
         <label class="flex gap-2 items-center input">
             <input
                 type="text"
                 class="grow"
-                placeholder="Catalog number"
+                placeholder="Catalog number, leave blank to generate one"
                 name="form[catalogue_number]"
                 value=move || release.get().catalogue_number
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="text"
+                class="grow"
+                placeholder="Barcode (UPC or EAN)"
+                name="form[barcode]"
+                value=move || release.get().barcode.unwrap_or_default()
+            />
+        </label>
+        <select class="w-full select" name="form[release_type]">
+            {ReleaseType::ALL
+                .map(|release_type| {
+                    view! {
+                        <option
+                            value=format!("{release_type:?}")
+                            selected=move || release.get().release_type == release_type
+                        >
+                            {release_type.to_string()}
+                        </option>
+                    }
+                })}
+        </select>
+        // Releases are created as drafts, they can be published once they have a track
         {move || {
             view! {
-                <div class="flex gap-6">
-                    <div class="w-1/2">
-                        <DateField
-                            title="Release Date".to_string()
-                            field="form[release_date]"
-                            date=release.get().release_date
-                        />
-                    </div>
-                    <div class="w-1/2">
-                        <DateField
-                            title="Published At".to_string()
-                            field="form[published_at]"
-                            date=release.get().published_at
-                        />
-                    </div>
-                </div>
+                <DateField
+                    title="Release Date".to_string()
+                    field="form[release_date]"
+                    date=release.get().release_date
+                />
             }
         }}
         <button class="btn btn-primary">Create</button>
@@ -5,12 +5,14 @@ use leptos_meta::Title;
 use leptos_router::hooks::use_params_map;
 use reactive_stores::{Store, Subfield};
 
-use super::{delete::DeleteRelease, restore::RestoreRelease};
+use super::{delete::DeleteRelease, preview_link::PreviewLink, restore::RestoreRelease};
 use crate::components::{
     admin::{
         artist::release::track::list::Tracks,
         shared::{
-            artist_select::ArtistSelect, date_field::DateField, markdown_field::MarkdownField,
+            artist_select::{ArtistSelect, RemixerSelect},
+            date_field::DateField,
+            markdown_field::MarkdownField,
         },
     },
     files::upload::FileUploadWithProgress,
@@ -20,7 +22,10 @@ use crate::components::{
     },
 };
 use crate::config::upload::UploadConfiguration;
-use crate::models::{artist::Artist, release::Release};
+use crate::models::{
+    artist::Artist,
+    release::{Release, ReleaseType},
+};
 use crate::routes::release::{UpdateRelease, get_release};
 use crate::store::{GlobalState, GlobalStateStoreFields};
 use crate::utils::redirect::redirect;
@@ -45,6 +50,7 @@ pub fn EditRelease() -> impl IntoView {
     let release = RwSignal::new(Release::default());
     let artists = RwSignal::new(Vec::new()); // Artists on the release
     let artist_ids = RwSignal::new(vec![]);
+    let remixer_ids = RwSignal::new(vec![]);
     let release_resource = Resource::new(
         move || {
             (
@@ -52,7 +58,7 @@ pub fn EditRelease() -> impl IntoView {
                 params.read().get("release_slug").unwrap_or_default(),
             )
         },
-        |(artist_slug, release_slug)| get_release(artist_slug, release_slug),
+        |(artist_slug, release_slug)| get_release(artist_slug, release_slug, None),
     );
 
     let update_release = ServerAction::<UpdateRelease>::new();
@@ -72,6 +78,7 @@ pub fn EditRelease() -> impl IntoView {
                                     release.set(this_release.release);
                                     artists.set(this_release.artists.clone());
                                     artist_ids.set(artists_ids(&this_release.artists));
+                                    remixer_ids.set(artists_ids(&this_release.remixers));
                                 }
                                 Err(e) => {
                                     tracing::error!("Failed to fetch release. Error: {e:?}");
@@ -90,6 +97,9 @@ pub fn EditRelease() -> impl IntoView {
                                         Some(Ok(release_result)) => {
                                             let fresh_release = release_result.release;
                                             let fresh_artists = release_result.artists;
+                                            let fresh_remixer_ids = artists_ids(
+                                                &release_result.remixers,
+                                            );
                                             if fresh_release.id > 0 {
                                                 if fresh_release.slug != release.get().slug {
                                                     redirect(
@@ -107,6 +117,9 @@ pub fn EditRelease() -> impl IntoView {
                                                     artists.set(fresh_artists.clone());
                                                     artist_ids.set(artists_ids(&fresh_artists));
                                                 }
+                                                if fresh_remixer_ids != remixer_ids.get() {
+                                                    remixer_ids.set(fresh_remixer_ids);
+                                                }
                                                 if !success.get() {
                                                     success.set(true);
                                                 }
@@ -132,9 +145,15 @@ pub fn EditRelease() -> impl IntoView {
                                             show=success.get()
                                         />
                                     }
-                                }} <Form release=release artist=artist artist_ids=artist_ids />
+                                }} <Form
+                                    release=release
+                                    artist=artist
+                                    artist_ids=artist_ids
+                                    remixer_ids=remixer_ids
+                                />
                             </div>
                         </ActionForm>
+                        <PreviewLink release=release artist=artist />
 
                     </ErrorBoundary>
                 </Transition>
@@ -151,7 +170,10 @@ fn Form(
     release: RwSignal<Release>,
     artist: Subfield<Store<GlobalState>, GlobalState, Artist>,
     artist_ids: RwSignal<Vec<i64>>,
+    remixer_ids: RwSignal<Vec<i64>>,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
+
     view! {
         <input
             type="text"
@@ -170,6 +192,15 @@ fn Form(
                 value=move || release.get().name
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="text"
+                class="grow"
+                placeholder="Sort name (optional)"
+                name="form[sort_name]"
+                value=move || release.get().sort_name
+            />
+        </label>
         {move || {
             view! {
                 <MarkdownField
@@ -187,6 +218,7 @@ fn Form(
                 />
             }
         }}
+        <RemixerSelect remixer_ids=remixer_ids />
 
         <label class="flex gap-2 items-center input">
             <input
@@ -197,6 +229,28 @@ fn Form(
                 value=move || release.get().catalogue_number
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="text"
+                class="grow"
+                placeholder="Barcode (UPC or EAN)"
+                name="form[barcode]"
+                value=move || release.get().barcode.unwrap_or_default()
+            />
+        </label>
+        <select class="w-full select" name="form[release_type]">
+            {ReleaseType::ALL
+                .map(|release_type| {
+                    view! {
+                        <option
+                            value=format!("{release_type:?}")
+                            selected=move || release.get().release_type == release_type
+                        >
+                            {release_type.to_string()}
+                        </option>
+                    }
+                })}
+        </select>
 
         {move || {
             view! {
@@ -217,7 +271,10 @@ fn Form(
                 />
             }
         }}
-        <img src=move || release.get().primary_image_url() alt=move || release.get().name />
+        <img
+            src=move || release.get().primary_image_url(&store.record_label().get().placeholder_images)
+            alt=move || release.get().name
+        />
 
         <div class="divider">Private</div>
         {move || {
@@ -229,6 +286,35 @@ fn Form(
                 />
             }
         }}
+        {move || {
+            view! {
+                <DateField
+                    title="Embargo Until".to_string()
+                    field="form[embargo_until]"
+                    date=release.get().embargo_until
+                />
+            }
+        }}
+        <label class="label">
+            <input
+                type="checkbox"
+                class="checkbox"
+                name="form[unlisted]"
+                value="true"
+                checked=move || release.get().unlisted
+            />
+            Unlisted
+        </label>
+        <label class="label">
+            <input
+                type="checkbox"
+                class="checkbox"
+                name="form[is_various]"
+                value="true"
+                checked=move || release.get().is_various
+            />
+            Various Artists
+        </label>
         <div class="flex flex-auto gap-6">
             <button class="flex-1 btn btn-primary">Update</button>
             {move || {
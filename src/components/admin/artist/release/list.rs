@@ -121,6 +121,10 @@ fn ReleaseRow(#[prop(into)] release: Release) -> impl IntoView {
         |date| date.format("%e %B %Y").to_string(),
     );
     let release_slug = release.slug.clone();
+    let primary_image_url = {
+        let release = release.clone();
+        move || release.primary_image_url(&store.record_label().get().placeholder_images)
+    };
     let release_url = move || {
         format!(
             "/admin/artist/{}/release/{}",
@@ -140,7 +144,7 @@ fn ReleaseRow(#[prop(into)] release: Release) -> impl IntoView {
                         <div class="w-12 rounded-full not-prose">
                             <img
                                 class="m-0"
-                                src=release.primary_image_url()
+                                src=primary_image_url
                                 alt=release.name.clone()
                             />
                         </div>
@@ -4,5 +4,6 @@ pub mod create;
 pub mod delete;
 pub mod edit;
 pub mod list;
+pub mod preview_link;
 pub mod restore;
 pub mod track;
@@ -0,0 +1,85 @@
+//! Preview link component for the admin panel
+//!
+//! Shows the link the press can use to see a release under embargo.
+//! The link can be copied, or replaced with a new one so the old link stops working.
+
+use leptos::ev::MouseEvent;
+use leptos::prelude::*;
+use reactive_stores::{Store, Subfield};
+
+use crate::components::utils::error::ServerErrors;
+use crate::models::{artist::Artist, release::Release};
+use crate::routes::release::RotatePreviewToken;
+use crate::store::GlobalState;
+use crate::utils::urls::release_path;
+
+/// Renders the preview link of a release under embargo.
+#[component]
+pub fn PreviewLink(
+    /// The release being previewed
+    release: RwSignal<Release>,
+    /// The artist of the release
+    artist: Subfield<Store<GlobalState>, GlobalState, Artist>,
+) -> impl IntoView {
+    let preview_path = move || {
+        release.get().preview_token.map(|token| {
+            format!(
+                "{}?preview={token}",
+                release_path(&artist.get().slug, &release.get().slug)
+            )
+        })
+    };
+
+    let copied = RwSignal::new(false);
+    // The origin is only read in the browser, so the rendered link is the same on the server and when hydrating
+    let on_click_copy = move |ev: MouseEvent| {
+        ev.prevent_default();
+        if let Some(path) = preview_path() {
+            let origin = window().location().origin().unwrap_or_default();
+            let _ = window()
+                .navigator()
+                .clipboard()
+                .write_text(&format!("{origin}{path}"));
+            copied.set(true);
+        }
+    };
+
+    let rotate_token = ServerAction::<RotatePreviewToken>::new();
+    let value = rotate_token.value();
+
+    view! {
+        <Show when=move || preview_path().is_some()>
+            <div class="divider">Preview Link</div>
+            <p>
+                "Anyone with this link can see the release before its embargo lifts. Make a new link if this one has been shared too widely."
+            </p>
+            {move || {
+                match value.get() {
+                    Some(Ok(release_result)) => {
+                        if release_result.release.preview_token != release.get_untracked().preview_token {
+                            release.set(release_result.release);
+                            copied.set(false);
+                        }
+                        view! { "" }.into_any()
+                    }
+                    Some(Err(errors)) => {
+                        view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                    }
+                    None => view! { "" }.into_any(),
+                }
+            }}
+            <div class="flex gap-6 items-center">
+                <a class="flex-1 truncate link" href=move || preview_path().unwrap_or_default()>
+                    {move || preview_path().unwrap_or_default()}
+                </a>
+                <button class="btn btn-secondary" on:click=on_click_copy>
+                    {move || if copied.get() { "Copied" } else { "Copy preview link" }}
+                </button>
+                <ActionForm action=rotate_token>
+                    <input name="slug" type="hidden" value=move || release.get().slug />
+                    <button class="btn btn-warning">"New link"</button>
+                </ActionForm>
+            </div>
+        </Show>
+    }
+}
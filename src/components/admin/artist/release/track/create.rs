@@ -44,7 +44,7 @@ pub fn CreateTrack() -> impl IntoView {
                 params.read().get("release_slug").unwrap_or_default(),
             ]
         },
-        |[artist_slug, release_slug]| get_release(artist_slug, release_slug),
+        |[artist_slug, release_slug]| get_release(artist_slug, release_slug, None),
     );
 
     let track = RwSignal::new(Track::default());
@@ -163,6 +163,25 @@ fn Form(
                 value=move || track.get().bpm
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="url"
+                class="grow"
+                placeholder="Preview URL"
+                name="form[preview_url]"
+                value=move || track.get().preview_url
+            />
+        </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="number"
+                min="0"
+                class="grow"
+                placeholder="Preview Start (ms)"
+                name="form[preview_start_ms]"
+                value=move || track.get().preview_start_ms
+            />
+        </label>
         <label class="flex gap-2 items-center input">
             <input
                 type="number"
@@ -45,7 +45,7 @@ pub fn EditTrack() -> impl IntoView {
                 params.read().get("release_slug").unwrap_or_default(),
             ]
         },
-        |[artist_slug, release_slug]| get_release(artist_slug, release_slug),
+        |[artist_slug, release_slug]| get_release(artist_slug, release_slug, None),
     );
 
     let track_resource = Resource::new(
@@ -229,6 +229,25 @@ fn Form(
                 value=move || track.get().bpm
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="url"
+                class="grow"
+                placeholder="Preview URL"
+                name="form[preview_url]"
+                value=move || track.get().preview_url
+            />
+        </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="number"
+                min="0"
+                class="grow"
+                placeholder="Preview Start (ms)"
+                name="form[preview_start_ms]"
+                value=move || track.get().preview_start_ms
+            />
+        </label>
         <label class="flex gap-2 items-center input">
             <input
                 type="number"
@@ -240,6 +259,26 @@ fn Form(
                 value=move || track.get().track_number
             />
         </label>
+        <label class="flex gap-2 items-center input">
+            <input
+                type="number"
+                min="1"
+                class="grow"
+                placeholder="Clean version of (track ID)"
+                name="form[clean_version_of]"
+                value=move || track.get().clean_version_of
+            />
+        </label>
+        <label class="label">
+            <input
+                type="checkbox"
+                class="checkbox"
+                name="form[explicit]"
+                value="true"
+                checked=move || track.get().explicit
+            />
+            Explicit
+        </label>
         {move || {
             view! {
                 <div class="flex gap-6">
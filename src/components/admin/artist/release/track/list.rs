@@ -32,7 +32,7 @@ pub fn Tracks() -> impl IntoView {
                 params.read().get("release_slug").unwrap_or_default(),
             ]
         },
-        |[artist_slug, release_slug]| get_release(artist_slug, release_slug),
+        |[artist_slug, release_slug]| get_release(artist_slug, release_slug, None),
     );
     let release = RwSignal::new(Release::default());
 
@@ -150,6 +150,12 @@ fn TrackRow(
     artist_slug: String,
     release_slug: String,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
+    let primary_image_url = {
+        let track = track.clone();
+        move || track.primary_image_url(&store.record_label().get().placeholder_images)
+    };
+
     view! {
         <tr>
 
@@ -157,7 +163,7 @@ fn TrackRow(
                 <div class="flex gap-3 items-center">
                     <div class="avatar">
                         <div class="w-12 rounded-full not-prose">
-                            <img class="m-0" src=track.primary_image_url() alt=track.name.clone() />
+                            <img class="m-0" src=primary_image_url alt=track.name.clone() />
                         </div>
                     </div>
                     <div>
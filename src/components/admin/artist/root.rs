@@ -139,6 +139,29 @@ pub fn AdminArtistRoot() -> impl IntoView {
             >
                 Images
             </A>
+            <A
+                href=move || {
+                    format!(
+                        "/admin/artist/{}/managers",
+                        params.read().get("artist_slug").unwrap_or_default(),
+                    )
+                }
+                attr:role="tab"
+                attr:class=move || {
+                    if url.get().path()
+                        == format!(
+                            "/admin/artist/{}/managers",
+                            params.read().get("artist_slug").unwrap_or_default(),
+                        )
+                    {
+                        "tab tab-active"
+                    } else {
+                        "tab"
+                    }
+                }
+            >
+                Managers
+            </A>
         </div>
 
         <Outlet />
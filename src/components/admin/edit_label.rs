@@ -109,6 +109,51 @@ pub fn EditLabel() -> impl IntoView {
                                             " 25 00001"
                                         </span>
                                     </p>
+                                </fieldset>
+                                <fieldset class="fieldset">
+                                    <legend class="fieldset-legend">Currency</legend>
+                                    <input
+                                        type="text"
+                                        class="w-full input"
+                                        placeholder="GBP"
+                                        name="currency"
+                                        maxlength="3"
+                                        value=move || store.record_label().get().currency
+                                    />
+                                    <p class="label">"ISO 4217 code used to display release prices"</p>
+                                </fieldset>
+                                <fieldset class="fieldset">
+                                    <legend class="fieldset-legend">Release Date Placeholder</legend>
+                                    <input
+                                        type="text"
+                                        class="w-full input"
+                                        placeholder="TBA"
+                                        name="release_date_placeholder"
+                                        value=move || {
+                                            store.record_label().get().release_date_placeholder
+                                        }
+                                    />
+                                    <p class="label">
+                                        "Shown for announced releases that don't have a release date yet"
+                                    </p>
+                                </fieldset>
+                                <fieldset class="fieldset">
+                                    <legend class="fieldset-legend">Catalogue Prefix</legend>
+                                    <input
+                                        type="text"
+                                        class="w-full input"
+                                        placeholder="CAT-"
+                                        name="catalogue_prefix"
+                                        maxlength="32"
+                                        value=move || store.record_label().get().catalogue_prefix
+                                    />
+                                    <p class="flex justify-between label">
+                                        <span>"Used to number releases created without a catalogue number"</span>
+                                        <span>
+                                            "Example " {move || store.record_label().get().catalogue_prefix}
+                                            "0001"
+                                        </span>
+                                    </p>
                                 </fieldset> <button class="btn btn-primary">Update</button>
                             </div>
                         </ActionForm>
@@ -48,6 +48,9 @@ pub fn AdminMenu() -> impl IntoView {
                             <li>
                                 <PagesMenu pages=menu.get().pages />
                             </li>
+                            <li>
+                                <A href="/admin/archive">"Archive"</A>
+                            </li>
                         </ul>
                     }
                 })}
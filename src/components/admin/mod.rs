@@ -1,5 +1,6 @@
 //! Components for the admin sections of the application.
 
+pub mod archive;
 pub mod artist;
 pub mod dash;
 pub mod dashboard;
@@ -1,7 +1,7 @@
 //! The admin root component for the application.
 
 use leptos::prelude::*;
-use leptos_router::{components::Outlet, components::Redirect};
+use leptos_router::{components::Outlet, components::Redirect, hooks::use_location};
 
 use crate::app::UserContext;
 use crate::components::{
@@ -15,6 +15,7 @@ use crate::models::auth::User;
 pub fn AdminRoot() -> impl IntoView {
     let user_context = expect_context::<UserContext>();
     let (user, set_user) = signal(User::default());
+    let location = use_location();
 
     view! {
         <div class="flex">
@@ -33,7 +34,12 @@ pub fn AdminRoot() -> impl IntoView {
                             }
                                 .into_any()
                         } else {
-                            view! { <Redirect path="/login" /> }.into_any()
+                            // Come back to this page after logging in
+                            let next: String = url::form_urlencoded::byte_serialize(
+                                    location.pathname.get_untracked().as_bytes(),
+                                )
+                                .collect();
+                            view! { <Redirect path=format!("/login?next={next}") /> }.into_any()
                         }
                     })}
                 </ErrorBoundary>
@@ -1,10 +1,12 @@
 //! Artist selection component for a form.
 
 use leptos::prelude::*;
+use reactive_stores::Store;
 
 use crate::components::utils::{error::ErrorPage, loading::Loading};
 use crate::models::artist::Artist;
 use crate::routes::record_label::get_label_artists;
+use crate::store::{GlobalState, GlobalStateStoreFields};
 
 fn toggle_artist_id(artist_ids: RwSignal<Vec<i64>>, artist_id: i64) {
     let mut ids = artist_ids.get();
@@ -98,6 +100,55 @@ pub fn ArtistSelect(
     }
 }
 
+/// Select component for choosing the artists who remixed a release.
+#[component]
+pub fn RemixerSelect(
+    /// The list of the remixers' artist IDs that are selected.
+    remixer_ids: RwSignal<Vec<i64>>,
+) -> impl IntoView {
+    let artists = RwSignal::new(vec![]);
+    let artists_resource = Resource::new(move || (), |()| get_label_artists());
+
+    view! {
+        <Transition fallback=Loading>
+            <ErrorBoundary fallback=|_| {
+                ErrorPage
+            }>
+                {move || Suspend::new(async move {
+                    if let Ok(artist_list) = artists_resource.await {
+                        artists.set(artist_list.artists);
+                    }
+                })}
+                <fieldset class="flex flex-row flex-wrap gap-6 justify-center p-4 fieldset">
+                    <legend class="label">
+                        <span class="label-text">"Remixers"</span>
+                    </legend>
+                    <Show
+                        when=move || { !artists.get().is_empty() }
+                        fallback=|| {
+                            view! { <p>"No artists found…"</p> }
+                        }
+                    >
+                        <For
+                            each=move || artists.get()
+                            key=|artist| (artist.slug.clone(), artist.name.clone())
+                            let(artist)
+                        >
+                            <ArtistCheckbox artist artist_ids=remixer_ids />
+                        </For>
+                    </Show>
+                </fieldset>
+                <input
+                    type="text"
+                    class="hidden"
+                    name="form[remixer_ids]"
+                    value=move || artist_ids_str(remixer_ids)
+                />
+            </ErrorBoundary>
+        </Transition>
+    }
+}
+
 /// Checkbox component for selecting an artist in the form.
 #[component]
 pub fn ArtistCheckbox(
@@ -106,7 +157,12 @@ pub fn ArtistCheckbox(
     /// The signal containing the list of selected artist IDs
     artist_ids: RwSignal<Vec<i64>>,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let checked = move || artist_ids.get().contains(&artist.id);
+    let primary_image_url = {
+        let artist = artist.clone();
+        move || artist.primary_image_url(&store.record_label().get().placeholder_images)
+    };
     view! {
         <label class="flex flex-row gap-4 label bg-base-100 border-base-300 rounded-box">
             <input
@@ -120,7 +176,7 @@ pub fn ArtistCheckbox(
             />
             <div class="avatar not-prose">
                 <div class="w-8 rounded-full">
-                    <img src=artist.primary_image_url() alt=artist.name.clone() />
+                    <img src=primary_image_url alt=artist.name.clone() />
                 </div>
             </div>
             {artist.name}
@@ -1,9 +1,10 @@
 //! The artist home page module
 
 use leptos::prelude::*;
-use leptos_meta::Title;
+use leptos_meta::{Meta, Title};
 use leptos_router::{components::A, hooks::use_params_map};
 use markdown;
+use reactive_stores::Store;
 
 use crate::components::utils::{error::ErrorPage, loading::Loading, status_badge::StatusBadge};
 use crate::models::{
@@ -14,12 +15,16 @@ use crate::routes::{
     links::{LinksResult, get_links},
     release::get_releases,
 };
+use crate::store::{GlobalState, GlobalStateStoreFields};
+use crate::utils::urls::release_path;
 
 /// Renders the artist home page.
 #[component]
 pub fn ArtistPage() -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let params = use_params_map();
     let artist = RwSignal::new(Artist::default());
+    let og_image_url = RwSignal::new(None::<String>);
     let artist_resource = Resource::new(
         move || params.read().get("slug").unwrap_or_default(),
         get_artist,
@@ -45,6 +50,7 @@ pub fn ArtistPage() -> impl IntoView {
                 {move || Suspend::new(async move {
                     if let Ok(this_artist) = artist_resource.await {
                         artist.set(this_artist.artist);
+                        og_image_url.set(this_artist.og_image_url);
                     } else {
                         tracing::error!("Error while getting artist");
                     }
@@ -60,6 +66,12 @@ pub fn ArtistPage() -> impl IntoView {
                         tracing::error!("Error while getting releases for artist");
                     }
                 })} <Title text=move || artist.get().name />
+                <Meta property="og:title" content=move || artist.get().name />
+                {move || {
+                    og_image_url
+                        .get()
+                        .map(|url| view! { <Meta property="og:image" content=url /> })
+                }}
                 <article class="my-6 md:container md:mx-auto prose">
                     <h1>{move || artist.get().name}</h1>
                     <div class="flex flex-wrap justify-between">
@@ -89,7 +101,7 @@ pub fn ArtistPage() -> impl IntoView {
                         <div class="w-1/2">
                             <img
                                 class="ml-6"
-                                src=move || artist.get().primary_image_url()
+                                src=move || artist.get().primary_image_url(&store.record_label().get().placeholder_images)
                                 alt=move || artist.get().name
                             />
                         </div>
@@ -223,6 +235,7 @@ pub fn Release(
     /// The artist
     artist: RwSignal<Artist>,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let release = RwSignal::new(release);
     let release_date = move || {
         release.get().release_date.map_or_else(
@@ -233,13 +246,13 @@ pub fn Release(
 
     view! {
         <A
-            href=move || format!("/artists/{}/{}", artist.get().slug, release.get().slug)
+            href=move || release_path(&artist.get().slug, &release.get().slug)
             attr:class="w-1/4 link link-hover min-w-96"
         >
             <div class="shadow-sm not-prose card bg-neutral text-neutral-content">
                 <figure class="not-prose">
                     <img
-                        src=move || release.get().primary_image_url()
+                        src=move || release.get().primary_image_url(&store.record_label().get().placeholder_images)
                         alt=move || release.get().name
                     />
                 </figure>
@@ -8,7 +8,7 @@ use crate::components::utils::{error::ErrorPage, loading::Loading, status_badge:
 use crate::models::artist::Artist;
 use crate::routes::record_label::get_label_artists;
 use crate::store::{GlobalState, GlobalStateStoreFields};
-use crate::utils::shorten_string::shorten_string;
+use crate::utils::{shorten_string::shorten_string, urls::artist_path};
 
 /// Renders the record label page.
 #[component]
@@ -73,12 +73,18 @@ fn ArtistBox(
     #[prop(into)]
     artist: Artist,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
+    let primary_image_url = {
+        let artist = artist.clone();
+        move || artist.primary_image_url(&store.record_label().get().placeholder_images)
+    };
+
     view! {
-        <A href=format!("/artists/{}", artist.slug) attr:class="no-underline">
+        <A href=artist_path(&artist.slug) attr:class="no-underline">
             <div class="w-96 shadow-xl not-prose card card-compact bg-neutral text-neutral-content indicator">
                 <StatusBadge deleted_at=artist.deleted_at published_at=artist.published_at />
                 <figure>
-                    <img src=artist.primary_image_url() alt=artist.name.clone() />
+                    <img src=primary_image_url alt=artist.name.clone() />
                 </figure>
                 <div class="card-body">
                     <h2 class="card-title">{artist.name}</h2>
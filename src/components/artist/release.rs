@@ -1,19 +1,24 @@
 //! This displays a release for an artist.
 
 use leptos::prelude::*;
-use leptos_meta::Title;
-use leptos_router::hooks::use_params_map;
+use leptos_meta::{Meta, Title};
+use leptos_router::hooks::{use_params_map, use_query_map};
 use markdown;
+use reactive_stores::Store;
 
 use crate::components::utils::{error::ErrorPage, loading::Loading};
 use crate::models::{artist::Artist, release::Release, track_with_artists::TrackWithArtists};
 use crate::routes::{artist::get_artist, release::get_release};
-use crate::utils::{redirect::redirect, shorten_string::shorten_string};
+use crate::store::{GlobalState, GlobalStateStoreFields};
+use crate::utils::{redirect::redirect, shorten_string::shorten_string, urls::artist_path};
 
 /// Renders the release page.
 #[component]
 pub fn ReleasePage() -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let params = use_params_map();
+    // Press can preview an embargoed release with the token in the link they were sent
+    let query = use_query_map();
     let artist = RwSignal::new(Artist::default());
     let artist_resource = Resource::new(
         move || params.read().get("artist_slug").unwrap_or_default(),
@@ -21,16 +26,20 @@ pub fn ReleasePage() -> impl IntoView {
     );
 
     let release = RwSignal::new(Release::default());
+    let og_image_url = RwSignal::new(None::<String>);
     let artists = RwSignal::new(Vec::new()); // Artists on the release
     let tracks = RwSignal::new(Vec::new()); // Tracks on the release
     let release_resource = Resource::new(
         move || {
-            [
+            (
                 params.read().get("artist_slug").unwrap_or_default(),
                 params.read().get("release_slug").unwrap_or_default(),
-            ]
+                query.read().get("preview"),
+            )
+        },
+        move |(artist_slug, release_slug, preview_token)| {
+            get_release(artist_slug, release_slug, preview_token)
         },
-        move |[artist_slug, release_slug]| get_release(artist_slug, release_slug),
     );
 
     view! {
@@ -51,18 +60,23 @@ pub fn ReleasePage() -> impl IntoView {
                         Ok(this_release) => {
                             if !this_release.release.slug.is_empty() {
                                 release.set(this_release.release.clone());
+                                og_image_url.set(this_release.og_image_url);
                                 artists.set(this_release.artists);
                                 tracks.set(this_release.tracks);
                             }
                         }
                         Err(e) => {
                             tracing::error!("Error while getting release {:?}", e);
-                            redirect(&format!("/artists/{}", artist.get().slug));
+                            redirect(&artist_path(&artist.get().slug));
                         }
                     }
 
                     view! {
                         <Title text=release.get().name />
+                        <Meta property="og:title" content=release.get().name />
+                        {og_image_url
+                            .get()
+                            .map(|url| view! { <Meta property="og:image" content=url /> })}
                         <article class="my-6 md:container md:mx-auto prose">
                             <h1>{release.get().name}</h1>
                             <div class="flex flex-wrap justify-between">
@@ -75,7 +89,7 @@ pub fn ReleasePage() -> impl IntoView {
                                     class="w-1/2"
                                 />
                                 <img
-                                    src=move || release.get().primary_image_url()
+                                    src=move || release.get().primary_image_url(&store.record_label().get().placeholder_images)
                                     alt=move || release.get().name
                                     class="pl-6 w-1/2 h-auto"
                                 />
@@ -130,14 +144,15 @@ pub fn Track(
     #[prop(into)]
     track: TrackWithArtists,
 ) -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let track = RwSignal::new(track);
 
     view! {
-        <li class="list-row">
+        <li class="list-row" id=move || track.get().track.slug>
             <div>
                 <img
                     class="not-prose size-10"
-                    src=move || track.get().track.primary_image_url()
+                    src=move || track.get().track.primary_image_url(&store.record_label().get().placeholder_images)
                     alt=move || track.get().track.name
                 />
             </div>
@@ -1,11 +1,13 @@
 //! Login page component.
 use leptos::form::ActionForm;
 use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
 
 use crate::app::UserContext;
 use crate::components::utils::{error::ErrorPage, error::ServerErrors};
-use crate::routes::auth::Login;
-use crate::utils::redirect::redirect;
+use crate::config::auth::DEFAULT_LOGIN_REDIRECT;
+use crate::routes::auth::{Login, get_login_redirect};
+use crate::utils::redirect::{redirect, safe_redirect_target};
 
 /// Renders the login page.
 #[component]
@@ -14,6 +16,9 @@ pub fn Login() -> impl IntoView {
     let value = login.value();
 
     let user_context = expect_context::<UserContext>();
+    let query = use_query_map();
+    // The configured redirect is only known to the server
+    let login_redirect = Resource::new(move || query.read().get("next"), get_login_redirect);
 
     view! {
         <article class="my-6 md:container md:mx-auto prose">
@@ -29,7 +34,16 @@ pub fn Login() -> impl IntoView {
                                 Some(Ok(user)) => {
                                     user_context.1.set(user.clone());
                                     if user.is_authenticated() {
-                                        redirect("/");
+                                        let target = login_redirect
+                                            .get_untracked()
+                                            .and_then(Result::ok)
+                                            .unwrap_or_else(|| {
+                                                safe_redirect_target(
+                                                    query.read_untracked().get("next").as_deref(),
+                                                    DEFAULT_LOGIN_REDIRECT,
+                                                )
+                                            });
+                                        redirect(&target);
                                     }
                                     view! { "" }.into_any()
                                 }
@@ -86,12 +100,14 @@ pub fn Login() -> impl IntoView {
                                 checked="checked"
                                 class="checkbox"
                                 name="remember"
+                                value="true"
                                 id="remember"
                             />
                             Remember me
                         </label>
 
                         <button class="btn btn-primary">Login</button>
+                        <a href="/forgot-password">Forgot your password?</a>
                     </fieldset>
                 </div>
             </ActionForm>
@@ -5,3 +5,5 @@ pub mod login;
 pub mod logout;
 pub mod profile;
 pub mod register;
+pub mod reset_password;
+pub mod verify_email;
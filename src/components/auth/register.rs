@@ -118,6 +118,7 @@ pub fn Form() -> impl IntoView {
                     checked="checked"
                     class="checkbox"
                     name="remember"
+                    value="true"
                     id="remember"
                 />
                 Remember me
@@ -0,0 +1,100 @@
+//! Password reset pages for the application.
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::components::utils::error::ServerErrors;
+use crate::routes::auth::{RequestPasswordReset, ResetPassword};
+
+/// Renders the page to ask for a password reset link.
+#[component]
+pub fn ForgotPassword() -> impl IntoView {
+    let request_reset = ServerAction::<RequestPasswordReset>::new();
+    let value = request_reset.value();
+
+    view! {
+        <article class="my-6 md:container md:mx-auto prose">
+            <h1>Forgot Password</h1>
+            <ActionForm action=request_reset>
+                <div class="grid gap-6">
+                    {move || match value.get() {
+                        Some(Ok(())) => {
+                            view! {
+                                <p>
+                                    "If there is an account for that email address, a link to reset the password has been sent to it."
+                                </p>
+                            }
+                                .into_any()
+                        }
+                        Some(Err(errors)) => {
+                            view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                        }
+                        None => view! { "" }.into_any(),
+                    }}
+                    <fieldset class="flex flex-col gap-6 fieldset">
+                        <label class="flex gap-2 items-center input">
+                            <input
+                                type="email"
+                                class="grow"
+                                placeholder="Email"
+                                name="email"
+                                required
+                            />
+                        </label>
+                        <button class="btn btn-primary">Send reset link</button>
+                    </fieldset>
+                </div>
+            </ActionForm>
+        </article>
+    }
+}
+
+/// Renders the page to choose a new password with a reset token.
+#[component]
+pub fn ResetPassword() -> impl IntoView {
+    let params = use_params_map();
+    let reset_password = ServerAction::<ResetPassword>::new();
+    let value = reset_password.value();
+
+    view! {
+        <article class="my-6 md:container md:mx-auto prose">
+            <h1>Reset Password</h1>
+            <ActionForm action=reset_password>
+                <div class="grid gap-6">
+                    {move || match value.get() {
+                        Some(Ok(_)) => {
+                            view! {
+                                <p>
+                                    "Your password has been reset. "
+                                    <a href="/login">Login</a>
+                                </p>
+                            }
+                                .into_any()
+                        }
+                        Some(Err(errors)) => {
+                            view! { <ServerErrors server_errors=Some(errors) /> }.into_any()
+                        }
+                        None => view! { "" }.into_any(),
+                    }}
+                    <input
+                        type="hidden"
+                        name="token"
+                        value=move || params.read().get("token").unwrap_or_default()
+                    />
+                    <fieldset class="flex flex-col gap-6 fieldset">
+                        <label class="flex gap-2 items-center input">
+                            <input
+                                type="password"
+                                class="grow"
+                                placeholder="New password"
+                                name="new_password"
+                                required
+                            />
+                        </label>
+                        <button class="btn btn-primary">Reset password</button>
+                    </fieldset>
+                </div>
+            </ActionForm>
+        </article>
+    }
+}
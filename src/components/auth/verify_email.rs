@@ -0,0 +1,61 @@
+//! Verify email page for the application.
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::components::utils::error::ServerErrors;
+use crate::routes::auth::VerifyEmail;
+
+/// Renders the verify email page.
+#[component]
+pub fn VerifyEmail() -> impl IntoView {
+    let params = use_params_map();
+    let verify_email = ServerAction::<VerifyEmail>::new();
+    let value = verify_email.value();
+    let (server_errors, set_server_errors) = signal(Option::<ServerFnError>::None);
+    let (success, set_success) = signal(false);
+
+    view! {
+        <article class="my-6 md:container md:mx-auto prose">
+            <h1>Verify Email</h1>
+            <ActionForm action=verify_email>
+                <div class="grid gap-6">
+                    <Suspense>
+                        {move || {
+                            match value.get() {
+                                Some(Ok(_)) => {
+                                    set_success.set(true);
+                                    set_server_errors.set(None);
+                                }
+                                Some(Err(error)) => {
+                                    set_server_errors.set(Some(error));
+                                }
+                                None => {}
+                            }
+                        }} {move || view! { <ServerErrors server_errors=server_errors.get() /> }}
+                    </Suspense>
+
+                    <input
+                        type="hidden"
+                        name="token"
+                        value=move || params.read().get("token").unwrap_or_default()
+                    />
+                    {move || {
+                        if success.get() {
+                            view! {
+                                <p>
+                                    "Your email address has been verified. "
+                                    <a href="/login">Login</a>
+                                </p>
+                            }
+                                .into_any()
+                        } else {
+                            view! { <button class="btn btn-primary">Verify email</button> }
+                                .into_any()
+                        }
+                    }}
+                </div>
+            </ActionForm>
+        </article>
+    }
+}
@@ -3,7 +3,6 @@
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::hooks::use_params_map;
-use markdown;
 
 use crate::components::utils::error::ErrorPage;
 use crate::components::utils::loading::Loading;
@@ -17,6 +16,7 @@ pub fn PageDetails() -> impl IntoView {
     let slug = move || params.read().get("slug");
 
     let (page, set_page) = signal(Page::default());
+    let (body_html, set_body_html) = signal(String::new());
     let page_resource = Resource::new(
         move || page.get(),
         move |_| slug().map_or_else(|| get_page(String::new()), get_page),
@@ -30,6 +30,7 @@ pub fn PageDetails() -> impl IntoView {
                     match page_resource.await {
                         Ok(this_page) => {
                             *set_page.write() = this_page.page.clone();
+                            *set_body_html.write() = this_page.body_html;
                             this_page.page
                         }
                         Err(_) => Page::default(),
@@ -39,11 +40,7 @@ pub fn PageDetails() -> impl IntoView {
                         <Title text=page.get().name />
                         <article class="my-6 md:container md:mx-auto prose">
                             <h1>{page.get().name}</h1>
-                            <div inner_html=markdown::to_html_with_options(
-                                    &page.get().body,
-                                    &markdown::Options::gfm(),
-                                )
-                                .unwrap_or_default() />
+                            <div inner_html=body_html.get() />
                         </article>
                     }
                 })}
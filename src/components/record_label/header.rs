@@ -49,6 +49,7 @@ pub fn LabelHeader() -> impl IntoView {
 /// Renders the user menu page.
 #[component]
 pub fn UserMenu() -> impl IntoView {
+    let store = expect_context::<Store<GlobalState>>();
     let user_context = expect_context::<UserContext>();
 
     view! {
@@ -68,7 +69,12 @@ pub fn UserMenu() -> impl IntoView {
                                                     <div class="w-10 rounded-full">
                                                         <img
                                                             alt=format!("{}'s Avatar", user_context.0.get().username)
-                                                            src=user_context.0.get().avatar_url()
+                                                            src=move || {
+                                                                user_context
+                                                                    .0
+                                                                    .get()
+                                                                    .avatar_url(&store.record_label().get().placeholder_images)
+                                                            }
                                                         />
                                                     </div>
                                                 </div>
@@ -0,0 +1,36 @@
+//! Keeps analytics off the admin pages after client-side navigation.
+//!
+//! The shell only adds the analytics snippet to public pages, but once a script has loaded it keeps running
+//! when the router moves on to another page. Entering the admin from a page with analytics reloads the page,
+//! so the server renders it without them.
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_location;
+
+use crate::config::analytics::{ANALYTICS_META_NAME, analytics_allowed_for_path};
+
+/// Was the page loaded with the analytics snippet.
+fn analytics_loaded() -> bool {
+    document()
+        .query_selector(&format!("meta[name=\"{ANALYTICS_META_NAME}\"]"))
+        .ok()
+        .flatten()
+        .is_some()
+}
+
+/// Reloads the page when navigating to an admin page while analytics are loaded.
+/// Effects only run in the browser, so this renders nothing on the server.
+#[component]
+pub fn AnalyticsGate() -> impl IntoView {
+    let location = use_location();
+
+    Effect::new(move || {
+        let path = location.pathname.get();
+        if !analytics_allowed_for_path(&path) && analytics_loaded() {
+            // The router has already moved to the new URL, so reloading fetches the admin page from the server
+            if let Err(e) = window().location().reload() {
+                tracing::error!("Couldn't reload to unload analytics: {e:?}");
+            }
+        }
+    });
+}
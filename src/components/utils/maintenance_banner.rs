@@ -0,0 +1,37 @@
+//! A banner shown across the site during maintenance or read-only mode.
+
+use leptos::prelude::*;
+
+use crate::routes::site_settings::get_maintenance_banner;
+
+/// Renders the maintenance banner, if the site is in maintenance or read-only mode.
+/// Visitors can dismiss it for the rest of their visit.
+#[component]
+pub fn MaintenanceBanner() -> impl IntoView {
+    let banner = Resource::new(|| (), |()| get_maintenance_banner());
+    let (dismissed, set_dismissed) = signal(false);
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                let message = banner.await.ok().flatten()?;
+                Some(
+                    view! {
+                        <Show when=move || !dismissed.get()>
+                            <div role="status" class="rounded-none alert alert-info">
+                                <span>{message.clone()}</span>
+                                <button
+                                    class="btn btn-sm btn-ghost"
+                                    aria-label="Dismiss"
+                                    on:click=move |_| set_dismissed.set(true)
+                                >
+                                    "✕"
+                                </button>
+                            </div>
+                        </Show>
+                    },
+                )
+            })}
+        </Suspense>
+    }
+}
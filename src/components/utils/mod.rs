@@ -1,7 +1,9 @@
 //! Utility component modules
 
+pub mod analytics_gate;
 pub mod error;
 pub mod loading;
+pub mod maintenance_banner;
 pub mod not_found;
 pub mod permissions;
 pub mod status_badge;
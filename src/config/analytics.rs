@@ -0,0 +1,258 @@
+//! Analytics configuration details.
+
+use anyhow::{Result, bail};
+
+/// The hosts analytics scripts can be loaded from.
+pub const ANALYTICS_ALLOWED_HOSTS: [&str; 5] = [
+    "plausible.io",
+    "www.googletagmanager.com",
+    "cdn.usefathom.com",
+    "static.cloudflareinsights.com",
+    "cloud.umami.is",
+];
+
+/// The analytics snippet to add to the `<head>` of public pages, e.g. the Plausible `<script>` tag.
+///
+/// This is read from the `ANALYTICS_SNIPPET` environment variable, and defaults to None.
+/// An invalid snippet is logged and ignored, see `parse_analytics_snippet`.
+#[must_use]
+pub fn analytics_snippet() -> Option<String> {
+    let value = std::env::var("ANALYTICS_SNIPPET").ok()?;
+    match parse_analytics_snippet(&value) {
+        Ok(snippet) => snippet,
+        Err(e) => {
+            tracing::error!("Invalid ANALYTICS_SNIPPET: {e}");
+            None
+        }
+    }
+}
+
+/// Check that an analytics snippet only loads scripts from the allowed hosts.
+///
+/// The snippet must only contain `<script src="..."></script>` tags.
+/// Each script must load over HTTPS from one of `ANALYTICS_ALLOWED_HOSTS`.
+/// Inline scripts and event handler attributes are rejected, as they could run anything.
+///
+/// # Arguments
+/// * `value` - The snippet
+///
+/// # Returns
+/// The trimmed snippet, or None if it is blank
+///
+/// # Errors
+/// If the snippet contains anything other than allowed script tags, return an error
+pub fn parse_analytics_snippet(value: &str) -> Result<Option<String>> {
+    let snippet = value.trim();
+    if snippet.is_empty() {
+        return Ok(None);
+    }
+
+    let mut rest = snippet;
+    while !rest.is_empty() {
+        // Lowercasing ASCII keeps the byte offsets the same
+        let lower = rest.to_ascii_lowercase();
+        if !lower.starts_with("<script")
+            || !lower[7..].starts_with(|c: char| c.is_ascii_whitespace() || c == '>')
+        {
+            bail!("Analytics snippet must only contain script tags.");
+        }
+        let Some(open_end) = rest.find('>') else {
+            bail!("Analytics snippet script tags must be closed.");
+        };
+        let Some(close) = lower[open_end..].find("</script>") else {
+            bail!("Analytics snippet script tags must be closed.");
+        };
+        if !rest[open_end + 1..open_end + close].trim().is_empty() {
+            bail!("Analytics snippet must not contain inline scripts.");
+        }
+
+        let src = script_src(&rest[7..open_end])?;
+        let host = url::Url::parse(&src)
+            .ok()
+            .filter(|url| url.scheme() == "https")
+            .and_then(|url| url.host_str().map(str::to_string));
+        let Some(host) = host else {
+            bail!("Analytics scripts must be loaded over https.");
+        };
+        if !ANALYTICS_ALLOWED_HOSTS.contains(&host.as_str()) {
+            bail!("Analytics scripts can't be loaded from {host}.");
+        }
+
+        rest = rest[open_end + close + "</script>".len()..].trim_start();
+    }
+
+    Ok(Some(snippet.to_string()))
+}
+
+/// Get the `src` of a script tag from its attributes, rejecting event handlers.
+/// A duplicate `src` is rejected, as browsers load the first and ignore the rest.
+fn script_src(attributes: &str) -> Result<String> {
+    let mut src = None;
+    let mut seen_src = false;
+    let mut rest = attributes.trim().trim_end_matches('/').trim();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = None;
+        if let Some(after_equals) = rest.strip_prefix('=') {
+            let after_equals = after_equals.trim_start();
+            let quote = after_equals
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'');
+            let Some(quote) = quote else {
+                bail!("Analytics script attributes must be quoted.");
+            };
+            let Some(value_end) = after_equals[1..].find(quote) else {
+                bail!("Analytics script attributes must be quoted.");
+            };
+            value = Some(after_equals[1..=value_end].to_string());
+            rest = after_equals[value_end + 2..].trim_start();
+        }
+
+        if name.starts_with("on") {
+            bail!("Analytics scripts must not have event handlers.");
+        }
+        if name == "src" {
+            if seen_src {
+                bail!("Analytics scripts must only have one src.");
+            }
+            seen_src = true;
+            src = value;
+        }
+    }
+
+    match src {
+        Some(src) => Ok(src),
+        None => bail!("Analytics scripts must have a src."),
+    }
+}
+
+/// The name of the `<meta>` tag the shell adds to pages that load the analytics snippet.
+/// The browser checks for it, as a loaded script can't be removed from the page.
+pub const ANALYTICS_META_NAME: &str = "analytics";
+
+/// Can the page at the path include analytics.
+/// Admin pages never include analytics.
+///
+/// # Arguments
+/// * `path` - The path of the page
+#[must_use]
+pub fn analytics_allowed_for_path(path: &str) -> bool {
+    !(path == "/admin" || path.starts_with("/admin/"))
+}
+
+/// The analytics snippet to add to the page at the path.
+/// Admin pages never include analytics.
+///
+/// # Arguments
+/// * `path` - The path of the page being rendered
+/// * `snippet` - The configured analytics snippet
+#[must_use]
+pub fn analytics_snippet_for_path(path: &str, snippet: Option<String>) -> Option<String> {
+    snippet.filter(|_| analytics_allowed_for_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAUSIBLE: &str = r#"<script defer data-domain="example.com" src="https://plausible.io/js/script.js"></script>"#;
+
+    #[test]
+    fn test_parse_analytics_snippet() {
+        assert_eq!(
+            parse_analytics_snippet(&format!("  {PLAUSIBLE}\n")).unwrap(),
+            Some(PLAUSIBLE.to_string())
+        );
+        assert_eq!(parse_analytics_snippet(" ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_analytics_snippet_multiple_scripts() {
+        let snippet = format!(
+            "{PLAUSIBLE}\n<script async src='https://www.googletagmanager.com/gtag/js?id=G-1'></script>"
+        );
+
+        assert_eq!(parse_analytics_snippet(&snippet).unwrap(), Some(snippet));
+    }
+
+    #[test]
+    fn test_parse_analytics_snippet_disallowed_host() {
+        let result =
+            parse_analytics_snippet(r#"<script src="https://evil.example.com/x.js"></script>"#);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Analytics scripts can't be loaded from evil.example.com."
+        );
+    }
+
+    #[test]
+    fn test_parse_analytics_snippet_rejects_other_content() {
+        for (snippet, message) in [
+            (
+                "<img src=\"https://plausible.io/x.gif\">",
+                "Analytics snippet must only contain script tags.",
+            ),
+            (
+                "<script>alert(1)</script>",
+                "Analytics snippet must not contain inline scripts.",
+            ),
+            (
+                "<script src=\"https://plausible.io/js/script.js\" onload=\"alert(1)\"></script>",
+                "Analytics scripts must not have event handlers.",
+            ),
+            (
+                "<script src=\"https://evil.example/x.js\" src=\"https://plausible.io/js/script.js\"></script>",
+                "Analytics scripts must only have one src.",
+            ),
+            (
+                "<script src=\"http://plausible.io/js/script.js\"></script>",
+                "Analytics scripts must be loaded over https.",
+            ),
+            (
+                "<script src=\"https://plausible.io/js/script.js\">",
+                "Analytics snippet script tags must be closed.",
+            ),
+        ] {
+            assert_eq!(
+                parse_analytics_snippet(snippet).unwrap_err().to_string(),
+                message,
+                "{snippet}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_analytics_allowed_for_path() {
+        assert!(analytics_allowed_for_path("/"));
+        assert!(analytics_allowed_for_path("/administrators"));
+        assert!(!analytics_allowed_for_path("/admin"));
+        assert!(!analytics_allowed_for_path("/admin/label"));
+    }
+
+    #[test]
+    fn test_analytics_snippet_for_path() {
+        let snippet = Some(PLAUSIBLE.to_string());
+
+        assert_eq!(
+            analytics_snippet_for_path("/artists/an-artist", snippet.clone()),
+            snippet
+        );
+        assert_eq!(analytics_snippet_for_path("/", snippet.clone()), snippet);
+        assert_eq!(analytics_snippet_for_path("/admin", snippet.clone()), None);
+        assert_eq!(
+            analytics_snippet_for_path("/admin/artist/an-artist", snippet.clone()),
+            None
+        );
+        assert_eq!(
+            analytics_snippet_for_path("/administrators", snippet.clone()),
+            snippet
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Authentication configuration details.
+
+/// How long an email verification token is valid for, in hours.
+pub const EMAIL_VERIFICATION_EXPIRY_HOURS: i64 = 24;
+
+/// How long a password reset token is valid for, in hours.
+pub const PASSWORD_RESET_EXPIRY_HOURS: i64 = 1;
+
+/// Should users verify their email address before they can log in.
+/// This is read from the `REQUIRE_EMAIL_VERIFICATION` environment variable, and defaults to false.
+#[must_use]
+pub fn require_email_verification() -> bool {
+    std::env::var("REQUIRE_EMAIL_VERIFICATION")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
+
+/// Where users are sent after logging in when no safe `next` page was asked for, used when `LOGIN_REDIRECT` is not set.
+pub const DEFAULT_LOGIN_REDIRECT: &str = "/admin";
+
+/// Where users are sent after logging in.
+///
+/// This is read from the `LOGIN_REDIRECT` environment variable, falling back to `DEFAULT_LOGIN_REDIRECT`.
+/// Only same-origin paths are allowed.
+/// Only the server reads it, the login page asks for it with `get_login_redirect`.
+#[cfg(feature = "ssr")]
+#[must_use]
+pub fn login_redirect() -> String {
+    std::env::var("LOGIN_REDIRECT")
+        .ok()
+        .map(|target| target.trim().to_string())
+        .filter(|target| crate::utils::redirect::is_safe_redirect(target))
+        .unwrap_or_else(|| DEFAULT_LOGIN_REDIRECT.to_string())
+}
+
+/// The default number of login or password reset attempts allowed in a window, used when `AUTH_MAX_ATTEMPTS` is not set.
+pub const DEFAULT_AUTH_MAX_ATTEMPTS: u32 = 5;
+
+/// The number of login or password reset attempts allowed in a window.
+/// This is read from the `AUTH_MAX_ATTEMPTS` environment variable, falling back to `DEFAULT_AUTH_MAX_ATTEMPTS`.
+#[must_use]
+pub fn auth_max_attempts() -> u32 {
+    std::env::var("AUTH_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|attempts| attempts.trim().parse::<u32>().ok())
+        .filter(|attempts| *attempts > 0)
+        .unwrap_or(DEFAULT_AUTH_MAX_ATTEMPTS)
+}
+
+/// The default number of login or password reset attempts allowed from one address in a window, used when `AUTH_MAX_ATTEMPTS_PER_IP` is not set.
+///
+/// This is higher than the per-account limit, as an office or mobile network can share one address.
+pub const DEFAULT_AUTH_MAX_ATTEMPTS_PER_IP: u32 = 20;
+
+/// The number of login or password reset attempts allowed from one address in a window.
+///
+/// This is read from the `AUTH_MAX_ATTEMPTS_PER_IP` environment variable, falling back to `DEFAULT_AUTH_MAX_ATTEMPTS_PER_IP`.
+#[must_use]
+pub fn auth_max_attempts_per_ip() -> u32 {
+    std::env::var("AUTH_MAX_ATTEMPTS_PER_IP")
+        .ok()
+        .and_then(|attempts| attempts.trim().parse::<u32>().ok())
+        .filter(|attempts| *attempts > 0)
+        .unwrap_or(DEFAULT_AUTH_MAX_ATTEMPTS_PER_IP)
+}
+
+/// The default length of the attempt window in minutes, used when `AUTH_ATTEMPT_WINDOW_MINUTES` is not set.
+pub const DEFAULT_AUTH_ATTEMPT_WINDOW_MINUTES: u64 = 15;
+
+/// How long attempts are counted for before the count starts again.
+/// This is read from the `AUTH_ATTEMPT_WINDOW_MINUTES` environment variable, falling back to `DEFAULT_AUTH_ATTEMPT_WINDOW_MINUTES`.
+#[must_use]
+pub fn auth_attempt_window() -> std::time::Duration {
+    let minutes = std::env::var("AUTH_ATTEMPT_WINDOW_MINUTES")
+        .ok()
+        .and_then(|minutes| minutes.trim().parse::<u64>().ok())
+        .filter(|minutes| *minutes > 0)
+        .unwrap_or(DEFAULT_AUTH_ATTEMPT_WINDOW_MINUTES);
+    std::time::Duration::from_secs(minutes * 60)
+}
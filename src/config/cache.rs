@@ -0,0 +1,14 @@
+//! Cache configuration details.
+
+/// The default number of seconds a record label is cached for, used when `RECORD_LABEL_CACHE_TTL_SECONDS` is not set.
+pub const DEFAULT_RECORD_LABEL_CACHE_TTL_SECONDS: u64 = 60;
+
+/// The number of seconds a record label is cached for.
+/// This is read from the `RECORD_LABEL_CACHE_TTL_SECONDS` environment variable, falling back to `DEFAULT_RECORD_LABEL_CACHE_TTL_SECONDS`.
+#[must_use]
+pub fn record_label_cache_ttl_seconds() -> u64 {
+    std::env::var("RECORD_LABEL_CACHE_TTL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_RECORD_LABEL_CACHE_TTL_SECONDS)
+}
@@ -0,0 +1,19 @@
+//! Database configuration details.
+
+use std::time::Duration;
+
+/// The default number of seconds a server function may run for, used when `REQUEST_TIMEOUT_SECONDS` is not set.
+pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// How long a server function or database statement may run for.
+/// This is read from the `REQUEST_TIMEOUT_SECONDS` environment variable, falling back to `DEFAULT_REQUEST_TIMEOUT_SECONDS`.
+#[must_use]
+pub fn request_timeout() -> Duration {
+    Duration::from_secs(
+        std::env::var("REQUEST_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|seconds| seconds.trim().parse::<u64>().ok())
+            .filter(|seconds| *seconds > 0)
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECONDS),
+    )
+}
@@ -0,0 +1,15 @@
+//! New release digest configuration details.
+
+/// The default number of days between digests, used when `RELEASE_DIGEST_INTERVAL_DAYS` is not set.
+pub const DEFAULT_RELEASE_DIGEST_INTERVAL_DAYS: i64 = 7;
+
+/// The number of days between new release digests.
+/// This is read from the `RELEASE_DIGEST_INTERVAL_DAYS` environment variable, falling back to `DEFAULT_RELEASE_DIGEST_INTERVAL_DAYS`.
+#[must_use]
+pub fn release_digest_interval_days() -> i64 {
+    std::env::var("RELEASE_DIGEST_INTERVAL_DAYS")
+        .ok()
+        .and_then(|days| days.trim().parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_RELEASE_DIGEST_INTERVAL_DAYS)
+}
@@ -0,0 +1,15 @@
+//! Homepage configuration details.
+
+/// The default number of releases shown in each homepage section, used when `HOMEPAGE_RELEASE_COUNT` is not set.
+pub const DEFAULT_HOMEPAGE_RELEASE_COUNT: i64 = 4;
+
+/// The number of releases shown in each homepage section.
+/// This is read from the `HOMEPAGE_RELEASE_COUNT` environment variable, falling back to `DEFAULT_HOMEPAGE_RELEASE_COUNT`.
+#[must_use]
+pub fn homepage_release_count() -> i64 {
+    std::env::var("HOMEPAGE_RELEASE_COUNT")
+        .ok()
+        .and_then(|count| count.trim().parse::<i64>().ok())
+        .filter(|count| *count > 0)
+        .unwrap_or(DEFAULT_HOMEPAGE_RELEASE_COUNT)
+}
@@ -0,0 +1,163 @@
+//! Placeholder image configuration details.
+//!
+//! The placeholders are read from the environment on the server, and sent to the browser with the record label,
+//! so the server and the hydrated page always render the same image URLs.
+
+use serde::{Deserialize, Serialize};
+
+/// The image used when there is no uploaded image, and no placeholder is configured.
+pub const DEFAULT_IMAGE: &str = "/Logo.svg";
+
+/// The kinds of image that can have their own placeholder.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PlaceholderImage {
+    /// Artist images, e.g. a silhouette.
+    Artist,
+    /// Release artwork, e.g. a vinyl icon.
+    Release,
+    /// Track artwork.
+    Track,
+    /// User avatars.
+    Avatar,
+}
+
+impl PlaceholderImage {
+    /// The environment variable the placeholder is read from.
+    #[must_use]
+    pub const fn env_var(self) -> &'static str {
+        match self {
+            Self::Artist => "DEFAULT_ARTIST_IMAGE",
+            Self::Release => "DEFAULT_RELEASE_IMAGE",
+            Self::Track => "DEFAULT_TRACK_IMAGE",
+            Self::Avatar => "DEFAULT_AVATAR_IMAGE",
+        }
+    }
+}
+
+/// The placeholder image URL for each kind of image.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct PlaceholderImages {
+    /// The placeholder for artist images.
+    pub artist: String,
+    /// The placeholder for release artwork.
+    pub release: String,
+    /// The placeholder for track artwork.
+    pub track: String,
+    /// The placeholder for user avatars.
+    pub avatar: String,
+}
+
+impl Default for PlaceholderImages {
+    fn default() -> Self {
+        Self {
+            artist: DEFAULT_IMAGE.to_string(),
+            release: DEFAULT_IMAGE.to_string(),
+            track: DEFAULT_IMAGE.to_string(),
+            avatar: DEFAULT_IMAGE.to_string(),
+        }
+    }
+}
+
+impl PlaceholderImages {
+    /// Read the placeholders from their environment variables, falling back to `DEFAULT_IMAGE`.
+    /// Only the server reads the environment, the browser is sent the placeholders with the record label.
+    #[cfg(feature = "ssr")]
+    #[must_use]
+    pub fn from_env() -> Self {
+        let configured =
+            |image: PlaceholderImage| placeholder_url(std::env::var(image.env_var()).ok());
+        Self {
+            artist: configured(PlaceholderImage::Artist),
+            release: configured(PlaceholderImage::Release),
+            track: configured(PlaceholderImage::Track),
+            avatar: configured(PlaceholderImage::Avatar),
+        }
+    }
+
+    /// The URL of the placeholder for a kind of image.
+    #[must_use]
+    pub fn url(&self, image: PlaceholderImage) -> &str {
+        match image {
+            PlaceholderImage::Artist => &self.artist,
+            PlaceholderImage::Release => &self.release,
+            PlaceholderImage::Track => &self.track,
+            PlaceholderImage::Avatar => &self.avatar,
+        }
+    }
+}
+
+/// Use a configured placeholder URL, falling back to `DEFAULT_IMAGE` when it is unset or blank.
+fn placeholder_url(configured: Option<String>) -> String {
+    configured
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_IMAGE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder_url() {
+        assert_eq!(
+            placeholder_url(Some("/images/vinyl.svg".to_string())),
+            "/images/vinyl.svg"
+        );
+        assert_eq!(
+            placeholder_url(Some(" /images/silhouette.svg ".to_string())),
+            "/images/silhouette.svg"
+        );
+        assert_eq!(placeholder_url(Some("  ".to_string())), DEFAULT_IMAGE);
+        assert_eq!(placeholder_url(None), DEFAULT_IMAGE);
+    }
+
+    #[test]
+    fn test_placeholder_image_env_var() {
+        assert_eq!(PlaceholderImage::Artist.env_var(), "DEFAULT_ARTIST_IMAGE");
+        assert_eq!(PlaceholderImage::Release.env_var(), "DEFAULT_RELEASE_IMAGE");
+        assert_eq!(PlaceholderImage::Track.env_var(), "DEFAULT_TRACK_IMAGE");
+        assert_eq!(PlaceholderImage::Avatar.env_var(), "DEFAULT_AVATAR_IMAGE");
+    }
+
+    #[test]
+    fn test_placeholder_images_url() {
+        let placeholders = PlaceholderImages {
+            artist: "/images/silhouette.svg".to_string(),
+            release: "/images/vinyl.svg".to_string(),
+            track: "/images/note.svg".to_string(),
+            avatar: "/images/avatar.svg".to_string(),
+        };
+
+        assert_eq!(
+            placeholders.url(PlaceholderImage::Artist),
+            "/images/silhouette.svg"
+        );
+        assert_eq!(
+            placeholders.url(PlaceholderImage::Release),
+            "/images/vinyl.svg"
+        );
+        assert_eq!(
+            placeholders.url(PlaceholderImage::Track),
+            "/images/note.svg"
+        );
+        assert_eq!(
+            placeholders.url(PlaceholderImage::Avatar),
+            "/images/avatar.svg"
+        );
+    }
+
+    #[test]
+    fn test_placeholder_images_default() {
+        let placeholders = PlaceholderImages::default();
+
+        for image in [
+            PlaceholderImage::Artist,
+            PlaceholderImage::Release,
+            PlaceholderImage::Track,
+            PlaceholderImage::Avatar,
+        ] {
+            assert_eq!(placeholders.url(image), DEFAULT_IMAGE);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Maintenance configuration details.
+
+use crate::utils::shorten_string::strip_markdown;
+
+/// The banner message shown during maintenance when `MAINTENANCE_MESSAGE` is not set.
+pub const DEFAULT_MAINTENANCE_MESSAGE: &str =
+    "We're carrying out maintenance, so changes can't be made right now.";
+
+/// Is the site down for maintenance.
+/// This is read from the `MAINTENANCE_MODE` environment variable, and defaults to false.
+/// The banner is also shown whenever the deployment is read-only.
+#[must_use]
+pub fn maintenance_mode() -> bool {
+    std::env::var("MAINTENANCE_MODE")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
+
+/// The message to show in the maintenance banner.
+///
+/// This is read from the `MAINTENANCE_MESSAGE` environment variable, and defaults to None.
+/// The message is sanitised to plain text, see `sanitise_maintenance_message`.
+#[must_use]
+pub fn maintenance_message() -> Option<String> {
+    sanitise_maintenance_message(&std::env::var("MAINTENANCE_MESSAGE").ok()?)
+}
+
+/// Reduce a maintenance message to plain text.
+/// Markdown and HTML are stripped, and whitespace is collapsed.
+///
+/// # Arguments
+/// * `message` - The configured message
+///
+/// # Returns
+/// The plain text message, or None if it is blank
+#[must_use]
+pub fn sanitise_maintenance_message(message: &str) -> Option<String> {
+    let message = strip_markdown(message)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if message.is_empty() {
+        None
+    } else {
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitise_maintenance_message() {
+        assert_eq!(
+            sanitise_maintenance_message("  Back **soon**,\n <b>promise</b>! ").unwrap(),
+            "Back soon, promise!"
+        );
+        assert_eq!(
+            sanitise_maintenance_message("<script>alert(1)</script>"),
+            None
+        );
+        assert_eq!(sanitise_maintenance_message(" "), None);
+    }
+}
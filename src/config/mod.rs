@@ -1,3 +1,15 @@
 //! Contains configuration and utility modules for the application.
 
+pub mod analytics;
+pub mod auth;
+pub mod cache;
+pub mod database;
+pub mod digest;
+pub mod homepage;
+pub mod image;
+pub mod maintenance;
+pub mod page;
+pub mod session;
+pub mod site;
+pub mod slug;
 pub mod upload;
@@ -0,0 +1,15 @@
+//! Page configuration details.
+
+/// The default maximum length of a page body, used when `PAGE_MAX_BODY_LENGTH` is not set.
+pub const DEFAULT_PAGE_MAX_BODY_LENGTH: usize = 100_000;
+
+/// The maximum length of a page body, in bytes.
+/// This is read from the `PAGE_MAX_BODY_LENGTH` environment variable, falling back to `DEFAULT_PAGE_MAX_BODY_LENGTH`.
+#[must_use]
+pub fn page_max_body_length() -> usize {
+    std::env::var("PAGE_MAX_BODY_LENGTH")
+        .ok()
+        .and_then(|length| length.trim().parse::<usize>().ok())
+        .filter(|length| *length > 0)
+        .unwrap_or(DEFAULT_PAGE_MAX_BODY_LENGTH)
+}
@@ -0,0 +1,254 @@
+//! Session cookie configuration details.
+
+use anyhow::{Result, bail};
+use std::str::FromStr;
+
+/// Which cross-site requests the session cookie is sent with.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum CookieSameSite {
+    /// Only send the cookie with same-site requests.
+    Strict,
+    /// Also send the cookie when following a link to the site.
+    #[default]
+    Lax,
+    /// Send the cookie with all requests. Browsers require the cookie to be secure.
+    None,
+}
+
+impl FromStr for CookieSameSite {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "lax" => Ok(Self::Lax),
+            "none" => Ok(Self::None),
+            _ => bail!("Invalid cookie SameSite value"),
+        }
+    }
+}
+
+/// Should the session cookie only be sent over HTTPS.
+/// This is read from the `COOKIE_SECURE` environment variable, and defaults to true.
+#[must_use]
+pub fn cookie_secure() -> bool {
+    std::env::var("COOKIE_SECURE").map_or(true, |value| {
+        !matches!(value.trim().to_lowercase().as_str(), "false" | "0" | "no")
+    })
+}
+
+/// Which cross-site requests the session cookie is sent with.
+/// This is read from the `COOKIE_SAME_SITE` environment variable, and defaults to `Lax`.
+#[must_use]
+pub fn cookie_same_site() -> CookieSameSite {
+    std::env::var("COOKIE_SAME_SITE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+/// The domain the session cookie is set for, e.g. to share it with subdomains.
+/// This is read from the `COOKIE_DOMAIN` environment variable, and defaults to the current host.
+#[must_use]
+pub fn cookie_domain() -> Option<String> {
+    std::env::var("COOKIE_DOMAIN")
+        .ok()
+        .map(|domain| domain.trim().to_string())
+        .filter(|domain| !domain.is_empty())
+}
+
+/// The default length of a session in hours, used when `SESSION_LIFETIME_HOURS` is not set.
+pub const DEFAULT_SESSION_LIFETIME_HOURS: i64 = 2;
+
+/// How long a session lasts when the user did not ask to be remembered.
+/// This is read from the `SESSION_LIFETIME_HOURS` environment variable, falling back to `DEFAULT_SESSION_LIFETIME_HOURS`.
+#[must_use]
+pub fn session_lifetime() -> chrono::Duration {
+    session_lifetime_from(std::env::var("SESSION_LIFETIME_HOURS").ok())
+}
+
+/// Turn a configured number of hours into a session lifetime.
+fn session_lifetime_from(hours: Option<String>) -> chrono::Duration {
+    chrono::Duration::hours(positive_or(hours, DEFAULT_SESSION_LIFETIME_HOURS))
+}
+
+/// The default length of a remembered session in days, used when `REMEMBER_ME_LIFETIME_DAYS` is not set.
+pub const DEFAULT_REMEMBER_ME_LIFETIME_DAYS: i64 = 30;
+
+/// How long a session lasts when the user asked to be remembered.
+/// This is read from the `REMEMBER_ME_LIFETIME_DAYS` environment variable, falling back to `DEFAULT_REMEMBER_ME_LIFETIME_DAYS`.
+#[must_use]
+pub fn remember_me_lifetime() -> chrono::Duration {
+    remember_me_lifetime_from(std::env::var("REMEMBER_ME_LIFETIME_DAYS").ok())
+}
+
+/// Turn a configured number of days into a remembered session lifetime.
+fn remember_me_lifetime_from(days: Option<String>) -> chrono::Duration {
+    chrono::Duration::days(positive_or(days, DEFAULT_REMEMBER_ME_LIFETIME_DAYS))
+}
+
+/// Parse a configured whole number, falling back to the default when it is unset, invalid or not positive.
+fn positive_or(configured: Option<String>, default: i64) -> i64 {
+    configured
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(default)
+}
+
+/// Apply the session lifetimes to a session configuration.
+///
+/// # Arguments
+/// * `config` - The session configuration
+/// * `lifetime` - How long a session lasts
+/// * `remember_lifetime` - How long a session lasts when the user asked to be remembered
+#[cfg(feature = "ssr")]
+#[must_use]
+pub fn with_session_lifetimes(
+    config: axum_session::SessionConfig,
+    lifetime: chrono::Duration,
+    remember_lifetime: chrono::Duration,
+) -> axum_session::SessionConfig {
+    config
+        .with_lifetime(lifetime)
+        .with_max_lifetime(remember_lifetime)
+}
+
+/// Apply the cookie attributes to a session configuration.
+///
+/// # Arguments
+/// * `config` - The session configuration
+/// * `secure` - Should the cookie only be sent over HTTPS
+/// * `same_site` - Which cross-site requests the cookie is sent with
+/// * `domain` - The domain the cookie is set for
+#[cfg(feature = "ssr")]
+#[must_use]
+pub fn with_cookie_attributes(
+    config: axum_session::SessionConfig,
+    secure: bool,
+    same_site: CookieSameSite,
+    domain: Option<String>,
+) -> axum_session::SessionConfig {
+    use axum_session::SameSite;
+
+    let config = config
+        .with_secure(secure)
+        .with_cookie_same_site(match same_site {
+            CookieSameSite::Strict => SameSite::Strict,
+            CookieSameSite::Lax => SameSite::Lax,
+            CookieSameSite::None => SameSite::None,
+        });
+    match domain {
+        Some(domain) => config.with_cookie_domain(domain),
+        None => config,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_same_site_from_str() {
+        assert_eq!(
+            "Strict".parse::<CookieSameSite>().unwrap(),
+            CookieSameSite::Strict
+        );
+        assert_eq!(
+            " lax ".parse::<CookieSameSite>().unwrap(),
+            CookieSameSite::Lax
+        );
+        assert_eq!(
+            "none".parse::<CookieSameSite>().unwrap(),
+            CookieSameSite::None
+        );
+        assert!("sometimes".parse::<CookieSameSite>().is_err());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_with_cookie_attributes_defaults() {
+        let config = with_cookie_attributes(
+            axum_session::SessionConfig::default(),
+            true,
+            CookieSameSite::default(),
+            None,
+        );
+
+        let config = format!("{config:?}");
+        assert!(config.contains("cookie_secure: true"));
+        assert!(config.contains("cookie_same_site: Lax"));
+        assert!(config.contains("cookie_domain: None"));
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_with_cookie_attributes() {
+        let config = with_cookie_attributes(
+            axum_session::SessionConfig::default(),
+            false,
+            CookieSameSite::Strict,
+            Some("example.com".to_string()),
+        );
+
+        let config = format!("{config:?}");
+        assert!(config.contains("cookie_secure: false"));
+        assert!(config.contains("cookie_same_site: Strict"));
+        assert!(config.contains("cookie_domain: Some(\"example.com\")"));
+    }
+
+    #[test]
+    fn test_positive_or() {
+        assert_eq!(positive_or(Some("12".to_string()), 2), 12);
+        assert_eq!(positive_or(Some(" 12 ".to_string()), 2), 12);
+        assert_eq!(positive_or(Some("0".to_string()), 2), 2);
+        assert_eq!(positive_or(Some("-3".to_string()), 2), 2);
+        assert_eq!(positive_or(Some("two".to_string()), 2), 2);
+        assert_eq!(positive_or(None, 2), 2);
+    }
+
+    #[test]
+    fn test_session_lifetime() {
+        assert_eq!(
+            session_lifetime_from(None),
+            chrono::Duration::hours(DEFAULT_SESSION_LIFETIME_HOURS)
+        );
+        assert_eq!(
+            session_lifetime_from(Some("8".to_string())),
+            chrono::Duration::hours(8)
+        );
+        assert_eq!(
+            session_lifetime_from(Some("forever".to_string())),
+            chrono::Duration::hours(DEFAULT_SESSION_LIFETIME_HOURS)
+        );
+    }
+
+    #[test]
+    fn test_remember_me_lifetime() {
+        assert_eq!(
+            remember_me_lifetime_from(None),
+            chrono::Duration::days(DEFAULT_REMEMBER_ME_LIFETIME_DAYS)
+        );
+        assert_eq!(
+            remember_me_lifetime_from(Some("90".to_string())),
+            chrono::Duration::days(90)
+        );
+        assert_eq!(
+            remember_me_lifetime_from(Some("0".to_string())),
+            chrono::Duration::days(DEFAULT_REMEMBER_ME_LIFETIME_DAYS)
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_with_session_lifetimes() {
+        let config = with_session_lifetimes(
+            axum_session::SessionConfig::default(),
+            chrono::Duration::hours(2),
+            chrono::Duration::days(30),
+        );
+
+        let config = format!("{config:?}");
+        assert!(config.contains("lifespan: TimeDelta { secs: 7200, nanos: 0 }"));
+        assert!(config.contains("max_lifespan: TimeDelta { secs: 2592000, nanos: 0 }"));
+    }
+}
@@ -0,0 +1,38 @@
+//! Site configuration details.
+
+/// The default public base URL, used when `BASE_URL` is not set.
+pub const DEFAULT_BASE_URL: &str = "http://localhost:3000";
+
+/// The number of releases listed in the feed of new releases.
+pub const RELEASE_FEED_LENGTH: i64 = 20;
+
+/// The public base URL of the site, used to build absolute links for exports, feeds and emails.
+///
+/// This is read from the `BASE_URL` environment variable, falling back to `DEFAULT_BASE_URL`.
+/// Any trailing slash is removed.
+#[must_use]
+pub fn base_url() -> String {
+    std::env::var("BASE_URL")
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+/// Should plain HTTP requests be redirected to HTTPS.
+/// This is read from the `FORCE_HTTPS` environment variable, and defaults to false.
+#[must_use]
+pub fn force_https() -> bool {
+    std::env::var("FORCE_HTTPS")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
+
+/// Is the server behind a proxy that adds the client's address to the `X-Forwarded-For` header.
+///
+/// This is read from the `TRUST_PROXY` environment variable, and defaults to false.
+/// Without a proxy the header comes straight from the client, so it is ignored.
+#[must_use]
+pub fn trust_proxy() -> bool {
+    std::env::var("TRUST_PROXY")
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
@@ -0,0 +1,89 @@
+//! Slug configuration details.
+
+/// The default separator between words in a slug, used when `SLUG_SEPARATOR` is not set.
+pub const DEFAULT_SLUG_SEPARATOR: char = '-';
+
+/// The separators that are safe to use in a URL.
+pub const SLUG_SEPARATORS: [char; 2] = ['-', '_'];
+
+/// The separator between words in a slug.
+/// This is read from the `SLUG_SEPARATOR` environment variable, falling back to `DEFAULT_SLUG_SEPARATOR`.
+/// Only `-` and `_` are allowed.
+#[must_use]
+pub fn slug_separator() -> char {
+    std::env::var("SLUG_SEPARATOR")
+        .ok()
+        .and_then(|separator| separator.trim().parse::<char>().ok())
+        .filter(|separator| SLUG_SEPARATORS.contains(separator))
+        .unwrap_or(DEFAULT_SLUG_SEPARATOR)
+}
+
+/// Should slugs be lowercased.
+/// This is read from the `SLUG_LOWERCASE` environment variable, and defaults to true.
+#[must_use]
+pub fn slug_lowercase() -> bool {
+    std::env::var("SLUG_LOWERCASE").map_or(true, |value| {
+        !matches!(value.trim().to_lowercase().as_str(), "false" | "0" | "no")
+    })
+}
+
+/// What to do when a new slug is already taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlugCollisionStrategy {
+    /// Append the next free number, e.g. "live-2"
+    #[default]
+    Suffix,
+    /// Refuse the slug with "Slug must be unique."
+    Error,
+    /// Append the current date and time, e.g. "live-20250101120000"
+    Timestamp,
+}
+
+impl std::str::FromStr for SlugCollisionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "suffix" => Ok(Self::Suffix),
+            "error" => Ok(Self::Error),
+            "timestamp" => Ok(Self::Timestamp),
+            _ => Err(format!(
+                "Slug collision strategy must be one of suffix, error, timestamp, not {s}."
+            )),
+        }
+    }
+}
+
+/// What to do when a new slug is already taken.
+///
+/// This is read from the `SLUG_COLLISION_STRATEGY` environment variable, one of "suffix", "error" or "timestamp".
+/// Falls back to `SlugCollisionStrategy::Suffix`.
+#[must_use]
+pub fn slug_collision_strategy() -> SlugCollisionStrategy {
+    std::env::var("SLUG_COLLISION_STRATEGY")
+        .ok()
+        .and_then(|strategy| strategy.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug_collision_strategy_from_str() {
+        assert_eq!(
+            "Suffix".parse::<SlugCollisionStrategy>(),
+            Ok(SlugCollisionStrategy::Suffix)
+        );
+        assert_eq!(
+            " error ".parse::<SlugCollisionStrategy>(),
+            Ok(SlugCollisionStrategy::Error)
+        );
+        assert_eq!(
+            "timestamp".parse::<SlugCollisionStrategy>(),
+            Ok(SlugCollisionStrategy::Timestamp)
+        );
+        assert!("random".parse::<SlugCollisionStrategy>().is_err());
+    }
+}
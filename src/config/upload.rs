@@ -3,6 +3,67 @@
 use anyhow::{Result, bail};
 use std::{fmt, str::FromStr};
 
+/// The default maximum upload size in megabytes, used when `MAX_UPLOAD_SIZE_MB` is not set.
+pub const DEFAULT_MAX_UPLOAD_SIZE_MB: usize = 10;
+
+/// The maximum size of any upload, in bytes.
+/// This is read from the `MAX_UPLOAD_SIZE_MB` environment variable, falling back to `DEFAULT_MAX_UPLOAD_SIZE_MB`.
+#[must_use]
+pub fn max_upload_size() -> usize {
+    std::env::var("MAX_UPLOAD_SIZE_MB")
+        .ok()
+        .and_then(|size| size.trim().parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE_MB)
+        * 1024
+        * 1024
+}
+
+/// Room left in the request body for the boundaries and part headers around an uploaded file, in bytes.
+pub const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+/// The maximum size of any request body, in bytes.
+/// This is larger than `max_upload_size`, so a file at the limit still reaches the upload's own size check.
+#[must_use]
+pub fn max_request_body_size() -> usize {
+    max_upload_size() + MULTIPART_OVERHEAD
+}
+
+/// The image types that can be uploaded.
+/// Uploads are checked by their content, not just the content type and extension they were sent with.
+pub const IMAGE_MIME_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+/// The default maximum length of the longest edge of an uploaded image in pixels, used when `MAX_IMAGE_DIMENSION` is not set.
+pub const DEFAULT_MAX_IMAGE_DIMENSION: u32 = 3000;
+
+/// The maximum length of the longest edge of an uploaded image, in pixels.
+///
+/// Larger images are downscaled to fit before they are stored.
+/// This is read from the `MAX_IMAGE_DIMENSION` environment variable, falling back to `DEFAULT_MAX_IMAGE_DIMENSION`.
+#[must_use]
+pub fn max_image_dimension() -> u32 {
+    std::env::var("MAX_IMAGE_DIMENSION")
+        .ok()
+        .and_then(|dimension| dimension.trim().parse::<u32>().ok())
+        .filter(|dimension| *dimension > 0)
+        .unwrap_or(DEFAULT_MAX_IMAGE_DIMENSION)
+}
+
+/// The default quality of the WebP variants of uploaded images, from 0 to 100, used when `WEBP_QUALITY` is not set.
+pub const DEFAULT_WEBP_QUALITY: u8 = 80;
+
+/// The quality of the WebP variants of uploaded images, from 1 to 100.
+///
+/// Lower qualities give smaller files with more visible compression.
+/// This is read from the `WEBP_QUALITY` environment variable, falling back to `DEFAULT_WEBP_QUALITY`.
+#[must_use]
+pub fn webp_quality() -> u8 {
+    std::env::var("WEBP_QUALITY")
+        .ok()
+        .and_then(|quality| quality.trim().parse::<u8>().ok())
+        .filter(|quality| (1..=100).contains(quality))
+        .unwrap_or(DEFAULT_WEBP_QUALITY)
+}
+
 /// Represents the details required for file uploads in different locations, with specific configurations for artists, avatars, and releases etc.
 #[derive(Debug, Clone)]
 pub struct UploadDetails {
@@ -43,41 +104,39 @@ impl FromStr for UploadConfiguration {
     }
 }
 
+/// The image types that can be uploaded, as owned strings for `UploadDetails`.
+fn image_mime_types() -> Vec<String> {
+    IMAGE_MIME_TYPES.iter().map(ToString::to_string).collect()
+}
+
 impl UploadConfiguration {
     /// Returns the upload details for the given configuration.
+    /// The size limit is capped at the configured maximum upload size.
     pub fn get_details(&self) -> UploadDetails {
+        let mut details = self.default_details();
+        details.size_limit = details.size_limit.min(max_upload_size());
+        details
+    }
+
+    /// Returns the upload details for the given configuration, before any limits are applied.
+    fn default_details(self) -> UploadDetails {
         match self {
             Self::Artist => UploadDetails {
-                mime_types: vec![
-                    "image/jpeg".to_string(),
-                    "image/png".to_string(),
-                    "image/gif".to_string(),
-                    "image/webp".to_string(),
-                ],
+                mime_types: image_mime_types(),
                 path: "artists".to_string(),
                 permissions: vec!["admin".to_string(), "label_owner".to_string()],
                 size_limit: 100 * 1024 * 1024, // 100MB
                 rename: false,
             },
             Self::Avatar => UploadDetails {
-                mime_types: vec![
-                    "image/jpeg".to_string(),
-                    "image/png".to_string(),
-                    "image/gif".to_string(),
-                    "image/webp".to_string(),
-                ],
+                mime_types: image_mime_types(),
                 path: "avatars".to_string(),
                 permissions: vec![],
                 size_limit: 10 * 1024 * 1024, // 10MB
                 rename: true,
             },
             Self::Release => UploadDetails {
-                mime_types: vec![
-                    "image/jpeg".to_string(),
-                    "image/png".to_string(),
-                    "image/gif".to_string(),
-                    "image/webp".to_string(),
-                ],
+                mime_types: image_mime_types(),
                 path: "releases".to_string(),
                 permissions: vec!["admin".to_string(), "label_owner".to_string()],
                 size_limit: 100 * 1024 * 1024, // 100MB
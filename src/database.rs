@@ -2,11 +2,25 @@
 //!
 //! It reads the database URL from the `DATABASE_URL` environment variable, which can be set in a `.env` file.
 //! It also runs migrations to ensure the database schema is up-to-date.
+//! Requests and background work use separate pools, as only requests have a statement timeout.
+
+use std::str::FromStr;
 
 use anyhow::Context;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::config::database::request_timeout;
+
+/// The most connections the request pool opens.
+const REQUEST_POOL_MAX_CONNECTIONS: u32 = 200;
 
-/// Create a database connection pool.
+/// The most connections the background pool opens.
+const BACKGROUND_POOL_MAX_CONNECTIONS: u32 = 10;
+
+/// Create the database connection pool for migrations and background jobs.
+///
+/// Its statements have no time limit, so long migrations and scheduled jobs can finish.
+/// Requests use the pool from `create_request_pool` instead.
 ///
 /// # Returns
 ///
@@ -14,7 +28,6 @@ use sqlx::postgres::PgPoolOptions;
 ///
 /// # Errors
 ///
-/// If the `.env` file is not found, return an error.
 /// If the `DATABASE_URL` environment variable is not set, return an error.
 /// If the migrations fail, return an error.
 ///
@@ -22,7 +35,46 @@ use sqlx::postgres::PgPoolOptions;
 ///
 /// If the database connection fails, the program will panic.
 pub async fn create_pool() -> sqlx::PgPool {
-    // Set up database connection
+    let pool = connect(connect_options(), BACKGROUND_POOL_MAX_CONNECTIONS).await;
+
+    match sqlx::migrate!().run(&pool).await {
+        Ok(()) => (),
+        Err(e) => {
+            handle_error(e);
+        }
+    }
+
+    pool
+}
+
+/// Create the database connection pool for requests.
+///
+/// Statements are cancelled by the database after the request timeout,
+/// so an aborted request doesn't leave its query running.
+/// Migrations aren't run, call `create_pool` first.
+///
+/// # Returns
+///
+/// A `PgPool` connection pool.
+///
+/// # Errors
+///
+/// If the `DATABASE_URL` environment variable is not set, return an error.
+///
+/// # Panics
+///
+/// If the database connection fails, the program will panic.
+pub async fn create_request_pool() -> sqlx::PgPool {
+    let connect_options = connect_options().options([(
+        "statement_timeout",
+        request_timeout().as_millis().to_string(),
+    )]);
+
+    connect(connect_options, REQUEST_POOL_MAX_CONNECTIONS).await
+}
+
+/// The connection options for the `DATABASE_URL` environment variable, which can be set in a `.env` file.
+fn connect_options() -> PgConnectOptions {
     let database_url =
         std::env::var("DATABASE_URL").context("DATABASE_URL environment variable must be set.");
 
@@ -33,27 +85,25 @@ pub async fn create_pool() -> sqlx::PgPool {
         }
     };
 
-    let pool = match PgPoolOptions::new()
-        .max_connections(200)
-        .connect(database_url.as_str())
-        .await
-    {
-        Ok(pool) => pool,
+    match PgConnectOptions::from_str(database_url.as_str()) {
+        Ok(connect_options) => connect_options,
         Err(e) => {
-            handle_error(format!(
-                "Could not connect to database_url {database_url}: {e}"
-            ));
+            handle_error(format!("Invalid database_url {database_url}: {e}"));
         }
-    };
+    }
+}
 
-    match sqlx::migrate!().run(&pool).await {
-        Ok(()) => (),
+async fn connect(connect_options: PgConnectOptions, max_connections: u32) -> sqlx::PgPool {
+    match PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(connect_options)
+        .await
+    {
+        Ok(pool) => pool,
         Err(e) => {
-            handle_error(e);
+            handle_error(format!("Could not connect to the database: {e}"));
         }
     }
-
-    pool
 }
 
 fn handle_error(e: impl std::fmt::Display) -> ! {
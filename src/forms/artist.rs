@@ -22,10 +22,23 @@ pub struct UpdateArtistForm {
     pub slug: String,
     /// The name of the artist.
     pub name: String,
+    /// The name used when sorting the artist alphabetically.
+    #[serde(default)]
+    pub sort_name: Option<String>,
     /// The description of the artist.
     pub description: String,
     /// The artists website URL.
     pub website: String,
     /// The record label ID associated with the artist.
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Hide the artist from listings, while keeping it reachable by its slug.
+    #[serde(default)]
+    pub unlisted: bool,
+    /// The ID of the release pinned to the top of the artist's discography.
+    #[serde(default)]
+    pub pinned_release_id: Option<i64>,
+    /// Keep the slug when the name changes, or explicitly unlock it.
+    /// If this is None, the stored setting is kept.
+    #[serde(default)]
+    pub lock_slug: Option<bool>,
 }
@@ -1,23 +1,35 @@
 //! Release forms and related structures for managing releases in the application.
 
+use crate::models::release::ReleaseType;
+
 /// Create a release form
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Default)]
 pub struct CreateReleaseForm {
     /// Comma separated list of artist ids
     pub artist_ids: String,
+    /// Comma separated list of the ids of artists who remixed the release
+    #[serde(default)]
+    pub remixer_ids: String,
     /// Name of the release
     pub name: String,
     /// Description of the release
     pub description: String,
     /// Primary artist ID, this should also be in the `artist_ids` list
     pub primary_artist_id: i64,
+    /// The kind of release, e.g. album or single
+    #[serde(default)]
+    pub release_type: ReleaseType,
     /// Catalogue number for the release
     pub catalogue_number: String,
+    /// UPC-A or EAN-13 barcode for the release
+    #[serde(default)]
+    pub barcode: Option<String>,
     /// Release date of the release
     pub release_date: Option<chrono::DateTime<chrono::Utc>>,
     /// Record label ID associated with the release
     pub label_id: i64,
-    /// Published date of the release
+    /// Published date of the release, must be empty as a new release has no tracks to publish
+    #[serde(default)]
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -26,20 +38,48 @@ pub struct CreateReleaseForm {
 pub struct UpdateReleaseForm {
     /// Comma separated list of artist ids
     pub artist_ids: String,
+    /// Comma separated list of the ids of artists who remixed the release
+    #[serde(default)]
+    pub remixer_ids: String,
     /// Slug of the release
     pub slug: String,
     /// Name of the release
     pub name: String,
+    /// Name used when sorting the release alphabetically
+    #[serde(default)]
+    pub sort_name: Option<String>,
     /// Description of the release
     pub description: String,
     /// Primary artist ID, this should also be in the `artist_ids` list
     pub primary_artist_id: i64,
+    /// The kind of release, e.g. album or single
+    #[serde(default)]
+    pub release_type: ReleaseType,
     /// Catalogue number for the release
     pub catalogue_number: String,
+    /// UPC-A or EAN-13 barcode for the release
+    #[serde(default)]
+    pub barcode: Option<String>,
     /// Release date of the release
     pub release_date: Option<chrono::DateTime<chrono::Utc>>,
     /// Record label ID associated with the release
     pub label_id: i64,
     /// Published date of the release
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The date the embargo on the release lifts, until then only preview token holders can see it
+    #[serde(default)]
+    pub embargo_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Hide the release from listings, while keeping it reachable by its slug
+    #[serde(default)]
+    pub unlisted: bool,
+    /// The price of the release in the minor units of the record label's currency
+    #[serde(default)]
+    pub price_cents: Option<i32>,
+    /// Take the artists of the release from its tracks, for compilations by various artists
+    #[serde(default)]
+    pub is_various: bool,
+    /// Keep the slug when the name changes, or explicitly unlock it
+    /// If this is None, the stored setting is kept
+    #[serde(default)]
+    pub lock_slug: Option<bool>,
 }
@@ -19,6 +19,13 @@ pub struct CreateTrackForm {
     pub isrc_code: Option<String>,
     /// BPM (Beats Per Minute) of the track
     pub bpm: Option<i32>,
+    /// URL of a preview clip of the track
+    pub preview_url: Option<String>,
+    /// Start of the preview clip in milliseconds
+    pub preview_start_ms: Option<i32>,
+    /// Length of the track in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<i32>,
     /// Track number on the release
     pub track_number: i32,
     /// Published date of the track
@@ -46,8 +53,25 @@ pub struct UpdateTrackForm {
     pub isrc_code: Option<String>,
     /// BPM (Beats Per Minute) of the track
     pub bpm: Option<i32>,
+    /// URL of a preview clip of the track
+    pub preview_url: Option<String>,
+    /// Start of the preview clip in milliseconds
+    pub preview_start_ms: Option<i32>,
+    /// Length of the track in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<i32>,
+    /// Does the track contain explicit lyrics
+    #[serde(default)]
+    pub explicit: bool,
+    /// ID of the explicit track this is the clean version of
+    #[serde(default)]
+    pub clean_version_of: Option<i64>,
     /// Track number on the release
     pub track_number: i32,
     /// Published date of the track
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Keep the slug when the name changes, or explicitly unlock it
+    /// If this is None, the stored setting is kept
+    #[serde(default)]
+    pub lock_slug: Option<bool>,
 }
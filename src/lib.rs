@@ -2,6 +2,8 @@
 //!
 //! White Label is a web application for managing record labels, artists and releases.
 
+// Deeply nested views such as the artist page overflow the default query depth
+#![recursion_limit = "256"]
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 #![warn(clippy::pedantic)]
@@ -20,6 +22,8 @@ pub mod config;
 #[cfg(feature = "ssr")]
 pub mod database;
 pub mod forms;
+#[cfg(feature = "ssr")]
+pub mod middleware;
 pub mod models;
 pub mod routes;
 #[cfg(feature = "ssr")]
@@ -0,0 +1,439 @@
+//! Middleware for the server.
+//!
+//! Runs on every request before it reaches the routes.
+
+use std::{net::IpAddr, path::PathBuf, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, Uri, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use leptos::server_fn::{error::FromServerFnError, response::Res};
+
+use crate::services::timeout::with_timeout;
+use crate::utils::files::webp_variant_file_name;
+
+/// The path prefix server functions are served under.
+pub const SERVER_FUNCTION_PATH: &str = "/api/";
+
+/// Paths under `SERVER_FUNCTION_PATH` that are allowed to run past the request timeout.
+///
+/// Uploads and their progress stream last as long as the file takes to send,
+/// and the JSON API is served by plain axum handlers, which time their own requests out with a JSON error.
+pub const TIMEOUT_EXEMPT_PATHS: [&str; 3] = ["/api/upload_file", "/api/file_progress", "/api/v1/"];
+
+/// Paths used by load balancers to check the server is up.
+/// These are never redirected, as health checks are usually made over plain HTTP.
+pub const HEALTH_CHECK_PATHS: [&str; 2] = ["/health", "/healthz"];
+
+/// The HTTPS URL to redirect a plain HTTP request to
+///
+/// The scheme is read from the `X-Forwarded-Proto` header set by a TLS-terminating proxy,
+/// falling back to the request's own scheme.
+///
+/// # Arguments
+/// * `headers` - The request headers
+/// * `uri` - The request URI
+///
+/// # Returns
+/// The URL to redirect to, or None if the request is already HTTPS, is a health check, or has no host
+#[must_use]
+pub fn https_redirect_url(headers: &HeaderMap, uri: &Uri) -> Option<String> {
+    if HEALTH_CHECK_PATHS.contains(&uri.path()) {
+        return None;
+    }
+
+    let scheme = headers
+        .get("x-forwarded-proto")
+        .and_then(|proto| proto.to_str().ok())
+        .and_then(|proto| proto.split(',').next())
+        .map(|proto| proto.trim().to_lowercase())
+        .or_else(|| uri.scheme_str().map(str::to_lowercase))
+        .unwrap_or_else(|| "http".to_string());
+    if scheme == "https" {
+        return None;
+    }
+
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| uri.authority().map(axum::http::uri::Authority::as_str))?;
+    let path_and_query = uri
+        .path_and_query()
+        .map_or("/", axum::http::uri::PathAndQuery::as_str);
+
+    Some(format!("https://{host}{path_and_query}"))
+}
+
+/// The address of the client that made a request
+///
+/// Behind a proxy, this is the last address in the `X-Forwarded-For` header, which is the one the proxy added.
+/// The earlier addresses are sent by the client, so they can be forged.
+/// Otherwise it is the address of the connection.
+///
+/// # Arguments
+/// * `headers` - The request headers
+/// * `peer` - The address of the connection, if known
+/// * `trust_proxy` - Is the server behind a proxy that sets `X-Forwarded-For`, see `TRUST_PROXY`
+///
+/// # Returns
+/// The client's address, or None if it is not known
+#[must_use]
+pub fn client_ip(headers: &HeaderMap, peer: Option<IpAddr>, trust_proxy: bool) -> Option<IpAddr> {
+    if !trust_proxy {
+        return peer;
+    }
+
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|forwarded| forwarded.to_str().ok())
+        .flat_map(|forwarded| forwarded.split(','))
+        .next_back()
+        .and_then(|address| address.trim().parse::<IpAddr>().ok())
+        .or(peer)
+}
+
+/// Permanently redirect plain HTTP requests to HTTPS
+///
+/// Only added to the router when `FORCE_HTTPS` is set.
+pub async fn redirect_to_https(request: Request, next: Next) -> Response {
+    match https_redirect_url(request.headers(), request.uri()) {
+        Some(url) => (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, url)]).into_response(),
+        None => next.run(request).await,
+    }
+}
+
+/// Is a request to this path aborted when it takes longer than the request timeout
+///
+/// # Arguments
+/// * `path` - The request path
+///
+/// # Returns
+/// True for server functions, other than those in `TIMEOUT_EXEMPT_PATHS`
+#[must_use]
+pub fn has_request_timeout(path: &str) -> bool {
+    path.starts_with(SERVER_FUNCTION_PATH)
+        && !TIMEOUT_EXEMPT_PATHS
+            .iter()
+            .any(|exempt| path.starts_with(exempt))
+}
+
+/// Abort server function requests that take longer than the request timeout
+///
+/// The caller gets the server function error "The request timed out.", and the request's connections are released.
+pub async fn timeout_server_functions(
+    State(timeout): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    if !has_request_timeout(&path) {
+        return next.run(request).await;
+    }
+
+    match with_timeout(timeout, async { Ok(next.run(request).await) }).await {
+        Ok(response) => response,
+        Err(e) => Response::error_response(&path, e.ser()),
+    }
+}
+
+/// Does the client accept WebP images
+/// Checks the `Accept` header for `image/webp`, ignoring it if its quality is zero.
+#[must_use]
+pub fn accepts_webp(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|accept| accept.to_str().ok())
+        .flat_map(|accept| accept.split(','))
+        .any(|media_range| {
+            let mut parts = media_range.split(';').map(str::trim);
+            let media_type = parts.next().unwrap_or_default();
+            let refused = parts.any(|parameter| {
+                parameter
+                    .strip_prefix("q=")
+                    .and_then(|quality| quality.parse::<f32>().ok())
+                    .is_some_and(|quality| quality <= 0.0)
+            });
+            media_type.eq_ignore_ascii_case("image/webp") && !refused
+        })
+}
+
+/// Serve the WebP variant of an uploaded image to clients that accept WebP
+///
+/// The URL stays the same, and the original is served if there is no variant.
+/// Responses for images with variants vary on `Accept`, so caches keep both.
+pub async fn negotiate_webp_images(
+    State(upload_path): State<PathBuf>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let Some(variant) = webp_variant_file_name(&path) else {
+        return next.run(request).await;
+    };
+
+    let variant_path = upload_path.join(variant.trim_start_matches('/'));
+    if accepts_webp(request.headers())
+        && !variant.contains("..")
+        && variant_path.is_file()
+        && let Ok(uri) = variant.parse::<Uri>()
+    {
+        *request.uri_mut() = uri;
+    }
+
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, routing::get};
+    use axum_test::TestServer;
+
+    fn test_server() -> TestServer {
+        let app = Router::new()
+            .route("/artists", get(|| async { "artists" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(redirect_to_https));
+        TestServer::new(app).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_https_forwarded_http() {
+        let server = test_server();
+
+        let response = server
+            .get("/artists?page=2")
+            .add_header("host", "example.com")
+            .add_header("x-forwarded-proto", "http")
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            response.header("location"),
+            "https://example.com/artists?page=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_https_forwarded_https() {
+        let server = test_server();
+
+        let response = server
+            .get("/artists")
+            .add_header("host", "example.com")
+            .add_header("x-forwarded-proto", "https")
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.text(), "artists");
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_https_skips_health_checks() {
+        let server = test_server();
+
+        let response = server
+            .get("/health")
+            .add_header("host", "example.com")
+            .add_header("x-forwarded-proto", "http")
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+
+    fn timeout_test_server() -> TestServer {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "slow"
+        };
+        let app = Router::new()
+            .route("/api/slow", get(slow))
+            .route("/api/fast", get(|| async { "fast" }))
+            .route(
+                "/api/v1/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    "slow"
+                }),
+            )
+            .route("/slow", get(slow))
+            .layer(axum::middleware::from_fn_with_state(
+                Duration::from_millis(50),
+                timeout_server_functions,
+            ));
+        TestServer::new(app).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_timeout_server_functions_aborts_slow_request() {
+        let server = timeout_test_server();
+
+        let response = server.get("/api/slow").await;
+
+        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        let error: leptos::prelude::ServerFnError = FromServerFnError::de(response.into_bytes());
+        assert_eq!(
+            error.to_string(),
+            "error running server function: The request timed out."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_server_functions_completes_fast_request() {
+        let server = timeout_test_server();
+
+        let response = server.get("/api/fast").await;
+
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.text(), "fast");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_server_functions_skips_exempt_paths() {
+        let server = timeout_test_server();
+
+        let response = server.get("/api/v1/slow").await;
+
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.text(), "slow");
+    }
+
+    #[test]
+    fn test_has_request_timeout() {
+        assert!(has_request_timeout("/api/login"));
+        assert!(has_request_timeout("/api/update_release"));
+        assert!(!has_request_timeout("/api/upload_file"));
+        assert!(!has_request_timeout("/api/file_progress"));
+        assert!(!has_request_timeout("/api/v1/artists/some-artist"));
+        assert!(!has_request_timeout("/artists"));
+        assert!(!has_request_timeout("/uploads/image.jpg"));
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let peer: IpAddr = "10.0.0.1".parse().unwrap();
+        let mut headers = HeaderMap::new();
+
+        assert_eq!(client_ip(&headers, Some(peer), true), Some(peer));
+        assert_eq!(client_ip(&headers, None, false), None);
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 198.51.100.2"),
+        );
+        // The last address is the one added by the proxy
+        assert_eq!(
+            client_ip(&headers, Some(peer), true),
+            Some("198.51.100.2".parse().unwrap())
+        );
+        // Without a proxy the header is ignored, as the client can set it
+        assert_eq!(client_ip(&headers, Some(peer), false), Some(peer));
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("not an address"),
+        );
+        assert_eq!(client_ip(&headers, Some(peer), true), Some(peer));
+    }
+
+    #[test]
+    fn test_https_redirect_url_without_forwarded_header() {
+        let headers = HeaderMap::new();
+        let uri: Uri = "http://example.com/pages/about".parse().unwrap();
+
+        assert_eq!(
+            https_redirect_url(&headers, &uri),
+            Some("https://example.com/pages/about".to_string())
+        );
+        let uri: Uri = "https://example.com/pages/about".parse().unwrap();
+        assert_eq!(https_redirect_url(&headers, &uri), None);
+    }
+
+    fn webp_test_server(name: &str) -> (TestServer, PathBuf) {
+        let directory =
+            std::env::temp_dir().join(format!("white-label-webp-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(directory.join("releases")).unwrap();
+        std::fs::write(directory.join("releases/cover.png"), b"png").unwrap();
+        std::fs::write(directory.join("releases/cover.png.webp"), b"webp").unwrap();
+        std::fs::write(directory.join("releases/other.png"), b"other png").unwrap();
+
+        let uploads = Router::new()
+            .fallback_service(tower_http::services::ServeDir::new(&directory))
+            .layer(axum::middleware::from_fn_with_state(
+                directory.clone(),
+                negotiate_webp_images,
+            ));
+        let app = Router::new().nest_service("/uploads", uploads);
+        (TestServer::new(app).unwrap(), directory)
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_webp_images_accepts_webp() {
+        let (server, directory) = webp_test_server("accepts");
+
+        let response = server
+            .get("/uploads/releases/cover.png")
+            .add_header("accept", "image/avif,image/webp,*/*")
+            .await;
+
+        std::fs::remove_dir_all(directory).unwrap();
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.text(), "webp");
+        assert_eq!(response.header("content-type"), "image/webp");
+        assert_eq!(response.header("vary"), "accept");
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_webp_images_without_webp() {
+        let (server, directory) = webp_test_server("without");
+
+        let response = server
+            .get("/uploads/releases/cover.png")
+            .add_header("accept", "image/png,*/*")
+            .await;
+
+        std::fs::remove_dir_all(directory).unwrap();
+        assert_eq!(response.text(), "png");
+        assert_eq!(response.header("content-type"), "image/png");
+        assert_eq!(response.header("vary"), "accept");
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_webp_images_no_variant() {
+        let (server, directory) = webp_test_server("no-variant");
+
+        let response = server
+            .get("/uploads/releases/other.png")
+            .add_header("accept", "image/webp")
+            .await;
+
+        std::fs::remove_dir_all(directory).unwrap();
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.text(), "other png");
+    }
+
+    #[test]
+    fn test_accepts_webp() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_webp(&headers));
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("image/WebP;q=0.8"));
+        assert!(accepts_webp(&headers));
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("image/webp;q=0, */*"),
+        );
+        assert!(!accepts_webp(&headers));
+        headers.insert(header::ACCEPT, HeaderValue::from_static("image/png, */*"));
+        assert!(!accepts_webp(&headers));
+    }
+}
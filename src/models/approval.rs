@@ -0,0 +1,360 @@
+//! Approval model
+//!
+//! The Approval struct is used to represent a request to publish a release
+//! that must be signed off by a second label owner.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgConnection, PgPool};
+
+/// Errors returned by the approval model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ApprovalError {
+    /// Approval could not be requested for the release
+    #[error("Could not request approval for release with id {0}.")]
+    Create(i64),
+    /// The approvals for the release could not be found
+    #[error("Could not find approvals for release with id {0}.")]
+    NotFoundByRelease(i64),
+    /// The approver requested the approval themselves
+    #[error("You cannot approve your own publish request.")]
+    OwnRequest,
+    /// The approval was approved by someone else first
+    #[error("This release has already been approved.")]
+    AlreadyApproved,
+    /// The approval could not be approved
+    #[error("Could not approve approval with id {0}.")]
+    Approve(i64),
+}
+
+/// The Approval struct is used to represent a publish approval in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct Approval {
+    /// The unique identifier of the approval
+    pub id: i64,
+    /// The release waiting to be published
+    pub release_id: i64,
+    /// The user that requested the release be published
+    pub requested_by: i64,
+    /// The user that approved the request
+    /// If this is None, the approval is still pending
+    pub approved_by: Option<i64>,
+    /// The date and time the approval was requested
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the approval was granted
+    /// If this is None, the approval is still pending
+    pub approved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Approval {
+    /// Is the approval still waiting to be signed off
+    pub const fn is_pending(&self) -> bool {
+        self.approved_at.is_none()
+    }
+
+    /// Request approval to publish a release
+    /// If there is already a pending approval for the release, it is returned instead.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `release_id` - The ID of the release to publish
+    /// * `requested_by` - The ID of the user requesting the release be published
+    ///
+    /// # Returns
+    /// The pending approval
+    ///
+    /// # Errors
+    /// If the approval cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(
+        pool: &PgPool,
+        release_id: i64,
+        requested_by: i64,
+    ) -> Result<Self, ApprovalError> {
+        if let Some(approval) = Self::get_pending_by_release(pool, release_id).await? {
+            return Ok(approval);
+        }
+
+        let approval = sqlx::query_as::<_, Self>(
+            "INSERT INTO approvals (release_id, requested_by) VALUES ($1, $2) RETURNING *",
+        )
+        .bind(release_id)
+        .bind(requested_by)
+        .fetch_one(pool)
+        .await;
+
+        match approval {
+            Ok(approval) => Ok(approval),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ApprovalError::Create(release_id))
+            }
+        }
+    }
+
+    /// Get the pending approval for a release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `release_id` - The ID of the release
+    ///
+    /// # Returns
+    /// The pending approval, or None if there isn't one
+    ///
+    /// # Errors
+    /// If there is an error querying the database, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_pending_by_release(
+        pool: &PgPool,
+        release_id: i64,
+    ) -> Result<Option<Self>, ApprovalError> {
+        let approval = sqlx::query_as::<_, Self>(
+            "SELECT * FROM approvals WHERE release_id = $1 AND approved_at IS NULL",
+        )
+        .bind(release_id)
+        .fetch_optional(pool)
+        .await;
+
+        match approval {
+            Ok(approval) => Ok(approval),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ApprovalError::NotFoundByRelease(release_id))
+            }
+        }
+    }
+
+    /// Approve a pending request
+    /// The approver must be a different user to the one that requested it.
+    ///
+    /// # Arguments
+    /// * `conn` - The database connection, which may be part of a transaction
+    /// * `approver_id` - The ID of the user approving the request
+    ///
+    /// # Returns
+    /// The approved approval
+    ///
+    /// # Errors
+    /// If the approval has already been approved, return an error
+    /// If the approver requested the approval, return an error
+    /// If the approval cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn approve(
+        &self,
+        conn: &mut PgConnection,
+        approver_id: i64,
+    ) -> Result<Self, ApprovalError> {
+        if self.requested_by == approver_id {
+            return Err(ApprovalError::OwnRequest);
+        }
+
+        // Only a pending approval is updated, so when two approvers race the second finds no row
+        let approval = sqlx::query_as::<_, Self>(
+            "UPDATE approvals SET approved_by = $1, approved_at = $2 WHERE id = $3 AND approved_at IS NULL RETURNING *",
+        )
+        .bind(approver_id)
+        .bind(chrono::Utc::now())
+        .bind(self.id)
+        .fetch_optional(conn)
+        .await;
+
+        match approval {
+            Ok(Some(approval)) => Ok(approval),
+            Ok(None) => Err(ApprovalError::AlreadyApproved),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ApprovalError::Approve(self.id))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{create_test_release, create_test_user_with_permissions};
+
+    #[test]
+    fn test_approval_error_display() {
+        let cases = [
+            (
+                ApprovalError::Create(1),
+                "Could not request approval for release with id 1.",
+            ),
+            (
+                ApprovalError::NotFoundByRelease(1),
+                "Could not find approvals for release with id 1.",
+            ),
+            (
+                ApprovalError::OwnRequest,
+                "You cannot approve your own publish request.",
+            ),
+            (
+                ApprovalError::AlreadyApproved,
+                "This release has already been approved.",
+            ),
+            (
+                ApprovalError::Approve(1),
+                "Could not approve approval with id 1.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+
+        assert_eq!(approval.release_id, release.id);
+        assert_eq!(approval.requested_by, user.id);
+        assert!(approval.is_pending());
+        assert_eq!(approval.approved_by, None);
+    }
+
+    #[sqlx::test]
+    async fn test_create_returns_existing_pending_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let second_approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+
+        assert_eq!(approval.id, second_approval.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_pending_by_release(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let pending = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert_eq!(pending, None);
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let pending = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert_eq!(pending, Some(approval));
+    }
+
+    #[sqlx::test]
+    async fn test_approve(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let approval = approval
+            .approve(&mut pool.acquire().await.unwrap(), approver.id)
+            .await
+            .unwrap();
+
+        assert!(!approval.is_pending());
+        assert_eq!(approval.approved_by, Some(approver.id));
+        let pending = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert_eq!(pending, None);
+    }
+
+    #[sqlx::test]
+    async fn test_approve_own_request(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let result = approval
+            .approve(&mut pool.acquire().await.unwrap(), user.id)
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "You cannot approve your own publish request."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_approve_already_approved(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let approval = approval
+            .approve(&mut pool.acquire().await.unwrap(), approver.id)
+            .await
+            .unwrap();
+        let result = approval
+            .approve(&mut pool.acquire().await.unwrap(), approver.id)
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "This release has already been approved."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_approve_stale_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let second_approver = create_test_user_with_permissions(&pool, 3, vec!["label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        // Both approvers loaded the approval while it was still pending
+        let approval = Approval::create(&pool, release.id, user.id).await.unwrap();
+        let stale_approval = approval.clone();
+        approval
+            .approve(&mut pool.acquire().await.unwrap(), approver.id)
+            .await
+            .unwrap();
+        let result = stale_approval
+            .approve(&mut pool.acquire().await.unwrap(), second_approver.id)
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "This release has already been approved."
+        );
+        let approved_by: Option<i64> =
+            sqlx::query_scalar("SELECT approved_by FROM approvals WHERE id = $1")
+                .bind(approval.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(approved_by, Some(approver.id));
+    }
+}
@@ -7,10 +7,87 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool, Row};
 
 #[cfg(feature = "ssr")]
-use super::record_label::RecordLabel;
-use super::traits::Validate;
+use super::query_helpers::{published_clause, release_visible_clause};
 #[cfg(feature = "ssr")]
-use crate::utils::slugify::slugify;
+use super::{
+    genre::Genre,
+    record_label::RecordLabel,
+    release::Release,
+    slug::{SlugTable, unique_slug, unique_slug_with},
+    slug_redirect::SlugRedirect,
+};
+use super::{release::ReleaseError, traits::Validate};
+use crate::config::image::{PlaceholderImage, PlaceholderImages};
+#[cfg(feature = "ssr")]
+use crate::config::slug::slug_collision_strategy;
+#[cfg(feature = "ssr")]
+use crate::utils::pagination::Pagination;
+#[cfg(feature = "ssr")]
+use crate::utils::search::search_patterns;
+#[cfg(feature = "ssr")]
+use crate::utils::slugify::{is_reserved_slug, is_slug_locked, slugify};
+#[cfg(feature = "ssr")]
+use crate::utils::unicode::normalise_name;
+use crate::utils::{
+    files::{OG_IMAGE_DIRECTORY, og_image_file_name, upload_url, upload_url_or},
+    shorten_string::excerpt,
+    sort_name::default_sort_name,
+};
+
+/// Errors returned by the artist model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ArtistError {
+    /// The artist is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// No artist has the given ID
+    #[error("Could not find artist with id {0}.")]
+    NotFoundById(i64),
+    /// No artist has the given slug
+    #[error("Could not find artist with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The artists for the record label could not be found
+    #[error("Could not find artists for record label with id {0}.")]
+    ListByRecordLabel(i64),
+    /// The artists on the record label could not be searched
+    #[error("Could not search artists for record label with id {0}.")]
+    Search(i64),
+    /// The artist could not be updated
+    #[error("Could not update artist with id {0}.")]
+    Update(i64),
+    /// The old slug could not be redirected to the new one
+    #[error("Could not redirect artist slug {0}.")]
+    Redirect(String),
+    /// The pinned release could not be found
+    #[error(transparent)]
+    PinnedRelease(#[from] ReleaseError),
+    /// The genres of the artist could not be set
+    #[error("Could not set genres for artist with id {0}.")]
+    SetGenres(i64),
+    /// The genres of the artist could not be found
+    #[error("Could not find genres for artist with id {0}.")]
+    Genres(i64),
+    /// The streaming stats of the artist could not be updated
+    #[error("Could not update stats for artist with id {0}.")]
+    UpdateStats(i64),
+    /// The releases and tracks affected by deleting the artist could not be counted
+    #[error("Could not count releases and tracks for artist with id {0}.")]
+    DeleteImpact(i64),
+    /// The artist could not be deleted
+    #[error("Could not delete artist with id {0}.")]
+    Delete(i64),
+    /// Any other database error
+    #[error("Could not access artists.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for ArtistError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
 
 /// The Artist struct is used to represent a record artist in the database.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
@@ -20,8 +97,14 @@ pub struct Artist {
     pub id: i64,
     /// The name of the artist
     pub name: String,
+    /// The name used when sorting artists alphabetically
+    /// If this is None, the name is used with any leading article stripped
+    pub sort_name: Option<String>,
     /// The slug of the artist
     pub slug: String,
+    /// Should the slug be kept when the name changes
+    /// If this is None, the slug is locked once the artist has been published
+    pub lock_slug: Option<bool>,
     /// The description of the artist
     pub description: String,
     /// The primary image of the artist
@@ -35,6 +118,19 @@ pub struct Artist {
     /// If this is in the future, the artist is scheduled to be published
     /// If this is in the past, the artist is published
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Is the artist unlisted
+    /// Unlisted artists are excluded from listings, but can still be reached by their slug
+    pub unlisted: bool,
+    /// The release pinned to the top of the artist's discography
+    /// This must be a release by the artist
+    pub pinned_release_id: Option<i64>,
+    /// Monthly listeners on external streaming services, as entered by the label
+    /// This is only exposed to admins, so it is never serialised with the artist
+    #[serde(skip)]
+    pub monthly_listeners: Option<i32>,
+    /// The date and time the streaming stats were last updated
+    #[serde(skip)]
+    pub stats_updated_at: Option<chrono::DateTime<chrono::Utc>>,
     /// The date and time the artist was created in the database
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// The date and time the artist was last updated
@@ -56,18 +152,45 @@ impl Validate for Artist {
             ));
         }
 
+        if let Some(sort_name) = &self.sort_name
+            && sort_name.len() > 255
+        {
+            return Err(anyhow::anyhow!(
+                "Sort name must be less than 255 characters.".to_string()
+            ));
+        }
+
         if self.slug.len() > 255 {
             return Err(anyhow::anyhow!(
                 "Slug must be less than 255 characters.".to_string()
             ));
         }
+        if is_reserved_slug(&self.slug) {
+            return Err(anyhow::anyhow!("Slug is reserved.".to_string()));
+        }
         // Check that the slug is unique
-        if let Ok(artist) = Self::get_by_slug(pool, self.slug.clone()).await
+        if let Ok(artist) = Self::get_by_slug(pool, self.slug.clone(), true).await
             && artist.id != self.id
         {
             return Err(anyhow::anyhow!("Slug must be unique.".to_string()));
         }
 
+        // Check that the pinned release is by the artist
+        if let Some(release_id) = self.pinned_release_id {
+            let is_artists_release = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM release_artists WHERE artist_id = $1 AND release_id = $2)",
+            )
+            .bind(self.id)
+            .bind(release_id)
+            .fetch_one(pool)
+            .await?;
+            if !is_artists_release {
+                return Err(anyhow::anyhow!(
+                    "The pinned release must be by the artist.".to_string()
+                ));
+            }
+        }
+
         // Check that the record label exists
         if let Err(e) = RecordLabel::get_by_id(pool, self.label_id).await {
             tracing::error!("{e}");
@@ -83,14 +206,45 @@ impl Validate for Artist {
 
 impl Artist {
     /// Get the primary image URL
-    /// If the primary image is None, return the default image
-    pub fn primary_image_url(&self) -> String {
-        self.primary_image.clone().map_or_else(
-            || "/Logo.svg".to_string(),
-            |file| format!("/uploads/artists/{file}"),
+    /// If the primary image is None, return the artist placeholder
+    pub fn primary_image_url(&self, placeholders: &PlaceholderImages) -> String {
+        upload_url_or(
+            "artists",
+            self.primary_image.as_deref(),
+            placeholders.url(PlaceholderImage::Artist),
         )
     }
 
+    /// The file name of the Open Graph share image for the artist
+    #[must_use]
+    pub fn og_image_file_name(&self) -> String {
+        og_image_file_name("artist", self.id, &self.name, self.primary_image.as_deref())
+    }
+
+    /// The URL of the Open Graph share image for the artist
+    /// This stays the same until the name or artwork changes.
+    #[must_use]
+    pub fn og_image_url(&self) -> String {
+        upload_url(OG_IMAGE_DIRECTORY, Some(&self.og_image_file_name()))
+    }
+
+    /// A plain text excerpt of the description for listings and cards
+    /// Markdown is stripped, and long descriptions are cut on a word boundary with "…" appended.
+    #[must_use]
+    pub fn excerpt(&self, max_len: usize) -> String {
+        excerpt(&self.description, max_len)
+    }
+
+    /// The name used when sorting the artist alphabetically
+    /// Falls back to the name with any leading article stripped
+    #[must_use]
+    pub fn sort_name_or_default(&self) -> String {
+        self.sort_name
+            .clone()
+            .filter(|sort_name| !sort_name.trim().is_empty())
+            .unwrap_or_else(|| default_sort_name(&self.name))
+    }
+
     /// Create a new artist
     ///
     /// # Arguments
@@ -114,23 +268,35 @@ impl Artist {
         website: String,
         record_label_id: i64,
         published_at: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> anyhow::Result<Self> {
-        let slug = slugify(&name);
+    ) -> Result<Self, ArtistError> {
+        let name = normalise_name(&name);
+        let slug = unique_slug(pool, SlugTable::Artists, slugify(&name))
+            .await
+            .map_err(|e| ArtistError::Invalid(e.to_string()))?;
 
         let artist = Self {
             id: 0,
             name,
             slug,
+            lock_slug: None,
+            sort_name: None,
             description,
             primary_image: None,
             website,
             label_id: record_label_id,
             published_at,
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
         };
-        artist.validate(pool).await?;
+        artist
+            .validate(pool)
+            .await
+            .map_err(|e| ArtistError::Invalid(e.to_string()))?;
 
         let artist = sqlx::query_as::<_, Self>(
             "INSERT INTO artists (name, slug, description, website, label_id, published_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
@@ -159,7 +325,7 @@ impl Artist {
     /// # Errors
     /// If the artist cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_id(pool: &PgPool, id: i64) -> anyhow::Result<Self> {
+    pub async fn get_by_id(pool: &PgPool, id: i64) -> Result<Self, ArtistError> {
         let row = sqlx::query("SELECT * FROM artists WHERE id = $1")
             .bind(id)
             .fetch_one(pool)
@@ -169,19 +335,25 @@ impl Artist {
             Ok(row) => row,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!("Could not find artist with id {}.", id));
+                return Err(ArtistError::NotFoundById(id));
             }
         };
 
         Ok(Self {
             id: row.get("id"),
             name: row.get("name"),
+            sort_name: row.get("sort_name"),
             slug: row.get("slug"),
+            lock_slug: row.get("lock_slug"),
             description: row.get("description"),
             primary_image: row.get("primary_image"),
             website: row.get("website"),
             label_id: row.get("label_id"),
             published_at: row.get("published_at"),
+            unlisted: row.get("unlisted"),
+            pinned_release_id: row.get("pinned_release_id"),
+            monthly_listeners: row.get("monthly_listeners"),
+            stats_updated_at: row.get("stats_updated_at"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             deleted_at: row.get("deleted_at"),
@@ -193,6 +365,7 @@ impl Artist {
     /// # Arguments
     /// * `pool` - The database connection pool
     /// * `slug` - The slug of the artist
+    /// * `include_deleted` - Whether to resolve soft-deleted artists (admin edit and restore flows)
     ///
     /// # Returns
     /// The artist
@@ -200,8 +373,18 @@ impl Artist {
     /// # Errors
     /// If the artist cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_slug(pool: &PgPool, slug: String) -> anyhow::Result<Self> {
-        let artist = sqlx::query_as::<_, Self>("SELECT * FROM artists WHERE slug = $1")
+    pub async fn get_by_slug(
+        pool: &PgPool,
+        slug: String,
+        include_deleted: bool,
+    ) -> Result<Self, ArtistError> {
+        let query = if include_deleted {
+            "SELECT * FROM artists WHERE slug = $1"
+        } else {
+            "SELECT * FROM artists WHERE slug = $1 AND deleted_at IS NULL"
+        };
+
+        let artist = sqlx::query_as::<_, Self>(query)
             .bind(slug.clone())
             .fetch_one(pool)
             .await;
@@ -210,7 +393,7 @@ impl Artist {
             Ok(row) => Ok(row),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not find artist with slug {}.", slug))
+                Err(ArtistError::NotFoundBySlug(slug))
             }
         }
     }
@@ -222,6 +405,7 @@ impl Artist {
     /// * `pool` - The database connection pool
     /// * `record_label_id` - The ID of the record label
     /// * `include_hidden` - Whether to include unreleased releases
+    /// * `only_with_releases` - Whether to exclude artists without a published release
     ///
     /// # Returns
     /// The artists
@@ -233,22 +417,179 @@ impl Artist {
         pool: &PgPool,
         record_label_id: i64,
         include_hidden: bool,
-    ) -> anyhow::Result<Vec<Self>> {
+        only_with_releases: bool,
+    ) -> Result<Vec<Self>, ArtistError> {
+        Self::list_filtered(
+            pool,
+            record_label_id,
+            include_hidden,
+            only_with_releases,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// List artist by record label, added or changed within a date window
+    /// This is used by admins to review recent editorial changes
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `include_hidden` - Whether to include unreleased releases
+    /// * `only_with_releases` - Whether to exclude artists without a published release
+    /// * `created_after` - Only include artists created at or after this time (optional)
+    /// * `updated_after` - Only include artists updated at or after this time (optional)
+    ///
+    /// # Returns
+    /// The artists, ordered as `list_by_record_label`
+    ///
+    /// # Errors
+    /// If there is an error getting the artists, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_filtered(
+        pool: &PgPool,
+        record_label_id: i64,
+        include_hidden: bool,
+        only_with_releases: bool,
+        created_after: Option<chrono::DateTime<chrono::Utc>>,
+        updated_after: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<Self>, ArtistError> {
+        let releases_filter = if only_with_releases {
+            format!(
+                "AND EXISTS (
+                SELECT 1 FROM release_artists
+                INNER JOIN releases ON releases.id = release_artists.release_id
+                WHERE release_artists.artist_id = artists.id
+                  AND {}
+                  AND {}
+              )",
+                published_clause("releases"),
+                release_visible_clause("releases")
+            )
+        } else {
+            String::new()
+        };
+        let date_filter = "AND ($2::TIMESTAMPTZ IS NULL OR artists.created_at >= $2)
+                  AND ($3::TIMESTAMPTZ IS NULL OR artists.updated_at >= $3)";
         let query = if include_hidden {
-            "SELECT artists.* FROM artists
-             WHERE artists.label_id = $1
-             ORDER BY deleted_at DESC, published_at DESC, name ASC"
+            format!(
+                "SELECT artists.* FROM artists
+                 WHERE artists.label_id = $1
+                  {releases_filter}
+                  {date_filter}
+                 ORDER BY deleted_at DESC, published_at DESC, sort_name_or_default(sort_name, name) ASC"
+            )
         } else {
-            "SELECT artists.* FROM artists
-             WHERE artists.label_id = $1
-              AND deleted_at IS NULL
-              AND published_at < NOW()
-              AND published_at IS NOT NULL
-             ORDER BY published_at DESC, name ASC"
+            format!(
+                "SELECT artists.* FROM artists
+                 WHERE artists.label_id = $1
+                  AND {}
+                  AND unlisted = FALSE
+                  {releases_filter}
+                  {date_filter}
+                 ORDER BY published_at DESC, sort_name_or_default(sort_name, name) ASC",
+                published_clause("")
+            )
         };
 
-        let artists = sqlx::query_as::<_, Self>(query)
+        let artists = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(created_after)
+            .bind(updated_after)
+            .fetch_all(pool)
+            .await;
+
+        match artists {
+            Ok(artists) => Ok(artists),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ArtistError::ListByRecordLabel(record_label_id))
+            }
+        }
+    }
+
+    /// List the artists on a record label that were most recently updated, including drafts
+    /// Deleted artists are excluded.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The artists, most recently updated first
+    ///
+    /// # Errors
+    /// If the artists cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_recently_updated(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, ArtistError> {
+        let artists = sqlx::query_as::<_, Self>(
+            "SELECT * FROM artists
+             WHERE label_id = $1
+              AND deleted_at IS NULL
+             ORDER BY updated_at DESC, id DESC",
+        )
+        .bind(record_label_id)
+        .fetch_all(pool)
+        .await;
+
+        match artists {
+            Ok(artists) => Ok(artists),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ArtistError::ListByRecordLabel(record_label_id))
+            }
+        }
+    }
+
+    /// Search the public artists on a record label by name
+    /// Exact matches come first, then prefix matches, then substring matches.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `query` - The search query
+    /// * `pagination` - The page of results to return
+    ///
+    /// # Returns
+    /// The matching artists
+    ///
+    /// # Errors
+    /// If the artists cannot be searched, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn search(
+        pool: &PgPool,
+        record_label_id: i64,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<Vec<Self>, ArtistError> {
+        let patterns = search_patterns(query);
+        let query = format!(
+            "SELECT * FROM artists
+             WHERE label_id = $1
+              AND name ILIKE $4
+              AND {}
+              AND unlisted = FALSE
+             ORDER BY CASE
+               WHEN name ILIKE $2 THEN 0
+               WHEN name ILIKE $3 THEN 1
+               ELSE 2
+              END,
+              sort_name_or_default(sort_name, name) ASC,
+              id ASC
+             LIMIT $5 OFFSET $6",
+            published_clause("")
+        );
+        let artists = sqlx::query_as::<_, Self>(&query)
             .bind(record_label_id)
+            .bind(patterns.exact)
+            .bind(patterns.prefix)
+            .bind(patterns.contains)
+            .bind(pagination.limit())
+            .bind(pagination.offset())
             .fetch_all(pool)
             .await;
 
@@ -256,15 +597,14 @@ impl Artist {
             Ok(artists) => Ok(artists),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find artists for record label with id {}.",
-                    record_label_id
-                ))
+                Err(ArtistError::Search(record_label_id))
             }
         }
     }
 
     /// Update an artist
+    /// Once the artist has been published its slug is kept, unless `lock_slug` is explicitly false.
+    /// Renaming an explicitly unlocked artist redirects the old slug to the new one.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
@@ -278,17 +618,39 @@ impl Artist {
     /// # Panics
     /// If the artist cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.slug = slugify(&self.name);
-        self.validate(pool).await?;
+    pub async fn update(mut self, pool: &PgPool) -> Result<Self, ArtistError> {
+        self.name = normalise_name(&self.name);
+        let stored = Self::get_by_id(pool, self.id).await.ok();
+        let old_slug = stored
+            .as_ref()
+            .map_or_else(|| self.slug.clone(), |artist| artist.slug.clone());
+        let stored_published_at = stored.and_then(|artist| artist.published_at);
+        if !is_slug_locked(self.lock_slug, &[stored_published_at, self.published_at]) {
+            self.slug = unique_slug_with(
+                pool,
+                SlugTable::Artists,
+                slugify(&self.name),
+                self.id,
+                slug_collision_strategy(),
+            )
+            .await
+            .map_err(|e| ArtistError::Invalid(e.to_string()))?;
+        }
+        self.validate(pool)
+            .await
+            .map_err(|e| ArtistError::Invalid(e.to_string()))?;
 
-        let artist = match sqlx::query_as::<_, Self>("UPDATE artists SET name = $1, slug = $2, description = $3, primary_image = $4, website = $5, published_at = $6, updated_at = $7, deleted_at = $8 WHERE id = $9 RETURNING *")
+        let artist = match sqlx::query_as::<_, Self>("UPDATE artists SET name = $1, sort_name = $2, slug = $3, lock_slug = $4, description = $5, primary_image = $6, website = $7, published_at = $8, unlisted = $9, pinned_release_id = $10, updated_at = $11, deleted_at = $12 WHERE id = $13 RETURNING *")
             .bind(self.name)
+            .bind(self.sort_name)
             .bind(self.slug)
+            .bind(self.lock_slug)
             .bind(self.description)
             .bind(self.primary_image)
             .bind(self.website)
             .bind(self.published_at)
+            .bind(self.unlisted)
+            .bind(self.pinned_release_id)
             .bind(chrono::Utc::now())
             .bind(self.deleted_at)
             .bind(self.id)
@@ -298,13 +660,208 @@ impl Artist {
             Ok(artist) => artist,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!("Could not update artist with id {}.", self.id));
+                return Err(ArtistError::Update(self.id));
             }
         };
+        if artist.lock_slug == Some(false) && artist.slug != old_slug {
+            SlugRedirect::create(pool, "artist", artist.id, &old_slug, &artist.slug)
+                .await
+                .map_err(|e| {
+                    tracing::error!("{e}");
+                    ArtistError::Redirect(old_slug)
+                })?;
+        }
 
         Ok(artist)
     }
 
+    /// Get the release pinned to the top of the artist's discography
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The pinned release, or None if no release is pinned
+    ///
+    /// # Errors
+    /// If the pinned release cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn pinned_release(&self, pool: &PgPool) -> Result<Option<Release>, ArtistError> {
+        match self.pinned_release_id {
+            Some(release_id) => Ok(Some(Release::get_by_id(pool, release_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Set the genres for the artist
+    /// Any existing genres are replaced, so an empty list clears them.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `genre_ids` - The IDs of the genres
+    ///
+    /// # Returns
+    /// The artist
+    ///
+    /// # Errors
+    /// If the genres cannot be set, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_genres(
+        &self,
+        pool: &PgPool,
+        mut genre_ids: Vec<i64>,
+    ) -> Result<Self, ArtistError> {
+        genre_ids.sort_unstable();
+        genre_ids.dedup();
+
+        let mut tx = pool.begin().await?;
+
+        // Delete all genres for the artist
+        sqlx::query("DELETE FROM artist_genres WHERE artist_id = $1")
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Insert the new genres
+        for genre_id in genre_ids {
+            match sqlx::query("INSERT INTO artist_genres (artist_id, genre_id) VALUES ($1, $2)")
+                .bind(self.id)
+                .bind(genre_id)
+                .execute(&mut *tx)
+                .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ArtistError::SetGenres(self.id));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(self.clone())
+    }
+
+    /// Get the genres for the artist
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The genres for the artist, by name
+    ///
+    /// # Errors
+    /// If the genres cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_genres(&self, pool: &PgPool) -> Result<Vec<Genre>, ArtistError> {
+        let genres = sqlx::query_as::<_, Genre>(
+            "SELECT genres.* FROM genres
+             INNER JOIN artist_genres ON genres.id = artist_genres.genre_id
+             WHERE artist_genres.artist_id = $1
+             ORDER BY genres.name ASC, genres.id ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match genres {
+            Ok(genres) => Ok(genres),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ArtistError::Genres(self.id))
+            }
+        }
+    }
+
+    /// Update the external streaming stats for an artist
+    /// The stats updated timestamp is always set alongside the listener count.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `monthly_listeners` - The number of monthly listeners
+    ///
+    /// # Returns
+    /// The updated artist
+    ///
+    /// # Errors
+    /// If the monthly listeners is negative, return an error
+    /// If the artist cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn update_stats(
+        &self,
+        pool: &PgPool,
+        monthly_listeners: i32,
+    ) -> Result<Self, ArtistError> {
+        if monthly_listeners < 0 {
+            return Err(ArtistError::Invalid(
+                "Monthly listeners must be 0 or greater.".to_string(),
+            ));
+        }
+
+        let artist = sqlx::query_as::<_, Self>(
+            "UPDATE artists SET monthly_listeners = $1, stats_updated_at = $2 WHERE id = $3 RETURNING *",
+        )
+        .bind(monthly_listeners)
+        .bind(chrono::Utc::now())
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match artist {
+            Ok(artist) => Ok(artist),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ArtistError::UpdateStats(self.id))
+            }
+        }
+    }
+
+    /// Count what deleting the artist would affect, without changing anything
+    /// Deleted releases and tracks are not counted.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The number of releases the artist is the primary artist on,
+    /// the number of releases the artist is only featured on,
+    /// and the number of tracks the artist is credited on
+    ///
+    /// # Errors
+    /// If the releases or tracks cannot be counted, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn delete_impact_counts(
+        &self,
+        pool: &PgPool,
+    ) -> Result<(i64, i64, i64), ArtistError> {
+        let counts = sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT
+                (SELECT COUNT(*) FROM releases
+                 WHERE primary_artist_id = $1 AND deleted_at IS NULL),
+                (SELECT COUNT(*) FROM releases
+                 INNER JOIN release_artists ON releases.id = release_artists.release_id
+                 WHERE release_artists.artist_id = $1
+                  AND releases.primary_artist_id != $1
+                  AND releases.deleted_at IS NULL),
+                (SELECT COUNT(*) FROM tracks
+                 WHERE deleted_at IS NULL
+                  AND (primary_artist_id = $1
+                   OR EXISTS (SELECT 1 FROM track_artists WHERE track_artists.track_id = tracks.id AND track_artists.artist_id = $1)))",
+        )
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match counts {
+            Ok(counts) => Ok(counts),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ArtistError::DeleteImpact(self.id))
+            }
+        }
+    }
+
     /// Delete an artist
     /// This is a soft delete
     ///
@@ -317,7 +874,7 @@ impl Artist {
     /// # Errors
     /// If the artist cannot be deleted, return an error
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn delete(&self, pool: &PgPool) -> Result<Self, ArtistError> {
         let artist = sqlx::query_as::<_, Self>(
             "UPDATE artists SET deleted_at = $1 WHERE id = $2 RETURNING *",
         )
@@ -330,10 +887,7 @@ impl Artist {
             Ok(artist) => Ok(artist),
             Err(e) => {
                 eprintln!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not delete artist with id {}.",
-                    self.id
-                ))
+                Err(ArtistError::Delete(self.id))
             }
         }
     }
@@ -343,7 +897,76 @@ impl Artist {
 mod tests {
     use super::*;
     #[cfg(feature = "ssr")]
-    use crate::models::test_helpers::{create_test_artist, create_test_record_label};
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release,
+    };
+
+    #[test]
+    fn test_artist_error_display() {
+        let cases = [
+            (
+                ArtistError::Invalid("Name is required.".to_string()),
+                "Name is required.",
+            ),
+            (
+                ArtistError::NotFoundById(1),
+                "Could not find artist with id 1.",
+            ),
+            (
+                ArtistError::NotFoundBySlug("missing".to_string()),
+                "Could not find artist with slug missing.",
+            ),
+            (
+                ArtistError::ListByRecordLabel(3),
+                "Could not find artists for record label with id 3.",
+            ),
+            (
+                ArtistError::Search(3),
+                "Could not search artists for record label with id 3.",
+            ),
+            (ArtistError::Update(1), "Could not update artist with id 1."),
+            (
+                ArtistError::Redirect("old".to_string()),
+                "Could not redirect artist slug old.",
+            ),
+            (
+                ArtistError::PinnedRelease(ReleaseError::NotFoundById(2)),
+                "Could not find release with id 2.",
+            ),
+            (
+                ArtistError::SetGenres(1),
+                "Could not set genres for artist with id 1.",
+            ),
+            (
+                ArtistError::Genres(1),
+                "Could not find genres for artist with id 1.",
+            ),
+            (
+                ArtistError::UpdateStats(1),
+                "Could not update stats for artist with id 1.",
+            ),
+            (
+                ArtistError::DeleteImpact(1),
+                "Could not count releases and tracks for artist with id 1.",
+            ),
+            (ArtistError::Delete(1), "Could not delete artist with id 1."),
+            (ArtistError::Database, "Could not access artists."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_artist_error_hides_database_error() {
+        let error = ArtistError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, ArtistError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
 
     #[test]
     fn test_init_artist() {
@@ -351,11 +974,17 @@ mod tests {
             id: 1,
             name: "Test Artist".to_string(),
             slug: "test-artist".to_string(),
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: 1,
             published_at: None,
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -375,11 +1004,17 @@ mod tests {
             id: 1,
             name: "Test Artist".to_string(),
             slug: "test-artist".to_string(),
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: record_label.id,
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -396,11 +1031,17 @@ mod tests {
             id: 1,
             name: String::new(),
             slug: "test-artist".to_string(),
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: 1,
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -422,11 +1063,17 @@ mod tests {
             id: 1,
             name,
             slug: "test-artist".to_string(),
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: 1,
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -448,11 +1095,17 @@ mod tests {
             id: 1,
             name: "Test Artist".to_string(),
             slug,
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: 1,
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -489,11 +1142,17 @@ mod tests {
             id: 1,
             name: "Test Artist".to_string(),
             slug: "test-artist".to_string(),
+            lock_slug: None,
+            sort_name: None,
             description: "This is a test artist".to_string(),
             primary_image: None,
             website: "https://example.com".to_string(),
             label_id: 1,
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
+            monthly_listeners: None,
+            stats_updated_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -526,6 +1185,101 @@ mod tests {
         assert_eq!(artist.description, "This is a test artist".to_string());
     }
 
+    #[sqlx::test]
+    async fn test_create_normalises_name(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = Artist::create(
+            &pool,
+            "Sigur R\u{f3}s".to_string(),
+            String::new(),
+            String::new(),
+            record_label.id,
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        let other_artist = Artist::create(
+            &pool,
+            "Sigur Ro\u{301}s".to_string(),
+            String::new(),
+            String::new(),
+            record_label.id,
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(artist.name, "Sigur R\u{f3}s".to_string());
+        assert_eq!(artist.slug, "sigur-ros".to_string());
+        // The decomposed name has the same slug, so the second artist gets a suffix
+        assert_eq!(other_artist.slug, "sigur-ros-2".to_string());
+    }
+
+    #[sqlx::test]
+    async fn test_update_normalises_name(pool: PgPool) {
+        let mut artist = create_test_artist(&pool, 1, None).await.unwrap();
+        artist.lock_slug = Some(false);
+        artist.name = "Sigur Ro\u{301}s".to_string();
+
+        let artist = artist.update(&pool).await.unwrap();
+
+        assert_eq!(artist.name, "Sigur R\u{f3}s".to_string());
+        assert_eq!(artist.slug, "sigur-ros".to_string());
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_duplicate_names(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let mut slugs = vec![];
+        for _ in 0..3 {
+            let artist = Artist::create(
+                &pool,
+                "Live".to_string(),
+                String::new(),
+                String::new(),
+                record_label.id,
+                None,
+            )
+            .await
+            .unwrap();
+            slugs.push(artist.slug);
+        }
+
+        assert_eq!(slugs, vec!["live", "live-2", "live-3"]);
+    }
+
+    #[sqlx::test]
+    async fn test_set_genres(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let techno = Genre::create(&pool, "Techno").await.unwrap();
+        let ambient = Genre::create(&pool, "Ambient").await.unwrap();
+
+        artist
+            .set_genres(&pool, vec![techno.id, ambient.id])
+            .await
+            .unwrap();
+        assert_eq!(
+            artist.get_genres(&pool).await.unwrap(),
+            vec![ambient, techno.clone()]
+        );
+
+        artist.set_genres(&pool, vec![techno.id]).await.unwrap();
+        assert_eq!(artist.get_genres(&pool).await.unwrap(), vec![techno]);
+    }
+
+    #[sqlx::test]
+    async fn test_set_genres_not_found(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let result = artist.set_genres(&pool, vec![999]).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("Could not set genres for artist with id {}.", artist.id)
+        );
+    }
+
     #[sqlx::test]
     async fn test_create_with_validation_error(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
@@ -568,7 +1322,7 @@ mod tests {
     #[sqlx::test]
     async fn test_get_by_slug(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let artist_by_slug = Artist::get_by_slug(&pool, artist.slug.clone())
+        let artist_by_slug = Artist::get_by_slug(&pool, artist.slug.clone(), false)
             .await
             .unwrap();
 
@@ -577,7 +1331,7 @@ mod tests {
 
     #[sqlx::test]
     async fn test_get_by_slug_not_found(pool: PgPool) {
-        let artist = Artist::get_by_slug(&pool, "missing".to_string()).await;
+        let artist = Artist::get_by_slug(&pool, "missing".to_string(), false).await;
 
         assert!(artist.is_err());
         assert_eq!(
@@ -586,6 +1340,57 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_search_ranks_prefix_match_above_mid_string_match(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let mut mid_string = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        mid_string.name = "A Zebra".to_string();
+        let mid_string = mid_string.update(&pool).await.unwrap();
+        let mut prefix = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        prefix.name = "Zebra Crossing".to_string();
+        let prefix = prefix.update(&pool).await.unwrap();
+        let mut unmatched = create_test_artist(&pool, 3, Some(record_label.clone()))
+            .await
+            .unwrap();
+        unmatched.name = "Horse".to_string();
+        unmatched.update(&pool).await.unwrap();
+
+        let artists = Artist::search(&pool, prefix.label_id, "zebra", Pagination::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            artists.iter().map(|a| a.id).collect::<Vec<i64>>(),
+            vec![prefix.id, mid_string.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_search_excludes_hidden_artists(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut unlisted = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        unlisted.unlisted = true;
+        unlisted.update(&pool).await.unwrap();
+
+        let artists = Artist::search(&pool, artist.label_id, "Test Artist", Pagination::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            artists.iter().map(|a| a.id).collect::<Vec<i64>>(),
+            vec![artist.id]
+        );
+    }
+
     #[sqlx::test]
     async fn test_list_by_record_label(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
@@ -596,7 +1401,7 @@ mod tests {
             .await
             .unwrap();
 
-        let artists = Artist::list_by_record_label(&pool, record_label.id, false)
+        let artists = Artist::list_by_record_label(&pool, record_label.id, false, false)
             .await
             .unwrap();
 
@@ -619,7 +1424,7 @@ mod tests {
         artist2.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1)); // Future date to simulate hidden artist
         artist2 = artist2.clone().update(&pool).await.unwrap();
 
-        let artists = Artist::list_by_record_label(&pool, record_label.id, true)
+        let artists = Artist::list_by_record_label(&pool, record_label.id, true, false)
             .await
             .unwrap();
 
@@ -628,6 +1433,204 @@ mod tests {
         assert!(artists.contains(&artist2));
     }
 
+    #[sqlx::test]
+    async fn test_list_by_record_label_only_with_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist_with_release = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        create_test_release(&pool, 1, Some(artist_with_release.clone()))
+            .await
+            .unwrap();
+        let artist_with_draft = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut draft_release = create_test_release(&pool, 2, Some(artist_with_draft.clone()))
+            .await
+            .unwrap();
+        draft_release.published_at = None;
+        draft_release.update(&pool).await.unwrap();
+
+        let artists = Artist::list_by_record_label(&pool, record_label.id, false, true)
+            .await
+            .unwrap();
+        assert_eq!(artists, vec![artist_with_release.clone()]);
+
+        let artists = Artist::list_by_record_label(&pool, record_label.id, false, false)
+            .await
+            .unwrap();
+        assert_eq!(artists.len(), 2);
+        assert!(artists.contains(&artist_with_draft));
+    }
+
+    /// Create three artists, updated one, two and three days ago, and created ten days before that
+    async fn create_dated_artists(pool: &PgPool, record_label: &RecordLabel) -> Vec<Artist> {
+        let now = chrono::Utc::now();
+        let mut artists = Vec::new();
+        for id in 1..=3 {
+            let artist = create_test_artist(pool, id, Some(record_label.clone()))
+                .await
+                .unwrap();
+            let days = chrono::Duration::days(i64::try_from(id).unwrap());
+            sqlx::query("UPDATE artists SET created_at = $1, updated_at = $2 WHERE id = $3")
+                .bind(now - days - chrono::Duration::days(10))
+                .bind(now - days)
+                .bind(artist.id)
+                .execute(pool)
+                .await
+                .unwrap();
+            artists.push(Artist::get_by_id(pool, artist.id).await.unwrap());
+        }
+        artists
+    }
+
+    #[sqlx::test]
+    async fn test_list_filtered_created_after(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artists = create_dated_artists(&pool, &record_label).await;
+
+        // The boundary is inclusive
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            false,
+            false,
+            Some(artists[1].created_at),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.contains(&artists[0]));
+        assert!(filtered.contains(&artists[1]));
+
+        let filtered = Artist::list_filtered(&pool, record_label.id, false, false, None, None)
+            .await
+            .unwrap();
+        assert_eq!(filtered.len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_list_filtered_updated_after(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artists = create_dated_artists(&pool, &record_label).await;
+
+        // The boundary is inclusive
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            false,
+            false,
+            None,
+            Some(artists[0].updated_at),
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered, vec![artists[0].clone()]);
+
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            false,
+            false,
+            Some(artists[2].created_at),
+            Some(artists[1].updated_at),
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.len(), 2);
+        assert!(!filtered.contains(&artists[2]));
+    }
+
+    #[sqlx::test]
+    async fn test_list_filtered_with_hidden(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artists = create_dated_artists(&pool, &record_label).await;
+        artists[0].delete(&pool).await.unwrap();
+
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            false,
+            false,
+            None,
+            Some(artists[1].updated_at),
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered, vec![artists[1].clone()]);
+
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            true,
+            false,
+            None,
+            Some(artists[1].updated_at),
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().any(|artist| artist.id == artists[0].id));
+    }
+
+    #[sqlx::test]
+    async fn test_list_filtered_only_with_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artists = create_dated_artists(&pool, &record_label).await;
+        create_test_release(&pool, 1, Some(artists[0].clone()))
+            .await
+            .unwrap();
+        create_test_release(&pool, 2, Some(artists[2].clone()))
+            .await
+            .unwrap();
+
+        let filtered = Artist::list_filtered(
+            &pool,
+            record_label.id,
+            false,
+            true,
+            Some(artists[1].created_at),
+            None,
+        )
+        .await
+        .unwrap();
+        let ids = filtered.iter().map(|artist| artist.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![artists[0].id]);
+    }
+
+    #[sqlx::test]
+    async fn test_list_by_record_label_excludes_unlisted(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let mut unlisted_artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        unlisted_artist.unlisted = true;
+        let unlisted_artist = unlisted_artist.update(&pool).await.unwrap();
+        let listed_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let artists = Artist::list_by_record_label(&pool, record_label.id, false, false)
+            .await
+            .unwrap();
+        assert_eq!(artists, vec![listed_artist]);
+
+        let artists = record_label.clone().artists(&pool, false).await.unwrap();
+        assert_eq!(artists.len(), 1);
+
+        let artists = Artist::list_by_record_label(&pool, record_label.id, true, false)
+            .await
+            .unwrap();
+        assert_eq!(artists.len(), 2);
+
+        // Unlisted artists can still be reached directly
+        let artist = Artist::get_by_slug(&pool, unlisted_artist.slug.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(artist, unlisted_artist);
+    }
+
     #[sqlx::test]
     async fn test_update(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
@@ -639,7 +1642,7 @@ mod tests {
 
         let updated_artist = update_artist.update(&pool).await.unwrap();
         assert_eq!(updated_artist.name, "Updated Artist".to_string());
-        assert_eq!(updated_artist.slug, "updated-artist".to_string());
+        assert_eq!(updated_artist.slug, artist.slug);
         assert_eq!(
             updated_artist.description,
             "This is an updated artist".to_string()
@@ -652,6 +1655,42 @@ mod tests {
         assert_ne!(updated_artist.updated_at, artist.updated_at);
     }
 
+    #[sqlx::test]
+    async fn test_update_pinned_release(pool: PgPool) {
+        let mut artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        artist.pinned_release_id = Some(release.id);
+        let artist = artist.update(&pool).await.unwrap();
+
+        assert_eq!(artist.pinned_release_id, Some(release.id));
+        let pinned_release = artist.pinned_release(&pool).await.unwrap();
+        assert_eq!(pinned_release.map(|release| release.id), Some(release.id));
+    }
+
+    #[sqlx::test]
+    async fn test_update_pinned_release_by_another_artist(pool: PgPool) {
+        let mut artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let other_release = create_test_release(&pool, 2, None).await.unwrap();
+
+        artist.pinned_release_id = Some(other_release.id);
+        let result = artist.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The pinned release must be by the artist."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_pinned_release_none(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        assert!(artist.pinned_release(&pool).await.unwrap().is_none());
+    }
+
     #[sqlx::test]
     async fn test_update_validation_error(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
@@ -685,18 +1724,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sort_name_or_default() {
+        let mut artist = Artist {
+            name: "The Beatles".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(artist.sort_name_or_default(), "Beatles");
+
+        artist.sort_name = Some("Beatles, The".to_string());
+        assert_eq!(artist.sort_name_or_default(), "Beatles, The");
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let mut artist = Artist {
+            description: "## Biography\n\nA *five piece* band from Leeds, playing loud guitar music since 2009.".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(artist.excerpt(30), "Biography A five piece band…");
+
+        artist.description = "A band from **Leeds**.".to_string();
+        assert_eq!(artist.excerpt(30), "A band from Leeds.");
+    }
+
     #[sqlx::test]
     async fn test_primary_image_url(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let url = artist.primary_image_url();
-        assert_eq!(url, "/Logo.svg");
+        let placeholders = PlaceholderImages {
+            artist: "/images/silhouette.svg".to_string(),
+            ..Default::default()
+        };
+        let url = artist.primary_image_url(&placeholders);
+        assert_eq!(url, "/images/silhouette.svg");
     }
 
     #[sqlx::test]
     async fn test_primary_image_url_with_custom_image(pool: PgPool) {
         let mut artist = create_test_artist(&pool, 1, None).await.unwrap();
         artist.primary_image = Some("custom-image.jpg".to_string());
-        let url = artist.primary_image_url();
+        let url = artist.primary_image_url(&PlaceholderImages::default());
         assert_eq!(url, "/uploads/artists/custom-image.jpg");
     }
 }
@@ -6,6 +6,56 @@ use sqlx::PgPool;
 use std::collections::HashSet;
 
 use super::traits::Validate;
+use crate::config::image::{PlaceholderImage, PlaceholderImages};
+use crate::utils::files::upload_url_or;
+
+/// The permission tokens that can be granted to users.
+/// Grants of any other token have no effect.
+pub const PERMISSION_TOKENS: [&str; 2] = ["admin", "label_owner"];
+
+/// Errors returned by the user model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum UserError {
+    /// The user is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// No user has the given username or email
+    #[error("User not found.")]
+    NotFound,
+    /// The login time could not be recorded
+    #[error("Could not record login for user with id {0}.")]
+    RecordLogin(i64),
+    /// The user could not be assigned to manage the artist
+    #[error("Could not assign user with id {user_id} to artist with id {artist_id}.")]
+    AddManagedArtist {
+        /// The ID of the user
+        user_id: i64,
+        /// The ID of the artist
+        artist_id: i64,
+    },
+    /// The user could not be removed as a manager of the artist
+    #[error("Could not remove user with id {user_id} from artist with id {artist_id}.")]
+    RemoveManagedArtist {
+        /// The ID of the user
+        user_id: i64,
+        /// The ID of the artist
+        artist_id: i64,
+    },
+    /// The managers of the artist could not be listed
+    #[error("Could not list the managers of artist with id {0}.")]
+    ListArtistManagers(i64),
+    /// Any other database error
+    #[error("Could not access users.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for UserError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
 
 /// Represents a user in the system.
 ///
@@ -23,6 +73,10 @@ pub struct User {
     pub last_name: Option<String>,
     /// Email address of the user.
     pub email: String,
+    /// When the user verified their email address, if they have.
+    pub email_verified_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the user last logged in, if they have.
+    pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Optional description of the user - useful for user profiles.
     pub description: Option<String>,
     /// Optional avatar URL of the user - can be used to display a profile picture.
@@ -52,6 +106,8 @@ impl Default for User {
             first_name: None,
             last_name: None,
             email: "hello@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: None,
             permissions,
@@ -118,11 +174,18 @@ impl User {
         self.id == -1
     }
 
+    /// Check if the user has verified their email address.
+    pub const fn is_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
     /// Get the avatar URL of the user.
-    pub fn avatar_url(&self) -> String {
-        self.avatar.clone().map_or_else(
-            || "/Logo.svg".to_string(),
-            |file| format!("/uploads/avatars/{file}"),
+    /// If there is no avatar, return the avatar placeholder.
+    pub fn avatar_url(&self, placeholders: &PlaceholderImages) -> String {
+        upload_url_or(
+            "avatars",
+            self.avatar.as_deref(),
+            placeholders.url(PlaceholderImage::Avatar),
         )
     }
 
@@ -138,10 +201,10 @@ impl User {
     /// # Errors
     /// If the user cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_username(pool: &PgPool, username: String) -> anyhow::Result<Self> {
+    pub async fn get_by_username(pool: &PgPool, username: String) -> Result<Self, UserError> {
         Self::get_from_username(username, pool)
             .await
-            .map_or_else(|| Err(anyhow::anyhow!("User not found.")), Ok)
+            .ok_or(UserError::NotFound)
     }
 
     /// Get user by email
@@ -156,10 +219,10 @@ impl User {
     /// # Errors
     /// If the user cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_email(pool: &PgPool, email: String) -> anyhow::Result<Self> {
+    pub async fn get_by_email(pool: &PgPool, email: String) -> Result<Self, UserError> {
         Self::get_from_email(email, pool)
             .await
-            .map_or_else(|| Err(anyhow::anyhow!("User not found.")), Ok)
+            .ok_or(UserError::NotFound)
     }
 
     /// Update an user
@@ -176,8 +239,10 @@ impl User {
     /// # Panics
     /// If the user cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.validate(pool).await?;
+    pub async fn update(mut self, pool: &PgPool) -> Result<Self, UserError> {
+        self.validate(pool)
+            .await
+            .map_err(|e| UserError::Invalid(e.to_string()))?;
         tracing::info!("Updating user: {:?}", self);
         self.updated_at = chrono::Utc::now();
         sqlx::query(
@@ -196,12 +261,43 @@ impl User {
 
         Ok(self)
     }
+
+    /// Record that the user has logged in
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The user, with `last_login_at` set to now
+    ///
+    /// # Errors
+    /// If the user cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn record_login(mut self, pool: &PgPool) -> Result<Self, UserError> {
+        let last_login_at = sqlx::query_scalar::<_, chrono::DateTime<chrono::Utc>>(
+            "UPDATE users SET last_login_at = NOW() WHERE id = $1 RETURNING last_login_at",
+        )
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match last_login_at {
+            Ok(last_login_at) => {
+                self.last_login_at = Some(last_login_at);
+                Ok(self)
+            }
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(UserError::RecordLogin(self.id))
+            }
+        }
+    }
 }
 
 /// This module contains auth models that are only compiled when the `ssr` feature is enabled.
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    pub use super::{User, UserPasshash};
+    pub use super::{User, UserError, UserPasshash};
     pub use async_trait::async_trait;
     pub use axum_session_auth::Authentication;
     use axum_session_sqlx::SessionPgPool;
@@ -342,6 +438,143 @@ pub mod ssr {
                 .await
                 .map(|(user, _)| user)
         }
+
+        /// Can the user manage an artist, and their releases, tracks and links
+        /// Admins and label owners can manage every artist, other users only the artists they have been assigned.
+        ///
+        /// # Arguments
+        /// * `pool` - The database connection pool
+        /// * `artist_id` - The ID of the artist
+        ///
+        /// # Returns
+        /// True if the user can manage the artist
+        pub async fn can_manage_artist(&self, pool: &PgPool, artist_id: i64) -> bool {
+            if !self.is_authenticated() {
+                return false;
+            }
+            if self.permissions.contains("admin") || self.permissions.contains("label_owner") {
+                return true;
+            }
+
+            let managed = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM artist_managers WHERE user_id = $1 AND artist_id = $2)",
+            )
+            .bind(self.id)
+            .bind(artist_id)
+            .fetch_one(pool)
+            .await;
+
+            match managed {
+                Ok(managed) => managed,
+                Err(e) => {
+                    tracing::error!("{e}");
+                    false
+                }
+            }
+        }
+
+        /// Assign the user as a manager of an artist
+        ///
+        /// # Arguments
+        /// * `pool` - The database connection pool
+        /// * `artist_id` - The ID of the artist
+        ///
+        /// # Errors
+        /// If the user cannot be assigned to the artist, return an error
+        pub async fn add_managed_artist(
+            &self,
+            pool: &PgPool,
+            artist_id: i64,
+        ) -> Result<(), UserError> {
+            let result = sqlx::query(
+                "INSERT INTO artist_managers (user_id, artist_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            )
+            .bind(self.id)
+            .bind(artist_id)
+            .execute(pool)
+            .await;
+
+            match result {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    Err(UserError::AddManagedArtist {
+                        user_id: self.id,
+                        artist_id,
+                    })
+                }
+            }
+        }
+
+        /// Stop the user managing an artist
+        ///
+        /// # Arguments
+        /// * `pool` - The database connection pool
+        /// * `artist_id` - The ID of the artist
+        ///
+        /// # Errors
+        /// If the user cannot be removed from the artist, return an error
+        pub async fn remove_managed_artist(
+            &self,
+            pool: &PgPool,
+            artist_id: i64,
+        ) -> Result<(), UserError> {
+            let result =
+                sqlx::query("DELETE FROM artist_managers WHERE user_id = $1 AND artist_id = $2")
+                    .bind(self.id)
+                    .bind(artist_id)
+                    .execute(pool)
+                    .await;
+
+            match result {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    Err(UserError::RemoveManagedArtist {
+                        user_id: self.id,
+                        artist_id,
+                    })
+                }
+            }
+        }
+
+        /// List the users assigned to manage an artist
+        /// Admins and label owners can manage every artist, but are only listed if they have been assigned.
+        ///
+        /// # Arguments
+        /// * `pool` - The database connection pool
+        /// * `artist_id` - The ID of the artist
+        ///
+        /// # Returns
+        /// The managers, ordered by username
+        ///
+        /// # Errors
+        /// If the managers cannot be listed, return an error
+        pub async fn list_artist_managers(
+            pool: &PgPool,
+            artist_id: i64,
+        ) -> Result<Vec<Self>, UserError> {
+            let sql_users = sqlx::query_as::<_, SqlUser>(
+                "SELECT users.* FROM users
+                 INNER JOIN artist_managers ON artist_managers.user_id = users.id
+                 WHERE artist_managers.artist_id = $1
+                 ORDER BY users.username ASC",
+            )
+            .bind(artist_id)
+            .fetch_all(pool)
+            .await;
+
+            match sql_users {
+                Ok(sql_users) => Ok(sql_users
+                    .into_iter()
+                    .map(|sql_user| sql_user.into_user(None).0)
+                    .collect()),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    Err(UserError::ListArtistManagers(artist_id))
+                }
+            }
+        }
     }
 
     /// Represents a permission token for a user.
@@ -394,6 +627,10 @@ pub mod ssr {
         pub last_name: Option<String>,
         /// Email address of the user.
         pub email: String,
+        /// When the user verified their email address, if they have.
+        pub email_verified_at: Option<chrono::DateTime<chrono::Utc>>,
+        /// When the user last logged in, if they have.
+        pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
         /// Optional description of the user - useful for user profiles.
         pub description: Option<String>,
         /// Optional avatar URL of the user - can be used to display a profile picture.
@@ -425,6 +662,8 @@ pub mod ssr {
                     first_name: self.first_name,
                     last_name: self.last_name,
                     email: self.email,
+                    email_verified_at: self.email_verified_at,
+                    last_login_at: self.last_login_at,
                     description: self.description,
                     avatar: self.avatar,
                     permissions: sql_user_perms.map_or_else(HashSet::<String>::new, |user_perms| {
@@ -448,6 +687,54 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_user_error_display() {
+        let cases = [
+            (
+                UserError::Invalid("Username is required.".to_string()),
+                "Username is required.",
+            ),
+            (UserError::NotFound, "User not found."),
+            (
+                UserError::RecordLogin(1),
+                "Could not record login for user with id 1.",
+            ),
+            (
+                UserError::AddManagedArtist {
+                    user_id: 1,
+                    artist_id: 2,
+                },
+                "Could not assign user with id 1 to artist with id 2.",
+            ),
+            (
+                UserError::RemoveManagedArtist {
+                    user_id: 1,
+                    artist_id: 2,
+                },
+                "Could not remove user with id 1 from artist with id 2.",
+            ),
+            (
+                UserError::ListArtistManagers(2),
+                "Could not list the managers of artist with id 2.",
+            ),
+            (UserError::Database, "Could not access users."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_user_error_hides_database_error() {
+        let error = UserError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, UserError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
     #[test]
     fn test_user_default() {
         let user = User::default();
@@ -559,6 +846,8 @@ mod tests {
             first_name: None,
             last_name: None,
             email: "test@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: None,
             permissions: HashSet::new(),
@@ -579,6 +868,8 @@ mod tests {
             first_name: None,
             last_name: None,
             email: "test@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: None,
             permissions: HashSet::new(),
@@ -599,6 +890,8 @@ mod tests {
             first_name: None,
             last_name: None,
             email: "test@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: None,
             permissions: HashSet::new(),
@@ -616,7 +909,79 @@ mod tests {
         use sqlx::PgPool;
         use std::collections::HashSet;
 
-        use crate::models::test_helpers::create_test_user;
+        use crate::models::test_helpers::{
+            create_test_artist, create_test_user, create_test_user_with_permissions,
+        };
+
+        #[sqlx::test]
+        async fn test_can_manage_artist(pool: PgPool) {
+            let artist = create_test_artist(&pool, 1, None).await.unwrap();
+            let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+            let manager = create_test_user_with_permissions(&pool, 1, vec![])
+                .await
+                .unwrap();
+
+            assert!(!manager.can_manage_artist(&pool, artist.id).await);
+            manager.add_managed_artist(&pool, artist.id).await.unwrap();
+            // Assigning twice is harmless
+            manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+            assert!(manager.can_manage_artist(&pool, artist.id).await);
+            assert!(!manager.can_manage_artist(&pool, other_artist.id).await);
+        }
+
+        #[sqlx::test]
+        async fn test_remove_managed_artist(pool: PgPool) {
+            let artist = create_test_artist(&pool, 1, None).await.unwrap();
+            let manager = create_test_user_with_permissions(&pool, 1, vec![])
+                .await
+                .unwrap();
+            manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+            manager
+                .remove_managed_artist(&pool, artist.id)
+                .await
+                .unwrap();
+
+            assert!(!manager.can_manage_artist(&pool, artist.id).await);
+        }
+
+        #[sqlx::test]
+        async fn test_list_artist_managers(pool: PgPool) {
+            let artist = create_test_artist(&pool, 1, None).await.unwrap();
+            let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+            let manager = create_test_user_with_permissions(&pool, 1, vec![])
+                .await
+                .unwrap();
+            let other_manager = create_test_user_with_permissions(&pool, 2, vec![])
+                .await
+                .unwrap();
+            manager.add_managed_artist(&pool, artist.id).await.unwrap();
+            other_manager
+                .add_managed_artist(&pool, other_artist.id)
+                .await
+                .unwrap();
+
+            let managers = User::list_artist_managers(&pool, artist.id).await.unwrap();
+
+            assert_eq!(managers.len(), 1);
+            assert_eq!(managers[0].id, manager.id);
+        }
+
+        #[sqlx::test]
+        async fn test_can_manage_artist_permissions(pool: PgPool) {
+            let artist = create_test_artist(&pool, 1, None).await.unwrap();
+            let admin = create_test_user_with_permissions(&pool, 1, vec!["admin"])
+                .await
+                .unwrap();
+            let label_owner = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+                .await
+                .unwrap();
+
+            assert!(admin.can_manage_artist(&pool, artist.id).await);
+            assert!(label_owner.can_manage_artist(&pool, artist.id).await);
+            assert!(!User::default().can_manage_artist(&pool, artist.id).await);
+        }
 
         #[sqlx::test]
         async fn test_get_by_username(pool: PgPool) {
@@ -730,6 +1095,8 @@ mod tests {
                 first_name: None,
                 last_name: None,
                 email: "test@example.com".into(),
+                email_verified_at: None,
+                last_login_at: None,
                 description: None,
                 avatar: None,
                 permissions: HashSet::new(),
@@ -740,6 +1107,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_user_is_verified() {
+        let mut user = User::default();
+        assert!(!user.is_verified());
+
+        user.email_verified_at = Some(chrono::Utc::now());
+        assert!(user.is_verified());
+    }
+
     #[test]
     fn test_avatar_url() {
         let test_user = User {
@@ -748,14 +1124,20 @@ mod tests {
             first_name: None,
             last_name: None,
             email: "test@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: None,
             permissions: HashSet::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        let url = test_user.avatar_url();
-        assert_eq!(url, "/Logo.svg");
+        let placeholders = PlaceholderImages {
+            avatar: "/images/avatar.svg".to_string(),
+            ..Default::default()
+        };
+        let url = test_user.avatar_url(&placeholders);
+        assert_eq!(url, "/images/avatar.svg");
     }
 
     #[test]
@@ -766,13 +1148,15 @@ mod tests {
             first_name: None,
             last_name: None,
             email: "test@example.com".into(),
+            email_verified_at: None,
+            last_login_at: None,
             description: None,
             avatar: Some("custom-image.jpg".to_string()),
             permissions: HashSet::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        let url = test_user.avatar_url();
+        let url = test_user.avatar_url(&PlaceholderImages::default());
         assert_eq!(url, "/uploads/avatars/custom-image.jpg");
     }
 }
@@ -0,0 +1,284 @@
+//! Email verification model
+//!
+//! The `EmailVerificationToken` struct is used to confirm a user owns the email address they registered with.
+//! Only a hash of the token is stored.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+#[cfg(feature = "ssr")]
+use crate::config::auth::EMAIL_VERIFICATION_EXPIRY_HOURS;
+#[cfg(feature = "ssr")]
+use crate::utils::token::{generate_token, hash_token};
+
+/// Errors returned by the email verification model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum EmailVerificationError {
+    /// The verification token could not be created
+    #[error("Could not create verification token for user with id {0}.")]
+    Create(i64),
+    /// No verification token matches
+    #[error("Invalid verification token.")]
+    InvalidToken,
+    /// The verification token has expired
+    #[error("Verification token has expired.")]
+    Expired,
+    /// The user's email address could not be marked as verified
+    #[error("Could not verify email for user with id {0}.")]
+    Verify(i64),
+    /// The user's verification tokens could not be removed
+    #[error("Could not remove verification tokens for user with id {0}.")]
+    RemoveTokens(i64),
+    /// Any other database error
+    #[error("Could not access email verification tokens.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for EmailVerificationError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
+/// The `EmailVerificationToken` struct is used to represent an email verification token in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct EmailVerificationToken {
+    /// The SHA-256 hash of the token sent to the user
+    pub token_hash: String,
+    /// The user the token belongs to
+    pub user_id: i64,
+    /// The date and time the token was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the token expires
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl EmailVerificationToken {
+    /// Has the token expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < chrono::Utc::now()
+    }
+
+    /// Create a new verification token for a user
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `user_id` - The ID of the user to verify
+    ///
+    /// # Returns
+    /// The created token, and the token to send to the user
+    ///
+    /// # Errors
+    /// If the token cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(
+        pool: &PgPool,
+        user_id: i64,
+    ) -> Result<(Self, String), EmailVerificationError> {
+        let token = generate_token(32);
+
+        let verification_token = sqlx::query_as::<_, Self>(
+            "INSERT INTO email_verification_tokens (token_hash, user_id, expires_at) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(hash_token(&token))
+        .bind(user_id)
+        .bind(chrono::Utc::now() + chrono::Duration::hours(EMAIL_VERIFICATION_EXPIRY_HOURS))
+        .fetch_one(pool)
+        .await;
+
+        match verification_token {
+            Ok(verification_token) => Ok((verification_token, token)),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(EmailVerificationError::Create(user_id))
+            }
+        }
+    }
+
+    /// Get a verification token
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `token` - The token sent to the user
+    ///
+    /// # Returns
+    /// The token
+    ///
+    /// # Errors
+    /// If the token cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_token(
+        pool: &PgPool,
+        token: String,
+    ) -> Result<Self, EmailVerificationError> {
+        let token = sqlx::query_as::<_, Self>(
+            "SELECT * FROM email_verification_tokens WHERE token_hash = $1",
+        )
+        .bind(hash_token(&token))
+        .fetch_one(pool)
+        .await;
+
+        match token {
+            Ok(token) => Ok(token),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(EmailVerificationError::InvalidToken)
+            }
+        }
+    }
+
+    /// Mark the users email as verified
+    /// All of the users verification tokens are removed once their email is verified.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Errors
+    /// If the token has expired, return an error
+    /// If the user cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn verify(&self, pool: &PgPool) -> Result<(), EmailVerificationError> {
+        if self.is_expired() {
+            return Err(EmailVerificationError::Expired);
+        }
+
+        let mut tx = pool.begin().await?;
+        if let Err(e) = sqlx::query(
+            "UPDATE users SET email_verified_at = COALESCE(email_verified_at, NOW()) WHERE id = $1",
+        )
+        .bind(self.user_id)
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!("{e}");
+            return Err(EmailVerificationError::Verify(self.user_id));
+        }
+        if let Err(e) = sqlx::query("DELETE FROM email_verification_tokens WHERE user_id = $1")
+            .bind(self.user_id)
+            .execute(&mut *tx)
+            .await
+        {
+            tracing::error!("{e}");
+            return Err(EmailVerificationError::RemoveTokens(self.user_id));
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::{auth::User, test_helpers::create_test_user};
+
+    #[test]
+    fn test_email_verification_error_display() {
+        let cases = [
+            (
+                EmailVerificationError::Create(1),
+                "Could not create verification token for user with id 1.",
+            ),
+            (
+                EmailVerificationError::InvalidToken,
+                "Invalid verification token.",
+            ),
+            (
+                EmailVerificationError::Expired,
+                "Verification token has expired.",
+            ),
+            (
+                EmailVerificationError::Verify(1),
+                "Could not verify email for user with id 1.",
+            ),
+            (
+                EmailVerificationError::RemoveTokens(1),
+                "Could not remove verification tokens for user with id 1.",
+            ),
+            (
+                EmailVerificationError::Database,
+                "Could not access email verification tokens.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_email_verification_error_hides_database_error() {
+        let error = EmailVerificationError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, EmailVerificationError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+
+        let (verification_token, token) = EmailVerificationToken::create(&pool, user.id)
+            .await
+            .unwrap();
+
+        assert_eq!(verification_token.user_id, user.id);
+        assert_eq!(token.len(), 32);
+        assert_eq!(verification_token.token_hash, hash_token(&token));
+        assert!(!verification_token.is_expired());
+        let found = EmailVerificationToken::get_by_token(&pool, token)
+            .await
+            .unwrap();
+        assert_eq!(found, verification_token);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_token_invalid(pool: PgPool) {
+        let result = EmailVerificationToken::get_by_token(&pool, "invalid".to_string()).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid verification token."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_verify(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (verification_token, token) = EmailVerificationToken::create(&pool, user.id)
+            .await
+            .unwrap();
+
+        verification_token.verify(&pool).await.unwrap();
+
+        let user = User::get(user.id, &pool).await.unwrap();
+        assert!(user.is_verified());
+        let result = EmailVerificationToken::get_by_token(&pool, token).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_verify_expired(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (mut token, _) = EmailVerificationToken::create(&pool, user.id)
+            .await
+            .unwrap();
+        token.expires_at = chrono::Utc::now() - chrono::Duration::hours(1);
+
+        let result = token.verify(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Verification token has expired."
+        );
+        let user = User::get(user.id, &pool).await.unwrap();
+        assert!(!user.is_verified());
+    }
+}
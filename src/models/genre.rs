@@ -0,0 +1,198 @@
+//! Genre model
+//!
+//! The `Genre` struct is used to browse artists and releases by genre.
+//! Genres are shared by every record label, and their slugs are unique.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+#[cfg(feature = "ssr")]
+use crate::utils::{slugify::slugify, unicode::normalise_name};
+
+/// Errors returned by the genre model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum GenreError {
+    /// The genre has no name
+    #[error("Genre name is required.")]
+    NameRequired,
+    /// The genre could not be created
+    #[error("Could not create genre {0}.")]
+    Create(String),
+    /// No genre has the given slug
+    #[error("Could not find genre with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The genres could not be listed
+    #[error("Could not list genres.")]
+    List,
+}
+
+/// The `Genre` struct is used to represent a genre in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct Genre {
+    /// The unique identifier of the genre
+    pub id: i64,
+    /// The name of the genre
+    pub name: String,
+    /// The slug of the genre
+    pub slug: String,
+}
+
+impl Genre {
+    /// Create a genre
+    /// If a genre with the same slug already exists, it is returned instead, so "Deep House" and "deep house" are one genre.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `name` - The name of the genre
+    ///
+    /// # Returns
+    /// The genre
+    ///
+    /// # Errors
+    /// If the name is empty, return an error
+    /// If the genre cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(pool: &PgPool, name: &str) -> Result<Self, GenreError> {
+        let name = normalise_name(name.trim());
+        if name.is_empty() {
+            return Err(GenreError::NameRequired);
+        }
+        let slug = slugify(&name);
+
+        let genre = sqlx::query_as::<_, Self>(
+            "INSERT INTO genres (name, slug) VALUES ($1, $2) ON CONFLICT (slug) DO UPDATE SET slug = EXCLUDED.slug RETURNING *",
+        )
+        .bind(&name)
+        .bind(slug)
+        .fetch_one(pool)
+        .await;
+
+        match genre {
+            Ok(genre) => Ok(genre),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(GenreError::Create(name))
+            }
+        }
+    }
+
+    /// Get a genre by slug
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `slug` - The slug of the genre
+    ///
+    /// # Returns
+    /// The genre
+    ///
+    /// # Errors
+    /// If the genre cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_slug(pool: &PgPool, slug: &str) -> Result<Self, GenreError> {
+        let genre = sqlx::query_as::<_, Self>("SELECT * FROM genres WHERE slug = $1")
+            .bind(slug)
+            .fetch_one(pool)
+            .await;
+
+        match genre {
+            Ok(genre) => Ok(genre),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(GenreError::NotFoundBySlug(slug.to_string()))
+            }
+        }
+    }
+
+    /// List every genre, by name
+    /// Used to populate genre dropdowns.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The genres
+    ///
+    /// # Errors
+    /// If the genres cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list(pool: &PgPool) -> Result<Vec<Self>, GenreError> {
+        let genres = sqlx::query_as::<_, Self>("SELECT * FROM genres ORDER BY name ASC, id ASC")
+            .fetch_all(pool)
+            .await;
+
+        match genres {
+            Ok(genres) => Ok(genres),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(GenreError::List)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genre_error_display() {
+        let cases = [
+            (GenreError::NameRequired, "Genre name is required."),
+            (
+                GenreError::Create("Techno".to_string()),
+                "Could not create genre Techno.",
+            ),
+            (
+                GenreError::NotFoundBySlug("missing".to_string()),
+                "Could not find genre with slug missing.",
+            ),
+            (GenreError::List, "Could not list genres."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let genre = Genre::create(&pool, " Drum & Bass ").await.unwrap();
+        assert_eq!(genre.name, "Drum & Bass");
+        assert_eq!(genre.slug, "drum-bass");
+
+        // The same slug returns the existing genre
+        let again = Genre::create(&pool, "drum bass").await.unwrap();
+        assert_eq!(again, genre);
+    }
+
+    #[sqlx::test]
+    async fn test_create_empty_name(pool: PgPool) {
+        let result = Genre::create(&pool, " ").await;
+        assert_eq!(result.unwrap_err().to_string(), "Genre name is required.");
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_slug(pool: PgPool) {
+        let genre = Genre::create(&pool, "Ambient").await.unwrap();
+
+        assert_eq!(Genre::get_by_slug(&pool, "ambient").await.unwrap(), genre);
+        assert_eq!(
+            Genre::get_by_slug(&pool, "missing")
+                .await
+                .unwrap_err()
+                .to_string(),
+            "Could not find genre with slug missing."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list(pool: PgPool) {
+        let techno = Genre::create(&pool, "Techno").await.unwrap();
+        let ambient = Genre::create(&pool, "Ambient").await.unwrap();
+
+        assert_eq!(Genre::list(&pool).await.unwrap(), vec![ambient, techno]);
+    }
+}
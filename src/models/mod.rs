@@ -1,14 +1,32 @@
 //! Models module
+//!
+//! Each model returns its own error enum, e.g. `ReleaseError`.
+//! The messages are shown to users, so they never include database details.
+//! Validation still returns `anyhow` errors, which the models carry in their `Invalid` variants.
 
+pub mod approval;
 pub mod artist;
 pub mod auth;
+pub mod email_verification;
+pub mod genre;
 pub mod music_service;
 pub mod page;
+pub mod password_reset;
+pub mod placement;
+pub mod query_helpers;
 pub mod record_label;
 pub mod release;
+pub mod release_credit;
+pub mod release_image;
+pub mod site_settings;
+pub mod slug;
+pub mod slug_redirect;
 pub mod social_media;
+pub mod subscriber;
+pub mod tag;
 #[cfg(test)]
 pub mod test_helpers;
 pub mod track;
+pub mod track_relation;
 pub mod track_with_artists;
 pub mod traits;
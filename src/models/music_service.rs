@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgConnection, PgPool};
 use std::fmt;
 use std::slice::Iter;
 
@@ -71,6 +71,25 @@ impl Platform {
     }
 }
 
+/// Errors returned by the music service model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum MusicServiceError {
+    /// The music service service is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// Any other database error
+    #[error("Could not access music services.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for MusicServiceError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
 /// Represents a music service associated with an artist.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "ssr", derive(FromRow))]
@@ -128,7 +147,7 @@ impl MusicService {
         artist_id: i64,
         platform: Platform,
         url: String,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, MusicServiceError> {
         let service = Self {
             id: 0, // This will be set by the database
             artist_id,
@@ -137,17 +156,37 @@ impl MusicService {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        service.validate(pool).await?;
+        service
+            .validate(pool)
+            .await
+            .map_err(|e| MusicServiceError::Invalid(e.to_string()))?;
+
+        let mut conn = pool.acquire().await?;
+        service.insert(&mut conn).await
+    }
 
+    /// Inserts the music service into the database.
+    /// The music service should be validated first.
+    ///
+    /// # Arguments
+    /// * `conn`: The database connection, which may be part of a transaction.
+    ///
+    /// # Returns
+    /// * The inserted music service.
+    ///
+    /// # Errors
+    /// If the music service cannot be inserted, an error will be returned.
+    #[cfg(feature = "ssr")]
+    pub async fn insert(self, conn: &mut PgConnection) -> Result<Self, MusicServiceError> {
         let service = sqlx::query_as::<_, Self>(
             "INSERT INTO music_services (artist_id, platform, url)
             VALUES ($1, $2, $3)
             RETURNING *",
         )
-        .bind(service.artist_id)
-        .bind(service.platform)
-        .bind(service.url)
-        .fetch_one(pool)
+        .bind(self.artist_id)
+        .bind(self.platform)
+        .bind(self.url)
+        .fetch_one(conn)
         .await?;
 
         Ok(service)
@@ -165,10 +204,13 @@ impl MusicService {
     /// # Errors
     /// If the artist does not exist, or if there is an issue with the database connection, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn list_by_artist(pool: &PgPool, artist_id: i64) -> anyhow::Result<Vec<Self>> {
+    pub async fn list_by_artist(
+        pool: &PgPool,
+        artist_id: i64,
+    ) -> Result<Vec<Self>, MusicServiceError> {
         if artist_id <= 0 {
-            return Err(anyhow::anyhow!(
-                "Artist ID must be greater than 0".to_string()
+            return Err(MusicServiceError::Invalid(
+                "Artist ID must be greater than 0".to_string(),
             ));
         }
 
@@ -193,8 +235,10 @@ impl MusicService {
     /// # Errors
     /// If the music service does not exist, or if the artist does not exist, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn update(&self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.validate(pool).await?;
+    pub async fn update(&self, pool: &PgPool) -> Result<Self, MusicServiceError> {
+        self.validate(pool)
+            .await
+            .map_err(|e| MusicServiceError::Invalid(e.to_string()))?;
 
         let service = sqlx::query_as::<_, Self>(
             "UPDATE music_services SET artist_id = $1, platform = $2, url = $3, updated_at = NOW()
@@ -221,7 +265,7 @@ impl MusicService {
     /// # Errors
     /// If the music service does not exist, or if there is an issue with the database connection, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<()> {
+    pub async fn delete(&self, pool: &PgPool) -> Result<(), MusicServiceError> {
         sqlx::query("DELETE FROM music_services WHERE id = $1")
             .bind(self.id)
             .execute(pool)
@@ -237,6 +281,34 @@ mod tests {
     #[cfg(feature = "ssr")]
     use crate::models::test_helpers::create_test_artist;
 
+    #[test]
+    fn test_music_service_error_display() {
+        let cases = [
+            (
+                MusicServiceError::Invalid("URL cannot be empty".to_string()),
+                "URL cannot be empty",
+            ),
+            (
+                MusicServiceError::Database,
+                "Could not access music services.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_music_service_error_hides_database_error() {
+        let error = MusicServiceError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, MusicServiceError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
     #[test]
     fn test_init_music_service() {
         let service = MusicService {
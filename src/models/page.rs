@@ -6,11 +6,53 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
 use sqlx::{FromRow, PgPool, Row};
 
+#[cfg(feature = "ssr")]
+use super::query_helpers::published_clause;
 #[cfg(feature = "ssr")]
 use super::record_label::RecordLabel;
 use super::traits::Validate;
 #[cfg(feature = "ssr")]
-use crate::utils::slugify::slugify;
+use crate::config::page::page_max_body_length;
+#[cfg(feature = "ssr")]
+use crate::utils::markdown::render_markdown;
+#[cfg(feature = "ssr")]
+use crate::utils::slugify::{is_reserved_slug, slugify};
+#[cfg(feature = "ssr")]
+use crate::utils::unicode::normalise_name;
+
+/// Errors returned by the page model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum PageError {
+    /// The page is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// No page has the given slug
+    #[error("Could not find page with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The pages could not be listed
+    #[error("Could not list pages.")]
+    List,
+    /// The pages for the record label could not be found
+    #[error("Could not find pages for record label with id {0}.")]
+    ListByRecordLabel(i64),
+    /// The page could not be updated
+    #[error("Could not update page with id {0}.")]
+    Update(i64),
+    /// The page could not be deleted
+    #[error("Could not delete page with id {0}.")]
+    Delete(i64),
+    /// Any other database error
+    #[error("Could not access pages.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for PageError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
 
 /// The Page struct is used to represent a page in the database.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
@@ -59,6 +101,9 @@ impl Validate for Page {
                 "Slug must be less than 255 characters.".to_string()
             ));
         }
+        if is_reserved_slug(&self.slug) {
+            return Err(anyhow::anyhow!("Slug is reserved.".to_string()));
+        }
         // Check that the slug is unique
         let include_hidden = true; // We want to check for uniqueness even if the page is hidden
         if let Ok(page) = Self::get_by_slug(pool, self.slug.clone(), include_hidden).await
@@ -74,6 +119,19 @@ impl Validate for Page {
             ));
         }
 
+        // Check the body isn't too long, and that published pages have one. Drafts can be empty.
+        let max_body_length = page_max_body_length();
+        if self.body.len() > max_body_length {
+            return Err(anyhow::anyhow!(
+                "Body must be less than {max_body_length} characters."
+            ));
+        }
+        if self.published_at.is_some() && self.body.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "Body is required to publish a page.".to_string()
+            ));
+        }
+
         // Check that the record label exists
         if let Err(e) = RecordLabel::get_by_id(pool, self.label_id).await {
             tracing::error!("{e}");
@@ -88,6 +146,14 @@ impl Validate for Page {
 }
 
 impl Page {
+    /// Render the markdown body to sanitised HTML, ready to inject into the page
+    /// Only the server renders, the browser is sent the HTML with the page.
+    #[cfg(feature = "ssr")]
+    #[must_use]
+    pub fn rendered_body(&self) -> String {
+        render_markdown(&self.body)
+    }
+
     /// Create a new page
     ///
     /// # Arguments
@@ -110,7 +176,8 @@ impl Page {
         body: String,
         record_label_id: i64,
         published_at: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, PageError> {
+        let name = normalise_name(&name);
         let slug = slugify(&name);
 
         let page = Self {
@@ -125,7 +192,9 @@ impl Page {
             updated_at: chrono::Utc::now(),
             deleted_at: None,
         };
-        page.validate(pool).await?;
+        page.validate(pool)
+            .await
+            .map_err(|e| PageError::Invalid(e.to_string()))?;
 
         let page = sqlx::query_as::<_, Self>(
             "INSERT INTO pages (name, slug, description, body, label_id, published_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
@@ -158,14 +227,17 @@ impl Page {
         pool: &PgPool,
         slug: String,
         include_hidden: bool,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, PageError> {
         let query = if include_hidden {
-            "SELECT * FROM pages WHERE slug = $1"
+            "SELECT * FROM pages WHERE slug = $1".to_string()
         } else {
-            "SELECT * FROM pages WHERE slug = $1 AND deleted_at IS NULL AND published_at < NOW() AND published_at IS NOT NULL"
+            format!(
+                "SELECT * FROM pages WHERE slug = $1 AND {}",
+                published_clause("")
+            )
         };
 
-        match sqlx::query_as::<_, Self>(query)
+        match sqlx::query_as::<_, Self>(&query)
             .bind(&slug)
             .fetch_one(pool)
             .await
@@ -173,7 +245,7 @@ impl Page {
             Ok(page) => Ok(page),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not find page with slug {slug}."))
+                Err(PageError::NotFoundBySlug(slug))
             }
         }
     }
@@ -190,19 +262,21 @@ impl Page {
     /// # Errors
     /// If the pages cannot be listed, return an error
     #[cfg(feature = "ssr")]
-    pub async fn list(pool: &PgPool, include_hidden: bool) -> anyhow::Result<Vec<Self>> {
+    pub async fn list(pool: &PgPool, include_hidden: bool) -> Result<Vec<Self>, PageError> {
         let query = if include_hidden {
             "SELECT * FROM pages
             ORDER BY deleted_at DESC, published_at DESC, name ASC"
+                .to_string()
         } else {
-            "SELECT pages.* FROM pages
-            WHERE deleted_at IS NULL
-            AND published_at < NOW()
-            AND published_at IS NOT NULL
-            ORDER BY published_at DESC, name ASC"
+            format!(
+                "SELECT pages.* FROM pages
+            WHERE {}
+            ORDER BY published_at DESC, name ASC",
+                published_clause("pages")
+            )
         };
 
-        let rows = sqlx::query(query).fetch_all(pool).await;
+        let rows = sqlx::query(&query).fetch_all(pool).await;
 
         match rows {
             Ok(rows) => {
@@ -225,7 +299,43 @@ impl Page {
             }
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not list pages."))
+                Err(PageError::List)
+            }
+        }
+    }
+
+    /// List the pages on a record label that were most recently updated, including drafts
+    /// Deleted pages are excluded.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The pages, most recently updated first
+    ///
+    /// # Errors
+    /// If the pages cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_recently_updated(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, PageError> {
+        let pages = sqlx::query_as::<_, Self>(
+            "SELECT * FROM pages
+             WHERE label_id = $1
+              AND deleted_at IS NULL
+             ORDER BY updated_at DESC, id DESC",
+        )
+        .bind(record_label_id)
+        .fetch_all(pool)
+        .await;
+
+        match pages {
+            Ok(pages) => Ok(pages),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PageError::ListByRecordLabel(record_label_id))
             }
         }
     }
@@ -244,9 +354,12 @@ impl Page {
     /// # Panics
     /// If the page cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn update(mut self, pool: &PgPool) -> Result<Self, PageError> {
+        self.name = normalise_name(&self.name);
         self.slug = slugify(&self.name);
-        self.validate(pool).await?;
+        self.validate(pool)
+            .await
+            .map_err(|e| PageError::Invalid(e.to_string()))?;
 
         let page = match sqlx::query_as::<_, Self>(
             "UPDATE pages SET name = $1, slug = $2, description = $3, body = $4, published_at = $5, updated_at = $6, deleted_at = $7 WHERE id = $8 RETURNING *",
@@ -265,7 +378,7 @@ impl Page {
             Ok(page) => page,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!("Could not update page with id {}.", self.id));
+                return Err(PageError::Update(self.id));
             }
         };
 
@@ -284,7 +397,7 @@ impl Page {
     /// # Errors
     /// If the page cannot be deleted, return an error
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn delete(&self, pool: &PgPool) -> Result<Self, PageError> {
         let page =
             sqlx::query_as::<_, Self>("UPDATE pages SET deleted_at = $1 WHERE id = $2 RETURNING *")
                 .bind(chrono::Utc::now())
@@ -296,10 +409,7 @@ impl Page {
             Ok(page) => Ok(page),
             Err(e) => {
                 eprintln!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not delete page with id {}.",
-                    self.id
-                ))
+                Err(PageError::Delete(self.id))
             }
         }
     }
@@ -308,9 +418,46 @@ impl Page {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::page::DEFAULT_PAGE_MAX_BODY_LENGTH;
     #[cfg(feature = "ssr")]
     use crate::models::test_helpers::{create_test_page, create_test_record_label};
 
+    #[test]
+    fn test_page_error_display() {
+        let cases = [
+            (
+                PageError::Invalid("Name is required.".to_string()),
+                "Name is required.",
+            ),
+            (
+                PageError::NotFoundBySlug("missing".to_string()),
+                "Could not find page with slug missing.",
+            ),
+            (PageError::List, "Could not list pages."),
+            (
+                PageError::ListByRecordLabel(3),
+                "Could not find pages for record label with id 3.",
+            ),
+            (PageError::Update(1), "Could not update page with id 1."),
+            (PageError::Delete(1), "Could not delete page with id 1."),
+            (PageError::Database, "Could not access pages."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_page_error_hides_database_error() {
+        let error = PageError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, PageError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
     #[test]
     fn test_init_page() {
         let page = Page {
@@ -333,6 +480,21 @@ mod tests {
         assert_eq!(page.label_id, 1);
     }
 
+    #[test]
+    fn test_rendered_body() {
+        let page = Page {
+            body: "# This is an updated page\n\n<script>alert('hi')</script>\n\n[Shop](https://example.com)"
+                .to_string(),
+            ..Default::default()
+        };
+
+        let html = page.rendered_body();
+
+        assert!(html.starts_with("<h1>This is an updated page</h1>"));
+        assert!(!html.contains("script"));
+        assert!(html.ends_with("<p><a href=\"https://example.com\" rel=\"noopener\">Shop</a></p>"));
+    }
+
     #[sqlx::test]
     async fn test_validate_success(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
@@ -428,6 +590,29 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_validate_slug_reserved(pool: PgPool) {
+        let page = Page {
+            id: 1,
+            name: "Admin".to_string(),
+            slug: "admin".to_string(),
+            description: "This is a test page".to_string(),
+            body: "This is a test page".to_string(),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            deleted_at: None,
+        };
+
+        let result = page.validate(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Slug is reserved.".to_string()
+        );
+    }
+
     #[sqlx::test]
     async fn test_validate_slug_unique(pool: PgPool) {
         let page = create_test_page(&pool, 1, None).await.unwrap();
@@ -469,6 +654,62 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_validate_body_length(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let page = Page {
+            name: "Test Page".to_string(),
+            slug: "test-page".to_string(),
+            body: "a".repeat(DEFAULT_PAGE_MAX_BODY_LENGTH + 1),
+            label_id: record_label.id,
+            ..Default::default()
+        };
+
+        let result = page.validate(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("Body must be less than {DEFAULT_PAGE_MAX_BODY_LENGTH} characters.")
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_body_required_to_publish(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let page = Page {
+            name: "Test Page".to_string(),
+            slug: "test-page".to_string(),
+            body: "  \n".to_string(),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+
+        let result = page.validate(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Body is required to publish a page."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_empty_body_draft(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let page = Page {
+            name: "Test Page".to_string(),
+            slug: "test-page".to_string(),
+            body: String::new(),
+            label_id: record_label.id,
+            published_at: None,
+            ..Default::default()
+        };
+
+        let result = page.validate(&pool).await;
+
+        assert!(result.is_ok());
+    }
+
     #[sqlx::test]
     async fn test_validate_record_label_exists(pool: PgPool) {
         let page = Page {
@@ -513,6 +754,49 @@ mod tests {
         assert_eq!(page.body, "This is a test page body".to_string());
     }
 
+    #[sqlx::test]
+    async fn test_create_normalises_name(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let page = Page::create(
+            &pool,
+            "Caf\u{e9}".to_string(),
+            String::new(),
+            String::new(),
+            record_label.id,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let result = Page::create(
+            &pool,
+            "Cafe\u{301}".to_string(),
+            String::new(),
+            String::new(),
+            record_label.id,
+            None,
+        )
+        .await;
+
+        assert_eq!(page.name, "Caf\u{e9}".to_string());
+        assert_eq!(page.slug, "cafe".to_string());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Slug must be unique.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_normalises_name(pool: PgPool) {
+        let mut page = create_test_page(&pool, 1, None).await.unwrap();
+        page.name = "Cafe\u{301}".to_string();
+
+        let page = page.update(&pool).await.unwrap();
+
+        assert_eq!(page.name, "Caf\u{e9}".to_string());
+        assert_eq!(page.slug, "cafe".to_string());
+    }
+
     #[sqlx::test]
     async fn test_create_with_validation_error(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
@@ -0,0 +1,358 @@
+//! Password reset model
+//!
+//! The `PasswordResetToken` struct lets a user who has forgotten their password choose a new one.
+//! Only a hash of the token is stored, and each token can be used once.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+#[cfg(feature = "ssr")]
+use crate::config::auth::PASSWORD_RESET_EXPIRY_HOURS;
+#[cfg(feature = "ssr")]
+use crate::utils::token::generate_token;
+
+/// Errors returned by the password reset model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum PasswordResetError {
+    /// The user's unused reset tokens could not be removed
+    #[error("Could not remove password reset tokens for user with id {0}.")]
+    RemoveTokens(i64),
+    /// The reset token could not be created
+    #[error("Could not create password reset token for user with id {0}.")]
+    Create(i64),
+    /// No reset token matches
+    #[error("Invalid password reset token.")]
+    InvalidToken,
+    /// The reset token has already been used
+    #[error("Password reset token has already been used.")]
+    Used,
+    /// The reset token has expired
+    #[error("Password reset token has expired.")]
+    Expired,
+    /// The user's password could not be changed
+    #[error("Could not reset password for user with id {0}.")]
+    Reset(i64),
+    /// Any other database error
+    #[error("Could not access password reset tokens.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for PasswordResetError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
+/// The `PasswordResetToken` struct is used to represent a password reset token in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct PasswordResetToken {
+    /// The unique identifier of the token
+    pub id: i64,
+    /// The SHA-256 hash of the token sent to the user
+    pub token_hash: String,
+    /// The user the token belongs to
+    pub user_id: i64,
+    /// The date and time the token was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the token expires
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the token was used, if it has been
+    pub used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl PasswordResetToken {
+    /// Has the token expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at < chrono::Utc::now()
+    }
+
+    /// Has the token been used
+    pub const fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+
+    /// Hash a token, so it can be stored or looked up
+    #[cfg(feature = "ssr")]
+    #[must_use]
+    pub fn hash_token(token: &str) -> String {
+        crate::utils::token::hash_token(token)
+    }
+
+    /// Create a new password reset token for a user
+    /// Any of the user's tokens that haven't been used are removed, so only the newest link works.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `user_id` - The ID of the user resetting their password
+    ///
+    /// # Returns
+    /// The created token, and the token to send to the user
+    ///
+    /// # Errors
+    /// If the token cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(pool: &PgPool, user_id: i64) -> Result<(Self, String), PasswordResetError> {
+        let token = generate_token(48);
+
+        let mut tx = pool.begin().await?;
+        if let Err(e) =
+            sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1 AND used_at IS NULL")
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await
+        {
+            tracing::error!("{e}");
+            return Err(PasswordResetError::RemoveTokens(user_id));
+        }
+        let reset_token = sqlx::query_as::<_, Self>(
+            "INSERT INTO password_reset_tokens (token_hash, user_id, expires_at) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(Self::hash_token(&token))
+        .bind(user_id)
+        .bind(chrono::Utc::now() + chrono::Duration::hours(PASSWORD_RESET_EXPIRY_HOURS))
+        .fetch_one(&mut *tx)
+        .await;
+
+        match reset_token {
+            Ok(reset_token) => {
+                tx.commit().await?;
+                Ok((reset_token, token))
+            }
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PasswordResetError::Create(user_id))
+            }
+        }
+    }
+
+    /// Get a password reset token
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `token` - The token sent to the user
+    ///
+    /// # Returns
+    /// The token
+    ///
+    /// # Errors
+    /// If the token cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_token(pool: &PgPool, token: &str) -> Result<Self, PasswordResetError> {
+        let reset_token =
+            sqlx::query_as::<_, Self>("SELECT * FROM password_reset_tokens WHERE token_hash = $1")
+                .bind(Self::hash_token(token))
+                .fetch_one(pool)
+                .await;
+
+        match reset_token {
+            Ok(reset_token) => Ok(reset_token),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PasswordResetError::InvalidToken)
+            }
+        }
+    }
+
+    /// Set the user's new password and use up the token
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `password_hash` - The hash of the new password
+    ///
+    /// # Errors
+    /// If the token has been used, return an error
+    /// If the token has expired, return an error
+    /// If the password cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn consume(
+        &self,
+        pool: &PgPool,
+        password_hash: &str,
+    ) -> Result<(), PasswordResetError> {
+        if self.is_used() {
+            return Err(PasswordResetError::Used);
+        }
+        if self.is_expired() {
+            return Err(PasswordResetError::Expired);
+        }
+
+        let mut tx = pool.begin().await?;
+        // Only one request can use the token, even if two arrive at once
+        let used = sqlx::query(
+            "UPDATE password_reset_tokens SET used_at = NOW() WHERE id = $1 AND used_at IS NULL",
+        )
+        .bind(self.id)
+        .execute(&mut *tx)
+        .await?;
+        if used.rows_affected() == 0 {
+            return Err(PasswordResetError::Used);
+        }
+        if let Err(e) = sqlx::query("UPDATE users SET password = $1 WHERE id = $2")
+            .bind(password_hash)
+            .bind(self.user_id)
+            .execute(&mut *tx)
+            .await
+        {
+            tracing::error!("{e}");
+            return Err(PasswordResetError::Reset(self.user_id));
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::create_test_user;
+
+    #[test]
+    fn test_password_reset_error_display() {
+        let cases = [
+            (
+                PasswordResetError::RemoveTokens(1),
+                "Could not remove password reset tokens for user with id 1.",
+            ),
+            (
+                PasswordResetError::Create(1),
+                "Could not create password reset token for user with id 1.",
+            ),
+            (
+                PasswordResetError::InvalidToken,
+                "Invalid password reset token.",
+            ),
+            (
+                PasswordResetError::Used,
+                "Password reset token has already been used.",
+            ),
+            (
+                PasswordResetError::Expired,
+                "Password reset token has expired.",
+            ),
+            (
+                PasswordResetError::Reset(1),
+                "Could not reset password for user with id 1.",
+            ),
+            (
+                PasswordResetError::Database,
+                "Could not access password reset tokens.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_password_reset_error_hides_database_error() {
+        let error = PasswordResetError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, PasswordResetError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
+    #[test]
+    fn test_hash_token() {
+        let hash = PasswordResetToken::hash_token("token");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, PasswordResetToken::hash_token("token"));
+        assert_ne!(hash, PasswordResetToken::hash_token("other"));
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+
+        let (reset_token, token) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+
+        assert_eq!(reset_token.user_id, user.id);
+        assert_eq!(token.len(), 48);
+        assert_eq!(
+            reset_token.token_hash,
+            PasswordResetToken::hash_token(&token)
+        );
+        assert!(!reset_token.is_expired());
+        assert!(!reset_token.is_used());
+    }
+
+    #[sqlx::test]
+    async fn test_create_removes_unused_tokens(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (used_token, _) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+        used_token.consume(&pool, "new-hash").await.unwrap();
+        let (_, old_token) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+
+        let (_, token) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+
+        assert!(
+            PasswordResetToken::get_by_token(&pool, &old_token)
+                .await
+                .is_err()
+        );
+        assert!(
+            PasswordResetToken::get_by_token(&pool, &token)
+                .await
+                .is_ok()
+        );
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM password_reset_tokens WHERE user_id = $1")
+                .bind(user.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_token_invalid(pool: PgPool) {
+        let result = PasswordResetToken::get_by_token(&pool, "invalid").await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid password reset token."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_consume(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (reset_token, token) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+
+        reset_token.consume(&pool, "new-hash").await.unwrap();
+
+        let reset_token = PasswordResetToken::get_by_token(&pool, &token)
+            .await
+            .unwrap();
+        assert!(reset_token.is_used());
+        let password: String = sqlx::query_scalar("SELECT password FROM users WHERE id = $1")
+            .bind(user.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(password, "new-hash");
+    }
+
+    #[sqlx::test]
+    async fn test_consume_stale_copy(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (reset_token, _) = PasswordResetToken::create(&pool, user.id).await.unwrap();
+        reset_token.consume(&pool, "new-hash").await.unwrap();
+
+        // A copy loaded before the token was used can't use it again
+        let result = reset_token.consume(&pool, "other-hash").await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Password reset token has already been used."
+        );
+    }
+}
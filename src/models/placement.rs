@@ -0,0 +1,412 @@
+//! Placement model
+//!
+//! The `Placement` struct is used to represent a playlist a release or track has been featured on.
+//! Placements are only used for reporting, and are never shown publicly.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+use std::fmt;
+
+use super::traits::Validate;
+#[cfg(feature = "ssr")]
+use super::{release::Release, track::Track};
+#[cfg(feature = "ssr")]
+use crate::utils::urls::is_valid_url;
+
+/// Errors returned by the placement model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum PlacementError {
+    /// The placement is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// The placement could not be created
+    #[error("Could not create placement.")]
+    Create,
+    /// No placement has the given ID
+    #[error("Could not find placement with id {0}.")]
+    NotFoundById(i64),
+    /// The placements for the release or track could not be found
+    #[error("Could not find placements for {entity_type} with id {entity_id}.")]
+    ListByEntity {
+        /// The type of entity
+        entity_type: PlacementEntity,
+        /// The ID of the release or track
+        entity_id: i64,
+    },
+    /// The placement could not be removed
+    #[error("Could not remove placement with id {0}.")]
+    Delete(i64),
+}
+
+/// The type of entity a placement is for.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum PlacementEntity {
+    /// The placement is for a release.
+    #[default]
+    Release,
+    /// The placement is for a track.
+    Track,
+}
+
+impl fmt::Display for PlacementEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Release => write!(f, "Release"),
+            Self::Track => write!(f, "Track"),
+        }
+    }
+}
+
+/// The `Placement` struct is used to represent a playlist placement in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct Placement {
+    /// The unique identifier of the placement
+    pub id: i64,
+    /// The type of entity the placement is for
+    pub entity_type: PlacementEntity,
+    /// The ID of the release or track
+    pub entity_id: i64,
+    /// The name of the playlist
+    pub playlist_name: String,
+    /// The URL of the playlist
+    pub url: String,
+    /// The date and time the release or track was added to the playlist
+    pub added_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Validate for Placement {
+    #[cfg(feature = "ssr")]
+    async fn validate(&self, pool: &PgPool) -> anyhow::Result<()> {
+        if self.playlist_name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Playlist name is required.".to_string()));
+        }
+        if self.playlist_name.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Playlist name must be less than 255 characters.".to_string()
+            ));
+        }
+        if !is_valid_url(&self.url) {
+            return Err(anyhow::anyhow!(
+                "Playlist URL must be a valid URL.".to_string()
+            ));
+        }
+
+        let exists = match self.entity_type {
+            PlacementEntity::Release => Release::get_by_id(pool, self.entity_id).await.is_ok(),
+            PlacementEntity::Track => Track::get_by_id(pool, self.entity_id).await.is_ok(),
+        };
+        if !exists {
+            return Err(anyhow::anyhow!(
+                "{} with id {} not found.",
+                self.entity_type,
+                self.entity_id
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Placement {
+    /// Add a playlist placement to a release or track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `entity_type` - The type of entity the placement is for
+    /// * `entity_id` - The ID of the release or track
+    /// * `playlist_name` - The name of the playlist
+    /// * `url` - The URL of the playlist
+    ///
+    /// # Returns
+    /// The placement
+    ///
+    /// # Errors
+    /// If the playlist name or URL is invalid, return an error
+    /// If the release or track cannot be found, return an error
+    /// If the placement cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(
+        pool: &PgPool,
+        entity_type: PlacementEntity,
+        entity_id: i64,
+        playlist_name: String,
+        url: String,
+    ) -> Result<Self, PlacementError> {
+        let placement = Self {
+            entity_type,
+            entity_id,
+            playlist_name: playlist_name.trim().to_string(),
+            url: url.trim().to_string(),
+            ..Default::default()
+        };
+        placement
+            .validate(pool)
+            .await
+            .map_err(|e| PlacementError::Invalid(e.to_string()))?;
+
+        let placement = sqlx::query_as::<_, Self>(
+            "INSERT INTO placements (entity_type, entity_id, playlist_name, url)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+        )
+        .bind(placement.entity_type)
+        .bind(placement.entity_id)
+        .bind(placement.playlist_name)
+        .bind(placement.url)
+        .fetch_one(pool)
+        .await;
+
+        match placement {
+            Ok(placement) => Ok(placement),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PlacementError::Create)
+            }
+        }
+    }
+
+    /// Get a placement by id
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `id` - The ID of the placement
+    ///
+    /// # Returns
+    /// The placement
+    ///
+    /// # Errors
+    /// If the placement cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_id(pool: &PgPool, id: i64) -> Result<Self, PlacementError> {
+        let placement = sqlx::query_as::<_, Self>("SELECT * FROM placements WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await;
+
+        match placement {
+            Ok(placement) => Ok(placement),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PlacementError::NotFoundById(id))
+            }
+        }
+    }
+
+    /// List the placements for a release or track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `entity_type` - The type of entity
+    /// * `entity_id` - The ID of the release or track
+    ///
+    /// # Returns
+    /// The placements, most recently added first
+    ///
+    /// # Errors
+    /// If the placements cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_by_entity(
+        pool: &PgPool,
+        entity_type: PlacementEntity,
+        entity_id: i64,
+    ) -> Result<Vec<Self>, PlacementError> {
+        let placements = sqlx::query_as::<_, Self>(
+            "SELECT * FROM placements
+            WHERE entity_type = $1 AND entity_id = $2
+            ORDER BY added_at DESC, id DESC",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .fetch_all(pool)
+        .await;
+
+        match placements {
+            Ok(placements) => Ok(placements),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PlacementError::ListByEntity {
+                    entity_type,
+                    entity_id,
+                })
+            }
+        }
+    }
+
+    /// Remove the placement
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The removed placement
+    ///
+    /// # Errors
+    /// If the placement cannot be removed, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn delete(self, pool: &PgPool) -> Result<Self, PlacementError> {
+        let placement =
+            sqlx::query_as::<_, Self>("DELETE FROM placements WHERE id = $1 RETURNING *")
+                .bind(self.id)
+                .fetch_one(pool)
+                .await;
+
+        match placement {
+            Ok(placement) => Ok(placement),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(PlacementError::Delete(self.id))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{create_test_release, create_test_track};
+
+    #[test]
+    fn test_placement_error_display() {
+        let cases = [
+            (
+                PlacementError::Invalid("Playlist name is required.".to_string()),
+                "Playlist name is required.",
+            ),
+            (PlacementError::Create, "Could not create placement."),
+            (
+                PlacementError::NotFoundById(1),
+                "Could not find placement with id 1.",
+            ),
+            (
+                PlacementError::ListByEntity {
+                    entity_type: PlacementEntity::Track,
+                    entity_id: 2,
+                },
+                "Could not find placements for Track with id 2.",
+            ),
+            (
+                PlacementError::Delete(1),
+                "Could not remove placement with id 1.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let placement = Placement::create(
+            &pool,
+            PlacementEntity::Release,
+            release.id,
+            " New Music Friday ".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(placement.entity_type, PlacementEntity::Release);
+        assert_eq!(placement.entity_id, release.id);
+        assert_eq!(placement.playlist_name, "New Music Friday");
+        assert_eq!(placement.url, "https://open.spotify.com/playlist/1");
+    }
+
+    #[sqlx::test]
+    async fn test_create_invalid_url(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let result = Placement::create(
+            &pool,
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "not a url".to_string(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Playlist URL must be a valid URL."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create_missing_entity(pool: PgPool) {
+        let result = Placement::create(
+            &pool,
+            PlacementEntity::Track,
+            1,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Track with id 1 not found."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list_by_entity(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let release_placement = Placement::create(
+            &pool,
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+        Placement::create(
+            &pool,
+            PlacementEntity::Track,
+            track.id,
+            "Chill Vibes".to_string(),
+            "https://open.spotify.com/playlist/2".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let placements = Placement::list_by_entity(&pool, PlacementEntity::Release, release.id)
+            .await
+            .unwrap();
+
+        assert_eq!(placements, vec![release_placement]);
+    }
+
+    #[sqlx::test]
+    async fn test_delete(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let placement = Placement::create(
+            &pool,
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        placement.clone().delete(&pool).await.unwrap();
+
+        let placements = Placement::list_by_entity(&pool, PlacementEntity::Release, release.id)
+            .await
+            .unwrap();
+        assert!(placements.is_empty());
+        assert!(Placement::get_by_id(&pool, placement.id).await.is_err());
+    }
+}
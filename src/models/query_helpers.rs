@@ -0,0 +1,139 @@
+//! Query helpers
+//!
+//! Fragments of SQL shared between the models, so that the same rules are applied everywhere.
+
+/// The clause that limits a query to records whose published date has passed
+///
+/// A record published at exactly `NOW()` counts as published, the same as `published_at <= now` in Rust.
+///
+/// # Arguments
+/// * `alias` - The table name or alias to prefix the columns with, or an empty string for none
+///
+/// # Returns
+/// The clause, without a leading `AND`
+///
+/// # Example
+/// ```
+/// use white_label::models::query_helpers::published_at_clause;
+/// assert_eq!(
+///     published_at_clause("t"),
+///     "t.published_at IS NOT NULL AND t.published_at <= NOW()"
+/// );
+/// ```
+#[must_use]
+pub fn published_at_clause(alias: &str) -> String {
+    let prefix = if alias.is_empty() {
+        String::new()
+    } else {
+        format!("{alias}.")
+    };
+    format!("{prefix}published_at IS NOT NULL AND {prefix}published_at <= NOW()")
+}
+
+/// The clause that limits a query to published records
+///
+/// A record is published when it has not been deleted and its published date has passed, see `published_at_clause`.
+///
+/// # Arguments
+/// * `alias` - The table name or alias to prefix the columns with, or an empty string for none
+///
+/// # Returns
+/// The clause, without a leading `AND`
+///
+/// # Example
+/// ```
+/// use white_label::models::query_helpers::published_clause;
+/// assert_eq!(
+///     published_clause("t"),
+///     "t.deleted_at IS NULL AND t.published_at IS NOT NULL AND t.published_at <= NOW()"
+/// );
+/// ```
+#[must_use]
+pub fn published_clause(alias: &str) -> String {
+    let prefix = if alias.is_empty() {
+        String::new()
+    } else {
+        format!("{alias}.")
+    };
+    format!(
+        "{prefix}deleted_at IS NULL AND {}",
+        published_at_clause(alias)
+    )
+}
+
+/// The clause that limits a query to releases that can be listed publicly
+///
+/// A release is listed once its embargo has lifted, unless it is unlisted or archived.
+/// Unlisted and archived releases can still be reached by their slug, so this is only for listings.
+/// Combine it with `published_clause`, which this does not include.
+///
+/// # Arguments
+/// * `alias` - The table name or alias to prefix the columns with, or an empty string for none
+///
+/// # Returns
+/// The clause, without a leading `AND`
+///
+/// # Example
+/// ```
+/// use white_label::models::query_helpers::release_visible_clause;
+/// assert_eq!(
+///     release_visible_clause("r"),
+///     "(r.embargo_until IS NULL OR r.embargo_until < NOW()) AND r.unlisted = FALSE AND r.archived_at IS NULL"
+/// );
+/// ```
+#[must_use]
+pub fn release_visible_clause(alias: &str) -> String {
+    let prefix = if alias.is_empty() {
+        String::new()
+    } else {
+        format!("{alias}.")
+    };
+    format!(
+        "({prefix}embargo_until IS NULL OR {prefix}embargo_until < NOW()) AND {prefix}unlisted = FALSE AND {prefix}archived_at IS NULL"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_at_clause() {
+        assert_eq!(
+            published_at_clause(""),
+            "published_at IS NOT NULL AND published_at <= NOW()"
+        );
+        assert_eq!(
+            published_at_clause("tracks"),
+            "tracks.published_at IS NOT NULL AND tracks.published_at <= NOW()"
+        );
+    }
+
+    #[test]
+    fn test_published_clause() {
+        assert_eq!(
+            published_clause(""),
+            "deleted_at IS NULL AND published_at IS NOT NULL AND published_at <= NOW()"
+        );
+        assert_eq!(
+            published_clause("t"),
+            "t.deleted_at IS NULL AND t.published_at IS NOT NULL AND t.published_at <= NOW()"
+        );
+        assert_eq!(
+            published_clause("releases"),
+            "releases.deleted_at IS NULL AND releases.published_at IS NOT NULL AND releases.published_at <= NOW()"
+        );
+    }
+
+    #[test]
+    fn test_release_visible_clause() {
+        assert_eq!(
+            release_visible_clause(""),
+            "(embargo_until IS NULL OR embargo_until < NOW()) AND unlisted = FALSE AND archived_at IS NULL"
+        );
+        assert_eq!(
+            release_visible_clause("releases"),
+            "(releases.embargo_until IS NULL OR releases.embargo_until < NOW()) AND releases.unlisted = FALSE AND releases.archived_at IS NULL"
+        );
+    }
+}
@@ -8,9 +8,43 @@ use sqlx::{FromRow, PgPool, Row};
 
 use super::traits::Validate;
 #[cfg(feature = "ssr")]
-use super::{artist::Artist, page::Page};
+use super::{artist::Artist, page::Page, query_helpers::published_clause};
+use crate::config::image::PlaceholderImages;
 #[cfg(feature = "ssr")]
-use crate::utils::slugify::slugify;
+use crate::state::RecordLabelCache;
+#[cfg(feature = "ssr")]
+use crate::utils::{
+    catalogue_number::next_catalogue_number, currency::is_valid_currency, slugify::slugify,
+};
+
+/// Errors returned by the record label model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum RecordLabelError {
+    /// The record label is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// There are no record labels
+    #[error("Could not find label")]
+    First,
+    /// No record label has the given ID
+    #[error("Could not find record label with id {0}.")]
+    NotFoundById(i64),
+    /// No record label has the given slug
+    #[error("Could not find record label with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The record label could not be updated
+    #[error("Could not update label.")]
+    Update,
+    /// The artists on the record label could not be found
+    #[error("Could not find artists")]
+    Artists,
+    /// The catalogue numbers on the record label could not be found
+    #[error("Could not find catalogue numbers")]
+    CatalogueNumbers,
+    /// The pages on the record label could not be found
+    #[error("Could not find pages")]
+    Pages,
+}
 
 /// The Label struct is used to represent a record label in the database.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
@@ -26,6 +60,18 @@ pub struct RecordLabel {
     pub description: String,
     /// The ISRC base of the label
     pub isrc_base: String,
+    /// Whether publishing a release requires approval from a second label owner
+    pub require_approval: bool,
+    /// The ISO 4217 code of the currency the label sells in
+    pub currency: String,
+    /// Shown instead of the release date for announced releases without one, e.g. "TBA"
+    pub release_date_placeholder: String,
+    /// The prefix for generated catalogue numbers, e.g. "CAT-" gives "CAT-0001"
+    pub catalogue_prefix: String,
+    /// The placeholder images, read from the environment on the server and sent to the browser with the label
+    #[cfg_attr(feature = "ssr", sqlx(skip))]
+    #[serde(default)]
+    pub placeholder_images: PlaceholderImages,
     /// The date and time the label was created in the database
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// The date and time the label was last updated
@@ -56,6 +102,22 @@ impl Validate for RecordLabel {
             return Err(anyhow::anyhow!("Slug must be unique.".to_string()));
         }
 
+        if !is_valid_currency(&self.currency) {
+            return Err(anyhow::anyhow!(
+                "Currency must be a valid ISO 4217 code.".to_string()
+            ));
+        }
+        if self.release_date_placeholder.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Release date placeholder must be less than 255 characters.".to_string()
+            ));
+        }
+        if self.catalogue_prefix.chars().count() > 32 {
+            return Err(anyhow::anyhow!(
+                "Catalogue prefix must be at most 32 characters.".to_string()
+            ));
+        }
+
         Ok(())
     }
 }
@@ -72,7 +134,7 @@ impl RecordLabel {
     /// # Errors
     /// If the record label cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn first(pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn first(pool: &PgPool) -> Result<Self, RecordLabelError> {
         let row = sqlx::query("SELECT * FROM labels ORDER BY id ASC LIMIT 1")
             .fetch_one(pool)
             .await;
@@ -81,7 +143,7 @@ impl RecordLabel {
             Ok(row) => row,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!("Could not find label"));
+                return Err(RecordLabelError::First);
             }
         };
 
@@ -91,6 +153,11 @@ impl RecordLabel {
             slug: row.get("slug"),
             description: row.get("description"),
             isrc_base: row.get("isrc_base"),
+            require_approval: row.get("require_approval"),
+            currency: row.get("currency"),
+            release_date_placeholder: row.get("release_date_placeholder"),
+            catalogue_prefix: row.get("catalogue_prefix"),
+            placeholder_images: PlaceholderImages::from_env(),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -108,7 +175,7 @@ impl RecordLabel {
     /// # Errors
     /// If the record label cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_id(pool: &PgPool, id: i64) -> anyhow::Result<Self> {
+    pub async fn get_by_id(pool: &PgPool, id: i64) -> Result<Self, RecordLabelError> {
         let row = sqlx::query("SELECT * FROM labels WHERE id = $1")
             .bind(id)
             .fetch_one(pool)
@@ -118,10 +185,7 @@ impl RecordLabel {
             Ok(row) => row,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!(
-                    "Could not find record label with id {}.",
-                    id
-                ));
+                return Err(RecordLabelError::NotFoundById(id));
             }
         };
 
@@ -131,11 +195,45 @@ impl RecordLabel {
             slug: row.get("slug"),
             description: row.get("description"),
             isrc_base: row.get("isrc_base"),
+            require_approval: row.get("require_approval"),
+            currency: row.get("currency"),
+            release_date_placeholder: row.get("release_date_placeholder"),
+            catalogue_prefix: row.get("catalogue_prefix"),
+            placeholder_images: PlaceholderImages::from_env(),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
     }
 
+    /// Get a record label by id, using the in-memory cache
+    ///
+    /// The label is read from the database on a cache miss, then cached until it expires or the label is updated.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `cache` - The record label cache, kept in `AppState`
+    /// * `id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The record label
+    ///
+    /// # Errors
+    /// If the record label cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_id_cached(
+        pool: &PgPool,
+        cache: &RecordLabelCache,
+        id: i64,
+    ) -> Result<Self, RecordLabelError> {
+        if let Some(record_label) = cache.get(id) {
+            return Ok(record_label);
+        }
+
+        let record_label = Self::get_by_id(pool, id).await?;
+        cache.insert(record_label.clone());
+        Ok(record_label)
+    }
+
     /// Get a record label by slug
     ///
     /// # Arguments
@@ -148,7 +246,7 @@ impl RecordLabel {
     /// # Errors
     /// If the record label cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_slug(pool: &PgPool, slug: String) -> anyhow::Result<Self> {
+    pub async fn get_by_slug(pool: &PgPool, slug: String) -> Result<Self, RecordLabelError> {
         let row = sqlx::query("SELECT * FROM labels WHERE slug = $1")
             .bind(&slug)
             .fetch_one(pool)
@@ -158,10 +256,7 @@ impl RecordLabel {
             Ok(row) => row,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!(
-                    "Could not find record label with slug {}.",
-                    slug
-                ));
+                return Err(RecordLabelError::NotFoundBySlug(slug));
             }
         };
 
@@ -171,6 +266,11 @@ impl RecordLabel {
             slug: row.get("slug"),
             description: row.get("description"),
             isrc_base: row.get("isrc_base"),
+            require_approval: row.get("require_approval"),
+            currency: row.get("currency"),
+            release_date_placeholder: row.get("release_date_placeholder"),
+            catalogue_prefix: row.get("catalogue_prefix"),
+            placeholder_images: PlaceholderImages::from_env(),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         })
@@ -178,8 +278,11 @@ impl RecordLabel {
 
     /// Update a label
     ///
+    /// The updated label is put in the cache, so the next cached lookup reads the update.
+    ///
     /// # Arguments
     /// * `pool` - The database connection pool
+    /// * `cache` - The record label cache, kept in `AppState`
     ///
     /// # Returns
     /// The updated label
@@ -187,15 +290,25 @@ impl RecordLabel {
     /// # Errors
     /// If the label cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
+    pub async fn update(
+        mut self,
+        pool: &PgPool,
+        cache: &RecordLabelCache,
+    ) -> Result<Self, RecordLabelError> {
         self.slug = slugify(&self.name);
-        self.validate(pool).await?;
+        self.validate(pool)
+            .await
+            .map_err(|e| RecordLabelError::Invalid(e.to_string()))?;
 
-        let row = sqlx::query("UPDATE labels SET name = $1, slug=$2, description = $3, isrc_base = $4, updated_at = NOW() WHERE id = $5 RETURNING *")
+        let row = sqlx::query("UPDATE labels SET name = $1, slug=$2, description = $3, isrc_base = $4, require_approval = $5, currency = $6, release_date_placeholder = $7, catalogue_prefix = $8, updated_at = NOW() WHERE id = $9 RETURNING *")
             .bind(self.name)
             .bind(self.slug)
             .bind(self.description)
             .bind(self.isrc_base)
+            .bind(self.require_approval)
+            .bind(self.currency.clone())
+            .bind(self.release_date_placeholder.clone())
+            .bind(self.catalogue_prefix.clone())
             .bind(self.id)
             .fetch_one(pool)
             .await;
@@ -204,19 +317,26 @@ impl RecordLabel {
             Ok(row) => row,
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!("Could not update label."));
+                return Err(RecordLabelError::Update);
             }
         };
-
-        Ok(Self {
+        let record_label = Self {
             id: row.get("id"),
             name: row.get("name"),
             slug: row.get("slug"),
             description: row.get("description"),
             isrc_base: row.get("isrc_base"),
+            require_approval: row.get("require_approval"),
+            currency: row.get("currency"),
+            release_date_placeholder: row.get("release_date_placeholder"),
+            catalogue_prefix: row.get("catalogue_prefix"),
+            placeholder_images: PlaceholderImages::from_env(),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
-        })
+        };
+        cache.insert(record_label.clone());
+
+        Ok(record_label)
     }
 
     /// Get a labels artists
@@ -230,23 +350,30 @@ impl RecordLabel {
     /// # Errors
     /// If the artists cannot be retrieved, return an error
     #[cfg(feature = "ssr")]
-    pub async fn artists(self, pool: &PgPool, include_hidden: bool) -> anyhow::Result<Vec<Artist>> {
+    pub async fn artists(
+        self,
+        pool: &PgPool,
+        include_hidden: bool,
+    ) -> Result<Vec<Artist>, RecordLabelError> {
         let query = if include_hidden {
             "SELECT *
              FROM artists
              WHERE label_id = $1
-             ORDER BY deleted_at DESC, name ASC"
+             ORDER BY deleted_at DESC, sort_name_or_default(sort_name, name) ASC"
+                .to_string()
         } else {
-            "SELECT *
+            format!(
+                "SELECT *
             FROM artists
             WHERE label_id = $1
-              AND deleted_at IS NULL
-              AND published_at < NOW()
-              AND published_at IS NOT NULL
-            ORDER BY name ASC"
+              AND {}
+              AND unlisted = FALSE
+            ORDER BY sort_name_or_default(sort_name, name) ASC",
+                published_clause("")
+            )
         };
 
-        let artists = sqlx::query_as::<_, Artist>(query)
+        let artists = sqlx::query_as::<_, Artist>(&query)
             .bind(self.id)
             //.bind(chrono::Utc::now())
             .fetch_all(pool)
@@ -256,7 +383,41 @@ impl RecordLabel {
             Ok(artists) => Ok(artists),
             Err(e) => {
                 eprintln!("{e}");
-                Err(anyhow::anyhow!("Could not find artists"))
+                Err(RecordLabelError::Artists)
+            }
+        }
+    }
+
+    /// Suggest the next catalogue number for the label
+    ///
+    /// The number is the label's catalogue prefix followed by a zero-padded sequence, e.g. "CAT-0042".
+    /// The sequence carries on from the highest number already used with the prefix, including on deleted releases.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The suggested catalogue number
+    ///
+    /// # Errors
+    /// If the existing catalogue numbers cannot be retrieved, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn next_catalogue_number(&self, pool: &PgPool) -> Result<String, RecordLabelError> {
+        let catalogue_numbers = sqlx::query_scalar::<_, String>(
+            "SELECT catalogue_number FROM releases WHERE label_id = $1 AND catalogue_number IS NOT NULL",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match catalogue_numbers {
+            Ok(catalogue_numbers) => Ok(next_catalogue_number(
+                &self.catalogue_prefix,
+                catalogue_numbers.iter().map(String::as_str),
+            )),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(RecordLabelError::CatalogueNumbers)
             }
         }
     }
@@ -272,23 +433,29 @@ impl RecordLabel {
     /// # Errors
     /// If the pages cannot be retrieved, return an error
     #[cfg(feature = "ssr")]
-    pub async fn pages(self, pool: &PgPool, include_hidden: bool) -> anyhow::Result<Vec<Page>> {
+    pub async fn pages(
+        self,
+        pool: &PgPool,
+        include_hidden: bool,
+    ) -> Result<Vec<Page>, RecordLabelError> {
         let query = if include_hidden {
             "SELECT *
              FROM pages
              WHERE label_id = $1
              ORDER BY deleted_at DESC, name ASC"
+                .to_string()
         } else {
-            "SELECT *
+            format!(
+                "SELECT *
             FROM pages
             WHERE label_id = $1
-              AND deleted_at IS NULL
-              AND published_at < NOW()
-              AND published_at IS NOT NULL
-            ORDER BY name ASC"
+              AND {}
+            ORDER BY name ASC",
+                published_clause("")
+            )
         };
 
-        let pages = sqlx::query_as::<_, Page>(query)
+        let pages = sqlx::query_as::<_, Page>(&query)
             .bind(self.id)
             //.bind(chrono::Utc::now())
             .fetch_all(pool)
@@ -298,7 +465,7 @@ impl RecordLabel {
             Ok(pages) => Ok(pages),
             Err(e) => {
                 eprintln!("{e}");
-                Err(anyhow::anyhow!("Could not find pages"))
+                Err(RecordLabelError::Pages)
             }
         }
     }
@@ -309,7 +476,7 @@ mod tests {
     use super::*;
     #[cfg(feature = "ssr")]
     use crate::models::test_helpers::{
-        create_test_artist, create_test_page, create_test_record_label,
+        create_test_artist, create_test_page, create_test_record_label, create_test_release,
     };
 
     #[test]
@@ -320,6 +487,11 @@ mod tests {
             slug: "test-label".to_string(),
             description: "This is a test label".to_string(),
             isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            placeholder_images: PlaceholderImages::default(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -331,6 +503,37 @@ mod tests {
         assert_eq!(record_label.isrc_base, "UK ABC".to_string());
     }
 
+    #[test]
+    fn test_record_label_error_display() {
+        let cases = [
+            (
+                RecordLabelError::Invalid("Name is required.".to_string()),
+                "Name is required.",
+            ),
+            (RecordLabelError::First, "Could not find label"),
+            (
+                RecordLabelError::NotFoundById(1),
+                "Could not find record label with id 1.",
+            ),
+            (
+                RecordLabelError::NotFoundBySlug("missing".to_string()),
+                "Could not find record label with slug missing.",
+            ),
+            (RecordLabelError::Update, "Could not update label."),
+            (RecordLabelError::Artists, "Could not find artists"),
+            (
+                RecordLabelError::CatalogueNumbers,
+                "Could not find catalogue numbers",
+            ),
+            (RecordLabelError::Pages, "Could not find pages"),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
     #[sqlx::test]
     async fn test_validate_success(pool: PgPool) {
         let record_label = RecordLabel {
@@ -339,6 +542,11 @@ mod tests {
             slug: "test-record-label".to_string(),
             description: "This is a test record label".to_string(),
             isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            placeholder_images: PlaceholderImages::default(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -356,6 +564,11 @@ mod tests {
             slug: "test-record-label".to_string(),
             description: "This is a test record label".to_string(),
             isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            placeholder_images: PlaceholderImages::default(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -378,6 +591,11 @@ mod tests {
             slug: "test-record-label".to_string(),
             description: "This is a test record label".to_string(),
             isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            placeholder_images: PlaceholderImages::default(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -391,6 +609,33 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_validate_catalogue_prefix_length(pool: PgPool) {
+        let mut record_label = RecordLabel {
+            id: 1,
+            name: "Test Record Label".to_string(),
+            slug: "test-record-label".to_string(),
+            description: "This is a test record label".to_string(),
+            isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "é".repeat(32),
+            placeholder_images: PlaceholderImages::default(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        assert!(record_label.validate(&pool).await.is_ok());
+
+        record_label.catalogue_prefix = "a".repeat(33);
+        let result = record_label.validate(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Catalogue prefix must be at most 32 characters.".to_string()
+        );
+    }
+
     #[sqlx::test]
     async fn test_validate_slug_length(pool: PgPool) {
         let slug = "a".repeat(256);
@@ -400,6 +645,11 @@ mod tests {
             slug,
             description: "This is a test record label".to_string(),
             isrc_base: "UK ABC".to_string(),
+            require_approval: false,
+            currency: "GBP".to_string(),
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            placeholder_images: PlaceholderImages::default(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -475,6 +725,65 @@ mod tests {
         assert_eq!(record_label.isrc_base, test_label.isrc_base);
     }
 
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_get_by_id_cached(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let test_label = create_test_record_label(&pool, 1).await.unwrap();
+        let record_label = RecordLabel::get_by_id_cached(&pool, &cache, test_label.id)
+            .await
+            .unwrap();
+        assert_eq!(record_label.name, test_label.name);
+
+        // Change the label behind the cache's back, the second lookup shouldn't query the database
+        sqlx::query("UPDATE labels SET name = 'Changed' WHERE id = $1")
+            .bind(test_label.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let record_label = RecordLabel::get_by_id_cached(&pool, &cache, test_label.id)
+            .await
+            .unwrap();
+        assert_eq!(record_label.name, test_label.name);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_update_caches_updated_label(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let test_label = create_test_record_label(&pool, 1).await.unwrap();
+        let mut record_label = RecordLabel::get_by_id_cached(&pool, &cache, test_label.id)
+            .await
+            .unwrap();
+
+        record_label.name = "Updated Label".to_string();
+        record_label.update(&pool, &cache).await.unwrap();
+
+        let record_label = RecordLabel::get_by_id_cached(&pool, &cache, test_label.id)
+            .await
+            .unwrap();
+        assert_eq!(record_label.name, "Updated Label");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_cache_ignores_stale_label(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let stale_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let mut record_label = stale_label.clone();
+        record_label.name = "Updated Label".to_string();
+        record_label.update(&pool, &cache).await.unwrap();
+
+        // A lookup that read the label before the update finishes after it
+        cache.insert(stale_label.clone());
+
+        let record_label = RecordLabel::get_by_id_cached(&pool, &cache, stale_label.id)
+            .await
+            .unwrap();
+        assert_eq!(record_label.name, "Updated Label");
+    }
+
     #[cfg(feature = "ssr")]
     #[sqlx::test]
     async fn test_get_by_slug_no_label(pool: PgPool) {
@@ -507,7 +816,10 @@ mod tests {
         record_label.name = "Updated Label".to_string();
         record_label.description = "This is an updated label".to_string();
         record_label.isrc_base = "UK XYZ".to_string();
-        let updated_label = record_label.update(&pool).await.unwrap();
+        let updated_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
         assert_eq!(updated_label.id, 1);
         assert_eq!(updated_label.name, "Updated Label".to_string());
         assert_eq!(updated_label.slug, "updated-label".to_string());
@@ -518,13 +830,42 @@ mod tests {
         assert_eq!(updated_label.isrc_base, "UK XYZ".to_string());
     }
 
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_update_label_currency(pool: PgPool) {
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        assert_eq!(record_label.currency, "GBP".to_string());
+        record_label.currency = "EUR".to_string();
+        let updated_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        assert_eq!(updated_label.currency, "EUR".to_string());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_update_label_invalid_currency(pool: PgPool) {
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.currency = "POUNDS".to_string();
+        let updated_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await;
+        assert_eq!(
+            updated_label.unwrap_err().to_string(),
+            "Currency must be a valid ISO 4217 code.".to_string()
+        );
+    }
+
     #[cfg(feature = "ssr")]
     #[sqlx::test]
     async fn test_update_label_change_id(pool: PgPool) {
         let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
         record_label.id = 2;
         record_label.name = "Updated Label".to_string();
-        let updated_label = record_label.update(&pool).await;
+        let updated_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await;
         assert!(updated_label.is_err());
         assert_eq!(
             updated_label.unwrap_err().to_string(),
@@ -559,6 +900,38 @@ mod tests {
         assert_eq!(artists, vec![published_artist]);
     }
 
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_artists_sorted_by_sort_name(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let mut names = vec![];
+        for (id, name) in [(1, "The Beatles"), (2, "Abba"), (3, "Coldplay")] {
+            let mut artist = create_test_artist(&pool, id, Some(record_label.clone()))
+                .await
+                .unwrap();
+            artist.name = name.to_string();
+            names.push(artist.update(&pool).await.unwrap());
+        }
+
+        let artists = record_label.clone().artists(&pool, false).await.unwrap();
+        let sorted = artists
+            .iter()
+            .map(|artist| artist.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(sorted, vec!["Abba", "The Beatles", "Coldplay"]);
+
+        // An explicit sort name overrides the derived one
+        let mut beatles = names.remove(0);
+        beatles.sort_name = Some("The Beatles".to_string());
+        beatles.update(&pool).await.unwrap();
+        let artists = record_label.artists(&pool, false).await.unwrap();
+        let sorted = artists
+            .iter()
+            .map(|artist| artist.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(sorted, vec!["Abba", "Coldplay", "The Beatles"]);
+    }
+
     #[cfg(feature = "ssr")]
     #[sqlx::test]
     async fn test_artists_include_hidden_artists(pool: PgPool) {
@@ -650,4 +1023,32 @@ mod tests {
             vec![unpublished_page, published_page, future_page, deleted_page,]
         );
     }
+
+    #[cfg(feature = "ssr")]
+    #[sqlx::test]
+    async fn test_next_catalogue_number(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        assert_eq!(
+            record_label.next_catalogue_number(&pool).await.unwrap(),
+            "CAT-0001"
+        );
+
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_release(&pool, 7, Some(artist)).await.unwrap();
+        let mut record_label = record_label;
+        record_label.catalogue_prefix = "TEST-".to_string();
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            record_label.next_catalogue_number(&pool).await.unwrap(),
+            "TEST-0008"
+        );
+    }
 }
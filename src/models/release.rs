@@ -4,15 +4,286 @@
 
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgConnection, PgPool};
+#[cfg(feature = "ssr")]
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
-use super::traits::Validate;
 #[cfg(feature = "ssr")]
 use super::{
-    artist::Artist, record_label::RecordLabel, track::Track, track_with_artists::TrackWithArtists,
+    artist::Artist,
+    genre::Genre,
+    query_helpers::{published_at_clause, published_clause, release_visible_clause},
+    release_credit::ReleaseCredit,
+    release_image::ReleaseImage,
+    slug::{SlugTable, unique_slug, unique_slug_with},
+    slug_redirect::SlugRedirect,
+    track::Track,
+    track_with_artists::TrackWithArtists,
+};
+use super::{record_label::RecordLabel, traits::Validate};
+use crate::config::image::{PlaceholderImage, PlaceholderImages};
+#[cfg(feature = "ssr")]
+use crate::config::slug::slug_collision_strategy;
+#[cfg(feature = "ssr")]
+use crate::utils::{
+    barcode::validate_barcode,
+    isrc::{generate_isrc, normalise_isrc_base},
+    pagination::Pagination,
+    search::search_patterns,
+    slugify::{is_reserved_slug, is_slug_locked, slugify},
+    unicode::normalise_name,
 };
+use crate::utils::{
+    currency::format_price,
+    files::{OG_IMAGE_DIRECTORY, og_image_file_name, upload_url, upload_url_or},
+    shorten_string::excerpt,
+    sort_name::default_sort_name,
+};
+
+/// Errors returned by the release model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ReleaseError {
+    /// The release is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// No release has the given ID
+    #[error("Could not find release with id {0}.")]
+    NotFoundById(i64),
+    /// No release has the given slug
+    #[error("Could not find release with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The artist has no release with the given slug on the record label
+    #[error(
+        "Could not find release {slug} for artist with id {artist_id} and record label with id {record_label_id}."
+    )]
+    NotFoundForArtist {
+        /// The slug of the release
+        slug: String,
+        /// The ID of the artist
+        artist_id: i64,
+        /// The ID of the record label
+        record_label_id: i64,
+    },
+    /// The next scheduled release could not be found
+    #[error("Could not find next scheduled release.")]
+    NextScheduled,
+    /// The upcoming releases for the record label could not be found
+    #[error("Could not find upcoming releases for record label with id {0}.")]
+    Upcoming(i64),
+    /// The scheduled releases for the record label could not be found
+    #[error("Could not find scheduled releases for record label with id {0}.")]
+    Scheduled(i64),
+    /// The latest releases for the record label could not be found
+    #[error("Could not find latest releases for record label with id {0}.")]
+    Latest(i64),
+    /// The releases for the artist could not be found
+    #[error(
+        "Could not find releases for artist with id {artist_id} and record label with id {record_label_id}."
+    )]
+    ListByArtist {
+        /// The ID of the artist
+        artist_id: i64,
+        /// The ID of the record label
+        record_label_id: i64,
+    },
+    /// The catalogue numbers on the record label could not be checked
+    #[error("Could not check catalogue numbers for record label with id {0}.")]
+    CatalogueNumber(i64),
+    /// The barcodes on the record label could not be checked
+    #[error("Could not check barcodes for record label with id {0}.")]
+    Barcode(i64),
+    /// The releases on the record label could not be searched
+    #[error("Could not search releases for record label with id {0}.")]
+    Search(i64),
+    /// The releases for the new release digest could not be found
+    #[error("Could not find digest releases for record label with id {0}.")]
+    Digest(i64),
+    /// The recently updated releases for the record label could not be found
+    #[error("Could not find recently updated releases for record label with id {0}.")]
+    RecentlyUpdated(i64),
+    /// The releases could not be marked as sent in a digest
+    #[error("Could not mark releases as sent.")]
+    MarkDigestSent,
+    /// The release could not be created
+    #[error("Could not create release.")]
+    Create,
+    /// The release could not be updated
+    #[error("Could not update release with id {0}.")]
+    Update(i64),
+    /// The release could not be deleted
+    #[error("Could not delete release with id {0}.")]
+    Delete(i64),
+    /// The release could not be archived or unarchived
+    #[error("Could not change the archived state of release with id {0}.")]
+    Archive(i64),
+    /// The archived releases for the record label could not be found
+    #[error("Could not find archived releases for record label with id {0}.")]
+    Archived(i64),
+    /// The old slug could not be redirected to the new one
+    #[error("Could not redirect release slug {0}.")]
+    Redirect(String),
+    /// The artists of the release could not be set
+    #[error("Could not set artists for release with id {0}.")]
+    SetArtists(i64),
+    /// The genres of the release could not be set
+    #[error("Could not set genres for release with id {0}.")]
+    SetGenres(i64),
+    /// The credits of the release could not be set
+    #[error("Could not set credits for release with id {0}.")]
+    SetCredits(i64),
+    /// The credits of the release could not be found
+    #[error("Could not find credits for release with id {0}.")]
+    Credits(i64),
+    /// The tracks of the release could not be renumbered
+    #[error("Could not reorder tracks for release with id {0}.")]
+    ReorderTracks(i64),
+    /// The published date of the releases could not be changed
+    #[error("Could not change the published date of releases.")]
+    BulkPublish,
+    /// The images of the release could not be added, removed or reordered
+    #[error("Could not update images for release with id {0}.")]
+    SetImages(i64),
+    /// The images of the release could not be found
+    #[error("Could not find images for release with id {0}.")]
+    Images(i64),
+    /// The release has no image with the given ID
+    #[error("Could not find image with id {0}.")]
+    ImageNotFound(i64),
+    /// The artists of the release could not be found
+    #[error("Could not find artists for release with id {0}.")]
+    Artists(i64),
+    /// The genres of the release could not be found
+    #[error("Could not find genres for release with id {0}.")]
+    Genres(i64),
+    /// The tracks of the release could not be found
+    #[error("Could not find tracks for release with id {0}.")]
+    Tracks(i64),
+    /// The tracks of the release could not be counted
+    #[error("Could not count tracks for release with id {0}.")]
+    CountTracks(i64),
+    /// An ISRC code could not be assigned to the track
+    #[error("Could not assign ISRC code to track with id {0}.")]
+    AssignIsrc(i64),
+    /// Any other database error
+    #[error("Could not access releases.")]
+    Database,
+}
+
+/// Generate a random token for previewing an embargoed release
+#[cfg(feature = "ssr")]
+fn generate_preview_token() -> String {
+    use rand::{Rng, distr::Alphanumeric};
+
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect::<String>()
+}
+
 #[cfg(feature = "ssr")]
-use crate::utils::slugify::slugify;
+impl From<sqlx::Error> for ReleaseError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
+/// The kind of release, used to group an artist's discography
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default, Hash)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum ReleaseType {
+    /// A full length album
+    #[default]
+    Album,
+    /// An extended play, longer than a single but shorter than an album
+    Ep,
+    /// A single, usually one or two tracks
+    Single,
+    /// A collection of tracks, often by several artists
+    Compilation,
+    /// A mixtape
+    Mixtape,
+}
+
+impl ReleaseType {
+    /// Every release type, in display order
+    pub const ALL: [Self; 5] = [
+        Self::Album,
+        Self::Ep,
+        Self::Single,
+        Self::Compilation,
+        Self::Mixtape,
+    ];
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Album => write!(f, "Album"),
+            Self::Ep => write!(f, "EP"),
+            Self::Single => write!(f, "Single"),
+            Self::Compilation => write!(f, "Compilation"),
+            Self::Mixtape => write!(f, "Mixtape"),
+        }
+    }
+}
+
+impl FromStr for ReleaseType {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|release_type| release_type.to_string().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Release type must be one of {}.",
+                    Self::ALL
+                        .map(|release_type| release_type.to_string())
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// The part an artist plays on a release
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default, Hash)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum ArtistRole {
+    /// The main artist, each release has exactly one
+    #[default]
+    Primary,
+    /// A featured artist, e.g. "Artist feat. Other"
+    Featured,
+    /// An artist who remixed the release
+    Remixer,
+}
+
+impl fmt::Display for ArtistRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Primary => write!(f, "Primary"),
+            Self::Featured => write!(f, "Featured"),
+            Self::Remixer => write!(f, "Remixer"),
+        }
+    }
+}
+
+/// How a release appears to the public
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReleaseStatus {
+    /// The release has no publish date, so it is hidden
+    Draft,
+    /// The release will be published in the future, so it is still hidden
+    Scheduled,
+    /// The release is under embargo, so it is only shown to preview token holders
+    Embargoed,
+    /// The release is public
+    Published,
+}
 
 /// The Release struct is used to represent a record release in the database.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq, Hash)]
@@ -22,8 +293,14 @@ pub struct Release {
     pub id: i64,
     /// The name of the release
     pub name: String,
+    /// The name used when sorting releases alphabetically
+    /// If this is None, the name is used with any leading article stripped
+    pub sort_name: Option<String>,
     /// The slug of the release
     pub slug: String,
+    /// Should the slug be kept when the name changes
+    /// If this is None, the slug is locked once the release has been published
+    pub lock_slug: Option<bool>,
     /// The description of the release
     pub description: String,
     /// The primary artist
@@ -32,9 +309,17 @@ pub struct Release {
     pub primary_artist_id: i64,
     /// The primary image of the release
     pub primary_image: Option<String>,
+    /// The file name of the first image in the gallery, used when there is no primary image
+    /// This is kept in sync by the image methods, see `Release::get_images`
+    pub gallery_image: Option<String>,
+    /// The kind of release, e.g. album or single
+    pub release_type: ReleaseType,
     /// The catalogue number of the release
     /// This is unique to the record label
     pub catalogue_number: String,
+    /// The UPC-A or EAN-13 barcode of the release
+    /// This is unique to the record label
+    pub barcode: Option<String>,
     /// The release date of the release
     /// This is the date the release is available to the public
     /// If this is None, the release is not released
@@ -51,10 +336,33 @@ pub struct Release {
     /// If this is in the future, the release is scheduled to be published
     /// If this is in the past, the release is published
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The date the release first went live, used for "in catalogue since" displays
+    /// This is set once and kept when the release is rescheduled or unpublished.
+    pub first_published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The date the embargo on the release lifts
+    /// Until then, the release is only shown to holders of the preview token, even once it is published.
+    /// Drafts are never shown, whatever the embargo.
+    pub embargo_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// The token given to the press to preview the release while it is under embargo
+    /// This is generated when an embargo is set.
+    pub preview_token: Option<String>,
+    /// Is the release unlisted
+    /// Unlisted releases are excluded from listings, but can still be reached by their slug
+    pub unlisted: bool,
+    /// The price of the release in the minor units of the record label's currency, e.g. pence
+    /// If this is None, the release is not for sale
+    pub price_cents: Option<i32>,
+    /// Is the release a compilation by various artists
+    /// The artists of a compilation are taken from its tracks, as well as those set on the release.
+    pub is_various: bool,
     /// The date and time the release was created in the database
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// The date and time the release was last updated
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the release was archived, e.g. when it went out of print
+    /// Archived releases are excluded from listings, but can still be reached by their slug.
+    /// If this is None, the release is not archived
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
     /// The date and time the release was deleted
     /// If this is None, the release is not deleted
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -71,48 +379,86 @@ impl Validate for Release {
                 "Name must be less than 255 characters.".to_string()
             ));
         }
+        if let Some(sort_name) = &self.sort_name
+            && sort_name.len() > 255
+        {
+            return Err(anyhow::anyhow!(
+                "Sort name must be less than 255 characters.".to_string()
+            ));
+        }
 
         if self.slug.len() > 255 {
             return Err(anyhow::anyhow!(
                 "Slug must be less than 255 characters.".to_string()
             ));
         }
+        if is_reserved_slug(&self.slug) {
+            return Err(anyhow::anyhow!("Slug is reserved.".to_string()));
+        }
         // Check that the slug is unique
-        if let Ok(release) = Self::get_by_slug(pool, self.slug.clone()).await
+        if let Ok(release) = Self::get_by_slug(pool, self.slug.clone(), true).await
             && release.id != self.id
         {
             return Err(anyhow::anyhow!("Slug must be unique.".to_string()));
         }
 
         // Check that the artist referenced in the primary_artist_id exists
-        if let Err(e) = Artist::get_by_id(pool, self.primary_artist_id).await {
-            tracing::error!("{e}");
-            return Err(anyhow::anyhow!(
-                "Artist with id {} does not exist.",
-                self.primary_artist_id
-            ));
+        match Artist::get_by_id(pool, self.primary_artist_id).await {
+            Ok(artist) if artist.deleted_at.is_some() && self.deleted_at.is_none() => {
+                return Err(anyhow::anyhow!(
+                    "Cannot add a release to a deleted artist.".to_string()
+                ));
+            }
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(anyhow::anyhow!(
+                    "Artist with id {} does not exist.",
+                    self.primary_artist_id
+                ));
+            }
         }
 
-        if self.catalogue_number.len() > 255 {
+        if self.catalogue_number.chars().count() > 255 {
             return Err(anyhow::anyhow!(
-                "Catalogue number must be less than 255 characters.".to_string()
+                "Catalogue number must be at most 255 characters.".to_string()
             ));
         }
-        // Check that the catalogue number is unique to the record label
-        let row = sqlx::query(
-            "SELECT * FROM releases WHERE catalogue_number = $1 AND label_id = $2 AND id != $3",
-        )
-        .bind(self.catalogue_number.clone())
-        .bind(self.label_id)
-        .bind(self.id)
-        .fetch_one(pool)
-        .await;
-        if row.is_ok() {
+        // Check that the catalogue number is unique to the record label, whatever its format
+        if Self::catalogue_number_taken(pool, self.label_id, &self.catalogue_number, Some(self.id))
+            .await?
+        {
             return Err(anyhow::anyhow!(
                 "Catalogue number must be unique.".to_string()
             ));
         }
 
+        if let Some(barcode) = &self.barcode {
+            validate_barcode(barcode)?;
+            if Self::barcode_taken(pool, self.label_id, barcode, self.id).await? {
+                return Err(anyhow::anyhow!("Barcode must be unique.".to_string()));
+            }
+        }
+
+        if self.price_cents.is_some_and(|price| price < 0) {
+            return Err(anyhow::anyhow!("Price must be 0 or greater.".to_string()));
+        }
+
+        // Drafts can be empty, but a release must have a track before it is published.
+        // A new release has no tracks yet, so it can only be created as a draft.
+        if self.published_at.is_some() {
+            let was_published = if self.id == 0 {
+                false
+            } else {
+                Self::get_by_id(pool, self.id).await?.published_at.is_some()
+            };
+            if !was_published && !self.has_tracks(pool).await? {
+                return Err(anyhow::anyhow!(
+                    "A release must have at least one track to be published.".to_string()
+                ));
+            }
+        }
+
         // Check that the record label exists
         if let Err(e) = RecordLabel::get_by_id(pool, self.label_id).await {
             tracing::error!("{e}");
@@ -126,25 +472,171 @@ impl Validate for Release {
     }
 }
 
+/// The clause matching the releases by the artist `$1`
+/// This is any release the artist is set on, and any compilation by various artists with a published track by them.
+#[cfg(feature = "ssr")]
+fn artist_release_clause() -> String {
+    format!(
+        "(EXISTS (
+    SELECT 1 FROM release_artists
+    WHERE release_artists.release_id = releases.id AND release_artists.artist_id = $1
+  ) OR (releases.is_various AND EXISTS (
+    SELECT 1 FROM tracks
+    INNER JOIN track_artists ON tracks.id = track_artists.track_id
+    WHERE tracks.release_id = releases.id AND {}
+     AND track_artists.artist_id = $1
+  )))",
+        published_clause("tracks")
+    )
+}
+
+/// An artist along with the track they are on, for loading the artists of many tracks at once
+#[cfg(feature = "ssr")]
+#[derive(FromRow)]
+struct TrackArtist {
+    /// The ID of the track
+    track_id: i64,
+    /// The artist
+    #[sqlx(flatten)]
+    artist: Artist,
+}
+
 impl Release {
     /// Get the primary image URL
-    /// If the primary image is None, return the default image
-    pub fn primary_image_url(&self) -> String {
-        self.primary_image.clone().map_or_else(
-            || "/Logo.svg".to_string(),
-            |file| format!("/uploads/releases/{file}"),
+    /// If the primary image is None, use the first image in the gallery, then the release placeholder
+    pub fn primary_image_url(&self, placeholders: &PlaceholderImages) -> String {
+        upload_url_or(
+            "releases",
+            self.primary_image
+                .as_deref()
+                .or(self.gallery_image.as_deref()),
+            placeholders.url(PlaceholderImage::Release),
+        )
+    }
+
+    /// The file name of the Open Graph share image for the release
+    #[must_use]
+    pub fn og_image_file_name(&self) -> String {
+        og_image_file_name(
+            "release",
+            self.id,
+            &self.name,
+            self.primary_image.as_deref(),
+        )
+    }
+
+    /// The URL of the Open Graph share image for the release
+    /// This stays the same until the name or artwork changes.
+    #[must_use]
+    pub fn og_image_url(&self) -> String {
+        upload_url(OG_IMAGE_DIRECTORY, Some(&self.og_image_file_name()))
+    }
+
+    /// A plain text excerpt of the description for listings and cards
+    /// Markdown is stripped, and long descriptions are cut on a word boundary with "…" appended.
+    #[must_use]
+    pub fn excerpt(&self, max_len: usize) -> String {
+        excerpt(&self.description, max_len)
+    }
+
+    /// The name used when sorting the release alphabetically
+    /// Falls back to the name with any leading article stripped
+    #[must_use]
+    pub fn sort_name_or_default(&self) -> String {
+        self.sort_name
+            .clone()
+            .filter(|sort_name| !sort_name.trim().is_empty())
+            .unwrap_or_else(|| default_sort_name(&self.name))
+    }
+
+    /// Get the price formatted in the record label's currency, e.g. "£9.99"
+    /// If the release has no price, return None
+    #[must_use]
+    pub fn formatted_price(&self, record_label: &RecordLabel) -> Option<String> {
+        self.price_cents.map(|price| {
+            if price == 0 {
+                "Free".to_string()
+            } else {
+                format_price(price, &record_label.currency)
+            }
+        })
+    }
+
+    /// The release date as shown to fans, e.g. "7 October 2025"
+    /// Announced releases without a date show the record label's placeholder, e.g. "TBA".
+    /// Drafts show nothing.
+    #[must_use]
+    pub fn release_date_display(&self, record_label: &RecordLabel) -> String {
+        if self
+            .published_at
+            .is_none_or(|date| date > chrono::Utc::now())
+        {
+            return String::new();
+        }
+
+        self.release_date.map_or_else(
+            || record_label.release_date_placeholder.clone(),
+            |date| date.format("%-d %B %Y").to_string(),
         )
     }
 
+    /// The status the release has at a moment in time, based on when it is published
+    ///
+    /// # Arguments
+    /// * `at` - The moment to check the status at
+    #[must_use]
+    pub fn status_at(&self, at: chrono::DateTime<chrono::Utc>) -> ReleaseStatus {
+        match self.published_at {
+            None => ReleaseStatus::Draft,
+            Some(_) if self.is_under_embargo_at(at) => ReleaseStatus::Embargoed,
+            Some(published_at) if published_at > at => ReleaseStatus::Scheduled,
+            Some(_) => ReleaseStatus::Published,
+        }
+    }
+
+    /// Is the release under embargo at a moment in time
+    #[must_use]
+    pub fn is_under_embargo_at(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        self.embargo_until
+            .is_some_and(|embargo_until| embargo_until > at)
+    }
+
+    /// Can the release be seen at a moment in time
+    /// Published releases are public. Embargoed releases are only shown to holders of the preview token.
+    ///
+    /// # Arguments
+    /// * `at` - The moment to check
+    /// * `preview_token` - The preview token supplied by the viewer, if any
+    #[must_use]
+    pub fn is_visible_at(
+        &self,
+        at: chrono::DateTime<chrono::Utc>,
+        preview_token: Option<&str>,
+    ) -> bool {
+        if self.deleted_at.is_some() {
+            return false;
+        }
+        match self.status_at(at) {
+            ReleaseStatus::Published => true,
+            ReleaseStatus::Embargoed => {
+                preview_token.is_some_and(|token| self.preview_token.as_deref() == Some(token))
+            }
+            ReleaseStatus::Draft | ReleaseStatus::Scheduled => false,
+        }
+    }
+
     /// Create a new release
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
     /// * `name` - The name of the release
     /// * `description` - The description of the release
-    /// * `catalogue_number` - The catalogue number of the release
+    /// * `catalogue_number` - The catalogue number of the release, generated from the record label's prefix if None
     /// * `release_date` - The release date of the release
     /// * `record_label_id` - The ID of the record label the release is signed to
+    /// * `published_at` - When the release is published
+    /// * `release_type` - The kind of release, e.g. album or single
+    /// * `barcode` - The UPC-A or EAN-13 barcode of the release
     ///
     /// # Returns
     /// The created release
@@ -152,6 +644,7 @@ impl Release {
     /// # Errors
     /// If the release cannot be created, return an error
     /// If the record label is not found, return an error
+    /// If a catalogue number cannot be generated, return an error
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "ssr")]
     pub async fn create(
@@ -159,45 +652,111 @@ impl Release {
         name: String,
         description: String,
         primary_artist_id: i64,
-        catalogue_number: String,
+        catalogue_number: Option<String>,
         release_date: Option<chrono::DateTime<chrono::Utc>>,
         record_label_id: i64,
         published_at: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> anyhow::Result<Self> {
-        let slug = slugify(&name);
+        release_type: ReleaseType,
+        barcode: Option<String>,
+    ) -> Result<Self, ReleaseError> {
+        let name = normalise_name(&name);
+        let slug = unique_slug(pool, SlugTable::Releases, slugify(&name))
+            .await
+            .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+        let generated = catalogue_number.is_none();
+        let catalogue_number = match catalogue_number {
+            Some(catalogue_number) => catalogue_number,
+            None => Self::generate_catalogue_number(pool, record_label_id).await?,
+        };
 
-        let release = Self {
+        let mut release = Self {
             id: 0,
             name,
             slug,
+            lock_slug: None,
+            sort_name: None,
             description,
             primary_artist_id,
             primary_image: None,
+            gallery_image: None,
+            release_type,
             catalogue_number,
             release_date,
             label_id: record_label_id,
             published_at,
+            first_published_at: published_at
+                .filter(|published_at| *published_at <= chrono::Utc::now()),
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
-        release.validate(pool).await?;
+        release
+            .validate(pool)
+            .await
+            .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+
+        let mut created = release.insert(pool).await;
+        // Another release may have taken the generated number since it was suggested, so try the next one
+        if generated
+            && let Err(e) = &created
+            && e.as_database_error()
+                .is_some_and(sqlx::error::DatabaseError::is_unique_violation)
+        {
+            release.catalogue_number =
+                Self::generate_catalogue_number(pool, record_label_id).await?;
+            created = release.insert(pool).await;
+        }
 
-        let release = sqlx::query_as::<_, Self>(
-         "INSERT INTO releases (name, slug, description, primary_artist_id, catalogue_number, release_date, label_id, published_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
+        match created {
+            Ok(release) => Ok(release),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Create)
+            }
+        }
+    }
+
+    /// Insert the release as a new row
+    #[cfg(feature = "ssr")]
+    async fn insert(&self, pool: &PgPool) -> Result<Self, sqlx::Error> {
+        sqlx::query_as::<_, Self>(
+         "INSERT INTO releases (name, slug, description, primary_artist_id, catalogue_number, release_date, label_id, published_at, first_published_at, release_type, barcode) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING *",
      )
-         .bind(release.name)
-         .bind(release.slug)
-         .bind(release.description)
-         .bind(release.primary_artist_id)
-         .bind(release.catalogue_number)
-         .bind(release.release_date)
-         .bind(release.label_id)
-         .bind(release.published_at)
+         .bind(&self.name)
+         .bind(&self.slug)
+         .bind(&self.description)
+         .bind(self.primary_artist_id)
+         .bind(&self.catalogue_number)
+         .bind(self.release_date)
+         .bind(self.label_id)
+         .bind(self.published_at)
+         .bind(self.first_published_at)
+         .bind(self.release_type)
+         .bind(&self.barcode)
          .fetch_one(pool)
-         .await?;
+         .await
+    }
 
-        Ok(release)
+    /// Generate the next catalogue number on the record label
+    #[cfg(feature = "ssr")]
+    async fn generate_catalogue_number(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<String, ReleaseError> {
+        let record_label = RecordLabel::get_by_id(pool, record_label_id)
+            .await
+            .map_err(|_| ReleaseError::CatalogueNumber(record_label_id))?;
+        record_label
+            .next_catalogue_number(pool)
+            .await
+            .map_err(|_| ReleaseError::CatalogueNumber(record_label_id))
     }
 
     /// Get release by id
@@ -211,8 +770,9 @@ impl Release {
     ///
     /// # Errors
     /// If the release cannot be found, return an error
+    /// If the database cannot be read, return `ReleaseError::Database`
     #[cfg(feature = "ssr")]
-    pub async fn get_by_id(pool: &PgPool, id: i64) -> anyhow::Result<Self> {
+    pub async fn get_by_id(pool: &PgPool, id: i64) -> Result<Self, ReleaseError> {
         let release = sqlx::query_as::<_, Self>("SELECT * FROM releases WHERE id = $1")
             .bind(id)
             .fetch_one(pool)
@@ -220,9 +780,10 @@ impl Release {
 
         match release {
             Ok(release) => Ok(release),
+            Err(sqlx::Error::RowNotFound) => Err(ReleaseError::NotFoundById(id)),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not find release with id {}.", id))
+                Err(ReleaseError::Database)
             }
         }
     }
@@ -232,6 +793,7 @@ impl Release {
     /// # Arguments
     /// * `pool` - The database connection pool
     /// * `slug` - The slug of the release
+    /// * `include_deleted` - Whether to resolve soft-deleted releases (admin edit and restore flows)
     ///
     /// # Returns
     /// The release
@@ -239,8 +801,18 @@ impl Release {
     /// # Errors
     /// If the release cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_slug(pool: &PgPool, slug: String) -> anyhow::Result<Self> {
-        let release = sqlx::query_as::<_, Self>("SELECT * FROM releases WHERE slug = $1")
+    pub async fn get_by_slug(
+        pool: &PgPool,
+        slug: String,
+        include_deleted: bool,
+    ) -> Result<Self, ReleaseError> {
+        let query = if include_deleted {
+            "SELECT * FROM releases WHERE slug = $1"
+        } else {
+            "SELECT * FROM releases WHERE slug = $1 AND deleted_at IS NULL"
+        };
+
+        let release = sqlx::query_as::<_, Self>(query)
             .bind(slug.clone())
             .fetch_one(pool)
             .await;
@@ -249,10 +821,7 @@ impl Release {
             Ok(release) => Ok(release),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find release with slug {}.",
-                    slug
-                ))
+                Err(ReleaseError::NotFoundBySlug(slug))
             }
         }
     }
@@ -278,25 +847,28 @@ impl Release {
         record_label_id: i64,
         slug: String,
         include_hidden: bool,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, ReleaseError> {
         let query = if include_hidden {
             "SELECT releases.* FROM releases
              INNER JOIN release_artists
              ON releases.id = release_artists.release_id
              WHERE release_artists.artist_id = $1 AND releases.label_id = $2 AND releases.slug = $3
-             ORDER BY deleted_at DESC, name ASC"
+             ORDER BY deleted_at DESC, sort_name_or_default(sort_name, name) ASC"
+                .to_string()
         } else {
-            "SELECT releases.* FROM releases
+            format!(
+                "SELECT releases.* FROM releases
              INNER JOIN release_artists
              ON releases.id = release_artists.release_id
              WHERE release_artists.artist_id = $1 AND releases.label_id = $2 AND releases.slug = $3
-              AND deleted_at IS NULL
-              AND published_at < NOW()
-              AND published_at IS NOT NULL
-             ORDER BY name ASC"
+              AND {}
+              AND (embargo_until IS NULL OR embargo_until < NOW())
+             ORDER BY sort_name_or_default(sort_name, name) ASC",
+                published_clause("")
+            )
         };
 
-        let release = sqlx::query_as::<_, Self>(query)
+        let release = sqlx::query_as::<_, Self>(&query)
             .bind(artist_id)
             .bind(record_label_id)
             .bind(slug.clone())
@@ -307,12 +879,11 @@ impl Release {
             Ok(release) => Ok(release),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find release {} for artist with id {} and record label with id {}.",
+                Err(ReleaseError::NotFoundForArtist {
                     slug,
                     artist_id,
                     record_label_id,
-                ))
+                })
             }
         }
     }
@@ -335,8 +906,11 @@ impl Release {
         pool: &PgPool,
         artist_id: Option<i64>,
         record_label_id: i64,
-    ) -> anyhow::Result<Option<Self>> {
-        let mut query = "SELECT * FROM releases WHERE release_date > NOW() AND published_at IS NOT NULL AND label_id = $1".to_string();
+    ) -> Result<Option<Self>, ReleaseError> {
+        let mut query = format!(
+            "SELECT * FROM releases WHERE release_date > NOW() AND published_at IS NOT NULL AND {} AND label_id = $1",
+            release_visible_clause("")
+        );
         if artist_id.is_some() {
             query.push_str(" AND primary_artist_id = $2");
         }
@@ -352,52 +926,57 @@ impl Release {
             Ok(release) => Ok(release),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not find next scheduled release."))
+                Err(ReleaseError::NextScheduled)
             }
         }
     }
 
-    /// List releases by artist and record label
-    /// This is used to get all releases by an artist on a record label
+    /// List the upcoming releases for a record label
+    /// Releases are ordered by release date, soonest first.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
-    /// * `artist_id` - The ID of the artist
     /// * `record_label_id` - The ID of the record label
-    /// * `include_hidden` - Whether to include unreleased releases
+    /// * `limit` - The maximum number of releases to return
+    /// * `include_hidden` - Whether to include unpublished and unlisted releases
     ///
     /// # Returns
-    /// The releases
+    /// The upcoming releases
     ///
     /// # Errors
     /// If there is an error getting the releases, return an error
     #[cfg(feature = "ssr")]
-    pub async fn list_by_artist_and_record_label(
+    pub async fn list_upcoming(
         pool: &PgPool,
-        artist_id: i64,
         record_label_id: i64,
+        limit: i64,
         include_hidden: bool,
-    ) -> anyhow::Result<Vec<Self>> {
+    ) -> Result<Vec<Self>, ReleaseError> {
         let query = if include_hidden {
-            "SELECT releases.* FROM releases
-             INNER JOIN release_artists
-             ON releases.id = release_artists.release_id
-             WHERE release_artists.artist_id = $1 AND releases.label_id = $2
-             ORDER BY deleted_at DESC, release_date DESC, name ASC"
-        } else {
-            "SELECT releases.* FROM releases
-             INNER JOIN release_artists
-             ON releases.id = release_artists.release_id
-             WHERE release_artists.artist_id = $1 AND releases.label_id = $2
+            "SELECT * FROM releases
+             WHERE label_id = $1
               AND deleted_at IS NULL
-              AND published_at < NOW()
-              AND published_at IS NOT NULL
-             ORDER BY release_date DESC, name ASC"
+              AND release_date > NOW()
+             ORDER BY release_date ASC, sort_name_or_default(sort_name, name) ASC
+             LIMIT $2"
+                .to_string()
+        } else {
+            format!(
+                "SELECT * FROM releases
+             WHERE label_id = $1
+              AND {}
+              AND release_date > NOW()
+              AND {}
+             ORDER BY release_date ASC, sort_name_or_default(sort_name, name) ASC
+             LIMIT $2",
+                published_clause(""),
+                release_visible_clause("")
+            )
         };
 
-        let releases = sqlx::query_as::<_, Self>(query)
-            .bind(artist_id)
+        let releases = sqlx::query_as::<_, Self>(&query)
             .bind(record_label_id)
+            .bind(limit)
             .fetch_all(pool)
             .await;
 
@@ -405,895 +984,4351 @@ impl Release {
             Ok(releases) => Ok(releases),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find releases for artist with id {} and record label with id {}.",
-                    artist_id,
-                    record_label_id
-                ))
+                Err(ReleaseError::Upcoming(record_label_id))
             }
         }
     }
 
-    /// Update an release
+    /// List every scheduled release for a record label
+    /// These are published releases with a release date in the future, soonest first.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
     ///
     /// # Returns
-    /// The updated release
+    /// The scheduled releases
     ///
     /// # Errors
-    /// If the release cannot be updated, return an error
-    ///
-    /// # Panics
-    /// If the release cannot be updated, return an error
+    /// If there is an error getting the releases, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.slug = slugify(&self.name);
-        self.validate(pool).await?;
+    pub async fn list_scheduled_releases(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let query = format!(
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND {}
+              AND release_date > NOW()
+              AND {}
+             ORDER BY release_date ASC, sort_name_or_default(sort_name, name) ASC",
+            published_clause(""),
+            release_visible_clause("")
+        );
+        let releases = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .fetch_all(pool)
+            .await;
 
-        let release = match sqlx::query_as::<_, Self>(
-            "UPDATE releases SET name = $1, slug = $2, description = $3, primary_artist_id = $4, primary_image = $5, catalogue_number = $6, release_date = $7, published_at = $8, updated_at = $9, deleted_at = $10 WHERE id = $11 RETURNING *",
-        )
-        .bind(self.name)
-        .bind(self.slug)
-        .bind(self.description)
-        .bind(self.primary_artist_id)
-        .bind(self.primary_image)
-        .bind(self.catalogue_number)
-        .bind(self.release_date)
-        .bind(self.published_at)
-        .bind(chrono::Utc::now())
-        .bind(self.deleted_at)
-        .bind(self.id)
-        .fetch_one(pool)
-        .await {
-            Ok(release) => release,
+        match releases {
+            Ok(releases) => Ok(releases),
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!(
-                    "Could not update release with id {}. {e}",
-                    self.id
-                ));
+                Err(ReleaseError::Scheduled(record_label_id))
             }
-        };
-
-        Ok(release)
+        }
     }
 
-    /// Delete an release
-    /// This is a soft delete
+    /// List the latest releases for a record label
+    /// Releases are ordered by release date, most recent first.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `limit` - The maximum number of releases to return
+    /// * `include_hidden` - Whether to include unpublished and unlisted releases
     ///
     /// # Returns
-    /// The deleted release
+    /// The latest releases
     ///
     /// # Errors
-    /// If the release cannot be deleted, return an error
+    /// If there is an error getting the releases, return an error
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<Self> {
-        let release = sqlx::query_as::<_, Self>(
-            "UPDATE releases SET deleted_at = $1 WHERE id = $2 RETURNING *",
-        )
-        .bind(chrono::Utc::now())
-        .bind(self.id)
-        .fetch_one(pool)
-        .await;
+    pub async fn list_latest(
+        pool: &PgPool,
+        record_label_id: i64,
+        limit: i64,
+        include_hidden: bool,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let query = if include_hidden {
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND deleted_at IS NULL
+              AND release_date <= NOW()
+             ORDER BY release_date DESC, sort_name_or_default(sort_name, name) ASC
+             LIMIT $2"
+                .to_string()
+        } else {
+            format!(
+                "SELECT * FROM releases
+             WHERE label_id = $1
+              AND {}
+              AND release_date <= NOW()
+              AND {}
+             ORDER BY release_date DESC, sort_name_or_default(sort_name, name) ASC
+             LIMIT $2",
+                published_clause(""),
+                release_visible_clause("")
+            )
+        };
 
-        match release {
-            Ok(release) => Ok(release),
+        let releases = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await;
+
+        match releases {
+            Ok(releases) => Ok(releases),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not delete release with id {}.",
-                    self.id
-                ))
+                Err(ReleaseError::Latest(record_label_id))
             }
         }
     }
 
-    /// Set the artists for the release
+    /// List releases by artist and record label
+    /// This is used to get all releases by an artist on a record label
+    /// Compilations by various artists are included for every artist with a track on them.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
-    /// * `artist_ids` - The IDs of the artists
+    /// * `artist_id` - The ID of the artist
+    /// * `record_label_id` - The ID of the record label
+    /// * `include_hidden` - Whether to include unreleased releases
+    /// * `release_type` - Only include releases of this kind, or every kind if None
+    ///
     /// # Returns
-    /// The release
+    /// The releases
+    ///
     /// # Errors
-    /// If the release cannot be updated, return an error
-    /// # Panics
-    /// If the release cannot be updated, return an error
+    /// If there is an error getting the releases, return an error
     #[cfg(feature = "ssr")]
-    pub async fn set_artists(&self, pool: &PgPool, artist_ids: Vec<i64>) -> anyhow::Result<Self> {
-        if artist_ids.is_empty() {
-            return Err(anyhow::anyhow!("Artist IDs cannot be empty."));
-        }
+    pub async fn list_by_artist_and_record_label(
+        pool: &PgPool,
+        artist_id: i64,
+        record_label_id: i64,
+        include_hidden: bool,
+        release_type: Option<ReleaseType>,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let artist_releases = artist_release_clause();
+        let query = if include_hidden {
+            format!(
+                "SELECT releases.* FROM releases
+             WHERE {artist_releases} AND releases.label_id = $2
+              AND ($3::ReleaseType IS NULL OR release_type = $3)
+             ORDER BY COALESCE(releases.id = (SELECT pinned_release_id FROM artists WHERE id = $1), FALSE) DESC,
+              deleted_at DESC, release_date DESC, sort_name_or_default(sort_name, name) ASC"
+            )
+        } else {
+            format!(
+                "SELECT releases.* FROM releases
+             WHERE {artist_releases} AND releases.label_id = $2
+              AND ($3::ReleaseType IS NULL OR release_type = $3)
+              AND {}
+              AND {}
+             ORDER BY COALESCE(releases.id = (SELECT pinned_release_id FROM artists WHERE id = $1), FALSE) DESC,
+              release_date DESC, sort_name_or_default(sort_name, name) ASC",
+                published_clause(""),
+                release_visible_clause("")
+            )
+        };
 
-        let mut tx = pool.begin().await?;
+        let releases = sqlx::query_as::<_, Self>(&query)
+            .bind(artist_id)
+            .bind(record_label_id)
+            .bind(release_type)
+            .fetch_all(pool)
+            .await;
 
-        // Delete all artists for the release
-        sqlx::query("DELETE FROM release_artists WHERE release_id = $1")
-            .bind(self.id)
-            .execute(&mut *tx)
-            .await?;
+        match releases {
+            Ok(releases) => Ok(releases),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::ListByArtist {
+                    artist_id,
+                    record_label_id,
+                })
+            }
+        }
+    }
 
-        // Insert the new artists
-        for artist_id in artist_ids {
-            match sqlx::query("INSERT INTO release_artists (release_id, artist_id) VALUES ($1, $2)")
-                .bind(self.id)
-                .bind(artist_id)
-                .execute(&mut *tx)
-                .await
-            {
-                Ok(_) => (),
-                Err(e) => {
-                    tracing::error!("{e}");
-                    return Err(anyhow::anyhow!(
-                        "Could not set artists for release with id {}.",
-                        self.id
-                    ));
-                }
+    /// Search the public releases on a record label by name or catalogue number
+    /// Exact matches come first, then prefix matches, then substring matches.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `query` - The search query
+    /// * `pagination` - The page of results to return
+    ///
+    /// # Returns
+    /// The matching releases
+    ///
+    /// # Errors
+    /// If the releases cannot be searched, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn search(
+        pool: &PgPool,
+        record_label_id: i64,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let patterns = search_patterns(query);
+        let query = format!(
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND (name ILIKE $4 OR catalogue_number ILIKE $4)
+              AND {}
+              AND {}
+             ORDER BY CASE
+               WHEN name ILIKE $2 OR catalogue_number ILIKE $2 THEN 0
+               WHEN name ILIKE $3 OR catalogue_number ILIKE $3 THEN 1
+               ELSE 2
+              END,
+              sort_name_or_default(sort_name, name) ASC,
+              id ASC
+             LIMIT $5 OFFSET $6",
+            published_clause(""),
+            release_visible_clause("")
+        );
+        let releases = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(patterns.exact)
+            .bind(patterns.prefix)
+            .bind(patterns.contains)
+            .bind(pagination.limit())
+            .bind(pagination.offset())
+            .fetch_all(pool)
+            .await;
+
+        match releases {
+            Ok(releases) => Ok(releases),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Search(record_label_id))
             }
         }
+    }
 
-        let _ = tx.commit().await;
+    /// List the releases for a record label's new release digest
+    /// Only public releases published since the given time, that haven't already been sent in a digest, are included.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `since` - Only include releases published after this time
+    ///
+    /// # Returns
+    /// The releases, oldest first
+    ///
+    /// # Errors
+    /// If there is an error getting the releases, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_for_digest(
+        pool: &PgPool,
+        record_label_id: i64,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let query = format!(
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND {}
+              AND published_at > $2
+              AND {}
+              AND NOT EXISTS (SELECT 1 FROM digest_releases WHERE digest_releases.release_id = releases.id)
+             ORDER BY published_at ASC, sort_name_or_default(sort_name, name) ASC",
+            published_clause(""),
+            release_visible_clause("")
+        );
+        let releases = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(since)
+            .fetch_all(pool)
+            .await;
 
-        Ok(self.clone())
+        match releases {
+            Ok(releases) => Ok(releases),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Digest(record_label_id))
+            }
+        }
     }
 
-    /// Get the artists for the release
+    /// List the releases on a record label that were most recently updated, including drafts
+    /// Deleted releases are excluded.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
     ///
     /// # Returns
-    /// The artists for the release
+    /// The releases, most recently updated first
     ///
     /// # Errors
-    /// If the release cannot be found, return an error
-    /// # Panics
-    /// If the release cannot be found, return an error
+    /// If the releases cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_artists(&self, pool: &PgPool) -> anyhow::Result<Vec<Artist>> {
-        let artists = sqlx::query_as::<_, Artist>(
-            "SELECT artists.* FROM artists
-             INNER JOIN release_artists ON artists.id = release_artists.artist_id
-             WHERE release_artists.release_id = $1",
+    pub async fn list_recently_updated(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let releases = sqlx::query_as::<_, Self>(
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND deleted_at IS NULL
+             ORDER BY updated_at DESC, id DESC",
         )
-        .bind(self.id)
+        .bind(record_label_id)
         .fetch_all(pool)
-        .await?;
+        .await;
 
-        Ok(artists)
+        match releases {
+            Ok(releases) => Ok(releases),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::RecentlyUpdated(record_label_id))
+            }
+        }
     }
 
-    /// Get the tracks for the release
+    /// Record that the releases have been sent in a digest, so they are not sent again
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `release_ids` - The IDs of the releases that were sent
+    ///
+    /// # Errors
+    /// If the releases cannot be marked as sent, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn mark_digest_sent(pool: &PgPool, release_ids: &[i64]) -> Result<(), ReleaseError> {
+        if let Err(e) = sqlx::query(
+            "INSERT INTO digest_releases (release_id) SELECT * FROM UNNEST($1::BIGINT[]) ON CONFLICT DO NOTHING",
+        )
+        .bind(release_ids)
+        .execute(pool)
+        .await
+        {
+            tracing::error!("{e}");
+            return Err(ReleaseError::MarkDigestSent);
+        }
+
+        Ok(())
+    }
+
+    /// Stamp the first published date on releases whose scheduled publish date has passed
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
     ///
     /// # Returns
-    /// The tracks for the release
+    /// The number of releases stamped
     ///
     /// # Errors
-    /// If the release cannot be found, return an error
+    /// If the releases cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn stamp_first_published(pool: &PgPool) -> Result<u64, ReleaseError> {
+        let query = format!(
+            "UPDATE releases SET first_published_at = published_at WHERE first_published_at IS NULL AND {}",
+            published_at_clause("")
+        );
+        let result = sqlx::query(&query).execute(pool).await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Update an release
+    /// Once the release has been published its slug is kept, unless `lock_slug` is explicitly false.
+    /// Renaming an explicitly unlocked release redirects the old slug to the new one.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The updated release
+    ///
+    /// # Errors
+    /// If the release cannot be updated, return an error
+    ///
     /// # Panics
-    /// If the release cannot be found, return an error
+    /// If the release cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_tracks(&self, pool: &PgPool) -> anyhow::Result<Vec<TrackWithArtists>> {
-        let tracks = sqlx::query_as::<_, Track>(
-            "SELECT tracks.* FROM tracks
-             WHERE tracks.release_id = $1
-             ORDER BY tracks.track_number ASC",
+    pub async fn update(self, pool: &PgPool) -> Result<Self, ReleaseError> {
+        let id = self.id;
+        let mut tx = pool.begin().await.map_err(|e| {
+            tracing::error!("{e}");
+            ReleaseError::Update(id)
+        })?;
+        let release = self.update_in(pool, &mut tx).await?;
+        tx.commit().await.map_err(|e| {
+            tracing::error!("{e}");
+            ReleaseError::Update(id)
+        })?;
+
+        Ok(release)
+    }
+
+    /// Update a release, writing through the given connection so it can be part of a larger transaction
+    /// The stored release and slugs are read from the pool, the release and any slug redirect are written through `conn`.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `conn` - The database connection, which may be part of a transaction
+    ///
+    /// # Returns
+    /// The updated release
+    ///
+    /// # Errors
+    /// If the release is invalid, or cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn update_in(
+        mut self,
+        pool: &PgPool,
+        conn: &mut PgConnection,
+    ) -> Result<Self, ReleaseError> {
+        self.name = normalise_name(&self.name);
+        let stored = Self::get_by_id(pool, self.id).await.ok();
+        let old_slug = stored
+            .as_ref()
+            .map_or_else(|| self.slug.clone(), |release| release.slug.clone());
+        // The first published date is never overwritten once it is set
+        self.first_published_at = stored
+            .as_ref()
+            .map_or(self.first_published_at, |release| {
+                release.first_published_at
+            })
+            .or_else(|| {
+                self.published_at
+                    .filter(|published_at| *published_at <= chrono::Utc::now())
+            });
+        let stored_published_at = stored.as_ref().and_then(|release| release.published_at);
+        if !is_slug_locked(self.lock_slug, &[stored_published_at, self.published_at]) {
+            self.slug = unique_slug_with(
+                pool,
+                SlugTable::Releases,
+                slugify(&self.name),
+                self.id,
+                slug_collision_strategy(),
+            )
+            .await
+            .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+        }
+        // A preview token only lasts while the release is under embargo.
+        // It is cleared once the release is public, and a new embargo gets a new token, so old press links stop working.
+        let now = chrono::Utc::now();
+        if !self.is_under_embargo_at(now) {
+            self.preview_token = None;
+        } else if self.preview_token.is_none()
+            || !stored
+                .as_ref()
+                .is_some_and(|release| release.is_under_embargo_at(now))
+        {
+            self.preview_token = Some(generate_preview_token());
+        }
+        self.validate(pool)
+            .await
+            .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+
+        let release = match sqlx::query_as::<_, Self>(
+            "UPDATE releases SET name = $1, sort_name = $2, slug = $3, lock_slug = $4, description = $5, primary_artist_id = $6, primary_image = $7, catalogue_number = $8, release_date = $9, published_at = $10, first_published_at = $11, embargo_until = $12, preview_token = $13, unlisted = $14, price_cents = $15, updated_at = $16, deleted_at = $17, release_type = $18, is_various = $19, barcode = $20 WHERE id = $21 RETURNING *",
         )
+        .bind(self.name)
+        .bind(self.sort_name)
+        .bind(self.slug)
+        .bind(self.lock_slug)
+        .bind(self.description)
+        .bind(self.primary_artist_id)
+        .bind(self.primary_image)
+        .bind(self.catalogue_number)
+        .bind(self.release_date)
+        .bind(self.published_at)
+        .bind(self.first_published_at)
+        .bind(self.embargo_until)
+        .bind(self.preview_token)
+        .bind(self.unlisted)
+        .bind(self.price_cents)
+        .bind(chrono::Utc::now())
+        .bind(self.deleted_at)
+        .bind(self.release_type)
+        .bind(self.is_various)
+        .bind(self.barcode)
         .bind(self.id)
-        .fetch_all(pool)
-        .await?;
+        .fetch_one(&mut *conn)
+        .await {
+            Ok(release) => release,
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(ReleaseError::Update(self.id));
+            }
+        };
+        if release.lock_slug == Some(false) && release.slug != old_slug {
+            SlugRedirect::create(&mut *conn, "release", release.id, &old_slug, &release.slug)
+                .await
+                .map_err(|e| {
+                    tracing::error!("{e}");
+                    ReleaseError::Redirect(old_slug)
+                })?;
+        }
 
-        let mut tracks_with_artists: Vec<TrackWithArtists> = Vec::new();
-        for track in &tracks {
-            // Ensure each track has its artists loaded
-            let artists = track.get_artists(pool).await?;
+        Ok(release)
+    }
 
-            tracks_with_artists.push(TrackWithArtists {
-                track: track.clone(),
-                artists: artists.clone(),
-            });
+    /// Delete an release
+    /// This is a soft delete
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The deleted release
+    ///
+    /// # Errors
+    /// If the release cannot be deleted, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn delete(&self, pool: &PgPool) -> Result<Self, ReleaseError> {
+        let release = sqlx::query_as::<_, Self>(
+            "UPDATE releases SET deleted_at = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(chrono::Utc::now())
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match release {
+            Ok(release) => Ok(release),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Delete(self.id))
+            }
         }
+    }
 
-        Ok(tracks_with_artists)
+    /// Archive or unarchive the release
+    /// Archived releases are kept out of listings, but are not deleted.
+    /// Archiving an already archived release keeps the original archive date.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `archived` - Whether the release should be archived
+    ///
+    /// # Returns
+    /// The updated release
+    ///
+    /// # Errors
+    /// If the release cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_archived(&self, pool: &PgPool, archived: bool) -> Result<Self, ReleaseError> {
+        let release = sqlx::query_as::<_, Self>(
+            "UPDATE releases
+             SET archived_at = CASE WHEN $1 THEN COALESCE(archived_at, NOW()) ELSE NULL END
+             WHERE id = $2
+             RETURNING *",
+        )
+        .bind(archived)
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match release {
+            Ok(release) => Ok(release),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Archive(self.id))
+            }
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Replace the preview token of a release under embargo, so links with the old token stop working
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The updated release
+    ///
+    /// # Errors
+    /// If the release is not under embargo, or cannot be updated, return an error
     #[cfg(feature = "ssr")]
-    use crate::models::test_helpers::{
-        create_test_artist, create_test_record_label, create_test_release, create_test_track,
-    };
+    pub async fn rotate_preview_token(&self, pool: &PgPool) -> Result<Self, ReleaseError> {
+        if !self.is_under_embargo_at(chrono::Utc::now()) {
+            return Err(ReleaseError::Invalid(
+                "Only releases under embargo have a preview link.".to_string(),
+            ));
+        }
+
+        let release = sqlx::query_as::<_, Self>(
+            "UPDATE releases SET preview_token = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+        )
+        .bind(generate_preview_token())
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match release {
+            Ok(release) => Ok(release),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Update(self.id))
+            }
+        }
+    }
+
+    /// Set the published date of many releases at once
+    /// Deleted releases are left alone. Passing `None` unpublishes the releases.
+    /// Releases without any tracks can't be published, so they are skipped when publishing.
+    /// Releases that go live now get their first published date, scheduled releases are stamped when they go live.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `ids` - The IDs of the releases
+    /// * `published_at` - The published date to set
+    ///
+    /// # Returns
+    /// The IDs of the releases updated
+    ///
+    /// # Errors
+    /// If the releases cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn bulk_set_published(
+        pool: &PgPool,
+        ids: Vec<i64>,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<i64>, ReleaseError> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut tx = pool.begin().await?;
+        let result = sqlx::query_scalar::<_, i64>(
+            "UPDATE releases
+             SET published_at = $1,
+                 first_published_at = CASE
+                     WHEN $1 <= NOW() THEN COALESCE(first_published_at, NOW())
+                     ELSE first_published_at
+                 END,
+                 preview_token = CASE
+                     WHEN embargo_until IS NULL OR embargo_until <= NOW() THEN NULL
+                     ELSE preview_token
+                 END,
+                 updated_at = NOW()
+             WHERE id = ANY($2::BIGINT[])
+              AND deleted_at IS NULL
+              AND (
+                  $1::TIMESTAMPTZ IS NULL
+                  OR EXISTS (SELECT 1 FROM tracks WHERE tracks.release_id = releases.id AND tracks.deleted_at IS NULL)
+              )
+             RETURNING id",
+        )
+        .bind(published_at)
+        .bind(&ids)
+        .fetch_all(&mut *tx)
+        .await;
+
+        let updated = match result {
+            Ok(updated) => updated,
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(ReleaseError::BulkPublish);
+            }
+        };
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// List the archived releases on a record label
+    /// Deleted releases are in the trash, so are excluded.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The archived releases, most recently archived first
+    ///
+    /// # Errors
+    /// If the releases cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_archived(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, ReleaseError> {
+        let releases = sqlx::query_as::<_, Self>(
+            "SELECT * FROM releases
+             WHERE label_id = $1
+              AND archived_at IS NOT NULL
+              AND deleted_at IS NULL
+             ORDER BY archived_at DESC, sort_name_or_default(sort_name, name) ASC",
+        )
+        .bind(record_label_id)
+        .fetch_all(pool)
+        .await;
+
+        match releases {
+            Ok(releases) => Ok(releases),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Archived(record_label_id))
+            }
+        }
+    }
+
+    /// Set the artists for the release
+    /// The artists are stored in the order given, so list the primary artist first.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `artists` - The IDs and roles of the artists, in display order
+    /// # Returns
+    /// The release
+    /// # Errors
+    /// If there is not exactly one primary artist, return an error
+    /// If the release cannot be updated, return an error
+    /// # Panics
+    /// If the release cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_artists(
+        &self,
+        pool: &PgPool,
+        artists: Vec<(i64, ArtistRole)>,
+    ) -> Result<Self, ReleaseError> {
+        if artists.is_empty() {
+            return Err(ReleaseError::Invalid(
+                "Artist IDs cannot be empty.".to_string(),
+            ));
+        }
+        if artists
+            .iter()
+            .filter(|(_, role)| *role == ArtistRole::Primary)
+            .count()
+            != 1
+        {
+            return Err(ReleaseError::Invalid(
+                "A release must have exactly one primary artist.".to_string(),
+            ));
+        }
+
+        let mut tx = pool.begin().await?;
+
+        // Delete all artists for the release
+        sqlx::query("DELETE FROM release_artists WHERE release_id = $1")
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Insert the new artists, keeping the order they were given in
+        for (sort_order, (artist_id, role)) in (0_i32..).zip(artists) {
+            match sqlx::query(
+                "INSERT INTO release_artists (release_id, artist_id, sort_order, role) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(self.id)
+            .bind(artist_id)
+            .bind(sort_order)
+            .bind(role)
+            .execute(&mut *tx)
+            .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ReleaseError::SetArtists(self.id));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(self.clone())
+    }
+
+    /// Set the genres for the release
+    /// Any existing genres are replaced, so an empty list clears them.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `genre_ids` - The IDs of the genres
+    ///
+    /// # Returns
+    /// The release
+    ///
+    /// # Errors
+    /// If the genres cannot be set, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_genres(
+        &self,
+        pool: &PgPool,
+        mut genre_ids: Vec<i64>,
+    ) -> Result<Self, ReleaseError> {
+        genre_ids.sort_unstable();
+        genre_ids.dedup();
+
+        let mut tx = pool.begin().await?;
+
+        // Delete all genres for the release
+        sqlx::query("DELETE FROM release_genres WHERE release_id = $1")
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Insert the new genres
+        for genre_id in genre_ids {
+            match sqlx::query("INSERT INTO release_genres (release_id, genre_id) VALUES ($1, $2)")
+                .bind(self.id)
+                .bind(genre_id)
+                .execute(&mut *tx)
+                .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ReleaseError::SetGenres(self.id));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(self.clone())
+    }
+
+    /// Set the credits for the release
+    /// Any existing credits are replaced.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `credits` - The credits, ordered by their `sort_order`
+    ///
+    /// # Returns
+    /// The credits for the release
+    ///
+    /// # Errors
+    /// If any of the credits are invalid, return an error
+    /// If the credits cannot be set, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_credits(
+        &self,
+        pool: &PgPool,
+        credits: Vec<ReleaseCredit>,
+    ) -> Result<Vec<ReleaseCredit>, ReleaseError> {
+        for credit in &credits {
+            credit
+                .validate()
+                .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+        }
+
+        let mut tx = pool.begin().await?;
+
+        // Delete all credits for the release
+        sqlx::query("DELETE FROM release_credits WHERE release_id = $1")
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Insert the new credits
+        for credit in credits {
+            match sqlx::query(
+                "INSERT INTO release_credits (release_id, role, name, sort_order) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(self.id)
+            .bind(credit.role.trim())
+            .bind(credit.name.trim())
+            .bind(credit.sort_order)
+            .execute(&mut *tx)
+            .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ReleaseError::SetCredits(self.id));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        self.get_credits(pool).await
+    }
+
+    /// Get the credits for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The credits for the release, in order
+    ///
+    /// # Errors
+    /// If the credits cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_credits(&self, pool: &PgPool) -> Result<Vec<ReleaseCredit>, ReleaseError> {
+        let credits = sqlx::query_as::<_, ReleaseCredit>(
+            "SELECT * FROM release_credits
+             WHERE release_id = $1
+             ORDER BY sort_order ASC, id ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match credits {
+            Ok(credits) => Ok(credits),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Credits(self.id))
+            }
+        }
+    }
+
+    /// Get the images in the gallery for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The images for the release, in order
+    ///
+    /// # Errors
+    /// If the images cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_images(&self, pool: &PgPool) -> Result<Vec<ReleaseImage>, ReleaseError> {
+        let images = sqlx::query_as::<_, ReleaseImage>(
+            "SELECT * FROM release_images
+             WHERE release_id = $1
+             ORDER BY position ASC, id ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match images {
+            Ok(images) => Ok(images),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Images(self.id))
+            }
+        }
+    }
+
+    /// Add an image to the end of the gallery for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `filename` - The file name of the uploaded image
+    /// * `alt_text` - A description of the image for screen readers
+    ///
+    /// # Returns
+    /// The added image
+    ///
+    /// # Errors
+    /// If the image is invalid, return an error
+    /// If the image cannot be added, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn add_image(
+        &self,
+        pool: &PgPool,
+        filename: &str,
+        alt_text: &str,
+    ) -> Result<ReleaseImage, ReleaseError> {
+        let image = ReleaseImage {
+            release_id: self.id,
+            filename: filename.trim().to_string(),
+            alt_text: alt_text.trim().to_string(),
+            ..Default::default()
+        };
+        image
+            .validate()
+            .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+
+        let mut tx = pool.begin().await?;
+
+        let image = match sqlx::query_as::<_, ReleaseImage>(
+            "INSERT INTO release_images (release_id, filename, position, alt_text)
+             VALUES ($1, $2, (SELECT COALESCE(MAX(position) + 1, 0) FROM release_images WHERE release_id = $1), $3)
+             RETURNING *",
+        )
+        .bind(self.id)
+        .bind(&image.filename)
+        .bind(&image.alt_text)
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Ok(image) => image,
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(ReleaseError::SetImages(self.id));
+            }
+        };
+        Self::sync_gallery_image(&mut tx, self.id).await?;
+
+        tx.commit().await?;
+
+        Ok(image)
+    }
+
+    /// Remove an image from the gallery for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `image_id` - The ID of the image
+    ///
+    /// # Returns
+    /// The remaining images for the release, in order
+    ///
+    /// # Errors
+    /// If the release has no image with the ID, return an error
+    /// If the image cannot be removed, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn remove_image(
+        &self,
+        pool: &PgPool,
+        image_id: i64,
+    ) -> Result<Vec<ReleaseImage>, ReleaseError> {
+        let mut tx = pool.begin().await?;
+
+        let removed = sqlx::query("DELETE FROM release_images WHERE id = $1 AND release_id = $2")
+            .bind(image_id)
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await;
+        match removed {
+            Ok(result) if result.rows_affected() == 0 => {
+                return Err(ReleaseError::ImageNotFound(image_id));
+            }
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(ReleaseError::SetImages(self.id));
+            }
+        }
+        Self::sync_gallery_image(&mut tx, self.id).await?;
+
+        tx.commit().await?;
+
+        self.get_images(pool).await
+    }
+
+    /// Reorder the images in the gallery for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `image_ids` - The IDs of every image for the release, in their new order
+    ///
+    /// # Returns
+    /// The images for the release, in order
+    ///
+    /// # Errors
+    /// If the IDs are not exactly the images for the release, return an error
+    /// If the images cannot be reordered, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn reorder_images(
+        &self,
+        pool: &PgPool,
+        image_ids: Vec<i64>,
+    ) -> Result<Vec<ReleaseImage>, ReleaseError> {
+        let mut tx = pool.begin().await?;
+
+        // Lock the images so they can't change while they are reordered
+        let mut current_ids = sqlx::query_scalar::<_, i64>(
+            "SELECT id FROM release_images WHERE release_id = $1 FOR UPDATE",
+        )
+        .bind(self.id)
+        .fetch_all(&mut *tx)
+        .await?;
+        current_ids.sort_unstable();
+        let mut new_ids = image_ids.clone();
+        new_ids.sort_unstable();
+        if current_ids != new_ids {
+            return Err(ReleaseError::Invalid(
+                "Image IDs must include every image for the release once.".to_string(),
+            ));
+        }
+
+        for (position, image_id) in (0_i32..).zip(image_ids) {
+            match sqlx::query("UPDATE release_images SET position = $1 WHERE id = $2")
+                .bind(position)
+                .bind(image_id)
+                .execute(&mut *tx)
+                .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ReleaseError::SetImages(self.id));
+                }
+            }
+        }
+        Self::sync_gallery_image(&mut tx, self.id).await?;
+
+        tx.commit().await?;
+
+        self.get_images(pool).await
+    }
+
+    /// Store the file name of the first gallery image on the release, for `primary_image_url`
+    #[cfg(feature = "ssr")]
+    async fn sync_gallery_image(
+        connection: &mut sqlx::PgConnection,
+        release_id: i64,
+    ) -> Result<(), ReleaseError> {
+        let updated = sqlx::query(
+            "UPDATE releases SET gallery_image = (
+                SELECT filename FROM release_images
+                WHERE release_id = $1
+                ORDER BY position ASC, id ASC
+                LIMIT 1
+             ) WHERE id = $1",
+        )
+        .bind(release_id)
+        .execute(connection)
+        .await;
+
+        match updated {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::SetImages(release_id))
+            }
+        }
+    }
+
+    /// Get the artists for the release
+    /// These are only the artists set on the release, the artists of a compilation's tracks are in `get_various_artists`.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The artists for the release, in the order they were set
+    ///
+    /// # Errors
+    /// If the release cannot be found, return an error
+    /// # Panics
+    /// If the release cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_artists(&self, pool: &PgPool) -> Result<Vec<Artist>, ReleaseError> {
+        let artists = sqlx::query_as::<_, Artist>(
+            "SELECT artists.* FROM artists
+             INNER JOIN release_artists ON artists.id = release_artists.artist_id
+             WHERE release_artists.release_id = $1
+             ORDER BY release_artists.sort_order ASC, artists.id ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match artists {
+            Ok(artists) => Ok(artists),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Artists(self.id))
+            }
+        }
+    }
+
+    /// Get the artists a compilation by various artists takes from its tracks
+    /// Only published tracks count, and artists already set on the release are left out.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The artists of the release's tracks, in track order, or none if the release isn't a compilation
+    ///
+    /// # Errors
+    /// If the artists cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_various_artists(&self, pool: &PgPool) -> Result<Vec<Artist>, ReleaseError> {
+        if !self.is_various {
+            return Ok(vec![]);
+        }
+
+        let query = format!(
+            "SELECT artists.* FROM artists
+             INNER JOIN (
+               SELECT track_artists.artist_id, MIN(tracks.track_number) AS position
+               FROM track_artists
+               INNER JOIN tracks ON tracks.id = track_artists.track_id
+               WHERE tracks.release_id = $1
+                AND {}
+               GROUP BY track_artists.artist_id
+             ) AS contributors ON artists.id = contributors.artist_id
+             WHERE NOT EXISTS (
+               SELECT 1 FROM release_artists
+               WHERE release_artists.release_id = $1 AND release_artists.artist_id = artists.id
+             )
+             ORDER BY contributors.position ASC NULLS LAST, artists.id ASC",
+            published_clause("tracks")
+        );
+        let artists = sqlx::query_as::<_, Artist>(&query)
+            .bind(self.id)
+            .fetch_all(pool)
+            .await;
+
+        match artists {
+            Ok(artists) => Ok(artists),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Artists(self.id))
+            }
+        }
+    }
+
+    /// Get the featured artists for the release, e.g. for "Artist feat. Other"
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The featured artists for the release, in the order they were set
+    ///
+    /// # Errors
+    /// If the artists cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_featured_artists(&self, pool: &PgPool) -> Result<Vec<Artist>, ReleaseError> {
+        self.get_artists_with_role(pool, ArtistRole::Featured).await
+    }
+
+    /// Get the artists who remixed the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The remixers of the release, in the order they were set
+    ///
+    /// # Errors
+    /// If the artists cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_remixers(&self, pool: &PgPool) -> Result<Vec<Artist>, ReleaseError> {
+        self.get_artists_with_role(pool, ArtistRole::Remixer).await
+    }
+
+    /// Get the artists with a role on the release, in the order they were set
+    #[cfg(feature = "ssr")]
+    async fn get_artists_with_role(
+        &self,
+        pool: &PgPool,
+        role: ArtistRole,
+    ) -> Result<Vec<Artist>, ReleaseError> {
+        let artists = sqlx::query_as::<_, Artist>(
+            "SELECT artists.* FROM artists
+             INNER JOIN release_artists ON artists.id = release_artists.artist_id
+             WHERE release_artists.release_id = $1
+              AND release_artists.role = $2
+             ORDER BY release_artists.sort_order ASC, artists.id ASC",
+        )
+        .bind(self.id)
+        .bind(role)
+        .fetch_all(pool)
+        .await;
+
+        match artists {
+            Ok(artists) => Ok(artists),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Artists(self.id))
+            }
+        }
+    }
+
+    /// Get the genres for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The genres for the release, by name
+    ///
+    /// # Errors
+    /// If the genres cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_genres(&self, pool: &PgPool) -> Result<Vec<Genre>, ReleaseError> {
+        let genres = sqlx::query_as::<_, Genre>(
+            "SELECT genres.* FROM genres
+             INNER JOIN release_genres ON genres.id = release_genres.genre_id
+             WHERE release_genres.release_id = $1
+             ORDER BY genres.name ASC, genres.id ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await;
+
+        match genres {
+            Ok(genres) => Ok(genres),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Genres(self.id))
+            }
+        }
+    }
+
+    /// Get the tracks for the release
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The tracks for the release
+    ///
+    /// # Errors
+    /// If the release cannot be found, return an error
+    /// # Panics
+    /// If the release cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_tracks(&self, pool: &PgPool) -> Result<Vec<TrackWithArtists>, ReleaseError> {
+        let tracks = sqlx::query_as::<_, Track>(
+            "SELECT tracks.* FROM tracks
+             WHERE tracks.release_id = $1
+             ORDER BY tracks.track_number ASC, tracks.name ASC",
+        )
+        .bind(self.id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("{e}");
+            ReleaseError::Tracks(self.id)
+        })?;
+
+        // Load the artists for every track at once, rather than a query per track
+        let track_ids = tracks.iter().map(|track| track.id).collect::<Vec<i64>>();
+        let track_artists = sqlx::query_as::<_, TrackArtist>(
+            "SELECT track_artists.track_id, artists.* FROM artists
+             INNER JOIN track_artists ON artists.id = track_artists.artist_id
+             WHERE track_artists.track_id = ANY($1)
+             ORDER BY artists.id ASC",
+        )
+        .bind(&track_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("{e}");
+            ReleaseError::Tracks(self.id)
+        })?;
+
+        let mut artists_by_track: HashMap<i64, Vec<Artist>> = HashMap::new();
+        for track_artist in track_artists {
+            artists_by_track
+                .entry(track_artist.track_id)
+                .or_default()
+                .push(track_artist.artist);
+        }
+        let tracks_with_artists = tracks
+            .into_iter()
+            .map(|track| TrackWithArtists {
+                artists: artists_by_track.remove(&track.id).unwrap_or_default(),
+                track,
+            })
+            .collect();
+
+        Ok(tracks_with_artists)
+    }
+
+    /// Renumber the tracks on the release, from 1 in the given order
+    /// Every number is cleared before the new ones are set, so tracks can swap places without clashing.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `ordered_track_ids` - The IDs of every track on the release, in their new order
+    ///
+    /// # Returns
+    /// The tracks for the release, in order
+    ///
+    /// # Errors
+    /// If the IDs are not exactly the tracks on the release, return an error
+    /// If the tracks cannot be renumbered, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn reorder_tracks(
+        &self,
+        pool: &PgPool,
+        ordered_track_ids: Vec<i64>,
+    ) -> Result<Vec<TrackWithArtists>, ReleaseError> {
+        let mut tx = pool.begin().await?;
+
+        // Lock the tracks so they can't change while they are renumbered
+        let mut current_ids =
+            sqlx::query_scalar::<_, i64>("SELECT id FROM tracks WHERE release_id = $1 FOR UPDATE")
+                .bind(self.id)
+                .fetch_all(&mut *tx)
+                .await?;
+        current_ids.sort_unstable();
+        let mut new_ids = ordered_track_ids.clone();
+        new_ids.sort_unstable();
+        if current_ids != new_ids {
+            return Err(ReleaseError::Invalid(
+                "Track IDs must include every track for the release once.".to_string(),
+            ));
+        }
+
+        if let Err(e) = sqlx::query("UPDATE tracks SET track_number = NULL WHERE release_id = $1")
+            .bind(self.id)
+            .execute(&mut *tx)
+            .await
+        {
+            tracing::error!("{e}");
+            return Err(ReleaseError::ReorderTracks(self.id));
+        }
+        if let Err(e) = sqlx::query(
+            "UPDATE tracks
+             SET track_number = ordered.track_number::INT, updated_at = NOW()
+             FROM UNNEST($1::BIGINT[]) WITH ORDINALITY AS ordered(id, track_number)
+             WHERE tracks.id = ordered.id",
+        )
+        .bind(&ordered_track_ids)
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!("{e}");
+            return Err(ReleaseError::ReorderTracks(self.id));
+        }
+
+        tx.commit().await?;
+
+        self.get_tracks(pool).await
+    }
+
+    /// Get the total length of the release, in seconds
+    /// Deleted tracks are not counted.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The sum of the track durations, or None if the release has no tracks or any track has no duration
+    ///
+    /// # Errors
+    /// If the tracks cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn total_duration(&self, pool: &PgPool) -> Result<Option<i32>, ReleaseError> {
+        let total_duration = sqlx::query_scalar::<_, Option<i32>>(
+            "SELECT CASE
+                WHEN COUNT(*) = 0 OR COUNT(duration_ms) < COUNT(*) THEN NULL
+                ELSE (SUM(duration_ms) / 1000)::INT
+             END
+             FROM tracks
+             WHERE release_id = $1
+              AND deleted_at IS NULL",
+        )
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match total_duration {
+            Ok(total_duration) => Ok(total_duration),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Tracks(self.id))
+            }
+        }
+    }
+
+    /// Is a catalogue number already used on the record label
+    ///
+    /// Numbers are compared by their `catalogue_sort_key`, so "WL-001" and "wl 1" collide.
+    /// Applies the same rule as validation, so the create form can warn before it is submitted.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The ID of the record label
+    /// * `catalogue_number` - The proposed catalogue number
+    ///
+    /// # Returns
+    /// True if a release on the record label already has the catalogue number
+    ///
+    /// # Errors
+    /// If the catalogue numbers cannot be checked, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn catalogue_number_in_use(
+        pool: &PgPool,
+        label_id: i64,
+        catalogue_number: &str,
+    ) -> Result<bool, ReleaseError> {
+        Self::catalogue_number_taken(pool, label_id, catalogue_number, None).await
+    }
+
+    /// Is a catalogue number used on the record label by a release other than `release_id`
+    #[cfg(feature = "ssr")]
+    async fn catalogue_number_taken(
+        pool: &PgPool,
+        label_id: i64,
+        catalogue_number: &str,
+        release_id: Option<i64>,
+    ) -> Result<bool, ReleaseError> {
+        let taken = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (
+                SELECT 1 FROM releases
+                WHERE label_id = $1
+                  AND catalogue_sort_key = catalogue_sort_key($2)
+                  AND ($3::BIGINT IS NULL OR id != $3)
+            )",
+        )
+        .bind(label_id)
+        .bind(catalogue_number)
+        .bind(release_id)
+        .fetch_one(pool)
+        .await;
+
+        match taken {
+            Ok(taken) => Ok(taken),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::CatalogueNumber(label_id))
+            }
+        }
+    }
+
+    /// Is a barcode already used by another release on the record label
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The ID of the record label
+    /// * `barcode` - The barcode to check
+    /// * `release_id` - The ID of the release being checked, which is ignored
+    ///
+    /// # Returns
+    /// True if another release has the barcode
+    ///
+    /// # Errors
+    /// If the barcodes cannot be checked, return an error
+    #[cfg(feature = "ssr")]
+    async fn barcode_taken(
+        pool: &PgPool,
+        label_id: i64,
+        barcode: &str,
+        release_id: i64,
+    ) -> Result<bool, ReleaseError> {
+        let taken = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (
+                SELECT 1 FROM releases
+                WHERE label_id = $1 AND barcode = $2 AND id != $3
+            )",
+        )
+        .bind(label_id)
+        .bind(barcode)
+        .bind(release_id)
+        .fetch_one(pool)
+        .await;
+
+        match taken {
+            Ok(taken) => Ok(taken),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::Barcode(label_id))
+            }
+        }
+    }
+
+    /// Can the release be published, checked inside a transaction
+    /// The release must not be deleted, and must have at least one track that is not deleted.
+    /// The release's row is locked until the transaction ends.
+    ///
+    /// # Arguments
+    /// * `conn` - The connection of the transaction
+    ///
+    /// # Returns
+    /// True if the release can be published
+    ///
+    /// # Errors
+    /// If the release cannot be checked, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn is_publishable_in(&self, conn: &mut PgConnection) -> Result<bool, ReleaseError> {
+        let publishable = sqlx::query_scalar::<_, bool>(
+            "SELECT deleted_at IS NULL
+                AND EXISTS (SELECT 1 FROM tracks WHERE tracks.release_id = releases.id AND tracks.deleted_at IS NULL)
+             FROM releases
+             WHERE id = $1
+             FOR UPDATE",
+        )
+        .bind(self.id)
+        .fetch_optional(&mut *conn)
+        .await;
+
+        match publishable {
+            Ok(publishable) => Ok(publishable.unwrap_or(false)),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::CountTracks(self.id))
+            }
+        }
+    }
+
+    /// Does the release have any tracks that are not deleted
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// True if the release has at least one track
+    ///
+    /// # Errors
+    /// If the tracks cannot be counted, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn has_tracks(&self, pool: &PgPool) -> Result<bool, ReleaseError> {
+        let has_tracks = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM tracks WHERE release_id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match has_tracks {
+            Ok(has_tracks) => Ok(has_tracks),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(ReleaseError::CountTracks(self.id))
+            }
+        }
+    }
+
+    /// Assign sequential ISRC codes to the tracks on the release that don't have one
+    ///
+    /// The next designation code follows on from the highest existing ISRC
+    /// with the same base and year, so the codes stay contiguous.
+    /// Assignments for the same base and year are serialised with an advisory lock.
+    /// Tracks that already have an ISRC code are left untouched.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `isrc_base` - The ISRC base of the record label
+    /// * `year` - The year of reference for the ISRC codes
+    ///
+    /// # Returns
+    /// The tracks that were assigned a new ISRC code, in track number order
+    ///
+    /// # Errors
+    /// If the ISRC base is invalid, return an error
+    /// If the tracks cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn assign_isrcs(
+        &self,
+        pool: &PgPool,
+        isrc_base: &str,
+        year: i32,
+    ) -> Result<Vec<Track>, ReleaseError> {
+        let prefix = format!(
+            "{}{:02}",
+            normalise_isrc_base(isrc_base),
+            year.rem_euclid(100)
+        );
+
+        let mut tx = pool.begin().await?;
+
+        // Hold a lock on the base and year until commit, so concurrent assignments can't pick the same designation
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(&prefix)
+            .execute(&mut *tx)
+            .await?;
+
+        let existing_codes: Vec<String> =
+            sqlx::query_scalar("SELECT isrc_code FROM tracks WHERE isrc_code LIKE $1")
+                .bind(format!("{prefix}%"))
+                .fetch_all(&mut *tx)
+                .await?;
+        let mut designation = existing_codes
+            .iter()
+            .filter_map(|code| code.get(prefix.len()..)?.parse::<i64>().ok())
+            .max()
+            .unwrap_or(0);
+
+        let tracks = sqlx::query_as::<_, Track>(
+            "SELECT * FROM tracks
+             WHERE release_id = $1
+               AND (isrc_code IS NULL OR isrc_code = '')
+               AND deleted_at IS NULL
+             ORDER BY track_number ASC
+             FOR UPDATE",
+        )
+        .bind(self.id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut updated_tracks = Vec::new();
+        for track in tracks {
+            designation += 1;
+            let isrc_code = generate_isrc(isrc_base, year, designation)
+                .map_err(|e| ReleaseError::Invalid(e.to_string()))?;
+            match sqlx::query_as::<_, Track>(
+                "UPDATE tracks SET isrc_code = $1, updated_at = $2 WHERE id = $3 RETURNING *",
+            )
+            .bind(isrc_code)
+            .bind(chrono::Utc::now())
+            .bind(track.id)
+            .fetch_one(&mut *tx)
+            .await
+            {
+                Ok(track) => updated_tracks.push(track),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(ReleaseError::AssignIsrc(track.id));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(updated_tracks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        fail_commits_on,
+    };
+
+    #[test]
+    #[allow(clippy::too_many_lines)] // one case per error variant
+    fn test_release_error_display() {
+        let cases = [
+            (
+                ReleaseError::Invalid("Name is required.".to_string()),
+                "Name is required.",
+            ),
+            (
+                ReleaseError::NotFoundById(1),
+                "Could not find release with id 1.",
+            ),
+            (
+                ReleaseError::NotFoundBySlug("missing".to_string()),
+                "Could not find release with slug missing.",
+            ),
+            (
+                ReleaseError::NotFoundForArtist {
+                    slug: "missing".to_string(),
+                    artist_id: 2,
+                    record_label_id: 3,
+                },
+                "Could not find release missing for artist with id 2 and record label with id 3.",
+            ),
+            (
+                ReleaseError::NextScheduled,
+                "Could not find next scheduled release.",
+            ),
+            (
+                ReleaseError::Upcoming(3),
+                "Could not find upcoming releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::Scheduled(3),
+                "Could not find scheduled releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::Latest(3),
+                "Could not find latest releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::CatalogueNumber(3),
+                "Could not check catalogue numbers for record label with id 3.",
+            ),
+            (
+                ReleaseError::Search(3),
+                "Could not search releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::ListByArtist {
+                    artist_id: 2,
+                    record_label_id: 3,
+                },
+                "Could not find releases for artist with id 2 and record label with id 3.",
+            ),
+            (
+                ReleaseError::Digest(3),
+                "Could not find digest releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::RecentlyUpdated(3),
+                "Could not find recently updated releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::MarkDigestSent,
+                "Could not mark releases as sent.",
+            ),
+            (ReleaseError::Create, "Could not create release."),
+            (
+                ReleaseError::Update(1),
+                "Could not update release with id 1.",
+            ),
+            (
+                ReleaseError::Delete(1),
+                "Could not delete release with id 1.",
+            ),
+            (
+                ReleaseError::Archive(1),
+                "Could not change the archived state of release with id 1.",
+            ),
+            (
+                ReleaseError::Archived(3),
+                "Could not find archived releases for record label with id 3.",
+            ),
+            (
+                ReleaseError::Redirect("old-slug".to_string()),
+                "Could not redirect release slug old-slug.",
+            ),
+            (
+                ReleaseError::SetArtists(1),
+                "Could not set artists for release with id 1.",
+            ),
+            (
+                ReleaseError::SetGenres(1),
+                "Could not set genres for release with id 1.",
+            ),
+            (
+                ReleaseError::SetCredits(1),
+                "Could not set credits for release with id 1.",
+            ),
+            (
+                ReleaseError::Credits(1),
+                "Could not find credits for release with id 1.",
+            ),
+            (
+                ReleaseError::SetImages(1),
+                "Could not update images for release with id 1.",
+            ),
+            (
+                ReleaseError::Images(1),
+                "Could not find images for release with id 1.",
+            ),
+            (
+                ReleaseError::ImageNotFound(2),
+                "Could not find image with id 2.",
+            ),
+            (
+                ReleaseError::Artists(1),
+                "Could not find artists for release with id 1.",
+            ),
+            (
+                ReleaseError::Genres(1),
+                "Could not find genres for release with id 1.",
+            ),
+            (
+                ReleaseError::Tracks(1),
+                "Could not find tracks for release with id 1.",
+            ),
+            (
+                ReleaseError::CountTracks(1),
+                "Could not count tracks for release with id 1.",
+            ),
+            (
+                ReleaseError::AssignIsrc(4),
+                "Could not assign ISRC code to track with id 4.",
+            ),
+            (
+                ReleaseError::ReorderTracks(1),
+                "Could not reorder tracks for release with id 1.",
+            ),
+            (
+                ReleaseError::BulkPublish,
+                "Could not change the published date of releases.",
+            ),
+            (
+                ReleaseError::Barcode(1),
+                "Could not check barcodes for record label with id 1.",
+            ),
+            (ReleaseError::Database, "Could not access releases."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[test]
+    fn test_release_date_display_announced_without_date() {
+        let record_label = RecordLabel {
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            ..Default::default()
+        };
+        let release = Release {
+            release_date: None,
+            published_at: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+
+        assert_eq!(release.release_date_display(&record_label), "TBA");
+    }
+
+    #[test]
+    fn test_release_date_display_with_date() {
+        let record_label = RecordLabel {
+            release_date_placeholder: "Coming soon".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            ..Default::default()
+        };
+        let release = Release {
+            release_date: Some(
+                chrono::DateTime::parse_from_rfc3339("2025-10-07T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&chrono::Utc),
+            ),
+            published_at: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            release.release_date_display(&record_label),
+            "7 October 2025"
+        );
+    }
+
+    #[test]
+    fn test_status_at() {
+        let now = chrono::Utc::now();
+        let mut release = Release::default();
+        assert_eq!(release.status_at(now), ReleaseStatus::Draft);
+
+        release.published_at = Some(now + chrono::Duration::days(1));
+        assert_eq!(release.status_at(now), ReleaseStatus::Scheduled);
+        assert_eq!(
+            release.status_at(now + chrono::Duration::days(2)),
+            ReleaseStatus::Published
+        );
+
+        release.published_at = Some(now);
+        assert_eq!(release.status_at(now), ReleaseStatus::Published);
+    }
+
+    #[test]
+    fn test_status_at_embargoed() {
+        let now = chrono::Utc::now();
+        let release = Release {
+            published_at: Some(now - chrono::Duration::days(1)),
+            embargo_until: Some(now + chrono::Duration::days(1)),
+            preview_token: Some("token".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(release.status_at(now), ReleaseStatus::Embargoed);
+        assert!(release.is_visible_at(now, Some("token")));
+        assert!(!release.is_visible_at(now, Some("other")));
+        assert!(!release.is_visible_at(now, None));
+        let lifted = now + chrono::Duration::days(2);
+        assert_eq!(release.status_at(lifted), ReleaseStatus::Published);
+        assert!(release.is_visible_at(lifted, None));
+    }
+
+    #[test]
+    fn test_is_visible_at_draft() {
+        let now = chrono::Utc::now();
+        let release = Release {
+            published_at: None,
+            embargo_until: Some(now + chrono::Duration::days(1)),
+            preview_token: Some("token".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(release.status_at(now), ReleaseStatus::Draft);
+        assert!(!release.is_visible_at(now, Some("token")));
+        assert!(!release.is_visible_at(now + chrono::Duration::days(2), None));
+    }
+
+    #[test]
+    fn test_release_date_display_draft() {
+        let record_label = RecordLabel {
+            release_date_placeholder: "TBA".to_string(),
+            catalogue_prefix: "CAT-".to_string(),
+            ..Default::default()
+        };
+        let release = Release {
+            release_date: None,
+            published_at: None,
+            ..Default::default()
+        };
+
+        assert_eq!(release.release_date_display(&record_label), "");
+    }
+
+    #[test]
+    fn test_release_type_from_str() {
+        for release_type in ReleaseType::ALL {
+            assert_eq!(
+                ReleaseType::from_str(&release_type.to_string()).unwrap(),
+                release_type
+            );
+        }
+        assert_eq!(ReleaseType::from_str(" ep ").unwrap(), ReleaseType::Ep);
+        assert_eq!(
+            ReleaseType::from_str("Boxset").unwrap_err().to_string(),
+            "Release type must be one of Album, EP, Single, Compilation, Mixtape."
+        );
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let mut release = Release {
+            description: "# Debut\n\nA **bold** first record, with [liner notes](https://example.com).\n\n```\ncode\n```".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(release.excerpt(24), "Debut A bold first…");
+        assert_eq!(
+            release.excerpt(100),
+            "Debut A bold first record, with liner notes."
+        );
+
+        release.description = String::new();
+        assert_eq!(release.excerpt(24), "");
+    }
+
+    #[test]
+    fn test_release_error_hides_database_error() {
+        let error = ReleaseError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, ReleaseError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+        let error: leptos::prelude::ServerFnError = error.into();
+        assert_eq!(
+            error.to_string(),
+            "error running server function: Could not access releases."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_success(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: "Test Release".to_string(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_validate_name_is_empty(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: String::new(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Name is required.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_name_length(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let name = "a".repeat(256);
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name,
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Name must be less than 255 characters.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_slug_length(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let slug = "a".repeat(256);
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: "Test Release".to_string(),
+            slug,
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Slug must be less than 255 characters.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_slug_unique(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut new_release = release.clone();
+        new_release.id = 2;
+        new_release.slug = release.slug.clone();
+
+        let result = new_release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Slug must be unique.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_primary_artist_does_not_exist(pool: PgPool) {
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: "Test Release".to_string(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: 1,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Artist with id 1 does not exist.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_catalogue_number_length(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let catalogue_number = "a".repeat(256);
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: "Test Release".to_string(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number,
+            release_date: Some(chrono::Utc::now()),
+            label_id: 1,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Catalogue number must be at most 255 characters.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_catalogue_number_unique(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut new_release = release.clone();
+        new_release.id = 2;
+        new_release.slug = "new-release-2".to_string();
+        new_release.catalogue_number = release.catalogue_number.clone();
+
+        let result = new_release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Catalogue number must be unique.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_catalogue_number_in_use_matches_validation(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        assert_eq!(release.catalogue_number, "TEST-1");
+
+        for (catalogue_number, in_use) in [
+            ("TEST-1", true),
+            ("test-1", true),
+            ("Test 001", true),
+            ("test1", true),
+            ("TEST-10", false),
+            ("TEST-1-LP", false),
+        ] {
+            let proactive =
+                Release::catalogue_number_in_use(&pool, release.label_id, catalogue_number)
+                    .await
+                    .unwrap();
+            assert_eq!(proactive, in_use, "{catalogue_number}");
+
+            let mut new_release = release.clone();
+            new_release.id = 2;
+            new_release.slug = "new-release-2".to_string();
+            new_release.catalogue_number = catalogue_number.to_string();
+            let result = new_release.validate(&pool).await;
+            assert_eq!(result.is_err(), proactive, "{catalogue_number}");
+        }
+
+        // The release doesn't collide with itself when it is validated
+        assert!(release.validate(&pool).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_catalogue_number_in_use_other_record_label(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let other_record_label = create_test_record_label(&pool, 2).await.unwrap();
+
+        assert!(
+            !Release::catalogue_number_in_use(&pool, other_record_label.id, "test-1")
+                .await
+                .unwrap()
+        );
+        assert!(
+            Release::catalogue_number_in_use(&pool, release.label_id, "test-1")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_record_label_exists(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let release = Release {
+            release_type: ReleaseType::default(),
+            id: 1,
+            name: "Test Release".to_string(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "This is a test release".to_string(),
+            primary_artist_id: artist.id,
+            primary_image: None,
+            gallery_image: None,
+            catalogue_number: "TEST-0001".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: 100,
+            published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
+            embargo_until: None,
+            preview_token: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            archived_at: None,
+            deleted_at: None,
+        };
+
+        let result = release.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Record Label with id 100 does not exist.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_price_negative(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.price_cents = Some(-1);
+
+        let result = release.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Price must be 0 or greater.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_barcode_ean_13(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.barcode = Some("4006381333931".to_string());
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.barcode, Some("4006381333931".to_string()));
+    }
+
+    #[sqlx::test]
+    async fn test_validate_barcode_check_digit(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.barcode = Some("4006381333932".to_string());
+
+        let result = release.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Barcode check digit is invalid.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_barcode_not_numeric(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.barcode = Some("40063813339A1".to_string());
+
+        let result = release.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Barcode must be a 12 digit UPC or 13 digit EAN.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_barcode_unique(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.barcode = Some("4006381333931".to_string());
+        release.update(&pool).await.unwrap();
+        let mut other_release = create_test_release(&pool, 2, Some(artist)).await.unwrap();
+        other_release.barcode = Some("4006381333931".to_string());
+
+        let result = other_release.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Barcode must be unique.".to_string()
+        );
+
+        // Another record label can use the same barcode
+        let mut other_label_release = create_test_release(&pool, 3, None).await.unwrap();
+        other_label_release.barcode = Some("4006381333931".to_string());
+        other_label_release.update(&pool).await.unwrap();
+    }
+
+    #[test]
+    fn test_formatted_price() {
+        let mut record_label = RecordLabel {
+            currency: "GBP".to_string(),
+            ..Default::default()
+        };
+        let release = Release {
+            price_cents: Some(999),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            release.formatted_price(&record_label),
+            Some("£9.99".to_string())
+        );
+
+        record_label.currency = "USD".to_string();
+        assert_eq!(
+            release.formatted_price(&record_label),
+            Some("$9.99".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formatted_price_free() {
+        let record_label = RecordLabel {
+            currency: "GBP".to_string(),
+            ..Default::default()
+        };
+        let release = Release {
+            price_cents: Some(0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            release.formatted_price(&record_label),
+            Some("Free".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formatted_price_not_for_sale() {
+        let record_label = RecordLabel::default();
+        let release = Release::default();
+
+        assert_eq!(release.formatted_price(&record_label), None);
+    }
+
+    #[sqlx::test]
+    async fn test_update_price(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.price_cents = Some(999);
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.price_cents, Some(999));
+    }
+
+    #[sqlx::test]
+    async fn test_validate_publish_without_tracks(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        release.published_at = Some(chrono::Utc::now());
+
+        let result = release.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "A release must have at least one track to be published.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_publish_with_track(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        release.published_at = Some(chrono::Utc::now());
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert!(release.published_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_validate_publish_with_deleted_track(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        track.delete(&pool).await.unwrap();
+        release.published_at = Some(chrono::Utc::now());
+
+        let result = release.update(&pool).await;
+
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = Release::create(
+            &pool,
+            "Test Release".to_string(),
+            "This is a test release".to_string(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            None,
+            record_label.id,
+            None,
+            ReleaseType::Album,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.name, "Test Release".to_string());
+        assert_eq!(release.description, "This is a test release".to_string());
+        assert!(release.published_at.is_none());
+        assert!(release.first_published_at.is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_create_published_without_tracks(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let result = Release::create(
+            &pool,
+            "Test Release".to_string(),
+            "This is a test release".to_string(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            None,
+            record_label.id,
+            Some(chrono::Utc::now()),
+            ReleaseType::Album,
+            None,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "A release must have at least one track to be published.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create_generates_catalogue_number(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut catalogue_numbers = vec![];
+        for name in ["First Release", "Second Release"] {
+            let release = Release::create(
+                &pool,
+                name.to_string(),
+                String::new(),
+                artist.id,
+                None,
+                None,
+                artist.label_id,
+                None,
+                ReleaseType::Album,
+                None,
+            )
+            .await
+            .unwrap();
+            catalogue_numbers.push(release.catalogue_number);
+        }
+
+        assert_eq!(catalogue_numbers, vec!["CAT-0001", "CAT-0002"]);
+    }
+
+    #[sqlx::test]
+    async fn test_create_stamps_timestamps_server_side(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let before = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let release = Release::create(
+            &pool,
+            "Test Release".to_string(),
+            String::new(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            None,
+            artist.label_id,
+            None,
+            ReleaseType::Album,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let after = chrono::Utc::now() + chrono::Duration::seconds(1);
+        assert!(release.created_at > before && release.created_at < after);
+        assert!(release.updated_at > before && release.updated_at < after);
+    }
+
+    #[sqlx::test]
+    async fn test_create_normalises_name(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = Release::create(
+            &pool,
+            "\u{c1}g\u{e6}tis byrjun".to_string(),
+            String::new(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            None,
+            record_label.id,
+            None,
+            ReleaseType::Album,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let other_release = Release::create(
+            &pool,
+            "A\u{301}g\u{e6}tis byrjun".to_string(),
+            String::new(),
+            artist.id,
+            Some("TEST-0002".to_string()),
+            None,
+            record_label.id,
+            None,
+            ReleaseType::Album,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.name, "\u{c1}g\u{e6}tis byrjun".to_string());
+        // The decomposed name has the same slug, so the second release gets a suffix
+        assert_eq!(other_release.slug, format!("{}-2", release.slug));
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_duplicate_names(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let mut slugs = vec![];
+        for _ in 0..3 {
+            let release = Release::create(
+                &pool,
+                "Live".to_string(),
+                String::new(),
+                artist.id,
+                None,
+                None,
+                record_label.id,
+                None,
+                ReleaseType::Album,
+                None,
+            )
+            .await
+            .unwrap();
+            slugs.push(release.slug);
+        }
+
+        assert_eq!(slugs, vec!["live", "live-2", "live-3"]);
+    }
+
+    #[sqlx::test]
+    async fn test_update_normalises_name(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.lock_slug = Some(false);
+        release.name = "A\u{301}g\u{e6}tis byrjun".to_string();
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.name, "\u{c1}g\u{e6}tis byrjun".to_string());
+        assert_eq!(release.slug, slugify("\u{c1}g\u{e6}tis byrjun"));
+    }
+
+    #[sqlx::test]
+    async fn test_create_for_deleted_artist(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let artist = artist.delete(&pool).await.unwrap();
+
+        let release = Release::create(
+            &pool,
+            "Test Release".to_string(),
+            String::new(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            None,
+            record_label.id,
+            None,
+            ReleaseType::Album,
+            None,
+        )
+        .await;
+
+        assert_eq!(
+            release.unwrap_err(),
+            ReleaseError::Invalid("Cannot add a release to a deleted artist.".to_string())
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_validation_error(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = Release::create(
+            &pool,
+            String::new(),
+            "This is a test release".to_string(),
+            artist.id,
+            Some("TEST-0001".to_string()),
+            Some(chrono::Utc::now()),
+            record_label.id,
+            Some(chrono::Utc::now()),
+            ReleaseType::Album,
+            None,
+        )
+        .await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Name is required.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_slug(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let release_by_slug = Release::get_by_slug(&pool, release.slug.clone(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(release, release_by_slug);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_slug_not_found(pool: PgPool) {
+        let release = Release::get_by_slug(&pool, "missing".to_string(), false).await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Could not find release with slug missing.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_slug_deleted(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let release = release.delete(&pool).await.unwrap();
+
+        let public_release = Release::get_by_slug(&pool, release.slug.clone(), false).await;
+        assert_eq!(
+            public_release.unwrap_err().to_string(),
+            "Could not find release with slug test-release-1.".to_string()
+        );
+
+        let admin_release = Release::get_by_slug(&pool, release.slug.clone(), true)
+            .await
+            .unwrap();
+        assert_eq!(admin_release.id, release.id);
+        assert!(admin_release.deleted_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_no_releases(pool: PgPool) {
+        let release =
+            Release::get_by_artist_and_record_label_and_slug(&pool, 1, 1, "test".to_string(), true)
+                .await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Could not find release test for artist with id 1 and record label with id 1."
+                .to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug(pool: PgPool) {
+        let test_release = create_test_release(&pool, 1, None).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            test_release.slug.clone(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.id, test_release.id);
+        assert_eq!(release.slug, test_release.slug);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_deleted_release_include_hidden(
+        pool: PgPool,
+    ) {
+        let deleted_release = create_test_release(&pool, 1, None).await.unwrap();
+        deleted_release.delete(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            deleted_release.slug.clone(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.id, deleted_release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_deleted_release(pool: PgPool) {
+        let deleted_release = create_test_release(&pool, 1, None).await.unwrap();
+        deleted_release.delete(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            deleted_release.slug.clone(),
+            false,
+        )
+        .await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_unpublished_release_include_hidden(
+        pool: PgPool,
+    ) {
+        let mut unpublished_release = create_test_release(&pool, 1, None).await.unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            unpublished_release.slug.clone(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.id, unpublished_release.id);
+        assert_eq!(release.slug, unpublished_release.slug);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_unpublished_release(pool: PgPool) {
+        let mut unpublished_release = create_test_release(&pool, 1, None).await.unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            unpublished_release.slug.clone(),
+            false,
+        )
+        .await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_future_release_include_hidden(
+        pool: PgPool,
+    ) {
+        let mut future_release = create_test_release(&pool, 1, None).await.unwrap();
+        future_release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        future_release.clone().update(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            future_release.slug.clone(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release.id, future_release.id);
+        assert_eq!(release.slug, future_release.slug);
+    }
+
+    #[sqlx::test]
+    async fn test_get_by_artist_and_record_label_and_slug_future_release(pool: PgPool) {
+        let mut future_release = create_test_release(&pool, 1, None).await.unwrap();
+        future_release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        future_release.clone().update(&pool).await.unwrap();
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            1,
+            1,
+            future_release.slug.clone(),
+            false,
+        )
+        .await;
+
+        assert!(release.is_err());
+        assert_eq!(
+            release.unwrap_err().to_string(),
+            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_next_scheduled_release_no_releases(pool: PgPool) {
+        let release = Release::get_next_scheduled_release(&pool, None, 1)
+            .await
+            .unwrap();
+        assert!(release.is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_get_next_scheduled_release_with_releases(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        release.clone().update(&pool).await.unwrap();
+
+        let next_release = Release::get_next_scheduled_release(&pool, None, release.label_id)
+            .await
+            .unwrap();
+
+        assert!(next_release.is_some());
+        assert_eq!(next_release.unwrap().id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_next_scheduled_release_with_artist(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(2));
+        release.clone().update(&pool).await.unwrap();
+        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+        let mut other_upcoming_release = create_test_release(&pool, 2, Some(other_artist.clone()))
+            .await
+            .unwrap();
+        other_upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        other_upcoming_release.clone().update(&pool).await.unwrap();
+
+        let next_release =
+            Release::get_next_scheduled_release(&pool, Some(artist.id), release.label_id)
+                .await
+                .unwrap();
+
+        assert!(next_release.is_some());
+        assert_eq!(next_release.unwrap().id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_list_upcoming(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut later_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        later_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(2));
+        later_release.clone().update(&pool).await.unwrap();
+        let mut sooner_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        sooner_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        sooner_release.clone().update(&pool).await.unwrap();
+        let mut unpublished_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+        create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let releases = Release::list_upcoming(&pool, artist.label_id, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![sooner_release.id, later_release.id]
+        );
+
+        let releases = Release::list_upcoming(&pool, artist.label_id, 10, true)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 3);
+
+        let releases = Release::list_upcoming(&pool, artist.label_id, 1, false)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_list_scheduled_releases(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut later_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        later_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(2));
+        later_release.clone().update(&pool).await.unwrap();
+        let mut sooner_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        sooner_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        sooner_release.clone().update(&pool).await.unwrap();
+        let mut unpublished_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+        let mut unlisted_release = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+        unlisted_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        unlisted_release.unlisted = true;
+        unlisted_release.clone().update(&pool).await.unwrap();
+        create_test_release(&pool, 5, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let releases = Release::list_scheduled_releases(&pool, artist.label_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![sooner_release.id, later_release.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list_latest(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut older_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        older_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(2));
+        older_release.clone().update(&pool).await.unwrap();
+        let mut newer_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        newer_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        newer_release.clone().update(&pool).await.unwrap();
+        let mut upcoming_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        upcoming_release.clone().update(&pool).await.unwrap();
+        let mut unlisted_release = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+        unlisted_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(3));
+        unlisted_release.unlisted = true;
+        unlisted_release.clone().update(&pool).await.unwrap();
+
+        let releases = Release::list_latest(&pool, artist.label_id, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![newer_release.id, older_release.id]
+        );
+
+        let releases = Release::list_latest(&pool, artist.label_id, 10, true)
+            .await
+            .unwrap();
+        assert_eq!(releases.len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_list_latest_excludes_embargoed(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        release.clone().update(&pool).await.unwrap();
+        let mut embargoed_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        embargoed_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        embargoed_release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        embargoed_release.clone().update(&pool).await.unwrap();
+
+        let releases = Release::list_latest(&pool, artist.label_id, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![release.id]
+        );
 
-    #[sqlx::test]
-    async fn test_validate_success(pool: PgPool) {
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let releases = Release::list_latest(&pool, artist.label_id, 10, true)
             .await
             .unwrap();
-        let release = Release {
-            id: 1,
-            name: "Test Release".to_string(),
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+        assert_eq!(releases.len(), 2);
+    }
 
-        let result = release.validate(&pool).await;
+    #[sqlx::test]
+    async fn test_set_archived_keeps_original_date(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
 
-        assert!(result.is_ok());
+        let archived = release.set_archived(&pool, true).await.unwrap();
+        let archived_at = archived.archived_at;
+        assert!(archived_at.is_some());
+        let archived = archived.set_archived(&pool, true).await.unwrap();
+        assert_eq!(archived.archived_at, archived_at);
+
+        let unarchived = archived.set_archived(&pool, false).await.unwrap();
+        assert_eq!(unarchived.archived_at, None);
     }
 
     #[sqlx::test]
-    async fn test_validate_name_is_empty(pool: PgPool) {
-        let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let release = Release {
-            id: 1,
-            name: String::new(),
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: 1,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+    async fn test_update_preview_token_follows_embargo(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        assert_eq!(release.preview_token, None);
+
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let embargoed = release.update(&pool).await.unwrap();
+        let preview_token = embargoed.preview_token.clone();
+        assert!(preview_token.is_some());
+
+        // Moving the embargo keeps the link the press already has
+        let mut moved = embargoed.clone();
+        moved.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(2));
+        let moved = moved.update(&pool).await.unwrap();
+        assert_eq!(moved.preview_token, preview_token);
+
+        // Lifting the embargo clears the token
+        let mut lifted = moved.clone();
+        lifted.embargo_until = None;
+        let lifted = lifted.update(&pool).await.unwrap();
+        assert_eq!(lifted.preview_token, None);
+
+        // A new embargo gets a new token
+        let mut embargoed_again = lifted.clone();
+        embargoed_again.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let embargoed_again = embargoed_again.update(&pool).await.unwrap();
+        assert!(embargoed_again.preview_token.is_some());
+        assert_ne!(embargoed_again.preview_token, preview_token);
+    }
 
-        let result = release.validate(&pool).await;
+    #[sqlx::test]
+    async fn test_rotate_preview_token(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
 
-        assert!(result.is_err());
+        let rotated = release.rotate_preview_token(&pool).await.unwrap();
+        assert!(rotated.preview_token.is_some());
+        assert_ne!(rotated.preview_token, release.preview_token);
+    }
+
+    #[sqlx::test]
+    async fn test_rotate_preview_token_not_embargoed(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let result = release.rotate_preview_token(&pool).await;
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Name is required.".to_string()
+            "Only releases under embargo have a preview link."
         );
     }
 
     #[sqlx::test]
-    async fn test_validate_name_length(pool: PgPool) {
+    async fn test_bulk_set_published(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let name = "a".repeat(256);
-        let release = Release {
-            id: 1,
-            name,
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: 1,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+        let mut ids = vec![];
+        for id in 1..=3 {
+            let mut release = create_test_release(&pool, id, Some(artist.clone()))
+                .await
+                .unwrap();
+            release.published_at = None;
+            let release = release.update(&pool).await.unwrap();
+            create_test_track(&pool, id, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+            ids.push(release.id);
+        }
+        let untouched = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+        let published_at = chrono::DateTime::from_timestamp(1_735_689_600, 0).unwrap();
 
-        let result = release.validate(&pool).await;
+        let updated = Release::bulk_set_published(&pool, ids.clone(), Some(published_at))
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Name must be less than 255 characters.".to_string()
-        );
+        assert_eq!(updated, ids);
+        for id in &ids {
+            let release = Release::get_by_id(&pool, *id).await.unwrap();
+            assert_eq!(release.published_at, Some(published_at));
+            assert!(release.first_published_at.is_some());
+        }
+        let untouched_after = Release::get_by_id(&pool, untouched.id).await.unwrap();
+        assert_eq!(untouched_after.published_at, untouched.published_at);
     }
 
     #[sqlx::test]
-    async fn test_validate_slug_length(pool: PgPool) {
+    async fn test_bulk_set_published_clears_lapsed_preview_token(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let slug = "a".repeat(256);
-        let release = Release {
-            id: 1,
-            name: "Test Release".to_string(),
-            slug,
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: 1,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), Some(artist))
+            .await
+            .unwrap();
+        // The embargo lifted on its own, leaving the token behind
+        sqlx::query(
+            "UPDATE releases SET embargo_until = NOW() - INTERVAL '1 day', preview_token = 'token' WHERE id = $1",
+        )
+        .bind(release.id)
+        .execute(&pool)
+        .await
+        .unwrap();
 
-        let result = release.validate(&pool).await;
+        Release::bulk_set_published(&pool, vec![release.id], Some(chrono::Utc::now()))
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Slug must be less than 255 characters.".to_string()
-        );
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.preview_token, None);
     }
 
     #[sqlx::test]
-    async fn test_validate_slug_unique(pool: PgPool) {
+    async fn test_bulk_set_published_skips_releases_without_tracks(pool: PgPool) {
+        let mut with_tracks = create_test_release(&pool, 1, None).await.unwrap();
+        with_tracks.published_at = None;
+        let with_tracks = with_tracks.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(with_tracks.clone()), None)
+            .await
+            .unwrap();
+        let mut without_tracks = create_test_release(&pool, 2, None).await.unwrap();
+        without_tracks.published_at = None;
+        let without_tracks = without_tracks.update(&pool).await.unwrap();
+
+        let updated = Release::bulk_set_published(
+            &pool,
+            vec![with_tracks.id, without_tracks.id],
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated, vec![with_tracks.id]);
+        let release = Release::get_by_id(&pool, without_tracks.id).await.unwrap();
+        assert_eq!(release.published_at, None);
+        assert_eq!(release.first_published_at, None);
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_set_published_keeps_first_published_at(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let mut new_release = release.clone();
-        new_release.id = 2;
-        new_release.slug = release.slug.clone();
+        let first_published_at = chrono::DateTime::from_timestamp(1_735_689_600, 0);
+        sqlx::query("UPDATE releases SET first_published_at = $1 WHERE id = $2")
+            .bind(first_published_at)
+            .bind(release.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
 
-        let result = new_release.validate(&pool).await;
+        Release::bulk_set_published(&pool, vec![release.id], Some(chrono::Utc::now()))
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Slug must be unique.".to_string()
-        );
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.first_published_at, first_published_at);
     }
 
     #[sqlx::test]
-    async fn test_primary_artist_does_not_exist(pool: PgPool) {
-        let release = Release {
-            id: 1,
-            name: "Test Release".to_string(),
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: 1,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: 1,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+    async fn test_bulk_set_published_scheduled(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let published_at = chrono::Utc::now() + chrono::Duration::days(7);
 
-        let result = release.validate(&pool).await;
+        Release::bulk_set_published(&pool, vec![release.id], Some(published_at))
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Artist with id 1 does not exist.".to_string()
-        );
+        // Stamped by the scheduler when it goes live
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.published_at.is_some());
+        assert_eq!(release.first_published_at, None);
     }
 
     #[sqlx::test]
-    async fn test_validate_catalogue_number_length(pool: PgPool) {
-        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+    async fn test_bulk_set_published_clears_publication(pool: PgPool) {
+        let release_one = create_test_release(&pool, 1, None).await.unwrap();
+        let release_two = create_test_release(&pool, 2, None).await.unwrap();
 
-        let catalogue_number = "a".repeat(256);
-        let release = Release {
-            id: 1,
-            name: "Test Release".to_string(),
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number,
-            release_date: Some(chrono::Utc::now()),
-            label_id: 1,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
+        let updated =
+            Release::bulk_set_published(&pool, vec![release_one.id, release_two.id], None)
+                .await
+                .unwrap();
 
-        let result = release.validate(&pool).await;
+        assert_eq!(updated.len(), 2);
+        for id in [release_one.id, release_two.id] {
+            let release = Release::get_by_id(&pool, id).await.unwrap();
+            assert_eq!(release.published_at, None);
+        }
+    }
 
-        assert!(result.is_err());
+    #[sqlx::test]
+    async fn test_bulk_set_published_no_ids(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let updated = Release::bulk_set_published(&pool, vec![], None)
+            .await
+            .unwrap();
+
+        assert!(updated.is_empty());
+        let release_after = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release_after.published_at, release.published_at);
+    }
+
+    #[sqlx::test]
+    async fn test_list_latest_excludes_archived(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+        let mut archived_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        archived_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let archived_release = archived_release.update(&pool).await.unwrap();
+        archived_release.set_archived(&pool, true).await.unwrap();
+
+        let releases = Release::list_latest(&pool, artist.label_id, 10, false)
+            .await
+            .unwrap();
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Catalogue number must be less than 255 characters.".to_string()
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![release.id]
         );
+        let release = Release::get_by_slug(&pool, archived_release.slug.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(release.id, archived_release.id);
     }
 
     #[sqlx::test]
-    async fn test_validate_catalogue_number_unique(pool: PgPool) {
-        let release = create_test_release(&pool, 1, None).await.unwrap();
-        let mut new_release = release.clone();
-        new_release.id = 2;
-        new_release.slug = "new-release-2".to_string();
-        new_release.catalogue_number = release.catalogue_number.clone();
+    async fn test_search_ranks_exact_catalogue_number_above_substring_match(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut substring = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        substring.name = "Alpha".to_string();
+        substring.catalogue_number = "XCAT-12".to_string();
+        let substring = substring.update(&pool).await.unwrap();
+        let mut exact = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        exact.name = "Beta".to_string();
+        exact.catalogue_number = "CAT-1".to_string();
+        let exact = exact.update(&pool).await.unwrap();
 
-        let result = new_release.validate(&pool).await;
+        let releases = Release::search(&pool, artist.label_id, "cat-1", Pagination::default())
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Catalogue number must be unique.".to_string()
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![exact.id, substring.id]
         );
     }
 
     #[sqlx::test]
-    async fn test_validate_record_label_exists(pool: PgPool) {
+    async fn test_search_ranks_prefix_name_above_mid_string_match(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut mid_string = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        mid_string.name = "A Night Out".to_string();
+        let mid_string = mid_string.update(&pool).await.unwrap();
+        let mut prefix = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        prefix.name = "Night Shift".to_string();
+        let prefix = prefix.update(&pool).await.unwrap();
 
-        let release = Release {
-            id: 1,
-            name: "Test Release".to_string(),
-            slug: "test-release".to_string(),
-            description: "This is a test release".to_string(),
-            primary_artist_id: artist.id,
-            primary_image: None,
-            catalogue_number: "TEST-0001".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: 100,
-            published_at: Some(chrono::Utc::now()),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            deleted_at: None,
-        };
-
-        let result = release.validate(&pool).await;
+        let releases = Release::search(&pool, artist.label_id, "night", Pagination::default())
+            .await
+            .unwrap();
 
-        assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Record Label with id 100 does not exist.".to_string()
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![prefix.id, mid_string.id]
         );
     }
 
     #[sqlx::test]
-    async fn test_create(pool: PgPool) {
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+    async fn test_search_excludes_embargoed(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-        let release = Release::create(
+        let mut embargoed_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        embargoed_release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        embargoed_release.update(&pool).await.unwrap();
+
+        let releases = Release::search(
             &pool,
-            "Test Release".to_string(),
-            "This is a test release".to_string(),
-            artist.id,
-            "TEST-0001".to_string(),
-            None,
-            record_label.id,
-            Some(chrono::Utc::now()),
+            artist.label_id,
+            "Test Release",
+            Pagination::default(),
         )
         .await
         .unwrap();
 
-        assert_eq!(release.name, "Test Release".to_string());
-        assert_eq!(release.description, "This is a test release".to_string());
+        assert_eq!(
+            releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![release.id]
+        );
     }
 
     #[sqlx::test]
-    async fn test_create_with_validation_error(pool: PgPool) {
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+    async fn test_list_by_artist_and_record_label_no_releases(pool: PgPool) {
+        let releases = Release::list_by_artist_and_record_label(&pool, 1, 1, true, None)
             .await
             .unwrap();
-        let release = Release::create(
-            &pool,
-            String::new(),
-            "This is a test release".to_string(),
-            artist.id,
-            "TEST-0001".to_string(),
-            Some(chrono::Utc::now()),
-            record_label.id,
-            Some(chrono::Utc::now()),
-        )
-        .await;
 
-        assert!(release.is_err());
-        assert_eq!(
-            release.unwrap_err().to_string(),
-            "Name is required.".to_string()
-        );
+        assert_eq!(releases.len(), 0);
     }
 
     #[sqlx::test]
-    async fn test_get_by_slug(pool: PgPool) {
+    async fn test_list_by_artist_and_record_label_with_releases(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let release_by_slug = Release::get_by_slug(&pool, release.slug.clone())
+        let releases = Release::list_by_artist_and_record_label(&pool, 1, 1, true, None)
             .await
             .unwrap();
 
-        assert_eq!(release, release_by_slug);
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].id, release.id);
     }
 
     #[sqlx::test]
-    async fn test_get_by_slug_not_found(pool: PgPool) {
-        let release = Release::get_by_slug(&pool, "missing".to_string()).await;
-
-        assert!(release.is_err());
-        assert_eq!(
-            release.unwrap_err().to_string(),
-            "Could not find release with slug missing.".to_string()
-        );
+    async fn test_list_by_artist_and_record_label_by_release_type(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let album = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut single = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        single.release_type = ReleaseType::Single;
+        let single = single.update(&pool).await.unwrap();
+        assert_eq!(album.release_type, ReleaseType::Album);
+        assert_eq!(single.release_type, ReleaseType::Single);
+
+        for (release_type, expected) in [
+            (Some(ReleaseType::Album), vec![album.id]),
+            (Some(ReleaseType::Single), vec![single.id]),
+            (Some(ReleaseType::Ep), vec![]),
+        ] {
+            let releases = Release::list_by_artist_and_record_label(
+                &pool,
+                artist.id,
+                artist.label_id,
+                false,
+                release_type,
+            )
+            .await
+            .unwrap();
+            let ids = releases
+                .iter()
+                .map(|release| release.id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, expected);
+        }
+        let releases =
+            Release::list_by_artist_and_record_label(&pool, artist.id, artist.label_id, true, None)
+                .await
+                .unwrap();
+        assert_eq!(releases.len(), 2);
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_no_releases(pool: PgPool) {
-        let release =
-            Release::get_by_artist_and_record_label_and_slug(&pool, 1, 1, "test".to_string(), true)
-                .await;
-
-        assert!(release.is_err());
-        assert_eq!(
-            release.unwrap_err().to_string(),
-            "Could not find release test for artist with id 1 and record label with id 1."
-                .to_string()
-        );
-    }
+    async fn test_list_by_artist_and_record_label_pinned_release_first(pool: PgPool) {
+        let mut artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut older_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        older_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(30));
+        let older_release = older_release.update(&pool).await.unwrap();
+        let mut newer_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        newer_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let newer_release = newer_release.update(&pool).await.unwrap();
 
-    #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug(pool: PgPool) {
-        let test_release = create_test_release(&pool, 1, None).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
+        let releases = Release::list_by_artist_and_record_label(
             &pool,
-            1,
-            1,
-            test_release.slug.clone(),
-            true,
+            artist.id,
+            artist.label_id,
+            false,
+            None,
         )
         .await
         .unwrap();
+        assert_eq!(releases[0].id, newer_release.id);
 
-        assert_eq!(release.id, test_release.id);
-        assert_eq!(release.slug, test_release.slug);
-    }
-
-    #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_deleted_release_include_hidden(
-        pool: PgPool,
-    ) {
-        let deleted_release = create_test_release(&pool, 1, None).await.unwrap();
-        deleted_release.delete(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
+        artist.pinned_release_id = Some(older_release.id);
+        let artist = artist.update(&pool).await.unwrap();
+        let releases = Release::list_by_artist_and_record_label(
             &pool,
-            1,
-            1,
-            deleted_release.slug.clone(),
-            true,
+            artist.id,
+            artist.label_id,
+            false,
+            None,
         )
         .await
         .unwrap();
-
-        assert_eq!(release.id, deleted_release.id);
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0].id, older_release.id);
+        assert_eq!(releases[1].id, newer_release.id);
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_deleted_release(pool: PgPool) {
-        let deleted_release = create_test_release(&pool, 1, None).await.unwrap();
+    async fn test_list_by_artist_and_record_label_with_unpublished_releases(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let record_label_id = artist.label_id;
+        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.update(&pool).await.unwrap();
+        let mut published_in_future_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        published_in_future_release.published_at =
+            Some(chrono::Utc::now() + chrono::Duration::days(1));
+        published_in_future_release.update(&pool).await.unwrap();
+        let mut published_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        published_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        published_release.clone().update(&pool).await.unwrap();
+        let deleted_release = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
         deleted_release.delete(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
+
+        let releases = Release::list_by_artist_and_record_label(
             &pool,
-            1,
-            1,
-            deleted_release.slug.clone(),
+            artist.id,
+            record_label_id,
             false,
+            None,
         )
-        .await;
+        .await
+        .unwrap();
 
-        assert!(release.is_err());
-        assert_eq!(
-            release.unwrap_err().to_string(),
-            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
-        );
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].id, published_release.id);
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_unpublished_release_include_hidden(
-        pool: PgPool,
-    ) {
-        let mut unpublished_release = create_test_release(&pool, 1, None).await.unwrap();
+    async fn test_list_by_artist_and_record_label_with_unpublished_releases_show_all(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let record_label_id = artist.label_id;
+        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
         unpublished_release.published_at = None;
+        unpublished_release.release_date = None;
         unpublished_release.clone().update(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
-            &pool,
-            1,
-            1,
-            unpublished_release.slug.clone(),
-            true,
-        )
-        .await
-        .unwrap();
+        let mut published_in_future_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        published_in_future_release.published_at =
+            Some(chrono::Utc::now() + chrono::Duration::days(1));
+        published_in_future_release.release_date =
+            Some(chrono::Utc::now() + chrono::Duration::days(1));
+        published_in_future_release
+            .clone()
+            .update(&pool)
+            .await
+            .unwrap();
+        let mut published_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        published_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        published_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        published_release.clone().update(&pool).await.unwrap();
+        let deleted_release = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+        deleted_release.clone().delete(&pool).await.unwrap();
 
-        assert_eq!(release.id, unpublished_release.id);
-        assert_eq!(release.slug, unpublished_release.slug);
+        let releases =
+            Release::list_by_artist_and_record_label(&pool, artist.id, record_label_id, true, None)
+                .await
+                .unwrap();
+
+        assert_eq!(releases.len(), 4);
+        assert_eq!(releases[0].id, unpublished_release.id);
+        assert_eq!(releases[1].id, published_in_future_release.id);
+        assert_eq!(releases[2].id, published_release.id);
+        assert_eq!(releases[3].id, deleted_release.id);
+    }
+
+    /// Create a release by one artist, with a track each by two other artists on the same label
+    async fn create_test_compilation(pool: &PgPool) -> (Release, Artist, Artist, Artist) {
+        let artist = create_test_artist(pool, 1, None).await.unwrap();
+        let record_label = RecordLabel::get_by_id(pool, artist.label_id).await.unwrap();
+        let artist2 = create_test_artist(pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let artist3 = create_test_artist(pool, 3, Some(record_label))
+            .await
+            .unwrap();
+        let release = create_test_release(pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(pool, 1, Some(release.clone()), Some(artist2.clone()))
+            .await
+            .unwrap();
+        create_test_track(pool, 2, Some(release.clone()), Some(artist3.clone()))
+            .await
+            .unwrap();
+
+        (release, artist, artist2, artist3)
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_unpublished_release(pool: PgPool) {
-        let mut unpublished_release = create_test_release(&pool, 1, None).await.unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
-            &pool,
-            1,
-            1,
-            unpublished_release.slug.clone(),
-            false,
+    async fn test_get_artists_various(pool: PgPool) {
+        let (mut release, artist, artist2, artist3) = create_test_compilation(&pool).await;
+
+        assert!(release.get_various_artists(&pool).await.unwrap().is_empty());
+
+        release.is_various = true;
+        let release = release.update(&pool).await.unwrap();
+        assert!(release.is_various);
+
+        // The artists set on the release are kept apart from those taken from the tracks
+        let artists = release.get_artists(&pool).await.unwrap();
+        assert_eq!(
+            artists.iter().map(|a| a.id).collect::<Vec<i64>>(),
+            vec![artist.id]
+        );
+        let various_artists = release.get_various_artists(&pool).await.unwrap();
+        assert_eq!(
+            various_artists.iter().map(|a| a.id).collect::<Vec<i64>>(),
+            vec![artist2.id, artist3.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_various_artists_published_tracks_only(pool: PgPool) {
+        let (mut release, _, artist2, artist3) = create_test_compilation(&pool).await;
+        release.is_various = true;
+        let release = release.update(&pool).await.unwrap();
+        sqlx::query(
+            "UPDATE tracks SET published_at = NULL WHERE id IN (
+               SELECT track_id FROM track_artists WHERE artist_id = $1
+             )",
         )
-        .await;
+        .bind(artist3.id)
+        .execute(&pool)
+        .await
+        .unwrap();
 
-        assert!(release.is_err());
+        let various_artists = release.get_various_artists(&pool).await.unwrap();
         assert_eq!(
-            release.unwrap_err().to_string(),
-            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+            various_artists.iter().map(|a| a.id).collect::<Vec<i64>>(),
+            vec![artist2.id]
         );
+
+        let releases = Release::list_by_artist_and_record_label(
+            &pool,
+            artist3.id,
+            artist3.label_id,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(releases.is_empty());
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_future_release_include_hidden(
-        pool: PgPool,
-    ) {
-        let mut future_release = create_test_release(&pool, 1, None).await.unwrap();
-        future_release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
-        future_release.clone().update(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
+    async fn test_list_by_artist_and_record_label_various(pool: PgPool) {
+        let (mut release, artist, artist2, artist3) = create_test_compilation(&pool).await;
+
+        let releases = Release::list_by_artist_and_record_label(
             &pool,
-            1,
-            1,
-            future_release.slug.clone(),
-            true,
+            artist2.id,
+            artist2.label_id,
+            false,
+            None,
         )
         .await
         .unwrap();
-
-        assert_eq!(release.id, future_release.id);
-        assert_eq!(release.slug, future_release.slug);
+        assert!(releases.is_empty());
+
+        release.is_various = true;
+        let release = release.update(&pool).await.unwrap();
+
+        for artist in [artist, artist2, artist3] {
+            let releases = Release::list_by_artist_and_record_label(
+                &pool,
+                artist.id,
+                artist.label_id,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+                vec![release.id]
+            );
+        }
     }
 
     #[sqlx::test]
-    async fn test_get_by_artist_and_record_label_and_slug_future_release(pool: PgPool) {
-        let mut future_release = create_test_release(&pool, 1, None).await.unwrap();
-        future_release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
-        future_release.clone().update(&pool).await.unwrap();
-        let release = Release::get_by_artist_and_record_label_and_slug(
+    async fn test_list_by_artist_and_record_label_excludes_unlisted(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let record_label_id = artist.label_id;
+        let mut unlisted_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        unlisted_release.unlisted = true;
+        let unlisted_release = unlisted_release.update(&pool).await.unwrap();
+        let listed_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let releases = Release::list_by_artist_and_record_label(
             &pool,
-            1,
-            1,
-            future_release.slug.clone(),
+            artist.id,
+            record_label_id,
             false,
+            None,
         )
-        .await;
+        .await
+        .unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].id, listed_release.id);
 
-        assert!(release.is_err());
-        assert_eq!(
-            release.unwrap_err().to_string(),
-            "Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
-        );
-    }
+        let releases =
+            Release::list_by_artist_and_record_label(&pool, artist.id, record_label_id, true, None)
+                .await
+                .unwrap();
+        assert_eq!(releases.len(), 2);
 
-    #[sqlx::test]
-    async fn test_get_next_scheduled_release_no_releases(pool: PgPool) {
-        let release = Release::get_next_scheduled_release(&pool, None, 1)
+        // Unlisted releases can still be reached directly
+        let release = Release::get_by_artist_and_record_label_and_slug(
+            &pool,
+            artist.id,
+            record_label_id,
+            unlisted_release.slug.clone(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.id, unlisted_release.id);
+        let release = Release::get_by_slug(&pool, unlisted_release.slug.clone(), false)
             .await
             .unwrap();
-        assert!(release.is_none());
+        assert_eq!(release.id, unlisted_release.id);
     }
 
     #[sqlx::test]
-    async fn test_get_next_scheduled_release_with_releases(pool: PgPool) {
+    async fn test_get_next_scheduled_release_excludes_unlisted(pool: PgPool) {
         let mut release = create_test_release(&pool, 1, None).await.unwrap();
         release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        release.unlisted = true;
         release.clone().update(&pool).await.unwrap();
 
         let next_release = Release::get_next_scheduled_release(&pool, None, release.label_id)
             .await
             .unwrap();
 
-        assert!(next_release.is_some());
-        assert_eq!(next_release.unwrap().id, release.id);
+        assert!(next_release.is_none());
     }
 
     #[sqlx::test]
-    async fn test_get_next_scheduled_release_with_artist(pool: PgPool) {
-        let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+    async fn test_list_by_artist_and_record_label_wrong_artist(pool: PgPool) {
+        create_test_release(&pool, 1, None).await.unwrap();
+        let releases = Release::list_by_artist_and_record_label(&pool, 2, 1, true, None)
             .await
             .unwrap();
-        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(2));
-        release.clone().update(&pool).await.unwrap();
-        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
-        let mut other_upcoming_release = create_test_release(&pool, 2, Some(other_artist.clone()))
+
+        assert_eq!(releases.len(), 0);
+    }
+
+    #[sqlx::test]
+    async fn test_list_by_artist_and_record_label_wrong_label(pool: PgPool) {
+        create_test_release(&pool, 1, None).await.unwrap();
+        let releases = Release::list_by_artist_and_record_label(&pool, 1, 2, true, None)
             .await
             .unwrap();
-        other_upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
-        other_upcoming_release.clone().update(&pool).await.unwrap();
 
-        let next_release =
-            Release::get_next_scheduled_release(&pool, Some(artist.id), release.label_id)
-                .await
-                .unwrap();
+        assert_eq!(releases.len(), 0);
+    }
 
-        assert!(next_release.is_some());
-        assert_eq!(next_release.unwrap().id, release.id);
+    #[sqlx::test]
+    async fn test_update_first_published_at(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        assert!(release.first_published_at.is_none());
+
+        // The first publish stamps it
+        let published_at = chrono::Utc::now() - chrono::Duration::minutes(1);
+        release.published_at = Some(published_at);
+        let mut release = release.update(&pool).await.unwrap();
+        let first_published_at = release.first_published_at;
+        assert!(first_published_at.is_some());
+        assert_eq!(first_published_at, release.published_at);
+
+        // Rescheduling doesn't change it
+        release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        release.first_published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        assert_eq!(release.first_published_at, first_published_at);
+
+        // Unpublishing leaves it intact
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        assert_eq!(release.first_published_at, first_published_at);
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_no_releases(pool: PgPool) {
-        let releases = Release::list_by_artist_and_record_label(&pool, 1, 1, true)
+    async fn test_update_first_published_at_scheduled(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
 
-        assert_eq!(releases.len(), 0);
+        release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+
+        assert!(release.first_published_at.is_none());
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_with_releases(pool: PgPool) {
+    async fn test_update(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let releases = Release::list_by_artist_and_record_label(&pool, 1, 1, true)
+        let mut update_release = release.clone();
+        update_release.name = "Updated Release".to_string();
+        update_release.description = "This is an updated release".to_string();
+        update_release.primary_image = Some("an-image.jpg".to_string());
+        update_release.catalogue_number = "UPDATED-0001".to_string();
+        update_release.release_date = Some(chrono::Utc::now());
+
+        let updated_release = update_release.update(&pool).await.unwrap();
+        assert_eq!(updated_release.name, "Updated Release".to_string());
+        assert_eq!(updated_release.slug, release.slug);
+        assert_eq!(
+            updated_release.description,
+            "This is an updated release".to_string()
+        );
+        assert_eq!(
+            updated_release.primary_image,
+            Some("an-image.jpg".to_string())
+        );
+        assert_ne!(updated_release.updated_at, release.updated_at);
+    }
+
+    #[sqlx::test]
+    async fn test_update_published_keeps_slug(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        let slug = release.slug.clone();
+        release.name = "Renamed Release".to_string();
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.name, "Renamed Release");
+        assert_eq!(release.slug, slug);
+        let redirects = SlugRedirect::list_by_old_slug(&pool, "release", &slug)
             .await
             .unwrap();
+        assert!(redirects.is_empty());
+    }
 
-        assert_eq!(releases.len(), 1);
-        assert_eq!(releases[0].id, release.id);
+    #[sqlx::test]
+    async fn test_update_unlocked_slug_creates_redirect(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        let slug = release.slug.clone();
+        release.name = "Renamed Release".to_string();
+        release.lock_slug = Some(false);
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.slug, "renamed-release");
+        let redirects = SlugRedirect::list_by_old_slug(&pool, "release", &slug)
+            .await
+            .unwrap();
+        assert_eq!(redirects.len(), 1);
+        assert_eq!(redirects[0].entity_id, release.id);
+        assert_eq!(redirects[0].new_slug, "renamed-release");
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_with_unpublished_releases(pool: PgPool) {
-        let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let record_label_id = artist.label_id;
-        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+    async fn test_update_unpublished_regenerates_slug(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let mut release = release.update(&pool).await.unwrap();
+        let slug = release.slug.clone();
+        release.name = "Renamed Release".to_string();
+        release.published_at = None;
+
+        let release = release.update(&pool).await.unwrap();
+
+        assert_eq!(release.slug, "renamed-release");
+        let redirects = SlugRedirect::list_by_old_slug(&pool, "release", &slug)
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.update(&pool).await.unwrap();
-        let mut published_in_future_release = create_test_release(&pool, 2, Some(artist.clone()))
+        assert!(redirects.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_update_validation_error(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut update_release = release.clone();
+        update_release.name = String::new();
+        let updated_release = update_release.update(&pool).await;
+
+        assert!(updated_release.is_err());
+        assert_eq!(
+            updated_release.unwrap_err().to_string(),
+            "Name is required.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let result = release.delete(&pool).await.unwrap();
+        assert!(result.deleted_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_delete_not_found(pool: PgPool) {
+        let release = Release::default();
+        let result = release.delete(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Could not delete release with id 0.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_set_credits(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let credits = release
+            .set_credits(
+                &pool,
+                vec![
+                    ReleaseCredit::new("Artwork by", "Alex Smith", 2),
+                    ReleaseCredit::new("Mastered by", "Jane Doe", 1),
+                ],
+            )
             .await
             .unwrap();
-        published_in_future_release.published_at =
-            Some(chrono::Utc::now() + chrono::Duration::days(1));
-        published_in_future_release.update(&pool).await.unwrap();
-        let mut published_release = create_test_release(&pool, 3, Some(artist.clone()))
+
+        assert_eq!(credits.len(), 2);
+        assert_eq!(credits[0].role, "Mastered by");
+        assert_eq!(credits[0].name, "Jane Doe");
+        assert_eq!(credits[1].role, "Artwork by");
+        assert_eq!(credits[1].release_id, release.id);
+        assert_eq!(release.get_credits(&pool).await.unwrap(), credits);
+    }
+
+    #[sqlx::test]
+    async fn test_add_image(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let front = release
+            .add_image(&pool, "front.jpg", "The front cover")
             .await
             .unwrap();
-        published_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
-        published_release.clone().update(&pool).await.unwrap();
-        let deleted_release = create_test_release(&pool, 4, Some(artist.clone()))
+        let back = release.add_image(&pool, "back.jpg", "").await.unwrap();
+
+        assert_eq!(front.position, 0);
+        assert_eq!(front.alt_text, "The front cover");
+        assert_eq!(back.position, 1);
+        assert_eq!(release.get_images(&pool).await.unwrap(), vec![front, back]);
+
+        let result = release.add_image(&pool, " ", "").await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Image file name is required."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_primary_image_url_falls_back_to_gallery(pool: PgPool) {
+        let placeholders = PlaceholderImages {
+            release: "/images/vinyl.svg".to_string(),
+            ..Default::default()
+        };
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        assert_eq!(
+            release.primary_image_url(&placeholders),
+            "/images/vinyl.svg"
+        );
+
+        let front = release.add_image(&pool, "front.jpg", "").await.unwrap();
+        let back = release.add_image(&pool, "back.jpg", "").await.unwrap();
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(
+            release.primary_image_url(&placeholders),
+            "/uploads/releases/front.jpg"
+        );
+
+        release
+            .reorder_images(&pool, vec![back.id, front.id])
             .await
             .unwrap();
-        deleted_release.delete(&pool).await.unwrap();
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(
+            release.primary_image_url(&placeholders),
+            "/uploads/releases/back.jpg"
+        );
 
-        let releases =
-            Release::list_by_artist_and_record_label(&pool, artist.id, record_label_id, false)
-                .await
-                .unwrap();
+        // The primary image still wins when it is set
+        let release = Release {
+            primary_image: Some("primary.jpg".to_string()),
+            ..release
+        };
+        assert_eq!(
+            release.primary_image_url(&placeholders),
+            "/uploads/releases/primary.jpg"
+        );
+    }
 
-        assert_eq!(releases.len(), 1);
-        assert_eq!(releases[0].id, published_release.id);
+    #[sqlx::test]
+    async fn test_remove_image(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let front = release.add_image(&pool, "front.jpg", "").await.unwrap();
+        let back = release.add_image(&pool, "back.jpg", "").await.unwrap();
+
+        let images = release.remove_image(&pool, front.id).await.unwrap();
+        assert_eq!(images, vec![back.clone()]);
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.gallery_image, Some("back.jpg".to_string()));
+
+        let images = release.remove_image(&pool, back.id).await.unwrap();
+        assert!(images.is_empty());
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.gallery_image, None);
+
+        let result = release.remove_image(&pool, back.id).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!("Could not find image with id {}.", back.id)
+        );
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_with_unpublished_releases_show_all(pool: PgPool) {
-        let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let record_label_id = artist.label_id;
-        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+    async fn test_reorder_images(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let other_release = create_test_release(&pool, 2, None).await.unwrap();
+        let front = release.add_image(&pool, "front.jpg", "").await.unwrap();
+        let back = release.add_image(&pool, "back.jpg", "").await.unwrap();
+        let inlay = release.add_image(&pool, "inlay.jpg", "").await.unwrap();
+        let other = other_release
+            .add_image(&pool, "other.jpg", "")
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.release_date = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
-        let mut published_in_future_release = create_test_release(&pool, 2, Some(artist.clone()))
+
+        let images = release
+            .reorder_images(&pool, vec![inlay.id, front.id, back.id])
             .await
             .unwrap();
-        published_in_future_release.published_at =
-            Some(chrono::Utc::now() + chrono::Duration::days(1));
-        published_in_future_release.release_date =
-            Some(chrono::Utc::now() + chrono::Duration::days(1));
-        published_in_future_release
-            .clone()
-            .update(&pool)
+        let ids = images.iter().map(|image| image.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![inlay.id, front.id, back.id]);
+        assert_eq!(images[0].position, 0);
+        assert_eq!(images[2].position, 2);
+
+        // Missing or foreign images are rejected, and nothing changes
+        for image_ids in [vec![front.id, back.id], vec![front.id, back.id, other.id]] {
+            let result = release.reorder_images(&pool, image_ids).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "Image IDs must include every image for the release once."
+            );
+        }
+        assert_eq!(release.get_images(&pool).await.unwrap(), images);
+    }
+
+    #[sqlx::test]
+    async fn test_reorder_tracks_swap(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let first = create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
-        let mut published_release = create_test_release(&pool, 3, Some(artist.clone()))
+        let second = create_test_track(&pool, 2, Some(release.clone()), None)
             .await
             .unwrap();
-        published_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
-        published_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
-        published_release.clone().update(&pool).await.unwrap();
-        let deleted_release = create_test_release(&pool, 4, Some(artist.clone()))
+
+        let tracks = release
+            .reorder_tracks(&pool, vec![second.id, first.id])
             .await
             .unwrap();
-        deleted_release.clone().delete(&pool).await.unwrap();
 
-        let releases =
-            Release::list_by_artist_and_record_label(&pool, artist.id, record_label_id, true)
-                .await
-                .unwrap();
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| (track.track.id, track.track.track_number))
+                .collect::<Vec<_>>(),
+            vec![(second.id, 1), (first.id, 2)]
+        );
+    }
 
-        assert_eq!(releases.len(), 4);
-        assert_eq!(releases[0].id, unpublished_release.id);
-        assert_eq!(releases[1].id, published_in_future_release.id);
-        assert_eq!(releases[2].id, published_release.id);
-        assert_eq!(releases[3].id, deleted_release.id);
+    #[sqlx::test]
+    async fn test_reorder_tracks_must_include_every_track(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let first = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let second = create_test_track(&pool, 2, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let other = create_test_track(&pool, 3, None, None).await.unwrap();
+
+        // Missing or foreign tracks are rejected, and nothing changes
+        for track_ids in [vec![second.id], vec![second.id, first.id, other.id]] {
+            let result = release.reorder_tracks(&pool, track_ids).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "Track IDs must include every track for the release once."
+            );
+        }
+        let tracks = release.get_tracks(&pool).await.unwrap();
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| track.track.id)
+                .collect::<Vec<_>>(),
+            vec![first.id, second.id]
+        );
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_wrong_artist(pool: PgPool) {
-        create_test_release(&pool, 1, None).await.unwrap();
-        let releases = Release::list_by_artist_and_record_label(&pool, 2, 1, true)
+    async fn test_set_credits_replace(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        release
+            .set_credits(
+                &pool,
+                vec![
+                    ReleaseCredit::new("Mastered by", "Jane Doe", 0),
+                    ReleaseCredit::new("Artwork by", "Alex Smith", 1),
+                ],
+            )
             .await
             .unwrap();
 
-        assert_eq!(releases.len(), 0);
+        let credits = release
+            .set_credits(&pool, vec![ReleaseCredit::new("Mixed by", "Sam Lee", 0)])
+            .await
+            .unwrap();
+
+        assert_eq!(credits.len(), 1);
+        assert_eq!(credits[0].role, "Mixed by");
+        assert_eq!(release.get_credits(&pool).await.unwrap(), credits);
     }
 
     #[sqlx::test]
-    async fn test_list_by_artist_and_record_label_wrong_label(pool: PgPool) {
-        create_test_release(&pool, 1, None).await.unwrap();
-        let releases = Release::list_by_artist_and_record_label(&pool, 1, 2, true)
+    async fn test_set_credits_invalid(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        release
+            .set_credits(
+                &pool,
+                vec![ReleaseCredit::new("Mastered by", "Jane Doe", 0)],
+            )
             .await
             .unwrap();
 
-        assert_eq!(releases.len(), 0);
+        let result = release
+            .set_credits(&pool, vec![ReleaseCredit::new("Mixed by", "", 0)])
+            .await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Credit name is required.");
+        // The existing credits are left in place
+        assert_eq!(release.get_credits(&pool).await.unwrap().len(), 1);
     }
 
     #[sqlx::test]
-    async fn test_update(pool: PgPool) {
+    async fn test_set_artists(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let mut update_release = release.clone();
-        update_release.name = "Updated Release".to_string();
-        update_release.description = "This is an updated release".to_string();
-        update_release.primary_image = Some("an-image.jpg".to_string());
-        update_release.catalogue_number = "UPDATED-0001".to_string();
-        update_release.release_date = Some(chrono::Utc::now());
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
 
-        let updated_release = update_release.update(&pool).await.unwrap();
-        assert_eq!(updated_release.name, "Updated Release".to_string());
-        assert_eq!(updated_release.slug, "updated-release".to_string());
-        assert_eq!(
-            updated_release.description,
-            "This is an updated release".to_string()
-        );
-        assert_eq!(
-            updated_release.primary_image,
-            Some("an-image.jpg".to_string())
-        );
-        assert_ne!(updated_release.updated_at, release.updated_at);
+        let result = release
+            .set_artists(
+                &pool,
+                vec![
+                    (artist.id, ArtistRole::Primary),
+                    (artist2.id, ArtistRole::Featured),
+                ],
+            )
+            .await;
+
+        assert!(result.is_ok());
     }
 
     #[sqlx::test]
-    async fn test_update_validation_error(pool: PgPool) {
+    async fn test_set_genres(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let mut update_release = release.clone();
-        update_release.name = String::new();
-        let updated_release = update_release.update(&pool).await;
+        let techno = Genre::create(&pool, "Techno").await.unwrap();
+        let ambient = Genre::create(&pool, "Ambient").await.unwrap();
 
-        assert!(updated_release.is_err());
+        release
+            .set_genres(&pool, vec![techno.id, ambient.id, techno.id])
+            .await
+            .unwrap();
         assert_eq!(
-            updated_release.unwrap_err().to_string(),
-            "Name is required.".to_string()
+            release.get_genres(&pool).await.unwrap(),
+            vec![ambient.clone(), techno]
         );
+
+        release.set_genres(&pool, vec![ambient.id]).await.unwrap();
+        assert_eq!(release.get_genres(&pool).await.unwrap(), vec![ambient]);
+
+        release.set_genres(&pool, vec![]).await.unwrap();
+        assert!(release.get_genres(&pool).await.unwrap().is_empty());
     }
 
     #[sqlx::test]
-    async fn test_delete(pool: PgPool) {
+    async fn test_set_genres_not_found(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let result = release.delete(&pool).await.unwrap();
-        assert!(result.deleted_at.is_some());
+        let result = release.set_genres(&pool, vec![999]).await;
+
+        assert_eq!(result.unwrap_err(), ReleaseError::SetGenres(release.id));
     }
 
     #[sqlx::test]
-    async fn test_delete_not_found(pool: PgPool) {
+    async fn test_set_artists_not_found(pool: PgPool) {
         let release = Release::default();
-        let result = release.delete(&pool).await;
+        let result = release
+            .set_artists(
+                &pool,
+                vec![(1, ArtistRole::Primary), (2, ArtistRole::Featured)],
+            )
+            .await;
 
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Could not delete release with id 0.".to_string()
+            "Could not set artists for release with id 0.".to_string()
         );
     }
 
     #[sqlx::test]
-    async fn test_set_artists(pool: PgPool) {
+    async fn test_release_artists_single_primary_index(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
         let artist = release.get_artists(&pool).await.unwrap()[0].clone();
         let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
@@ -1303,23 +5338,44 @@ mod tests {
             .await
             .unwrap();
 
-        let result = release
-            .set_artists(&pool, vec![artist.id, artist2.id])
-            .await;
+        // The database refuses a second primary artist, even without going through `set_artists`
+        let result = sqlx::query(
+            "INSERT INTO release_artists (release_id, artist_id, role) VALUES ($1, $2, 'Primary')",
+        )
+        .bind(release.id)
+        .bind(artist2.id)
+        .execute(&pool)
+        .await;
 
-        assert!(result.is_ok());
+        assert!(
+            result
+                .unwrap_err()
+                .as_database_error()
+                .is_some_and(sqlx::error::DatabaseError::is_unique_violation)
+        );
     }
 
     #[sqlx::test]
-    async fn test_set_artists_not_found(pool: PgPool) {
-        let release = Release::default();
-        let result = release.set_artists(&pool, vec![1, 2]).await;
+    async fn test_set_artists_commit_error(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
+        fail_commits_on(&pool, "release_artists").await.unwrap();
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.unwrap_err().to_string(),
-            "Could not set artists for release with id 0.".to_string()
-        );
+        let result = release
+            .set_artists(&pool, vec![(artist2.id, ArtistRole::Primary)])
+            .await;
+
+        assert_eq!(result.unwrap_err(), ReleaseError::Database);
+        // Nothing was changed
+        let artists = release.get_artists(&pool).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].id, artist.id);
     }
 
     #[sqlx::test]
@@ -1350,12 +5406,20 @@ mod tests {
 
         // Set the artists for the release
         release
-            .set_artists(&pool, vec![artist.id, artist2.id])
+            .set_artists(
+                &pool,
+                vec![
+                    (artist.id, ArtistRole::Primary),
+                    (artist2.id, ArtistRole::Featured),
+                ],
+            )
             .await
             .unwrap();
 
         // Replace the artists for the release
-        let result = release.set_artists(&pool, vec![artist3.id]).await;
+        let result = release
+            .set_artists(&pool, vec![(artist3.id, ArtistRole::Primary)])
+            .await;
 
         assert!(result.is_ok());
         let artists = release.get_artists(&pool).await.unwrap();
@@ -1363,6 +5427,133 @@ mod tests {
         assert_eq!(artists[0].id, artist3.id);
     }
 
+    #[sqlx::test]
+    async fn test_set_artists_preserves_order(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let artist3 = create_test_artist(&pool, 3, Some(record_label))
+            .await
+            .unwrap();
+
+        release
+            .set_artists(
+                &pool,
+                vec![
+                    (artist3.id, ArtistRole::Primary),
+                    (artist.id, ArtistRole::Featured),
+                    (artist2.id, ArtistRole::Featured),
+                ],
+            )
+            .await
+            .unwrap();
+        let artist_ids = release
+            .get_artists(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|artist| artist.id)
+            .collect::<Vec<i64>>();
+        assert_eq!(artist_ids, vec![artist3.id, artist.id, artist2.id]);
+
+        // Replacing the artists uses the new order
+        release
+            .set_artists(
+                &pool,
+                vec![
+                    (artist2.id, ArtistRole::Primary),
+                    (artist3.id, ArtistRole::Featured),
+                ],
+            )
+            .await
+            .unwrap();
+        let artist_ids = release
+            .get_artists(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|artist| artist.id)
+            .collect::<Vec<i64>>();
+        assert_eq!(artist_ids, vec![artist2.id, artist3.id]);
+    }
+
+    #[sqlx::test]
+    async fn test_set_artists_requires_one_primary(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
+
+        for artists in [
+            vec![(artist.id, ArtistRole::Featured)],
+            vec![
+                (artist.id, ArtistRole::Primary),
+                (artist2.id, ArtistRole::Primary),
+            ],
+        ] {
+            let result = release.set_artists(&pool, artists).await;
+            assert_eq!(
+                result.unwrap_err(),
+                ReleaseError::Invalid(
+                    "A release must have exactly one primary artist.".to_string()
+                )
+            );
+        }
+        // Nothing was changed
+        assert_eq!(release.get_artists(&pool).await.unwrap(), vec![artist]);
+    }
+
+    #[sqlx::test]
+    async fn test_get_featured_artists(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let featured = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let remixer = create_test_artist(&pool, 3, Some(record_label))
+            .await
+            .unwrap();
+        assert!(
+            release
+                .get_featured_artists(&pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        release
+            .set_artists(
+                &pool,
+                vec![
+                    (artist.id, ArtistRole::Primary),
+                    (remixer.id, ArtistRole::Remixer),
+                    (featured.id, ArtistRole::Featured),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            release.get_featured_artists(&pool).await.unwrap(),
+            vec![featured]
+        );
+        assert_eq!(release.get_remixers(&pool).await.unwrap(), vec![remixer]);
+        // Everyone is still one of the release's artists
+        assert_eq!(release.get_artists(&pool).await.unwrap().len(), 3);
+    }
+
     /// Test `get_artists`
     #[sqlx::test]
     async fn test_get_artists(pool: PgPool) {
@@ -1377,7 +5568,13 @@ mod tests {
 
         // Set the artists for the release
         release
-            .set_artists(&pool, vec![artist.id, artist2.id])
+            .set_artists(
+                &pool,
+                vec![
+                    (artist.id, ArtistRole::Primary),
+                    (artist2.id, ArtistRole::Featured),
+                ],
+            )
             .await
             .unwrap();
 
@@ -1418,11 +5615,166 @@ mod tests {
         assert_eq!(tracks[1].artists[0].id, artist.id);
     }
 
+    #[sqlx::test]
+    async fn test_total_duration(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        assert_eq!(release.total_duration(&pool).await.unwrap(), None);
+
+        let mut track1 = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        track1.duration_ms = Some(225_500);
+        track1.update(&pool).await.unwrap();
+        let mut track2 = create_test_track(&pool, 2, Some(release.clone()), None)
+            .await
+            .unwrap();
+        assert_eq!(release.total_duration(&pool).await.unwrap(), None);
+
+        track2.duration_ms = Some(180_000);
+        track2.update(&pool).await.unwrap();
+        let deleted_track = create_test_track(&pool, 3, Some(release.clone()), None)
+            .await
+            .unwrap();
+        deleted_track.delete(&pool).await.unwrap();
+        assert_eq!(release.total_duration(&pool).await.unwrap(), Some(405));
+    }
+
+    #[sqlx::test]
+    async fn test_get_tracks_batches_artists(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let featured_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let remixer = create_test_artist(&pool, 3, Some(record_label))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut track_artist_ids = vec![
+            vec![artist.id],
+            vec![artist.id, featured_artist.id],
+            vec![artist.id, featured_artist.id, remixer.id],
+        ];
+        let mut track_ids = vec![];
+        for (id, artist_ids) in (1..).zip(&track_artist_ids) {
+            let track = create_test_track(&pool, id, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+            track.set_artists(&pool, artist_ids.clone()).await.unwrap();
+            track_ids.push(track.id);
+        }
+        // A track without artists still comes back, with none
+        let lonely_track = create_test_track(&pool, 4, Some(release.clone()), Some(artist))
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM track_artists WHERE track_id = $1")
+            .bind(lonely_track.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        track_ids.push(lonely_track.id);
+        track_artist_ids.push(vec![]);
+
+        let tracks = release.get_tracks(&pool).await.unwrap();
+
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| track.track.id)
+                .collect::<Vec<_>>(),
+            track_ids
+        );
+        for (track, artist_ids) in tracks.iter().zip(&track_artist_ids) {
+            let mut ids = track
+                .artists
+                .iter()
+                .map(|artist| artist.id)
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            assert_eq!(&ids, artist_ids);
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        track.isrc_code = None;
+        let track = track.update(&pool).await.unwrap();
+        let mut deleted_track = create_test_track(&pool, 2, Some(release.clone()), None)
+            .await
+            .unwrap();
+        deleted_track.isrc_code = None;
+        let deleted_track = deleted_track.update(&pool).await.unwrap();
+        deleted_track.delete(&pool).await.unwrap();
+
+        let tracks = release.assign_isrcs(&pool, "UK-AAA", 2025).await.unwrap();
+
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].id, track.id);
+        assert_eq!(tracks[0].isrc_code, Some("UKAAA2500001".to_string()));
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs_concurrently(pool: PgPool) {
+        let release_1 = create_test_release(&pool, 1, None).await.unwrap();
+        let release_2 = create_test_release(&pool, 2, None).await.unwrap();
+        for (i, release) in [&release_1, &release_2].into_iter().enumerate() {
+            let mut track = create_test_track(&pool, i + 1, Some(release.clone()), None)
+                .await
+                .unwrap();
+            track.isrc_code = None;
+            track.update(&pool).await.unwrap();
+        }
+
+        let (tracks_1, tracks_2) = futures::join!(
+            release_1.assign_isrcs(&pool, "UK-AAA", 2025),
+            release_2.assign_isrcs(&pool, "UK-AAA", 2025)
+        );
+
+        let mut codes = [
+            tracks_1.unwrap()[0].isrc_code.clone().unwrap(),
+            tracks_2.unwrap()[0].isrc_code.clone().unwrap(),
+        ];
+        codes.sort();
+        assert_eq!(codes, ["UKAAA2500001", "UKAAA2500002"]);
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs_invalid_isrc_base(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        track.isrc_code = None;
+        let track = track.update(&pool).await.unwrap();
+
+        let result = release.assign_isrcs(&pool, "UK", 2025).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ISRC base must be 5 alphanumeric characters."
+        );
+        // The transaction was rolled back
+        let track = Track::get_by_slug(&pool, track.slug, false).await.unwrap();
+        assert_eq!(track.isrc_code, None);
+    }
+
     #[sqlx::test]
     async fn test_primary_image_url(pool: PgPool) {
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let url = release.primary_image_url();
-        assert_eq!(url, "/Logo.svg");
+        let placeholders = PlaceholderImages {
+            release: "/images/vinyl.svg".to_string(),
+            ..Default::default()
+        };
+        let url = release.primary_image_url(&placeholders);
+        assert_eq!(url, "/images/vinyl.svg");
     }
 
     #[sqlx::test]
@@ -1430,7 +5782,7 @@ mod tests {
         let release = create_test_release(&pool, 1, None).await.unwrap();
         let mut release = release;
         release.primary_image = Some("custom-image.jpg".to_string());
-        let url = release.primary_image_url();
+        let url = release.primary_image_url(&PlaceholderImages::default());
         assert_eq!(url, "/uploads/releases/custom-image.jpg");
     }
 }
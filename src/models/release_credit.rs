@@ -0,0 +1,100 @@
+//! Release credit model
+//!
+//! The `ReleaseCredit` struct is used to represent a free-form credit on a release, such as "Mastered by" or "Artwork by".
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::FromRow;
+
+/// The `ReleaseCredit` struct is used to represent a release credit in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct ReleaseCredit {
+    /// The unique identifier of the credit
+    pub id: i64,
+    /// The release the credit belongs to
+    pub release_id: i64,
+    /// The role being credited, e.g. "Mastered by"
+    pub role: String,
+    /// The name of the person or organisation being credited
+    pub name: String,
+    /// The position of the credit, lowest first
+    pub sort_order: i32,
+}
+
+impl ReleaseCredit {
+    /// Create a new credit, ready to be set on a release
+    #[must_use]
+    pub fn new(role: &str, name: &str, sort_order: i32) -> Self {
+        Self {
+            role: role.to_string(),
+            name: name.to_string(),
+            sort_order,
+            ..Default::default()
+        }
+    }
+
+    /// Validate the credit
+    ///
+    /// # Errors
+    /// If the role or name is empty, return an error
+    /// If the role or name is longer than 255 characters, return an error
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.role.trim().is_empty() {
+            return Err(anyhow::anyhow!("Credit role is required."));
+        }
+        if self.role.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Credit role must be less than 255 characters."
+            ));
+        }
+        if self.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Credit name is required."));
+        }
+        if self.name.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Credit name must be less than 255 characters."
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            ReleaseCredit::new("Mastered by", "Jane Doe", 0)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_missing_fields() {
+        assert_eq!(
+            ReleaseCredit::new(" ", "Jane Doe", 0)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Credit role is required."
+        );
+        assert_eq!(
+            ReleaseCredit::new("Mastered by", "", 0)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Credit name is required."
+        );
+        assert_eq!(
+            ReleaseCredit::new("Mastered by", &"a".repeat(256), 0)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Credit name must be less than 255 characters."
+        );
+    }
+}
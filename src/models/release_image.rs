@@ -0,0 +1,75 @@
+//! Release image model
+//!
+//! The `ReleaseImage` struct is used to represent an image in a release's gallery, such as a back cover, inlay scan or press photo.
+//! Images are shown in order of their position, lowest first.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::FromRow;
+
+use crate::utils::files::upload_url;
+
+/// The `ReleaseImage` struct is used to represent a release image in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct ReleaseImage {
+    /// The unique identifier of the image
+    pub id: i64,
+    /// The release the image belongs to
+    pub release_id: i64,
+    /// The file name of the uploaded image
+    pub filename: String,
+    /// The position of the image in the gallery, lowest first
+    pub position: i32,
+    /// A description of the image for screen readers
+    pub alt_text: String,
+}
+
+impl ReleaseImage {
+    /// Get the URL of the image
+    #[must_use]
+    pub fn url(&self) -> String {
+        upload_url("releases", Some(&self.filename))
+    }
+
+    /// Validate the image
+    ///
+    /// # Errors
+    /// If the file name is empty, return an error
+    /// If the alt text is longer than 255 characters, return an error
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.filename.trim().is_empty() {
+            return Err(anyhow::anyhow!("Image file name is required."));
+        }
+        if self.alt_text.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Image alt text must be less than 255 characters."
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let image = ReleaseImage {
+            filename: "back-cover.jpg".to_string(),
+            alt_text: "The back cover".to_string(),
+            ..Default::default()
+        };
+        assert!(image.validate().is_ok());
+
+        let image = ReleaseImage {
+            filename: " ".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            image.validate().unwrap_err().to_string(),
+            "Image file name is required."
+        );
+    }
+}
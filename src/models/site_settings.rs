@@ -0,0 +1,124 @@
+//! Site settings model
+//!
+//! The `SiteSettings` struct holds deployment wide settings that can be changed at runtime.
+//! There is only ever a single row of settings.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+/// Errors returned by the site settings model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum SiteSettingsError {
+    /// The site settings could not be found
+    #[error("Could not find site settings.")]
+    NotFound,
+    /// The site settings could not be updated
+    #[error("Could not update site settings.")]
+    Update,
+}
+
+/// The `SiteSettings` struct is used to represent the site settings in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct SiteSettings {
+    /// Is the deployment read-only
+    /// When this is true, content cannot be created, updated or deleted
+    pub read_only: bool,
+    /// The date and time the settings were last updated
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SiteSettings {
+    /// Get the site settings
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The site settings
+    ///
+    /// # Errors
+    /// If the settings cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get(pool: &PgPool) -> Result<Self, SiteSettingsError> {
+        let settings =
+            sqlx::query_as::<_, Self>("SELECT read_only, updated_at FROM site_settings LIMIT 1")
+                .fetch_one(pool)
+                .await;
+
+        match settings {
+            Ok(settings) => Ok(settings),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SiteSettingsError::NotFound)
+            }
+        }
+    }
+
+    /// Turn read-only mode on or off
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `read_only` - Should the deployment be read-only
+    ///
+    /// # Returns
+    /// The updated site settings
+    ///
+    /// # Errors
+    /// If the settings cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_read_only(pool: &PgPool, read_only: bool) -> Result<Self, SiteSettingsError> {
+        let settings = sqlx::query_as::<_, Self>(
+            "INSERT INTO site_settings (id, read_only, updated_at) VALUES (TRUE, $1, $2)
+             ON CONFLICT (id) DO UPDATE SET read_only = $1, updated_at = $2
+             RETURNING read_only, updated_at",
+        )
+        .bind(read_only)
+        .bind(chrono::Utc::now())
+        .fetch_one(pool)
+        .await;
+
+        match settings {
+            Ok(settings) => Ok(settings),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SiteSettingsError::Update)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_settings_error_display() {
+        let cases = [
+            (SiteSettingsError::NotFound, "Could not find site settings."),
+            (SiteSettingsError::Update, "Could not update site settings."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_get(pool: PgPool) {
+        let settings = SiteSettings::get(&pool).await.unwrap();
+        assert!(!settings.read_only);
+    }
+
+    #[sqlx::test]
+    async fn test_set_read_only(pool: PgPool) {
+        let settings = SiteSettings::set_read_only(&pool, true).await.unwrap();
+        assert!(settings.read_only);
+        assert!(SiteSettings::get(&pool).await.unwrap().read_only);
+
+        let settings = SiteSettings::set_read_only(&pool, false).await.unwrap();
+        assert!(!settings.read_only);
+    }
+}
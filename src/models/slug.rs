@@ -0,0 +1,260 @@
+//! Unique slugs
+//!
+//! Artists, releases and tracks each need a slug no other row in their table has, including deleted rows.
+//! When a new slug is taken, the configured `SlugCollisionStrategy` decides what happens.
+
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+
+#[cfg(feature = "ssr")]
+use crate::config::slug::{SlugCollisionStrategy, slug_collision_strategy, slug_separator};
+
+/// Errors returned when finding a unique slug
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum SlugError {
+    /// The slug is taken and the collision strategy doesn't allow another
+    #[error("Slug must be unique.")]
+    Taken,
+    /// The existing slugs could not be checked
+    #[error("Could not check slug.")]
+    Check,
+}
+
+/// A table whose rows have unique slugs
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlugTable {
+    /// The `artists` table
+    Artists,
+    /// The `releases` table
+    Releases,
+    /// The `tracks` table
+    Tracks,
+}
+
+impl SlugTable {
+    /// The name of the table in the database
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Artists => "artists",
+            Self::Releases => "releases",
+            Self::Tracks => "tracks",
+        }
+    }
+}
+
+/// Find a free slug for a new row, following the slug collision strategy
+/// By default a number is appended, e.g. "live", "live-2", "live-3".
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `table` - The table the slug must be unique in
+/// * `base` - The slug wanted for the row
+///
+/// # Returns
+/// A slug no other row in the table has
+///
+/// # Errors
+/// If the slug is taken and the strategy is `Error`, or the timestamped slug is also taken, return an error
+/// If the slugs cannot be checked, return an error
+#[cfg(feature = "ssr")]
+pub async fn unique_slug(
+    pool: &PgPool,
+    table: SlugTable,
+    base: String,
+) -> Result<String, SlugError> {
+    unique_slug_with(pool, table, base, 0, slug_collision_strategy()).await
+}
+
+/// Find a free slug for a row, ignoring the row itself
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `table` - The table the slug must be unique in
+/// * `base` - The slug wanted for the row
+/// * `id` - The ID of the row, which may already have the slug
+/// * `strategy` - What to do if another row has the slug
+///
+/// # Returns
+/// A slug no other row in the table has
+///
+/// # Errors
+/// If the slug is taken and the strategy is `Error`, or the timestamped slug is also taken, return an error
+/// If the slugs cannot be checked, return an error
+#[cfg(feature = "ssr")]
+pub async fn unique_slug_with(
+    pool: &PgPool,
+    table: SlugTable,
+    base: String,
+    id: i64,
+    strategy: SlugCollisionStrategy,
+) -> Result<String, SlugError> {
+    let query = format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE slug = $1 AND id != $2)",
+        table.name()
+    );
+    let taken = async |candidate: &str| {
+        sqlx::query_scalar::<_, bool>(&query)
+            .bind(candidate)
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| {
+                tracing::error!("{e}");
+                SlugError::Check
+            })
+    };
+    if !taken(&base).await? {
+        return Ok(base);
+    }
+
+    let separator = slug_separator();
+    match strategy {
+        SlugCollisionStrategy::Suffix => {
+            let mut suffix = 2;
+            let mut candidate = format!("{base}{separator}{suffix}");
+            while taken(&candidate).await? {
+                suffix += 1;
+                candidate = format!("{base}{separator}{suffix}");
+            }
+            Ok(candidate)
+        }
+        SlugCollisionStrategy::Timestamp => {
+            let candidate = format!(
+                "{base}{separator}{}",
+                chrono::Utc::now().format("%Y%m%d%H%M%S")
+            );
+            if taken(&candidate).await? {
+                return Err(SlugError::Taken);
+            }
+            Ok(candidate)
+        }
+        SlugCollisionStrategy::Error => Err(SlugError::Taken),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{create_test_artist, create_test_release, create_test_track};
+
+    #[test]
+    fn test_slug_table_name() {
+        assert_eq!(SlugTable::Artists.name(), "artists");
+        assert_eq!(SlugTable::Releases.name(), "releases");
+        assert_eq!(SlugTable::Tracks.name(), "tracks");
+    }
+
+    #[test]
+    fn test_slug_error_display() {
+        let cases = [
+            (SlugError::Taken, "Slug must be unique."),
+            (SlugError::Check, "Could not check slug."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_free(pool: PgPool) {
+        let slug = unique_slug(&pool, SlugTable::Releases, "live".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(slug, "live");
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_checks_the_table(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let slug = unique_slug(&pool, SlugTable::Artists, artist.slug.clone())
+            .await
+            .unwrap();
+        assert_eq!(slug, format!("{}-2", artist.slug));
+
+        let slug = unique_slug(&pool, SlugTable::Releases, artist.slug.clone())
+            .await
+            .unwrap();
+        assert_eq!(slug, artist.slug);
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_includes_deleted(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        release.delete(&pool).await.unwrap();
+
+        let slug = unique_slug(&pool, SlugTable::Releases, release.slug.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(slug, format!("{}-2", release.slug));
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_with_suffix(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let slug = unique_slug_with(
+            &pool,
+            SlugTable::Tracks,
+            track.slug.clone(),
+            0,
+            SlugCollisionStrategy::Suffix,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(slug, format!("{}-2", track.slug));
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_with_error(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = unique_slug_with(
+            &pool,
+            SlugTable::Tracks,
+            track.slug.clone(),
+            0,
+            SlugCollisionStrategy::Error,
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Slug must be unique.");
+        // The track's own slug is not a collision
+        let slug = unique_slug_with(
+            &pool,
+            SlugTable::Tracks,
+            track.slug.clone(),
+            track.id,
+            SlugCollisionStrategy::Error,
+        )
+        .await
+        .unwrap();
+        assert_eq!(slug, track.slug);
+    }
+
+    #[sqlx::test]
+    async fn test_unique_slug_with_timestamp(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let slug = unique_slug_with(
+            &pool,
+            SlugTable::Tracks,
+            track.slug.clone(),
+            0,
+            SlugCollisionStrategy::Timestamp,
+        )
+        .await
+        .unwrap();
+
+        let datestamp = slug.strip_prefix(&format!("{}-", track.slug)).unwrap();
+        assert_eq!(datestamp.len(), 14);
+        assert!(datestamp.starts_with(&chrono::Utc::now().format("%Y%m%d").to_string()));
+    }
+}
@@ -0,0 +1,131 @@
+//! Slug redirect model
+//!
+//! The `SlugRedirect` struct is used to remember the old slug of an artist, release or track after it was renamed.
+//! Redirects are only created when a published slug is explicitly unlocked and changed.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgExecutor, PgPool};
+
+/// Errors returned by the slug redirect model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum SlugRedirectError {
+    /// The redirect could not be created
+    #[error("Could not create redirect from {old_slug} to {new_slug}.")]
+    Create {
+        /// The slug being redirected
+        old_slug: String,
+        /// The slug redirected to
+        new_slug: String,
+    },
+    /// The redirects from the old slug could not be found
+    #[error("Could not find redirects for {entity_type} with slug {old_slug}.")]
+    ListByOldSlug {
+        /// The type of entity, e.g. "artist"
+        entity_type: String,
+        /// The old slug
+        old_slug: String,
+    },
+}
+
+/// The `SlugRedirect` struct is used to represent a slug redirect in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct SlugRedirect {
+    /// The unique identifier of the redirect
+    pub id: i64,
+    /// The type of entity the slug is for, one of "artist", "release" or "track"
+    pub entity_type: String,
+    /// The ID of the artist, release or track
+    pub entity_id: i64,
+    /// The slug before the rename
+    pub old_slug: String,
+    /// The slug after the rename
+    pub new_slug: String,
+    /// The date and time the redirect was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl SlugRedirect {
+    /// Create a slug redirect
+    ///
+    /// # Arguments
+    /// * `executor` - The database connection pool, or a connection that may be part of a transaction
+    /// * `entity_type` - The type of entity, one of "artist", "release" or "track"
+    /// * `entity_id` - The ID of the entity
+    /// * `old_slug` - The slug before the rename
+    /// * `new_slug` - The slug after the rename
+    ///
+    /// # Returns
+    /// The slug redirect
+    ///
+    /// # Errors
+    /// If the redirect cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(
+        executor: impl PgExecutor<'_>,
+        entity_type: &str,
+        entity_id: i64,
+        old_slug: &str,
+        new_slug: &str,
+    ) -> Result<Self, SlugRedirectError> {
+        let redirect = sqlx::query_as::<_, Self>(
+            "INSERT INTO slug_redirects (entity_type, entity_id, old_slug, new_slug) VALUES ($1, $2, $3, $4) RETURNING *",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(old_slug)
+        .bind(new_slug)
+        .fetch_one(executor)
+        .await;
+
+        match redirect {
+            Ok(redirect) => Ok(redirect),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SlugRedirectError::Create {
+                    old_slug: old_slug.to_string(),
+                    new_slug: new_slug.to_string(),
+                })
+            }
+        }
+    }
+
+    /// List the redirects from an old slug, newest first
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `entity_type` - The type of entity, one of "artist", "release" or "track"
+    /// * `old_slug` - The slug before the rename
+    ///
+    /// # Returns
+    /// The slug redirects
+    ///
+    /// # Errors
+    /// If the redirects cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_by_old_slug(
+        pool: &PgPool,
+        entity_type: &str,
+        old_slug: &str,
+    ) -> Result<Vec<Self>, SlugRedirectError> {
+        let redirects = sqlx::query_as::<_, Self>(
+            "SELECT * FROM slug_redirects WHERE entity_type = $1 AND old_slug = $2 ORDER BY created_at DESC, id DESC",
+        )
+        .bind(entity_type)
+        .bind(old_slug)
+        .fetch_all(pool)
+        .await;
+
+        match redirects {
+            Ok(redirects) => Ok(redirects),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SlugRedirectError::ListByOldSlug {
+                    entity_type: entity_type.to_string(),
+                    old_slug: old_slug.to_string(),
+                })
+            }
+        }
+    }
+}
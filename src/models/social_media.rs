@@ -1,7 +1,7 @@
 //! This module defines an enumeration for various social media platforms.
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgConnection, PgPool};
 use std::fmt;
 use std::slice::Iter;
 
@@ -59,6 +59,25 @@ impl SocialMedia {
     }
 }
 
+/// Errors returned by the social media model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum SocialMediaError {
+    /// The social media service is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// Any other database error
+    #[error("Could not access social media services.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for SocialMediaError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
 /// Represents a social media service associated with an artist.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "ssr", derive(FromRow))]
@@ -116,7 +135,7 @@ impl SocialMediaService {
         artist_id: i64,
         platform: SocialMedia,
         url: String,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, SocialMediaError> {
         let service = Self {
             id: 0, // This will be set by the database
             artist_id,
@@ -125,17 +144,37 @@ impl SocialMediaService {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        service.validate(pool).await?;
+        service
+            .validate(pool)
+            .await
+            .map_err(|e| SocialMediaError::Invalid(e.to_string()))?;
+
+        let mut conn = pool.acquire().await?;
+        service.insert(&mut conn).await
+    }
 
+    /// Inserts the social media service into the database.
+    /// The social media service should be validated first.
+    ///
+    /// # Arguments
+    /// * `conn`: The database connection, which may be part of a transaction.
+    ///
+    /// # Returns
+    /// * The inserted social media service.
+    ///
+    /// # Errors
+    /// If the social media service cannot be inserted, an error will be returned.
+    #[cfg(feature = "ssr")]
+    pub async fn insert(self, conn: &mut PgConnection) -> Result<Self, SocialMediaError> {
         let service = sqlx::query_as::<_, Self>(
             "INSERT INTO social_media (artist_id, platform, url)
                 VALUES ($1, $2, $3)
                 RETURNING *",
         )
-        .bind(service.artist_id)
-        .bind(service.platform)
-        .bind(service.url)
-        .fetch_one(pool)
+        .bind(self.artist_id)
+        .bind(self.platform)
+        .bind(self.url)
+        .fetch_one(conn)
         .await?;
 
         Ok(service)
@@ -153,10 +192,13 @@ impl SocialMediaService {
     /// # Errors
     /// If the artist does not exist, or if there is an issue with the database connection, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn list_by_artist(pool: &PgPool, artist_id: i64) -> anyhow::Result<Vec<Self>> {
+    pub async fn list_by_artist(
+        pool: &PgPool,
+        artist_id: i64,
+    ) -> Result<Vec<Self>, SocialMediaError> {
         if artist_id <= 0 {
-            return Err(anyhow::anyhow!(
-                "Artist ID must be greater than 0".to_string()
+            return Err(SocialMediaError::Invalid(
+                "Artist ID must be greater than 0".to_string(),
             ));
         }
 
@@ -181,8 +223,10 @@ impl SocialMediaService {
     /// # Errors
     /// If the social media service does not exist, or if the artist does not exist, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn update(&self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.validate(pool).await?;
+    pub async fn update(&self, pool: &PgPool) -> Result<Self, SocialMediaError> {
+        self.validate(pool)
+            .await
+            .map_err(|e| SocialMediaError::Invalid(e.to_string()))?;
 
         let service = sqlx::query_as::<_, Self>(
             "UPDATE social_media SET artist_id = $1, platform = $2, url = $3, updated_at = NOW()
@@ -209,7 +253,7 @@ impl SocialMediaService {
     /// # Errors
     /// If the social media service does not exist, or if there is an issue with the database connection, an error will be returned.
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<()> {
+    pub async fn delete(&self, pool: &PgPool) -> Result<(), SocialMediaError> {
         sqlx::query("DELETE FROM social_media WHERE id = $1")
             .bind(self.id)
             .execute(pool)
@@ -225,6 +269,34 @@ mod tests {
     #[cfg(feature = "ssr")]
     use crate::models::test_helpers::create_test_artist;
 
+    #[test]
+    fn test_social_media_error_display() {
+        let cases = [
+            (
+                SocialMediaError::Invalid("URL cannot be empty".to_string()),
+                "URL cannot be empty",
+            ),
+            (
+                SocialMediaError::Database,
+                "Could not access social media services.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_social_media_error_hides_database_error() {
+        let error = SocialMediaError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, SocialMediaError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
     #[test]
     fn test_init_social_media_service() {
         let service = SocialMediaService {
@@ -0,0 +1,187 @@
+//! Subscriber model
+//!
+//! The `Subscriber` struct is used to represent someone who receives the new release digest for a record label.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+/// Errors returned by the subscriber model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum SubscriberError {
+    /// The subscriber is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// The email address could not be subscribed
+    #[error("Could not subscribe {0}.")]
+    Create(String),
+    /// The subscribers for the record label could not be found
+    #[error("Could not find subscribers for record label with id {0}.")]
+    ListByRecordLabel(i64),
+}
+
+/// The `Subscriber` struct is used to represent a digest subscriber in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct Subscriber {
+    /// The unique identifier of the subscriber
+    pub id: i64,
+    /// The record label the subscriber receives the digest for
+    pub label_id: i64,
+    /// The email address the digest is sent to
+    pub email: String,
+    /// The date and time the subscriber signed up
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Subscriber {
+    /// Subscribe an email address to a record label's digest
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The ID of the record label
+    /// * `email` - The email address to send the digest to
+    ///
+    /// # Returns
+    /// The subscriber
+    ///
+    /// # Errors
+    /// If the email is invalid, return an error
+    /// If the email is already subscribed, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn create(
+        pool: &PgPool,
+        label_id: i64,
+        email: String,
+    ) -> Result<Self, SubscriberError> {
+        let email = email.trim().to_lowercase();
+        if email.len() > 255 {
+            return Err(SubscriberError::Invalid(
+                "Email must be less than 255 characters.".to_string(),
+            ));
+        }
+        if !email.contains('@') {
+            return Err(SubscriberError::Invalid("Email must be valid.".to_string()));
+        }
+
+        let subscriber = sqlx::query_as::<_, Self>(
+            "INSERT INTO subscribers (label_id, email) VALUES ($1, $2) RETURNING *",
+        )
+        .bind(label_id)
+        .bind(email.clone())
+        .fetch_one(pool)
+        .await;
+
+        match subscriber {
+            Ok(subscriber) => Ok(subscriber),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SubscriberError::Create(email))
+            }
+        }
+    }
+
+    /// List the subscribers for a record label
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The subscribers, oldest first
+    ///
+    /// # Errors
+    /// If the subscribers cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_by_record_label(
+        pool: &PgPool,
+        label_id: i64,
+    ) -> Result<Vec<Self>, SubscriberError> {
+        let subscribers = sqlx::query_as::<_, Self>(
+            "SELECT * FROM subscribers WHERE label_id = $1 ORDER BY created_at ASC, id ASC",
+        )
+        .bind(label_id)
+        .fetch_all(pool)
+        .await;
+
+        match subscribers {
+            Ok(subscribers) => Ok(subscribers),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(SubscriberError::ListByRecordLabel(label_id))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::create_test_record_label;
+
+    #[test]
+    fn test_subscriber_error_display() {
+        let cases = [
+            (
+                SubscriberError::Invalid("Email must be valid.".to_string()),
+                "Email must be valid.",
+            ),
+            (
+                SubscriberError::Create("fan@example.com".to_string()),
+                "Could not subscribe fan@example.com.",
+            ),
+            (
+                SubscriberError::ListByRecordLabel(3),
+                "Could not find subscribers for record label with id 3.",
+            ),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let subscriber =
+            Subscriber::create(&pool, record_label.id, " Fan@Example.com ".to_string())
+                .await
+                .unwrap();
+
+        assert_eq!(subscriber.label_id, record_label.id);
+        assert_eq!(subscriber.email, "fan@example.com");
+        let subscribers = Subscriber::list_by_record_label(&pool, record_label.id)
+            .await
+            .unwrap();
+        assert_eq!(subscribers, vec![subscriber]);
+    }
+
+    #[sqlx::test]
+    async fn test_create_invalid_email(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let result = Subscriber::create(&pool, record_label.id, "not-an-email".to_string()).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "Email must be valid.");
+    }
+
+    #[sqlx::test]
+    async fn test_create_duplicate(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        Subscriber::create(&pool, record_label.id, "fan@example.com".to_string())
+            .await
+            .unwrap();
+
+        let result =
+            Subscriber::create(&pool, record_label.id, "fan@example.com".to_string()).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Could not subscribe fan@example.com."
+        );
+    }
+}
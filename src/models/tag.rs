@@ -0,0 +1,312 @@
+//! Tag model
+//!
+//! The `Tag` struct is used to organise the releases in a record label's catalogue.
+//! Tags belong to a record label, and their slugs are unique within it.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::{FromRow, PgPool};
+
+#[cfg(feature = "ssr")]
+use crate::config::slug::{slug_lowercase, slug_separator};
+#[cfg(feature = "ssr")]
+use crate::utils::slugify::slugify_with;
+
+/// Errors returned by the tag model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum TagError {
+    /// No tag has the given slug
+    #[error("Could not find tag with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The tags for the release could not be found
+    #[error("Could not find tags for release with id {0}.")]
+    ListByRelease(i64),
+    /// No tag was given
+    #[error("Tag is required.")]
+    Required,
+    /// Some of the releases are not on the record label
+    #[error("Releases must belong to record label with id {0}.")]
+    WrongRecordLabel(i64),
+    /// The releases could not be tagged or untagged
+    #[error("Could not update releases with tag {0}.")]
+    UpdateReleases(String),
+    /// Any other database error
+    #[error("Could not access tags.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for TagError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
+
+/// The `Tag` struct is used to represent a tag in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct Tag {
+    /// The unique identifier of the tag
+    pub id: i64,
+    /// The record label the tag belongs to
+    pub label_id: i64,
+    /// The name of the tag
+    pub name: String,
+    /// The slug of the tag, unique within the record label
+    pub slug: String,
+    /// The date and time the tag was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the tag was last updated
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Tag {
+    /// Get a tag by slug
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The record label the tag belongs to
+    /// * `slug` - The slug of the tag
+    ///
+    /// # Returns
+    /// The tag
+    ///
+    /// # Errors
+    /// If the tag cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_slug(pool: &PgPool, label_id: i64, slug: &str) -> Result<Self, TagError> {
+        let tag = sqlx::query_as::<_, Self>("SELECT * FROM tags WHERE label_id = $1 AND slug = $2")
+            .bind(label_id)
+            .bind(slug)
+            .fetch_one(pool)
+            .await;
+
+        match tag {
+            Ok(tag) => Ok(tag),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TagError::NotFoundBySlug(slug.to_string()))
+            }
+        }
+    }
+
+    /// List the tags on a release, by name
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `release_id` - The ID of the release
+    ///
+    /// # Returns
+    /// The tags
+    ///
+    /// # Errors
+    /// If the tags cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_by_release(pool: &PgPool, release_id: i64) -> Result<Vec<Self>, TagError> {
+        let tags = sqlx::query_as::<_, Self>(
+            "SELECT tags.* FROM tags INNER JOIN release_tags ON release_tags.tag_id = tags.id WHERE release_tags.release_id = $1 ORDER BY tags.name ASC",
+        )
+        .bind(release_id)
+        .fetch_all(pool)
+        .await;
+
+        match tags {
+            Ok(tags) => Ok(tags),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TagError::ListByRelease(release_id))
+            }
+        }
+    }
+
+    /// Add a tag to, or remove it from, many releases at once
+    ///
+    /// Runs in a single transaction, so either every release is changed or none are.
+    /// When adding, the tag is created if the label doesn't have it yet.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `label_id` - The record label the tag and releases belong to
+    /// * `tag` - The name or slug of the tag
+    /// * `release_ids` - The IDs of the releases
+    /// * `add` - Add the tag if true, remove it if false
+    ///
+    /// # Returns
+    /// The tag, or `None` if removing a tag the label doesn't have
+    ///
+    /// # Errors
+    /// If the tag is empty, return an error
+    /// If any release does not belong to the record label, return an error
+    /// If the tag cannot be added or removed, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn bulk_tag_releases(
+        pool: &PgPool,
+        label_id: i64,
+        tag: &str,
+        release_ids: &[i64],
+        add: bool,
+    ) -> Result<Option<Self>, TagError> {
+        let name = tag.trim();
+        // Accept an existing slug as well as a name, e.g. "deep-house" or "Deep House"
+        let slug = slugify_with(name, slug_separator(), slug_lowercase());
+        if slug.is_empty() {
+            return Err(TagError::Required);
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let releases_in_label = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM releases WHERE id = ANY($1) AND label_id = $2 AND deleted_at IS NULL",
+        )
+        .bind(release_ids)
+        .bind(label_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let mut unique_ids = release_ids.to_vec();
+        unique_ids.sort_unstable();
+        unique_ids.dedup();
+        if usize::try_from(releases_in_label).unwrap_or_default() != unique_ids.len() {
+            return Err(TagError::WrongRecordLabel(label_id));
+        }
+
+        let tag = if add {
+            sqlx::query_as::<_, Self>(
+                "INSERT INTO tags (label_id, name, slug) VALUES ($1, $2, $3) ON CONFLICT (label_id, slug) DO UPDATE SET updated_at = NOW() RETURNING *",
+            )
+            .bind(label_id)
+            .bind(name)
+            .bind(&slug)
+            .fetch_one(&mut *tx)
+            .await
+            .map(Some)
+        } else {
+            sqlx::query_as::<_, Self>("SELECT * FROM tags WHERE label_id = $1 AND slug = $2")
+                .bind(label_id)
+                .bind(&slug)
+                .fetch_optional(&mut *tx)
+                .await
+        };
+        let tag = match tag {
+            Ok(Some(tag)) => tag,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(TagError::NotFoundBySlug(slug));
+            }
+        };
+
+        let query = if add {
+            "INSERT INTO release_tags (release_id, tag_id) SELECT UNNEST($1::BIGINT[]), $2 ON CONFLICT DO NOTHING"
+        } else {
+            "DELETE FROM release_tags WHERE release_id = ANY($1) AND tag_id = $2"
+        };
+        match sqlx::query(query)
+            .bind(&unique_ids)
+            .bind(tag.id)
+            .execute(&mut *tx)
+            .await
+        {
+            Ok(_) => (),
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(TagError::UpdateReleases(tag.name));
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{create_test_artist, create_test_release};
+
+    #[test]
+    fn test_tag_error_display() {
+        let cases = [
+            (
+                TagError::NotFoundBySlug("missing".to_string()),
+                "Could not find tag with slug missing.",
+            ),
+            (
+                TagError::ListByRelease(1),
+                "Could not find tags for release with id 1.",
+            ),
+            (TagError::Required, "Tag is required."),
+            (
+                TagError::WrongRecordLabel(3),
+                "Releases must belong to record label with id 3.",
+            ),
+            (
+                TagError::UpdateReleases("Deep House".to_string()),
+                "Could not update releases with tag Deep House.",
+            ),
+            (TagError::Database, "Could not access tags."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_tag_error_hides_database_error() {
+        let error = TagError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, TagError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_tag_releases(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let tag =
+            Tag::bulk_tag_releases(&pool, release.label_id, "Deep House", &[release.id], true)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(tag.name, "Deep House");
+        assert_eq!(tag.slug, "deep-house");
+
+        // Tagging again reuses the tag
+        let again =
+            Tag::bulk_tag_releases(&pool, release.label_id, "deep house", &[release.id], true)
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(again.id, tag.id);
+        assert_eq!(
+            Tag::list_by_release(&pool, release.id).await.unwrap(),
+            vec![again]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_tag_releases_remove_unknown_tag(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let tag = Tag::bulk_tag_releases(&pool, release.label_id, "Ambient", &[release.id], false)
+            .await
+            .unwrap();
+        assert!(tag.is_none());
+        assert!(
+            Tag::get_by_slug(&pool, release.label_id, "ambient")
+                .await
+                .is_err()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_tag_releases_empty_tag(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let result = Tag::bulk_tag_releases(&pool, artist.label_id, " ", &[], true).await;
+        assert_eq!(result.unwrap_err().to_string(), "Tag is required.");
+    }
+}
@@ -310,3 +310,33 @@ pub async fn create_test_user_with_permissions(
     let (user, _) = user.into_user(Some(permission_tokens));
     Ok(user)
 }
+
+/// Make every transaction that changes the table fail when it commits
+///
+/// A deferred constraint trigger raises an error at commit time, after every statement in the transaction has succeeded.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `table` - The table to watch
+///
+/// # Errors
+/// If the trigger cannot be created, return an error
+#[cfg(feature = "ssr")]
+pub async fn fail_commits_on(pool: &PgPool, table: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "CREATE OR REPLACE FUNCTION fail_commit() RETURNS TRIGGER AS $$
+         BEGIN
+             RAISE EXCEPTION 'Commit failed on purpose';
+         END;
+         $$ LANGUAGE plpgsql",
+    )
+    .execute(pool)
+    .await?;
+    let trigger = format!(
+        "CREATE CONSTRAINT TRIGGER fail_commit AFTER INSERT OR UPDATE OR DELETE ON {table}
+         DEFERRABLE INITIALLY DEFERRED
+         FOR EACH ROW EXECUTE FUNCTION fail_commit()"
+    );
+    sqlx::query(&trigger).execute(pool).await?;
+    Ok(())
+}
@@ -4,13 +4,131 @@
 
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgConnection, PgPool};
 
 use super::traits::Validate;
 #[cfg(feature = "ssr")]
-use super::{artist::Artist, release::Release};
+use super::{
+    artist::Artist,
+    query_helpers::{published_at_clause, published_clause, release_visible_clause},
+    release::Release,
+    slug::{SlugTable, unique_slug, unique_slug_with},
+    slug_redirect::SlugRedirect,
+    track_relation::TrackRelation,
+};
+use crate::config::image::{PlaceholderImage, PlaceholderImages};
 #[cfg(feature = "ssr")]
-use crate::utils::slugify::slugify;
+use crate::config::slug::{slug_collision_strategy, slug_lowercase, slug_separator};
+use crate::utils::{duration::format_duration, files::upload_url_or};
+#[cfg(feature = "ssr")]
+use crate::utils::{
+    pagination::Pagination,
+    search::search_patterns,
+    slugify::{is_reserved_slug, is_slug_locked, slugify, slugify_with},
+    unicode::normalise_name,
+    urls::is_valid_url,
+};
+
+/// Errors returned by the track model
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum TrackError {
+    /// The track is invalid, with the reason why
+    #[error("{0}")]
+    Invalid(String),
+    /// No track has the given ID
+    #[error("Could not find track with id {0}.")]
+    NotFoundById(i64),
+    /// No track has the given slug
+    #[error("Could not find track with slug {0}.")]
+    NotFoundBySlug(String),
+    /// The release has no track with the given slug for the artist on the record label
+    #[error(
+        "Could not find track {slug} for release with id {release_id} and artist with id {artist_id} and record label with id {record_label_id}."
+    )]
+    NotFoundForRelease {
+        /// The slug of the track
+        slug: String,
+        /// The ID of the release
+        release_id: i64,
+        /// The ID of the artist
+        artist_id: i64,
+        /// The ID of the record label
+        record_label_id: i64,
+    },
+    /// The tracks for the release could not be found
+    #[error(
+        "Could not find tracks for release with id {release_id} and artist with id {artist_id} and record label with id {record_label_id}."
+    )]
+    ListByRelease {
+        /// The ID of the release
+        release_id: i64,
+        /// The ID of the artist
+        artist_id: i64,
+        /// The ID of the record label
+        record_label_id: i64,
+    },
+    /// The tracks for the artist could not be found
+    #[error("Could not find tracks for artist with id {0}.")]
+    ListByArtist(i64),
+    /// The tracks on the record label could not be searched
+    #[error("Could not search tracks for record label with id {0}.")]
+    Search(i64),
+    /// The clean version of the track could not be found
+    #[error("Could not find clean version of track with id {0}.")]
+    CleanCounterpart(i64),
+    /// The track could not be created
+    #[error("Could not create track.")]
+    Create,
+    /// The track could not be updated
+    #[error("Could not update track with id {0}.")]
+    Update(i64),
+    /// The track could not be deleted
+    #[error("Could not delete track with id {0}.")]
+    Delete(i64),
+    /// The track could not be archived or unarchived
+    #[error("Could not change the archived state of track with id {0}.")]
+    Archive(i64),
+    /// The archived tracks for the record label could not be found
+    #[error("Could not find archived tracks for record label with id {0}.")]
+    Archived(i64),
+    /// The old slug could not be redirected to the new one
+    #[error("Could not redirect track slug {0}.")]
+    Redirect(String),
+    /// The artists of the track could not be set
+    #[error("Could not set artists for track with id {0}.")]
+    SetArtists(i64),
+    /// The artists of the track could not be found
+    #[error("Could not find artists for track with id {0}.")]
+    Artists(i64),
+    /// The relations of the track could not be set
+    #[error("Could not set relations for track with id {0}.")]
+    SetRelations(i64),
+    /// The relations of the track could not be found
+    #[error("Could not find relations for track with id {0}.")]
+    Relations(i64),
+    /// Another track on the release already has the track number
+    #[error("Track number {track_number} must be unique for release with id {release_id}.")]
+    TrackNumberConflict {
+        /// The conflicting track number
+        track_number: i32,
+        /// The ID of the release
+        release_id: i64,
+    },
+    /// The tracks could not be moved to the release
+    #[error("Could not move tracks to release with id {0}.")]
+    Move(i64),
+    /// Any other database error
+    #[error("Could not access tracks.")]
+    Database,
+}
+
+#[cfg(feature = "ssr")]
+impl From<sqlx::Error> for TrackError {
+    fn from(e: sqlx::Error) -> Self {
+        tracing::error!("{e}");
+        Self::Database
+    }
+}
 
 /// The Track struct is used to represent a record track in the database.
 #[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
@@ -22,6 +140,9 @@ pub struct Track {
     pub name: String,
     /// The slug of the track
     pub slug: String,
+    /// Should the slug be kept when the name changes
+    /// If this is None, the slug is locked once the track has been published
+    pub lock_slug: Option<bool>,
     /// The description of the track
     pub description: String,
     /// Lyrics for the track
@@ -40,6 +161,16 @@ pub struct Track {
     /// The BPM or beats per minute of the track
     /// For tracks with variable BPM, this value is undefined
     pub bpm: Option<i32>,
+    /// A URL to a short preview clip of the track
+    pub preview_url: Option<String>,
+    /// Where the preview clip starts playing from, in milliseconds
+    pub preview_start_ms: Option<i32>,
+    /// The length of the track, in milliseconds
+    pub duration_ms: Option<i32>,
+    /// Does the track contain explicit lyrics
+    pub explicit: bool,
+    /// The explicit track on the same release that this track is the clean version of
+    pub clean_version_of: Option<i64>,
     /// Track number is the position of the track on a release
     pub track_number: i32,
     /// The date the track is published.
@@ -47,10 +178,17 @@ pub struct Track {
     /// If this is in the future, the track is scheduled to be published
     /// If this is in the past, the track is published
     pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The date the track first went live, used for "in catalogue since" displays
+    /// This is set once and kept when the track is rescheduled or unpublished.
+    pub first_published_at: Option<chrono::DateTime<chrono::Utc>>,
     /// The date and time the track was created in the database
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// The date and time the track was last updated
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// The date and time the track was archived
+    /// Archived tracks are excluded from listings, but can still be reached by their slug.
+    /// If this is None, the track is not archived
+    pub archived_at: Option<chrono::DateTime<chrono::Utc>>,
     /// The date and time the track was deleted
     /// If this is None, the track is not deleted
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -73,21 +211,27 @@ impl Validate for Track {
                 "Slug must be less than 255 characters.".to_string()
             ));
         }
+        if is_reserved_slug(&self.slug) {
+            return Err(anyhow::anyhow!("Slug is reserved.".to_string()));
+        }
         // Check that the slug is unique
-        if let Ok(track) = Self::get_by_slug(pool, self.slug.clone()).await
+        if let Ok(track) = Self::get_by_slug(pool, self.slug.clone(), true).await
             && track.id != self.id
         {
             return Err(anyhow::anyhow!("Slug must be unique.".to_string()));
         }
 
         // Check that the artist referenced in the primary_artist_id exists
-        if let Err(e) = Artist::get_by_id(pool, self.primary_artist_id).await {
-            tracing::error!("{e}");
-            return Err(anyhow::anyhow!(
-                "Artist with id {} does not exist.",
-                self.primary_artist_id
-            ));
-        }
+        let artist = match Artist::get_by_id(pool, self.primary_artist_id).await {
+            Ok(artist) => artist,
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(anyhow::anyhow!(
+                    "Artist with id {} does not exist.",
+                    self.primary_artist_id
+                ));
+            }
+        };
 
         // Check that the release exists
         let release = match Release::get_by_id(pool, self.release_id).await {
@@ -100,6 +244,19 @@ impl Validate for Track {
                 ));
             }
         };
+        // Live tracks can't belong to deleted parents
+        if self.deleted_at.is_none() {
+            if release.deleted_at.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Cannot add a track to a deleted release.".to_string()
+                ));
+            }
+            if artist.deleted_at.is_some() {
+                return Err(anyhow::anyhow!(
+                    "Cannot add a track to a deleted artist.".to_string()
+                ));
+            }
+        }
         self.validate_track_number(&release, pool).await?;
 
         if let Some(ref isrc_code) = self.isrc_code {
@@ -119,6 +276,25 @@ impl Validate for Track {
             }
         }
 
+        if let Some(ref preview_url) = self.preview_url
+            && !is_valid_url(preview_url)
+        {
+            return Err(anyhow::anyhow!(
+                "Preview URL must be a valid URL.".to_string()
+            ));
+        }
+        if self.preview_start_ms.is_some_and(|start| start < 0) {
+            return Err(anyhow::anyhow!(
+                "Preview start must be 0 or greater.".to_string()
+            ));
+        }
+        if self.duration_ms.is_some_and(|duration| duration < 0) {
+            return Err(anyhow::anyhow!(
+                "Duration must be 0 or greater.".to_string()
+            ));
+        }
+        self.validate_clean_version_of(pool).await?;
+
         Ok(())
     }
 }
@@ -151,12 +327,59 @@ impl Track {
         Ok(())
     }
 
+    /// Validate that the track this is a clean version of is an explicit track on the same release
+    /// This is broken out from the main validate function to simplify the code
+    #[cfg(feature = "ssr")]
+    async fn validate_clean_version_of(&self, pool: &PgPool) -> anyhow::Result<()> {
+        let Some(explicit_id) = self.clean_version_of else {
+            return Ok(());
+        };
+        if explicit_id == self.id {
+            return Err(anyhow::anyhow!(
+                "A track cannot be the clean version of itself.".to_string()
+            ));
+        }
+        if self.explicit {
+            return Err(anyhow::anyhow!(
+                "A clean version cannot be marked as explicit.".to_string()
+            ));
+        }
+
+        let explicit_track = Self::get_by_id(pool, explicit_id).await?;
+        if explicit_track.release_id != self.release_id {
+            return Err(anyhow::anyhow!(
+                "The explicit version must be on the same release.".to_string()
+            ));
+        }
+        if !explicit_track.explicit {
+            return Err(anyhow::anyhow!(
+                "The explicit version must be marked as explicit.".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Format the duration of the track for display
+    /// Durations under an hour are shown as `m:ss`, longer durations as `h:mm:ss`.
+    /// If the duration is not set, return None
+    ///
+    /// # Example
+    /// ```
+    /// use white_label::models::track::Track;
+    /// let track = Track { duration_ms: Some(225_000), ..Default::default() };
+    /// assert_eq!(track.duration_display(), Some("3:45".to_string()));
+    /// ```
+    pub fn duration_display(&self) -> Option<String> {
+        Some(format_duration(self.duration_ms? / 1000))
+    }
+
     /// Get the primary image URL
-    /// If the primary image is None, return the default image
-    pub fn primary_image_url(&self) -> String {
-        self.primary_image.clone().map_or_else(
-            || "/Logo.svg".to_string(),
-            |file| format!("/uploads/tracks/{file}"),
+    /// If the primary image is None, return the track placeholder
+    pub fn primary_image_url(&self, placeholders: &PlaceholderImages) -> String {
+        upload_url_or(
+            "tracks",
+            self.primary_image.as_deref(),
+            placeholders.url(PlaceholderImage::Track),
         )
     }
 
@@ -168,6 +391,9 @@ impl Track {
     /// * `description` - The description of the track
     /// * `isrc_code` - The ISRC code of the track
     /// * `bpm` - The BPM of the track
+    /// * `preview_url` - A URL to a preview clip of the track
+    /// * `preview_start_ms` - Where the preview clip starts, in milliseconds
+    /// * `duration_ms` - The length of the track, in milliseconds
     ///
     /// # Returns
     /// The created track
@@ -186,15 +412,127 @@ impl Track {
         release_id: i64,
         isrc_code: Option<String>,
         bpm: Option<i32>,
+        preview_url: Option<String>,
+        preview_start_ms: Option<i32>,
+        duration_ms: Option<i32>,
+        track_number: i32,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Self, TrackError> {
+        let name = normalise_name(&name);
+        let slug = unique_slug(pool, SlugTable::Tracks, slugify(&name))
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
+
+        Self::insert(
+            pool,
+            name,
+            slug,
+            description,
+            lyrics,
+            primary_artist_id,
+            release_id,
+            isrc_code,
+            bpm,
+            preview_url,
+            preview_start_ms,
+            duration_ms,
+            track_number,
+            published_at,
+        )
+        .await
+    }
+
+    /// Create a new track with the given slug, e.g. to keep the URLs of imported tracks working
+    ///
+    /// The slug is sanitised with `slugify`, falling back to the name if nothing is left.
+    /// If another track already has the slug, the slug collision strategy applies, by default a number is appended, e.g. "my-track-2".
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `slug` - The slug to use for the track
+    /// * `name` - The name of the track
+    /// * `description` - The description of the track
+    /// * `isrc_code` - The ISRC code of the track
+    /// * `bpm` - The BPM of the track
+    /// * `preview_url` - A URL to a preview clip of the track
+    /// * `preview_start_ms` - Where the preview clip starts, in milliseconds
+    /// * `duration_ms` - The length of the track, in milliseconds
+    ///
+    /// # Returns
+    /// The created track
+    ///
+    /// # Errors
+    /// If the slug is reserved, return an error
+    /// If the track cannot be created, return an error
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "ssr")]
+    pub async fn create_with_slug(
+        pool: &PgPool,
+        slug: &str,
+        name: String,
+        description: String,
+        lyrics: String,
+        primary_artist_id: i64,
+        release_id: i64,
+        isrc_code: Option<String>,
+        bpm: Option<i32>,
+        preview_url: Option<String>,
+        preview_start_ms: Option<i32>,
+        duration_ms: Option<i32>,
         track_number: i32,
         published_at: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> anyhow::Result<Self> {
-        let slug = slugify(&name);
+    ) -> Result<Self, TrackError> {
+        let name = normalise_name(&name);
+        let slug = Some(slugify_with(slug, slug_separator(), slug_lowercase()))
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or_else(|| slugify(&name));
+        let slug = unique_slug(pool, SlugTable::Tracks, slug)
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
+
+        Self::insert(
+            pool,
+            name,
+            slug,
+            description,
+            lyrics,
+            primary_artist_id,
+            release_id,
+            isrc_code,
+            bpm,
+            preview_url,
+            preview_start_ms,
+            duration_ms,
+            track_number,
+            published_at,
+        )
+        .await
+    }
 
+    /// Validate and insert a new track
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "ssr")]
+    async fn insert(
+        pool: &PgPool,
+        name: String,
+        slug: String,
+        description: String,
+        lyrics: String,
+        primary_artist_id: i64,
+        release_id: i64,
+        isrc_code: Option<String>,
+        bpm: Option<i32>,
+        preview_url: Option<String>,
+        preview_start_ms: Option<i32>,
+        duration_ms: Option<i32>,
+        track_number: i32,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Self, TrackError> {
         let track = Self {
             id: 0,
             name,
             slug,
+            lock_slug: None,
             description,
             lyrics,
             primary_artist_id,
@@ -202,16 +540,27 @@ impl Track {
             primary_image: None,
             isrc_code,
             bpm,
+            preview_url,
+            preview_start_ms,
+            duration_ms,
+            explicit: false,
+            clean_version_of: None,
             track_number,
             published_at,
+            first_published_at: published_at
+                .filter(|published_at| *published_at <= chrono::Utc::now()),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
-        track.validate(pool).await?;
+        track
+            .validate(pool)
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
 
         let track = sqlx::query_as::<_, Self>(
-         "INSERT INTO tracks (name, slug, description, lyrics, primary_artist_id, release_id, isrc_code, bpm, track_number, published_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *",
+         "INSERT INTO tracks (name, slug, description, lyrics, primary_artist_id, release_id, isrc_code, bpm, preview_url, preview_start_ms, duration_ms, track_number, published_at, first_published_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) RETURNING *",
      )
          .bind(track.name)
          .bind(track.slug)
@@ -221,12 +570,95 @@ impl Track {
          .bind(track.release_id)
          .bind(track.isrc_code)
          .bind(track.bpm)
+         .bind(track.preview_url)
+         .bind(track.preview_start_ms)
+         .bind(track.duration_ms)
          .bind(track.track_number)
          .bind(track.published_at)
+         .bind(track.first_published_at)
          .fetch_one(pool)
-         .await?;
+         .await;
 
-        Ok(track)
+        match track {
+            Ok(track) => Ok(track),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Create)
+            }
+        }
+    }
+
+    /// Get track by ID
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `id` - The ID of the track
+    ///
+    /// # Returns
+    /// The track
+    ///
+    /// # Errors
+    /// If the track cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_id(pool: &PgPool, id: i64) -> Result<Self, TrackError> {
+        let track = sqlx::query_as::<_, Self>("SELECT * FROM tracks WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await;
+
+        match track {
+            Ok(track) => Ok(track),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::NotFoundById(id))
+            }
+        }
+    }
+
+    /// Get the clean version of this track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The clean version, or None if the track doesn't have one
+    ///
+    /// # Errors
+    /// If there is an error getting the clean version, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn clean_counterpart(&self, pool: &PgPool) -> Result<Option<Self>, TrackError> {
+        let track = sqlx::query_as::<_, Self>(
+            "SELECT * FROM tracks WHERE clean_version_of = $1 AND deleted_at IS NULL ORDER BY id ASC LIMIT 1",
+        )
+        .bind(self.id)
+        .fetch_optional(pool)
+        .await;
+
+        match track {
+            Ok(track) => Ok(track),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::CleanCounterpart(self.id))
+            }
+        }
+    }
+
+    /// Get the explicit version of this track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The explicit version, or None if this track isn't a clean version
+    ///
+    /// # Errors
+    /// If there is an error getting the explicit version, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn explicit_counterpart(&self, pool: &PgPool) -> Result<Option<Self>, TrackError> {
+        match self.clean_version_of {
+            Some(explicit_id) => Ok(Some(Self::get_by_id(pool, explicit_id).await?)),
+            None => Ok(None),
+        }
     }
 
     /// Get track by slug
@@ -234,6 +666,7 @@ impl Track {
     /// # Arguments
     /// * `pool` - The database connection pool
     /// * `slug` - The slug of the track
+    /// * `include_deleted` - Whether to resolve soft-deleted tracks (admin edit and restore flows)
     ///
     /// # Returns
     /// The track
@@ -241,8 +674,18 @@ impl Track {
     /// # Errors
     /// If the track cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_by_slug(pool: &PgPool, slug: String) -> anyhow::Result<Self> {
-        let track = sqlx::query_as::<_, Self>("SELECT * FROM tracks WHERE slug = $1")
+    pub async fn get_by_slug(
+        pool: &PgPool,
+        slug: String,
+        include_deleted: bool,
+    ) -> Result<Self, TrackError> {
+        let query = if include_deleted {
+            "SELECT * FROM tracks WHERE slug = $1"
+        } else {
+            "SELECT * FROM tracks WHERE slug = $1 AND deleted_at IS NULL"
+        };
+
+        let track = sqlx::query_as::<_, Self>(query)
             .bind(slug.clone())
             .fetch_one(pool)
             .await;
@@ -251,7 +694,7 @@ impl Track {
             Ok(track) => Ok(track),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!("Could not find track with slug {slug}."))
+                Err(TrackError::NotFoundBySlug(slug))
             }
         }
     }
@@ -279,7 +722,7 @@ impl Track {
         record_label_id: i64,
         slug: String,
         include_hidden: bool,
-    ) -> anyhow::Result<Self> {
+    ) -> Result<Self, TrackError> {
         let query = if include_hidden {
             "SELECT t.*
              FROM tracks t
@@ -292,8 +735,10 @@ impl Track {
                 AND a.id = $3
                 AND a.label_id = $4
             ORDER BY t.deleted_at DESC, t.track_number ASC"
+                .to_string()
         } else {
-            "SELECT t.*
+            format!(
+                "SELECT t.*
              FROM tracks t
              INNER JOIN release_artists ra
              ON t.release_id = ra.release_id
@@ -303,13 +748,13 @@ impl Track {
                 AND t.release_id = $2
                 AND a.id = $3
                 AND a.label_id = $4
-                AND t.deleted_at IS NULL
-                AND t.published_at < NOW()
-                AND t.published_at IS NOT NULL
-            ORDER BY t.track_number ASC"
+                AND {}
+            ORDER BY t.track_number ASC",
+                published_clause("t")
+            )
         };
 
-        let track = sqlx::query_as::<_, Self>(query)
+        let track = sqlx::query_as::<_, Self>(&query)
             .bind(slug.clone())
             .bind(release_id)
             .bind(artist_id)
@@ -322,13 +767,12 @@ impl Track {
             Ok(track) => Ok(track),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find track {} for release with id {} and artist with id {} and record label with id {}.",
+                Err(TrackError::NotFoundForRelease {
                     slug,
                     release_id,
                     artist_id,
                     record_label_id,
-                ))
+                })
             }
         }
     }
@@ -355,7 +799,7 @@ impl Track {
         artist_id: i64,
         record_label_id: i64,
         include_hidden: bool,
-    ) -> anyhow::Result<Vec<Self>> {
+    ) -> Result<Vec<Self>, TrackError> {
         let query = if include_hidden {
             "SELECT t.*
              FROM tracks t
@@ -365,21 +809,24 @@ impl Track {
              ON a.id = ra.artist_id
              WHERE t.release_id = $1 AND a.id = $2 AND a.label_id = $3
              ORDER BY t.deleted_at DESC, t.track_number ASC"
+                .to_string()
         } else {
-            "SELECT t.*
+            format!(
+                "SELECT t.*
              FROM tracks t
              INNER JOIN release_artists ra
              ON t.release_id = ra.release_id
              INNER JOIN artists a
              ON a.id = ra.artist_id
              WHERE t.release_id = $1 AND a.id = $2 AND a.label_id = $3
-              AND t.deleted_at IS NULL
-              AND t.published_at < NOW()
-              AND t.published_at IS NOT NULL
-             ORDER BY t.track_number ASC"
+              AND {}
+              AND t.archived_at IS NULL
+             ORDER BY t.track_number ASC",
+                published_clause("t")
+            )
         };
 
-        let tracks = sqlx::query_as::<_, Self>(query)
+        let tracks = sqlx::query_as::<_, Self>(&query)
             .bind(release_id)
             .bind(artist_id)
             .bind(record_label_id)
@@ -390,68 +837,345 @@ impl Track {
             Ok(tracks) => Ok(tracks),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not find tracks for release with id {} and artist with id {} and record label with id {}.",
+                Err(TrackError::ListByRelease {
                     release_id,
                     artist_id,
-                    record_label_id
-                ))
+                    record_label_id,
+                })
             }
         }
     }
 
-    /// Update an track
+    /// Search the public tracks on a record label by name
+    /// Exact matches come first, then prefix matches, then substring matches.
+    /// Tracks on hidden releases are excluded.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `query` - The search query
+    /// * `pagination` - The page of results to return
     ///
     /// # Returns
-    /// The updated track
+    /// The matching tracks
     ///
     /// # Errors
-    /// If the track cannot be updated, return an error
-    ///
-    /// # Panics
-    /// If the track cannot be updated, return an error
+    /// If the tracks cannot be searched, return an error
     #[cfg(feature = "ssr")]
-    pub async fn update(mut self, pool: &PgPool) -> anyhow::Result<Self> {
-        self.slug = slugify(&self.name);
-        self.validate(pool).await?;
+    pub async fn search(
+        pool: &PgPool,
+        record_label_id: i64,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<Vec<Self>, TrackError> {
+        let patterns = search_patterns(query);
+        let query = format!(
+            "SELECT t.*
+             FROM tracks t
+             INNER JOIN releases r
+             ON r.id = t.release_id
+             WHERE r.label_id = $1
+              AND t.name ILIKE $4
+              AND {}
+              AND t.archived_at IS NULL
+              AND {}
+              AND {}
+             ORDER BY CASE
+               WHEN t.name ILIKE $2 THEN 0
+               WHEN t.name ILIKE $3 THEN 1
+               ELSE 2
+              END,
+              t.name ASC,
+              t.id ASC
+             LIMIT $5 OFFSET $6",
+            published_clause("t"),
+            published_clause("r"),
+            release_visible_clause("r")
+        );
+        let tracks = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(patterns.exact)
+            .bind(patterns.prefix)
+            .bind(patterns.contains)
+            .bind(pagination.limit())
+            .bind(pagination.offset())
+            .fetch_all(pool)
+            .await;
 
-        let track = match sqlx::query_as::<_, Self>(
-            "UPDATE tracks SET name = $1, slug = $2, description = $3, lyrics = $4, primary_artist_id = $5, release_id = $6, primary_image = $7, isrc_code = $8, bpm = $9, track_number = $10, published_at = $11, updated_at = $12, deleted_at = $13 WHERE id = $14 RETURNING *",
-        )
-        .bind(self.name)
-        .bind(self.slug)
-        .bind(self.description)
-        .bind(self.lyrics)
-        .bind(self.primary_artist_id)
-        .bind(self.release_id)
-        .bind(self.primary_image)
-        .bind(self.isrc_code)
-        .bind(self.bpm)
-        .bind(self.track_number)
-        .bind(self.published_at)
-        .bind(chrono::Utc::now())
-        .bind(self.deleted_at)
-        .bind(self.id)
-        .fetch_one(pool)
-        .await {
-            Ok(track) => track,
+        match tracks {
+            Ok(tracks) => Ok(tracks),
             Err(e) => {
                 tracing::error!("{e}");
-                return Err(anyhow::anyhow!(
-                    "Could not update track with id {}. {e}",
-                    self.id
-                ));
+                Err(TrackError::Search(record_label_id))
             }
-        };
-
-        Ok(track)
+        }
     }
 
-    /// Delete an track
-    /// This is a soft delete
+    /// Search the public tracks on a record label by tempo
+    /// Either end of the range can be left open. When any bound is given, tracks without a BPM are excluded.
+    /// Tracks on hidden releases are excluded.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    /// * `bpm_min` - The lowest BPM to include
+    /// * `bpm_max` - The highest BPM to include
+    /// * `sort_by_bpm` - Whether to order the tracks by BPM, slowest first, rather than by name
+    ///
+    /// # Returns
+    /// The matching tracks
+    ///
+    /// # Errors
+    /// If the tracks cannot be searched, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn search_by_bpm(
+        pool: &PgPool,
+        record_label_id: i64,
+        bpm_min: Option<i32>,
+        bpm_max: Option<i32>,
+        sort_by_bpm: bool,
+    ) -> Result<Vec<Self>, TrackError> {
+        let order = if sort_by_bpm {
+            "t.bpm ASC NULLS LAST, t.name ASC"
+        } else {
+            "t.name ASC"
+        };
+        let query = format!(
+            "SELECT t.*
+             FROM tracks t
+             INNER JOIN releases r
+             ON r.id = t.release_id
+             WHERE r.label_id = $1
+              AND ($2::INT IS NULL OR t.bpm >= $2)
+              AND ($3::INT IS NULL OR t.bpm <= $3)
+              AND {}
+              AND t.archived_at IS NULL
+              AND {}
+              AND {}
+             ORDER BY {order}",
+            published_clause("t"),
+            published_clause("r"),
+            release_visible_clause("r")
+        );
+        let tracks = sqlx::query_as::<_, Self>(&query)
+            .bind(record_label_id)
+            .bind(bpm_min)
+            .bind(bpm_max)
+            .fetch_all(pool)
+            .await;
+
+        match tracks {
+            Ok(tracks) => Ok(tracks),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Search(record_label_id))
+            }
+        }
+    }
+
+    /// Stamp the first published date on tracks whose scheduled publish date has passed
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The number of tracks stamped
+    ///
+    /// # Errors
+    /// If the tracks cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn stamp_first_published(pool: &PgPool) -> Result<u64, TrackError> {
+        let query = format!(
+            "UPDATE tracks SET first_published_at = published_at WHERE first_published_at IS NULL AND {}",
+            published_at_clause("")
+        );
+        let result = sqlx::query(&query).execute(pool).await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Update an track
+    /// Once the track has been published its slug is kept, unless `lock_slug` is explicitly false.
+    /// Renaming an explicitly unlocked track redirects the old slug to the new one.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The updated track
+    ///
+    /// # Errors
+    /// If the track cannot be updated, return an error
+    ///
+    /// # Panics
+    /// If the track cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn update(mut self, pool: &PgPool) -> Result<Self, TrackError> {
+        let old_slug = self.refresh_from_stored(pool).await?;
+        self.validate(pool)
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
+
+        let mut conn = pool.acquire().await?;
+        let track = self.save(&mut conn).await?;
+        track.redirect_slug(pool, &old_slug).await?;
+
+        Ok(track)
+    }
+
+    /// Update a track and replace its artists in a single transaction
+    /// If either step fails, neither the track nor its artists are changed.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `artist_ids` - The IDs of the artists
+    ///
+    /// # Returns
+    /// The updated track
+    ///
+    /// # Errors
+    /// If the track is invalid, return an error
+    /// If the artist IDs are empty, return an error
+    /// If the track or its artists cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn update_with_artists(
+        mut self,
+        pool: &PgPool,
+        artist_ids: Vec<i64>,
+    ) -> Result<Self, TrackError> {
+        let old_slug = self.refresh_from_stored(pool).await?;
+        self.validate(pool)
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
+        if artist_ids.is_empty() {
+            return Err(TrackError::Invalid(
+                "Artist IDs cannot be empty.".to_string(),
+            ));
+        }
+
+        let mut tx = pool.begin().await?;
+        let track = self.save(&mut tx).await?;
+        track.replace_artists(&mut tx, artist_ids).await?;
+        tx.commit().await?;
+        track.redirect_slug(pool, &old_slug).await?;
+
+        Ok(track)
+    }
+
+    /// Refresh the fields derived from the stored track before saving
+    /// Normalises the name, regenerates the slug unless it is locked, and stamps the first published date.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The slug stored before the update
+    ///
+    /// # Errors
+    /// If the regenerated slug is taken and cannot be resolved, return an error
+    #[cfg(feature = "ssr")]
+    async fn refresh_from_stored(&mut self, pool: &PgPool) -> Result<String, TrackError> {
+        self.name = normalise_name(&self.name);
+        let stored = Self::get_by_id(pool, self.id).await.ok();
+        let old_slug = stored
+            .as_ref()
+            .map_or_else(|| self.slug.clone(), |track| track.slug.clone());
+        // The first published date is never overwritten once it is set
+        self.first_published_at = stored
+            .as_ref()
+            .map_or(self.first_published_at, |track| track.first_published_at)
+            .or_else(|| {
+                self.published_at
+                    .filter(|published_at| *published_at <= chrono::Utc::now())
+            });
+        let stored_published_at = stored.and_then(|track| track.published_at);
+        if !is_slug_locked(self.lock_slug, &[stored_published_at, self.published_at]) {
+            self.slug = unique_slug_with(
+                pool,
+                SlugTable::Tracks,
+                slugify(&self.name),
+                self.id,
+                slug_collision_strategy(),
+            )
+            .await
+            .map_err(|e| TrackError::Invalid(e.to_string()))?;
+        }
+
+        Ok(old_slug)
+    }
+
+    /// Redirect the old slug to the new one, if the slug was explicitly unlocked and changed
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `old_slug` - The slug stored before the update
+    ///
+    /// # Errors
+    /// If the redirect cannot be created, return an error
+    #[cfg(feature = "ssr")]
+    async fn redirect_slug(&self, pool: &PgPool, old_slug: &str) -> Result<(), TrackError> {
+        if self.lock_slug == Some(false) && self.slug != old_slug {
+            SlugRedirect::create(pool, "track", self.id, old_slug, &self.slug)
+                .await
+                .map_err(|e| {
+                    tracing::error!("{e}");
+                    TrackError::Redirect(old_slug.to_string())
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the track to the database
+    ///
+    /// # Arguments
+    /// * `conn` - The database connection, which may be part of a transaction
+    ///
+    /// # Returns
+    /// The saved track
+    ///
+    /// # Errors
+    /// If the track cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    async fn save(self, conn: &mut PgConnection) -> Result<Self, TrackError> {
+        let track = match sqlx::query_as::<_, Self>(
+            "UPDATE tracks SET name = $1, slug = $2, lock_slug = $3, description = $4, lyrics = $5, primary_artist_id = $6, release_id = $7, primary_image = $8, isrc_code = $9, bpm = $10, preview_url = $11, preview_start_ms = $12, duration_ms = $13, explicit = $14, clean_version_of = $15, track_number = $16, published_at = $17, first_published_at = $18, updated_at = $19, deleted_at = $20 WHERE id = $21 RETURNING *",
+        )
+        .bind(self.name)
+        .bind(self.slug)
+        .bind(self.lock_slug)
+        .bind(self.description)
+        .bind(self.lyrics)
+        .bind(self.primary_artist_id)
+        .bind(self.release_id)
+        .bind(self.primary_image)
+        .bind(self.isrc_code)
+        .bind(self.bpm)
+        .bind(self.preview_url)
+        .bind(self.preview_start_ms)
+        .bind(self.duration_ms)
+        .bind(self.explicit)
+        .bind(self.clean_version_of)
+        .bind(self.track_number)
+        .bind(self.published_at)
+        .bind(self.first_published_at)
+        .bind(chrono::Utc::now())
+        .bind(self.deleted_at)
+        .bind(self.id)
+        .fetch_one(conn)
+        .await {
+            Ok(track) => track,
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(TrackError::Update(self.id));
+            }
+        };
+
+        Ok(track)
+    }
+
+    /// Delete an track
+    /// This is a soft delete
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
@@ -462,100 +1186,457 @@ impl Track {
     /// # Errors
     /// If the track cannot be deleted, return an error
     #[cfg(feature = "ssr")]
-    pub async fn delete(&self, pool: &PgPool) -> anyhow::Result<Self> {
-        let track = sqlx::query_as::<_, Self>(
-            "UPDATE tracks SET deleted_at = $1 WHERE id = $2 RETURNING *",
+    pub async fn delete(&self, pool: &PgPool) -> Result<Self, TrackError> {
+        let track = sqlx::query_as::<_, Self>(
+            "UPDATE tracks SET deleted_at = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(chrono::Utc::now())
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match track {
+            Ok(track) => Ok(track),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Delete(self.id))
+            }
+        }
+    }
+
+    /// Archive or unarchive the track
+    /// Archived tracks are kept out of listings, but are not deleted.
+    /// Archiving an already archived track keeps the original archive date.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `archived` - Whether the track should be archived
+    ///
+    /// # Returns
+    /// The updated track
+    ///
+    /// # Errors
+    /// If the track cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_archived(&self, pool: &PgPool, archived: bool) -> Result<Self, TrackError> {
+        let track = sqlx::query_as::<_, Self>(
+            "UPDATE tracks
+             SET archived_at = CASE WHEN $1 THEN COALESCE(archived_at, NOW()) ELSE NULL END
+             WHERE id = $2
+             RETURNING *",
+        )
+        .bind(archived)
+        .bind(self.id)
+        .fetch_one(pool)
+        .await;
+
+        match track {
+            Ok(track) => Ok(track),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Archive(self.id))
+            }
+        }
+    }
+
+    /// List the archived tracks on a record label
+    /// Deleted tracks are in the trash, so are excluded.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `record_label_id` - The ID of the record label
+    ///
+    /// # Returns
+    /// The archived tracks, most recently archived first
+    ///
+    /// # Errors
+    /// If the tracks cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_archived(
+        pool: &PgPool,
+        record_label_id: i64,
+    ) -> Result<Vec<Self>, TrackError> {
+        let tracks = sqlx::query_as::<_, Self>(
+            "SELECT t.*
+             FROM tracks t
+             INNER JOIN releases r
+             ON r.id = t.release_id
+             WHERE r.label_id = $1
+              AND t.archived_at IS NOT NULL
+              AND t.deleted_at IS NULL
+             ORDER BY t.archived_at DESC, t.name ASC",
+        )
+        .bind(record_label_id)
+        .fetch_all(pool)
+        .await;
+
+        match tracks {
+            Ok(tracks) => Ok(tracks),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Archived(record_label_id))
+            }
+        }
+    }
+
+    /// List a page of every track on the artist's releases, for the admin export
+    /// Drafts, scheduled and archived tracks are always included.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `artist_id` - The ID of the artist
+    /// * `pagination` - The page to list
+    /// * `include_deleted` - Whether to include deleted tracks, and tracks on deleted releases
+    ///
+    /// # Returns
+    /// The tracks, ordered by release then track number
+    ///
+    /// # Errors
+    /// If the tracks cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn list_all_for_artist(
+        pool: &PgPool,
+        artist_id: i64,
+        pagination: Pagination,
+        include_deleted: bool,
+    ) -> Result<Vec<Self>, TrackError> {
+        let tracks = sqlx::query_as::<_, Self>(
+            "SELECT t.*
+             FROM tracks t
+             INNER JOIN releases r
+             ON r.id = t.release_id
+             WHERE EXISTS (SELECT 1 FROM release_artists ra WHERE ra.release_id = r.id AND ra.artist_id = $1)
+              AND ($2 OR (t.deleted_at IS NULL AND r.deleted_at IS NULL))
+             ORDER BY r.release_date ASC NULLS LAST, r.id ASC, t.track_number ASC, t.id ASC
+             LIMIT $3 OFFSET $4",
+        )
+        .bind(artist_id)
+        .bind(include_deleted)
+        .bind(pagination.limit())
+        .bind(pagination.offset())
+        .fetch_all(pool)
+        .await;
+
+        match tracks {
+            Ok(tracks) => Ok(tracks),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::ListByArtist(artist_id))
+            }
+        }
+    }
+
+    /// Count every track on the artist's releases, to page through `list_all_for_artist`
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `artist_id` - The ID of the artist
+    /// * `include_deleted` - Whether to include deleted tracks, and tracks on deleted releases
+    ///
+    /// # Returns
+    /// The number of tracks
+    ///
+    /// # Errors
+    /// If the tracks cannot be counted, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn count_all_for_artist(
+        pool: &PgPool,
+        artist_id: i64,
+        include_deleted: bool,
+    ) -> Result<i64, TrackError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*)
+             FROM tracks t
+             INNER JOIN releases r
+             ON r.id = t.release_id
+             WHERE EXISTS (SELECT 1 FROM release_artists ra WHERE ra.release_id = r.id AND ra.artist_id = $1)
+              AND ($2 OR (t.deleted_at IS NULL AND r.deleted_at IS NULL))",
+        )
+        .bind(artist_id)
+        .bind(include_deleted)
+        .fetch_one(pool)
+        .await;
+
+        match count {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::ListByArtist(artist_id))
+            }
+        }
+    }
+
+    /// Set the artists for the track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `artist_ids` - The IDs of the artists
+    /// # Returns
+    /// The track
+    /// # Errors
+    /// If the track cannot be updated, return an error
+    /// # Panics
+    /// If the track cannot be updated, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn set_artists(
+        &self,
+        pool: &PgPool,
+        artist_ids: Vec<i64>,
+    ) -> Result<Self, TrackError> {
+        if artist_ids.is_empty() {
+            return Err(TrackError::Invalid(
+                "Artist IDs cannot be empty.".to_string(),
+            ));
+        }
+
+        let mut tx = pool.begin().await?;
+        self.replace_artists(&mut tx, artist_ids).await?;
+        tx.commit().await?;
+
+        Ok(self.clone())
+    }
+
+    /// Move tracks to another release, keeping their IDs, ISRC codes and artists
+    /// Either all of the tracks are moved, or none are.
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    /// * `track_ids` - The IDs of the tracks to move
+    /// * `release_id` - The ID of the release to move the tracks to
+    ///
+    /// # Returns
+    /// The moved tracks, ordered by track number
+    ///
+    /// # Errors
+    /// If a track cannot be found, return an error
+    /// If a track number is already used on the release, return an error
+    /// If the tracks cannot be moved, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn move_to_release(
+        pool: &PgPool,
+        track_ids: &[i64],
+        release_id: i64,
+    ) -> Result<Vec<Self>, TrackError> {
+        let mut tx = pool.begin().await?;
+
+        let tracks = sqlx::query_as::<_, Self>(
+            "SELECT * FROM tracks WHERE id = ANY($1) ORDER BY track_number ASC FOR UPDATE",
+        )
+        .bind(track_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        if let Some(missing_id) = track_ids
+            .iter()
+            .find(|id| !tracks.iter().any(|track| track.id == **id))
+        {
+            return Err(TrackError::NotFoundById(*missing_id));
+        }
+
+        // The track numbers must be unique on the release, including deleted tracks
+        let mut track_numbers = sqlx::query_scalar::<_, i32>(
+            "SELECT track_number FROM tracks
+             WHERE release_id = $1 AND id <> ALL($2) AND track_number IS NOT NULL
+             FOR UPDATE",
+        )
+        .bind(release_id)
+        .bind(track_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        for track in &tracks {
+            if track_numbers.contains(&track.track_number) {
+                return Err(TrackError::TrackNumberConflict {
+                    track_number: track.track_number,
+                    release_id,
+                });
+            }
+            track_numbers.push(track.track_number);
+        }
+
+        let moved_tracks = match sqlx::query_as::<_, Self>(
+            "UPDATE tracks SET release_id = $1, updated_at = $2 WHERE id = ANY($3) RETURNING *",
+        )
+        .bind(release_id)
+        .bind(chrono::Utc::now())
+        .bind(track_ids)
+        .fetch_all(&mut *tx)
+        .await
+        {
+            Ok(mut tracks) => {
+                tracks.sort_by_key(|track| track.track_number);
+                tracks
+            }
+            Err(e) => {
+                tracing::error!("{e}");
+                return Err(TrackError::Move(release_id));
+            }
+        };
+        tx.commit().await?;
+
+        Ok(moved_tracks)
+    }
+
+    /// Replace the artists for the track
+    ///
+    /// # Arguments
+    /// * `conn` - The database connection, which may be part of a transaction
+    /// * `artist_ids` - The IDs of the artists
+    ///
+    /// # Errors
+    /// If the artists cannot be replaced, return an error
+    #[cfg(feature = "ssr")]
+    async fn replace_artists(
+        &self,
+        conn: &mut PgConnection,
+        artist_ids: Vec<i64>,
+    ) -> Result<(), TrackError> {
+        // Delete all artists for the track
+        sqlx::query("DELETE FROM track_artists WHERE track_id = $1")
+            .bind(self.id)
+            .execute(&mut *conn)
+            .await?;
+
+        // Insert the new artists
+        for artist_id in artist_ids {
+            match sqlx::query("INSERT INTO track_artists (track_id, artist_id) VALUES ($1, $2)")
+                .bind(self.id)
+                .bind(artist_id)
+                .execute(&mut *conn)
+                .await
+            {
+                Ok(_) => (),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return Err(TrackError::SetArtists(self.id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the artists for the track
+    ///
+    /// # Arguments
+    /// * `pool` - The database connection pool
+    ///
+    /// # Returns
+    /// The artists for the track
+    ///
+    /// # Errors
+    /// If the track cannot be found, return an error
+    /// # Panics
+    /// If the track cannot be found, return an error
+    #[cfg(feature = "ssr")]
+    pub async fn get_artists(&self, pool: &PgPool) -> Result<Vec<Artist>, TrackError> {
+        let artists = sqlx::query_as::<_, Artist>(
+            "SELECT artists.* FROM artists
+             INNER JOIN track_artists ON artists.id = track_artists.artist_id
+             WHERE track_artists.track_id = $1",
         )
-        .bind(chrono::Utc::now())
         .bind(self.id)
-        .fetch_one(pool)
+        .fetch_all(pool)
         .await;
 
-        match track {
-            Ok(track) => Ok(track),
+        match artists {
+            Ok(artists) => Ok(artists),
             Err(e) => {
                 tracing::error!("{e}");
-                Err(anyhow::anyhow!(
-                    "Could not delete track with id {}.",
-                    self.id
-                ))
+                Err(TrackError::Artists(self.id))
             }
         }
     }
 
-    /// Set the artists for the track
+    /// Set the works the track covers, samples or remixes
+    /// Any existing relations are replaced.
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
-    /// * `artist_ids` - The IDs of the artists
+    /// * `relations` - The relations, in the order they should be listed
+    ///
     /// # Returns
-    /// The track
+    /// The relations for the track
+    ///
     /// # Errors
-    /// If the track cannot be updated, return an error
-    /// # Panics
-    /// If the track cannot be updated, return an error
+    /// If any of the relations are invalid, return an error
+    /// If the relations cannot be set, return an error
     #[cfg(feature = "ssr")]
-    pub async fn set_artists(&self, pool: &PgPool, artist_ids: Vec<i64>) -> anyhow::Result<Self> {
-        if artist_ids.is_empty() {
-            return Err(anyhow::anyhow!("Artist IDs cannot be empty."));
+    pub async fn set_relations(
+        &self,
+        pool: &PgPool,
+        relations: Vec<TrackRelation>,
+    ) -> Result<Vec<TrackRelation>, TrackError> {
+        for relation in &relations {
+            relation
+                .validate()
+                .map_err(|e| TrackError::Invalid(e.to_string()))?;
         }
 
         let mut tx = pool.begin().await?;
 
-        // Delete all artists for the track
-        sqlx::query("DELETE FROM track_artists WHERE track_id = $1")
+        // Delete all relations for the track
+        sqlx::query("DELETE FROM track_relations WHERE track_id = $1")
             .bind(self.id)
             .execute(&mut *tx)
             .await?;
 
-        // Insert the new artists
-        for artist_id in artist_ids {
-            match sqlx::query("INSERT INTO track_artists (track_id, artist_id) VALUES ($1, $2)")
-                .bind(self.id)
-                .bind(artist_id)
-                .execute(&mut *tx)
-                .await
+        // Insert the new relations
+        for relation in relations {
+            match sqlx::query(
+                "INSERT INTO track_relations (track_id, relation_type, related_title, related_artist, related_isrc) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(self.id)
+            .bind(&relation.relation_type)
+            .bind(relation.related_title.trim())
+            .bind(
+                relation
+                    .related_artist
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|artist| !artist.is_empty()),
+            )
+            .bind(relation.normalised_isrc())
+            .execute(&mut *tx)
+            .await
             {
                 Ok(_) => (),
                 Err(e) => {
                     tracing::error!("{e}");
-                    return Err(anyhow::anyhow!(
-                        "Could not set artists for track with id {}.",
-                        self.id
-                    ));
+                    return Err(TrackError::SetRelations(self.id));
                 }
             }
         }
 
-        let _ = tx.commit().await;
+        tx.commit().await?;
 
-        Ok(self.clone())
+        self.get_relations(pool).await
     }
 
-    /// Get the artists for the track
+    /// Get the works the track covers, samples or remixes
     ///
     /// # Arguments
     /// * `pool` - The database connection pool
     ///
     /// # Returns
-    /// The artists for the track
+    /// The relations for the track, in order
     ///
     /// # Errors
-    /// If the track cannot be found, return an error
-    /// # Panics
-    /// If the track cannot be found, return an error
+    /// If the relations cannot be found, return an error
     #[cfg(feature = "ssr")]
-    pub async fn get_artists(&self, pool: &PgPool) -> anyhow::Result<Vec<Artist>> {
-        let artists = sqlx::query_as::<_, Artist>(
-            "SELECT artists.* FROM artists
-             INNER JOIN track_artists ON artists.id = track_artists.artist_id
-             WHERE track_artists.track_id = $1",
+    pub async fn get_relations(&self, pool: &PgPool) -> Result<Vec<TrackRelation>, TrackError> {
+        let relations = sqlx::query_as::<_, TrackRelation>(
+            "SELECT * FROM track_relations WHERE track_id = $1 ORDER BY id ASC",
         )
         .bind(self.id)
         .fetch_all(pool)
-        .await?;
+        .await;
 
-        Ok(artists)
+        match relations {
+            Ok(relations) => Ok(relations),
+            Err(e) => {
+                tracing::error!("{e}");
+                Err(TrackError::Relations(self.id))
+            }
+        }
     }
 }
 
@@ -567,8 +1648,112 @@ mod tests {
     #[cfg(feature = "ssr")]
     use crate::models::test_helpers::{
         create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        fail_commits_on,
     };
 
+    #[test]
+    fn test_track_error_display() {
+        let cases = [
+            (
+                TrackError::Invalid("Name is required.".to_string()),
+                "Name is required.",
+            ),
+            (
+                TrackError::NotFoundById(1),
+                "Could not find track with id 1.",
+            ),
+            (
+                TrackError::NotFoundBySlug("missing".to_string()),
+                "Could not find track with slug missing.",
+            ),
+            (
+                TrackError::NotFoundForRelease {
+                    slug: "missing".to_string(),
+                    release_id: 2,
+                    artist_id: 3,
+                    record_label_id: 4,
+                },
+                "Could not find track missing for release with id 2 and artist with id 3 and record label with id 4.",
+            ),
+            (
+                TrackError::ListByRelease {
+                    release_id: 2,
+                    artist_id: 3,
+                    record_label_id: 4,
+                },
+                "Could not find tracks for release with id 2 and artist with id 3 and record label with id 4.",
+            ),
+            (
+                TrackError::ListByArtist(2),
+                "Could not find tracks for artist with id 2.",
+            ),
+            (
+                TrackError::Search(4),
+                "Could not search tracks for record label with id 4.",
+            ),
+            (
+                TrackError::CleanCounterpart(1),
+                "Could not find clean version of track with id 1.",
+            ),
+            (TrackError::Create, "Could not create track."),
+            (TrackError::Update(1), "Could not update track with id 1."),
+            (TrackError::Delete(1), "Could not delete track with id 1."),
+            (
+                TrackError::Archive(1),
+                "Could not change the archived state of track with id 1.",
+            ),
+            (
+                TrackError::Archived(3),
+                "Could not find archived tracks for record label with id 3.",
+            ),
+            (
+                TrackError::Redirect("old-slug".to_string()),
+                "Could not redirect track slug old-slug.",
+            ),
+            (
+                TrackError::SetArtists(1),
+                "Could not set artists for track with id 1.",
+            ),
+            (
+                TrackError::Artists(1),
+                "Could not find artists for track with id 1.",
+            ),
+            (
+                TrackError::SetRelations(1),
+                "Could not set relations for track with id 1.",
+            ),
+            (
+                TrackError::Relations(1),
+                "Could not find relations for track with id 1.",
+            ),
+            (
+                TrackError::TrackNumberConflict {
+                    track_number: 1,
+                    release_id: 2,
+                },
+                "Track number 1 must be unique for release with id 2.",
+            ),
+            (
+                TrackError::Move(2),
+                "Could not move tracks to release with id 2.",
+            ),
+            (TrackError::Database, "Could not access tracks."),
+        ];
+
+        for (error, message) in cases {
+            assert_eq!(error.to_string(), message);
+            assert_ne!(error.to_string(), format!("{error:?}"));
+        }
+    }
+
+    #[test]
+    fn test_track_error_hides_database_error() {
+        let error = TrackError::from(sqlx::Error::RowNotFound);
+
+        assert_eq!(error, TrackError::Database);
+        assert!(!error.to_string().contains("RowNotFound"));
+    }
+
     #[sqlx::test]
     async fn test_validate_success(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
@@ -582,6 +1767,7 @@ mod tests {
             id: 1,
             name: "Test Track".to_string(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -589,10 +1775,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKUXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -611,6 +1804,7 @@ mod tests {
             id: 1,
             name: String::new(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -618,10 +1812,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -645,6 +1846,7 @@ mod tests {
             id: 1,
             name,
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -652,10 +1854,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -679,6 +1888,7 @@ mod tests {
             id: 1,
             name: "Test Track".to_string(),
             slug,
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -686,10 +1896,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -725,6 +1942,7 @@ mod tests {
             id: 1,
             name: "Test Track".to_string(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: 10,
@@ -732,10 +1950,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -755,6 +1980,7 @@ mod tests {
             id: 1,
             name: "Test Track".to_string(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -762,10 +1988,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -789,6 +2022,7 @@ mod tests {
             id: 1,
             name: "Test Track".to_string(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "This is a test track".to_string(),
             lyrics: "These are the lyrics".to_string(),
             primary_artist_id: artist.id,
@@ -796,10 +2030,17 @@ mod tests {
             primary_image: None,
             isrc_code: Some(isrc_code),
             bpm: Some(123),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            explicit: false,
+            clean_version_of: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
+            first_published_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            archived_at: None,
             deleted_at: None,
         };
 
@@ -821,99 +2062,483 @@ mod tests {
         new_track.track_number = 2;
         new_track.isrc_code = track.isrc_code;
 
-        let result = new_track.validate(&pool).await;
+        let result = new_track.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ISRC code must be unique.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_preview_url(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.preview_url = Some("not a url".to_string());
+
+        let result = track.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Preview URL must be a valid URL.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_preview_start_ms_negative(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.preview_url = Some("https://example.com/preview.mp3".to_string());
+        track.preview_start_ms = Some(-1);
+
+        let result = track.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Preview start must be 0 or greater.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_preview(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.preview_url = Some("https://example.com/preview.mp3".to_string());
+        track.preview_start_ms = Some(30_000);
+
+        let track = track.update(&pool).await.unwrap();
+
+        assert_eq!(
+            track.preview_url,
+            Some("https://example.com/preview.mp3".to_string())
+        );
+        assert_eq!(track.preview_start_ms, Some(30_000));
+    }
+
+    #[sqlx::test]
+    async fn test_clean_version_of(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let mut explicit_track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        explicit_track.explicit = true;
+        let explicit_track = explicit_track.update(&pool).await.unwrap();
+        let mut clean_track = create_test_track(&pool, 2, Some(release), None)
+            .await
+            .unwrap();
+        clean_track.clean_version_of = Some(explicit_track.id);
+
+        let clean_track = clean_track.update(&pool).await.unwrap();
+
+        assert_eq!(clean_track.clean_version_of, Some(explicit_track.id));
+        assert_eq!(
+            explicit_track.clean_counterpart(&pool).await.unwrap(),
+            Some(clean_track.clone())
+        );
+        assert_eq!(
+            clean_track.explicit_counterpart(&pool).await.unwrap(),
+            Some(explicit_track.clone())
+        );
+        assert_eq!(clean_track.clean_counterpart(&pool).await.unwrap(), None);
+        assert_eq!(
+            explicit_track.explicit_counterpart(&pool).await.unwrap(),
+            None
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_clean_version_of_other_release(pool: PgPool) {
+        let mut explicit_track = create_test_track(&pool, 1, None, None).await.unwrap();
+        explicit_track.explicit = true;
+        let explicit_track = explicit_track.update(&pool).await.unwrap();
+        let mut clean_track = create_test_track(&pool, 2, None, None).await.unwrap();
+        clean_track.clean_version_of = Some(explicit_track.id);
+
+        let result = clean_track.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The explicit version must be on the same release.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_clean_version_of_not_explicit(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let mut clean_track = create_test_track(&pool, 2, Some(release), None)
+            .await
+            .unwrap();
+        clean_track.clean_version_of = Some(track.id);
+
+        let result = clean_track.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The explicit version must be marked as explicit.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_clean_version_of_itself(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.clean_version_of = Some(track.id);
+
+        let result = track.update(&pool).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "A track cannot be the clean version of itself.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_validate_track_number_unique_for_release(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let mut new_track = track.clone();
+        new_track.id = 2;
+        new_track.isrc_code = Some("UKUXX2020456".to_string());
+        new_track.slug = "new-track-2".to_string();
+        new_track.track_number = track.track_number;
+
+        let result = new_track.validate(&pool).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "Track number {} must be unique for release with id {}.",
+                track.track_number, track.release_id
+            )
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = Track::create(
+            &pool,
+            "Test Track".to_string(),
+            "This is a test track".to_string(),
+            "These are some lyrics".to_string(),
+            artist.id,
+            release.id,
+            Some("UKXXX2020123".to_string()),
+            Some(120),
+            None,
+            None,
+            Some(225_000),
+            1,
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(track.name, "Test Track".to_string());
+        assert_eq!(track.description, "This is a test track".to_string());
+        assert_eq!(track.duration_ms, Some(225_000));
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_slug(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let mut slugs = vec![];
+        for (track_number, slug) in (1..).zip(["original-slug", "original-slug", "original-slug"]) {
+            let track = Track::create_with_slug(
+                &pool,
+                slug,
+                "Renamed Track".to_string(),
+                String::new(),
+                String::new(),
+                artist.id,
+                release.id,
+                None,
+                None,
+                None,
+                None,
+                None,
+                track_number,
+                None,
+            )
+            .await
+            .unwrap();
+            slugs.push(track.slug);
+        }
+
+        // The slug is kept while it's free, then numbered
+        assert_eq!(
+            slugs,
+            vec!["original-slug", "original-slug-2", "original-slug-3"]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_slug_sanitises_slug(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let track = Track::create_with_slug(
+            &pool,
+            "../Caf\u{e9} <script>",
+            "Imported Track".to_string(),
+            String::new(),
+            String::new(),
+            artist.id,
+            release.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            1,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(track.slug, "cafe-script");
+
+        // Nothing usable is left, so the name is used
+        let track = Track::create_with_slug(
+            &pool,
+            "/../",
+            "Imported Track".to_string(),
+            String::new(),
+            String::new(),
+            artist.id,
+            release.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            2,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(track.slug, "imported-track");
+    }
+
+    #[sqlx::test]
+    async fn test_create_normalises_name(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = Track::create(
+            &pool,
+            "Caf\u{e9}".to_string(),
+            String::new(),
+            String::new(),
+            artist.id,
+            release.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            1,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let result = Track::create(
+            &pool,
+            "Cafe\u{301}".to_string(),
+            String::new(),
+            String::new(),
+            artist.id,
+            release.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            2,
+            None,
+        )
+        .await;
+
+        // Both names normalise to the same slug, so the second is numbered
+        assert_eq!(track.name, "Caf\u{e9}".to_string());
+        assert_eq!(result.unwrap().slug, format!("{}-2", track.slug));
+    }
+
+    #[sqlx::test]
+    async fn test_update_resolves_slug_collision(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let mut other_track = create_test_track(&pool, 2, None, None).await.unwrap();
+        other_track.lock_slug = Some(false);
+        other_track.name = track.name.clone();
+
+        let other_track = other_track.update(&pool).await.unwrap();
+
+        assert_eq!(other_track.slug, format!("{}-2", track.slug));
+    }
+
+    #[sqlx::test]
+    async fn test_update_normalises_name(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.lock_slug = Some(false);
+        track.name = "Cafe\u{301}".to_string();
 
-        assert!(result.is_err());
+        let track = track.update(&pool).await.unwrap();
+
+        assert_eq!(track.name, "Caf\u{e9}".to_string());
+        assert_eq!(track.slug, slugify("Caf\u{e9}"));
+    }
+
+    #[sqlx::test]
+    async fn test_create_with_validation_error(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = Track::create(
+            &pool,
+            String::new(),
+            "This is a test track".to_string(),
+            "These are some lyrics".to_string(),
+            artist.id,
+            release.id,
+            Some("UKXXX2020123".to_string()),
+            Some(120),
+            None,
+            None,
+            None,
+            1,
+            Some(chrono::Utc::now()),
+        )
+        .await;
+
+        assert!(track.is_err());
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "ISRC code must be unique.".to_string()
+            track.unwrap_err().to_string(),
+            "Name is required.".to_string()
         );
     }
 
     #[sqlx::test]
-    async fn test_validate_track_number_unique_for_release(pool: PgPool) {
-        let track = create_test_track(&pool, 1, None, None).await.unwrap();
-        let mut new_track = track.clone();
-        new_track.id = 2;
-        new_track.isrc_code = Some("UKUXX2020456".to_string());
-        new_track.slug = "new-track-2".to_string();
-        new_track.track_number = track.track_number;
+    async fn test_create_on_deleted_release(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let release = release.delete(&pool).await.unwrap();
 
-        let result = new_track.validate(&pool).await;
+        let track = Track::create(
+            &pool,
+            "Test Track".to_string(),
+            String::new(),
+            String::new(),
+            artist.id,
+            release.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            1,
+            Some(chrono::Utc::now()),
+        )
+        .await;
 
-        assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err().to_string(),
-            format!(
-                "Track number {} must be unique for release with id {}.",
-                track.track_number, track.release_id
-            )
+            track.unwrap_err(),
+            TrackError::Invalid("Cannot add a track to a deleted release.".to_string())
         );
     }
 
     #[sqlx::test]
-    async fn test_create(pool: PgPool) {
+    async fn test_create_on_live_release(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap()
+            .delete(&pool)
+            .await
+            .unwrap();
+
         let track = Track::create(
             &pool,
             "Test Track".to_string(),
-            "This is a test track".to_string(),
-            "These are some lyrics".to_string(),
+            String::new(),
+            String::new(),
             artist.id,
             release.id,
-            Some("UKXXX2020123".to_string()),
-            Some(120),
+            None,
+            None,
+            None,
+            None,
+            None,
             1,
             Some(chrono::Utc::now()),
         )
         .await
         .unwrap();
 
-        assert_eq!(track.name, "Test Track".to_string());
-        assert_eq!(track.description, "This is a test track".to_string());
+        assert_eq!(track.release_id, release.id);
     }
 
     #[sqlx::test]
-    async fn test_create_with_validation_error(pool: PgPool) {
+    async fn test_create_for_deleted_artist(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        let artist = artist.delete(&pool).await.unwrap();
+
         let track = Track::create(
             &pool,
+            "Test Track".to_string(),
+            String::new(),
             String::new(),
-            "This is a test track".to_string(),
-            "These are some lyrics".to_string(),
             artist.id,
             release.id,
-            Some("UKXXX2020123".to_string()),
-            Some(120),
+            None,
+            None,
+            None,
+            None,
+            None,
             1,
             Some(chrono::Utc::now()),
         )
         .await;
 
-        assert!(track.is_err());
         assert_eq!(
-            track.unwrap_err().to_string(),
-            "Name is required.".to_string()
+            track.unwrap_err(),
+            TrackError::Invalid("Cannot add a track to a deleted artist.".to_string())
         );
     }
 
     #[sqlx::test]
     async fn test_get_by_slug(pool: PgPool) {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
-        let track_by_slug = Track::get_by_slug(&pool, track.slug.clone()).await.unwrap();
+        let track_by_slug = Track::get_by_slug(&pool, track.slug.clone(), false)
+            .await
+            .unwrap();
 
         assert_eq!(track, track_by_slug);
     }
 
     #[sqlx::test]
     async fn test_get_by_slug_not_found(pool: PgPool) {
-        let track = Track::get_by_slug(&pool, "missing".to_string()).await;
+        let track = Track::get_by_slug(&pool, "missing".to_string(), false).await;
 
         assert!(track.is_err());
         assert_eq!(
@@ -1090,6 +2715,131 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_search_ranks_prefix_match_above_mid_string_match(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut mid_string =
+            create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        mid_string.name = "After Midnight".to_string();
+        let mid_string = mid_string.update(&pool).await.unwrap();
+        let mut prefix = create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        prefix.name = "Midnight Drive".to_string();
+        let prefix = prefix.update(&pool).await.unwrap();
+
+        let tracks = Track::search(&pool, artist.label_id, "midnight", Pagination::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![prefix.id, mid_string.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_search_excludes_tracks_on_unlisted_releases(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        release.unlisted = true;
+        release.update(&pool).await.unwrap();
+
+        let tracks = Track::search(&pool, artist.label_id, "Test Track", Pagination::default())
+            .await
+            .unwrap();
+
+        assert!(tracks.is_empty());
+    }
+
+    /// Create three tracks at 128, 120 and no BPM, named so that name order differs from BPM order
+    async fn create_bpm_tracks(pool: &PgPool) -> (i64, Track, Track, Track) {
+        let artist = create_test_artist(pool, 1, None).await.unwrap();
+        let release = create_test_release(pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut fast = create_test_track(pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        fast.name = "A Fast Track".to_string();
+        fast.bpm = Some(128);
+        let fast = fast.update(pool).await.unwrap();
+        let mut slow = create_test_track(pool, 2, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        slow.name = "B Slow Track".to_string();
+        slow.bpm = Some(120);
+        let slow = slow.update(pool).await.unwrap();
+        let mut unknown = create_test_track(pool, 3, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        unknown.name = "C Unknown Track".to_string();
+        unknown.bpm = None;
+        let unknown = unknown.update(pool).await.unwrap();
+
+        (artist.label_id, fast, slow, unknown)
+    }
+
+    #[sqlx::test]
+    async fn test_search_by_bpm_range(pool: PgPool) {
+        let (label_id, fast, slow, _unknown) = create_bpm_tracks(&pool).await;
+
+        let tracks = Track::search_by_bpm(&pool, label_id, Some(120), Some(128), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![fast.id, slow.id]
+        );
+
+        let tracks = Track::search_by_bpm(&pool, label_id, Some(125), None, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![fast.id]
+        );
+
+        let tracks = Track::search_by_bpm(&pool, label_id, None, Some(124), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![slow.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_search_by_bpm_sorted(pool: PgPool) {
+        let (label_id, fast, slow, unknown) = create_bpm_tracks(&pool).await;
+
+        let tracks = Track::search_by_bpm(&pool, label_id, Some(100), Some(140), true)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![slow.id, fast.id]
+        );
+
+        let tracks = Track::search_by_bpm(&pool, label_id, None, None, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![slow.id, fast.id, unknown.id]
+        );
+    }
+
     #[sqlx::test]
     async fn test_list_by_release_and_artist_and_record_label_no_tracks(pool: PgPool) {
         let tracks = Track::list_by_release_and_artist_and_record_label(&pool, 1, 1, 1, true)
@@ -1244,6 +2994,45 @@ mod tests {
         assert_eq!(tracks.len(), 0);
     }
 
+    #[sqlx::test]
+    async fn test_update_first_published_at(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.published_at = None;
+        let mut track = track.update(&pool).await.unwrap();
+        assert!(track.first_published_at.is_none());
+
+        // The first publish stamps it
+        let published_at = chrono::Utc::now() - chrono::Duration::minutes(1);
+        track.published_at = Some(published_at);
+        let mut track = track.update(&pool).await.unwrap();
+        let first_published_at = track.first_published_at;
+        assert!(first_published_at.is_some());
+        assert_eq!(first_published_at, track.published_at);
+
+        // Rescheduling doesn't change it
+        track.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        track.first_published_at = None;
+        let mut track = track.update(&pool).await.unwrap();
+        assert_eq!(track.first_published_at, first_published_at);
+
+        // Unpublishing leaves it intact
+        track.published_at = None;
+        let track = track.update(&pool).await.unwrap();
+        assert_eq!(track.first_published_at, first_published_at);
+    }
+
+    #[sqlx::test]
+    async fn test_update_first_published_at_scheduled(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.published_at = None;
+        let mut track = track.update(&pool).await.unwrap();
+
+        track.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let track = track.update(&pool).await.unwrap();
+
+        assert!(track.first_published_at.is_none());
+    }
+
     #[sqlx::test]
     async fn test_update(pool: PgPool) {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
@@ -1255,7 +3044,7 @@ mod tests {
 
         let updated_track = update_track.update(&pool).await.unwrap();
         assert_eq!(updated_track.name, "Updated Track".to_string());
-        assert_eq!(updated_track.slug, "updated-track".to_string());
+        assert_eq!(updated_track.slug, track.slug);
         assert_eq!(
             updated_track.description,
             "This is an updated track".to_string()
@@ -1333,6 +3122,27 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_set_artists_commit_error(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let artist = track.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
+        fail_commits_on(&pool, "track_artists").await.unwrap();
+
+        let result = track.set_artists(&pool, vec![artist2.id]).await;
+
+        assert_eq!(result.unwrap_err(), TrackError::Database);
+        // Nothing was changed
+        let artists = track.get_artists(&pool).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].id, artist.id);
+    }
+
     #[sqlx::test]
     async fn test_set_artists_no_artists(pool: PgPool) {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
@@ -1374,6 +3184,50 @@ mod tests {
         assert_eq!(artists[0].id, artist3.id);
     }
 
+    #[sqlx::test]
+    async fn test_update_with_artists(pool: PgPool) {
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let artist = track.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let artist2 = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
+
+        track.name = "Updated Track".to_string();
+        track.lock_slug = Some(false);
+        let track = track
+            .update_with_artists(&pool, vec![artist2.id])
+            .await
+            .unwrap();
+
+        assert_eq!(track.name, "Updated Track");
+        assert_eq!(track.slug, "updated-track");
+        let artists = track.get_artists(&pool).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].id, artist2.id);
+    }
+
+    #[sqlx::test]
+    async fn test_update_with_artists_rolls_back(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let artist = track.get_artists(&pool).await.unwrap()[0].clone();
+
+        let mut updated_track = track.clone();
+        updated_track.name = "Updated Track".to_string();
+        let result = updated_track
+            .update_with_artists(&pool, vec![artist.id, 9999])
+            .await;
+
+        assert!(result.is_err());
+        let track = Track::get_by_slug(&pool, track.slug, false).await.unwrap();
+        assert_eq!(track.name, "Test Track 1");
+        let artists = track.get_artists(&pool).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].id, artist.id);
+    }
+
     /// Test `get_artists`
     #[sqlx::test]
     async fn test_get_artists(pool: PgPool) {
@@ -1400,11 +3254,30 @@ mod tests {
         assert_eq!(artists[1].id, artist2.id);
     }
 
+    #[test]
+    fn test_duration_display() {
+        let mut track = Track::default();
+        assert_eq!(track.duration_display(), None);
+
+        track.duration_ms = Some(45_500);
+        assert_eq!(track.duration_display(), Some("0:45".to_string()));
+
+        track.duration_ms = Some(754_000);
+        assert_eq!(track.duration_display(), Some("12:34".to_string()));
+
+        track.duration_ms = Some(3_723_000);
+        assert_eq!(track.duration_display(), Some("1:02:03".to_string()));
+    }
+
     #[sqlx::test]
     async fn test_primary_image_url(pool: PgPool) {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
-        let url = track.primary_image_url();
-        assert_eq!(url, "/Logo.svg");
+        let placeholders = PlaceholderImages {
+            track: "/images/note.svg".to_string(),
+            ..Default::default()
+        };
+        let url = track.primary_image_url(&placeholders);
+        assert_eq!(url, "/images/note.svg");
     }
 
     #[sqlx::test]
@@ -1412,7 +3285,97 @@ mod tests {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
         let mut track = track;
         track.primary_image = Some("custom-image.jpg".to_string());
-        let url = track.primary_image_url();
+        let url = track.primary_image_url(&PlaceholderImages::default());
         assert_eq!(url, "/uploads/tracks/custom-image.jpg");
     }
+
+    #[sqlx::test]
+    async fn test_set_relations(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let relations = track
+            .set_relations(
+                &pool,
+                vec![
+                    TrackRelation::new("cover_of", "Original Song", Some("Original Artist"), None),
+                    TrackRelation::new("samples", "Break", None, Some("us-abc-99-00001")),
+                    TrackRelation::new("remix_of", "Test Track 1", Some(" "), None),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(relations.len(), 3);
+        assert_eq!(relations[0].relation_type, "cover_of");
+        assert_eq!(
+            relations[0].related_artist,
+            Some("Original Artist".to_string())
+        );
+        assert_eq!(relations[1].relation_type, "samples");
+        assert_eq!(relations[1].related_isrc, Some("USABC9900001".to_string()));
+        assert_eq!(relations[2].relation_type, "remix_of");
+        assert_eq!(relations[2].related_artist, None);
+        assert_eq!(relations[2].track_id, track.id);
+        assert_eq!(track.get_relations(&pool).await.unwrap(), relations);
+
+        // Setting the relations again replaces them
+        let relations = track
+            .set_relations(
+                &pool,
+                vec![TrackRelation::new("samples", "Other Break", None, None)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(track.get_relations(&pool).await.unwrap(), relations);
+    }
+
+    #[sqlx::test]
+    async fn test_set_relations_unknown_type(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track
+            .set_relations(
+                &pool,
+                vec![TrackRelation::new("cover_of", "Original Song", None, None)],
+            )
+            .await
+            .unwrap();
+
+        let result = track
+            .set_relations(
+                &pool,
+                vec![
+                    TrackRelation::new("samples", "Break", None, None),
+                    TrackRelation::new("inspired_by", "Original Song", None, None),
+                ],
+            )
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Relation type must be one of cover_of, samples, remix_of."
+        );
+        // The existing relations are left in place
+        let relations = track.get_relations(&pool).await.unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].relation_type, "cover_of");
+    }
+
+    #[sqlx::test]
+    async fn test_set_relations_invalid_isrc(pool: PgPool) {
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = track
+            .set_relations(
+                &pool,
+                vec![TrackRelation::new("samples", "Break", None, Some("12345"))],
+            )
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Related ISRC code must be a valid ISRC."
+        );
+        assert!(track.get_relations(&pool).await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,143 @@
+//! Track relation model
+//!
+//! The `TrackRelation` struct is used to record another work a track covers, samples or remixes, for rights management.
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use sqlx::FromRow;
+
+use crate::utils::isrc::{is_valid_isrc, normalise_isrc_base};
+
+/// The relationships a track can have to another work.
+pub const TRACK_RELATION_TYPES: [&str; 3] = ["cover_of", "samples", "remix_of"];
+
+/// The `TrackRelation` struct is used to represent a track relation in the database.
+#[derive(Serialize, Deserialize, Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(FromRow))]
+pub struct TrackRelation {
+    /// The unique identifier of the relation
+    pub id: i64,
+    /// The track the relation belongs to
+    pub track_id: i64,
+    /// How the track relates to the other work, one of `TRACK_RELATION_TYPES`
+    pub relation_type: String,
+    /// The title of the other work
+    pub related_title: String,
+    /// The artist of the other work
+    pub related_artist: Option<String>,
+    /// The ISRC code of the other work, if it has one
+    pub related_isrc: Option<String>,
+}
+
+impl TrackRelation {
+    /// Create a new relation, ready to be set on a track
+    #[must_use]
+    pub fn new(
+        relation_type: &str,
+        related_title: &str,
+        related_artist: Option<&str>,
+        related_isrc: Option<&str>,
+    ) -> Self {
+        Self {
+            relation_type: relation_type.to_string(),
+            related_title: related_title.to_string(),
+            related_artist: related_artist.map(ToString::to_string),
+            related_isrc: related_isrc.map(ToString::to_string),
+            ..Default::default()
+        }
+    }
+
+    /// The ISRC code without spaces or hyphens, if one was given
+    #[must_use]
+    pub fn normalised_isrc(&self) -> Option<String> {
+        self.related_isrc
+            .as_deref()
+            .map(normalise_isrc_base)
+            .filter(|isrc| !isrc.is_empty())
+    }
+
+    /// Validate the relation
+    ///
+    /// # Errors
+    /// If the relation type is unknown, return an error
+    /// If the title is empty, return an error
+    /// If the title or artist is longer than 255 characters, return an error
+    /// If the ISRC code is not well formed, return an error
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !TRACK_RELATION_TYPES.contains(&self.relation_type.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Relation type must be one of {}.",
+                TRACK_RELATION_TYPES.join(", ")
+            ));
+        }
+        if self.related_title.trim().is_empty() {
+            return Err(anyhow::anyhow!("Related title is required."));
+        }
+        if self.related_title.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "Related title must be less than 255 characters."
+            ));
+        }
+        if self
+            .related_artist
+            .as_ref()
+            .is_some_and(|artist| artist.len() > 255)
+        {
+            return Err(anyhow::anyhow!(
+                "Related artist must be less than 255 characters."
+            ));
+        }
+        if let Some(isrc) = self.normalised_isrc()
+            && !is_valid_isrc(&isrc)
+        {
+            return Err(anyhow::anyhow!("Related ISRC code must be a valid ISRC."));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        for relation_type in TRACK_RELATION_TYPES {
+            assert!(
+                TrackRelation::new(relation_type, "Original", Some("Someone"), None)
+                    .validate()
+                    .is_ok()
+            );
+        }
+        assert!(
+            TrackRelation::new("samples", "Original", None, Some("uk-aaa-25-00001"))
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid() {
+        assert_eq!(
+            TrackRelation::new("inspired_by", "Original", None, None)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Relation type must be one of cover_of, samples, remix_of."
+        );
+        assert_eq!(
+            TrackRelation::new("cover_of", " ", None, None)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Related title is required."
+        );
+        assert_eq!(
+            TrackRelation::new("cover_of", "Original", None, Some("NOT-AN-ISRC"))
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Related ISRC code must be a valid ISRC."
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! A public JSON API, for clients such as the mobile app.
+//!
+//! The endpoints reuse the services behind the server functions, so anonymous callers only see published records,
+//! and label owners signed in with a session see hidden ones too.
+
+use axum::{
+    Json, Router,
+    extract::{FromRef, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use sqlx::PgPool;
+use std::time::Duration;
+
+use crate::config::database::request_timeout;
+use crate::models::auth::ssr::AuthSession;
+use crate::routes::{artist::ArtistResult, release::ReleasesResult, track::TracksResult};
+use crate::services::{
+    artist::get_artist_service, errors::ServiceError, release::get_releases_service,
+    track::get_release_tracks_service,
+};
+
+/// The routes of the JSON API, served under `/api/v1`.
+/// Any state the database pool can be taken from will do, e.g. `AppState`.
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    PgPool: FromRef<S>,
+{
+    Router::new()
+        .route("/api/v1/artists/{slug}", get(artist))
+        .route("/api/v1/artists/{slug}/releases", get(artist_releases))
+        .route("/api/v1/releases/{slug}/tracks", get(release_tracks))
+}
+
+/// An error from the JSON API, returned as `{"error": "..."}`.
+/// Missing records are a 404, permission failures a 403, timeouts a 504 and anything else a 500.
+#[derive(Debug)]
+pub struct ApiError(ServiceError);
+
+/// The body of an error response.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ApiErrorBody {
+    /// What went wrong
+    error: String,
+}
+
+impl From<ServiceError> for ApiError {
+    fn from(error: ServiceError) -> Self {
+        Self(error)
+    }
+}
+
+/// The status code for a service error.
+const fn status_for(error: &ServiceError) -> StatusCode {
+    match error {
+        ServiceError::NotFound { .. } => StatusCode::NOT_FOUND,
+        ServiceError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        ServiceError::TimedOut => StatusCode::GATEWAY_TIMEOUT,
+        ServiceError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ApiErrorBody {
+            error: self.0.to_string(),
+        };
+        (status_for(&self.0), Json(body)).into_response()
+    }
+}
+
+/// Run a service, giving up once it takes longer than the timeout
+/// The JSON API is exempt from the server function timeout, so it times its own requests out with a JSON error.
+///
+/// # Errors
+/// If the service takes longer than the timeout, return `ServiceError::TimedOut`
+/// If the service returns an error, return it
+async fn within_timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, ServiceError>>,
+) -> Result<T, ApiError> {
+    let result = tokio::time::timeout(timeout, future).await.map_err(|_| {
+        tracing::error!("API request aborted after {timeout:?}");
+        ServiceError::TimedOut
+    })?;
+    Ok(result?)
+}
+
+/// `GET /api/v1/artists/{slug}` - Get an artist
+///
+/// # Errors
+/// If the artist cannot be found, return a 404, if it takes too long a 504, otherwise a 500
+async fn artist(
+    State(pool): State<PgPool>,
+    auth: AuthSession,
+    Path(slug): Path<String>,
+) -> Result<Json<ArtistResult>, ApiError> {
+    let user = auth.current_user.as_ref();
+    let artist = within_timeout(request_timeout(), get_artist_service(&pool, user, slug)).await?;
+    Ok(Json(artist))
+}
+
+/// `GET /api/v1/artists/{slug}/releases` - Get an artist's releases
+///
+/// # Errors
+/// If the artist cannot be found, return a 404, if it takes too long a 504, otherwise a 500
+async fn artist_releases(
+    State(pool): State<PgPool>,
+    auth: AuthSession,
+    Path(slug): Path<String>,
+) -> Result<Json<ReleasesResult>, ApiError> {
+    let user = auth.current_user.as_ref();
+    let releases =
+        within_timeout(request_timeout(), get_releases_service(&pool, user, slug)).await?;
+    Ok(Json(releases))
+}
+
+/// `GET /api/v1/releases/{slug}/tracks` - Get the tracks on a release
+///
+/// # Errors
+/// If the release cannot be found, return a 404, if it takes too long a 504, otherwise a 500
+async fn release_tracks(
+    State(pool): State<PgPool>,
+    auth: AuthSession,
+    Path(slug): Path<String>,
+) -> Result<Json<TracksResult>, ApiError> {
+    let user = auth.current_user.as_ref();
+    let tracks = within_timeout(
+        request_timeout(),
+        get_release_tracks_service(&pool, user, slug),
+    )
+    .await?;
+    Ok(Json(tracks))
+}
+
+// axum-test's request futures aren't `Send`, which is fine for a test
+#[cfg(test)]
+#[allow(clippy::future_not_send)]
+mod tests {
+    use super::*;
+    use axum_session::{SessionConfig, SessionLayer, SessionStore};
+    use axum_session_auth::AuthSessionLayer;
+    use axum_session_sqlx::SessionPgPool;
+    use axum_test::TestServer;
+
+    use crate::models::{
+        auth::User,
+        test_helpers::{
+            create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        },
+    };
+
+    async fn test_server(pool: PgPool) -> TestServer {
+        let session_store = SessionStore::<SessionPgPool>::new(
+            Some(SessionPgPool::from(pool.clone())),
+            SessionConfig::default().with_table_name("axum_sessions"),
+        )
+        .await
+        .unwrap();
+        let app = router()
+            .layer(AuthSessionLayer::<User, i64, SessionPgPool, PgPool>::new(
+                Some(pool.clone()),
+            ))
+            .layer(SessionLayer::new(session_store))
+            .with_state(pool);
+        TestServer::new(app).unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_api_anonymous_sees_published_records(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        let unpublished_release = unpublished_release.update(&pool).await.unwrap();
+        let server = test_server(pool).await;
+
+        let response = server
+            .get(&format!("/api/v1/artists/{}", artist.slug))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<ArtistResult>().artist.id, artist.id);
+
+        let response = server
+            .get(&format!("/api/v1/artists/{}/releases", artist.slug))
+            .await;
+        response.assert_status_ok();
+        let releases = response.json::<ReleasesResult>().releases;
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.id)
+                .collect::<Vec<_>>(),
+            vec![release.id]
+        );
+
+        let response = server
+            .get(&format!("/api/v1/releases/{}/tracks", release.slug))
+            .await;
+        response.assert_status_ok();
+        let tracks = response.json::<TracksResult>().tracks;
+        assert_eq!(
+            tracks.iter().map(|track| track.id).collect::<Vec<_>>(),
+            vec![track.id]
+        );
+
+        let response = server
+            .get(&format!(
+                "/api/v1/releases/{}/tracks",
+                unpublished_release.slug
+            ))
+            .await;
+        response.assert_status_not_found();
+        assert_eq!(
+            response.json::<ApiErrorBody>().error,
+            format!(
+                "Could not find release with slug {}.",
+                unpublished_release.slug
+            )
+        );
+    }
+
+    #[test]
+    fn test_status_for() {
+        assert_eq!(
+            status_for(&ServiceError::NotFound {
+                context: "Error while getting artist".to_string(),
+                message: "Could not find artist with slug missing.".to_string(),
+            }),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status_for(&ServiceError::Forbidden {
+                message: "You do not have permission.".to_string(),
+            }),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status_for(&ServiceError::TimedOut),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(
+            status_for(&ServiceError::Internal {
+                message: "Could not access releases.".to_string(),
+            }),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn test_api_error_into_response() {
+        let response = ApiError::from(ServiceError::Forbidden {
+            message: "You do not have permission.".to_string(),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = ApiError::from(ServiceError::Internal {
+            message: "Could not access releases.".to_string(),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_within_timeout_times_out() {
+        let result = within_timeout(Duration::from_millis(10), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, ServiceError>(())
+        })
+        .await;
+
+        let response = result.unwrap_err().into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_within_timeout_completes() {
+        let result = within_timeout(Duration::from_secs(5), async { Ok::<_, ServiceError>(42) })
+            .await
+            .unwrap();
+
+        assert_eq!(result, 42);
+    }
+
+    #[sqlx::test]
+    async fn test_api_missing_artist(pool: PgPool) {
+        let server = test_server(pool).await;
+
+        let response = server.get("/api/v1/artists/missing").await;
+
+        response.assert_status_not_found();
+    }
+}
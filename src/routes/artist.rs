@@ -7,10 +7,14 @@ use crate::models::artist::Artist;
 
 #[cfg(feature = "ssr")]
 use crate::services::artist::{
-    create_artist_service, delete_artist_service, get_artist_service, restore_artist_service,
-    update_artist_service,
+    add_artist_manager_service, create_artist_service, delete_artist_service, delete_impact,
+    get_artist_managers_service, get_artist_service, get_artist_stats_service,
+    remove_artist_manager_service, restore_artist_service, update_artist_service,
+    update_artist_stats_service,
 };
 #[cfg(feature = "ssr")]
+use crate::services::publishing::Republish;
+#[cfg(feature = "ssr")]
 use crate::state::{auth, pool};
 
 /// Contains the result of fetching a single artist.
@@ -18,6 +22,62 @@ use crate::state::{auth, pool};
 pub struct ArtistResult {
     /// The artist being fetched.
     pub artist: Artist,
+    /// The absolute URL of the artist's Open Graph share image, if it has been drawn.
+    /// Only included when the artist is fetched for their page.
+    #[serde(default)]
+    pub og_image_url: Option<String>,
+}
+
+/// A user assigned to manage an artist.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug, Eq, PartialEq)]
+pub struct ArtistManager {
+    /// The ID of the user.
+    pub user_id: i64,
+    /// The username of the user.
+    pub username: String,
+}
+
+/// Contains the users assigned to manage an artist.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct ArtistManagersResult {
+    /// The managers, ordered by username.
+    pub managers: Vec<ArtistManager>,
+}
+
+/// Contains an artist along with their external streaming stats.
+/// This is only returned to admins; the stats are not part of the public artist.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct ArtistStatsResult {
+    /// The artist the stats belong to.
+    pub artist: Artist,
+    /// Monthly listeners on external streaming services.
+    pub monthly_listeners: Option<i32>,
+    /// When the stats were last updated.
+    pub stats_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<Artist> for ArtistStatsResult {
+    fn from(artist: Artist) -> Self {
+        Self {
+            monthly_listeners: artist.monthly_listeners,
+            stats_updated_at: artist.stats_updated_at,
+            artist,
+        }
+    }
+}
+
+/// What deleting an artist would affect. Nothing is changed when this is worked out.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct DeleteImpact {
+    /// The releases the artist is the primary artist on.
+    pub primary_releases: i64,
+    /// The releases the artist is only featured on.
+    pub featured_releases: i64,
+    /// The tracks the artist is credited on.
+    pub tracks: i64,
+    /// Whether merging the artist into another would be safer than deleting it,
+    /// because other artists' releases credit it.
+    pub merge_recommended: bool,
 }
 
 /// Get a specific artist by its slug.
@@ -36,7 +96,9 @@ pub async fn get_artist(
     slug: String,
 ) -> Result<ArtistResult, ServerFnError> {
     let pool = pool()?;
-    get_artist_service(&pool, slug).await
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    Ok(get_artist_service(&pool, user, slug).await?)
 }
 
 /// Create a new artist.
@@ -102,10 +164,33 @@ pub async fn delete_artist(
     delete_artist_service(&pool, user, slug).await
 }
 
+/// Report what deleting an artist would affect, before it is deleted. Nothing is changed.
+///
+/// # Arguments:
+/// * `artist_id`: The ID of the artist.
+///
+/// # Returns:
+/// * A `DeleteImpact` with the number of releases and tracks that would be affected.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist cannot be found, if the user does not have permission, or if there is an issue with the database connection.
+#[server(GetDeleteImpact, "/api", endpoint = "artist_delete_impact")]
+pub async fn get_delete_impact(
+    /// The ID of the artist.
+    artist_id: i64,
+) -> Result<DeleteImpact, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    delete_impact(&pool, user, artist_id).await
+}
+
 /// Restore a deleted artist.
 ///
 /// # Arguments:
 /// * `slug`: The slug of the artist to be restored.
+/// * `republish`: Whether to change the published date of the restored artist. If not, the original date is kept.
+/// * `published_at`: The date to publish the restored artist at, when `republish` is set. If empty, it is restored as a draft.
 ///
 /// # Returns:
 /// * A `ArtistResult` containing the restored artist.
@@ -116,9 +201,135 @@ pub async fn delete_artist(
 pub async fn restore_artist(
     /// The slug of the artist to be restored.
     slug: String,
+    /// Whether to change the published date when restoring.
+    #[server(default)]
+    republish: bool,
+    /// The date to publish at when `republish` is set, or empty to restore as a draft.
+    #[server(default)]
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<ArtistResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    restore_artist_service(&pool, user, slug).await
+    restore_artist_service(
+        &pool,
+        user,
+        slug,
+        Republish::from_form(republish, published_at),
+    )
+    .await
+}
+
+/// Get the external streaming stats for an artist.
+///
+/// # Arguments:
+/// * `slug`: The slug of the artist.
+///
+/// # Returns:
+/// * An `ArtistStatsResult` containing the artist and their stats.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist cannot be found, or if the user does not have permission.
+#[server(GetArtistStats, "/api", endpoint = "get_artist_stats")]
+pub async fn get_artist_stats(
+    /// The slug of the artist.
+    slug: String,
+) -> Result<ArtistStatsResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    get_artist_stats_service(&pool, user, slug).await
+}
+
+/// Update the external streaming stats for an artist.
+///
+/// # Arguments:
+/// * `slug`: The slug of the artist.
+/// * `monthly_listeners`: The number of monthly listeners.
+///
+/// # Returns:
+/// * An `ArtistStatsResult` containing the artist and their updated stats.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the value is negative, the artist cannot be found, or if the user does not have permission.
+#[server(UpdateArtistStats, "/api", endpoint = "update_artist_stats")]
+pub async fn update_artist_stats(
+    /// The slug of the artist.
+    slug: String,
+    /// The number of monthly listeners.
+    monthly_listeners: i32,
+) -> Result<ArtistStatsResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    update_artist_stats_service(&pool, user, slug, monthly_listeners).await
+}
+
+/// Get the users assigned to manage an artist.
+///
+/// # Arguments:
+/// * `slug`: The slug of the artist.
+///
+/// # Returns:
+/// * An `ArtistManagersResult` containing the artist's managers.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist cannot be found, or if the user does not have permission.
+#[server(GetArtistManagers, "/api", endpoint = "get_artist_managers")]
+pub async fn get_artist_managers(
+    /// The slug of the artist.
+    slug: String,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    get_artist_managers_service(&pool, user, slug).await
+}
+
+/// Assign a user to manage an artist.
+///
+/// # Arguments:
+/// * `slug`: The slug of the artist.
+/// * `username`: The username of the user to assign.
+///
+/// # Returns:
+/// * An `ArtistManagersResult` containing the artist's managers.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist or user cannot be found, or if the user does not have permission.
+#[server(AddArtistManager, "/api", endpoint = "add_artist_manager")]
+pub async fn add_artist_manager(
+    /// The slug of the artist.
+    slug: String,
+    /// The username of the user to assign.
+    username: String,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    add_artist_manager_service(&pool, user, slug, username).await
+}
+
+/// Stop a user managing an artist.
+///
+/// # Arguments:
+/// * `slug`: The slug of the artist.
+/// * `user_id`: The ID of the manager to remove.
+///
+/// # Returns:
+/// * An `ArtistManagersResult` containing the artist's remaining managers.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist or user cannot be found, or if the user does not have permission.
+#[server(RemoveArtistManager, "/api", endpoint = "remove_artist_manager")]
+pub async fn remove_artist_manager(
+    /// The slug of the artist.
+    slug: String,
+    /// The ID of the manager to remove.
+    user_id: i64,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    remove_artist_manager_service(&pool, user, slug, user_id).await
 }
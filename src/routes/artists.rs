@@ -3,9 +3,12 @@
 use leptos::prelude::ServerFnError;
 use leptos::server;
 
-use crate::models::release::Release;
+use crate::config::image::PlaceholderImages;
 #[cfg(feature = "ssr")]
-use crate::services::artists::get_releases_for_artist_service;
+use crate::models::record_label::RecordLabel;
+use crate::models::{artist::Artist, release::Release};
+#[cfg(feature = "ssr")]
+use crate::services::artists::{artist_index, get_releases_for_artist_service};
 #[cfg(feature = "ssr")]
 use crate::state::{auth, pool};
 
@@ -16,6 +19,42 @@ pub struct ArtistsReleaseResult {
     pub releases: Vec<Release>,
 }
 
+/// The public details of an artist, for listings such as the artist index.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug, Eq, PartialEq)]
+pub struct ArtistDto {
+    /// The ID of the artist.
+    pub id: i64,
+    /// The name of the artist.
+    pub name: String,
+    /// The name used when sorting the artist, e.g. "Beatles" for "The Beatles".
+    pub sort_name: String,
+    /// The slug of the artist.
+    pub slug: String,
+    /// The URL of the artist's primary image.
+    pub primary_image_url: String,
+}
+
+impl ArtistDto {
+    /// Create the public details of an artist, using the placeholder image if the artist has no image.
+    #[must_use]
+    pub fn new(artist: Artist, placeholders: &PlaceholderImages) -> Self {
+        Self {
+            sort_name: artist.sort_name_or_default(),
+            primary_image_url: artist.primary_image_url(placeholders),
+            id: artist.id,
+            name: artist.name,
+            slug: artist.slug,
+        }
+    }
+}
+
+/// The published artists on the record label, grouped by the first letter of their sort name.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct ArtistIndexResult {
+    /// The groups of artists, in alphabetical order, with "#" for names that don't start with a letter.
+    pub groups: Vec<(char, Vec<ArtistDto>)>,
+}
+
 /// Get releases for multiple artists by their IDs.
 ///
 /// # Arguments:
@@ -44,3 +83,24 @@ pub async fn get_releases_for_artist(
         })?;
     Ok(ArtistsReleaseResult { releases })
 }
+
+/// Get the A-Z index of the published artists on the record label.
+///
+/// # Returns:
+/// * An `ArtistIndexResult` containing the artists grouped by the first letter of their sort name.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the record label cannot be found, or if there is an issue with the database connection.
+#[server(GetArtistIndex, "/api", endpoint = "artist_index")]
+pub async fn get_artist_index() -> Result<ArtistIndexResult, ServerFnError> {
+    let pool = pool()?;
+    let record_label = RecordLabel::first(&pool).await.map_err(|x| {
+        let err = format!("Error while getting record label: {x:?}");
+        tracing::error!("{err}");
+        ServerFnError::new("Could not retrieve label, try again later")
+    })?;
+
+    Ok(ArtistIndexResult {
+        groups: artist_index(&pool, record_label.id).await?,
+    })
+}
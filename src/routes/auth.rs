@@ -1,16 +1,25 @@
 //! Routes for user authentication and management.
-#[cfg(feature = "ssr")]
-use bcrypt::verify;
 use leptos::prelude::*;
 
+#[cfg(feature = "ssr")]
+use crate::config::auth::{login_redirect, require_email_verification};
 use crate::forms::user::{ChangePasswordForm, RegisterUserForm, UpdateUserForm};
 use crate::models::auth::User;
 #[cfg(feature = "ssr")]
-use crate::models::auth::UserPasshash;
+use crate::services::authentication_helpers::{
+    AUTH_IP_RATE_LIMITER, AUTH_RATE_LIMITER, rate_limit_key,
+};
+#[cfg(feature = "ssr")]
+use crate::services::mailer::mailer;
 #[cfg(feature = "ssr")]
-use crate::services::user::{change_password_service, register_user_service, update_user_service};
+use crate::services::user::{
+    change_password_service, login_user_service, register_user_service, request_password_reset,
+    reset_password, update_user_service, verify_email,
+};
 #[cfg(feature = "ssr")]
-use crate::state::{auth, pool};
+use crate::state::{auth, pool, request_client_ip};
+#[cfg(feature = "ssr")]
+use crate::utils::redirect::safe_redirect_target;
 
 /// Get the current user from the session.
 ///
@@ -33,7 +42,7 @@ pub async fn get_user() -> Result<Option<User>, ServerFnError> {
 /// # Arguments:
 /// * `username`: The username of the user.
 /// * `password`: The password of the user.
-/// * `remember`: An optional string indicating whether to remember the user (e.g., "true" or "false"). The session will be remembered for a longer period if this is set.
+/// * `remember`: Whether to remember the user. If set the session lasts for `REMEMBER_ME_LIFETIME_DAYS` (30 days by default), otherwise for `SESSION_LIFETIME_HOURS` (2 hours by default).
 ///
 /// # Returns:
 /// * A `Result<User, ServerFnError>` where `Ok(User)` contains the authenticated user if successful, or an error if the login fails.
@@ -43,45 +52,60 @@ pub async fn get_user() -> Result<Option<User>, ServerFnError> {
 /// * The username or password is empty
 /// * If the user does not exist
 /// * If the password does not match the stored hash
+/// * If email verification is required and the user has not verified their email
+/// * If the login cannot be recorded
+/// * If there have been too many login attempts for the username, or from the client's address
 #[server(Login, "/api", endpoint = "login")]
 pub async fn login(
     /// The username of the user.
     username: String,
     /// The password of the user.
     password: String,
-    /// An optional string indicating whether to remember the user (e.g., "true" or "false"). The session will be remembered for a longer period if this is set.
-    remember: Option<String>,
+    /// Whether to remember the user. If set the session lasts for `REMEMBER_ME_LIFETIME_DAYS` (30 days by default), otherwise for `SESSION_LIFETIME_HOURS` (2 hours by default).
+    #[server(default)]
+    remember: bool,
 ) -> Result<User, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
 
-    if username.is_empty() || password.is_empty() {
-        return Err(ServerFnError::ServerError(
-            "Username and password are required.".to_string(),
-        ));
+    if let Some(ip) = request_client_ip() {
+        AUTH_IP_RATE_LIMITER
+            .check(&rate_limit_key("login_ip", &ip.to_string()))
+            .await?;
     }
+    let key = rate_limit_key("login", &username);
+    AUTH_RATE_LIMITER.check(&key).await?;
+    let user = login_user_service(&pool, username, password, require_email_verification()).await?;
+    // The address count is left alone, so logging in to one account doesn't clear the guesses at others
+    AUTH_RATE_LIMITER.reset(&key).await;
+    auth.login_user(user.id);
+    auth.remember_user(remember);
+    Ok(user)
+}
 
-    let (user, UserPasshash(expected_passhash)) =
-        User::get_from_username_with_passhash(username, &pool)
-            .await
-            .ok_or_else(|| ServerFnError::new("User does not exist."))?;
-
-    if verify(password, &expected_passhash)? {
-        auth.login_user(user.id);
-        auth.remember_user(remember.is_some());
-        Ok(user)
-    } else {
-        Err(ServerFnError::ServerError(
-            "Password does not match.".to_string(),
-        ))
-    }
+/// Get the page to send a user to after they log in.
+///
+/// # Arguments:
+/// * `next`: The page the user asked for, if any. Only used if it is a path on this site.
+///
+/// # Returns:
+/// * The `next` page if it is safe, otherwise the configured `LOGIN_REDIRECT`.
+///
+/// # Errors:
+/// Never returns an error, server functions must return a `Result`.
+#[server(GetLoginRedirect, "/api", endpoint = "login_redirect")]
+pub async fn get_login_redirect(
+    /// The page the user asked for, if any.
+    next: Option<String>,
+) -> Result<String, ServerFnError> {
+    Ok(safe_redirect_target(next.as_deref(), &login_redirect()))
 }
 
 /// Register a new user with the provided form data.
 ///
 /// # Arguments:
 /// * `form`: The form data containing the details of the user to be registered.
-/// * `remember`: An optional string indicating whether to remember the user (e.g., "true" or "false"). The session will be remembered for a longer period if this is set.
+/// * `remember`: Whether to remember the user. If set the session lasts for `REMEMBER_ME_LIFETIME_DAYS` (30 days by default), otherwise for `SESSION_LIFETIME_HOURS` (2 hours by default).
 ///
 /// # Returns:
 /// * A `Result<User, ServerFnError>` where `Ok(User)` contains the newly registered user if successful, or an error if the registration fails.
@@ -95,23 +119,108 @@ pub async fn login(
 pub async fn register(
     /// The form data containing the details of the user to be registered.
     form: RegisterUserForm,
-    /// An optional string indicating whether to remember the user (e.g., "true" or "false"). The session will be remembered for a longer period if this is set.
-    remember: Option<String>,
+    /// Whether to remember the user. If set the session lasts for `REMEMBER_ME_LIFETIME_DAYS` (30 days by default), otherwise for `SESSION_LIFETIME_HOURS` (2 hours by default).
+    #[server(default)]
+    remember: bool,
 ) -> Result<User, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
 
-    let user = register_user_service(&pool, form).await;
+    let user = register_user_service(&pool, mailer().as_ref(), form).await;
     match user {
         Ok(user) => {
+            // Users must verify their email before they can log in
+            if require_email_verification() {
+                return Ok(User::default());
+            }
             auth.login_user(user.id);
-            auth.remember_user(remember.is_some());
+            auth.remember_user(remember);
             Ok(user)
         }
         Err(error) => Err(error),
     }
 }
 
+/// Verify the email address of a user with the token they were sent.
+///
+/// # Arguments:
+/// * `token`: The verification token sent to the user.
+///
+/// # Returns:
+/// * A `Result<User, ServerFnError>` where `Ok(User)` contains the verified user if successful, or an error if the verification fails.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if:
+/// * The token does not exist
+/// * The token has expired
+/// * If the user cannot be updated
+#[server(VerifyEmail, "/api", endpoint = "verify_email")]
+pub async fn verify_email_address(
+    /// The verification token sent to the user.
+    token: String,
+) -> Result<User, ServerFnError> {
+    let pool = pool()?;
+
+    verify_email(&pool, token).await
+}
+
+/// Send a password reset link to the user with the given email address.
+/// The response is the same whether or not the email address is known.
+///
+/// # Arguments:
+/// * `email`: The email address of the user.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if:
+/// * The site is read only
+/// * The token cannot be created
+/// * There have been too many reset requests for the email address, or from the client's address
+#[server(RequestPasswordReset, "/api", endpoint = "request_password_reset")]
+pub async fn request_password_reset_email(
+    /// The email address of the user.
+    email: String,
+) -> Result<(), ServerFnError> {
+    let pool = pool()?;
+
+    if let Some(ip) = request_client_ip() {
+        AUTH_IP_RATE_LIMITER
+            .check(&rate_limit_key("password_reset_ip", &ip.to_string()))
+            .await?;
+    }
+    AUTH_RATE_LIMITER
+        .check(&rate_limit_key("password_reset", &email))
+        .await?;
+    request_password_reset(&pool, mailer().as_ref(), email).await?;
+    Ok(())
+}
+
+/// Reset the password of a user with the token they were sent.
+///
+/// # Arguments:
+/// * `token`: The password reset token sent to the user.
+/// * `new_password`: The new password.
+///
+/// # Returns:
+/// * A `Result<User, ServerFnError>` where `Ok(User)` contains the user whose password was reset, or an error if the reset fails.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if:
+/// * The token does not exist
+/// * The token has expired or already been used
+/// * The password is empty
+/// * If the password cannot be updated
+#[server(ResetPassword, "/api", endpoint = "reset_password")]
+pub async fn reset_user_password(
+    /// The password reset token sent to the user.
+    token: String,
+    /// The new password.
+    new_password: String,
+) -> Result<User, ServerFnError> {
+    let pool = pool()?;
+
+    reset_password(&pool, token, new_password).await
+}
+
 /// Logout the current user.
 ///
 /// # Returns:
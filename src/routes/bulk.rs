@@ -0,0 +1,98 @@
+//! Shared result type for bulk operations.
+//!
+//! Bulk operations act on many items at once, and report the outcome for each item.
+//! Where the operation isn't atomic, it carries on past failures and reports every failed item.
+//! Where it is atomic, nothing succeeds and the item that caused the rollback is reported.
+
+use std::fmt::Display;
+
+/// The per-item outcome of a bulk operation.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct BulkResult<T> {
+    /// The items that succeeded.
+    pub succeeded: Vec<T>,
+    /// The ID of each item that failed, with the reason.
+    pub failed: Vec<(i64, String)>,
+}
+
+impl<T> Default for BulkResult<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: vec![],
+            failed: vec![],
+        }
+    }
+}
+
+impl<T> BulkResult<T> {
+    /// Create an empty bulk result.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the result of an atomic bulk operation that was rolled back.
+    /// Nothing succeeded, and only the item that caused the rollback is reported.
+    #[must_use]
+    pub fn rolled_back(id: i64, reason: impl Display) -> Self {
+        Self {
+            succeeded: vec![],
+            failed: vec![(id, reason.to_string())],
+        }
+    }
+
+    /// Record the outcome for an item.
+    pub fn record<E: Display>(&mut self, id: i64, result: Result<T, E>) {
+        match result {
+            Ok(item) => self.succeeded.push(item),
+            Err(e) => self.failed.push((id, e.to_string())),
+        }
+    }
+
+    /// Did every item succeed.
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_mixed_batch() {
+        let mut result = BulkResult::new();
+        for id in [1, 2, 3] {
+            let outcome = if id == 2 {
+                Err(anyhow::anyhow!("Could not find release with id 2."))
+            } else {
+                Ok(id)
+            };
+            result.record(id, outcome);
+        }
+
+        assert_eq!(result.succeeded, vec![1, 3]);
+        assert_eq!(
+            result.failed,
+            vec![(2, "Could not find release with id 2.".to_string())]
+        );
+        assert!(!result.is_complete());
+    }
+
+    #[test]
+    fn test_rolled_back() {
+        let result: BulkResult<i64> = BulkResult::rolled_back(2, "Name is required.");
+
+        assert!(result.succeeded.is_empty());
+        assert_eq!(result.failed, vec![(2, "Name is required.".to_string())]);
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let mut result = BulkResult::new();
+        result.record::<String>(1, Ok(1));
+
+        assert!(result.is_complete());
+    }
+}
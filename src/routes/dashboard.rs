@@ -0,0 +1,76 @@
+//! Routes for the label owner dashboard.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+use crate::models::{artist::Artist, page::Page, release::Release};
+#[cfg(feature = "ssr")]
+use crate::services::dashboard::my_content;
+#[cfg(feature = "ssr")]
+use crate::services::stats::dashboard_stats;
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool};
+
+/// The content a label owner can edit.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct MyContent {
+    /// The artists, most recently updated first.
+    pub artists: Vec<Artist>,
+    /// The releases, most recently updated first.
+    pub releases: Vec<Release>,
+    /// The pages, most recently updated first.
+    pub pages: Vec<Page>,
+}
+
+/// Counts of the content on a record label, for the dashboard.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct DashboardStats {
+    /// The number of artists.
+    pub total_artists: i64,
+    /// The number of releases, including drafts.
+    pub total_releases: i64,
+    /// The number of tracks, including drafts.
+    pub total_tracks: i64,
+    /// The number of releases that have been published.
+    pub published_releases: i64,
+    /// The number of releases with a release date in the future.
+    pub upcoming_releases: i64,
+    /// The number of pages that have not been published.
+    pub draft_pages: i64,
+}
+
+/// Get the artists, releases and pages the current user can edit, including drafts.
+///
+/// # Returns:
+/// A `MyContent` containing the artists, releases and pages.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user does not have permission, or if there is an issue with the database connection.
+#[server(GetMyContent, "/api", endpoint = "my_content")]
+pub async fn get_my_content() -> Result<MyContent, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+
+    my_content(&pool, auth.current_user.as_ref()).await
+}
+
+/// Get counts of the content on a record label, for the dashboard.
+///
+/// # Arguments:
+/// * `label_id`: The ID of the record label.
+///
+/// # Returns:
+/// A `DashboardStats` with the counts. Deleted content is not counted.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user does not have permission, or if there is an issue with the database connection.
+#[server(GetDashboardStats, "/api", endpoint = "dashboard_stats")]
+pub async fn get_dashboard_stats(
+    /// The ID of the record label.
+    label_id: i64,
+) -> Result<DashboardStats, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+
+    dashboard_stats(&pool, auth.current_user.as_ref(), label_id).await
+}
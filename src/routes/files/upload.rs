@@ -7,21 +7,30 @@
 use leptos::prelude::*;
 use server_fn::codec::{MultipartData, MultipartFormData, StreamingText, TextStream};
 #[cfg(feature = "ssr")]
-use std::{fs::File, io::Write};
+use std::{fs::File, io::Write, path::Path};
 
 #[cfg(feature = "ssr")]
-use crate::config::upload::{UploadConfiguration, UploadDetails};
+use crate::config::upload::{
+    UploadConfiguration, UploadDetails, max_image_dimension, webp_quality,
+};
 #[cfg(feature = "ssr")]
 use crate::models::{artist::Artist, auth::User, release::Release};
 #[cfg(feature = "ssr")]
 use crate::services::{
     authentication_helpers::user_with_permissions,
-    files::progress::{FILES, add_chunk, progress_for_file},
+    files::{
+        file_type::{SNIFF_LENGTH, check_file_extension, check_file_type},
+        limits::check_upload_size,
+        progress::{FILES, add_chunk, progress_for_file},
+        resize::downscale_image,
+        webp::create_webp_variant,
+    },
+    read_only::ensure_writable,
 };
 #[cfg(feature = "ssr")]
 use crate::state::{auth, pool, user_context};
 #[cfg(feature = "ssr")]
-use crate::utils::files::valid_file_name;
+use crate::utils::files::{SafeFileName, valid_file_name};
 
 /// Get the upload details based on the upload configuration type.
 /// This function parses the upload configuration string and returns the details for the upload.
@@ -124,6 +133,7 @@ pub async fn upload_file(
         Ok(user) => user,
         Err(e) => return Err(ServerFnError::new(e)),
     };
+    ensure_writable(&pool()?).await?;
 
     let mut more = true;
     while more {
@@ -153,6 +163,7 @@ pub async fn upload_file(
                         return Err(ServerFnError::new("No filename on field.".to_string()));
                     }
                 };
+                check_file_extension(&original_file_name, &upload_details.mime_types)?;
 
                 let file_name = match valid_file_name(
                     &original_file_name,
@@ -168,15 +179,26 @@ pub async fn upload_file(
                     return Err(ServerFnError::new("No upload path specified.".to_string()));
                 };
 
-                let tmp_path = format!("{upload_path}/tmp/{file_name}");
-                tracing::info!("Uploading {file_name} to {tmp_path}");
+                let tmp_path = file_name.path_in(&format!("{upload_path}/tmp"));
+                tracing::info!("Uploading {file_name} to {}", tmp_path.display());
                 let mut f = File::create(tmp_path.clone())?;
+                let progress_id = format!("{}-{original_file_name}", user.username);
+                // The start of the file, kept until there is enough to check the file type
+                let mut header: Vec<u8> = Vec::with_capacity(SNIFF_LENGTH);
+                let mut type_checked = false;
                 let mut chunk_more = true;
                 while chunk_more {
                     match field.chunk().await {
                         Ok(None) => {
                             // File upload complete
                             chunk_more = false;
+                            // Files shorter than the header are checked once they are complete
+                            if !type_checked
+                                && let Err(e) = check_file_type(&header, &upload_details.mime_types)
+                            {
+                                discard_upload(&tmp_path, &progress_id)?;
+                                return Err(e);
+                            }
                             match finalise_file_upload(
                                 upload_config_type.clone(),
                                 file_name.clone(),
@@ -195,12 +217,24 @@ pub async fn upload_file(
                             let total_so_far =
                                 add_chunk(&original_file_name, len, &user.username).await;
 
-                            if total_so_far > upload_details.size_limit {
-                                // Delete file and return error
-                                std::fs::remove_file(tmp_path.clone())?;
-                                let id = format!("{}-{original_file_name}", user.username);
-                                FILES.remove(&id);
-                                return Err(ServerFnError::new("File too large.".to_string()));
+                            if let Err(e) =
+                                check_upload_size(total_so_far, upload_details.size_limit)
+                            {
+                                discard_upload(&tmp_path, &progress_id)?;
+                                return Err(e);
+                            }
+                            if !type_checked {
+                                let needed = SNIFF_LENGTH - header.len();
+                                header.extend_from_slice(&chunk[..needed.min(len)]);
+                                if header.len() == SNIFF_LENGTH {
+                                    type_checked = true;
+                                    if let Err(e) =
+                                        check_file_type(&header, &upload_details.mime_types)
+                                    {
+                                        discard_upload(&tmp_path, &progress_id)?;
+                                        return Err(e);
+                                    }
+                                }
                             }
 
                             f.write_all(&chunk)?;
@@ -220,6 +254,22 @@ pub async fn upload_file(
     Ok(())
 }
 
+/// Discard a rejected upload.
+/// The partly written file is deleted and its progress is no longer tracked.
+///
+/// # Arguments
+/// * `tmp_path`: The temporary path the file was being written to.
+/// * `progress_id`: The ID the upload's progress is tracked under.
+///
+/// # Errors
+/// * Unable to delete the file
+#[cfg(feature = "ssr")]
+fn discard_upload(tmp_path: &Path, progress_id: &str) -> Result<(), ServerFnError> {
+    std::fs::remove_file(tmp_path)?;
+    FILES.remove(progress_id);
+    Ok(())
+}
+
 /// Finalise the file upload.
 /// This function will move the file from the temporary location to the final location.
 /// It will also associate the file with the object.
@@ -241,7 +291,7 @@ pub async fn upload_file(
 #[cfg(feature = "ssr")]
 async fn finalise_file_upload(
     upload_config_type: String,
-    file_name: String,
+    file_name: SafeFileName,
     original_file_name: String,
     slug_field: String,
     user: User,
@@ -249,15 +299,27 @@ async fn finalise_file_upload(
     let Ok(upload_path) = std::env::var("UPLOAD_PATH") else {
         return Err(ServerFnError::new("No upload path specified.".to_string()));
     };
-    let tmp_path = format!("{upload_path}/tmp/{file_name}");
+    let tmp_path = file_name.path_in(&format!("{upload_path}/tmp"));
 
     let upload_details = match upload_details(&upload_config_type) {
         Ok(details) => details,
         Err(e) => return Err(ServerFnError::new(e)),
     };
 
-    let path = format!("{upload_path}/{}/{file_name}", upload_details.path);
-    match std::fs::rename(tmp_path.clone(), path) {
+    // Very large images waste storage, so store them at the maximum size.
+    // Decoding, resizing and encoding are CPU bound, so they run on the blocking thread pool.
+    let resize_path = tmp_path.clone();
+    let max_dimension = max_image_dimension();
+    tokio::task::spawn_blocking(move || downscale_image(&resize_path, max_dimension))
+        .await
+        .map_err(|e| {
+            let err = format!("Error while waiting for image resize: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not resize image.".to_string())
+        })??;
+
+    let path = file_name.path_in(&format!("{upload_path}/{}", upload_details.path));
+    match std::fs::rename(tmp_path.clone(), &path) {
         Ok(()) => {
             tracing::info!("File uploaded.");
             let id = format!("{}-{original_file_name}", user.username);
@@ -267,8 +329,15 @@ async fn finalise_file_upload(
             return Err(ServerFnError::new(e.to_string()));
         }
     }
+    // Clients that accept WebP are sent the variant, but the original is always there to fall back to
+    let quality = webp_quality();
+    match tokio::task::spawn_blocking(move || create_webp_variant(&path, quality)).await {
+        Ok(Ok(_)) => (),
+        Ok(Err(e)) => tracing::warn!("Couldn't create WebP variant of {file_name}: {e}"),
+        Err(e) => tracing::warn!("Couldn't create WebP variant of {file_name}: {e:?}"),
+    }
     // Associate the file with the object.
-    let _ = store_file_to_object(&file_name, &upload_config_type, &slug_field).await;
+    let _ = store_file_to_object(file_name.as_str(), &upload_config_type, &slug_field).await;
 
     Ok(())
 }
@@ -338,7 +407,7 @@ async fn store_artist_primary_image(
         };
 
     // Store the file to the artist
-    let mut artist = match Artist::get_by_slug(&pool, slug_field.to_string()).await {
+    let mut artist = match Artist::get_by_slug(&pool, slug_field.to_string(), true).await {
         Ok(artist) => artist,
         Err(e) => {
             tracing::error!("Couldn't get artist: {e}");
@@ -429,7 +498,7 @@ async fn store_release(file_name: &str, slug_field: &str) -> Result<(), ServerFn
         };
 
     // Store the file to the artist
-    let mut release = match Release::get_by_slug(&pool, slug_field.to_string()).await {
+    let mut release = match Release::get_by_slug(&pool, slug_field.to_string(), true).await {
         Ok(release) => release,
         Err(e) => {
             tracing::error!("Couldn't get release: {e}");
@@ -487,3 +556,90 @@ pub async fn file_progress(
     let progress = progress.map(|bytes| Ok(format!("{bytes}\n")));
     Ok(TextStream::new(progress))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::upload::{max_request_body_size, max_upload_size};
+    use axum::{Router, body::Body, http::StatusCode, routing::post};
+    use axum_test::{
+        TestServer,
+        multipart::{MultipartForm, Part},
+    };
+    use server_fn::codec::FromReq;
+    use tower_http::limit::RequestBodyLimitLayer;
+
+    /// Read a multipart upload the way `upload_file` does, behind the same body limit as the app.
+    fn upload_test_server() -> TestServer {
+        let upload = |request: axum::http::Request<Body>| async move {
+            let error = |e: ServerFnError| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+            let data =
+                <MultipartData as FromReq<MultipartFormData, _, ServerFnError>>::from_req(request)
+                    .await
+                    .map_err(error)?;
+            let Some(mut data) = data.into_inner() else {
+                return Err(error(ServerFnError::new("No data in request.")));
+            };
+            let mut total_so_far = 0;
+            while let Some(mut field) = data
+                .next_field()
+                .await
+                .map_err(|e| error(ServerFnError::new(e.to_string())))?
+            {
+                while let Some(chunk) = field
+                    .chunk()
+                    .await
+                    .map_err(|e| error(ServerFnError::new(e.to_string())))?
+                {
+                    total_so_far += chunk.len();
+                    check_upload_size(total_so_far, max_upload_size()).map_err(error)?;
+                }
+            }
+            Ok::<_, (StatusCode, String)>(())
+        };
+        let app = Router::new()
+            .route("/api/upload_file", post(upload))
+            .layer(RequestBodyLimitLayer::new(max_request_body_size()));
+        TestServer::new(app).unwrap()
+    }
+
+    fn upload_form(size: usize) -> MultipartForm {
+        MultipartForm::new().add_part(
+            "file",
+            Part::bytes(vec![0_u8; size])
+                .file_name("cover.png")
+                .mime_type("image/png"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_upload_at_size_limit() {
+        let server = upload_test_server();
+
+        let response = server
+            .post("/api/upload_file")
+            .multipart(upload_form(max_upload_size()))
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_size_limit() {
+        let server = upload_test_server();
+
+        let response = server
+            .post("/api/upload_file")
+            .multipart(upload_form(max_upload_size() + 1))
+            .await;
+
+        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.text(),
+            format!(
+                "error running server function: File exceeds the maximum size of {} MB.",
+                max_upload_size() / (1024 * 1024)
+            )
+        );
+    }
+}
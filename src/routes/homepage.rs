@@ -0,0 +1,45 @@
+//! Routes for the public homepage.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+#[cfg(feature = "ssr")]
+use crate::models::record_label::RecordLabel;
+use crate::models::release::Release;
+#[cfg(feature = "ssr")]
+use crate::services::homepage::summary;
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool};
+
+/// Everything the homepage needs in a single result.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct HomepageSummary {
+    /// The release to feature at the top of the homepage.
+    /// This is the next upcoming release, or the latest release if nothing is scheduled.
+    pub featured_release: Option<Release>,
+    /// The next upcoming releases, soonest first.
+    pub upcoming_releases: Vec<Release>,
+    /// The latest releases, most recent first.
+    pub latest_releases: Vec<Release>,
+}
+
+/// Get the homepage summary for the record label.
+///
+/// # Returns:
+/// A `HomepageSummary` containing the featured, upcoming and latest releases.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the record label cannot be found, or if there is an issue with the database connection.
+#[server(GetHomepageSummary, "/api", endpoint = "homepage_summary")]
+pub async fn get_homepage_summary() -> Result<HomepageSummary, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+
+    let record_label = RecordLabel::first(&pool).await.map_err(|x| {
+        let err = format!("Error while getting label: {x:?}");
+        tracing::error!("{err}");
+        ServerFnError::new("Could not retrieve label, try again later")
+    })?;
+
+    summary(&pool, user, record_label.id).await
+}
@@ -11,6 +11,26 @@ use crate::services::links::{get_links_service, update_links_service};
 #[cfg(feature = "ssr")]
 use crate::state::{auth, pool};
 
+/// What happened to a link when the links were updated.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinkAction {
+    /// The link was added.
+    Created,
+    /// The link's URL was changed.
+    Updated,
+    /// The link was removed.
+    Deleted,
+}
+
+/// A link that was changed when the links were updated.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct LinkChange {
+    /// The platform of the link, e.g. "Spotify".
+    pub platform: String,
+    /// What happened to the link.
+    pub action: LinkAction,
+}
+
 /// Contains the result of fetching music services for an artist.
 #[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
 pub struct LinksResult {
@@ -18,6 +38,9 @@ pub struct LinksResult {
     pub music_services: Vec<MusicService>,
     /// A vector of social links associated with the artist.
     pub social_media_services: Vec<SocialMediaService>,
+    /// The links that were changed, when the links were updated.
+    #[serde(default)]
+    pub changes: Vec<LinkChange>,
 }
 
 /// Get all music services and social links for a specific artist.
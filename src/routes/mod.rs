@@ -1,13 +1,23 @@
 //! This module contains the API endpoints for the application. It maps between the web API request and the service layer.
 
+#[cfg(feature = "ssr")]
+pub mod api;
 pub mod artist;
 pub mod artists;
 #[allow(clippy::unused_async)]
 pub mod auth;
+pub mod bulk;
+pub mod dashboard;
 pub mod files;
+pub mod homepage;
 pub mod links;
 pub mod menu;
 pub mod page;
+pub mod placement;
 pub mod record_label;
 pub mod release;
+pub mod search;
+pub mod site_settings;
+pub mod slug;
+pub mod tags;
 pub mod track;
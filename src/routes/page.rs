@@ -11,6 +11,8 @@ use crate::services::page::{
     update_page_service,
 };
 #[cfg(feature = "ssr")]
+use crate::services::publishing::Republish;
+#[cfg(feature = "ssr")]
 use crate::state::{auth, pool};
 
 /// Contains a single Page.
@@ -18,6 +20,18 @@ use crate::state::{auth, pool};
 pub struct PageResult {
     /// The page being fetched or modified.
     pub page: Page,
+    /// The page body rendered to sanitised HTML.
+    pub body_html: String,
+}
+
+#[cfg(feature = "ssr")]
+impl PageResult {
+    /// Create a page result, rendering the body on the server so the sanitiser isn't sent to the browser.
+    #[must_use]
+    pub fn new(page: Page) -> Self {
+        let body_html = page.rendered_body();
+        Self { page, body_html }
+    }
 }
 
 /// Get a specific page by its slug.
@@ -108,6 +122,8 @@ pub async fn delete_page(
 ///
 /// # Arguments:
 /// * `slug`: The slug of the page to be restored.
+/// * `republish`: Whether to change the published date of the restored page. If not, the original date is kept.
+/// * `published_at`: The date to publish the restored page at, when `republish` is set. If empty, it is restored as a draft.
 ///
 /// # Returns:
 /// * A `PageResult` containing the restored page.
@@ -118,9 +134,21 @@ pub async fn delete_page(
 pub async fn restore_page(
     /// The slug of the page to be restored.
     slug: String,
+    /// Whether to change the published date when restoring.
+    #[server(default)]
+    republish: bool,
+    /// The date to publish at when `republish` is set, or empty to restore as a draft.
+    #[server(default)]
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<PageResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    restore_page_service(&pool, user, slug).await
+    restore_page_service(
+        &pool,
+        user,
+        slug,
+        Republish::from_form(republish, published_at),
+    )
+    .await
 }
@@ -0,0 +1,93 @@
+//! Routes for managing the playlist placements of releases and tracks.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+use crate::models::placement::{Placement, PlacementEntity};
+#[cfg(feature = "ssr")]
+use crate::services::placement::{
+    add_placement_service, list_placements_service, remove_placement_service,
+};
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool};
+
+/// Contains multiple placements.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct PlacementsResult {
+    /// A vector of placements.
+    pub placements: Vec<Placement>,
+}
+
+/// List the playlist placements for a release or track.
+///
+/// # Arguments:
+/// * `entity_type`: The type of entity.
+/// * `entity_id`: The ID of the release or track.
+///
+/// # Returns:
+/// * A `PlacementsResult` containing the placements, most recently added first.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user is not a label owner, or if there is an issue with the database connection.
+#[server(ListPlacements, "/api", endpoint = "list_placements")]
+pub async fn list_placements(
+    /// The type of entity.
+    entity_type: PlacementEntity,
+    /// The ID of the release or track.
+    entity_id: i64,
+) -> Result<PlacementsResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    list_placements_service(&pool, user, entity_type, entity_id).await
+}
+
+/// Add a playlist placement to a release or track.
+///
+/// # Arguments:
+/// * `entity_type`: The type of entity.
+/// * `entity_id`: The ID of the release or track.
+/// * `playlist_name`: The name of the playlist.
+/// * `url`: The URL of the playlist.
+///
+/// # Returns:
+/// * The added `Placement`.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user is not a label owner, if the placement is invalid, or if there is an issue with the database connection.
+#[server(AddPlacement, "/api", endpoint = "add_placement")]
+pub async fn add_placement(
+    /// The type of entity.
+    entity_type: PlacementEntity,
+    /// The ID of the release or track.
+    entity_id: i64,
+    /// The name of the playlist.
+    playlist_name: String,
+    /// The URL of the playlist.
+    url: String,
+) -> Result<Placement, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    add_placement_service(&pool, user, entity_type, entity_id, playlist_name, url).await
+}
+
+/// Remove a playlist placement.
+///
+/// # Arguments:
+/// * `id`: The ID of the placement.
+///
+/// # Returns:
+/// * The removed `Placement`.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user is not a label owner, if the placement cannot be found, or if there is an issue with the database connection.
+#[server(RemovePlacement, "/api", endpoint = "remove_placement")]
+pub async fn remove_placement(
+    /// The ID of the placement.
+    id: i64,
+) -> Result<Placement, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    remove_placement_service(&pool, user, id).await
+}
@@ -4,7 +4,13 @@ use leptos::server;
 
 use crate::models::{artist::Artist, page::Page, record_label::RecordLabel};
 #[cfg(feature = "ssr")]
-use crate::state::{auth, pool};
+use crate::services::export::{release_calendar, release_feed};
+#[cfg(feature = "ssr")]
+use crate::services::read_only::ensure_writable;
+#[cfg(feature = "ssr")]
+use crate::services::sitemap::sitemap;
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool, record_label_cache};
 
 /// A result containing a single `RecordLabel`.
 #[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
@@ -101,7 +107,8 @@ pub async fn get_label_pages(
     let current_user = auth.current_user.unwrap_or_default();
     let show_hidden = current_user.permissions.contains("label_owner");
 
-    let record_label = RecordLabel::get_by_id(&pool, record_label_id)
+    let cache = record_label_cache()?;
+    let record_label = RecordLabel::get_by_id_cached(&pool, &cache, record_label_id)
         .await
         .map_err(|x| {
             let err = format!("Error while getting label: {x:?}");
@@ -123,6 +130,9 @@ pub async fn get_label_pages(
 /// * `name`: The new name for the record label.
 /// * `description`: The new description for the record label.
 /// * `isrc_base`: The new ISRC base for the record label.
+/// * `currency`: The ISO 4217 currency code used for the record label's prices.
+/// * `release_date_placeholder`: Shown instead of the release date for announced releases without one.
+/// * `catalogue_prefix`: The prefix for generated catalogue numbers.
 ///
 /// # Returns:
 /// A `LabelResult` containing the updated record label.
@@ -139,6 +149,12 @@ pub async fn update_record_label(
     description: String,
     /// The new ISRC base for the record label.
     isrc_base: String,
+    /// The ISO 4217 currency code used for the record label's prices.
+    currency: String,
+    /// Shown instead of the release date for announced releases without one, e.g. "TBA".
+    release_date_placeholder: String,
+    /// The prefix for generated catalogue numbers, e.g. "CAT-".
+    catalogue_prefix: String,
 ) -> Result<LabelResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
@@ -152,6 +168,7 @@ pub async fn update_record_label(
             "You do not have permission to update a label",
         ));
     }
+    ensure_writable(&pool).await?;
 
     let mut record_label = RecordLabel::get_by_id(&pool, id).await.map_err(|x| {
         let err = format!("Error while getting label: {x:?}");
@@ -162,7 +179,14 @@ pub async fn update_record_label(
     record_label.name = name;
     record_label.description = description;
     record_label.isrc_base = isrc_base;
-    match record_label.clone().update(&pool).await {
+    record_label.currency = currency;
+    record_label.release_date_placeholder = release_date_placeholder;
+    record_label.catalogue_prefix = catalogue_prefix;
+    match record_label
+        .clone()
+        .update(&pool, &record_label_cache()?)
+        .await
+    {
         Ok(record_label) => Ok(LabelResult { record_label }),
         Err(e) => {
             let err = format!("Error while updating label: {e}");
@@ -173,3 +197,127 @@ pub async fn update_record_label(
         }
     }
 }
+
+/// The path of the iCalendar feed of scheduled releases.
+pub const RELEASE_CALENDAR_PATH: &str = "/releases.ics";
+
+/// Serve the record label's scheduled releases as an iCalendar feed.
+/// This is a plain axum handler rather than a server function, so calendar apps can subscribe to it.
+///
+/// # Returns
+/// The feed, with a `text/calendar` content type.
+///
+/// # Errors
+/// Will return a 500 if there is an issue retrieving the record label or its releases.
+#[cfg(feature = "ssr")]
+pub async fn release_calendar_feed(
+    axum::extract::State(pool): axum::extract::State<sqlx::PgPool>,
+) -> Result<impl axum::response::IntoResponse, (axum::http::StatusCode, String)> {
+    let internal_error =
+        |e: ServerFnError| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let record_label = RecordLabel::first(&pool).await.map_err(|e| {
+        let err = format!("Error while getting record label: {e:?}");
+        tracing::error!("{err}");
+        internal_error(ServerFnError::new(e))
+    })?;
+    let calendar = release_calendar(&pool, &record_label)
+        .await
+        .map_err(internal_error)?;
+
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/calendar; charset=utf-8",
+        )],
+        calendar,
+    ))
+}
+
+/// The path of the RSS feed of the latest releases.
+pub const RELEASE_FEED_PATH: &str = "/releases.rss";
+
+/// Serve the record label's latest releases as an RSS feed, so fans can follow them in a feed reader.
+///
+/// # Returns
+/// The feed, with an `application/rss+xml` content type.
+///
+/// # Errors
+/// Will return a 500 if there is an issue retrieving the record label or its releases.
+#[cfg(feature = "ssr")]
+pub async fn release_rss_feed(
+    axum::extract::State(pool): axum::extract::State<sqlx::PgPool>,
+) -> Result<impl axum::response::IntoResponse, (axum::http::StatusCode, String)> {
+    let internal_error =
+        |e: ServerFnError| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let record_label = RecordLabel::first(&pool).await.map_err(|e| {
+        let err = format!("Error while getting record label: {e:?}");
+        tracing::error!("{err}");
+        internal_error(ServerFnError::new(e))
+    })?;
+    let feed_url =
+        crate::utils::urls::absolute_url(&crate::config::site::base_url(), RELEASE_FEED_PATH);
+    let feed = release_feed(&pool, &record_label, &feed_url)
+        .await
+        .map_err(internal_error)?;
+
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "application/rss+xml; charset=utf-8",
+        )],
+        feed,
+    ))
+}
+
+/// The path of the sitemap of the public site.
+pub const SITEMAP_PATH: &str = "/sitemap.xml";
+
+/// The query string of the sitemap.
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct SitemapQuery {
+    /// The page of the sitemap, when it is too large for a single file.
+    pub page: Option<usize>,
+}
+
+/// Serve the sitemap of the public site, so search engines can find every public page.
+/// Like the release calendar, this is a plain axum handler so it can be served at a fixed path.
+///
+/// # Returns
+/// The sitemap, with an `application/xml` content type.
+///
+/// # Errors
+/// Will return a 404 if the requested page of the sitemap doesn't exist.
+/// Will return a 500 if there is an issue retrieving the record label or its public pages.
+#[cfg(feature = "ssr")]
+pub async fn sitemap_feed(
+    axum::extract::State(pool): axum::extract::State<sqlx::PgPool>,
+    axum::extract::Query(query): axum::extract::Query<SitemapQuery>,
+) -> Result<impl axum::response::IntoResponse, (axum::http::StatusCode, String)> {
+    let internal_error =
+        |e: ServerFnError| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let record_label = RecordLabel::first(&pool).await.map_err(|e| {
+        let err = format!("Error while getting record label: {e:?}");
+        tracing::error!("{err}");
+        internal_error(ServerFnError::new(e))
+    })?;
+    let sitemap_url =
+        crate::utils::urls::absolute_url(&crate::config::site::base_url(), SITEMAP_PATH);
+    let Some(sitemap) = sitemap(&pool, &record_label, &sitemap_url, query.page)
+        .await
+        .map_err(internal_error)?
+    else {
+        return Err((
+            axum::http::StatusCode::NOT_FOUND,
+            "Sitemap page not found".to_string(),
+        ));
+    };
+
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "application/xml; charset=utf-8",
+        )],
+        sitemap,
+    ))
+}
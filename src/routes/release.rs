@@ -4,16 +4,34 @@ use leptos::prelude::ServerFnError;
 use leptos::server;
 
 use crate::forms::release::{CreateReleaseForm, UpdateReleaseForm};
+use crate::forms::track::CreateTrackForm;
 #[cfg(feature = "ssr")]
 use crate::models::record_label::RecordLabel;
-use crate::models::{artist::Artist, release::Release, track_with_artists::TrackWithArtists};
+use crate::models::{
+    approval::Approval,
+    artist::Artist,
+    placement::Placement,
+    release::{Release, ReleaseStatus},
+    release_credit::ReleaseCredit,
+    track_with_artists::TrackWithArtists,
+};
+use crate::routes::bulk::BulkResult;
+#[cfg(feature = "ssr")]
+use crate::services::export::release_ddex;
+#[cfg(feature = "musicbrainz")]
+use crate::services::import::import_release_service;
+#[cfg(feature = "ssr")]
+use crate::services::publishing::Republish;
 #[cfg(feature = "ssr")]
 use crate::services::release::{
-    create_release_service, delete_release_service, get_next_scheduled_release_service,
-    get_release_service, get_releases_service, restore_release_service, update_release_service,
+    approve_publish, archive_release_service, bulk_publish_releases_service,
+    catalogue_number_in_use_service, create_release_service, delete_release_service,
+    get_archived_releases_service, get_next_scheduled_release_service, get_release_service,
+    get_releases_service, preview_at, publish_now, reorder_tracks_service, restore_release_service,
+    rotate_preview_token_service, update_release_service,
 };
 #[cfg(feature = "ssr")]
-use crate::state::{auth, pool};
+use crate::state::{auth, pool, record_label_cache};
 
 /// Contains multiple Releases.
 #[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
@@ -29,8 +47,72 @@ pub struct ReleaseResult {
     pub release: Release,
     /// A vector of artists associated with the release.
     pub artists: Vec<Artist>,
+    /// The artists who remixed the release, who are also in `artists`.
+    /// Only included when the release is fetched or updated.
+    #[serde(default)]
+    pub remixers: Vec<Artist>,
+    /// The artists a compilation by various artists takes from its published tracks, not including `artists`.
+    /// Only included when the release is fetched for its page.
+    #[serde(default)]
+    pub various_artists: Vec<Artist>,
     /// A vector of tracks with artists associated with the release.
     pub tracks: Vec<TrackWithArtists>,
+    /// The credits for the release, in order.
+    pub credits: Vec<ReleaseCredit>,
+    /// The playlists the release has been featured on. Only included for label owners.
+    pub placements: Vec<Placement>,
+    /// The name of the primary artist, kept even if the artist has been deleted.
+    pub primary_artist_name: String,
+    /// Whether the primary artist has been deleted.
+    pub primary_artist_deleted: bool,
+    /// The absolute URL of the release's Open Graph share image, if it has been drawn.
+    /// Only included when the release is fetched for its page.
+    #[serde(default)]
+    pub og_image_url: Option<String>,
+}
+
+impl ReleaseResult {
+    /// Create a release result, naming the primary artist even if they have been deleted.
+    #[must_use]
+    pub fn new(
+        release: Release,
+        artists: Vec<Artist>,
+        tracks: Vec<TrackWithArtists>,
+        credits: Vec<ReleaseCredit>,
+        primary_artist: &Artist,
+    ) -> Self {
+        Self {
+            release,
+            artists,
+            remixers: vec![],
+            various_artists: vec![],
+            tracks,
+            credits,
+            placements: vec![],
+            primary_artist_name: primary_artist.name.clone(),
+            primary_artist_deleted: primary_artist.deleted_at.is_some(),
+            og_image_url: None,
+        }
+    }
+}
+
+/// The result of publishing a release.
+/// If the record label requires approval, the release is not published until the approval is granted.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct PublishResult {
+    /// The release being published.
+    pub release: Release,
+    /// The approval for the publish, if the record label requires one.
+    pub approval: Option<Approval>,
+}
+
+/// A release imported from another service, as forms for an admin to review before creating it.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct ImportedReleaseResult {
+    /// The form to create the release.
+    pub release: CreateReleaseForm,
+    /// The forms to create the release's tracks, in order.
+    pub tracks: Vec<CreateTrackForm>,
 }
 
 /// Get all releases for a specific artist.
@@ -51,7 +133,7 @@ pub async fn get_releases(
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    get_releases_service(&pool, user, artist_slug).await
+    Ok(get_releases_service(&pool, user, artist_slug).await?)
 }
 
 /// Get the next scheduled release for a specific artist.
@@ -78,7 +160,7 @@ pub async fn get_next_scheduled_release(
         .await
         .map_err(|_| ServerFnError::new("Failed to retrieve record label"))?;
     let artist_id = match artist_slug {
-        Some(slug) if slug.is_empty() => match Artist::get_by_slug(&pool, slug).await {
+        Some(slug) if slug.is_empty() => match Artist::get_by_slug(&pool, slug, false).await {
             Ok(artist) => Some(artist.id),
             Err(_) => None,
         },
@@ -92,6 +174,7 @@ pub async fn get_next_scheduled_release(
 /// # Arguments:
 /// * `artist_slug`: The slug of the artist.
 /// * `slug`: The slug of the release.
+/// * `preview_token`: The token for previewing the release while it is under embargo.
 ///
 /// # Returns:
 /// * A `ReleaseResult` containing the release, its associated artists, and tracks.
@@ -104,11 +187,14 @@ pub async fn get_release(
     artist_slug: String,
     /// The slug of the release.
     slug: String,
+    /// The token for previewing the release while it is under embargo.
+    #[server(default)]
+    preview_token: Option<String>,
 ) -> Result<ReleaseResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    get_release_service(&pool, user, artist_slug, slug).await
+    get_release_service(&pool, user, artist_slug, slug, preview_token).await
 }
 
 /// Create a new release with the provided form data.
@@ -150,7 +236,7 @@ pub async fn update_release(
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    update_release_service(&pool, user, form).await
+    update_release_service(&pool, &record_label_cache()?, user, form).await
 }
 
 /// Delete a release by its slug.
@@ -178,6 +264,8 @@ pub async fn delete_release(
 ///
 /// # Arguments:
 /// * `slug`: The slug of the release to be restored.
+/// * `republish`: Whether to change the published date of the restored release. If not, the original date is kept.
+/// * `published_at`: The date to publish the restored release at, when `republish` is set. If empty, it is restored as a draft.
 ///
 /// # Returns:
 /// * A `ReleaseResult` containing the restored release.
@@ -188,9 +276,284 @@ pub async fn delete_release(
 pub async fn restore_release(
     /// The slug of the release to be restored.
     slug: String,
+    /// Whether to change the published date when restoring.
+    #[server(default)]
+    republish: bool,
+    /// The date to publish at when `republish` is set, or empty to restore as a draft.
+    #[server(default)]
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<ReleaseResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    restore_release_service(
+        &pool,
+        &record_label_cache()?,
+        user,
+        slug,
+        Republish::from_form(republish, published_at),
+    )
+    .await
+}
+
+/// Archive or unarchive a release.
+/// Archived releases are kept out of listings, but can still be reached by their slug.
+///
+/// # Arguments:
+/// * `slug`: The slug of the release.
+/// * `archived`: Whether the release should be archived.
+///
+/// # Returns:
+/// * A `ReleaseResult` containing the archived or unarchived release.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the release cannot be found, or if the user does not have permission.
+#[server(ArchiveRelease, "/api", endpoint = "archive_release")]
+pub async fn archive_release(
+    /// The slug of the release.
+    slug: String,
+    /// Whether the release should be archived.
+    archived: bool,
+) -> Result<ReleaseResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    archive_release_service(&pool, user, slug, archived).await
+}
+
+/// Replace the preview token of a release under embargo, so links shared with the old token stop working.
+///
+/// # Arguments:
+/// * `slug`: The slug of the release.
+///
+/// # Returns:
+/// * A `ReleaseResult` containing the release with its new preview token.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the release cannot be found, if it is not under embargo, or if the user does not have permission.
+#[server(RotatePreviewToken, "/api", endpoint = "rotate_preview_token")]
+pub async fn rotate_preview_token(
+    /// The slug of the release.
+    slug: String,
 ) -> Result<ReleaseResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    restore_release_service(&pool, user, slug).await
+    rotate_preview_token_service(&pool, user, slug).await
+}
+
+/// Get the archived releases, for the admin archive.
+///
+/// # Returns:
+/// * A `ReleasesResult` containing the archived releases.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user does not have permission, or if there is an issue with the database connection.
+#[server(GetArchivedReleases, "/api", endpoint = "get_archived_releases")]
+pub async fn get_archived_releases() -> Result<ReleasesResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    get_archived_releases_service(&pool, user).await
+}
+
+/// Check if a catalogue number is already used on the record label, before the create form is submitted.
+///
+/// # Arguments:
+/// * `label_id`: The record label the release belongs to.
+/// * `catalogue_number`: The proposed catalogue number.
+///
+/// # Returns:
+/// * True if the catalogue number is taken, in any format.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user does not have permission, or if there is an issue with the database connection.
+#[server(
+    CheckCatalogueNumberInUse,
+    "/api",
+    endpoint = "catalogue_number_in_use"
+)]
+pub async fn check_catalogue_number_in_use(
+    /// The record label the release belongs to.
+    label_id: i64,
+    /// The proposed catalogue number.
+    catalogue_number: String,
+) -> Result<bool, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    catalogue_number_in_use_service(&pool, user, label_id, catalogue_number).await
+}
+
+/// Publish a release now.
+/// If the record label requires approval, a pending approval is created instead.
+///
+/// # Arguments:
+/// * `release_id`: The ID of the release to be published.
+///
+/// # Returns:
+/// * A `PublishResult` containing the release and any pending approval.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the release cannot be published, or if the user does not have permission.
+#[server(PublishRelease, "/api", endpoint = "publish_release")]
+pub async fn publish_release(
+    /// The ID of the release to be published.
+    release_id: i64,
+) -> Result<PublishResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    publish_now(&pool, &record_label_cache()?, user, release_id).await
+}
+
+/// Renumber the tracks on a release, from 1 in the given order.
+///
+/// # Arguments:
+/// * `release_id`: The ID of the release.
+/// * `track_ids`: The IDs of every track on the release, in their new order.
+///
+/// # Returns:
+/// * The tracks on the release, in their new order.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the IDs are not exactly the tracks on the release, or if the user does not have permission.
+#[server(ReorderReleaseTracks, "/api", endpoint = "reorder_release_tracks")]
+pub async fn reorder_release_tracks(
+    /// The ID of the release.
+    release_id: i64,
+    /// The IDs of every track on the release, in their new order.
+    track_ids: Vec<i64>,
+) -> Result<Vec<TrackWithArtists>, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    reorder_tracks_service(&pool, user, release_id, track_ids).await
+}
+
+/// Publish or unpublish many releases at once.
+///
+/// # Arguments:
+/// * `release_ids`: The IDs of the releases.
+/// * `published_at`: The published date to set, or `None` to unpublish the releases.
+///
+/// # Returns:
+/// * A `BulkResult` with the IDs of the updated releases, and the reason each other release was left alone.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the releases cannot be updated, or if the user does not have permission.
+#[server(BulkPublishReleases, "/api", endpoint = "bulk_publish_releases")]
+pub async fn bulk_publish_releases(
+    /// The IDs of the releases.
+    release_ids: Vec<i64>,
+    /// The published date to set.
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<BulkResult<i64>, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    bulk_publish_releases_service(&pool, user, release_ids, published_at).await
+}
+
+/// Approve a pending publish request for a release.
+///
+/// # Arguments:
+/// * `release_id`: The ID of the release to be approved.
+///
+/// # Returns:
+/// * A `PublishResult` containing the published release and the approval.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if there is no pending approval, the user requested the publish themselves, or the user does not have permission.
+#[server(ApprovePublish, "/api", endpoint = "approve_publish")]
+pub async fn approve_release_publish(
+    /// The ID of the release to be approved.
+    release_id: i64,
+) -> Result<PublishResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    approve_publish(&pool, user, release_id).await
+}
+
+/// Preview the status a release will have at a moment in time, e.g. when it is scheduled.
+///
+/// # Arguments:
+/// * `slug`: The slug of the release to preview.
+/// * `at`: The moment to preview.
+///
+/// # Returns:
+/// * The status the release would have at that moment.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the release cannot be found, or if the user does not have permission.
+#[server(PreviewRelease, "/api", endpoint = "preview_release")]
+pub async fn preview_release_at(
+    /// The slug of the release to preview.
+    slug: String,
+    /// The moment to preview.
+    at: chrono::DateTime<chrono::Utc>,
+) -> Result<ReleaseStatus, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    preview_at(&pool, user, slug, at).await
+}
+
+/// Export a published release as DDEX-lite XML for distributors.
+///
+/// # Arguments:
+/// * `release_id`: The ID of the release to export.
+///
+/// # Returns:
+/// * The XML document describing the release and its tracks.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the release cannot be found or is not published, or if there is an issue with the database connection.
+#[server(ExportReleaseDdex, "/api", endpoint = "release_ddex")]
+pub async fn export_release_ddex(
+    /// The ID of the release to export.
+    release_id: i64,
+) -> Result<String, ServerFnError> {
+    let pool = pool()?;
+    release_ddex(&pool, &record_label_cache()?, release_id).await
+}
+
+/// Import a release's metadata from MusicBrainz, to pre-fill the release and track forms.
+/// Nothing is saved, the forms are returned for review.
+///
+/// # Arguments:
+/// * `mbid`: The MusicBrainz ID of the release.
+/// * `artist_id`: The ID of the artist the release is by.
+///
+/// # Returns:
+/// * The release and track forms, filled in from MusicBrainz.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user cannot manage the artist, if MusicBrainz cannot be reached or the release cannot be found, or if the `musicbrainz` feature is not enabled.
+#[server(
+    ImportMusicBrainzRelease,
+    "/api",
+    endpoint = "import_musicbrainz_release"
+)]
+pub async fn import_musicbrainz_release(
+    /// The MusicBrainz ID of the release.
+    mbid: String,
+    /// The ID of the artist the release is by.
+    artist_id: i64,
+) -> Result<ImportedReleaseResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    #[cfg(feature = "musicbrainz")]
+    {
+        import_release_service(&pool, user, mbid, artist_id).await
+    }
+    #[cfg(not(feature = "musicbrainz"))]
+    {
+        let _ = (pool, user, mbid, artist_id);
+        Err(ServerFnError::new(
+            "Importing from MusicBrainz is not enabled.",
+        ))
+    }
 }
@@ -0,0 +1,55 @@
+//! Routes for searching the record label's catalogue.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+#[cfg(feature = "ssr")]
+use crate::models::record_label::RecordLabel;
+use crate::models::{artist::Artist, release::Release, track::Track};
+#[cfg(feature = "ssr")]
+use crate::services::search::search;
+#[cfg(feature = "ssr")]
+use crate::state::pool;
+use crate::utils::pagination::Pagination;
+
+/// The public artists, releases and tracks matching a search.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct SearchResults {
+    /// The matching artists, best match first.
+    pub artists: Vec<Artist>,
+    /// The matching releases, best match first.
+    pub releases: Vec<Release>,
+    /// The matching tracks, best match first.
+    pub tracks: Vec<Track>,
+    /// The page of results, shared by each type.
+    pub pagination: Pagination,
+}
+
+/// Search the record label's artists, releases and tracks.
+///
+/// # Arguments:
+/// * `query` - The search query, matched against names and catalogue numbers
+/// * `pagination` - The page of results, with at most `MAX_SEARCH_PER_PAGE` of each type. Defaults to the first page.
+///
+/// # Returns:
+/// A `SearchResults` containing the matching artists, releases and tracks.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the record label cannot be found, or if there is an issue with the database connection.
+#[server(Search, "/api", endpoint = "search")]
+pub async fn search_catalogue(
+    /// The search query.
+    query: String,
+    /// The page of results.
+    #[server(default)]
+    pagination: Pagination,
+) -> Result<SearchResults, ServerFnError> {
+    let pool = pool()?;
+
+    let record_label = RecordLabel::first(&pool).await.map_err(|x| {
+        let err = format!("Error while getting label: {x:?}");
+        tracing::error!("{err}");
+        ServerFnError::new("Could not retrieve label, try again later")
+    })?;
+
+    search(&pool, record_label.id, &query, pagination).await
+}
@@ -0,0 +1,60 @@
+//! Routes for the deployment wide site settings.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+#[cfg(feature = "ssr")]
+use crate::config::maintenance::{maintenance_message, maintenance_mode};
+use crate::models::site_settings::SiteSettings;
+#[cfg(feature = "ssr")]
+use crate::services::read_only::{
+    get_site_settings_service, maintenance_banner_service, set_read_only_service,
+};
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool};
+
+/// Get the site settings.
+///
+/// # Returns:
+/// * The current `SiteSettings`.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if there is an issue with the database connection.
+#[server(GetSiteSettings, "/api", endpoint = "get_site_settings")]
+pub async fn get_site_settings() -> Result<SiteSettings, ServerFnError> {
+    let pool = pool()?;
+    get_site_settings_service(&pool).await
+}
+
+/// Turn read-only mode on or off.
+///
+/// # Arguments:
+/// * `read_only`: Should the deployment be read-only.
+///
+/// # Returns:
+/// * The updated `SiteSettings`.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user is not an admin, or if there is an issue with the database connection.
+#[server(SetReadOnly, "/api", endpoint = "set_read_only")]
+pub async fn set_read_only(
+    /// Should the deployment be read-only.
+    read_only: bool,
+) -> Result<SiteSettings, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    set_read_only_service(&pool, user, read_only).await
+}
+
+/// Get the maintenance banner message.
+///
+/// # Returns:
+/// * The plain text message, or None if the site is not in maintenance or read-only mode.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if there is an issue with the database connection.
+#[server(GetMaintenanceBanner, "/api", endpoint = "get_maintenance_banner")]
+pub async fn get_maintenance_banner() -> Result<Option<String>, ServerFnError> {
+    let pool = pool()?;
+    maintenance_banner_service(&pool, maintenance_mode(), maintenance_message()).await
+}
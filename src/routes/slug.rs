@@ -0,0 +1,34 @@
+//! Routes for checking slugs.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+#[cfg(feature = "ssr")]
+use crate::services::slug::slug_available;
+#[cfg(feature = "ssr")]
+use crate::state::{pool, record_label_cache};
+
+/// Check if a slug is available before a create form is submitted.
+///
+/// # Arguments:
+/// * `entity_type`: The type of entity the slug is for, one of "artist", "release", "page" or "track".
+/// * `slug`: The proposed slug.
+/// * `label_id`: The record label the entity belongs to.
+///
+/// # Returns:
+/// * True if the slug is free to use.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the entity type is unknown, the record label does not exist, or if there is an issue with the database connection.
+#[server(CheckSlugAvailable, "/api", endpoint = "slug_available")]
+pub async fn check_slug_available(
+    /// The type of entity the slug is for.
+    entity_type: String,
+    /// The proposed slug.
+    slug: String,
+    /// The record label the entity belongs to.
+    label_id: i64,
+) -> Result<bool, ServerFnError> {
+    let pool = pool()?;
+    let cache = record_label_cache()?;
+    slug_available(&pool, &cache, &entity_type, slug, label_id).await
+}
@@ -0,0 +1,36 @@
+//! Routes for tagging releases.
+use leptos::prelude::ServerFnError;
+use leptos::server;
+
+use crate::routes::bulk::BulkResult;
+#[cfg(feature = "ssr")]
+use crate::services::tags::bulk_tag;
+#[cfg(feature = "ssr")]
+use crate::state::{auth, pool};
+
+/// Add a tag to, or remove it from, many releases at once.
+///
+/// # Arguments:
+/// * `release_ids`: The IDs of the releases.
+/// * `tag_slug`: The tag, created if it doesn't exist yet.
+/// * `add`: Add the tag if true, remove it if false.
+///
+/// # Returns:
+/// * A `BulkResult` with the IDs of the changed releases. The change is atomic, so if any release fails, none are changed.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user is not a label owner, or if there is an issue with the database connection.
+#[server(BulkTagReleases, "/api", endpoint = "bulk_tag_releases")]
+pub async fn bulk_tag_releases(
+    /// The IDs of the releases.
+    release_ids: Vec<i64>,
+    /// The tag, created if it doesn't exist yet.
+    tag_slug: String,
+    /// Add the tag if true, remove it if false.
+    add: bool,
+) -> Result<BulkResult<i64>, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    bulk_tag(&pool, user, release_ids, tag_slug, add).await
+}
@@ -4,14 +4,21 @@ use leptos::prelude::ServerFnError;
 use leptos::server;
 
 use crate::forms::track::{CreateTrackForm, UpdateTrackForm};
-use crate::models::{artist::Artist, track::Track};
+use crate::models::{
+    artist::Artist, placement::Placement, release::Release, track::Track,
+    track_relation::TrackRelation,
+};
+#[cfg(feature = "ssr")]
+use crate::services::publishing::Republish;
 #[cfg(feature = "ssr")]
 use crate::services::track::{
-    create_track_service, delete_track_service, get_track_service, get_tracks_service,
-    restore_track_service, update_track_service,
+    archive_track_service, assign_isrcs_for_release, create_track_service, delete_track_service,
+    get_archived_tracks_service, get_track_service, get_tracks_service, list_all_for_artist_admin,
+    move_tracks, restore_track_service, set_track_relations_service, update_track_service,
 };
 #[cfg(feature = "ssr")]
-use crate::state::{auth, pool};
+use crate::state::{auth, pool, record_label_cache};
+use crate::utils::pagination::Pagination;
 
 /// Contains multiple Tracks.
 #[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
@@ -20,6 +27,26 @@ pub struct TracksResult {
     pub tracks: Vec<Track>,
 }
 
+/// A track along with the release it is on.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct TrackWithRelease {
+    /// The track, including its ISRC code.
+    pub track: Track,
+    /// The release the track is on.
+    pub release: Release,
+}
+
+/// A page of every track for an artist, for the admin export.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct ArtistTracksPage {
+    /// The tracks on this page, ordered by release then track number.
+    pub tracks: Vec<TrackWithRelease>,
+    /// The page that was fetched.
+    pub pagination: Pagination,
+    /// The total number of tracks across all pages.
+    pub total: i64,
+}
+
 /// The result of fetching a single track along with its associated artists and releases.
 #[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
 pub struct TrackResult {
@@ -27,6 +54,33 @@ pub struct TrackResult {
     pub track: Track,
     /// A vector of artists associated with the track.
     pub artists: Vec<Artist>,
+    /// The duration of the track formatted for display, e.g. "3:45".
+    pub duration_display: Option<String>,
+    /// The playlists the track has been featured on. Only included for label owners.
+    pub placements: Vec<Placement>,
+    /// The works the track covers, samples or remixes. Only included for label owners.
+    pub relations: Vec<TrackRelation>,
+    /// The name of the primary artist, kept even if the artist has been deleted.
+    pub primary_artist_name: String,
+    /// Whether the primary artist has been deleted.
+    pub primary_artist_deleted: bool,
+}
+
+impl TrackResult {
+    /// Create a track result, formatting the track duration for display.
+    /// The primary artist is named even if they have been deleted.
+    #[must_use]
+    pub fn new(track: Track, artists: Vec<Artist>, primary_artist: &Artist) -> Self {
+        Self {
+            duration_display: track.duration_display(),
+            track,
+            artists,
+            placements: vec![],
+            relations: vec![],
+            primary_artist_name: primary_artist.name.clone(),
+            primary_artist_deleted: primary_artist.deleted_at.is_some(),
+        }
+    }
 }
 
 /// Get all tracks for a specific artist and release.
@@ -50,7 +104,7 @@ pub async fn get_tracks(
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    get_tracks_service(&pool, user, artist_slug, release_slug).await
+    Ok(get_tracks_service(&pool, user, artist_slug, release_slug).await?)
 }
 
 /// Get a specific track by its slug, along with its associated artists and releases.
@@ -146,6 +200,8 @@ pub async fn delete_track(
 ///
 /// # Arguments:
 /// * `slug`: The slug of the track to be restored.
+/// * `republish`: Whether to change the published date of the restored track. If not, the original date is kept.
+/// * `published_at`: The date to publish the restored track at, when `republish` is set. If empty, it is restored as a draft.
 ///
 /// # Returns:
 /// * A `TrackResult` containing the restored track.
@@ -156,9 +212,164 @@ pub async fn delete_track(
 pub async fn restore_track(
     /// The slug of the track to be restored.
     slug: String,
+    /// Whether to change the published date when restoring.
+    #[server(default)]
+    republish: bool,
+    /// The date to publish at when `republish` is set, or empty to restore as a draft.
+    #[server(default)]
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Result<TrackResult, ServerFnError> {
     let pool = pool()?;
     let auth = auth().await?;
     let user = auth.current_user.as_ref();
-    restore_track_service(&pool, user, slug).await
+    restore_track_service(
+        &pool,
+        user,
+        slug,
+        Republish::from_form(republish, published_at),
+    )
+    .await
+}
+
+/// Archive or unarchive a track.
+/// Archived tracks are kept out of listings, but can still be reached by their slug.
+///
+/// # Arguments:
+/// * `slug`: The slug of the track.
+/// * `archived`: Whether the track should be archived.
+///
+/// # Returns:
+/// * A `TrackResult` containing the archived or unarchived track.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the track cannot be found, or if the user does not have permission.
+#[server(ArchiveTrack, "/api", endpoint = "archive_track")]
+pub async fn archive_track(
+    /// The slug of the track.
+    slug: String,
+    /// Whether the track should be archived.
+    archived: bool,
+) -> Result<TrackResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    archive_track_service(&pool, user, slug, archived).await
+}
+
+/// Get the archived tracks, for the admin archive.
+///
+/// # Returns:
+/// * A `TracksResult` containing the archived tracks.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the user does not have permission, or if there is an issue with the database connection.
+#[server(GetArchivedTracks, "/api", endpoint = "get_archived_tracks")]
+pub async fn get_archived_tracks() -> Result<TracksResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    get_archived_tracks_service(&pool, user).await
+}
+
+/// Set the works a track covers, samples or remixes, replacing any existing relations.
+///
+/// # Arguments:
+/// * `slug`: The slug of the track.
+/// * `relations`: The relations for the track.
+///
+/// # Returns:
+/// * A `TrackResult` containing the track and its relations.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if a relation is invalid, if the user does not have permission, or if there is an issue with the database connection.
+#[server(SetTrackRelations, "/api", endpoint = "set_track_relations")]
+pub async fn set_track_relations(
+    /// The slug of the track.
+    slug: String,
+    /// The relations for the track.
+    relations: Vec<TrackRelation>,
+) -> Result<TrackResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    set_track_relations_service(&pool, user, slug, relations).await
+}
+
+/// List every track for an artist, one page at a time, so labels can reconcile metadata.
+///
+/// # Arguments:
+/// * `artist_id`: The ID of the artist.
+/// * `pagination`: The page to fetch.
+/// * `include_deleted`: Whether to include deleted tracks, and tracks on deleted releases.
+///
+/// # Returns:
+/// * An `ArtistTracksPage` containing the tracks and their releases.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the artist cannot be found, if the user does not have permission, or if there is an issue with the database connection.
+#[server(
+    ListAllTracksForArtist,
+    "/api",
+    endpoint = "list_all_tracks_for_artist"
+)]
+pub async fn list_all_tracks_for_artist(
+    /// The ID of the artist.
+    artist_id: i64,
+    /// The page to fetch.
+    pagination: Pagination,
+    /// Whether to include deleted tracks.
+    include_deleted: bool,
+) -> Result<ArtistTracksPage, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    list_all_for_artist_admin(&pool, user, artist_id, pagination, include_deleted).await
+}
+
+/// Assign ISRC codes to every track on a release that doesn't have one.
+///
+/// # Arguments:
+/// * `release_id`: The ID of the release.
+/// * `year`: The year of reference for the ISRC codes.
+///
+/// # Returns:
+/// * A `TracksResult` containing the tracks that were assigned an ISRC code.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if the record label has an invalid ISRC base, if there is an issue with the database connection or if the user is not authenticated.
+#[server(AssignIsrcs, "/api", endpoint = "assign_isrcs")]
+pub async fn assign_isrcs(
+    /// The ID of the release.
+    release_id: i64,
+    /// The year of reference for the ISRC codes.
+    year: i32,
+) -> Result<TracksResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    assign_isrcs_for_release(&pool, &record_label_cache()?, user, release_id, year).await
+}
+
+/// Move tracks to another release, keeping their IDs and ISRC codes.
+///
+/// # Arguments:
+/// * `track_ids`: The IDs of the tracks to move.
+/// * `target_release_id`: The ID of the release to move the tracks to.
+///
+/// # Returns:
+/// A `TracksResult` containing the moved tracks.
+///
+/// # Errors:
+/// Will return a `ServerFnError` if a track number is already used on the release, if there is an issue with the database connection or if the user is not authenticated.
+#[server(MoveTracks, "/api", endpoint = "move_tracks")]
+pub async fn move_tracks_to_release(
+    /// The IDs of the tracks to move.
+    track_ids: Vec<i64>,
+    /// The ID of the release to move the tracks to.
+    target_release_id: i64,
+) -> Result<TracksResult, ServerFnError> {
+    let pool = pool()?;
+    let auth = auth().await?;
+    let user = auth.current_user.as_ref();
+    move_tracks(&pool, user, track_ids, target_release_id).await
 }
@@ -2,32 +2,48 @@
 use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
-use super::authentication_helpers::user_with_permissions;
+use super::{
+    authentication_helpers::user_with_permissions,
+    errors::{ServiceError, fetch_or_not_found},
+    files::og_image::{OgImageEntity, ensure_og_image},
+    publishing::Republish,
+    read_only::ensure_writable,
+};
 use crate::forms::artist::{CreateArtistForm, UpdateArtistForm};
 use crate::models::{artist::Artist, auth::User};
-use crate::routes::artist::ArtistResult;
+use crate::routes::artist::{
+    ArtistManager, ArtistManagersResult, ArtistResult, ArtistStatsResult, DeleteImpact,
+};
 
 /// Get an artist by slug
 ///
 /// # Arguments
 /// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user, label owners can resolve deleted artists
 /// slug: String - The slug of the artist
 ///
 /// # Returns
-/// Result<`ArtistResult`, `ServerFnError`> - The artist
+/// Result<`ArtistResult`, `ServiceError`> - The artist
 ///
 /// # Errors
-/// If the artist cannot be found, return an error
+/// If the artist cannot be found, return `ServiceError::NotFound`
 pub async fn get_artist_service(
     pool: &PgPool,
+    user: Option<&User>,
     slug: String,
-) -> Result<ArtistResult, ServerFnError> {
+) -> Result<ArtistResult, ServiceError> {
+    let include_deleted =
+        user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, include_deleted).await,
+        "Error while getting artist",
+    )?;
+    let og_image_url = ensure_og_image(OgImageEntity::Artist(artist.clone())).await;
+
     Ok(ArtistResult {
-        artist: Artist::get_by_slug(pool, slug).await.map_err(|e| {
-            let err = format!("Error while getting artist: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
+        artist,
+        og_image_url,
     })
 }
 
@@ -55,6 +71,7 @@ pub async fn create_artist_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
     Ok(ArtistResult {
         artist: Artist::create(
@@ -71,6 +88,7 @@ pub async fn create_artist_service(
             tracing::error!("{err}");
             ServerFnError::new(e)
         })?,
+        og_image_url: None,
     })
 }
 
@@ -98,18 +116,22 @@ pub async fn update_artist_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    let mut artist = Artist::get_by_slug(pool, artist_form.slug)
-        .await
-        .map_err(|e| {
-            let err = format!("Error while getting artist: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?;
+    let mut artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, artist_form.slug, true).await,
+        "Error while getting artist",
+    )?;
     artist.name = artist_form.name;
+    artist.sort_name = artist_form
+        .sort_name
+        .filter(|sort_name| !sort_name.trim().is_empty());
     artist.description = artist_form.description;
     artist.website = artist_form.website;
     artist.published_at = artist_form.published_at;
+    artist.unlisted = artist_form.unlisted;
+    artist.pinned_release_id = artist_form.pinned_release_id;
+    artist.lock_slug = artist_form.lock_slug.or(artist.lock_slug);
 
     Ok(ArtistResult {
         artist: artist.update(pool).await.map_err(|e| {
@@ -117,6 +139,51 @@ pub async fn update_artist_service(
             tracing::error!("{err}");
             ServerFnError::new(e)
         })?,
+        og_image_url: None,
+    })
+}
+
+/// Report what deleting an artist would affect, without changing anything
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// `artist_id`: i64 - The ID of the artist
+///
+/// # Returns
+/// Result<`DeleteImpact`, `ServerFnError`> - The releases and tracks that would be affected
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+/// If the releases or tracks cannot be counted, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn delete_impact(
+    pool: &PgPool,
+    user: Option<&User>,
+    artist_id: i64,
+) -> Result<DeleteImpact, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_id(pool, artist_id).await,
+        "Error while getting artist",
+    )?;
+    let (primary_releases, featured_releases, tracks) =
+        artist.delete_impact_counts(pool).await.map_err(|e| {
+            let err = format!("Error while counting artist releases and tracks: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(DeleteImpact {
+        primary_releases,
+        featured_releases,
+        tracks,
+        merge_recommended: featured_releases > 0,
     })
 }
 
@@ -143,12 +210,12 @@ pub async fn delete_artist_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    let artist = Artist::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting artist: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
 
     Ok(ArtistResult {
         artist: artist.delete(pool).await.map_err(|e| {
@@ -156,6 +223,7 @@ pub async fn delete_artist_service(
             tracing::error!("{err}");
             ServerFnError::new(e)
         })?,
+        og_image_url: None,
     })
 }
 
@@ -165,6 +233,7 @@ pub async fn delete_artist_service(
 /// pool: `PgPool` - The database connection pool
 /// user: Option<&User> - The user deleting the artist
 /// slug: String - The slug of the artist
+/// republish: `Republish` - Keep the original `published_at`, restore as a draft, or publish at a given date
 ///
 /// # Returns
 /// Result<`ArtistResult`, `ServerFnError`> - The restored artist
@@ -177,39 +246,304 @@ pub async fn restore_artist_service(
     pool: &PgPool,
     user: Option<&User>,
     slug: String,
+    republish: Republish,
 ) -> Result<ArtistResult, ServerFnError> {
     match user_with_permissions(user, vec!["admin", "label_owner"]) {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    let mut artist = Artist::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting artist: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    let mut artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
     artist.deleted_at = None;
+    artist.published_at = republish.published_at(artist.published_at);
     artist.clone().update(pool).await.map_err(|e| {
         let err = format!("Error while restoring artist: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
     tracing::info!("Artist restored: {}", artist.slug);
-    Ok(ArtistResult { artist })
+    Ok(ArtistResult {
+        artist,
+        og_image_url: None,
+    })
+}
+
+/// Get the external streaming stats for an artist
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user viewing the stats
+/// slug: String - The slug of the artist
+///
+/// # Returns
+/// Result<`ArtistStatsResult`, `ServerFnError`> - The artist and their stats
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_artist_stats_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+) -> Result<ArtistStatsResult, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
+    Ok(ArtistStatsResult::from(artist))
+}
+
+/// Update the external streaming stats for an artist
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user updating the stats
+/// slug: String - The slug of the artist
+/// `monthly_listeners`: i32 - The number of monthly listeners
+///
+/// # Returns
+/// Result<`ArtistStatsResult`, `ServerFnError`> - The artist and their updated stats
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+/// If the monthly listeners is negative, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn update_artist_stats_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    monthly_listeners: i32,
+) -> Result<ArtistStatsResult, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
+    let artist = artist
+        .update_stats(pool, monthly_listeners)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while updating artist stats: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    Ok(ArtistStatsResult::from(artist))
+}
+
+/// List the managers of an artist
+#[cfg(feature = "ssr")]
+async fn artist_managers(
+    pool: &PgPool,
+    artist_id: i64,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    let managers = User::list_artist_managers(pool, artist_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting artist managers: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(ArtistManagersResult {
+        managers: managers
+            .into_iter()
+            .map(|manager| ArtistManager {
+                user_id: manager.id,
+                username: manager.username,
+            })
+            .collect(),
+    })
+}
+
+/// Get the users assigned to manage an artist
+/// Only label owners can see and change who manages an artist.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// slug: String - The slug of the artist
+///
+/// # Returns
+/// Result<`ArtistManagersResult`, `ServerFnError`> - The artist's managers
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_artist_managers_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    match user_with_permissions(user, vec!["label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
+    artist_managers(pool, artist.id).await
+}
+
+/// Assign a user to manage an artist
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// slug: String - The slug of the artist
+/// username: String - The username of the user to assign
+///
+/// # Returns
+/// Result<`ArtistManagersResult`, `ServerFnError`> - The artist's managers
+///
+/// # Errors
+/// If the artist or the user to assign cannot be found, return an error
+/// If the user cannot be assigned, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn add_artist_manager_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    username: String,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    match user_with_permissions(user, vec!["label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
+    let manager = User::get_by_username(pool, username.trim().to_string())
+        .await
+        .map_err(ServerFnError::new)?;
+    manager
+        .add_managed_artist(pool, artist.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while assigning artist manager: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    artist_managers(pool, artist.id).await
+}
+
+/// Stop a user managing an artist
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// slug: String - The slug of the artist
+/// `user_id`: i64 - The ID of the manager to remove
+///
+/// # Returns
+/// Result<`ArtistManagersResult`, `ServerFnError`> - The artist's remaining managers
+///
+/// # Errors
+/// If the artist or the manager cannot be found, return an error
+/// If the manager cannot be removed, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn remove_artist_manager_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    user_id: i64,
+) -> Result<ArtistManagersResult, ServerFnError> {
+    match user_with_permissions(user, vec!["label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, true).await,
+        "Error while getting artist",
+    )?;
+    let manager = User::get(user_id, pool)
+        .await
+        .ok_or_else(|| ServerFnError::new("User not found."))?;
+    manager
+        .remove_managed_artist(pool, artist.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while removing artist manager: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    artist_managers(pool, artist.id).await
+}
+
+/// Get the primary artist of a release or track, even if the artist has been deleted
+///
+/// The artist is taken from the already loaded artists when it is one of them, otherwise it is fetched.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `primary_artist_id`: i64 - The ID of the primary artist
+/// artists: &[Artist] - The artists already loaded for the release or track
+///
+/// # Returns
+/// Result<`Artist`, `ServerFnError`> - The primary artist
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_primary_artist(
+    pool: &PgPool,
+    primary_artist_id: i64,
+    artists: &[Artist],
+) -> Result<Artist, ServerFnError> {
+    if let Some(artist) = artists.iter().find(|artist| artist.id == primary_artist_id) {
+        return Ok(artist.clone());
+    }
+    Artist::get_by_id(pool, primary_artist_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting primary artist: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     #[cfg(feature = "ssr")]
+    use crate::models::release::ArtistRole;
+    #[cfg(feature = "ssr")]
     use crate::models::test_helpers::{
-        create_test_artist, create_test_record_label, create_test_user_with_permissions,
+        create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        create_test_user_with_permissions,
     };
 
     #[sqlx::test]
     async fn test_get_artist_service(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
-        let artist_by_slug = get_artist_service(&pool, artist.slug.clone())
+        let artist_by_slug = get_artist_service(&pool, None, artist.slug.clone())
             .await
             .unwrap();
         assert_eq!(artist, artist_by_slug.artist);
@@ -217,11 +551,11 @@ mod tests {
 
     #[sqlx::test]
     async fn test_get_artist_service_no_artist(pool: PgPool) {
-        let result = get_artist_service(&pool, "missing".to_string()).await;
+        let result = get_artist_service(&pool, None, "missing".to_string()).await;
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "error running server function: Could not find artist with slug missing.".to_string()
+            "Could not find artist with slug missing.".to_string()
         );
     }
 
@@ -359,10 +693,14 @@ mod tests {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist_form = UpdateArtistForm {
             slug: artist.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
             name: "Updated Artist".to_string(),
             description: "This is an updated artist".to_string(),
             website: "https://update.example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist = update_artist_service(&pool, Some(&user), artist_form)
             .await
@@ -388,10 +726,14 @@ mod tests {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist_form = UpdateArtistForm {
             slug: artist.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
             name: String::new(),
             description: "This is an updated artist".to_string(),
             website: "https://example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist = update_artist_service(&pool, Some(&user), artist_form).await;
 
@@ -413,10 +755,14 @@ mod tests {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist_form = UpdateArtistForm {
             slug: artist.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
             name,
             description: "This is an updated artist".to_string(),
             website: "https://example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist = update_artist_service(&pool, Some(&user), artist_form).await;
 
@@ -436,10 +782,14 @@ mod tests {
 
         let artist_form = UpdateArtistForm {
             slug: "missing".to_string(),
+            lock_slug: None,
+            sort_name: None,
             name: "Updated Artist".to_string(),
             description: "This is an updated artist".to_string(),
             website: "https://example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist = update_artist_service(&pool, Some(&user), artist_form).await;
 
@@ -455,10 +805,14 @@ mod tests {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist_form = UpdateArtistForm {
             slug: artist.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
             name: "Updated Artist".to_string(),
             description: "This is an updated artist".to_string(),
             website: "https://example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist =
             update_artist_service(&pool, Some(&User::default()), artist_form).await;
@@ -478,10 +832,14 @@ mod tests {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist_form = UpdateArtistForm {
             slug: artist.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
             name: "Updated Artist".to_string(),
             description: "This is an updated artist".to_string(),
             website: "https://example.com".to_string(),
             published_at: Some(chrono::Utc::now()),
+            unlisted: false,
+            pinned_release_id: None,
         };
         let updated_artist = update_artist_service(&pool, Some(&user), artist_form).await;
 
@@ -492,6 +850,107 @@ mod tests {
         );
     }
 
+    #[sqlx::test]
+    async fn test_delete_impact(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let other_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        // Two tracks on the artist's own release
+        let primary_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(&pool, 1, Some(primary_release.clone()), None)
+            .await
+            .unwrap();
+        create_test_track(&pool, 2, Some(primary_release.clone()), None)
+            .await
+            .unwrap();
+        // A deleted release isn't affected
+        let deleted_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        deleted_release.delete(&pool).await.unwrap();
+        // Featured on one track of another artist's release
+        let featured_release = create_test_release(&pool, 3, Some(other_artist.clone()))
+            .await
+            .unwrap();
+        featured_release
+            .set_artists(
+                &pool,
+                vec![
+                    (other_artist.id, ArtistRole::Primary),
+                    (artist.id, ArtistRole::Featured),
+                ],
+            )
+            .await
+            .unwrap();
+        let featured_track = create_test_track(&pool, 3, Some(featured_release.clone()), None)
+            .await
+            .unwrap();
+        featured_track
+            .set_artists(&pool, vec![other_artist.id, artist.id])
+            .await
+            .unwrap();
+        create_test_track(&pool, 4, Some(featured_release.clone()), None)
+            .await
+            .unwrap();
+
+        let impact = delete_impact(&pool, Some(&user), artist.id).await.unwrap();
+        assert_eq!(
+            impact,
+            DeleteImpact {
+                primary_releases: 1,
+                featured_releases: 1,
+                tracks: 3,
+                merge_recommended: true,
+            }
+        );
+
+        let other_impact = delete_impact(&pool, Some(&user), other_artist.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            other_impact,
+            DeleteImpact {
+                primary_releases: 1,
+                featured_releases: 0,
+                tracks: 2,
+                merge_recommended: false,
+            }
+        );
+
+        // Nothing was deleted
+        let artist = Artist::get_by_id(&pool, artist.id).await.unwrap();
+        assert!(artist.deleted_at.is_none());
+        assert_eq!(
+            delete_impact(&pool, Some(&user), artist.id).await.unwrap(),
+            impact
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete_impact_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = delete_impact(&pool, Some(&user), artist.id).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
     #[sqlx::test]
     async fn test_delete_artist_service(pool: PgPool) {
         let permissions = vec!["admin", "label_owner"];
@@ -556,9 +1015,10 @@ mod tests {
 
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist = artist.delete(&pool).await.unwrap();
-        let restored_artist = restore_artist_service(&pool, Some(&user), artist.slug.clone())
-            .await
-            .unwrap();
+        let restored_artist =
+            restore_artist_service(&pool, Some(&user), artist.slug.clone(), Republish::Keep)
+                .await
+                .unwrap();
 
         assert!(restored_artist.artist.deleted_at.is_none());
     }
@@ -571,7 +1031,8 @@ mod tests {
             .unwrap();
 
         let restored_artist =
-            restore_artist_service(&pool, Some(&user), "missing".to_string()).await;
+            restore_artist_service(&pool, Some(&user), "missing".to_string(), Republish::Keep)
+                .await;
         assert!(restored_artist.is_err());
         assert_eq!(
             restored_artist.unwrap_err().to_string(),
@@ -583,8 +1044,13 @@ mod tests {
     async fn test_restore_artist_service_no_user(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist = artist.delete(&pool).await.unwrap();
-        let restored_artist =
-            restore_artist_service(&pool, Some(&User::default()), artist.slug.clone()).await;
+        let restored_artist = restore_artist_service(
+            &pool,
+            Some(&User::default()),
+            artist.slug.clone(),
+            Republish::Keep,
+        )
+        .await;
         assert!(restored_artist.is_err());
         assert_eq!(
             restored_artist.unwrap_err().to_string(),
@@ -599,11 +1065,203 @@ mod tests {
             .unwrap();
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
         let artist = artist.delete(&pool).await.unwrap();
-        let restored_artist = restore_artist_service(&pool, Some(&user), artist.slug.clone()).await;
+        let restored_artist =
+            restore_artist_service(&pool, Some(&user), artist.slug.clone(), Republish::Keep).await;
         assert!(restored_artist.is_err());
         assert_eq!(
             restored_artist.unwrap_err().to_string(),
             "error running server function: You do not have permission.".to_string()
         );
     }
+
+    #[sqlx::test]
+    async fn test_get_artist_stats_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        artist.update_stats(&pool, 1234).await.unwrap();
+
+        let result = get_artist_stats_service(&pool, Some(&user), artist.slug.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(result.artist.id, artist.id);
+        assert_eq!(result.monthly_listeners, Some(1234));
+        assert!(result.stats_updated_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_get_artist_stats_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = get_artist_stats_service(&pool, Some(&user), artist.slug.clone()).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_artist_stats_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        assert_eq!(artist.stats_updated_at, None);
+
+        let before = chrono::Utc::now();
+        let result = update_artist_stats_service(&pool, Some(&user), artist.slug.clone(), 5000)
+            .await
+            .unwrap();
+
+        assert_eq!(result.monthly_listeners, Some(5000));
+        assert!(result.stats_updated_at.unwrap() >= before);
+        let artist = Artist::get_by_slug(&pool, artist.slug, false)
+            .await
+            .unwrap();
+        assert_eq!(artist.monthly_listeners, Some(5000));
+        assert_eq!(artist.stats_updated_at, result.stats_updated_at);
+    }
+
+    #[sqlx::test]
+    async fn test_update_artist_stats_service_negative(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = update_artist_stats_service(&pool, Some(&user), artist.slug.clone(), -1).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Monthly listeners must be 0 or greater.".to_string()
+        );
+        let artist = Artist::get_by_slug(&pool, artist.slug, false)
+            .await
+            .unwrap();
+        assert_eq!(artist.monthly_listeners, None);
+        assert_eq!(artist.stats_updated_at, None);
+    }
+
+    #[sqlx::test]
+    async fn test_update_artist_stats_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = update_artist_stats_service(&pool, Some(&user), artist.slug.clone(), 10).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_artist_manager_service(pool: PgPool) {
+        let label_owner = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let manager = create_test_user_with_permissions(&pool, 2, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = add_artist_manager_service(
+            &pool,
+            Some(&label_owner),
+            artist.slug.clone(),
+            manager.username.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.managers,
+            vec![ArtistManager {
+                user_id: manager.id,
+                username: manager.username.clone(),
+            }]
+        );
+        assert!(manager.can_manage_artist(&pool, artist.id).await);
+
+        let result = get_artist_managers_service(&pool, Some(&label_owner), artist.slug)
+            .await
+            .unwrap();
+        assert_eq!(result.managers.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_add_artist_manager_service_unknown_user(pool: PgPool) {
+        let label_owner = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = add_artist_manager_service(
+            &pool,
+            Some(&label_owner),
+            artist.slug,
+            "nobody".to_string(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: User not found.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_artist_manager_service_no_permissions(pool: PgPool) {
+        // Managers can't hand their artists on to other users
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let other_user = create_test_user_with_permissions(&pool, 2, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+        let result = add_artist_manager_service(
+            &pool,
+            Some(&manager),
+            artist.slug,
+            other_user.username.clone(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+        assert!(!other_user.can_manage_artist(&pool, artist.id).await);
+    }
+
+    #[sqlx::test]
+    async fn test_remove_artist_manager_service(pool: PgPool) {
+        let label_owner = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+        let manager = create_test_user_with_permissions(&pool, 2, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+        let result =
+            remove_artist_manager_service(&pool, Some(&label_owner), artist.slug, manager.id)
+                .await
+                .unwrap();
+
+        assert!(result.managers.is_empty());
+        assert!(!manager.can_manage_artist(&pool, artist.id).await);
+    }
 }
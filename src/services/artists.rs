@@ -3,8 +3,13 @@ use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
 use super::authentication_helpers::user_with_permissions;
+use crate::config::image::PlaceholderImages;
 use crate::models::release::Release;
 use crate::models::{artist::Artist, auth::User};
+use crate::routes::artists::ArtistDto;
+
+/// The bucket in the artist index for sort names that don't start with a letter
+pub const NON_LETTER_BUCKET: char = '#';
 
 /// Get release for artist
 ///
@@ -35,7 +40,7 @@ pub async fn get_releases_for_artist_service(
         ServerFnError::new("Could not retrieve artist, try again later")
     })?;
 
-    Release::list_by_artist_and_record_label(pool, artist.id, artist.label_id, include_hidden)
+    Release::list_by_artist_and_record_label(pool, artist.id, artist.label_id, include_hidden, None)
         .await
         .map_err(|x| {
             let err = format!(
@@ -47,6 +52,65 @@ pub async fn get_releases_for_artist_service(
         })
 }
 
+/// Get the published artists on a record label, grouped by the first letter of their sort name
+///
+/// Sort names that don't start with a letter are grouped under "#", which comes first.
+/// Artists are ordered by sort name within each group, so "The Beatles" is listed under B.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `label_id`: `i64` - The record label id
+///
+/// # Returns
+/// Result<Vec<(char, Vec<`ArtistDto`>)>, `ServerFnError`> - The groups, in alphabetical order
+///
+/// # Errors
+/// If the artists cannot be found, return an error
+pub async fn artist_index(
+    pool: &PgPool,
+    label_id: i64,
+) -> Result<Vec<(char, Vec<ArtistDto>)>, ServerFnError> {
+    let placeholders = PlaceholderImages::from_env();
+    let mut artists = Artist::list_by_record_label(pool, label_id, false, false)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting artists: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?
+        .into_iter()
+        .map(|artist| ArtistDto::new(artist, &placeholders))
+        .map(|artist| (index_letter(&artist.sort_name), artist))
+        .collect::<Vec<_>>();
+    artists.sort_by_cached_key(|(letter, artist)| {
+        (
+            *letter != NON_LETTER_BUCKET,
+            *letter,
+            artist.sort_name.to_lowercase(),
+        )
+    });
+
+    let mut index: Vec<(char, Vec<ArtistDto>)> = vec![];
+    for (letter, artist) in artists {
+        match index.last_mut() {
+            Some((last_letter, group)) if *last_letter == letter => group.push(artist),
+            _ => index.push((letter, vec![artist])),
+        }
+    }
+    Ok(index)
+}
+
+/// The letter a sort name is listed under in the artist index
+fn index_letter(sort_name: &str) -> char {
+    sort_name
+        .trim_start()
+        .chars()
+        .next()
+        .filter(|first| first.is_alphabetic())
+        .and_then(|first| first.to_uppercase().next())
+        .unwrap_or(NON_LETTER_BUCKET)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,6 +142,55 @@ mod tests {
         assert_eq!(releases[0].id, release.id);
     }
 
+    #[sqlx::test]
+    async fn test_artist_index(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        for (id, name) in (1..).zip(["The Beatles", "808 State", "Blur", "abba"]) {
+            let mut artist = create_test_artist(&pool, id, Some(record_label.clone()))
+                .await
+                .unwrap();
+            artist.name = name.to_string();
+            artist.update(&pool).await.unwrap();
+        }
+        let mut unpublished = create_test_artist(&pool, 5, Some(record_label.clone()))
+            .await
+            .unwrap();
+        unpublished.name = "Unreleased".to_string();
+        unpublished.published_at = None;
+        unpublished.update(&pool).await.unwrap();
+
+        let index = artist_index(&pool, record_label.id).await.unwrap();
+
+        let index = index
+            .iter()
+            .map(|(letter, artists)| {
+                (
+                    *letter,
+                    artists
+                        .iter()
+                        .map(|artist| artist.name.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            index,
+            vec![
+                ('#', vec!["808 State"]),
+                ('A', vec!["abba"]),
+                ('B', vec!["The Beatles", "Blur"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_letter() {
+        assert_eq!(index_letter("Beatles"), 'B');
+        assert_eq!(index_letter("\u{e9}milie"), '\u{c9}');
+        assert_eq!(index_letter("!!!"), '#');
+        assert_eq!(index_letter(""), '#');
+    }
+
     #[sqlx::test]
     async fn test_get_releases_for_artists_no_permission(pool: PgPool) {
         let permissions = vec![];
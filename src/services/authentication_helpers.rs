@@ -2,8 +2,21 @@
 //!
 //! This module contains helper functions for authentication
 //! It will make your life a lot easier for handling authentication and authorisation
+#[cfg(feature = "ssr")]
+use async_trait::async_trait;
+#[cfg(feature = "ssr")]
+use dashmap::DashMap;
 use leptos::prelude::ServerFnError;
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+#[cfg(feature = "ssr")]
+use std::sync::{LazyLock, Mutex};
+#[cfg(feature = "ssr")]
+use std::time::{Duration, Instant};
 
+use super::errors::ServiceError;
+#[cfg(feature = "ssr")]
+use crate::config::auth::{auth_attempt_window, auth_max_attempts, auth_max_attempts_per_ip};
 use crate::models::auth::User;
 
 /// Return a user with required permissions
@@ -23,16 +36,33 @@ pub fn user_with_permissions(
     user: Option<&User>,
     permissions: Vec<&str>,
 ) -> Result<User, ServerFnError> {
+    Ok(permitted_user(user, permissions)?)
+}
+
+/// Return a user with required permissions, as a `ServiceError`
+/// Callers that map errors to HTTP statuses, such as the JSON API, can tell a permission failure apart from other errors.
+///
+/// # Arguments
+/// user: Option<&User> - The user to check permissions for
+/// permissions: Vec<&str> - The permissions required
+///
+/// # Returns
+/// Result<User, `ServiceError`> - The user with the required permissions
+///
+/// # Errors
+/// If the user is not supplied, not authenticated or does not have the required permissions, return `ServiceError::Forbidden`
+pub fn permitted_user(user: Option<&User>, permissions: Vec<&str>) -> Result<User, ServiceError> {
+    let forbidden = |message: &str| ServiceError::Forbidden {
+        message: message.to_string(),
+    };
     let Some(user) = user else {
         tracing::error!("User not supplied");
-        return Err(ServerFnError::new("User not supplied."));
+        return Err(forbidden("User not supplied."));
     };
     // Check if the user is authenticated
     // If the user is not authenticated, return an error
     if !user.is_authenticated() {
-        return Err(ServerFnError::new(
-            "You must be logged in to view this page.",
-        ));
+        return Err(forbidden("You must be logged in to view this page."));
     }
 
     // Check if the user has the required permissions
@@ -40,17 +70,191 @@ pub fn user_with_permissions(
     for permission in permissions {
         if !user.permissions.contains(permission) {
             tracing::error!("User does not have the required permission {permission}");
-            return Err(ServerFnError::new("You do not have permission."));
+            return Err(forbidden("You do not have permission."));
         }
     }
 
     Ok(user.clone())
 }
 
+/// Return a user who can manage an artist
+/// Admins and label owners can manage every artist, other users only the artists they have been assigned.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user to check
+/// `artist_id`: i64 - The ID of the artist
+///
+/// # Returns
+/// Result<User, `ServerFnError`> - The user who can manage the artist
+///
+/// # Errors
+/// If the user is not authenticated, return an error
+/// If the user cannot manage the artist, return an error
+/// If the user is not supplied, return an error
+#[cfg(feature = "ssr")]
+pub async fn user_can_manage_artist(
+    pool: &PgPool,
+    user: Option<&User>,
+    artist_id: i64,
+) -> Result<User, ServerFnError> {
+    let user = user_with_permissions(user, vec![])?;
+
+    if !user.can_manage_artist(pool, artist_id).await {
+        tracing::error!("User {} cannot manage artist {artist_id}", user.id);
+        return Err(ServerFnError::new("You do not have permission."));
+    }
+
+    Ok(user)
+}
+
+/// Somewhere to count authentication attempts
+/// The in-memory store suits a single server; implement this to share counts between servers, e.g. in Redis.
+#[cfg(feature = "ssr")]
+#[async_trait]
+pub trait AttemptStore: Send + Sync {
+    /// Record an attempt for the key
+    /// Returns the number of attempts in the current window, including this one.
+    /// A new window starts with the first attempt after the last one ended.
+    async fn record_attempt(&self, key: &str, window: Duration) -> u32;
+
+    /// Forget the attempts for the key
+    async fn reset(&self, key: &str);
+}
+
+/// An in-memory store of authentication attempts, keyed by username, email address or client address
+/// Keys whose window has ended are pruned as new attempts are recorded, at most once per window.
+#[cfg(feature = "ssr")]
+#[derive(Debug)]
+pub struct MemoryAttemptStore {
+    attempts: DashMap<String, (Instant, u32)>,
+    last_pruned: Mutex<Instant>,
+}
+
+#[cfg(feature = "ssr")]
+impl Default for MemoryAttemptStore {
+    fn default() -> Self {
+        Self {
+            attempts: DashMap::new(),
+            last_pruned: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl MemoryAttemptStore {
+    /// Forget the keys whose window has ended, unless they were pruned less than a window ago
+    fn prune(&self, window: Duration) {
+        let Ok(mut last_pruned) = self.last_pruned.try_lock() else {
+            // Another attempt is already pruning
+            return;
+        };
+        if last_pruned.elapsed() < window {
+            return;
+        }
+        *last_pruned = Instant::now();
+        self.attempts
+            .retain(|_, (started, _)| started.elapsed() < window);
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[async_trait]
+impl AttemptStore for MemoryAttemptStore {
+    async fn record_attempt(&self, key: &str, window: Duration) -> u32 {
+        self.prune(window);
+        let mut entry = self
+            .attempts
+            .entry(key.to_string())
+            .or_insert_with(|| (Instant::now(), 0));
+        if entry.0.elapsed() >= window {
+            *entry = (Instant::now(), 0);
+        }
+        entry.1 += 1;
+        entry.1
+    }
+
+    async fn reset(&self, key: &str) {
+        self.attempts.remove(key);
+    }
+}
+
+/// Limit the number of authentication attempts for a key in a window
+#[cfg(feature = "ssr")]
+pub struct RateLimiter<S: AttemptStore> {
+    store: S,
+    max_attempts: u32,
+    window: Duration,
+}
+
+#[cfg(feature = "ssr")]
+impl<S: AttemptStore> RateLimiter<S> {
+    /// Create a rate limiter that allows `max_attempts` attempts per `window`
+    pub const fn new(store: S, max_attempts: u32, window: Duration) -> Self {
+        Self {
+            store,
+            max_attempts,
+            window,
+        }
+    }
+
+    /// Record an attempt for the key
+    ///
+    /// # Errors
+    /// If there have been more than the allowed attempts in the window, return an error
+    pub async fn check(&self, key: &str) -> Result<(), ServerFnError> {
+        let attempts = self.store.record_attempt(key, self.window).await;
+        if attempts > self.max_attempts {
+            // The key holds a username, email or client address, so it is left out of the logs
+            tracing::warn!("Too many authentication attempts.");
+            return Err(ServerFnError::new(
+                "Too many attempts. Please try again later.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start counting attempts for the key again, e.g. after a successful login
+    pub async fn reset(&self, key: &str) {
+        self.store.reset(key).await;
+    }
+}
+
+/// The rate limiter for logins and password resets.
+/// Allows `AUTH_MAX_ATTEMPTS` attempts per `AUTH_ATTEMPT_WINDOW_MINUTES`.
+#[cfg(feature = "ssr")]
+pub static AUTH_RATE_LIMITER: LazyLock<RateLimiter<MemoryAttemptStore>> = LazyLock::new(|| {
+    RateLimiter::new(
+        MemoryAttemptStore::default(),
+        auth_max_attempts(),
+        auth_attempt_window(),
+    )
+});
+
+/// The rate limiter for logins and password resets from one client address.
+/// Allows `AUTH_MAX_ATTEMPTS_PER_IP` attempts per `AUTH_ATTEMPT_WINDOW_MINUTES`, across every account.
+#[cfg(feature = "ssr")]
+pub static AUTH_IP_RATE_LIMITER: LazyLock<RateLimiter<MemoryAttemptStore>> = LazyLock::new(|| {
+    RateLimiter::new(
+        MemoryAttemptStore::default(),
+        auth_max_attempts_per_ip(),
+        auth_attempt_window(),
+    )
+});
+
+/// The rate limiter key for an action, e.g. "login", and the username, email address or client address it was attempted for
+#[must_use]
+pub fn rate_limit_key(action: &str, identifier: &str) -> String {
+    format!("{action}:{}", identifier.trim().to_lowercase())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{auth::User, test_helpers::create_test_user_with_permissions};
+    use crate::models::{
+        auth::User,
+        test_helpers::{create_test_artist, create_test_user_with_permissions},
+    };
     use leptos::prelude::ServerFnError;
     use sqlx::PgPool;
 
@@ -111,4 +315,128 @@ mod tests {
             Err(ServerFnError::new("You do not have permission."))
         );
     }
+
+    #[sqlx::test]
+    fn test_permitted_user_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = permitted_user(Some(&user), vec!["label_owner"]);
+        assert_eq!(
+            result,
+            Err(ServiceError::Forbidden {
+                message: "You do not have permission.".to_string()
+            })
+        );
+    }
+
+    #[sqlx::test]
+    fn test_user_can_manage_artist(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+        let result = user_can_manage_artist(&pool, Some(&manager), artist.id).await;
+        assert_eq!(result, Ok(manager.clone()));
+
+        let result = user_can_manage_artist(&pool, Some(&manager), other_artist.id).await;
+        assert_eq!(
+            result,
+            Err(ServerFnError::new("You do not have permission."))
+        );
+    }
+
+    #[sqlx::test]
+    fn test_user_can_manage_artist_not_authenticated(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = user_can_manage_artist(&pool, Some(&User::default()), artist.id).await;
+        assert_eq!(
+            result,
+            Err(ServerFnError::new(
+                "You must be logged in to view this page."
+            ))
+        );
+        let result = user_can_manage_artist(&pool, None, artist.id).await;
+        assert_eq!(result, Err(ServerFnError::new("User not supplied.")));
+    }
+
+    #[test]
+    fn test_rate_limit_key() {
+        assert_eq!(rate_limit_key("login", " Test1 "), "login:test1");
+        assert_eq!(
+            rate_limit_key("password_reset", "Test@Example.com"),
+            "password_reset:test@example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_blocks_sixth_attempt() {
+        let limiter = RateLimiter::new(
+            MemoryAttemptStore::default(),
+            5,
+            Duration::from_mins(15),
+        );
+
+        for _ in 0..5 {
+            assert!(limiter.check("login:test").await.is_ok());
+        }
+        assert_eq!(
+            limiter.check("login:test").await,
+            Err(ServerFnError::new(
+                "Too many attempts. Please try again later."
+            ))
+        );
+        // Other keys are counted separately
+        assert!(limiter.check("login:other").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_reset() {
+        let limiter = RateLimiter::new(
+            MemoryAttemptStore::default(),
+            5,
+            Duration::from_mins(15),
+        );
+
+        for _ in 0..5 {
+            assert!(limiter.check("login:test").await.is_ok());
+        }
+        limiter.reset("login:test").await;
+
+        for _ in 0..5 {
+            assert!(limiter.check("login:test").await.is_ok());
+        }
+        assert!(limiter.check("login:test").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_memory_attempt_store_prunes_expired_keys() {
+        let store = MemoryAttemptStore::default();
+        let window = Duration::from_millis(50);
+
+        store.record_attempt("login:first", window).await;
+        store.record_attempt("login:second", window).await;
+        assert_eq!(store.attempts.len(), 2);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(store.record_attempt("login:third", window).await, 1);
+        assert_eq!(store.attempts.len(), 1);
+        assert!(store.attempts.contains_key("login:third"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_window_expires() {
+        let limiter = RateLimiter::new(MemoryAttemptStore::default(), 1, Duration::from_millis(50));
+
+        assert!(limiter.check("login:test").await.is_ok());
+        assert!(limiter.check("login:test").await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(limiter.check("login:test").await.is_ok());
+    }
 }
@@ -0,0 +1,144 @@
+//! Services for the label owner dashboard
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::authentication_helpers::user_with_permissions;
+use super::errors::fetch_or_not_found;
+use crate::models::{
+    artist::Artist, auth::User, page::Page, record_label::RecordLabel, release::Release,
+};
+use crate::routes::dashboard::MyContent;
+
+/// Get the artists, releases and pages the user can edit, including drafts
+///
+/// The site serves a single record label, so this is the content on that label.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `user` - The current user
+///
+/// # Returns
+/// The content, most recently updated first
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the record label cannot be found, return an error
+/// If the content cannot be found, return an error
+pub async fn my_content(pool: &PgPool, user: Option<&User>) -> Result<MyContent, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let record_label = fetch_or_not_found(
+        RecordLabel::first(pool).await,
+        "Error while getting record label",
+    )?;
+
+    let artists = Artist::list_recently_updated(pool, record_label.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting artists: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let releases = Release::list_recently_updated(pool, record_label.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let pages = Page::list_recently_updated(pool, record_label.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting pages: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(MyContent {
+        artists,
+        releases,
+        pages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_page, create_test_record_label, create_test_release,
+        create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_my_content(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let other_record_label = create_test_record_label(&pool, 2).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut draft_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        draft_release.published_at = None;
+        let draft_release = draft_release.update(&pool).await.unwrap();
+        let page = create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let other_artist = create_test_artist(&pool, 2, Some(other_record_label.clone()))
+            .await
+            .unwrap();
+        create_test_release(&pool, 2, Some(other_artist))
+            .await
+            .unwrap();
+        create_test_page(&pool, 2, Some(other_record_label))
+            .await
+            .unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let content = my_content(&pool, Some(&user)).await.unwrap();
+
+        assert_eq!(content.artists, vec![artist]);
+        assert_eq!(content.releases, vec![draft_release]);
+        assert_eq!(content.pages, vec![page]);
+    }
+
+    #[sqlx::test]
+    async fn test_my_content_ordered_by_updated_at(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release1 = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let release2 = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        let release1 = release1.update(&pool).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let content = my_content(&pool, Some(&user)).await.unwrap();
+
+        assert_eq!(content.releases, vec![release1, release2]);
+    }
+
+    #[sqlx::test]
+    async fn test_my_content_no_permission(pool: PgPool) {
+        create_test_record_label(&pool, 1).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = my_content(&pool, Some(&user)).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! New release digest service module
+//!
+//! Builds and sends the periodic email of new releases to a record label's subscribers.
+//! Each release is only ever sent in one digest.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::errors::fetch_or_not_found;
+use super::mailer::{Email, Mailer, mailer};
+use crate::config::digest::release_digest_interval_days;
+use crate::models::{
+    artist::Artist, record_label::RecordLabel, release::Release, subscriber::Subscriber,
+};
+use crate::utils::{urls::release_url, xml::escape_xml};
+
+/// Render the digest HTML for a list of releases
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: &`RecordLabel` - The record label the digest is for
+/// releases: &[Release] - The releases to include
+///
+/// # Returns
+/// Result<String, `ServerFnError`> - The digest HTML
+///
+/// # Errors
+/// If an artist cannot be found, return an error
+async fn render_digest(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+    releases: &[Release],
+) -> Result<String, ServerFnError> {
+    let mut lines = vec![
+        format!(
+            "<h1>New releases from {}</h1>",
+            escape_xml(&record_label.name)
+        ),
+        "<ul>".to_string(),
+    ];
+    for release in releases {
+        let artist = fetch_or_not_found(
+            Artist::get_by_id(pool, release.primary_artist_id).await,
+            "Error while getting artist",
+        )?;
+        lines.push(format!(
+            "  <li><a href=\"{}\">{}</a> by {}</li>",
+            escape_xml(&release_url(&artist.slug, &release.slug)),
+            escape_xml(&release.name),
+            escape_xml(&artist.name)
+        ));
+    }
+    lines.push("</ul>".to_string());
+
+    Ok(lines.join("\n"))
+}
+
+/// Build the new release digest for a record label
+///
+/// Only public releases published after `since`, that haven't already been sent in a digest, are included.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `label_id`: i64 - The ID of the record label
+/// since: `DateTime<Utc>` - Only include releases published after this time
+///
+/// # Returns
+/// Result<String, `ServerFnError`> - The digest HTML
+///
+/// # Errors
+/// If the record label cannot be found, return an error
+/// If the releases cannot be found, return an error
+pub async fn build_release_digest(
+    pool: &PgPool,
+    label_id: i64,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<String, ServerFnError> {
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id(pool, label_id).await,
+        "Error while getting record label",
+    )?;
+    let releases = Release::list_for_digest(pool, label_id, since)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    render_digest(pool, &record_label, &releases).await
+}
+
+/// Send the new release digest to a record label's subscribers
+///
+/// The releases in the digest are marked as sent once it has been delivered to at least one subscriber,
+/// so they are not included in the next one. If every delivery fails they are left for the next digest.
+/// Nothing is sent if there are no new releases or no subscribers.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// mailer: &dyn `Mailer` - Where to send the digest
+/// `label_id`: i64 - The ID of the record label
+/// since: `DateTime<Utc>` - Only include releases published after this time
+///
+/// # Returns
+/// Result<usize, `ServerFnError`> - The number of subscribers the digest was delivered to
+///
+/// # Errors
+/// If the record label cannot be found, return an error
+/// If the releases or subscribers cannot be found, return an error
+/// If the releases cannot be marked as sent, return an error
+pub async fn send_release_digest(
+    pool: &PgPool,
+    mailer: &dyn Mailer,
+    label_id: i64,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<usize, ServerFnError> {
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id(pool, label_id).await,
+        "Error while getting record label",
+    )?;
+    let releases = Release::list_for_digest(pool, label_id, since)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    if releases.is_empty() {
+        return Ok(0);
+    }
+    let subscribers = Subscriber::list_by_record_label(pool, label_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting subscribers: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    if subscribers.is_empty() {
+        return Ok(0);
+    }
+
+    let digest = render_digest(pool, &record_label, &releases).await?;
+    let subject = format!("New releases from {}", record_label.name);
+    let mut sent = 0;
+    for subscriber in &subscribers {
+        let email = Email {
+            to: subscriber.email.clone(),
+            subject: subject.clone(),
+            body: digest.clone(),
+        };
+        match mailer.send(&email).await {
+            Ok(()) => sent += 1,
+            Err(e) => tracing::warn!(
+                "Could not send the release digest to subscriber {}: {e}",
+                subscriber.id
+            ),
+        }
+    }
+    if sent == 0 {
+        tracing::error!(
+            "The release digest could not be sent to any of {} subscribers.",
+            subscribers.len()
+        );
+        return Ok(0);
+    }
+
+    let release_ids = releases
+        .iter()
+        .map(|release| release.id)
+        .collect::<Vec<i64>>();
+    Release::mark_digest_sent(pool, &release_ids)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while marking releases as sent: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(sent)
+}
+
+/// Send the new release digest on a schedule
+/// Runs forever, sending the digest every `RELEASE_DIGEST_INTERVAL_DAYS` days through the installed mailer.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+pub async fn schedule_release_digests(pool: PgPool) {
+    let interval_days = release_digest_interval_days();
+    let Ok(period) = chrono::Duration::days(interval_days).to_std() else {
+        tracing::error!("Invalid release digest interval of {interval_days} days.");
+        return;
+    };
+    let mut interval = tokio::time::interval(period);
+    // The first tick completes immediately, wait a full period before the first digest
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        let since = chrono::Utc::now() - chrono::Duration::days(interval_days);
+        let record_label = match RecordLabel::first(&pool).await {
+            Ok(record_label) => record_label,
+            Err(e) => {
+                tracing::error!("Error while getting record label: {e:?}");
+                continue;
+            }
+        };
+        match send_release_digest(&pool, mailer().as_ref(), record_label.id, since).await {
+            Ok(sent) => tracing::info!("Sent the release digest to {sent} subscribers."),
+            Err(e) => tracing::error!("Error while sending the release digest: {e:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release,
+    };
+    use crate::services::mailer::{DisabledMailer, MemoryMailer};
+
+    #[sqlx::test]
+    async fn test_build_release_digest(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut old_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        old_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(10));
+        let old_release = old_release.update(&pool).await.unwrap();
+        let mut new_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        new_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let new_release = new_release.update(&pool).await.unwrap();
+
+        let since = chrono::Utc::now() - chrono::Duration::days(7);
+        let digest = build_release_digest(&pool, record_label.id, since)
+            .await
+            .unwrap();
+
+        assert!(digest.contains(&format!("<h1>New releases from {}</h1>", record_label.name)));
+        assert!(digest.contains(&new_release.name));
+        assert!(digest.contains(&release_url(&artist.slug, &new_release.slug)));
+        assert!(!digest.contains(&old_release.name));
+    }
+
+    #[sqlx::test]
+    async fn test_send_release_digest_excludes_sent_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        Subscriber::create(&pool, record_label.id, "fan@example.com".to_string())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut sent_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        sent_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(2));
+        let sent_release = sent_release.update(&pool).await.unwrap();
+        let since = chrono::Utc::now() - chrono::Duration::days(7);
+
+        let mailer = MemoryMailer::default();
+        let sent = send_release_digest(&pool, &mailer, record_label.id, since)
+            .await
+            .unwrap();
+        assert_eq!(sent, 1);
+        let emails = mailer.sent();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].to, "fan@example.com");
+        assert!(emails[0].body.contains(&sent_release.name));
+
+        let mut new_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        new_release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let new_release = new_release.update(&pool).await.unwrap();
+
+        let digest = build_release_digest(&pool, record_label.id, since)
+            .await
+            .unwrap();
+
+        assert!(digest.contains(&new_release.name));
+        assert!(!digest.contains(&sent_release.name));
+    }
+
+    #[sqlx::test]
+    async fn test_send_release_digest_no_new_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        Subscriber::create(&pool, record_label.id, "fan@example.com".to_string())
+            .await
+            .unwrap();
+
+        let mailer = MemoryMailer::default();
+        let sent = send_release_digest(&pool, &mailer, record_label.id, chrono::Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 0);
+        assert!(mailer.sent().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_send_release_digest_delivery_fails(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        Subscriber::create(&pool, record_label.id, "fan@example.com".to_string())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+        let since = chrono::Utc::now() - chrono::Duration::days(7);
+
+        let sent = send_release_digest(&pool, &DisabledMailer, record_label.id, since)
+            .await
+            .unwrap();
+        assert_eq!(sent, 0);
+
+        // The release wasn't delivered, so it is still in the next digest
+        let digest = build_release_digest(&pool, record_label.id, since)
+            .await
+            .unwrap();
+        assert!(digest.contains(&release.name));
+    }
+
+    #[sqlx::test]
+    async fn test_send_release_digest_partial_delivery(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        Subscriber::create(&pool, record_label.id, "fan@example.com".to_string())
+            .await
+            .unwrap();
+        Subscriber::create(&pool, record_label.id, "bounce@example.com".to_string())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+        let since = chrono::Utc::now() - chrono::Duration::days(7);
+
+        let mailer = MemoryMailer::refusing(vec!["bounce@example.com".to_string()]);
+        let sent = send_release_digest(&pool, &mailer, record_label.id, since)
+            .await
+            .unwrap();
+        assert_eq!(sent, 1);
+        assert_eq!(mailer.sent()[0].to, "fan@example.com");
+
+        let digest = build_release_digest(&pool, record_label.id, since)
+            .await
+            .unwrap();
+        assert!(!digest.contains(&release.name));
+    }
+}
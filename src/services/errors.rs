@@ -0,0 +1,99 @@
+//! Service errors
+//!
+//! Shared error types and helpers for the service layer.
+//! `ServiceError` implements `std::error::Error`, so it converts into a `ServerFnError` with `?`.
+
+/// Errors returned by the service layer
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum ServiceError {
+    /// The requested item could not be found
+    #[error("{message}")]
+    NotFound {
+        /// What was being fetched, e.g. "Error while getting release"
+        context: String,
+        /// The message shown to the user
+        message: String,
+    },
+    /// The user is not signed in, or doesn't have the required permissions
+    #[error("{message}")]
+    Forbidden {
+        /// The message shown to the user
+        message: String,
+    },
+    /// The request took longer than the request timeout
+    #[error("The request timed out.")]
+    TimedOut,
+    /// Anything else, such as the database not being readable
+    #[error("{message}")]
+    Internal {
+        /// The message shown to the user
+        message: String,
+    },
+}
+
+/// Unwrap the result of fetching an item, or log the error and return `ServiceError::NotFound`
+///
+/// # Arguments
+/// result: `Result<T, E>` - The result of fetching the item, with an `anyhow` or model error
+/// context: &str - What was being fetched, used when logging the error
+///
+/// # Returns
+/// Result<T, `ServiceError`> - The fetched item
+///
+/// # Errors
+/// If the result is an error, return `ServiceError::NotFound` with the error's message
+pub fn fetch_or_not_found<T, E>(result: Result<T, E>, context: &str) -> Result<T, ServiceError>
+where
+    E: std::fmt::Display + std::fmt::Debug,
+{
+    result.map_err(|e| {
+        tracing::error!("{context}: {e:?}");
+        ServiceError::NotFound {
+            context: context.to_string(),
+            message: e.to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::prelude::ServerFnError;
+
+    #[test]
+    fn test_fetch_or_not_found_ok() {
+        let result = fetch_or_not_found(anyhow::Ok(42), "Error while getting answer");
+
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn test_fetch_or_not_found_err() {
+        let result: Result<i64, ServiceError> = fetch_or_not_found(
+            Err(anyhow::anyhow!("Could not find release with slug missing.")),
+            "Error while getting release",
+        );
+
+        assert_eq!(
+            result,
+            Err(ServiceError::NotFound {
+                context: "Error while getting release".to_string(),
+                message: "Could not find release with slug missing.".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_found_into_server_fn_error() {
+        let error: ServerFnError = ServiceError::NotFound {
+            context: "Error while getting release".to_string(),
+            message: "Could not find release with slug missing.".to_string(),
+        }
+        .into();
+
+        assert_eq!(
+            error.to_string(),
+            "error running server function: Could not find release with slug missing."
+        );
+    }
+}
@@ -0,0 +1,530 @@
+//! Export service module
+//!
+//! Produces release metadata in formats that distributors, calendar apps and feed readers can ingest.
+
+use leptos::prelude::ServerFnError;
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+
+#[cfg(feature = "ssr")]
+use crate::config::site::{RELEASE_FEED_LENGTH, base_url};
+#[cfg(feature = "ssr")]
+use crate::models::{artist::Artist, record_label::RecordLabel, release::Release};
+#[cfg(feature = "ssr")]
+use crate::state::RecordLabelCache;
+#[cfg(feature = "ssr")]
+use crate::utils::{
+    ical::{escape_ical_text, fold_line},
+    urls::{absolute_url, release_url},
+    xml::escape_xml,
+};
+
+/// Is the item published and not deleted
+#[cfg(feature = "ssr")]
+fn is_public(
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> bool {
+    deleted_at.is_none()
+        && published_at.is_some_and(|published_at| published_at <= chrono::Utc::now())
+}
+
+/// Export a release as DDEX-lite XML
+///
+/// This is a minimal subset of a DDEX `NewReleaseMessage`, containing the key identifiers for the release and its tracks.
+/// Only published releases and tracks are exported.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// cache: `RecordLabelCache` - The record label cache
+/// `release_id`: i64 - The ID of the release to export
+///
+/// # Returns
+/// Result<String, `ServerFnError`> - The XML document
+///
+/// # Errors
+/// If the release cannot be found or is not published, return an error
+/// If the artists or tracks cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn release_ddex(
+    pool: &PgPool,
+    cache: &RecordLabelCache,
+    release_id: i64,
+) -> Result<String, ServerFnError> {
+    let release = Release::get_by_id(pool, release_id).await.map_err(|e| {
+        let err = format!("Error while getting release: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    if !is_public(release.published_at, release.deleted_at) {
+        return Err(ServerFnError::new(format!(
+            "Release with id {release_id} is not published."
+        )));
+    }
+
+    let artist = Artist::get_by_id(pool, release.primary_artist_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting artist: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let record_label = RecordLabel::get_by_id_cached(pool, cache, release.label_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting record label: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let tracks = release.get_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        "<NewReleaseMessage>".to_string(),
+        "  <Release>".to_string(),
+        "    <ReleaseId>".to_string(),
+        format!(
+            "      <CatalogNumber>{}</CatalogNumber>",
+            escape_xml(&release.catalogue_number)
+        ),
+        "    </ReleaseId>".to_string(),
+        format!("    <Title>{}</Title>", escape_xml(&release.name)),
+        format!(
+            "    <DisplayArtistName>{}</DisplayArtistName>",
+            escape_xml(&artist.name)
+        ),
+        format!(
+            "    <LabelName>{}</LabelName>",
+            escape_xml(&record_label.name)
+        ),
+    ];
+    if let Some(release_date) = release.release_date {
+        lines.push(format!(
+            "    <ReleaseDate>{}</ReleaseDate>",
+            release_date.format("%Y-%m-%d")
+        ));
+    }
+    lines.push("  </Release>".to_string());
+
+    lines.push("  <SoundRecordingList>".to_string());
+    for track_with_artists in tracks
+        .iter()
+        .filter(|t| is_public(t.track.published_at, t.track.deleted_at))
+    {
+        let track = &track_with_artists.track;
+        let artist_names = track_with_artists
+            .artists
+            .iter()
+            .map(|artist| artist.name.clone())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        lines.push("    <SoundRecording>".to_string());
+        lines.push(format!(
+            "      <SequenceNumber>{}</SequenceNumber>",
+            track.track_number
+        ));
+        if let Some(isrc_code) = &track.isrc_code {
+            lines.push(format!("      <ISRC>{}</ISRC>", escape_xml(isrc_code)));
+        }
+        lines.push(format!("      <Title>{}</Title>", escape_xml(&track.name)));
+        lines.push(format!(
+            "      <DisplayArtistName>{}</DisplayArtistName>",
+            escape_xml(&artist_names)
+        ));
+        lines.push("    </SoundRecording>".to_string());
+    }
+    lines.push("  </SoundRecordingList>".to_string());
+    lines.push("</NewReleaseMessage>".to_string());
+
+    let xml = lines.join("\n");
+    Ok(xml)
+}
+
+/// Export a record label's scheduled releases as an iCalendar feed
+///
+/// Each release is an all-day event on its release date, so fans can subscribe to upcoming releases.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: `RecordLabel` - The record label whose releases to export
+///
+/// # Returns
+/// Result<String, `ServerFnError`> - The iCalendar document
+///
+/// # Errors
+/// If the releases or their primary artists cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn release_calendar(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+) -> Result<String, ServerFnError> {
+    let releases = Release::list_scheduled_releases(pool, record_label.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting scheduled releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    let host = url::Url::parse(&base_url())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "localhost".to_string());
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!(
+            "PRODID:-//{}//Releases//EN",
+            escape_ical_text(&record_label.name)
+        ),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!(
+            "X-WR-CALNAME:{}",
+            escape_ical_text(&format!("{} releases", record_label.name))
+        ),
+    ];
+    for release in releases {
+        let Some(release_date) = release.release_date else {
+            continue;
+        };
+        let artist = Artist::get_by_id(pool, release.primary_artist_id)
+            .await
+            .map_err(|e| {
+                let err = format!("Error while getting artist: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?;
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:release-{}@{host}", release.id));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!(
+            "DTSTART;VALUE=DATE:{}",
+            release_date.format("%Y%m%d")
+        ));
+        lines.push(format!(
+            "SUMMARY:{}",
+            escape_ical_text(&format!("{} - {}", artist.name, release.name))
+        ));
+        lines.push(format!("URL:{}", release_url(&artist.slug, &release.slug)));
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = String::new();
+    for line in lines {
+        calendar.push_str(&fold_line(&line));
+        calendar.push_str("\r\n");
+    }
+    Ok(calendar)
+}
+
+/// Export the record label's latest releases as an RSS 2.0 feed
+///
+/// The most recent `RELEASE_FEED_LENGTH` published releases are listed, newest first.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: &`RecordLabel` - The record label to list the releases of
+/// `feed_url`: &str - The absolute URL the feed is served from
+///
+/// # Returns
+/// Result<String, `ServerFnError`> - The RSS document
+///
+/// # Errors
+/// If the releases or their primary artists cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn release_feed(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+    feed_url: &str,
+) -> Result<String, ServerFnError> {
+    let releases = Release::list_latest(pool, record_label.id, RELEASE_FEED_LENGTH, false)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting latest releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">".to_string(),
+        "  <channel>".to_string(),
+        format!(
+            "    <title>{}</title>",
+            escape_xml(&format!("{} releases", record_label.name))
+        ),
+        format!(
+            "    <link>{}</link>",
+            escape_xml(&absolute_url(&base_url(), "/"))
+        ),
+        format!(
+            "    <description>{}</description>",
+            escape_xml(&record_label.description)
+        ),
+        format!(
+            "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\" />",
+            escape_xml(feed_url)
+        ),
+    ];
+    for release in releases {
+        let artist = Artist::get_by_id(pool, release.primary_artist_id)
+            .await
+            .map_err(|e| {
+                let err = format!("Error while getting artist: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?;
+        let link = escape_xml(&release_url(&artist.slug, &release.slug));
+
+        lines.push("    <item>".to_string());
+        lines.push(format!(
+            "      <title>{}</title>",
+            escape_xml(&format!("{} \u{2013} {}", artist.name, release.name))
+        ));
+        lines.push(format!("      <link>{link}</link>"));
+        lines.push(format!("      <guid isPermaLink=\"true\">{link}</guid>"));
+        if let Some(release_date) = release.release_date {
+            lines.push(format!(
+                "      <pubDate>{}</pubDate>",
+                release_date.to_rfc2822()
+            ));
+        }
+        lines.push(format!(
+            "      <description>{}</description>",
+            escape_xml(&release.description)
+        ));
+        lines.push("    </item>".to_string());
+    }
+    lines.push("  </channel>".to_string());
+    lines.push("</rss>".to_string());
+
+    let xml = lines.join("\n");
+    Ok(xml)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release, create_test_track,
+    };
+
+    /// Check every opening tag has a matching closing tag, in order.
+    fn assert_well_formed(xml: &str) {
+        let mut stack: Vec<&str> = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').unwrap() + start;
+            let tag = &rest[start + 1..end];
+            if tag.starts_with('?') || tag.ends_with('/') {
+                // XML declaration or self-closing tag
+            } else if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop(), Some(name), "Unexpected closing tag {name}");
+            } else {
+                stack.push(tag.split_whitespace().next().unwrap_or(tag));
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(stack.is_empty(), "Unclosed tags {stack:?}");
+    }
+
+    #[sqlx::test]
+    async fn test_release_ddex(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track1 = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let track2 = create_test_track(&pool, 2, Some(release.clone()), None)
+            .await
+            .unwrap();
+
+        let xml = release_ddex(&pool, &RecordLabelCache::default(), release.id)
+            .await
+            .unwrap();
+
+        assert_well_formed(&xml);
+        assert!(xml.contains("<CatalogNumber>TEST-1</CatalogNumber>"));
+        assert!(xml.contains("<Title>Test Release 1</Title>"));
+        assert!(xml.contains(&format!(
+            "<ISRC>{}</ISRC>",
+            track1.isrc_code.clone().unwrap()
+        )));
+        assert!(xml.contains(&format!(
+            "<ISRC>{}</ISRC>",
+            track2.isrc_code.clone().unwrap()
+        )));
+        assert!(xml.contains("<SequenceNumber>2</SequenceNumber>"));
+    }
+
+    #[sqlx::test]
+    async fn test_release_ddex_excludes_unpublished_tracks(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let mut unpublished_track = create_test_track(&pool, 2, Some(release.clone()), None)
+            .await
+            .unwrap();
+        unpublished_track.published_at = None;
+        let unpublished_track = unpublished_track.update(&pool).await.unwrap();
+        let deleted_track = create_test_track(&pool, 3, Some(release.clone()), None)
+            .await
+            .unwrap();
+        deleted_track.delete(&pool).await.unwrap();
+
+        let xml = release_ddex(&pool, &RecordLabelCache::default(), release.id)
+            .await
+            .unwrap();
+
+        assert_well_formed(&xml);
+        assert_eq!(xml.matches("<SoundRecording>").count(), 1);
+        assert!(!xml.contains(&unpublished_track.isrc_code.unwrap()));
+        assert!(!xml.contains(&deleted_track.isrc_code.unwrap()));
+    }
+
+    #[sqlx::test]
+    async fn test_release_ddex_escapes_text(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.name = "Rock & Roll".to_string();
+        let release = release.update(&pool).await.unwrap();
+
+        let xml = release_ddex(&pool, &RecordLabelCache::default(), release.id)
+            .await
+            .unwrap();
+
+        assert_well_formed(&xml);
+        assert!(xml.contains("<Title>Rock &amp; Roll</Title>"));
+    }
+
+    #[sqlx::test]
+    async fn test_release_ddex_unpublished_release(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+
+        let result = release_ddex(&pool, &RecordLabelCache::default(), release.id).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "error running server function: Release with id {} is not published.",
+                release.id
+            )
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_release_calendar(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let release_date = chrono::Utc::now() + chrono::Duration::days(7);
+        release.release_date = Some(release_date);
+        release.name = "Live, Loud; Proud".to_string();
+        let release = release.update(&pool).await.unwrap();
+        create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let calendar = release_calendar(&pool, &record_label).await.unwrap();
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 1);
+        assert!(calendar.contains(&format!("UID:release-{}@", release.id)));
+        assert!(calendar.contains(&format!(
+            "DTSTART;VALUE=DATE:{}\r\n",
+            release_date.format("%Y%m%d")
+        )));
+        assert!(calendar.contains(&format!(
+            "SUMMARY:{} - Live\\, Loud\\; Proud\r\n",
+            artist.name
+        )));
+        assert!(calendar.contains(&format!("/artists/{}/{}", artist.slug, release.slug)));
+    }
+
+    #[sqlx::test]
+    async fn test_release_calendar_no_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let calendar = release_calendar(&pool, &record_label).await.unwrap();
+
+        assert!(!calendar.contains("BEGIN:VEVENT"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[sqlx::test]
+    async fn test_release_feed(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut older_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        older_release.release_date = Some(chrono::Utc::now() - chrono::Duration::days(7));
+        older_release.name = "Rock & Roll".to_string();
+        let older_release = older_release.update(&pool).await.unwrap();
+        let newer_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.update(&pool).await.unwrap();
+
+        let feed = release_feed(&pool, &record_label, "https://example.com/releases.rss")
+            .await
+            .unwrap();
+
+        assert_well_formed(&feed);
+        assert!(feed.contains("<rss version=\"2.0\""));
+        assert!(feed.contains("href=\"https://example.com/releases.rss\""));
+        assert_eq!(feed.matches("<item>").count(), 2);
+        let newer_title = format!(
+            "<title>{} \u{2013} {}</title>",
+            artist.name, newer_release.name
+        );
+        let older_title = format!("<title>{} \u{2013} Rock &amp; Roll</title>", artist.name);
+        assert!(feed.find(&newer_title).unwrap() < feed.find(&older_title).unwrap());
+        assert!(feed.contains(&format!(
+            "<link>{}</link>",
+            release_url(&artist.slug, &older_release.slug)
+        )));
+        assert!(feed.contains(&format!(
+            "<pubDate>{}</pubDate>",
+            older_release.release_date.unwrap().to_rfc2822()
+        )));
+        assert!(feed.contains(&format!(
+            "<description>{}</description>",
+            older_release.description
+        )));
+    }
+
+    #[sqlx::test]
+    async fn test_release_feed_no_releases(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let feed = release_feed(&pool, &record_label, "https://example.com/releases.rss")
+            .await
+            .unwrap();
+
+        assert_well_formed(&feed);
+        assert!(!feed.contains("<item>"));
+    }
+}
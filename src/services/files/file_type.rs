@@ -0,0 +1,173 @@
+//! File type checks for uploads.
+//!
+//! The content type and file name sent with an upload are chosen by the client, so they can't be trusted.
+//! The first bytes of the file are checked to find out what it really is.
+
+use leptos::prelude::ServerFnError;
+
+use crate::utils::files::file_extension;
+
+/// The number of bytes needed to recognise a file type.
+pub const SNIFF_LENGTH: usize = 12;
+
+/// Recognise an image from the first bytes of the file.
+///
+/// # Arguments
+/// * `bytes`: The start of the file, at least `SNIFF_LENGTH` bytes if the file is that long.
+///
+/// # Returns
+/// * `Some(mime_type)`: If the bytes start a JPEG, PNG, GIF or WebP image.
+/// * `None`: If the file type is not recognised.
+#[must_use]
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= SNIFF_LENGTH && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP"
+    {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// The file extensions used for a mime type.
+#[must_use]
+pub fn extensions_for_mime_type(mime_type: &str) -> &'static [&'static str] {
+    match mime_type {
+        "image/jpeg" => &["jpg", "jpeg"],
+        "image/png" => &["png"],
+        "image/gif" => &["gif"],
+        "image/webp" => &["webp"],
+        _ => &[],
+    }
+}
+
+/// Check a file name has an extension used by one of the allowed types.
+/// Stored files keep their extension, so an image named `shell.php` must be refused even though its content is valid.
+///
+/// # Arguments
+/// * `file_name`: The name the file was uploaded with.
+/// * `mime_types`: The allowed mime types.
+///
+/// # Errors
+/// * `ServerFnError`: If the extension isn't used by any of the allowed types.
+pub fn check_file_extension(file_name: &str, mime_types: &[String]) -> Result<(), ServerFnError> {
+    let extension = file_extension(file_name).unwrap_or_default().to_lowercase();
+    let allowed = mime_types
+        .iter()
+        .any(|mime_type| extensions_for_mime_type(mime_type).contains(&extension.as_str()));
+    if !allowed {
+        return Err(ServerFnError::new(format!(
+            "Files with the extension {extension:?} are not allowed. Allowed types are {}.",
+            mime_types.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Check the content of a file is one of the allowed types.
+///
+/// # Arguments
+/// * `bytes`: The start of the file, at least `SNIFF_LENGTH` bytes if the file is that long.
+/// * `mime_types`: The allowed mime types.
+///
+/// # Returns
+/// * `Ok(mime_type)`: The type of the file.
+///
+/// # Errors
+/// * `ServerFnError`: If the file type is not recognised, or is not allowed.
+pub fn check_file_type(bytes: &[u8], mime_types: &[String]) -> Result<&'static str, ServerFnError> {
+    let Some(mime_type) = sniff_mime_type(bytes) else {
+        return Err(ServerFnError::new(format!(
+            "File type not recognised. Allowed types are {}.",
+            mime_types.join(", ")
+        )));
+    };
+    if !mime_types.iter().any(|allowed| allowed == mime_type) {
+        return Err(ServerFnError::new(format!(
+            "Files of type {mime_type} are not allowed. Allowed types are {}.",
+            mime_types.join(", ")
+        )));
+    }
+    Ok(mime_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::upload::IMAGE_MIME_TYPES;
+
+    const PNG_HEADER: [u8; 16] = [
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, b'I', b'H', b'D',
+        b'R',
+    ];
+
+    fn image_mime_types() -> Vec<String> {
+        IMAGE_MIME_TYPES.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_sniff_mime_type() {
+        assert_eq!(sniff_mime_type(&PNG_HEADER), Some("image/png"));
+        assert_eq!(
+            sniff_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]),
+            Some("image/jpeg")
+        );
+        assert_eq!(sniff_mime_type(b"GIF89a\x01\x00"), Some("image/gif"));
+        assert_eq!(
+            sniff_mime_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+            Some("image/webp")
+        );
+        // Other RIFF files, like WAV audio, are not WebP
+        assert_eq!(sniff_mime_type(b"RIFF\x24\x00\x00\x00WAVEfmt "), None);
+        assert_eq!(sniff_mime_type(b""), None);
+    }
+
+    #[test]
+    fn test_check_file_type_fake_png_header() {
+        // Only the header is needed to recognise the file
+        let result = check_file_type(&PNG_HEADER, &image_mime_types());
+        assert_eq!(result, Ok("image/png"));
+    }
+
+    #[test]
+    fn test_check_file_type_disguised_php() {
+        let result = check_file_type(b"<?php system($_GET['cmd']); ?>", &image_mime_types());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: File type not recognised. Allowed types are image/jpeg, image/png, image/webp."
+        );
+    }
+
+    #[test]
+    fn test_check_file_type_not_allowed() {
+        let result = check_file_type(b"GIF89a\x01\x00\x01\x00", &image_mime_types());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Files of type image/gif are not allowed. Allowed types are image/jpeg, image/png, image/webp."
+        );
+    }
+
+    #[test]
+    fn test_check_file_extension() {
+        let mime_types = image_mime_types();
+        assert!(check_file_extension("cover.jpg", &mime_types).is_ok());
+        assert!(check_file_extension("cover.JPEG", &mime_types).is_ok());
+        assert!(check_file_extension("cover.png", &mime_types).is_ok());
+        assert!(check_file_extension("cover.webp", &mime_types).is_ok());
+    }
+
+    #[test]
+    fn test_check_file_extension_disguised_php() {
+        let result = check_file_extension("cover.png.php", &image_mime_types());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Files with the extension \"php\" are not allowed. Allowed types are image/jpeg, image/png, image/webp."
+        );
+        assert!(check_file_extension("cover", &image_mime_types()).is_err());
+    }
+}
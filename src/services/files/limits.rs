@@ -0,0 +1,59 @@
+//! Size limits for file uploads.
+
+use leptos::prelude::ServerFnError;
+
+/// Check an upload is within its size limit.
+/// This is called as each chunk is received, so oversized files are rejected before they are fully read.
+///
+/// # Arguments
+/// * `total_so_far`: The number of bytes received so far.
+/// * `size_limit`: The maximum number of bytes allowed.
+///
+/// # Returns
+/// * `Ok(())`: If the upload is within the limit.
+///
+/// # Errors
+/// * `ServerFnError`: If the upload exceeds the limit.
+pub fn check_upload_size(total_so_far: usize, size_limit: usize) -> Result<(), ServerFnError> {
+    if total_so_far > size_limit {
+        return Err(ServerFnError::new(format!(
+            "File exceeds the maximum size of {} MB.",
+            size_limit / (1024 * 1024)
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::upload::DEFAULT_MAX_UPLOAD_SIZE_MB;
+
+    #[test]
+    fn test_check_upload_size_under_limit() {
+        let limit = 10 * 1024 * 1024;
+        assert!(check_upload_size(1024, limit).is_ok());
+        assert!(check_upload_size(limit, limit).is_ok());
+    }
+
+    #[test]
+    fn test_check_upload_size_over_limit() {
+        let limit = 10 * 1024 * 1024;
+        let result = check_upload_size(limit + 1, limit);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: File exceeds the maximum size of 10 MB."
+        );
+    }
+
+    #[test]
+    fn test_check_upload_size_oversized_buffer() {
+        let limit = DEFAULT_MAX_UPLOAD_SIZE_MB * 1024 * 1024;
+        let buffer = vec![0_u8; limit + 1];
+        let result = check_upload_size(buffer.len(), limit);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: File exceeds the maximum size of 10 MB."
+        );
+    }
+}
@@ -1,3 +1,8 @@
 //! This module provides functionality for handling file uploads and progress tracking.
 
+pub mod file_type;
+pub mod limits;
+pub mod og_image;
 pub mod progress;
+pub mod resize;
+pub mod webp;
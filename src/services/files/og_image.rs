@@ -0,0 +1,455 @@
+//! Generate Open Graph share images.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ab_glyph::{Font, FontRef, GlyphId, PxScale, ScaleFont, point};
+use image::{
+    DynamicImage, ImageFormat, ImageReader, Rgba, RgbaImage,
+    imageops::{self, FilterType},
+};
+use leptos::prelude::ServerFnError;
+
+use crate::config::site::base_url;
+use crate::models::{artist::Artist, release::Release};
+use crate::utils::files::{OG_IMAGE_DIRECTORY, SafeFileName};
+
+/// The width of an Open Graph share image, in pixels.
+pub const OG_IMAGE_WIDTH: u32 = 1200;
+/// The height of an Open Graph share image, in pixels.
+/// The artwork is drawn as a square of this size on the left.
+pub const OG_IMAGE_HEIGHT: u32 = 630;
+
+const MARGIN: u32 = 48;
+const TITLE_SIZE: f32 = 64.0;
+const TITLE_MAX_LINES: usize = 4;
+const LOGO_HEIGHT: u32 = 96;
+const BACKGROUND: Rgba<u8> = Rgba([17, 17, 17, 255]);
+const TEXT_COLOUR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+static FONT: &[u8] = include_bytes!("../../../assets/fonts/DejaVuSans-Bold.ttf");
+static LOGO: &[u8] = include_bytes!("../../../public/Logo.png");
+
+/// Counts the share images drawn by this process, to give each one its own temporary file.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Something that can be shared with an Open Graph image.
+/// The entity is owned, so it can be drawn on the blocking thread pool.
+#[derive(Debug, Clone)]
+pub enum OgImageEntity {
+    /// An artist, drawn with their primary image.
+    Artist(Artist),
+    /// A release, drawn with its artwork.
+    Release(Release),
+}
+
+impl OgImageEntity {
+    fn title(&self) -> &str {
+        match self {
+            Self::Artist(artist) => &artist.name,
+            Self::Release(release) => &release.name,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        match self {
+            Self::Artist(artist) => artist.og_image_file_name(),
+            Self::Release(release) => release.og_image_file_name(),
+        }
+    }
+
+    fn url(&self) -> String {
+        match self {
+            Self::Artist(artist) => artist.og_image_url(),
+            Self::Release(release) => release.og_image_url(),
+        }
+    }
+
+    /// The upload directory and stored file name of the artwork.
+    fn artwork(&self) -> (&'static str, Option<&str>) {
+        match self {
+            Self::Artist(artist) => ("artists", artist.primary_image.as_deref()),
+            Self::Release(release) => ("releases", release.primary_image.as_deref()),
+        }
+    }
+}
+
+/// Generate the Open Graph share image for an entity.
+///
+/// The image is 1200×630, with the artwork on the left and the title and label logo on the right.
+/// It is cached in the `og` upload directory under `og_image_file_name`, so an unchanged entity is only drawn once.
+///
+/// # Arguments
+/// * `entity`: The artist or release to draw.
+/// * `upload_path`: The upload directory.
+///
+/// # Returns
+/// * The path of the image.
+///
+/// # Errors
+/// * `ServerFnError`: If the image cannot be drawn or saved.
+#[allow(clippy::cast_precision_loss)] // pixel sizes are far below f32 precision
+pub fn generate_og_image(
+    entity: &OgImageEntity,
+    upload_path: &str,
+) -> Result<PathBuf, ServerFnError> {
+    let file_name = SafeFileName::new(&entity.file_name()).map_err(ServerFnError::new)?;
+    let path = file_name.path_in(&format!("{upload_path}/{OG_IMAGE_DIRECTORY}"));
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let mut canvas = RgbaImage::from_pixel(OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT, BACKGROUND);
+    if let Some(artwork) = load_artwork(entity, upload_path) {
+        let artwork = artwork
+            .resize_to_fill(OG_IMAGE_HEIGHT, OG_IMAGE_HEIGHT, FilterType::Lanczos3)
+            .to_rgba8();
+        imageops::overlay(&mut canvas, &artwork, 0, 0);
+    }
+
+    let text_left = OG_IMAGE_HEIGHT + MARGIN;
+    let text_width = OG_IMAGE_WIDTH - text_left - MARGIN;
+    let logo = image::load_from_memory_with_format(LOGO, ImageFormat::Png)
+        .map_err(|e| {
+            let err = format!("Error while decoding logo: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not draw share image.".to_string())
+        })?
+        .resize(text_width, LOGO_HEIGHT, FilterType::Lanczos3)
+        .to_rgba8();
+    imageops::overlay(
+        &mut canvas,
+        &logo,
+        i64::from(OG_IMAGE_WIDTH - MARGIN - logo.width()),
+        i64::from(OG_IMAGE_HEIGHT - MARGIN - logo.height()),
+    );
+
+    let font = FontRef::try_from_slice(FONT).map_err(|e| {
+        let err = format!("Error while loading font: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new("Could not draw share image.".to_string())
+    })?;
+    let scale = PxScale::from(TITLE_SIZE);
+    let line_height = font.as_scaled(scale).height() + font.as_scaled(scale).line_gap();
+    let mut baseline = MARGIN as f32 + font.as_scaled(scale).ascent();
+    for line in wrap_title(&font, scale, entity.title(), text_width as f32) {
+        draw_text(&mut canvas, &font, scale, &line, text_left as f32, baseline);
+        baseline += line_height;
+    }
+
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    // Save to a temporary file first, so a half written image is never served.
+    // Each call gets its own file, as two first views of a page can draw the same image at once.
+    let tmp_path = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(e) = canvas.save_with_format(&tmp_path, ImageFormat::Png) {
+        let _ = std::fs::remove_file(&tmp_path);
+        let err = format!("Error while saving share image: {e:?}");
+        tracing::error!("{err}");
+        return Err(ServerFnError::new(
+            "Could not save share image.".to_string(),
+        ));
+    }
+    if let Err(e) = std::fs::rename(&tmp_path, &path) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+
+    Ok(path)
+}
+
+/// Generate the Open Graph share image for an entity in the configured upload directory.
+///
+/// Drawing is CPU bound, so it runs on the blocking thread pool rather than holding up the request's worker.
+/// Share images are not essential, so failures are logged rather than returned.
+///
+/// # Arguments
+/// * `entity`: The artist or release to draw.
+///
+/// # Returns
+/// * The absolute URL of the image for the `og:image` tag, or None if the image could not be drawn.
+pub async fn ensure_og_image(entity: OgImageEntity) -> Option<String> {
+    let upload_path = std::env::var("UPLOAD_PATH").ok()?;
+    let url = entity.url();
+
+    match tokio::task::spawn_blocking(move || generate_og_image(&entity, &upload_path)).await {
+        Ok(Ok(path)) if path.exists() => Some(format!("{}{url}", base_url())),
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => {
+            tracing::error!("Error while generating share image: {e:?}");
+            None
+        }
+        Err(e) => {
+            tracing::error!("Error while waiting for share image: {e:?}");
+            None
+        }
+    }
+}
+
+/// Load the entity's artwork, if it has any that can be decoded.
+fn load_artwork(entity: &OgImageEntity, upload_path: &str) -> Option<DynamicImage> {
+    let (directory, file_name) = entity.artwork();
+    let file_name = SafeFileName::new(file_name?).ok()?;
+    let path = file_name.path_in(&format!("{upload_path}/{directory}"));
+    match ImageReader::open(&path).and_then(ImageReader::with_guessed_format) {
+        Ok(reader) => reader
+            .decode()
+            .map_err(|e| tracing::error!("Error while decoding {}: {e:?}", path.display()))
+            .ok(),
+        Err(e) => {
+            tracing::error!("Error while opening {}: {e:?}", path.display());
+            None
+        }
+    }
+}
+
+/// The width of a line of text, in pixels.
+fn text_width(font: &FontRef<'_>, scale: PxScale, text: &str) -> f32 {
+    let font = font.as_scaled(scale);
+    let mut width = 0.0;
+    let mut previous: Option<GlyphId> = None;
+    for c in text.chars() {
+        let glyph_id = font.glyph_id(c);
+        if let Some(previous) = previous {
+            width += font.kern(previous, glyph_id);
+        }
+        width += font.h_advance(glyph_id);
+        previous = Some(glyph_id);
+    }
+    width
+}
+
+/// Split a title into lines that fit the width.
+/// Titles too long for `TITLE_MAX_LINES` are cut short with an ellipsis.
+fn wrap_title(font: &FontRef<'_>, scale: PxScale, title: &str, max_width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in title.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if current.is_empty() || text_width(font, scale, &candidate) <= max_width {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > TITLE_MAX_LINES {
+        lines.truncate(TITLE_MAX_LINES);
+        if let Some(last) = lines.last_mut() {
+            while !last.is_empty() && text_width(font, scale, &format!("{last}…")) > max_width {
+                last.pop();
+            }
+            *last = format!("{}…", last.trim_end());
+        }
+    }
+    lines
+}
+
+/// Draw a line of text onto the canvas, blending it over what is already there.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)] // pixel coordinates are bounds checked
+fn draw_text(
+    canvas: &mut RgbaImage,
+    font: &FontRef<'_>,
+    scale: PxScale,
+    text: &str,
+    x: f32,
+    baseline: f32,
+) {
+    let scaled = font.as_scaled(scale);
+    let mut caret = x;
+    let mut previous: Option<GlyphId> = None;
+    for c in text.chars() {
+        let glyph_id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, glyph_id);
+        }
+        let glyph = glyph_id.with_scale_and_position(scale, point(caret, baseline));
+        caret += scaled.h_advance(glyph_id);
+        previous = Some(glyph_id);
+
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|glyph_x, glyph_y, coverage| {
+            let pixel_x = bounds.min.x + glyph_x as f32;
+            let pixel_y = bounds.min.y + glyph_y as f32;
+            if pixel_x < 0.0 || pixel_y < 0.0 {
+                return;
+            }
+            let (pixel_x, pixel_y) = (pixel_x as u32, pixel_y as u32);
+            if pixel_x >= canvas.width() || pixel_y >= canvas.height() {
+                return;
+            }
+            let pixel = canvas.get_pixel_mut(pixel_x, pixel_y);
+            for channel in 0..3 {
+                let background = f32::from(pixel.0[channel]);
+                let text = f32::from(TEXT_COLOUR.0[channel]);
+                pixel.0[channel] = coverage.mul_add(text - background, background).round() as u8;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    fn test_upload_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "white-label-og-image-{}-{name}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&path).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn test_release(primary_image: Option<&str>) -> Release {
+        Release {
+            id: 1,
+            name: "A Release With A Reasonably Long Title To Wrap".to_string(),
+            primary_image: primary_image.map(str::to_string),
+            ..Release::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_og_image_dimensions() {
+        let upload_path = test_upload_path("dimensions");
+        let release = test_release(None);
+
+        let path =
+            generate_og_image(&OgImageEntity::Release(release.clone()), &upload_path).unwrap();
+
+        let dimensions = image::image_dimensions(&path).unwrap();
+        std::fs::remove_dir_all(&upload_path).unwrap();
+        assert_eq!(dimensions, (OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT));
+        assert!(path.ends_with(release.og_image_file_name()));
+    }
+
+    #[test]
+    fn test_generate_og_image_with_artwork() {
+        let upload_path = test_upload_path("artwork");
+        std::fs::create_dir_all(format!("{upload_path}/artists")).unwrap();
+        ImageBuffer::from_pixel(50, 80, Rgb([255u8, 0, 0]))
+            .save(format!("{upload_path}/artists/artist.png"))
+            .unwrap();
+        let artist = Artist {
+            id: 1,
+            name: "An Artist".to_string(),
+            primary_image: Some("artist.png".to_string()),
+            ..Artist::default()
+        };
+
+        let path = generate_og_image(&OgImageEntity::Artist(artist), &upload_path).unwrap();
+
+        let image = image::open(&path).unwrap().to_rgba8();
+        std::fs::remove_dir_all(&upload_path).unwrap();
+        assert_eq!(image.dimensions(), (OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT));
+        // The artwork fills the square on the left
+        assert_eq!(image.get_pixel(10, 10), &Rgba([255, 0, 0, 255]));
+        assert_eq!(
+            image.get_pixel(OG_IMAGE_HEIGHT - 10, OG_IMAGE_HEIGHT - 10),
+            &Rgba([255, 0, 0, 255])
+        );
+        assert_eq!(image.get_pixel(OG_IMAGE_WIDTH - 1, 0), &BACKGROUND);
+    }
+
+    #[test]
+    fn test_generate_og_image_is_cached() {
+        let upload_path = test_upload_path("cached");
+        let release = test_release(None);
+
+        let path = generate_og_image(&OgImageEntity::Release(release), &upload_path).unwrap();
+        std::fs::write(&path, b"cached").unwrap();
+        let cached_path =
+            generate_og_image(&OgImageEntity::Release(test_release(None)), &upload_path).unwrap();
+
+        let cached = std::fs::read(&cached_path).unwrap();
+        std::fs::remove_dir_all(&upload_path).unwrap();
+        assert_eq!(cached_path, path);
+        assert_eq!(cached, b"cached");
+    }
+
+    #[test]
+    fn test_generate_og_image_concurrently() {
+        let upload_path = test_upload_path("concurrent");
+
+        let paths = std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        generate_og_image(&OgImageEntity::Release(test_release(None)), &upload_path)
+                            .unwrap()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let dimensions = image::image_dimensions(&paths[0]);
+        let files = std::fs::read_dir(format!("{upload_path}/{OG_IMAGE_DIRECTORY}"))
+            .unwrap()
+            .count();
+        std::fs::remove_dir_all(&upload_path).unwrap();
+        assert!(paths.iter().all(|path| *path == paths[0]));
+        assert_eq!(dimensions.unwrap(), (OG_IMAGE_WIDTH, OG_IMAGE_HEIGHT));
+        // No temporary files are left behind
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn test_og_image_url_is_stable() {
+        let release = test_release(Some("cover.jpg"));
+        let url = release.og_image_url();
+
+        assert!(url.starts_with("/uploads/og/release-1-"));
+        assert_eq!(test_release(Some("cover.jpg")).og_image_url(), url);
+        let mut updated = release.clone();
+        updated.description = "A new description".to_string();
+        assert_eq!(updated.og_image_url(), url);
+
+        let mut renamed = release.clone();
+        renamed.name = "A Different Name".to_string();
+        assert_ne!(renamed.og_image_url(), url);
+        let mut new_artwork = release;
+        new_artwork.primary_image = Some("new-cover.jpg".to_string());
+        assert_ne!(new_artwork.og_image_url(), url);
+    }
+
+    #[test]
+    fn test_wrap_title() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let scale = PxScale::from(TITLE_SIZE);
+
+        assert_eq!(wrap_title(&font, scale, "Short", 500.0), vec!["Short"]);
+        let lines = wrap_title(&font, scale, &"word ".repeat(40), 500.0);
+        assert_eq!(lines.len(), TITLE_MAX_LINES);
+        assert!(
+            lines
+                .iter()
+                .all(|line| text_width(&font, scale, line) <= 500.0)
+        );
+        assert!(lines[TITLE_MAX_LINES - 1].ends_with('…'));
+    }
+}
@@ -0,0 +1,109 @@
+//! Downscale oversized image uploads.
+
+use std::path::Path;
+
+use image::{ImageReader, imageops::FilterType};
+use leptos::prelude::ServerFnError;
+
+/// Downscale an image so its longest edge is no longer than the maximum, preserving the aspect ratio.
+///
+/// The image is overwritten in its original format. Smaller images are left untouched.
+/// This decodes and encodes the image, so call it from the blocking thread pool in async code.
+///
+/// # Arguments
+/// * `path`: The path of the image.
+/// * `max_dimension`: The maximum length of the longest edge, in pixels.
+///
+/// # Returns
+/// * `Ok(true)`: If the image was downscaled.
+/// * `Ok(false)`: If the image was already small enough.
+///
+/// # Errors
+/// * `ServerFnError`: If the image cannot be read, decoded or saved.
+pub fn downscale_image(path: &Path, max_dimension: u32) -> Result<bool, ServerFnError> {
+    let reader = ImageReader::open(path)?.with_guessed_format()?;
+    let Some(format) = reader.format() else {
+        return Err(ServerFnError::new("Unknown image format.".to_string()));
+    };
+    let (width, height) = reader.into_dimensions().map_err(|e| {
+        let err = format!("Error while reading image dimensions: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new("Could not read image.".to_string())
+    })?;
+    if width.max(height) <= max_dimension {
+        return Ok(false);
+    }
+
+    let image = ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| {
+            let err = format!("Error while decoding image: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not read image.".to_string())
+        })?;
+    tracing::info!(
+        "Downscaling {} from {width}x{height} to fit {max_dimension}px.",
+        path.display()
+    );
+    image
+        .resize(max_dimension, max_dimension, FilterType::Lanczos3)
+        .save_with_format(path, format)
+        .map_err(|e| {
+            let err = format!("Error while saving image: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not save image.".to_string())
+        })?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    fn test_image(name: &str, width: u32, height: u32) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{name}.png", std::process::id()));
+        ImageBuffer::from_pixel(width, height, Rgb([255u8, 0, 0]))
+            .save(&path)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_downscale_image_oversized() {
+        let path = test_image("oversized", 800, 400);
+
+        let downscaled = downscale_image(&path, 300).unwrap();
+
+        assert!(downscaled);
+        let dimensions = image::image_dimensions(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dimensions, (300, 150));
+    }
+
+    #[test]
+    fn test_downscale_image_portrait() {
+        let path = test_image("portrait", 200, 1000);
+
+        downscale_image(&path, 500).unwrap();
+
+        let dimensions = image::image_dimensions(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dimensions, (100, 500));
+    }
+
+    #[test]
+    fn test_downscale_image_under_cap() {
+        let path = test_image("under-cap", 200, 100);
+        let original = std::fs::read(&path).unwrap();
+
+        let downscaled = downscale_image(&path, 300).unwrap();
+
+        let stored = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!downscaled);
+        assert_eq!(stored, original);
+    }
+}
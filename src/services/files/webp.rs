@@ -0,0 +1,122 @@
+//! WebP variants of uploaded images.
+//!
+//! JPEG and PNG uploads are also stored as WebP, which the uploads route sends to clients that accept it.
+
+use std::path::{Path, PathBuf};
+
+use image::{DynamicImage, ImageReader};
+use leptos::prelude::ServerFnError;
+
+use crate::utils::files::webp_variant_file_name;
+
+/// Create a WebP variant alongside an uploaded image.
+///
+/// The variant is lossy, at the given quality. A variant that is no smaller than the original would only waste bandwidth,
+/// so such variants are not kept, and the original is served to every client.
+/// This decodes and encodes the image, so call it from the blocking thread pool in async code.
+///
+/// # Arguments
+/// * `path`: The path of the uploaded image.
+/// * `quality`: The quality of the variant, from 1 to 100, see `webp_quality`.
+///
+/// # Returns
+/// * `Ok(Some(path))`: The path of the WebP variant, if one was created.
+/// * `Ok(None)`: If the image doesn't get a variant, or the variant would be no smaller.
+///
+/// # Errors
+/// * `ServerFnError`: If the image cannot be read, decoded or encoded, or the variant cannot be saved.
+pub fn create_webp_variant(path: &Path, quality: u8) -> Result<Option<PathBuf>, ServerFnError> {
+    let Some(variant_name) = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .and_then(webp_variant_file_name)
+    else {
+        return Ok(None);
+    };
+
+    let image = ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| {
+            let err = format!("Error while decoding image: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not read image.".to_string())
+        })?;
+    // The encoder only takes 8 bit RGB or RGBA
+    let image = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(image.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(image.to_rgb8())
+    };
+    let webp = ::webp::Encoder::from_image(&image)
+        .map_err(|e| {
+            let err = format!("Error while encoding WebP image: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not convert image to WebP.".to_string())
+        })?
+        .encode(f32::from(quality));
+
+    let original_size = std::fs::metadata(path)?.len();
+    if webp.len() as u64 >= original_size {
+        tracing::info!(
+            "Not keeping WebP variant of {}, as it is larger.",
+            path.display()
+        );
+        return Ok(None);
+    }
+
+    let variant_path = path.with_file_name(variant_name);
+    std::fs::write(&variant_path, &*webp)?;
+    Ok(Some(variant_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::upload::DEFAULT_WEBP_QUALITY;
+    use image::{ImageBuffer, Rgb};
+
+    fn test_image(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-webp-{name}", std::process::id()));
+        ImageBuffer::from_fn(256, 256, |x, y| {
+            Rgb([u8::try_from(x).unwrap(), u8::try_from(y).unwrap(), 0])
+        })
+        .save_with_format(&path, image::ImageFormat::Png)
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_create_webp_variant() {
+        let path = test_image("cover.png");
+
+        let variant = create_webp_variant(&path, DEFAULT_WEBP_QUALITY)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            variant,
+            path.with_file_name(format!("{}-webp-cover.png.webp", std::process::id()))
+        );
+        let format = ImageReader::open(&variant)
+            .unwrap()
+            .with_guessed_format()
+            .unwrap()
+            .format();
+        let dimensions = image::image_dimensions(&variant).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&variant).unwrap();
+        assert_eq!(format, Some(image::ImageFormat::WebP));
+        assert_eq!(dimensions, (256, 256));
+    }
+
+    #[test]
+    fn test_create_webp_variant_webp_original() {
+        let path = test_image("cover.webp");
+
+        let variant = create_webp_variant(&path, DEFAULT_WEBP_QUALITY).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(variant, None);
+    }
+}
@@ -0,0 +1,150 @@
+//! Services for the homepage routes
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use crate::config::homepage::homepage_release_count;
+use crate::models::{auth::User, release::Release};
+use crate::routes::homepage::HomepageSummary;
+
+/// Get the featured, upcoming and latest releases for the homepage in a single call
+///
+/// Hidden releases are only included for label owners.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `user` - The current user
+/// * `label_id` - The ID of the record label
+///
+/// # Returns
+/// The homepage summary
+///
+/// # Errors
+/// If the releases cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn summary(
+    pool: &PgPool,
+    user: Option<&User>,
+    label_id: i64,
+) -> Result<HomepageSummary, ServerFnError> {
+    let include_hidden =
+        user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
+    let count = homepage_release_count();
+
+    let upcoming_releases = Release::list_upcoming(pool, label_id, count, include_hidden)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting upcoming releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let latest_releases = Release::list_latest(pool, label_id, count, include_hidden)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting latest releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    let featured_release = upcoming_releases
+        .first()
+        .or_else(|| latest_releases.first())
+        .cloned();
+
+    Ok(HomepageSummary {
+        featured_release,
+        upcoming_releases,
+        latest_releases,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_release, create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_summary_no_releases(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let summary = summary(&pool, None, artist.label_id).await.unwrap();
+
+        assert_eq!(summary.featured_release, None);
+        assert!(summary.upcoming_releases.is_empty());
+        assert!(summary.latest_releases.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_summary_featured_release_is_next_upcoming(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let latest_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut upcoming_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let upcoming_release = upcoming_release.update(&pool).await.unwrap();
+
+        let summary = summary(&pool, None, artist.label_id).await.unwrap();
+
+        assert_eq!(summary.featured_release, Some(upcoming_release.clone()));
+        assert_eq!(summary.upcoming_releases, vec![upcoming_release]);
+        assert_eq!(summary.latest_releases, vec![latest_release]);
+    }
+
+    #[sqlx::test]
+    async fn test_summary_featured_release_falls_back_to_latest(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let latest_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let summary = summary(&pool, None, artist.label_id).await.unwrap();
+
+        assert_eq!(summary.featured_release, Some(latest_release));
+    }
+
+    #[sqlx::test]
+    async fn test_summary_hidden_releases(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut hidden_latest_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        hidden_latest_release.published_at = None;
+        let hidden_latest_release = hidden_latest_release.update(&pool).await.unwrap();
+        let mut hidden_upcoming_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        hidden_upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        hidden_upcoming_release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let hidden_upcoming_release = hidden_upcoming_release.update(&pool).await.unwrap();
+
+        let public_summary = summary(&pool, None, artist.label_id).await.unwrap();
+        assert_eq!(public_summary.featured_release, None);
+        assert!(public_summary.upcoming_releases.is_empty());
+        assert!(public_summary.latest_releases.is_empty());
+
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin"])
+            .await
+            .unwrap();
+        let admin_summary = summary(&pool, Some(&user), artist.label_id).await.unwrap();
+        assert!(admin_summary.upcoming_releases.is_empty());
+
+        let user = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let owner_summary = summary(&pool, Some(&user), artist.label_id).await.unwrap();
+        assert_eq!(
+            owner_summary.featured_release,
+            Some(hidden_upcoming_release.clone())
+        );
+        assert_eq!(
+            owner_summary.upcoming_releases,
+            vec![hidden_upcoming_release]
+        );
+        assert_eq!(owner_summary.latest_releases, vec![hidden_latest_release]);
+    }
+}
@@ -0,0 +1,499 @@
+//! Import service module
+//!
+//! Fetches release metadata from MusicBrainz and turns it into draft forms for an admin to review.
+//! Nothing is written to the database, the admin creates the release and tracks from the forms as usual.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use crate::forms::{release::CreateReleaseForm, track::CreateTrackForm};
+use crate::models::{artist::Artist, auth::User};
+use crate::routes::release::ImportedReleaseResult;
+use crate::services::authentication_helpers::user_can_manage_artist;
+
+/// The base URL of the MusicBrainz web service
+pub const MUSICBRAINZ_API_URL: &str = "https://musicbrainz.org/ws/2";
+
+/// How long to wait for MusicBrainz before giving up
+const MUSICBRAINZ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A release, as returned by the MusicBrainz web service
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct MusicBrainzRelease {
+    /// The title of the release
+    pub title: String,
+    /// The release date, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+    #[serde(default)]
+    pub date: Option<String>,
+    /// The discs, sides or other media of the release
+    #[serde(default)]
+    pub media: Vec<MusicBrainzMedium>,
+}
+
+/// A disc, side or other medium of a MusicBrainz release
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct MusicBrainzMedium {
+    /// The tracks on the medium, in order
+    #[serde(default)]
+    pub tracks: Vec<MusicBrainzTrack>,
+}
+
+/// A track on a MusicBrainz medium
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct MusicBrainzTrack {
+    /// The title of the track
+    pub title: String,
+    /// The length of the track in milliseconds
+    #[serde(default)]
+    pub length: Option<u64>,
+    /// The recording the track is of
+    #[serde(default)]
+    pub recording: Option<MusicBrainzRecording>,
+}
+
+/// A recording on MusicBrainz
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct MusicBrainzRecording {
+    /// The length of the recording in milliseconds
+    #[serde(default)]
+    pub length: Option<u64>,
+    /// The ISRC codes of the recording
+    #[serde(default)]
+    pub isrcs: Vec<String>,
+}
+
+/// Is the string a MusicBrainz ID, which is a UUID
+///
+/// # Example
+/// ```
+/// use white_label::services::import::is_valid_mbid;
+/// assert!(is_valid_mbid("b84ee12a-09ef-421b-82de-0441a926375b"));
+/// assert!(!is_valid_mbid("not-an-mbid"));
+/// ```
+#[must_use]
+pub fn is_valid_mbid(mbid: &str) -> bool {
+    mbid.len() == 36
+        && mbid.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Parse a MusicBrainz date, which may only have a year or a year and month
+fn parse_release_date(date: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year = parts.next()?.parse::<i32>().ok()?;
+    let month = parts
+        .next()
+        .map_or(Some(1), |month| month.parse::<u32>().ok())?;
+    let day = parts
+        .next()
+        .map_or(Some(1), |day| day.parse::<u32>().ok())?;
+    chrono::NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+/// Fetch a release from the MusicBrainz web service
+///
+/// # Arguments
+/// `api_url`: &str - The base URL of the MusicBrainz web service
+/// `mbid`: &str - The MusicBrainz ID of the release
+///
+/// # Returns
+/// Result<`MusicBrainzRelease`, `ServerFnError`> - The release and its tracks
+///
+/// # Errors
+/// If the MBID is not valid, return an error
+/// If MusicBrainz cannot be reached, return an error
+/// If the release cannot be found, return an error
+/// If the response cannot be read, return an error
+pub async fn fetch_release(api_url: &str, mbid: &str) -> Result<MusicBrainzRelease, ServerFnError> {
+    let mbid = mbid.trim();
+    if !is_valid_mbid(mbid) {
+        return Err(ServerFnError::new(format!(
+            "{mbid} is not a valid MusicBrainz release ID."
+        )));
+    }
+
+    let client = reqwest::Client::builder()
+        .user_agent(format!(
+            "white-label/{} ( {} )",
+            env!("CARGO_PKG_VERSION"),
+            crate::config::site::base_url()
+        ))
+        .timeout(MUSICBRAINZ_TIMEOUT)
+        .build()
+        .map_err(|e| {
+            let err = format!("Error while building MusicBrainz client: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not reach MusicBrainz, try again later.")
+        })?;
+    let response = client
+        .get(format!("{}/release/{mbid}", api_url.trim_end_matches('/')))
+        .query(&[("inc", "recordings isrcs"), ("fmt", "json")])
+        .send()
+        .await
+        .map_err(|e| {
+            let err = format!("Error while fetching MusicBrainz release: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not reach MusicBrainz, try again later.")
+        })?;
+
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND => Err(ServerFnError::new(format!(
+            "Could not find a MusicBrainz release with the ID {mbid}."
+        ))),
+        status if !status.is_success() => {
+            tracing::error!("MusicBrainz returned {status} for release {mbid}");
+            Err(ServerFnError::new(format!(
+                "MusicBrainz could not return the release ({status}), try again later."
+            )))
+        }
+        _ => response.json::<MusicBrainzRelease>().await.map_err(|e| {
+            let err = format!("Error while reading MusicBrainz release: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not read the release from MusicBrainz.")
+        }),
+    }
+}
+
+/// Turn a MusicBrainz release into draft forms for an artist
+///
+/// The forms are unpublished, and the tracks are numbered in order across every medium.
+/// The tracks' `release_id` is left as 0, to be filled in once the release is created.
+///
+/// # Arguments
+/// `release`: `MusicBrainzRelease` - The release from MusicBrainz
+/// `artist`: &`Artist` - The artist the release is by
+///
+/// # Returns
+/// `ImportedReleaseResult` - The release and track forms
+#[must_use]
+pub fn release_forms(release: MusicBrainzRelease, artist: &Artist) -> ImportedReleaseResult {
+    let tracks = release
+        .media
+        .into_iter()
+        .flat_map(|medium| medium.tracks)
+        .zip(1..)
+        .map(|(track, track_number)| {
+            let recording = track.recording.unwrap_or_default();
+            CreateTrackForm {
+                artist_ids: artist.id.to_string(),
+                name: track.title,
+                primary_artist_id: artist.id,
+                isrc_code: recording.isrcs.into_iter().next(),
+                duration_ms: track
+                    .length
+                    .or(recording.length)
+                    .and_then(|length| i32::try_from(length).ok()),
+                track_number,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    ImportedReleaseResult {
+        release: CreateReleaseForm {
+            artist_ids: artist.id.to_string(),
+            name: release.title,
+            primary_artist_id: artist.id,
+            release_date: release.date.as_deref().and_then(parse_release_date),
+            label_id: artist.label_id,
+            ..Default::default()
+        },
+        tracks,
+    }
+}
+
+/// Import a release from MusicBrainz as draft forms
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user importing the release
+/// mbid: String - The MusicBrainz ID of the release
+/// `artist_id`: i64 - The ID of the artist the release is by
+///
+/// # Returns
+/// Result<`ImportedReleaseResult`, `ServerFnError`> - The release and track forms
+///
+/// # Errors
+/// If the user cannot manage the artist, return an error
+/// If the artist cannot be found, return an error
+/// If the release cannot be fetched from MusicBrainz, return an error
+pub async fn import_release_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    mbid: String,
+    artist_id: i64,
+) -> Result<ImportedReleaseResult, ServerFnError> {
+    user_can_manage_artist(pool, user, artist_id).await?;
+
+    let artist = Artist::get_by_id(pool, artist_id).await.map_err(|e| {
+        let err = format!("Error while getting artist: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let release = fetch_release(MUSICBRAINZ_API_URL, &mbid).await?;
+
+    Ok(release_forms(release, &artist))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{create_test_artist, create_test_user_with_permissions};
+
+    const MBID: &str = "b84ee12a-09ef-421b-82de-0441a926375b";
+
+    /// A trimmed down MusicBrainz release, as returned with `inc=recordings+isrcs`
+    const MUSICBRAINZ_JSON: &str = r#"{
+        "id": "b84ee12a-09ef-421b-82de-0441a926375b",
+        "title": "Test Import",
+        "date": "1997-05",
+        "media": [
+            {
+                "position": 1,
+                "tracks": [
+                    {
+                        "number": "1",
+                        "title": "First",
+                        "length": 123456,
+                        "recording": {"length": 123000, "isrcs": ["GBAYE9700001"]}
+                    },
+                    {
+                        "number": "2",
+                        "title": "Second",
+                        "length": null,
+                        "recording": {"length": 200000, "isrcs": []}
+                    }
+                ]
+            },
+            {
+                "position": 2,
+                "tracks": [{"number": "1", "title": "Third", "length": null}]
+            }
+        ]
+    }"#;
+
+    fn musicbrainz_release() -> MusicBrainzRelease {
+        let track = |title: &str, length, recording| MusicBrainzTrack {
+            title: title.to_string(),
+            length,
+            recording,
+        };
+        MusicBrainzRelease {
+            title: "Test Import".to_string(),
+            date: Some("1997-05".to_string()),
+            media: vec![
+                MusicBrainzMedium {
+                    tracks: vec![
+                        track(
+                            "First",
+                            Some(123_456),
+                            Some(MusicBrainzRecording {
+                                length: Some(123_000),
+                                isrcs: vec!["GBAYE9700001".to_string()],
+                            }),
+                        ),
+                        track(
+                            "Second",
+                            None,
+                            Some(MusicBrainzRecording {
+                                length: Some(200_000),
+                                isrcs: vec![],
+                            }),
+                        ),
+                    ],
+                },
+                MusicBrainzMedium {
+                    tracks: vec![track("Third", None, None)],
+                },
+            ],
+        }
+    }
+
+    /// Serve a fake MusicBrainz web service over HTTP
+    fn musicbrainz_server() -> axum_test::TestServer {
+        use axum::extract::Path;
+
+        let app = axum::Router::new().route(
+            "/ws/2/release/{mbid}",
+            axum::routing::get(|Path(mbid): Path<String>| async move {
+                if mbid == MBID {
+                    Ok((
+                        [(axum::http::header::CONTENT_TYPE, "application/json")],
+                        MUSICBRAINZ_JSON,
+                    ))
+                } else if mbid.starts_with('0') {
+                    Err(axum::http::StatusCode::SERVICE_UNAVAILABLE)
+                } else {
+                    Err(axum::http::StatusCode::NOT_FOUND)
+                }
+            }),
+        );
+        axum_test::TestServer::builder()
+            .http_transport()
+            .build(app)
+            .unwrap()
+    }
+
+    fn api_url(server: &axum_test::TestServer) -> String {
+        format!("{}ws/2", server.server_address().unwrap())
+    }
+
+    #[test]
+    fn test_is_valid_mbid() {
+        assert!(is_valid_mbid(MBID));
+        assert!(is_valid_mbid(&MBID.to_uppercase()));
+        assert!(!is_valid_mbid(""));
+        assert!(!is_valid_mbid("b84ee12a09ef421b82de0441a926375b"));
+        assert!(!is_valid_mbid("g84ee12a-09ef-421b-82de-0441a926375b"));
+        assert!(!is_valid_mbid("../artist/09ef-421b-82de-0441a926375b"));
+    }
+
+    #[test]
+    fn test_parse_release_date() {
+        let expected = |year, month, day| {
+            chrono::NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+
+        assert_eq!(
+            parse_release_date("1997-05-21"),
+            Some(expected(1997, 5, 21))
+        );
+        assert_eq!(parse_release_date("1997-05"), Some(expected(1997, 5, 1)));
+        assert_eq!(parse_release_date("1997"), Some(expected(1997, 1, 1)));
+        assert_eq!(parse_release_date(""), None);
+        assert_eq!(parse_release_date("1997-13-01"), None);
+    }
+
+    #[sqlx::test]
+    async fn test_release_forms(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let result = release_forms(musicbrainz_release(), &artist);
+
+        assert_eq!(result.release.name, "Test Import");
+        assert_eq!(result.release.primary_artist_id, artist.id);
+        assert_eq!(result.release.artist_ids, artist.id.to_string());
+        assert_eq!(result.release.label_id, artist.label_id);
+        assert_eq!(
+            result.release.release_date,
+            parse_release_date("1997-05-01")
+        );
+        assert_eq!(result.release.published_at, None);
+        assert_eq!(
+            result
+                .tracks
+                .iter()
+                .map(|track| (track.track_number, track.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "First"), (2, "Second"), (3, "Third")]
+        );
+        assert_eq!(result.tracks[0].isrc_code, Some("GBAYE9700001".to_string()));
+        assert_eq!(result.tracks[0].duration_ms, Some(123_456));
+        assert_eq!(result.tracks[1].isrc_code, None);
+        assert_eq!(result.tracks[1].duration_ms, Some(200_000));
+        assert_eq!(result.tracks[2].duration_ms, None);
+        assert!(
+            result
+                .tracks
+                .iter()
+                .all(|track| track.published_at.is_none()
+                    && track.release_id == 0
+                    && track.primary_artist_id == artist.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release() {
+        let server = musicbrainz_server();
+
+        let release = fetch_release(&api_url(&server), MBID).await.unwrap();
+
+        assert_eq!(release.title, "Test Import");
+        assert_eq!(release.date, Some("1997-05".to_string()));
+        assert_eq!(
+            release
+                .media
+                .iter()
+                .map(|medium| medium.tracks.len())
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(
+            release.media[0].tracks[0]
+                .recording
+                .as_ref()
+                .map(|recording| recording.isrcs.clone()),
+            Some(vec!["GBAYE9700001".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release_not_found() {
+        let server = musicbrainz_server();
+        let mbid = "f84ee12a-09ef-421b-82de-0441a926375b";
+
+        let result = fetch_release(&api_url(&server), mbid).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "error running server function: Could not find a MusicBrainz release with the ID {mbid}."
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release_server_error() {
+        let server = musicbrainz_server();
+
+        let result = fetch_release(&api_url(&server), "084ee12a-09ef-421b-82de-0441a926375b").await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: MusicBrainz could not return the release (503 Service Unavailable), try again later."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release_unreachable() {
+        let result = fetch_release("http://127.0.0.1:9/ws/2", MBID).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Could not reach MusicBrainz, try again later."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_release_invalid_mbid() {
+        let result = fetch_release(MUSICBRAINZ_API_URL, "not-an-mbid").await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: not-an-mbid is not a valid MusicBrainz release ID."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_import_release_service_no_permission(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = import_release_service(&pool, Some(&user), MBID.to_string(), artist.id).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            ServerFnError::new("You do not have permission.")
+        );
+    }
+}
@@ -1,16 +1,17 @@
 //! Module for routes handling music services and social links associated with artists.
 use leptos::prelude::ServerFnError;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
-use super::authentication_helpers::user_with_permissions;
+use super::{authentication_helpers::user_can_manage_artist, read_only::ensure_writable};
 use crate::forms::links::LinksForm;
 use crate::models::{
     artist::Artist,
     auth::User,
     music_service::{MusicService, Platform},
     social_media::{SocialMedia, SocialMediaService},
+    traits::Validate,
 };
-use crate::routes::links::LinksResult;
+use crate::routes::links::{LinkAction, LinkChange, LinksResult};
 
 /// Get all music services for a specific artist.
 ///
@@ -26,7 +27,7 @@ pub async fn get_links_service(
     pool: &PgPool,
     artist_slug: String,
 ) -> Result<LinksResult, ServerFnError> {
-    let artist = match Artist::get_by_slug(pool, artist_slug).await {
+    let artist = match Artist::get_by_slug(pool, artist_slug, false).await {
         Ok(artist) => artist,
         Err(e) => return Err(ServerFnError::new(format!("Artist not found: {e}"))),
     };
@@ -38,49 +39,70 @@ pub async fn get_links_service(
         (Ok(services), Ok(social_media_services)) => Ok(LinksResult {
             music_services: services,
             social_media_services,
+            changes: vec![],
         }),
-        (Err(e), _) | (_, Err(e)) => Err(ServerFnError::new(format!("Error fetching links: {e}"))),
+        (Err(e), _) => Err(ServerFnError::new(format!("Error fetching links: {e}"))),
+        (_, Err(e)) => Err(ServerFnError::new(format!("Error fetching links: {e}"))),
     }
 }
 
 /// Update music services and social media links for an artist.
 ///
+/// All the changes are made in a single transaction, so if any link fails
+/// to save, none of the links are changed.
+///
 /// # Arguments
 /// * `pool`: The database connection pool.
 /// * `user`: The user performing the update.
 /// * `form`: The form containing the music service and social links to update.
 ///
 /// # Returns
-/// * A `LinksResult` containing the updated music services and social media links.
+/// * A `LinksResult` containing the updated music services and social media links,
+///   and which platforms were created, updated or deleted.
 ///
 /// # Errors
-/// Will return a `ServerFnError` if the artist cannot be found, or if there
-/// is an issue with the database connection.
+/// Will return a `ServerFnError` if the artist cannot be found, if any link
+/// cannot be saved, or if there is an issue with the database connection.
 pub async fn update_links_service(
     pool: &PgPool,
     user: Option<&User>,
     form: LinksForm,
 ) -> Result<LinksResult, ServerFnError> {
-    tracing::warn!("DO LOADS OF TESTING!");
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
-    }
-
-    let artist = match Artist::get_by_slug(pool, form.artist_slug.clone()).await {
+    let artist = match Artist::get_by_slug(pool, form.artist_slug.clone(), true).await {
         Ok(artist) => artist,
         Err(e) => return Err(ServerFnError::new(format!("Artist not found: {e}"))),
     };
+    match user_can_manage_artist(pool, user, artist.id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
 
     tracing::info!("Updating links for artist: {}", artist.slug);
 
-    handle_music_services(pool, form.clone(), artist.clone())
+    let mut transaction = pool.begin().await.map_err(|e| {
+        let err = format!("Error while starting transaction: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    let mut changes = handle_music_services(pool, &mut transaction, form.clone(), artist.clone())
         .await
         .map_err(|e| ServerFnError::new(format!("Error handling music services: {e}")))?;
 
-    handle_social_media_services(pool, form, artist.clone())
-        .await
-        .map_err(|e| ServerFnError::new(format!("Error handling social media services: {e}")))?;
+    changes.extend(
+        handle_social_media_services(pool, &mut transaction, form, artist.clone())
+            .await
+            .map_err(|e| {
+                ServerFnError::new(format!("Error handling social media services: {e}"))
+            })?,
+    );
+
+    transaction.commit().await.map_err(|e| {
+        let err = format!("Error while committing links: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
 
     Ok(LinksResult {
         music_services: MusicService::list_by_artist(pool, artist.id)
@@ -93,14 +115,27 @@ pub async fn update_links_service(
             .map_err(|e| {
                 ServerFnError::new(format!("Error fetching updated social media services: {e}"))
             })?,
+        changes,
     })
 }
 
+/// Record the changes made to a list of platforms
+fn link_changes<T: std::fmt::Display>(platforms: &[T], action: LinkAction) -> Vec<LinkChange> {
+    platforms
+        .iter()
+        .map(|platform| LinkChange {
+            platform: platform.to_string(),
+            action,
+        })
+        .collect()
+}
+
 async fn handle_music_services(
     pool: &PgPool,
+    conn: &mut PgConnection,
     form: LinksForm,
     artist: Artist,
-) -> Result<(), ServerFnError> {
+) -> Result<Vec<LinkChange>, ServerFnError> {
     // Get existing music services and social media links
     let existing_music_services = match MusicService::list_by_artist(pool, artist.id).await {
         Ok(services) => services.clone(),
@@ -114,13 +149,22 @@ async fn handle_music_services(
 
     let (music_services_to_create, music_services_to_update, music_services_to_delete) =
         categorise_music_services(existing_music_services.clone(), &form);
+    let mut changes = link_changes(&music_services_to_create, LinkAction::Created);
+    changes.extend(link_changes(&music_services_to_update, LinkAction::Updated));
+    changes.extend(link_changes(&music_services_to_delete, LinkAction::Deleted));
 
-    create_music_services(pool, music_services_to_create, form.clone(), artist.clone())
-        .await
-        .map_err(|e| ServerFnError::new(format!("Error creating music services: {e}")))?;
+    create_music_services(
+        pool,
+        conn,
+        music_services_to_create,
+        form.clone(),
+        artist.clone(),
+    )
+    .await
+    .map_err(|e| ServerFnError::new(format!("Error creating music services: {e}")))?;
 
     update_music_services(
-        pool,
+        conn,
         music_services_to_update,
         existing_music_services.clone(),
         form.clone(),
@@ -130,7 +174,7 @@ async fn handle_music_services(
     .map_err(|e| ServerFnError::new(format!("Error updating music services: {e}")))?;
 
     delete_music_services(
-        pool,
+        conn,
         music_services_to_delete,
         existing_music_services.clone(),
         artist.clone(),
@@ -138,7 +182,7 @@ async fn handle_music_services(
     .await
     .map_err(|e| ServerFnError::new(format!("Error deleting music services: {e}")))?;
 
-    Ok(())
+    Ok(changes)
 }
 
 fn categorise_music_services(
@@ -192,25 +236,32 @@ fn categorise_music_services(
 
 async fn create_music_services(
     pool: &PgPool,
+    conn: &mut PgConnection,
     music_services: Vec<&Platform>,
     form: LinksForm,
     artist: Artist,
 ) -> Result<(), ServerFnError> {
     for platform in music_services {
-        MusicService::create(
-            pool,
-            artist.id,
-            platform.clone(),
-            form.clone().from_platform(platform),
-        )
-        .await
-        .map_err(|e| ServerFnError::new(format!("Error creating music service: {e}")))?;
+        let service = MusicService {
+            artist_id: artist.id,
+            platform: platform.clone(),
+            url: form.clone().from_platform(platform),
+            ..Default::default()
+        };
+        service
+            .validate(pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Error creating music service: {e}")))?;
+        service
+            .insert(conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Error creating music service: {e}")))?;
     }
     Ok(())
 }
 
 async fn update_music_services(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     music_services: Vec<&Platform>,
     mut existing_music_services: Vec<MusicService>,
     form: LinksForm,
@@ -231,7 +282,7 @@ async fn update_music_services(
             .bind(url.clone())
             .bind(artist.id)
             .bind(service.platform.clone())
-            .execute(pool)
+            .execute(&mut *conn)
             .await
             {
                 Ok(artist) => artist,
@@ -249,7 +300,7 @@ async fn update_music_services(
 }
 
 async fn delete_music_services(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     music_services: Vec<&Platform>,
     mut existing_music_services: Vec<MusicService>,
     artist: Artist,
@@ -263,7 +314,7 @@ async fn delete_music_services(
             sqlx::query("DELETE FROM music_services WHERE artist_id = $1 AND platform = $2")
                 .bind(artist.id)
                 .bind(platform)
-                .execute(pool)
+                .execute(&mut *conn)
                 .await
                 .map_err(|e| ServerFnError::new(format!("Error deleting music service: {e}")))?;
         }
@@ -273,9 +324,10 @@ async fn delete_music_services(
 
 async fn handle_social_media_services(
     pool: &PgPool,
+    conn: &mut PgConnection,
     form: LinksForm,
     artist: Artist,
-) -> Result<(), ServerFnError> {
+) -> Result<Vec<LinkChange>, ServerFnError> {
     let existing_social_media_services =
         match SocialMediaService::list_by_artist(pool, artist.id).await {
             Ok(services) => services,
@@ -292,13 +344,22 @@ async fn handle_social_media_services(
 
     let (social_media_to_create, social_media_to_update, social_media_to_delete) =
         categorise_social_media_services(existing_social_media_services.clone(), &form);
+    let mut changes = link_changes(&social_media_to_create, LinkAction::Created);
+    changes.extend(link_changes(&social_media_to_update, LinkAction::Updated));
+    changes.extend(link_changes(&social_media_to_delete, LinkAction::Deleted));
 
-    create_social_media_services(pool, social_media_to_create, form.clone(), artist.clone())
-        .await
-        .map_err(|e| ServerFnError::new(format!("Error creating social media services: {e}")))?;
+    create_social_media_services(
+        pool,
+        conn,
+        social_media_to_create,
+        form.clone(),
+        artist.clone(),
+    )
+    .await
+    .map_err(|e| ServerFnError::new(format!("Error creating social media services: {e}")))?;
 
     update_social_media_services(
-        pool,
+        conn,
         social_media_to_update,
         existing_social_media_services.clone(),
         form.clone(),
@@ -308,14 +369,14 @@ async fn handle_social_media_services(
     .map_err(|e| ServerFnError::new(format!("Error updating social media services: {e}")))?;
 
     delete_social_media_services(
-        pool,
+        conn,
         social_media_to_delete,
         existing_social_media_services.clone(),
         artist.clone(),
     )
     .await
     .map_err(|e| ServerFnError::new(format!("Error deleting social media services: {e}")))?;
-    Ok(())
+    Ok(changes)
 }
 
 fn categorise_social_media_services(
@@ -360,25 +421,32 @@ fn categorise_social_media_services(
 
 async fn create_social_media_services(
     pool: &PgPool,
+    conn: &mut PgConnection,
     social_media_services: Vec<SocialMedia>,
     form: LinksForm,
     artist: Artist,
 ) -> Result<(), ServerFnError> {
     for platform in social_media_services {
-        SocialMediaService::create(
-            pool,
-            artist.id,
-            platform.clone(),
-            form.clone().from_social_media(&platform),
-        )
-        .await
-        .map_err(|e| ServerFnError::new(format!("Error creating social media service: {e}")))?;
+        let service = SocialMediaService {
+            artist_id: artist.id,
+            url: form.clone().from_social_media(&platform),
+            platform,
+            ..Default::default()
+        };
+        service
+            .validate(pool)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Error creating social media service: {e}")))?;
+        service
+            .insert(conn)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Error creating social media service: {e}")))?;
     }
     Ok(())
 }
 
 async fn update_social_media_services(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     social_media_services: Vec<SocialMedia>,
     mut existing_social_media_services: Vec<SocialMediaService>,
     form: LinksForm,
@@ -398,7 +466,7 @@ async fn update_social_media_services(
             .bind(url.clone())
             .bind(artist.id)
             .bind(service.platform.clone())
-            .execute(pool)
+            .execute(&mut *conn)
             .await
             {
                 Ok(_) => (),
@@ -416,7 +484,7 @@ async fn update_social_media_services(
 }
 
 async fn delete_social_media_services(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     social_media_services: Vec<SocialMedia>,
     mut existing_social_media_services: Vec<SocialMediaService>,
     artist: Artist,
@@ -430,7 +498,7 @@ async fn delete_social_media_services(
             sqlx::query("DELETE FROM social_media WHERE artist_id = $1 AND platform = $2")
                 .bind(artist.id)
                 .bind(&platform)
-                .execute(pool)
+                .execute(&mut *conn)
                 .await
                 .map_err(|e| {
                     tracing::error!("{e}");
@@ -476,6 +544,7 @@ mod tests {
         if let Ok(LinksResult {
             music_services,
             social_media_services,
+            ..
         }) = result
         {
             assert_eq!(music_services.len(), 2);
@@ -510,6 +579,7 @@ mod tests {
         if let Ok(LinksResult {
             music_services,
             social_media_services,
+            ..
         }) = result
         {
             assert!(music_services.is_empty());
@@ -553,6 +623,37 @@ mod tests {
         }
     }
 
+    #[sqlx::test]
+    async fn test_update_links_artist_manager(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+
+        let form = LinksForm {
+            artist_slug: artist.slug.clone(),
+            spotify: "https://spotify.com/artist".to_string(),
+            ..Default::default()
+        };
+        let result = update_links_service(&pool, Some(&manager), form)
+            .await
+            .unwrap();
+        assert_eq!(result.music_services.len(), 1);
+
+        let form = LinksForm {
+            artist_slug: other_artist.slug.clone(),
+            spotify: "https://spotify.com/artist".to_string(),
+            ..Default::default()
+        };
+        let result = update_links_service(&pool, Some(&manager), form).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+
     #[sqlx::test]
     async fn test_update_links_with_social_media(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
@@ -571,6 +672,7 @@ mod tests {
         if let Ok(LinksResult {
             music_services,
             social_media_services,
+            ..
         }) = result
         {
             assert!(music_services.is_empty());
@@ -606,6 +708,7 @@ mod tests {
         if let Ok(LinksResult {
             music_services,
             social_media_services,
+            ..
         }) = result
         {
             assert_eq!(music_services.len(), 2);
@@ -623,9 +726,90 @@ mod tests {
         }
     }
 
+    #[sqlx::test]
+    async fn test_update_links_reports_changes(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        MusicService::create(
+            &pool,
+            artist.id,
+            Platform::AppleMusic,
+            "https://apple.com/artist".to_string(),
+        )
+        .await
+        .unwrap();
+        SocialMediaService::create(
+            &pool,
+            artist.id,
+            SocialMedia::Twitter,
+            "https://twitter.com/artist".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let form = LinksForm {
+            artist_slug: artist.slug.clone(),
+            spotify: "https://spotify.com/artist".to_string(),
+            apple_music: "https://apple.com/artist/changed".to_string(),
+            ..Default::default()
+        };
+        let result = update_links_service(&pool, Some(&user), form)
+            .await
+            .unwrap();
+
+        assert_eq!(result.changes.len(), 3);
+        assert!(result.changes.contains(&LinkChange {
+            platform: Platform::Spotify.to_string(),
+            action: LinkAction::Created,
+        }));
+        assert!(result.changes.contains(&LinkChange {
+            platform: Platform::AppleMusic.to_string(),
+            action: LinkAction::Updated,
+        }));
+        assert!(result.changes.contains(&LinkChange {
+            platform: SocialMedia::Twitter.to_string(),
+            action: LinkAction::Deleted,
+        }));
+    }
+
+    #[sqlx::test]
+    async fn test_update_links_rolls_back_on_failure(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        sqlx::query(
+            "ALTER TABLE social_media ADD CONSTRAINT test_reject_url CHECK (url <> 'https://reject.example.com')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let form = LinksForm {
+            artist_slug: artist.slug.clone(),
+            spotify: "https://spotify.com/artist".to_string(),
+            twitter: "https://reject.example.com".to_string(),
+            ..Default::default()
+        };
+        let result = update_links_service(&pool, Some(&user), form).await;
+
+        assert!(result.is_err());
+        let music_services = MusicService::list_by_artist(&pool, artist.id)
+            .await
+            .unwrap();
+        assert!(music_services.is_empty());
+        let social_media_services = SocialMediaService::list_by_artist(&pool, artist.id)
+            .await
+            .unwrap();
+        assert!(social_media_services.is_empty());
+    }
+
     #[sqlx::test]
     async fn test_handle_music_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let form = LinksForm {
             artist_slug: artist.slug.clone(),
             spotify: "https://spotify.com/artist".to_string(),
@@ -633,7 +817,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = handle_music_services(&pool, form, artist.clone()).await;
+        let result = handle_music_services(&pool, &mut conn, form, artist.clone()).await;
         assert!(result.is_ok());
 
         let music_services = MusicService::list_by_artist(&pool, artist.id)
@@ -645,13 +829,14 @@ mod tests {
     #[sqlx::test]
     async fn test_handle_social_media_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let form = LinksForm {
             artist_slug: artist.slug.clone(),
             twitter: "https://twitter.com/artist".to_string(),
             instagram: "https://instagram.com/artist".to_string(),
             ..Default::default()
         };
-        let result = handle_social_media_services(&pool, form, artist.clone()).await;
+        let result = handle_social_media_services(&pool, &mut conn, form, artist.clone()).await;
         assert!(result.is_ok());
         let social_media_services = SocialMediaService::list_by_artist(&pool, artist.id)
             .await
@@ -763,13 +948,20 @@ mod tests {
     #[sqlx::test]
     async fn test_create_music_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let form = LinksForm {
             artist_slug: artist.slug.clone(),
             spotify: "https://spotify.com/artist".to_string(),
             ..Default::default()
         };
-        let result =
-            create_music_services(&pool, vec![&Platform::Spotify], form, artist.clone()).await;
+        let result = create_music_services(
+            &pool,
+            &mut conn,
+            vec![&Platform::Spotify],
+            form,
+            artist.clone(),
+        )
+        .await;
         assert!(result.is_ok());
         let music_services = MusicService::list_by_artist(&pool, artist.id)
             .await
@@ -781,14 +973,20 @@ mod tests {
     #[sqlx::test]
     async fn test_create_social_media_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let form = LinksForm {
             artist_slug: artist.slug.clone(),
             twitter: "https://twitter.com/artist".to_string(),
             ..Default::default()
         };
-        let result =
-            create_social_media_services(&pool, vec![SocialMedia::Twitter], form, artist.clone())
-                .await;
+        let result = create_social_media_services(
+            &pool,
+            &mut conn,
+            vec![SocialMedia::Twitter],
+            form,
+            artist.clone(),
+        )
+        .await;
         assert!(result.is_ok());
         let social_media_services = SocialMediaService::list_by_artist(&pool, artist.id)
             .await
@@ -800,6 +998,7 @@ mod tests {
     #[sqlx::test]
     async fn test_update_music_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let existing_service = MusicService::create(
             &pool,
             artist.id,
@@ -814,7 +1013,7 @@ mod tests {
             ..Default::default()
         };
         let result = update_music_services(
-            &pool,
+            &mut conn,
             vec![&Platform::Spotify],
             vec![existing_service],
             form,
@@ -833,6 +1032,7 @@ mod tests {
     #[sqlx::test]
     async fn test_update_social_media_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let existing_service = SocialMediaService::create(
             &pool,
             artist.id,
@@ -847,7 +1047,7 @@ mod tests {
             ..Default::default()
         };
         let result = update_social_media_services(
-            &pool,
+            &mut conn,
             vec![SocialMedia::Twitter],
             vec![existing_service],
             form,
@@ -869,6 +1069,7 @@ mod tests {
     #[sqlx::test]
     async fn test_delete_music_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let existing_service = MusicService::create(
             &pool,
             artist.id,
@@ -878,7 +1079,7 @@ mod tests {
         .await
         .unwrap();
         let result = delete_music_services(
-            &pool,
+            &mut conn,
             vec![&Platform::Spotify],
             vec![existing_service],
             artist.clone(),
@@ -894,6 +1095,7 @@ mod tests {
     #[sqlx::test]
     async fn test_delete_social_media_services(pool: PgPool) {
         let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let mut conn = pool.acquire().await.unwrap();
         let existing_service = SocialMediaService::create(
             &pool,
             artist.id,
@@ -903,7 +1105,7 @@ mod tests {
         .await
         .unwrap();
         let result = delete_social_media_services(
-            &pool,
+            &mut conn,
             vec![SocialMedia::Twitter],
             vec![existing_service],
             artist.clone(),
@@ -0,0 +1,140 @@
+//! Email delivery
+//!
+//! Services send email through the `Mailer` trait, so the way it is delivered can be swapped without changing them.
+//! Emails can hold secrets such as password reset tokens, so mailers must never log their addresses or bodies.
+//! Until a mailer is installed with `set_mailer`, every email is refused.
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// An email to send
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Email {
+    /// The address to send the email to
+    pub to: String,
+    /// The subject of the email
+    pub subject: String,
+    /// The body of the email, either plain text or HTML
+    pub body: String,
+}
+
+/// Somewhere to send email
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Send an email
+    ///
+    /// # Errors
+    /// If the email cannot be sent, return an error
+    async fn send(&self, email: &Email) -> anyhow::Result<()>;
+}
+
+/// A mailer that refuses every email, used until a mailer is installed
+#[derive(Debug, Default)]
+pub struct DisabledMailer;
+
+#[async_trait]
+impl Mailer for DisabledMailer {
+    async fn send(&self, _email: &Email) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!("Email delivery is not configured."))
+    }
+}
+
+/// A mailer that keeps the emails it is sent in memory, for tests and development
+#[derive(Debug, Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<Email>>,
+    refused: Vec<String>,
+}
+
+impl MemoryMailer {
+    /// Create a mailer that refuses emails to some addresses, to simulate failed deliveries
+    #[must_use]
+    pub fn refusing(addresses: Vec<String>) -> Self {
+        Self {
+            sent: Mutex::default(),
+            refused: addresses,
+        }
+    }
+
+    /// The emails that have been sent, oldest first
+    /// The list is still returned if another thread panicked while sending an email.
+    #[must_use]
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, email: &Email) -> anyhow::Result<()> {
+        if self.refused.contains(&email.to) {
+            return Err(anyhow::anyhow!("The email address was refused."));
+        }
+        self.sent
+            .lock()
+            .map_err(|_| anyhow::anyhow!("The mailer is unavailable."))?
+            .push(email.clone());
+        Ok(())
+    }
+}
+
+/// The installed mailer
+static MAILER: OnceLock<Arc<dyn Mailer>> = OnceLock::new();
+
+/// Install the mailer used to send email
+/// This can only be done once, normally when the application starts.
+///
+/// # Errors
+/// If a mailer has already been installed, return an error
+pub fn set_mailer(mailer: Arc<dyn Mailer>) -> anyhow::Result<()> {
+    MAILER
+        .set(mailer)
+        .map_err(|_| anyhow::anyhow!("A mailer has already been installed."))
+}
+
+/// The mailer used to send email
+/// If no mailer has been installed, every email is refused.
+#[must_use]
+pub fn mailer() -> Arc<dyn Mailer> {
+    MAILER
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(DisabledMailer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email(to: &str) -> Email {
+        Email {
+            to: to.to_string(),
+            subject: "Subject".to_string(),
+            body: "Body".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_mailer() {
+        let result = DisabledMailer.send(&email("test@example.com")).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Email delivery is not configured."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_mailer() {
+        let mailer = MemoryMailer::refusing(vec!["refused@example.com".to_string()]);
+
+        mailer.send(&email("test@example.com")).await.unwrap();
+        let result = mailer.send(&email("refused@example.com")).await;
+
+        assert!(result.is_err());
+        assert_eq!(mailer.sent(), vec![email("test@example.com")]);
+    }
+}
@@ -36,7 +36,7 @@ pub async fn admin_menu(pool: &PgPool, user: Option<&User>) -> Result<AdminMenu,
         ServerFnError::new("Could not retrieve record label, try again later")
     })?;
 
-    let artists = Artist::list_by_record_label(pool, record_label.id, include_hidden)
+    let artists = Artist::list_by_record_label(pool, record_label.id, include_hidden, false)
         .await
         .map_err(|x| {
             tracing::error!("Error while getting artists: {x:?}");
@@ -50,6 +50,7 @@ pub async fn admin_menu(pool: &PgPool, user: Option<&User>) -> Result<AdminMenu,
             artist.id,
             record_label.id,
             include_hidden,
+            None,
         )
         .await
         .map_err(|x| {
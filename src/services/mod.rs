@@ -3,10 +3,27 @@
 pub mod artist;
 pub mod artists;
 pub mod authentication_helpers;
+pub mod dashboard;
+pub mod digest;
+pub mod errors;
+pub mod export;
 pub mod files;
+pub mod homepage;
+#[cfg(feature = "musicbrainz")]
+pub mod import;
 pub mod links;
+pub mod mailer;
 pub mod menu;
 pub mod page;
+pub mod placement;
+pub mod publishing;
+pub mod read_only;
 pub mod release;
+pub mod search;
+pub mod sitemap;
+pub mod slug;
+pub mod stats;
+pub mod tags;
+pub mod timeout;
 pub mod track;
 pub mod user;
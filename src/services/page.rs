@@ -2,7 +2,10 @@
 use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
-use super::authentication_helpers::user_with_permissions;
+use super::{
+    authentication_helpers::user_with_permissions, errors::fetch_or_not_found,
+    publishing::Republish, read_only::ensure_writable,
+};
 use crate::forms::page::{CreatePageForm, UpdatePageForm};
 use crate::models::{auth::User, page::Page};
 use crate::routes::page::PageResult;
@@ -25,15 +28,10 @@ pub async fn get_page_service(
     slug: String,
 ) -> Result<PageResult, ServerFnError> {
     let include_hidden = user.is_some_and(|user| user.permissions.contains("label_owner"));
-    Ok(PageResult {
-        page: Page::get_by_slug(pool, slug, include_hidden)
-            .await
-            .map_err(|e| {
-                let err = format!("Error while getting page: {e:?}");
-                tracing::error!("{err}");
-                ServerFnError::new(e)
-            })?,
-    })
+    Ok(PageResult::new(fetch_or_not_found(
+        Page::get_by_slug(pool, slug, include_hidden).await,
+        "Error while getting page",
+    )?))
 }
 
 /// Restore a soft deleted page
@@ -42,6 +40,7 @@ pub async fn get_page_service(
 /// pool: `PgPool` - The database connection pool
 /// user: Option<&User> - The user deleting the page
 /// slug: String - The slug of the page
+/// republish: `Republish` - Keep the original `published_at`, restore as a draft, or publish at a given date
 ///
 /// # Returns
 /// Result<`PageResult`, `ServerFnError`> - The restored page
@@ -54,28 +53,28 @@ pub async fn restore_page_service(
     pool: &PgPool,
     user: Option<&User>,
     slug: String,
+    republish: Republish,
 ) -> Result<PageResult, ServerFnError> {
     match user_with_permissions(user, vec!["admin", "label_owner"]) {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
     let include_hidden = true; // We have already checked permissions, so we can include hidden pages
 
-    let mut page = Page::get_by_slug(pool, slug, include_hidden)
-        .await
-        .map_err(|e| {
-            let err = format!("Error while getting page: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?;
+    let mut page = fetch_or_not_found(
+        Page::get_by_slug(pool, slug, include_hidden).await,
+        "Error while getting page",
+    )?;
     page.deleted_at = None;
+    page.published_at = republish.published_at(page.published_at);
     page.clone().update(pool).await.map_err(|e| {
         let err = format!("Error while restoring page: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
 
-    Ok(PageResult { page })
+    Ok(PageResult::new(page))
 }
 
 /// Create a new page
@@ -102,9 +101,10 @@ pub async fn create_page_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    Ok(PageResult {
-        page: Page::create(
+    Ok(PageResult::new(
+        Page::create(
             pool,
             page_form.name,
             page_form.description,
@@ -118,7 +118,7 @@ pub async fn create_page_service(
             tracing::error!("{err}");
             ServerFnError::new(e)
         })?,
-    })
+    ))
 }
 
 /// Update an page
@@ -145,27 +145,23 @@ pub async fn update_page_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
     let include_hidden = true; // We have already checked permissions, so we can include hidden pages
 
-    let mut page = Page::get_by_slug(pool, page_form.slug, include_hidden)
-        .await
-        .map_err(|e| {
-            let err = format!("Error while getting page: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?;
+    let mut page = fetch_or_not_found(
+        Page::get_by_slug(pool, page_form.slug, include_hidden).await,
+        "Error while getting page",
+    )?;
     page.name = page_form.name;
     page.description = page_form.description;
     page.body = page_form.body;
     page.published_at = page_form.published_at;
 
-    Ok(PageResult {
-        page: page.update(pool).await.map_err(|e| {
-            let err = format!("Error while updating page: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    Ok(PageResult::new(page.update(pool).await.map_err(|e| {
+        let err = format!("Error while updating page: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?))
 }
 
 /// Soft delete an page
@@ -191,23 +187,19 @@ pub async fn delete_page_service(
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
     let include_hidden = true; // We have already checked permissions, so we can include hidden pages
 
-    let page = Page::get_by_slug(pool, slug, include_hidden)
-        .await
-        .map_err(|e| {
-            let err = format!("Error while getting page: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?;
+    let page = fetch_or_not_found(
+        Page::get_by_slug(pool, slug, include_hidden).await,
+        "Error while getting page",
+    )?;
 
-    Ok(PageResult {
-        page: page.delete(pool).await.map_err(|e| {
-            let err = format!("Error while deleting page: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    Ok(PageResult::new(page.delete(pool).await.map_err(|e| {
+        let err = format!("Error while deleting page: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?))
 }
 
 #[cfg(test)]
@@ -229,6 +221,10 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(page, page_by_slug.page);
+        assert_eq!(
+            page_by_slug.body_html,
+            "<h1>A page for testing purposes with the id of 1</h1>"
+        );
     }
 
     #[sqlx::test]
@@ -599,9 +595,10 @@ mod tests {
 
         let page = create_test_page(&pool, 1, None).await.unwrap();
         let page = page.delete(&pool).await.unwrap();
-        let restored_page = restore_page_service(&pool, Some(&user), page.slug.clone())
-            .await
-            .unwrap();
+        let restored_page =
+            restore_page_service(&pool, Some(&user), page.slug.clone(), Republish::Keep)
+                .await
+                .unwrap();
 
         assert!(restored_page.page.deleted_at.is_none());
     }
@@ -613,7 +610,8 @@ mod tests {
             .await
             .unwrap();
 
-        let restored_page = restore_page_service(&pool, Some(&user), "missing".to_string()).await;
+        let restored_page =
+            restore_page_service(&pool, Some(&user), "missing".to_string(), Republish::Keep).await;
         assert!(restored_page.is_err());
         assert_eq!(
             restored_page.unwrap_err().to_string(),
@@ -625,8 +623,13 @@ mod tests {
     async fn test_restore_page_service_no_user(pool: PgPool) {
         let page = create_test_page(&pool, 1, None).await.unwrap();
         let page = page.delete(&pool).await.unwrap();
-        let restored_page =
-            restore_page_service(&pool, Some(&User::default()), page.slug.clone()).await;
+        let restored_page = restore_page_service(
+            &pool,
+            Some(&User::default()),
+            page.slug.clone(),
+            Republish::Keep,
+        )
+        .await;
         assert!(restored_page.is_err());
         assert_eq!(
             restored_page.unwrap_err().to_string(),
@@ -641,7 +644,8 @@ mod tests {
             .unwrap();
         let page = create_test_page(&pool, 1, None).await.unwrap();
         let page = page.delete(&pool).await.unwrap();
-        let restored_page = restore_page_service(&pool, Some(&user), page.slug.clone()).await;
+        let restored_page =
+            restore_page_service(&pool, Some(&user), page.slug.clone(), Republish::Keep).await;
         assert!(restored_page.is_err());
         assert_eq!(
             restored_page.unwrap_err().to_string(),
@@ -0,0 +1,279 @@
+//! Playlist placement service module
+//!
+//! Placements record the playlists a release or track has been featured on, for reporting.
+//! They are only available to label owners.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::{
+    authentication_helpers::user_with_permissions, errors::fetch_or_not_found,
+    read_only::ensure_writable,
+};
+use crate::models::{
+    auth::User,
+    placement::{Placement, PlacementEntity},
+};
+use crate::routes::placement::PlacementsResult;
+
+/// List the playlist placements for a release or track
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user listing the placements
+/// `entity_type`: `PlacementEntity` - The type of entity
+/// `entity_id`: i64 - The ID of the release or track
+///
+/// # Returns
+/// Result<`PlacementsResult`, `ServerFnError`> - The placements, most recently added first
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the placements cannot be found, return an error
+pub async fn list_placements_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    entity_type: PlacementEntity,
+    entity_id: i64,
+) -> Result<PlacementsResult, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let placements = Placement::list_by_entity(pool, entity_type, entity_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting placements: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(PlacementsResult { placements })
+}
+
+/// Add a playlist placement to a release or track
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user adding the placement
+/// `entity_type`: `PlacementEntity` - The type of entity
+/// `entity_id`: i64 - The ID of the release or track
+/// `playlist_name`: String - The name of the playlist
+/// url: String - The URL of the playlist
+///
+/// # Returns
+/// Result<`Placement`, `ServerFnError`> - The added placement
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the playlist name or URL is invalid, return an error
+/// If the release or track cannot be found, return an error
+pub async fn add_placement_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    entity_type: PlacementEntity,
+    entity_id: i64,
+    playlist_name: String,
+    url: String,
+) -> Result<Placement, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    Placement::create(pool, entity_type, entity_id, playlist_name, url)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while adding placement: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })
+}
+
+/// Remove a playlist placement
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user removing the placement
+/// id: i64 - The ID of the placement
+///
+/// # Returns
+/// Result<`Placement`, `ServerFnError`> - The removed placement
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the placement cannot be found or removed, return an error
+pub async fn remove_placement_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    id: i64,
+) -> Result<Placement, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let placement = fetch_or_not_found(
+        Placement::get_by_id(pool, id).await,
+        "Error while getting placement",
+    )?;
+    placement.delete(pool).await.map_err(|e| {
+        let err = format!("Error while removing placement: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{
+        create_test_release, create_test_track, create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_add_placement_service(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let placement = add_placement_service(
+            &pool,
+            Some(&user),
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(placement.entity_id, release.id);
+        assert_eq!(placement.playlist_name, "New Music Friday");
+    }
+
+    #[sqlx::test]
+    async fn test_add_placement_service_invalid_url(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let result = add_placement_service(
+            &pool,
+            Some(&user),
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "ftp://example.com/playlist".to_string(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Playlist URL must be a valid URL."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_add_placement_service_no_permission(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = add_placement_service(
+            &pool,
+            Some(&user),
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_list_placements_service(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let placement = add_placement_service(
+            &pool,
+            Some(&user),
+            PlacementEntity::Track,
+            track.id,
+            "Chill Vibes".to_string(),
+            "https://open.spotify.com/playlist/2".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let result = list_placements_service(&pool, Some(&user), PlacementEntity::Track, track.id)
+            .await
+            .unwrap();
+
+        assert_eq!(result.placements, vec![placement]);
+        let result =
+            list_placements_service(&pool, Some(&user), PlacementEntity::Release, release.id)
+                .await
+                .unwrap();
+        assert!(result.placements.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_remove_placement_service(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let placement = add_placement_service(
+            &pool,
+            Some(&user),
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let removed = remove_placement_service(&pool, Some(&user), placement.id)
+            .await
+            .unwrap();
+
+        assert_eq!(removed, placement);
+        let result =
+            list_placements_service(&pool, Some(&user), PlacementEntity::Release, release.id)
+                .await
+                .unwrap();
+        assert!(result.placements.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_remove_placement_service_not_found(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let result = remove_placement_service(&pool, Some(&user), 1).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Could not find placement with id 1."
+        );
+    }
+}
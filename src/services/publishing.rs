@@ -0,0 +1,156 @@
+//! Publishing service module
+//!
+//! Records when scheduled releases and tracks first go live.
+//! Publishing through an update stamps `first_published_at` straight away, this catches the ones that were scheduled.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use crate::models::{release::Release, track::Track};
+
+/// What to do with the published date of a restored artist, release, track or page.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Republish {
+    /// Keep the published date it had when it was deleted.
+    #[default]
+    Keep,
+    /// Restore it as a draft.
+    Draft,
+    /// Publish it at the given date.
+    At(chrono::DateTime<chrono::Utc>),
+}
+
+impl Republish {
+    /// Read the restore form, where `republish` is a checkbox and `published_at` the date to publish at.
+    /// Without `republish` the date is kept. With it, an empty `published_at` restores as a draft.
+    #[must_use]
+    pub const fn from_form(
+        republish: bool,
+        published_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Self {
+        match (republish, published_at) {
+            (false, _) => Self::Keep,
+            (true, None) => Self::Draft,
+            (true, Some(published_at)) => Self::At(published_at),
+        }
+    }
+
+    /// The published date once restored, given the date it had when it was deleted.
+    #[must_use]
+    pub const fn published_at(
+        self,
+        current: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            Self::Keep => current,
+            Self::Draft => None,
+            Self::At(published_at) => Some(published_at),
+        }
+    }
+}
+
+/// How often to check for scheduled releases and tracks that have gone live.
+pub const FIRST_PUBLISHED_INTERVAL_SECONDS: u64 = 60;
+
+/// Stamp the first published date on releases and tracks whose scheduled publish date has passed
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+///
+/// # Returns
+/// Result<u64, `ServerFnError`> - The number of releases and tracks stamped
+///
+/// # Errors
+/// If the releases or tracks cannot be updated, return an error
+pub async fn stamp_first_published(pool: &PgPool) -> Result<u64, ServerFnError> {
+    let releases = Release::stamp_first_published(pool).await.map_err(|e| {
+        let err = format!("Error while stamping releases: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let tracks = Track::stamp_first_published(pool).await.map_err(|e| {
+        let err = format!("Error while stamping tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    Ok(releases + tracks)
+}
+
+/// Stamp the first published date on a schedule
+/// Runs forever, checking every `FIRST_PUBLISHED_INTERVAL_SECONDS` seconds.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+pub async fn schedule_first_published_stamps(pool: PgPool) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        FIRST_PUBLISHED_INTERVAL_SECONDS,
+    ));
+
+    loop {
+        interval.tick().await;
+        match stamp_first_published(&pool).await {
+            Ok(0) => (),
+            Ok(stamped) => tracing::info!("Stamped {stamped} releases and tracks as published."),
+            Err(e) => tracing::error!("Error while stamping first published dates: {e:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{create_test_release, create_test_track};
+
+    #[test]
+    fn test_republish_from_form() {
+        let now = chrono::Utc::now();
+        assert_eq!(Republish::from_form(false, None), Republish::Keep);
+        assert_eq!(Republish::from_form(false, Some(now)), Republish::Keep);
+        assert_eq!(Republish::from_form(true, None), Republish::Draft);
+        assert_eq!(Republish::from_form(true, Some(now)), Republish::At(now));
+    }
+
+    #[test]
+    fn test_republish_published_at() {
+        let then = chrono::Utc::now() - chrono::Duration::days(1);
+        let now = chrono::Utc::now();
+        assert_eq!(Republish::Keep.published_at(Some(then)), Some(then));
+        assert_eq!(Republish::Keep.published_at(None), None);
+        assert_eq!(Republish::Draft.published_at(Some(then)), None);
+        assert_eq!(Republish::At(now).published_at(Some(then)), Some(now));
+        assert_eq!(Republish::At(now).published_at(None), Some(now));
+    }
+
+    #[sqlx::test]
+    async fn test_stamp_first_published(pool: PgPool) {
+        // The test records are inserted directly, as if their scheduled publish date has just passed
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        assert!(release.first_published_at.is_none());
+
+        let stamped = stamp_first_published(&pool).await.unwrap();
+
+        assert_eq!(stamped, 2);
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.first_published_at, release.published_at);
+        let track = Track::get_by_id(&pool, track.id).await.unwrap();
+        assert_eq!(track.first_published_at, track.published_at);
+        assert_eq!(stamp_first_published(&pool).await.unwrap(), 0);
+    }
+
+    #[sqlx::test]
+    async fn test_stamp_first_published_skips_scheduled(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+
+        let stamped = stamp_first_published(&pool).await.unwrap();
+
+        assert_eq!(stamped, 0);
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.first_published_at.is_none());
+    }
+}
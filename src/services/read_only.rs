@@ -0,0 +1,215 @@
+//! Read-only mode
+//!
+//! Demo deployments can be switched to read-only, blocking every create, update and delete service.
+//! Reads and logging in are unaffected.
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::authentication_helpers::user_with_permissions;
+use crate::config::maintenance::DEFAULT_MAINTENANCE_MESSAGE;
+use crate::models::{auth::User, site_settings::SiteSettings};
+
+/// Check the deployment can be written to
+/// Every service that creates, updates or deletes data should call this first.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+///
+/// # Errors
+/// If the deployment is read-only, return an error
+/// If the site settings cannot be found, return an error
+pub async fn ensure_writable(pool: &PgPool) -> Result<(), ServerFnError> {
+    let settings = SiteSettings::get(pool).await.map_err(|e| {
+        let err = format!("Error while getting site settings: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    if settings.read_only {
+        return Err(ServerFnError::new("This deployment is read-only."));
+    }
+    Ok(())
+}
+
+/// Get the site settings
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+///
+/// # Returns
+/// Result<`SiteSettings`, `ServerFnError`> - The site settings
+///
+/// # Errors
+/// If the site settings cannot be found, return an error
+pub async fn get_site_settings_service(pool: &PgPool) -> Result<SiteSettings, ServerFnError> {
+    SiteSettings::get(pool).await.map_err(|e| {
+        let err = format!("Error while getting site settings: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })
+}
+
+/// Turn read-only mode on or off
+/// This is always allowed, otherwise read-only mode could never be turned off.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user changing the setting
+/// `read_only`: bool - Should the deployment be read-only
+///
+/// # Returns
+/// Result<`SiteSettings`, `ServerFnError`> - The updated site settings
+///
+/// # Errors
+/// If the user is not an admin, return an error
+/// If the site settings cannot be updated, return an error
+pub async fn set_read_only_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    read_only: bool,
+) -> Result<SiteSettings, ServerFnError> {
+    let user = match user_with_permissions(user, vec!["admin"]) {
+        Ok(user) => user,
+        Err(e) => return Err(e),
+    };
+
+    let settings = SiteSettings::set_read_only(pool, read_only)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while updating site settings: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    tracing::info!("Read-only mode set to {read_only} by {}", user.username);
+    Ok(settings)
+}
+
+/// Get the maintenance banner message, if the banner should be shown
+/// The banner is shown during maintenance, or whenever the deployment is read-only.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `maintenance_mode`: bool - Is the site down for maintenance
+/// `maintenance_message`: Option<String> - The configured message, already sanitised to plain text
+///
+/// # Returns
+/// Result<Option<String>, `ServerFnError`> - The message, or None if the banner should be hidden
+///
+/// # Errors
+/// If the site settings cannot be found, return an error
+pub async fn maintenance_banner_service(
+    pool: &PgPool,
+    maintenance_mode: bool,
+    maintenance_message: Option<String>,
+) -> Result<Option<String>, ServerFnError> {
+    let settings = get_site_settings_service(pool).await?;
+    if !maintenance_mode && !settings.read_only {
+        return Ok(None);
+    }
+    Ok(Some(maintenance_message.unwrap_or_else(|| {
+        DEFAULT_MAINTENANCE_MESSAGE.to_string()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forms::artist::CreateArtistForm;
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_user_with_permissions,
+    };
+    use crate::services::artist::{create_artist_service, get_artist_service};
+
+    fn artist_form(label_id: i64) -> CreateArtistForm {
+        CreateArtistForm {
+            name: "Test Artist".to_string(),
+            description: "This is a test artist".to_string(),
+            website: "https://example.com".to_string(),
+            label_id,
+            published_at: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_read_only_blocks_mutations(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        set_read_only_service(&pool, Some(&user), true)
+            .await
+            .unwrap();
+
+        let result = create_artist_service(&pool, Some(&user), artist_form(record_label.id)).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: This deployment is read-only."
+        );
+
+        // Reads still work
+        let result = get_artist_service(&pool, None, artist.slug.clone()).await;
+        assert_eq!(result.unwrap().artist, artist);
+    }
+
+    #[sqlx::test]
+    async fn test_read_only_toggled_off(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        set_read_only_service(&pool, Some(&user), true)
+            .await
+            .unwrap();
+        let settings = set_read_only_service(&pool, Some(&user), false)
+            .await
+            .unwrap();
+        assert!(!settings.read_only);
+
+        let result = create_artist_service(&pool, Some(&user), artist_form(record_label.id)).await;
+        assert!(result.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_set_read_only_service_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
+            .await
+            .unwrap();
+
+        let result = set_read_only_service(&pool, Some(&user), true).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+        assert!(!get_site_settings_service(&pool).await.unwrap().read_only);
+    }
+
+    #[sqlx::test]
+    async fn test_maintenance_banner_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin"])
+            .await
+            .unwrap();
+        let message = Some("Back soon.".to_string());
+
+        let banner = maintenance_banner_service(&pool, false, message.clone()).await;
+        assert_eq!(banner.unwrap(), None);
+
+        set_read_only_service(&pool, Some(&user), true)
+            .await
+            .unwrap();
+        let banner = maintenance_banner_service(&pool, false, message.clone()).await;
+        assert_eq!(banner.unwrap(), message);
+        let banner = maintenance_banner_service(&pool, false, None).await;
+        assert_eq!(banner.unwrap().unwrap(), DEFAULT_MAINTENANCE_MESSAGE);
+    }
+
+    #[sqlx::test]
+    async fn test_maintenance_banner_service_maintenance_mode(pool: PgPool) {
+        let banner = maintenance_banner_service(&pool, true, Some("Back soon.".to_string())).await;
+        assert_eq!(banner.unwrap(), Some("Back soon.".to_string()));
+    }
+}
@@ -2,10 +2,27 @@
 use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
-use super::authentication_helpers::user_with_permissions;
+use super::{
+    artist::get_primary_artist,
+    authentication_helpers::{user_can_manage_artist, user_with_permissions},
+    errors::{ServiceError, fetch_or_not_found},
+    files::og_image::{OgImageEntity, ensure_og_image},
+    publishing::Republish,
+    read_only::ensure_writable,
+};
 use crate::forms::release::{CreateReleaseForm, UpdateReleaseForm};
-use crate::models::{artist::Artist, auth::User, release::Release};
-use crate::routes::release::{ReleaseResult, ReleasesResult};
+use crate::models::{
+    approval::Approval,
+    artist::Artist,
+    auth::User,
+    placement::{Placement, PlacementEntity},
+    record_label::RecordLabel,
+    release::{ArtistRole, Release, ReleaseError, ReleaseStatus},
+    track_with_artists::TrackWithArtists,
+};
+use crate::routes::bulk::BulkResult;
+use crate::routes::release::{PublishResult, ReleaseResult, ReleasesResult};
+use crate::state::RecordLabelCache;
 
 /// Get the next scheduled release, optionally filtered by artist slug.
 ///
@@ -44,12 +61,22 @@ pub async fn get_next_scheduled_release_service(
                 tracing::error!("{err}");
                 ServerFnError::new(e)
             })?;
+            let credits = release.get_credits(pool).await.map_err(|e| {
+                let err = format!("Error while getting credits: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?;
+
+            let primary_artist =
+                get_primary_artist(pool, release.primary_artist_id, &artists).await?;
 
-            Ok(Some(ReleaseResult {
+            Ok(Some(ReleaseResult::new(
                 release,
                 artists,
                 tracks,
-            }))
+                credits,
+                &primary_artist,
+            )))
         }
         Ok(None) => Ok(None),
         Err(e) => Err(e),
@@ -75,25 +102,22 @@ pub async fn get_releases_service(
     pool: &PgPool,
     user: Option<&User>,
     slug: String,
-) -> Result<ReleasesResult, ServerFnError> {
-    let artist = match Artist::get_by_slug(pool, slug).await {
-        Ok(artist) => artist,
-        Err(e) => {
-            let err = format!("Error while getting artist: {e:?}");
-            tracing::error!("get_releases_service {err}");
-            return Err(ServerFnError::new(e));
-        }
-    };
-
+) -> Result<ReleasesResult, ServiceError> {
     let include_hidden =
         user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
 
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, slug, include_hidden).await,
+        "Error while getting artist",
+    )?;
+
     Ok(ReleasesResult {
         releases: match Release::list_by_artist_and_record_label(
             pool,
             artist.id,
             artist.label_id,
             include_hidden,
+            None,
         )
         .await
         {
@@ -101,7 +125,9 @@ pub async fn get_releases_service(
             Err(e) => {
                 let err = format!("Error while getting releases: {e:?}");
                 tracing::error!("{err}");
-                return Err(ServerFnError::new(e));
+                return Err(ServiceError::Internal {
+                    message: e.to_string(),
+                });
             }
         },
     })
@@ -114,6 +140,7 @@ pub async fn get_releases_service(
 /// * `user` - The current user
 /// * `artist_slug` - The slug of the artist
 /// * `release_slug` - The slug of the release
+/// * `preview_token` - The token for previewing the release while it is under embargo
 ///
 /// # Returns
 /// The release for the artist
@@ -128,8 +155,12 @@ pub async fn get_release_service(
     user: Option<&User>,
     artist_slug: String,
     release_slug: String,
+    preview_token: Option<String>,
 ) -> Result<ReleaseResult, ServerFnError> {
-    let artist = match Artist::get_by_slug(pool, artist_slug).await {
+    let include_hidden =
+        user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
+
+    let artist = match Artist::get_by_slug(pool, artist_slug, include_hidden).await {
         Ok(artist) => artist,
         Err(e) => {
             let err = format!("get_release_service Error while getting artist: {e:?}");
@@ -138,22 +169,30 @@ pub async fn get_release_service(
         }
     };
 
-    let include_hidden =
-        user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
-
-    let release = Release::get_by_artist_and_record_label_and_slug(
-        pool,
-        artist.id,
-        artist.label_id,
-        release_slug.clone(),
-        include_hidden,
-    )
-    .await
-    .map_err(|e| {
-        let err = format!("Error while getting releases: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    // Embargoed releases are fetched with the hidden ones, then checked against the preview token
+    let release = fetch_or_not_found(
+        Release::get_by_artist_and_record_label_and_slug(
+            pool,
+            artist.id,
+            artist.label_id,
+            release_slug.clone(),
+            include_hidden || preview_token.is_some(),
+        )
+        .await
+        .and_then(|release| {
+            if include_hidden || release.is_visible_at(chrono::Utc::now(), preview_token.as_deref())
+            {
+                Ok(release)
+            } else {
+                Err(ReleaseError::NotFoundForArtist {
+                    slug: release_slug.clone(),
+                    artist_id: artist.id,
+                    record_label_id: artist.label_id,
+                })
+            }
+        }),
+        "Error while getting releases",
+    )?;
     let artists = release.get_artists(pool).await.map_err(|e| {
         let err = format!("Error while getting artists: {e:?}");
         tracing::error!("{err}");
@@ -164,16 +203,119 @@ pub async fn get_release_service(
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let placements = if include_hidden {
+        Placement::list_by_entity(pool, PlacementEntity::Release, release.id)
+            .await
+            .map_err(|e| {
+                let err = format!("Error while getting placements: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?
+    } else {
+        vec![]
+    };
+    let various_artists = release.get_various_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting various artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let remixers = release.get_remixers(pool).await.map_err(|e| {
+        let err = format!("Error while getting remixers: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let og_image_url = ensure_og_image(OgImageEntity::Release(release.clone())).await;
+
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
 
     Ok(ReleaseResult {
-        release,
-        artists,
-        tracks,
+        remixers,
+        various_artists,
+        placements,
+        og_image_url,
+        ..ReleaseResult::new(release, artists, tracks, credits, &primary_artist)
     })
 }
 
+/// Parse the artists from a release form, giving each their role
+/// The primary artist is `Primary`, the remixers are `Remixer` and everyone else is `Featured`.
+/// The primary artist is listed first if the form leaves them out, and stays `Primary` if they are also a remixer.
+#[cfg(feature = "ssr")]
+fn release_artists(
+    artist_ids: &str,
+    remixer_ids: &str,
+    primary_artist_id: i64,
+) -> Vec<(i64, ArtistRole)> {
+    let parse_ids = |ids: &str| {
+        ids.split(',')
+            .filter_map(|s| s.trim().parse::<i64>().ok())
+            .collect::<Vec<_>>()
+    };
+    let remixer_ids = parse_ids(remixer_ids);
+    let mut artists: Vec<(i64, ArtistRole)> = vec![];
+    for artist_id in parse_ids(artist_ids).into_iter().chain(remixer_ids.clone()) {
+        if artists.iter().any(|(id, _)| *id == artist_id) {
+            continue;
+        }
+        let role = if artist_id == primary_artist_id {
+            ArtistRole::Primary
+        } else if remixer_ids.contains(&artist_id) {
+            ArtistRole::Remixer
+        } else {
+            ArtistRole::Featured
+        };
+        artists.push((artist_id, role));
+    }
+    if !artists.iter().any(|(id, _)| *id == primary_artist_id) {
+        artists.insert(0, (primary_artist_id, ArtistRole::Primary));
+    }
+    artists
+}
+
+/// Can the user set a release's published date themselves
+/// Label owners can. Anyone else, such as an artist's manager, has to have the publish approved by a label owner.
+#[cfg(feature = "ssr")]
+fn can_publish_directly(user: &User) -> bool {
+    user.permissions.contains("label_owner")
+}
+
+/// Ask a label owner to approve publishing a release
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `release_id`: i64 - The ID of the release
+/// `requested_by`: i64 - The ID of the user asking for the release to be published
+///
+/// # Returns
+/// Result<`Approval`, `ServerFnError`> - The pending approval
+///
+/// # Errors
+/// If the approval cannot be requested, return an error
+#[cfg(feature = "ssr")]
+async fn request_publish_approval(
+    pool: &PgPool,
+    release_id: i64,
+    requested_by: i64,
+) -> Result<Approval, ServerFnError> {
+    Approval::create(pool, release_id, requested_by)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while requesting approval: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })
+}
+
 /// Create a new release
 ///
+/// Releases are always created as drafts. A release needs a track before it can be published,
+/// and a new release has none, so asking to publish it is an error.
+///
 /// # Arguments
 /// pool: `PgPool` - The database connection pool
 /// user: Option<&User> - The user creating the release
@@ -184,6 +326,7 @@ pub async fn get_release_service(
 ///
 /// # Errors
 /// If the name is empty, return an error
+/// If a published date is given, return an error
 /// If the release cannot be created, return an error
 /// If the user does not have the required permissions, return an error
 #[cfg(feature = "ssr")]
@@ -192,9 +335,13 @@ pub async fn create_release_service(
     user: Option<&User>,
     form: CreateReleaseForm,
 ) -> Result<ReleaseResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
+    user_can_manage_artist(pool, user, form.primary_artist_id).await?;
+    ensure_writable(pool).await?;
+    // A new release has no tracks, so it cannot be published or queued for approval yet
+    if form.published_at.is_some() {
+        return Err(ServerFnError::new(
+            "A release must have at least one track to be published.",
+        ));
     }
 
     let release = Release::create(
@@ -202,10 +349,12 @@ pub async fn create_release_service(
         form.name,
         form.description,
         form.primary_artist_id,
-        form.catalogue_number,
+        Some(form.catalogue_number).filter(|catalogue_number| !catalogue_number.trim().is_empty()),
         form.release_date,
         form.label_id,
-        form.published_at,
+        None,
+        form.release_type,
+        form.barcode.filter(|barcode| !barcode.trim().is_empty()),
     )
     .await
     .map_err(|e| {
@@ -214,12 +363,8 @@ pub async fn create_release_service(
         ServerFnError::new(e)
     })?;
 
-    let artist_ids = form
-        .artist_ids
-        .split(',')
-        .filter_map(|s| s.parse::<i64>().ok())
-        .collect::<Vec<i64>>();
-    release.set_artists(pool, artist_ids).await.map_err(|e| {
+    let artists = release_artists(&form.artist_ids, &form.remixer_ids, form.primary_artist_id);
+    release.set_artists(pool, artists).await.map_err(|e| {
         let err = format!("Error while setting artists: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
@@ -235,18 +380,32 @@ pub async fn create_release_service(
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
 
-    Ok(ReleaseResult {
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
+
+    Ok(ReleaseResult::new(
         release,
         artists,
         tracks,
-    })
+        credits,
+        &primary_artist,
+    ))
 }
 
 /// Update a release
 ///
+/// Only label owners can change the published date, and only if the record label doesn't require approval.
+/// Otherwise, asking for an unpublished release to be published leaves the published date alone,
+/// and a label owner is asked to approve the publish.
+///
 /// # Arguments
 /// `pool`: `PgPool` - The database connection pool
+/// `cache`: `RecordLabelCache` - The record label cache
 /// `user`: `Option<&User>` - The user creating the release
 /// `form`: `CreateReleaseForm` - The form to create the release
 ///
@@ -260,39 +419,61 @@ pub async fn create_release_service(
 #[cfg(feature = "ssr")]
 pub async fn update_release_service(
     pool: &PgPool,
+    cache: &RecordLabelCache,
     user: Option<&User>,
     form: UpdateReleaseForm,
 ) -> Result<ReleaseResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+    let mut release = fetch_or_not_found(
+        Release::get_by_slug(pool, form.slug, true).await,
+        "Error while getting release by slug",
+    )?;
+    let current_user = match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(current_user) => current_user,
+        Err(e) => return Err(e),
+    };
+    // A manager can't move a release to an artist they don't manage
+    match user_can_manage_artist(pool, user, form.primary_artist_id).await {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
-
-    let mut release = Release::get_by_slug(pool, form.slug).await.map_err(|e| {
-        let err = format!("Error while getting release by slug: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    ensure_writable(pool).await?;
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id_cached(pool, cache, release.label_id).await,
+        "Error while getting record label",
+    )?;
+    let publish_directly = !record_label.require_approval && can_publish_directly(&current_user);
+    let publish_requested = release.published_at.is_none() && form.published_at.is_some();
 
     release.name = form.name;
+    release.sort_name = form
+        .sort_name
+        .filter(|sort_name| !sort_name.trim().is_empty());
     release.description = form.description;
     release.primary_artist_id = form.primary_artist_id;
     release.catalogue_number = form.catalogue_number;
     release.release_date = form.release_date;
-    release.published_at = form.published_at;
+    if publish_directly {
+        release.published_at = form.published_at;
+    }
+    release.embargo_until = form.embargo_until;
+    release.unlisted = form.unlisted;
+    release.release_type = form.release_type;
+    release.price_cents = form.price_cents;
+    release.is_various = form.is_various;
+    release.barcode = form.barcode.filter(|barcode| !barcode.trim().is_empty());
+    release.lock_slug = form.lock_slug.or(release.lock_slug);
 
     release = release.update(pool).await.map_err(|e| {
         let err = format!("Error while updating release: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    if publish_requested && !publish_directly {
+        request_publish_approval(pool, release.id, current_user.id).await?;
+    }
 
-    let artist_ids = form
-        .artist_ids
-        .split(',')
-        .filter_map(|s| s.parse::<i64>().ok())
-        .collect::<Vec<i64>>();
-    release.set_artists(pool, artist_ids).await.map_err(|e| {
+    let artists = release_artists(&form.artist_ids, &form.remixer_ids, form.primary_artist_id);
+    release.set_artists(pool, artists).await.map_err(|e| {
         let err = format!("Error while setting artists: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
@@ -302,16 +483,27 @@ pub async fn update_release_service(
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let remixers = release.get_remixers(pool).await.map_err(|e| {
+        let err = format!("Error while getting remixers: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
     let tracks = release.get_tracks(pool).await.map_err(|e| {
         let err = format!("Error while getting tracks: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
 
     Ok(ReleaseResult {
-        release,
-        artists,
-        tracks,
+        remixers,
+        ..ReleaseResult::new(release, artists, tracks, credits, &primary_artist)
     })
 }
 
@@ -334,42 +526,58 @@ pub async fn delete_release_service(
     user: Option<&User>,
     slug: String,
 ) -> Result<ReleaseResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, slug, true).await,
+        "Error while getting release",
+    )?;
+    match user_can_manage_artist(pool, user, release.primary_artist_id).await {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    let release = Release::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting release: {e:?}");
+    let artists = release.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let tracks = release.get_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
+    let release = release.delete(pool).await.map_err(|e| {
+        let err = format!("Error while deleting release: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
 
-    Ok(ReleaseResult {
-        release: release.delete(pool).await.map_err(|e| {
-            let err = format!("Error while deleting release: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-        artists: release.get_artists(pool).await.map_err(|e| {
-            let err = format!("Error while getting artists: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-        tracks: release.get_tracks(pool).await.map_err(|e| {
-            let err = format!("Error while getting tracks: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    Ok(ReleaseResult::new(
+        release,
+        artists,
+        tracks,
+        credits,
+        &primary_artist,
+    ))
 }
 
 /// Restore a soft deleted release
 ///
+/// Only label owners can republish at a new date, and only if the record label doesn't require approval.
+/// Otherwise, asking to republish restores the release as a draft, and a label owner is asked to approve the publish.
+///
 /// # Arguments
 /// pool: `PgPool` - The database connection pool
+/// cache: `RecordLabelCache` - The record label cache
 /// user: Option<&User> - The user deleting the release
 /// slug: String - The slug of the release
+/// republish: `Republish` - Keep the original `published_at`, restore as a draft, or publish at a given date
 ///
 /// # Returns
 /// Result<`ReleaseResult`, `ServerFnError`> - The restored release
@@ -380,550 +588,2686 @@ pub async fn delete_release_service(
 #[cfg(feature = "ssr")]
 pub async fn restore_release_service(
     pool: &PgPool,
+    cache: &RecordLabelCache,
     user: Option<&User>,
     slug: String,
+    republish: Republish,
 ) -> Result<ReleaseResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
-        Ok(_) => (),
+    let mut release = fetch_or_not_found(
+        Release::get_by_slug(pool, slug, true).await,
+        "Error while getting release",
+    )?;
+    let current_user = match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(current_user) => current_user,
         Err(e) => return Err(e),
+    };
+    ensure_writable(pool).await?;
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id_cached(pool, cache, release.label_id).await,
+        "Error while getting record label",
+    )?;
+    let publish_directly = !record_label.require_approval && can_publish_directly(&current_user);
+    let publish_requested = matches!(republish, Republish::At(_));
+    release.deleted_at = None;
+    release.published_at = if publish_requested && !publish_directly {
+        None
+    } else {
+        republish.published_at(release.published_at)
+    };
+    release.clone().update(pool).await.map_err(|e| {
+        let err = format!("Error while restoring release: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    if publish_requested && !publish_directly {
+        request_publish_approval(pool, release.id, current_user.id).await?;
     }
 
-    let mut release = Release::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting release: {e:?}");
+    let artists = release.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
-    release.deleted_at = None;
-    release.clone().update(pool).await.map_err(|e| {
-        let err = format!("Error while restoring release: {e:?}");
+    let tracks = release.get_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
 
-    Ok(ReleaseResult {
-        release: release.clone(),
-        artists: release.get_artists(pool).await.map_err(|e| {
-            let err = format!("Error while getting artists: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-        tracks: release.get_tracks(pool).await.map_err(|e| {
-            let err = format!("Error while getting tracks: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    Ok(ReleaseResult::new(
+        release,
+        artists,
+        tracks,
+        credits,
+        &primary_artist,
+    ))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[cfg(feature = "ssr")]
-    use crate::models::test_helpers::{
-        create_test_artist, create_test_record_label, create_test_release, create_test_track,
-        create_test_user, create_test_user_with_permissions,
-    };
-
-    #[sqlx::test]
-    async fn test_get_releases_service_admin_user(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
-            .await
-            .unwrap();
+/// Archive or unarchive a release
+/// Archived releases are kept out of listings, but can still be reached by their slug.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user archiving the release
+/// slug: String - The slug of the release
+/// archived: bool - Whether the release should be archived
+///
+/// # Returns
+/// Result<`ReleaseResult`, `ServerFnError`> - The archived or unarchived release
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn archive_release_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    archived: bool,
+) -> Result<ReleaseResult, ServerFnError> {
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, slug, true).await,
+        "Error while getting release",
+    )?;
+    match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
 
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
-            .await
-            .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
-            .await
-            .unwrap();
-        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
-            .await
-            .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.release_date = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
+    let artists = release.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let tracks = release.get_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
+    let release = release.set_archived(pool, archived).await.map_err(|e| {
+        let err = format!("Error while archiving release: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
 
-        let releases = get_releases_service(&pool, Some(&user), artist.slug.clone())
-            .await
-            .unwrap();
+    Ok(ReleaseResult::new(
+        release,
+        artists,
+        tracks,
+        credits,
+        &primary_artist,
+    ))
+}
 
-        assert_eq!(releases.releases.len(), 2);
-        assert_eq!(releases.releases[0].id, unpublished_release.id);
-        assert_eq!(releases.releases[1].id, release.id);
+/// Replace the preview token of a release under embargo
+/// Links shared with the old token stop working, e.g. after a press link has leaked.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user rotating the token
+/// slug: String - The slug of the release
+///
+/// # Returns
+/// Result<`ReleaseResult`, `ServerFnError`> - The release with its new preview token
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+/// If the release is not under embargo, return an error
+#[cfg(feature = "ssr")]
+pub async fn rotate_preview_token_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+) -> Result<ReleaseResult, ServerFnError> {
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, slug, true).await,
+        "Error while getting release",
+    )?;
+    match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    #[sqlx::test]
-    async fn test_get_releases_service_no_user(pool: PgPool) {
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
-            .await
-            .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
-            .await
+    let artists = release.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let tracks = release.get_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let credits = release.get_credits(pool).await.map_err(|e| {
+        let err = format!("Error while getting credits: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, release.primary_artist_id, &artists).await?;
+    let release = release.rotate_preview_token(pool).await.map_err(|e| {
+        let err = format!("Error while rotating preview token: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    Ok(ReleaseResult::new(
+        release,
+        artists,
+        tracks,
+        credits,
+        &primary_artist,
+    ))
+}
+
+/// Get the archived releases on the record label, for the admin archive
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+///
+/// # Returns
+/// Result<`ReleasesResult`, `ServerFnError`> - The archived releases, most recently archived first
+///
+/// # Errors
+/// If the record label cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_archived_releases_service(
+    pool: &PgPool,
+    user: Option<&User>,
+) -> Result<ReleasesResult, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let record_label = fetch_or_not_found(
+        RecordLabel::first(pool).await,
+        "Error while getting record label",
+    )?;
+
+    Ok(ReleasesResult {
+        releases: Release::list_archived(pool, record_label.id)
+            .await
+            .map_err(|e| {
+                let err = format!("Error while getting archived releases: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?,
+    })
+}
+
+/// Renumber the tracks on a release in the given order
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user reordering the tracks
+/// `release_id`: i64 - The ID of the release
+/// `track_ids`: Vec<i64> - The IDs of every track on the release, in their new order
+///
+/// # Returns
+/// Result<Vec<`TrackWithArtists`>, `ServerFnError`> - The tracks on the release, in order
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the IDs are not exactly the tracks on the release, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn reorder_tracks_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    release_id: i64,
+    track_ids: Vec<i64>,
+) -> Result<Vec<TrackWithArtists>, ServerFnError> {
+    let release = fetch_or_not_found(
+        Release::get_by_id(pool, release_id).await,
+        "Error while getting release",
+    )?;
+    match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    release.reorder_tracks(pool, track_ids).await.map_err(|e| {
+        let err = format!("Error while reordering tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })
+}
+
+/// Publish or unpublish many releases at once
+/// Passing `None` for the published date unpublishes the releases.
+///
+/// Missing and deleted releases are reported as failed, as are releases without tracks when publishing.
+/// If the record label requires approval, or the user isn't a label owner, nothing is published; an approval is
+/// requested for each release instead, and the release is reported as waiting for it.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// `release_ids`: Vec<i64> - The IDs of the releases
+/// `published_at`: Option<`DateTime<Utc>`> - The published date to set
+///
+/// # Returns
+/// Result<`BulkResult<i64>`, `ServerFnError`> - The IDs of the updated releases, and the reason each other release was left alone
+///
+/// # Errors
+/// If the record label cannot be found, return an error
+/// If the releases cannot be read or updated, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn bulk_publish_releases_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    release_ids: Vec<i64>,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<BulkResult<i64>, ServerFnError> {
+    let current_user = match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(current_user) => current_user,
+        Err(e) => return Err(e),
+    };
+    ensure_writable(pool).await?;
+    let record_label = fetch_or_not_found(
+        RecordLabel::first(pool).await,
+        "Error while getting record label",
+    )?;
+
+    let mut result = BulkResult::new();
+    let mut eligible = vec![];
+    for release_id in release_ids {
+        let release = match Release::get_by_id(pool, release_id).await {
+            Ok(release) => release,
+            Err(e @ ReleaseError::NotFoundById(_)) => {
+                result.failed.push((release_id, e.to_string()));
+                continue;
+            }
+            Err(e) => {
+                let err = format!("Error while getting release: {e:?}");
+                tracing::error!("{err}");
+                return Err(ServerFnError::new(e));
+            }
+        };
+        if release.deleted_at.is_some() {
+            result.failed.push((
+                release_id,
+                format!("Release with id {release_id} has been deleted."),
+            ));
+            continue;
+        }
+        if published_at.is_some() {
+            let has_tracks = release.has_tracks(pool).await.map_err(|e| {
+                let err = format!("Error while getting tracks: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?;
+            if !has_tracks {
+                result.failed.push((
+                    release_id,
+                    format!("Release with id {release_id} has no tracks."),
+                ));
+                continue;
+            }
+        }
+        eligible.push(release_id);
+    }
+
+    if published_at.is_some()
+        && (record_label.require_approval || !can_publish_directly(&current_user))
+    {
+        for release_id in eligible {
+            request_publish_approval(pool, release_id, current_user.id).await?;
+            result.failed.push((
+                release_id,
+                format!("Publishing release with id {release_id} is waiting for approval."),
+            ));
+        }
+        return Ok(result);
+    }
+
+    let updated = Release::bulk_set_published(pool, eligible.clone(), published_at)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while publishing releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    for release_id in eligible {
+        if updated.contains(&release_id) {
+            result.succeeded.push(release_id);
+        } else {
+            // Deleted, or emptied of tracks, since it was checked
+            result.failed.push((
+                release_id,
+                format!("Release with id {release_id} could not be updated."),
+            ));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Is a catalogue number already used on the record label
+///
+/// Applies the same rule as validation, so the create form can warn before it is submitted.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// `label_id`: i64 - The record label the release belongs to
+/// `catalogue_number`: String - The proposed catalogue number
+///
+/// # Returns
+/// Result<bool, `ServerFnError`> - True if the catalogue number is taken
+///
+/// # Errors
+/// If the catalogue numbers cannot be checked, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn catalogue_number_in_use_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    label_id: i64,
+    catalogue_number: String,
+) -> Result<bool, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    Release::catalogue_number_in_use(pool, label_id, catalogue_number.trim())
+        .await
+        .map_err(|e| {
+            let err = format!("Error while checking catalogue number: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })
+}
+
+/// Publish a release now
+///
+/// If the record label requires approval, or the user isn't a label owner, a pending approval is created and
+/// the release is left unpublished until a label owner approves it.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// cache: `RecordLabelCache` - The record label cache
+/// user: Option<&User> - The user publishing the release
+/// `release_id`: i64 - The ID of the release
+///
+/// # Returns
+/// Result<`PublishResult`, `ServerFnError`> - The release and any pending approval
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the release has no tracks, return an error
+/// If the release cannot be published, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn publish_now(
+    pool: &PgPool,
+    cache: &RecordLabelCache,
+    user: Option<&User>,
+    release_id: i64,
+) -> Result<PublishResult, ServerFnError> {
+    let mut release = fetch_or_not_found(
+        Release::get_by_id(pool, release_id).await,
+        "Error while getting release",
+    )?;
+    let current_user = match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(current_user) => current_user,
+        Err(e) => return Err(e),
+    };
+    ensure_writable(pool).await?;
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id_cached(pool, cache, release.label_id).await,
+        "Error while getting record label",
+    )?;
+
+    // Check before requesting approval, so an empty release is never queued
+    let has_tracks = release.has_tracks(pool).await.map_err(|e| {
+        let err = format!("Error while getting tracks: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    if !has_tracks {
+        return Err(ServerFnError::new(
+            "A release must have at least one track to be published.",
+        ));
+    }
+
+    if record_label.require_approval || !can_publish_directly(&current_user) {
+        let approval = request_publish_approval(pool, release.id, current_user.id).await?;
+        return Ok(PublishResult {
+            release,
+            approval: Some(approval),
+        });
+    }
+
+    release.published_at = Some(chrono::Utc::now());
+    let release = release.update(pool).await.map_err(|e| {
+        let err = format!("Error while publishing release: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    Ok(PublishResult {
+        release,
+        approval: None,
+    })
+}
+
+/// Approve a pending publish request, publishing the release
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// approver: Option<&User> - The user approving the publish
+/// `release_id`: i64 - The ID of the release
+///
+/// # Returns
+/// Result<`PublishResult`, `ServerFnError`> - The published release and the approval
+///
+/// # Errors
+/// If there is no pending approval for the release, return an error
+/// If the approver requested the publish, return an error
+/// If the release has since been deleted or has no tracks, return an error and leave the approval pending
+/// If the release cannot be published, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn approve_publish(
+    pool: &PgPool,
+    approver: Option<&User>,
+    release_id: i64,
+) -> Result<PublishResult, ServerFnError> {
+    let approver = match user_with_permissions(approver, vec!["label_owner"]) {
+        Ok(approver) => approver,
+        Err(e) => return Err(e),
+    };
+    ensure_writable(pool).await?;
+
+    let approval = Approval::get_pending_by_release(pool, release_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting approval: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?
+        .ok_or_else(|| ServerFnError::new("There is no pending approval for this release."))?;
+    let mut release = fetch_or_not_found(
+        Release::get_by_id(pool, release_id).await,
+        "Error while getting release",
+    )?;
+
+    // The approval is only recorded if the release is published, so a failed publish can be approved again
+    let mut transaction = pool.begin().await.map_err(|e| {
+        let err = format!("Error while starting transaction: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    // The release may have been deleted, or had its tracks removed, since the approval was requested
+    let publishable = release
+        .is_publishable_in(&mut transaction)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while checking release: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    if !publishable {
+        return Err(ServerFnError::new(
+            "A release must have at least one track, and not be deleted, to be published.",
+        ));
+    }
+    let approval = approval
+        .approve(&mut transaction, approver.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while approving release: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    release.published_at = Some(chrono::Utc::now());
+    let release = release
+        .update_in(pool, &mut transaction)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while publishing release: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    transaction.commit().await.map_err(|e| {
+        let err = format!("Error while committing transaction: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    Ok(PublishResult {
+        release,
+        approval: Some(approval),
+    })
+}
+
+/// Preview the status a release will have at a moment in time
+/// Nothing is changed, so admins can check how a scheduled release will appear to the public.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user previewing the release
+/// slug: String - The slug of the release
+/// at: `DateTime<Utc>` - The moment to preview
+///
+/// # Returns
+/// Result<`ReleaseStatus`, `ServerFnError`> - The status the release would have at `at`
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn preview_at(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    at: chrono::DateTime<chrono::Utc>,
+) -> Result<ReleaseStatus, ServerFnError> {
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, slug, true).await,
+        "Error while getting release",
+    )?;
+    match user_can_manage_artist(pool, user, release.primary_artist_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    Ok(release.status_at(at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        create_test_user, create_test_user_with_permissions,
+    };
+    #[cfg(feature = "ssr")]
+    use crate::models::{release::ReleaseType, release_credit::ReleaseCredit};
+
+    #[test]
+    fn test_release_artists() {
+        assert_eq!(
+            release_artists("3, 1,2,1", "", 1),
+            vec![
+                (3, ArtistRole::Featured),
+                (1, ArtistRole::Primary),
+                (2, ArtistRole::Featured),
+            ]
+        );
+        assert_eq!(
+            release_artists("2", "", 1),
+            vec![(1, ArtistRole::Primary), (2, ArtistRole::Featured)]
+        );
+        assert_eq!(release_artists("", "", 1), vec![(1, ArtistRole::Primary)]);
+    }
+
+    #[test]
+    fn test_release_artists_with_remixers() {
+        // A remixer who is also listed as an artist is a remixer, and the primary artist stays primary
+        assert_eq!(
+            release_artists("1,2,3", "3,4,1", 1),
+            vec![
+                (1, ArtistRole::Primary),
+                (2, ArtistRole::Featured),
+                (3, ArtistRole::Remixer),
+                (4, ArtistRole::Remixer),
+            ]
+        );
+        assert_eq!(
+            release_artists("", "2", 1),
+            vec![(1, ArtistRole::Primary), (2, ArtistRole::Remixer)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_releases_service_admin_user(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.release_date = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+
+        let releases = get_releases_service(&pool, Some(&user), artist.slug.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(releases.releases.len(), 2);
+        assert_eq!(releases.releases[0].id, unpublished_release.id);
+        assert_eq!(releases.releases[1].id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_releases_service_no_user(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+
+        let releases = get_releases_service(&pool, None, artist.slug.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(releases.releases.len(), 1);
+        assert_eq!(releases.releases[0].id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_releases_service_anonymous_user(pool: PgPool) {
+        let (user, _) = create_test_user(&pool, 1).await.unwrap().into_user(None);
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+
+        let releases = get_releases_service(&pool, Some(&user), artist.slug.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(releases.releases.len(), 1);
+        assert_eq!(releases.releases[0].id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_next_scheduled_release_service(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        release.clone().update(&pool).await.unwrap();
+
+        let next_release =
+            get_next_scheduled_release_service(&pool, Some(artist.id), record_label.id)
+                .await
+                .unwrap();
+
+        assert!(next_release.is_some());
+        assert_eq!(next_release.unwrap().release.id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_next_scheduled_release_service_no_artist(pool: PgPool) {
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        release.clone().update(&pool).await.unwrap();
+
+        let next_release = get_next_scheduled_release_service(&pool, None, release.label_id)
+            .await
+            .unwrap();
+
+        assert!(next_release.is_some());
+        assert_eq!(next_release.unwrap().release.id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release_result.release.id, release.id);
+        assert_eq!(release_result.tracks.len(), 1);
+        assert_eq!(release_result.tracks[0].track.id, track.id);
+        assert_eq!(release_result.artists.len(), 1);
+        assert_eq!(release_result.artists[0].id, artist.id);
+        assert!(release_result.credits.is_empty());
+        assert_eq!(release_result.primary_artist_name, artist.name);
+        assert!(!release_result.primary_artist_deleted);
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_deleted_primary_artist(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        artist.delete(&pool).await.unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(release_result.release.id, release.id);
+        assert_eq!(release_result.primary_artist_name, artist.name);
+        assert!(release_result.primary_artist_deleted);
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_with_credits(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release
+            .set_credits(
+                &pool,
+                vec![
+                    ReleaseCredit::new("Mastered by", "Jane Doe", 0),
+                    ReleaseCredit::new("Artwork by", "Alex Smith", 1),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let release_result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None)
+                .await
+                .unwrap();
+
+        assert_eq!(release_result.credits.len(), 2);
+        assert_eq!(release_result.credits[0].role, "Mastered by");
+        assert_eq!(release_result.credits[1].name, "Alex Smith");
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_placements_for_label_owner_only(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let placement = Placement::create(
+            &pool,
+            PlacementEntity::Release,
+            release.id,
+            "New Music Friday".to_string(),
+            "https://open.spotify.com/playlist/1".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(release_result.placements, vec![placement]);
+
+        let release_result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None)
+                .await
+                .unwrap();
+        assert!(release_result.placements.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_no_permission(pool: PgPool) {
+        let permissions = vec![];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            unpublished_release.slug.clone(),
+            None,
+        )
+        .await;
+        assert!(release_result.is_err());
+        assert_eq!(
+            release_result.unwrap_err().to_string(),
+            "error running server function: Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_no_iser(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        unpublished_release.published_at = None;
+        unpublished_release.clone().update(&pool).await.unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            None,
+            artist.slug.clone(),
+            unpublished_release.slug.clone(),
+            None,
+        )
+        .await;
+        assert!(release_result.is_err());
+        assert_eq!(
+            release_result.unwrap_err().to_string(),
+            "error running server function: Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_embargoed(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+        let preview_token = release.preview_token.clone();
+        assert!(preview_token.is_some());
+
+        let token_result = get_release_service(
+            &pool,
+            None,
+            artist.slug.clone(),
+            release.slug.clone(),
+            preview_token,
+        )
+        .await
+        .unwrap();
+        let public_result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None).await;
+        let wrong_token_result = get_release_service(
+            &pool,
+            None,
+            artist.slug.clone(),
+            release.slug.clone(),
+            Some("not-the-token".to_string()),
+        )
+        .await;
+
+        assert_eq!(token_result.release.id, release.id);
+        assert_eq!(
+            public_result.unwrap_err().to_string(),
+            "error running server function: Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+        );
+        assert!(wrong_token_result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_embargo_lifted(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.embargo_until = Some(chrono::Utc::now() - chrono::Duration::minutes(1));
+        let release = release.update(&pool).await.unwrap();
+
+        let release_result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None)
+                .await
+                .unwrap();
+
+        assert_eq!(release_result.release.id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_get_release_service_embargoed_draft(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = None;
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
+
+        let release_result = get_release_service(
+            &pool,
+            None,
+            artist.slug.clone(),
+            release.slug.clone(),
+            release.preview_token.clone(),
+        )
+        .await;
+
+        assert!(release_result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_create_release_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let release_result = create_release_service(&pool, Some(&user), form.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(release_result.release.name, "Test Release");
+        assert_eq!(
+            release_result.release.description,
+            "Test Release Description"
+        );
+        assert_eq!(release_result.release.primary_artist_id, artist.id);
+        assert_eq!(release_result.release.catalogue_number, "TEST-123");
+        assert!(release_result.release.release_date.is_some());
+        assert_eq!(release_result.release.label_id, record_label.id);
+        assert!(release_result.release.published_at.is_none());
+        assert_eq!(release_result.artists.len(), 1);
+        assert_eq!(release_result.artists[0].id, artist.id);
+    }
+
+    #[sqlx::test]
+    async fn test_create_release_service_no_permision(pool: PgPool) {
+        let permissions = vec![];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let release_result = create_release_service(&pool, Some(&user), form).await;
+        assert!(release_result.is_err());
+        assert_eq!(
+            release_result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_no_permission(pool: PgPool) {
+        let permissions = vec![];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let release_result = create_release_service(&pool, Some(&user), form).await;
+        assert!(release_result.is_err());
+        assert_eq!(
+            release_result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_artist_manager(pool: PgPool) {
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let other_release = create_test_release(&pool, 2, Some(other_artist.clone()))
+            .await
+            .unwrap();
+        let update_form = |release: &Release, primary_artist_id: i64| UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Updated Release".to_string(),
+            slug: release.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
+            description: "Updated Release Description".to_string(),
+            primary_artist_id,
+            catalogue_number: release.catalogue_number.clone(),
+            release_date: release.release_date,
+            label_id: release.label_id,
+            published_at: release.published_at,
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: primary_artist_id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        // The manager can edit their artist's release
+        let result = update_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&manager),
+            update_form(&release, artist.id),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.release.name, "Updated Release");
+
+        // But not another artist's release, or give their release to another artist
+        let result = update_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&manager),
+            update_form(&other_release, other_artist.id),
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+        let result = update_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&manager),
+            update_form(&release, other_artist.id),
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_artist_manager_publish(pool: PgPool) {
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        let form = UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: release.name.clone(),
+            slug: release.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
+            description: release.description.clone(),
+            primary_artist_id: artist.id,
+            catalogue_number: release.catalogue_number.clone(),
+            release_date: release.release_date,
+            label_id: release.label_id,
+            published_at: Some(chrono::Utc::now()),
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let result =
+            update_release_service(&pool, &RecordLabelCache::default(), Some(&manager), form)
+                .await
+                .unwrap();
+
+        // The manager's publish waits for a label owner
+        assert!(result.release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approval.requested_by, manager.id);
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_requires_approval(pool: PgPool) {
+        let owner = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let form = UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: release.name.clone(),
+            slug: release.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
+            description: release.description.clone(),
+            primary_artist_id: artist.id,
+            catalogue_number: release.catalogue_number.clone(),
+            release_date: release.release_date,
+            label_id: release.label_id,
+            published_at: Some(chrono::Utc::now()),
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let result =
+            update_release_service(&pool, &RecordLabelCache::default(), Some(&owner), form)
+                .await
+                .unwrap();
+
+        // Even a label owner's publish waits for a second label owner
+        assert!(result.release.published_at.is_none());
+        let stored_release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(stored_release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approval.requested_by, owner.id);
+    }
+
+    #[sqlx::test]
+    async fn test_create_release_service_publish_without_tracks(pool: PgPool) {
+        let owner = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let manager = create_test_user_with_permissions(&pool, 2, vec![])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        // Neither publishing directly nor asking for approval is allowed before the release has a track
+        for user in [&owner, &manager] {
+            let result = create_release_service(&pool, Some(user), form.clone()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: A release must have at least one track to be published."
+            );
+        }
+        let approvals: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM approvals")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(approvals, 0);
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            primary_artist_id: artist.id,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let release_result = create_release_service(&pool, Some(&user), form).await;
+        assert!(release_result.is_ok());
+        let release = release_result.unwrap();
+        create_test_track(
+            &pool,
+            1,
+            Some(release.release.clone()),
+            Some(artist.clone()),
+        )
+        .await
+        .unwrap();
+
+        let update_form = UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Updated Release".to_string(),
+            slug: "test-release".to_string(),
+            lock_slug: None,
+            sort_name: None,
+            description: "Updated Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "UPDATED-123".to_string(),
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: Some(chrono::Utc::now()),
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let update_result = update_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            update_form.clone(),
+        )
+        .await;
+        assert!(update_result.is_ok());
+        let updated_release = update_result.unwrap();
+
+        assert_eq!(release.release.id, updated_release.release.id);
+        assert_eq!(updated_release.release.name, "Updated Release");
+        assert_eq!(
+            updated_release.release.description,
+            "Updated Release Description"
+        );
+        assert_eq!(updated_release.release.primary_artist_id, artist.id);
+        assert_eq!(updated_release.release.catalogue_number, "UPDATED-123");
+        assert!(updated_release.release.release_date.is_some());
+        assert_eq!(updated_release.release.label_id, record_label.id);
+        assert!(updated_release.release.published_at.is_some());
+        assert_eq!(updated_release.artists, vec![artist]);
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_remixers(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let artist = release.get_artists(&pool).await.unwrap()[0].clone();
+        let record_label = RecordLabel::get_by_id(&pool, artist.label_id)
+            .await
+            .unwrap();
+        let remixer = create_test_artist(&pool, 2, Some(record_label))
+            .await
+            .unwrap();
+        let form = UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: release.name.clone(),
+            slug: release.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
+            description: release.description.clone(),
+            primary_artist_id: artist.id,
+            catalogue_number: release.catalogue_number.clone(),
+            release_date: release.release_date,
+            label_id: release.label_id,
+            published_at: release.published_at,
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: remixer.id.to_string(),
+        };
+
+        let result = update_release_service(&pool, &RecordLabelCache::default(), Some(&user), form)
+            .await
+            .unwrap();
+
+        assert_eq!(result.artists.len(), 2);
+        assert_eq!(result.remixers, vec![remixer.clone()]);
+        let result = get_release_service(&pool, Some(&user), artist.slug, release.slug, None)
+            .await
+            .unwrap();
+        assert_eq!(result.remixers, vec![remixer]);
+    }
+
+    #[sqlx::test]
+    pub fn delete_release(pool: sqlx::PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Test Release".to_string(),
+            description: "Test Release Description".to_string(),
+            primary_artist_id: artist.id,
+            catalogue_number: "TEST-123".to_string(),
+            barcode: None,
+            release_date: Some(chrono::Utc::now()),
+            label_id: record_label.id,
+            published_at: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+
+        let release_result = create_release_service(&pool, Some(&user), form).await;
+        assert!(release_result.is_ok());
+        let release = release_result.unwrap();
+
+        let delete_result =
+            delete_release_service(&pool, Some(&user), release.release.slug.clone()).await;
+        assert!(delete_result.is_ok());
+
+        let get_result =
+            get_release_service(&pool, Some(&user), artist.slug, release.release.slug, None).await;
+        assert!(get_result.is_ok());
+        assert!(get_result.unwrap().release.deleted_at.is_some());
+    }
+
+    #[sqlx::test]
+    pub fn delete_release_not_found(pool: sqlx::PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let delete_result =
+            delete_release_service(&pool, Some(&user), "not-found".to_string()).await;
+        assert!(delete_result.is_err());
+        assert_eq!(
+            delete_result.unwrap_err().to_string(),
+            "error running server function: Could not find release with slug not-found."
+                .to_string()
+        );
+    }
+
+    #[sqlx::test]
+    pub fn delete_service_no_permission(pool: sqlx::PgPool) {
+        let permissions = vec![];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let delete_result = delete_release_service(&pool, Some(&user), release.slug.clone()).await;
+        assert_eq!(
+            delete_result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+
+        let get_result =
+            get_release_service(&pool, Some(&user), artist.slug, release.slug, None).await;
+        assert!(get_result.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let release = release.delete(&pool).await.unwrap();
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.slug.clone(),
+            Republish::Keep,
+        )
+        .await
+        .unwrap();
+
+        assert!(restored_release.release.deleted_at.is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_update_release_service_deleted_release(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let release = release.delete(&pool).await.unwrap();
+
+        // The public path cannot see the deleted release
+        let public_result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None).await;
+        assert!(public_result.is_err());
+
+        // The admin edit path still resolves it
+        let update_form = UpdateReleaseForm {
+            release_type: ReleaseType::default(),
+            name: "Updated Release".to_string(),
+            slug: release.slug.clone(),
+            lock_slug: None,
+            sort_name: None,
+            description: release.description.clone(),
+            primary_artist_id: artist.id,
+            catalogue_number: release.catalogue_number.clone(),
+            release_date: release.release_date,
+            label_id: record_label.id,
+            published_at: release.published_at,
+            embargo_until: None,
+            unlisted: false,
+            price_cents: None,
+            is_various: false,
+            barcode: None,
+            artist_ids: artist.id.to_string(),
+            remixer_ids: String::new(),
+        };
+        let updated_release = update_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            update_form,
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated_release.release.id, release.id);
+        assert_eq!(updated_release.release.name, "Updated Release");
+        assert!(updated_release.release.deleted_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_catalogue_number_in_use_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let in_use = catalogue_number_in_use_service(
+            &pool,
+            Some(&user),
+            release.label_id,
+            " test 001 ".to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(in_use);
+
+        let in_use = catalogue_number_in_use_service(
+            &pool,
+            Some(&user),
+            release.label_id,
+            "TEST-2".to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(!in_use);
+    }
+
+    #[sqlx::test]
+    async fn test_catalogue_number_in_use_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let result = catalogue_number_in_use_service(
+            &pool,
+            Some(&user),
+            release.label_id,
+            release.catalogue_number,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_keeps_published_at(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let original_published_at = release.published_at;
+        let release = release.delete(&pool).await.unwrap();
+
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.slug.clone(),
+            Republish::Keep,
+        )
+        .await
+        .unwrap();
+
+        assert!(restored_release.release.deleted_at.is_none());
+        assert_eq!(restored_release.release.published_at, original_published_at);
+        let release = Release::get_by_slug(&pool, release.slug, false)
+            .await
+            .unwrap();
+        assert_eq!(release.published_at, original_published_at);
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_as_draft(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        assert!(release.published_at.is_some());
+        let release = release.delete(&pool).await.unwrap();
+
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.slug.clone(),
+            Republish::Draft,
+        )
+        .await
+        .unwrap();
+
+        assert!(restored_release.release.deleted_at.is_none());
+        assert_eq!(restored_release.release.published_at, None);
+        let release = Release::get_by_slug(&pool, release.slug, false)
+            .await
+            .unwrap();
+        assert_eq!(release.published_at, None);
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_republish_now(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let release = release.delete(&pool).await.unwrap();
+
+        let now = chrono::Utc::now();
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.slug.clone(),
+            Republish::At(now),
+        )
+        .await
+        .unwrap();
+
+        assert!(restored_release.release.deleted_at.is_none());
+        let release = Release::get_by_slug(&pool, release.slug, false)
+            .await
+            .unwrap();
+        assert!(
+            release
+                .published_at
+                .is_some_and(|published_at| { (published_at - now).num_milliseconds().abs() < 1 })
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_artist_manager_republish(pool: PgPool) {
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let release = release.delete(&pool).await.unwrap();
+
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&manager),
+            release.slug.clone(),
+            Republish::At(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        // Restored as a draft until a label owner approves the publish
+        assert!(restored_release.release.deleted_at.is_none());
+        assert!(restored_release.release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert!(approval.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_requires_approval(pool: PgPool) {
+        let owner = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        let release = release.delete(&pool).await.unwrap();
+
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&owner),
+            release.slug.clone(),
+            Republish::At(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        // Restored as a draft until a second label owner approves the publish
+        assert!(restored_release.release.deleted_at.is_none());
+        assert!(restored_release.release.published_at.is_none());
+        let stored_release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(stored_release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approval.requested_by, owner.id);
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_no_release(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            "missing".to_string(),
+            Republish::Keep,
+        )
+        .await;
+        assert!(restored_release.is_err());
+        assert_eq!(
+            restored_release.unwrap_err().to_string(),
+            "error running server function: Could not find release with slug missing.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_no_user(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let release = release.delete(&pool).await.unwrap();
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&User::default()),
+            release.slug.clone(),
+            Republish::Keep,
+        )
+        .await;
+        assert!(restored_release.is_err());
+        assert_eq!(
+            restored_release.unwrap_err().to_string(),
+            "error running server function: You must be logged in to view this page.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_restore_release_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
             .unwrap();
-        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let release = release.delete(&pool).await.unwrap();
+        let restored_release = restore_release_service(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.slug.clone(),
+            Republish::Keep,
+        )
+        .await;
+        assert!(restored_release.is_err());
+        assert_eq!(
+            restored_release.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_archive_release_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let deleted_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap()
+            .delete(&pool)
+            .await
+            .unwrap();
+
+        let archived_release =
+            archive_release_service(&pool, Some(&user), release.slug.clone(), true)
+                .await
+                .unwrap();
+        assert!(archived_release.release.archived_at.is_some());
+        assert!(archived_release.release.deleted_at.is_none());
 
+        // Hidden from listings
         let releases = get_releases_service(&pool, None, artist.slug.clone())
             .await
             .unwrap();
+        assert!(releases.releases.is_empty());
 
-        assert_eq!(releases.releases.len(), 1);
-        assert_eq!(releases.releases[0].id, release.id);
+        // Still resolvable by slug
+        let result =
+            get_release_service(&pool, None, artist.slug.clone(), release.slug.clone(), None)
+                .await
+                .unwrap();
+        assert_eq!(result.release.id, release.id);
+
+        // In the archive, but not the trash
+        let archived = get_archived_releases_service(&pool, Some(&user))
+            .await
+            .unwrap();
+        assert_eq!(
+            archived.releases.iter().map(|r| r.id).collect::<Vec<i64>>(),
+            vec![release.id]
+        );
+        assert!(!archived.releases.iter().any(|r| r.id == deleted_release.id));
     }
 
     #[sqlx::test]
-    async fn test_get_releases_service_anonymous_user(pool: PgPool) {
-        let (user, _) = create_test_user(&pool, 1).await.unwrap().into_user(None);
-
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+    async fn test_reorder_tracks_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let first = create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
-        let mut unpublished_release = create_test_release(&pool, 2, Some(artist.clone()))
+        let second = create_test_track(&pool, 2, Some(release.clone()), None)
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
 
-        let releases = get_releases_service(&pool, Some(&user), artist.slug.clone())
+        let tracks =
+            reorder_tracks_service(&pool, Some(&user), release.id, vec![second.id, first.id])
+                .await
+                .unwrap();
+
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| (track.track.id, track.track.track_number))
+                .collect::<Vec<_>>(),
+            vec![(second.id, 1), (first.id, 2)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_reorder_tracks_service_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
 
-        assert_eq!(releases.releases.len(), 1);
-        assert_eq!(releases.releases[0].id, release.id);
+        let result = reorder_tracks_service(&pool, Some(&user), release.id, vec![track.id]).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            ServerFnError::new("You do not have permission.")
+        );
     }
 
     #[sqlx::test]
-    async fn test_get_next_scheduled_release_service(pool: PgPool) {
+    async fn test_bulk_publish_releases_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let artist = create_test_artist(&pool, 1, Some(record_label))
             .await
             .unwrap();
-        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+        let release_one = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
-        release.clone().update(&pool).await.unwrap();
+        let release_two = create_test_release(&pool, 2, Some(artist)).await.unwrap();
 
-        let next_release =
-            get_next_scheduled_release_service(&pool, Some(artist.id), record_label.id)
-                .await
-                .unwrap();
+        let result = bulk_publish_releases_service(
+            &pool,
+            Some(&user),
+            vec![release_one.id, release_two.id],
+            None,
+        )
+        .await
+        .unwrap();
 
-        assert!(next_release.is_some());
-        assert_eq!(next_release.unwrap().release.id, release.id);
+        assert_eq!(result.succeeded, vec![release_one.id, release_two.id]);
+        assert!(result.failed.is_empty());
+        let release = Release::get_by_id(&pool, release_one.id).await.unwrap();
+        assert_eq!(release.published_at, None);
     }
 
     #[sqlx::test]
-    async fn test_get_next_scheduled_release_service_no_artist(pool: PgPool) {
-        let mut release = create_test_release(&pool, 1, None).await.unwrap();
-        release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(1));
-        release.clone().update(&pool).await.unwrap();
-
-        let next_release = get_next_scheduled_release_service(&pool, None, release.label_id)
+    async fn test_bulk_publish_releases_service_skips_releases_without_tracks(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let with_tracks = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        create_test_track(&pool, 1, Some(with_tracks.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let without_tracks = create_test_release(&pool, 2, Some(artist)).await.unwrap();
 
-        assert!(next_release.is_some());
-        assert_eq!(next_release.unwrap().release.id, release.id);
+        let result = bulk_publish_releases_service(
+            &pool,
+            Some(&user),
+            vec![with_tracks.id, without_tracks.id],
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.succeeded, vec![with_tracks.id]);
+        assert_eq!(
+            result.failed,
+            vec![(
+                without_tracks.id,
+                format!("Release with id {} has no tracks.", without_tracks.id)
+            )]
+        );
     }
 
     #[sqlx::test]
-    async fn test_get_release_service(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_bulk_publish_releases_service_requires_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), Some(artist))
             .await
             .unwrap();
 
+        let result = bulk_publish_releases_service(
+            &pool,
+            Some(&user),
+            vec![release.id],
+            Some(chrono::Utc::now()),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.succeeded.is_empty());
+        assert_eq!(
+            result.failed,
+            vec![(
+                release.id,
+                format!(
+                    "Publishing release with id {} is waiting for approval.",
+                    release.id
+                )
+            )]
+        );
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert!(approval.is_some());
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.published_at, None);
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_publish_releases_service_admin_requires_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin"])
+            .await
+            .unwrap();
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let artist = create_test_artist(&pool, 1, Some(record_label))
             .await
             .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), Some(artist))
             .await
             .unwrap();
 
-        let release_result = get_release_service(
+        let result = bulk_publish_releases_service(
             &pool,
             Some(&user),
-            artist.slug.clone(),
-            release.slug.clone(),
+            vec![release.id],
+            Some(chrono::Utc::now()),
         )
         .await
         .unwrap();
 
-        assert_eq!(release_result.release.id, release.id);
-        assert_eq!(release_result.tracks.len(), 1);
-        assert_eq!(release_result.tracks[0].track.id, track.id);
-        assert_eq!(release_result.artists.len(), 1);
-        assert_eq!(release_result.artists[0].id, artist.id);
+        assert!(result.succeeded.is_empty());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(approval.requested_by, user.id);
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release.published_at, None);
     }
 
     #[sqlx::test]
-    async fn test_get_release_service_no_permission(pool: PgPool) {
-        let permissions = vec![];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_bulk_publish_releases_service_missing_and_deleted(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let artist = create_test_artist(&pool, 1, Some(record_label))
             .await
             .unwrap();
-        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), Some(artist))
+            .await
+            .unwrap();
+        release.delete(&pool).await.unwrap();
 
-        let release_result = get_release_service(
+        let result = bulk_publish_releases_service(
             &pool,
             Some(&user),
-            artist.slug.clone(),
-            unpublished_release.slug.clone(),
+            vec![release.id, 999],
+            Some(chrono::Utc::now()),
         )
-        .await;
-        assert!(release_result.is_err());
+        .await
+        .unwrap();
+
+        assert!(result.succeeded.is_empty());
         assert_eq!(
-            release_result.unwrap_err().to_string(),
-            "error running server function: Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
+            result.failed,
+            vec![
+                (
+                    release.id,
+                    format!("Release with id {} has been deleted.", release.id)
+                ),
+                (999, "Could not find release with id 999.".to_string()),
+            ]
         );
     }
 
     #[sqlx::test]
-    async fn test_get_release_service_no_iser(pool: PgPool) {
+    async fn test_bulk_publish_releases_service_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let result =
+            bulk_publish_releases_service(&pool, Some(&user), vec![release.id], None).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            ServerFnError::new("You do not have permission.")
+        );
+        let release_after = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert_eq!(release_after.published_at, release.published_at);
+    }
+
+    #[sqlx::test]
+    async fn test_archive_release_service_unarchive(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        release.set_archived(&pool, true).await.unwrap();
+
+        let unarchived_release =
+            archive_release_service(&pool, Some(&user), release.slug.clone(), false)
+                .await
+                .unwrap();
+
+        assert!(unarchived_release.release.archived_at.is_none());
+        let archived = get_archived_releases_service(&pool, Some(&user))
+            .await
+            .unwrap();
+        assert!(archived.releases.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_archive_release_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+
+        let result = archive_release_service(&pool, Some(&user), release.slug, true).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+        let result = get_archived_releases_service(&pool, Some(&user)).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_rotate_preview_token_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
         let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
             .await
             .unwrap();
-        let mut unpublished_release = create_test_release(&pool, 1, Some(artist.clone()))
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-        unpublished_release.published_at = None;
-        unpublished_release.clone().update(&pool).await.unwrap();
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
 
-        let release_result = get_release_service(
+        let result = rotate_preview_token_service(&pool, Some(&user), release.slug.clone())
+            .await
+            .unwrap();
+        assert!(result.release.preview_token.is_some());
+        assert_ne!(result.release.preview_token, release.preview_token);
+
+        // The old link no longer shows the release
+        let result = get_release_service(
             &pool,
             None,
             artist.slug.clone(),
-            unpublished_release.slug.clone(),
+            release.slug.clone(),
+            release.preview_token.clone(),
         )
         .await;
-        assert!(release_result.is_err());
-        assert_eq!(
-            release_result.unwrap_err().to_string(),
-            "error running server function: Could not find release test-release-1 for artist with id 1 and record label with id 1.".to_string()
-        );
+        assert!(result.is_err());
     }
 
     #[sqlx::test]
-    async fn test_create_release_service(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_rotate_preview_token_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
             .await
             .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.embargo_until = Some(chrono::Utc::now() + chrono::Duration::days(1));
+        let release = release.update(&pool).await.unwrap();
 
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
-            .await
-            .unwrap();
+        let result = rotate_preview_token_service(&pool, Some(&user), release.slug).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
 
-        let form = CreateReleaseForm {
-            name: "Test Release".to_string(),
-            description: "Test Release Description".to_string(),
-            primary_artist_id: artist.id,
-            catalogue_number: "TEST-123".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
+    #[sqlx::test]
+    async fn test_publish_now(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
 
-        let release_result = create_release_service(&pool, Some(&user), form.clone())
+        let result = publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id)
             .await
             .unwrap();
 
-        assert_eq!(release_result.release.name, "Test Release");
+        assert!(result.approval.is_none());
+        assert!(result.release.published_at.is_some());
+        assert!(result.release.published_at.unwrap() <= chrono::Utc::now());
         assert_eq!(
-            release_result.release.description,
-            "Test Release Description"
+            result.release.first_published_at,
+            result.release.published_at
         );
-        assert_eq!(release_result.release.primary_artist_id, artist.id);
-        assert_eq!(release_result.release.catalogue_number, "TEST-123");
-        assert!(release_result.release.release_date.is_some());
-        assert_eq!(release_result.release.label_id, record_label.id);
-        assert!(release_result.release.published_at.is_some());
-        assert_eq!(release_result.artists.len(), 1);
-        assert_eq!(release_result.artists[0].id, artist.id);
     }
 
     #[sqlx::test]
-    async fn test_create_release_service_no_permision(pool: PgPool) {
-        let permissions = vec![];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
-            .await
-            .unwrap();
-
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+    async fn test_publish_now_without_tracks(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
 
-        let form = CreateReleaseForm {
-            name: "Test Release".to_string(),
-            description: "Test Release Description".to_string(),
-            primary_artist_id: artist.id,
-            catalogue_number: "TEST-123".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
+        let result =
+            publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id).await;
 
-        let release_result = create_release_service(&pool, Some(&user), form).await;
-        assert!(release_result.is_err());
         assert_eq!(
-            release_result.unwrap_err().to_string(),
-            "error running server function: You do not have permission.".to_string()
+            result.unwrap_err().to_string(),
+            "error running server function: A release must have at least one track to be published."
+                .to_string()
         );
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.published_at.is_none());
     }
 
     #[sqlx::test]
-    async fn test_update_release_service_no_permission(pool: PgPool) {
-        let permissions = vec![];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_publish_now_requires_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
 
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        // The first step only requests approval
+        let result = publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id)
+            .await
+            .unwrap();
+        assert!(result.release.published_at.is_none());
+        let approval = result.approval.unwrap();
+        assert!(approval.is_pending());
+        assert_eq!(approval.requested_by, user.id);
+        let unpublished_release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(unpublished_release.published_at.is_none());
+
+        // The second step publishes the release
+        let result = approve_publish(&pool, Some(&approver), release.id)
             .await
             .unwrap();
+        assert!(result.release.published_at.is_some());
+        let approval = result.approval.unwrap();
+        assert!(!approval.is_pending());
+        assert_eq!(approval.approved_by, Some(approver.id));
+    }
 
-        let form = CreateReleaseForm {
-            name: "Test Release".to_string(),
-            description: "Test Release Description".to_string(),
-            primary_artist_id: artist.id,
-            catalogue_number: "TEST-123".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
+    #[sqlx::test]
+    async fn test_approve_publish_self_approval(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
 
-        let release_result = create_release_service(&pool, Some(&user), form).await;
-        assert!(release_result.is_err());
+        publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id)
+            .await
+            .unwrap();
+        let result = approve_publish(&pool, Some(&user), release.id).await;
+
+        assert!(result.is_err());
         assert_eq!(
-            release_result.unwrap_err().to_string(),
-            "error running server function: You do not have permission.".to_string()
+            result.unwrap_err().to_string(),
+            "error running server function: You cannot approve your own publish request."
+                .to_string()
         );
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.published_at.is_none());
     }
 
     #[sqlx::test]
-    async fn test_update_release_service(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_approve_publish_failure_keeps_approval_pending(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id)
             .await
             .unwrap();
 
-        let form = CreateReleaseForm {
-            name: "Test Release".to_string(),
-            description: "Test Release Description".to_string(),
-            catalogue_number: "TEST-123".to_string(),
-            primary_artist_id: artist.id,
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
-
-        let release_result = create_release_service(&pool, Some(&user), form).await;
-        assert!(release_result.is_ok());
-        let release = release_result.unwrap();
-
-        let update_form = UpdateReleaseForm {
-            name: "Updated Release".to_string(),
-            slug: "test-release".to_string(),
-            description: "Updated Release Description".to_string(),
-            primary_artist_id: artist.id,
-            catalogue_number: "UPDATED-123".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
-
-        let update_result = update_release_service(&pool, Some(&user), update_form.clone()).await;
-        assert!(update_result.is_ok());
-        let updated_release = update_result.unwrap();
+        // The release can no longer be published once its only track is deleted
+        track.delete(&pool).await.unwrap();
+        let result = approve_publish(&pool, Some(&approver), release.id).await;
 
-        assert_eq!(release.release.id, updated_release.release.id);
-        assert_eq!(updated_release.release.name, "Updated Release");
         assert_eq!(
-            updated_release.release.description,
-            "Updated Release Description"
+            result.unwrap_err(),
+            ServerFnError::new(
+                "A release must have at least one track, and not be deleted, to be published."
+            )
         );
-        assert_eq!(updated_release.release.primary_artist_id, artist.id);
-        assert_eq!(updated_release.release.catalogue_number, "UPDATED-123");
-        assert!(updated_release.release.release_date.is_some());
-        assert_eq!(updated_release.release.label_id, record_label.id);
-        assert!(updated_release.release.published_at.is_some());
-        assert_eq!(updated_release.artists, vec![artist]);
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert!(approval.is_some());
     }
 
     #[sqlx::test]
-    pub fn delete_release(pool: sqlx::PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_approve_publish_deleted_release_keeps_approval_pending(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let approver = create_test_user_with_permissions(&pool, 2, vec!["label_owner"])
+            .await
+            .unwrap();
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.require_approval = true;
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
+            .await
+            .unwrap();
+        publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id)
             .await
             .unwrap();
 
-        let form = CreateReleaseForm {
-            name: "Test Release".to_string(),
-            description: "Test Release Description".to_string(),
-            primary_artist_id: artist.id,
-            catalogue_number: "TEST-123".to_string(),
-            release_date: Some(chrono::Utc::now()),
-            label_id: record_label.id,
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-        };
-
-        let release_result = create_release_service(&pool, Some(&user), form).await;
-        assert!(release_result.is_ok());
-        let release = release_result.unwrap();
-
-        let delete_result =
-            delete_release_service(&pool, Some(&user), release.release.slug.clone()).await;
-        assert!(delete_result.is_ok());
+        release.delete(&pool).await.unwrap();
+        let result = approve_publish(&pool, Some(&approver), release.id).await;
 
-        let get_result =
-            get_release_service(&pool, Some(&user), artist.slug, release.release.slug).await;
-        assert!(get_result.is_ok());
-        assert!(get_result.unwrap().release.deleted_at.is_some());
+        assert_eq!(
+            result.unwrap_err(),
+            ServerFnError::new(
+                "A release must have at least one track, and not be deleted, to be published."
+            )
+        );
+        let release = Release::get_by_id(&pool, release.id).await.unwrap();
+        assert!(release.published_at.is_none());
+        let approval = Approval::get_pending_by_release(&pool, release.id)
+            .await
+            .unwrap();
+        assert!(approval.is_some());
     }
 
     #[sqlx::test]
-    pub fn delete_release_not_found(pool: sqlx::PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_approve_publish_no_pending_approval(pool: PgPool) {
+        let approver = create_test_user_with_permissions(&pool, 1, vec!["label_owner"])
             .await
             .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
 
-        let delete_result =
-            delete_release_service(&pool, Some(&user), "not-found".to_string()).await;
-        assert!(delete_result.is_err());
+        let result = approve_publish(&pool, Some(&approver), release.id).await;
+
+        assert!(result.is_err());
         assert_eq!(
-            delete_result.unwrap_err().to_string(),
-            "error running server function: Could not find release with slug not-found."
+            result.unwrap_err().to_string(),
+            "error running server function: There is no pending approval for this release."
                 .to_string()
         );
     }
 
     #[sqlx::test]
-    pub fn delete_service_no_permission(pool: sqlx::PgPool) {
-        let permissions = vec![];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_publish_now_artist_manager(pool: PgPool) {
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
             .await
             .unwrap();
-
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist)).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
+        create_test_track(&pool, 1, Some(release.clone()), None)
             .await
             .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
+
+        let result = publish_now(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&manager),
+            release.id,
+        )
+        .await
+        .unwrap();
+
+        // Even without the label requiring approval, a manager's publish waits for a label owner
+        assert!(result.release.published_at.is_none());
+        assert!(
+            result
+                .approval
+                .is_some_and(|approval| approval.is_pending())
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_publish_now_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
             .await
             .unwrap();
+        let release = create_test_release(&pool, 1, None).await.unwrap();
 
-        let delete_result = delete_release_service(&pool, Some(&user), release.slug.clone()).await;
+        let result =
+            publish_now(&pool, &RecordLabelCache::default(), Some(&user), release.id).await;
+
+        assert!(result.is_err());
         assert_eq!(
-            delete_result.unwrap_err().to_string(),
+            result.unwrap_err().to_string(),
             "error running server function: You do not have permission.".to_string()
         );
-
-        let get_result = get_release_service(&pool, Some(&user), artist.slug, release.slug).await;
-        assert!(get_result.is_ok());
     }
 
     #[sqlx::test]
-    async fn test_restore_release_service(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_preview_at(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        let published_at = chrono::Utc::now() + chrono::Duration::days(7);
+        release.published_at = Some(published_at);
+        let release = release.update(&pool).await.unwrap();
 
-        let release = create_test_release(&pool, 1, None).await.unwrap();
-        let release = release.delete(&pool).await.unwrap();
-        let restored_release = restore_release_service(&pool, Some(&user), release.slug.clone())
+        let now = preview_at(&pool, Some(&user), release.slug.clone(), chrono::Utc::now())
             .await
             .unwrap();
+        let later = preview_at(
+            &pool,
+            Some(&user),
+            release.slug.clone(),
+            published_at + chrono::Duration::days(1),
+        )
+        .await
+        .unwrap();
 
-        assert!(restored_release.release.deleted_at.is_none());
+        assert_eq!(now, ReleaseStatus::Scheduled);
+        assert_eq!(later, ReleaseStatus::Published);
+        let stored_release = Release::get_by_slug(&pool, release.slug.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(stored_release.published_at, release.published_at);
     }
 
     #[sqlx::test]
-    async fn test_restore_release_service_no_release(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_preview_at_draft(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
+        let mut release = create_test_release(&pool, 1, None).await.unwrap();
+        release.published_at = None;
+        let release = release.update(&pool).await.unwrap();
 
-        let restored_release =
-            restore_release_service(&pool, Some(&user), "missing".to_string()).await;
-        assert!(restored_release.is_err());
-        assert_eq!(
-            restored_release.unwrap_err().to_string(),
-            "error running server function: Could not find release with slug missing.".to_string()
-        );
-    }
+        let status = preview_at(
+            &pool,
+            Some(&user),
+            release.slug,
+            chrono::Utc::now() + chrono::Duration::days(365),
+        )
+        .await
+        .unwrap();
 
-    #[sqlx::test]
-    async fn test_restore_release_service_no_user(pool: PgPool) {
-        let release = create_test_release(&pool, 1, None).await.unwrap();
-        let release = release.delete(&pool).await.unwrap();
-        let restored_release =
-            restore_release_service(&pool, Some(&User::default()), release.slug.clone()).await;
-        assert!(restored_release.is_err());
-        assert_eq!(
-            restored_release.unwrap_err().to_string(),
-            "error running server function: You must be logged in to view this page.".to_string()
-        );
+        assert_eq!(status, ReleaseStatus::Draft);
     }
 
     #[sqlx::test]
-    async fn test_restore_release_service_no_permissions(pool: PgPool) {
+    async fn test_preview_at_no_permission(pool: PgPool) {
         let user = create_test_user_with_permissions(&pool, 1, vec![])
             .await
             .unwrap();
         let release = create_test_release(&pool, 1, None).await.unwrap();
-        let release = release.delete(&pool).await.unwrap();
-        let restored_release =
-            restore_release_service(&pool, Some(&user), release.slug.clone()).await;
-        assert!(restored_release.is_err());
+
+        let result = preview_at(&pool, Some(&user), release.slug, chrono::Utc::now()).await;
+
         assert_eq!(
-            restored_release.unwrap_err().to_string(),
+            result.unwrap_err().to_string(),
             "error running server function: You do not have permission.".to_string()
         );
     }
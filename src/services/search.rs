@@ -0,0 +1,140 @@
+//! Services for the search routes
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use crate::models::{artist::Artist, release::Release, track::Track};
+use crate::routes::search::SearchResults;
+use crate::utils::{pagination::Pagination, search::MAX_SEARCH_PER_PAGE};
+
+/// Search the public artists, releases and tracks on a record label
+///
+/// Within each type, exact matches come first, then prefix matches, then substring matches.
+/// Each type is paged separately, with at most `MAX_SEARCH_PER_PAGE` results on a page.
+/// A blank query returns no results.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `label_id` - The ID of the record label
+/// * `query` - The search query
+/// * `pagination` - The page of results to return
+///
+/// # Returns
+/// The search results
+///
+/// # Errors
+/// If the search fails, return an error
+#[cfg(feature = "ssr")]
+pub async fn search(
+    pool: &PgPool,
+    label_id: i64,
+    query: &str,
+    pagination: Pagination,
+) -> Result<SearchResults, ServerFnError> {
+    let pagination = Pagination::new(pagination.page, pagination.limit().min(MAX_SEARCH_PER_PAGE));
+    if query.trim().is_empty() {
+        return Ok(SearchResults {
+            pagination,
+            ..SearchResults::default()
+        });
+    }
+
+    let artists = Artist::search(pool, label_id, query, pagination)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while searching artists: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let releases = Release::search(pool, label_id, query, pagination)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while searching releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let tracks = Track::search(pool, label_id, query, pagination)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while searching tracks: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(SearchResults {
+        artists,
+        releases,
+        tracks,
+        pagination,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{create_test_artist, create_test_release, create_test_track};
+
+    #[sqlx::test]
+    async fn test_search_blank_query(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let results = search(&pool, artist.label_id, "  ", Pagination::default())
+            .await
+            .unwrap();
+
+        assert!(results.artists.is_empty());
+        assert!(results.releases.is_empty());
+        assert!(results.tracks.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_search(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let results = search(&pool, artist.label_id, "test", Pagination::default())
+            .await
+            .unwrap();
+
+        assert_eq!(results.artists, vec![artist]);
+        assert_eq!(results.releases, vec![release]);
+        assert_eq!(results.tracks, vec![track]);
+    }
+
+    #[sqlx::test]
+    async fn test_search_pages(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        for id in 1..=3 {
+            create_test_release(&pool, id, Some(artist.clone()))
+                .await
+                .unwrap();
+        }
+
+        let first = search(&pool, artist.label_id, "test", Pagination::new(1, 2))
+            .await
+            .unwrap();
+        let second = search(&pool, artist.label_id, "test", Pagination::new(2, 2))
+            .await
+            .unwrap();
+
+        assert_eq!(first.releases.len(), 2);
+        assert_eq!(second.releases.len(), 1);
+        assert!(!first.releases.contains(&second.releases[0]));
+    }
+
+    #[sqlx::test]
+    async fn test_search_caps_page_size(pool: PgPool) {
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+
+        let results = search(&pool, artist.label_id, "test", Pagination::new(1, 500))
+            .await
+            .unwrap();
+
+        assert_eq!(results.pagination.per_page, MAX_SEARCH_PER_PAGE);
+    }
+}
@@ -0,0 +1,538 @@
+//! Sitemap service module
+//!
+//! Lists the public pages of the site, so search engines can find them.
+//! Only items that an anonymous visitor can see are included.
+
+use leptos::prelude::ServerFnError;
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+
+#[cfg(feature = "ssr")]
+use crate::config::site::base_url;
+#[cfg(feature = "ssr")]
+use crate::models::{
+    query_helpers::{published_clause, release_visible_clause},
+    record_label::RecordLabel,
+};
+#[cfg(feature = "ssr")]
+use crate::utils::{
+    urls::{absolute_url, artist_url, page_url, release_url},
+    xml::escape_xml,
+};
+
+/// The most URLs a single sitemap file may contain, as set by the sitemap protocol.
+pub const MAX_SITEMAP_URLS: usize = 50_000;
+
+/// The namespace of sitemap documents
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// A single entry in the sitemap
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SitemapUrl {
+    /// The absolute URL of the page
+    pub loc: String,
+    /// When the page last changed, if known
+    pub lastmod: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The pages listed at the start of every sitemap, before the artists, releases and pages from the database
+const STATIC_PATHS: [&str; 2] = ["/", "/artists"];
+
+/// A public artist, release or page, as listed in the sitemap
+#[cfg(feature = "ssr")]
+#[derive(sqlx::FromRow)]
+struct SitemapEntry {
+    /// 1 for artists, 2 for releases and 3 for pages
+    section: i32,
+    /// The slug of the artist, or of the artist the release is listed under
+    artist_slug: Option<String>,
+    /// The slug of the release or page
+    slug: Option<String>,
+    /// When the entry last changed
+    lastmod: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl SitemapEntry {
+    fn into_url(self) -> SitemapUrl {
+        let artist_slug = self.artist_slug.unwrap_or_default();
+        let slug = self.slug.unwrap_or_default();
+        let loc = match self.section {
+            1 => artist_url(&artist_slug),
+            2 => release_url(&artist_slug, &slug),
+            _ => page_url(&slug),
+        };
+        SitemapUrl {
+            loc,
+            lastmod: self.lastmod,
+        }
+    }
+}
+
+/// The public artists, releases and pages of a record label as a common table expression called `entries`
+///
+/// Each row is one sitemap URL, ordered by `section` then `position`: artists, then releases, then pages.
+/// Tracks don't have a page of their own, so a release's `lastmod` is the latest change to the release or any of its tracks.
+/// A release by several artists is listed once, under its primary artist where they are public,
+/// otherwise under the first public artist it is listed for.
+///
+/// The record label ID is bound as `$1`.
+#[cfg(feature = "ssr")]
+fn sitemap_entries() -> String {
+    format!(
+        "WITH public_artists AS (
+            SELECT artists.id, artists.slug, artists.updated_at,
+              ROW_NUMBER() OVER (
+                ORDER BY artists.published_at DESC, sort_name_or_default(artists.sort_name, artists.name) ASC
+              ) AS position
+            FROM artists
+            WHERE artists.label_id = $1
+              AND {artists}
+              AND artists.unlisted = FALSE
+          ),
+          release_listings AS (
+            SELECT DISTINCT ON (releases.id)
+              releases.id, releases.slug, releases.updated_at, releases.release_date,
+              sort_name_or_default(releases.sort_name, releases.name) AS sort_key,
+              public_artists.slug AS artist_slug
+            FROM releases
+            INNER JOIN (
+              SELECT release_id, artist_id FROM release_artists
+              UNION
+              SELECT tracks.release_id, track_artists.artist_id
+              FROM tracks
+              INNER JOIN track_artists ON track_artists.track_id = tracks.id
+              INNER JOIN releases various ON various.id = tracks.release_id AND various.is_various
+              WHERE {tracks}
+            ) AS listed ON listed.release_id = releases.id
+            INNER JOIN public_artists ON public_artists.id = listed.artist_id
+            WHERE releases.label_id = $1
+              AND {releases}
+              AND {visible_releases}
+            ORDER BY releases.id, public_artists.id = releases.primary_artist_id DESC, public_artists.position ASC
+          ),
+          entries AS (
+            SELECT 1 AS section, position, slug::TEXT AS artist_slug, NULL::TEXT AS slug, updated_at AS lastmod
+            FROM public_artists
+            UNION ALL
+            SELECT 2, ROW_NUMBER() OVER (ORDER BY release_date DESC NULLS LAST, sort_key ASC),
+              artist_slug::TEXT, slug::TEXT,
+              GREATEST(updated_at, (
+                SELECT MAX(tracks.updated_at) FROM tracks
+                WHERE tracks.release_id = release_listings.id
+                  AND {tracks}
+                  AND tracks.archived_at IS NULL
+              ))
+            FROM release_listings
+            UNION ALL
+            SELECT 3, ROW_NUMBER() OVER (ORDER BY pages.published_at DESC, pages.name ASC),
+              NULL::TEXT, pages.slug::TEXT, pages.updated_at
+            FROM pages
+            WHERE pages.label_id = $1
+              AND {pages}
+          )",
+        artists = published_clause("artists"),
+        releases = published_clause("releases"),
+        visible_releases = release_visible_clause("releases"),
+        tracks = published_clause("tracks"),
+        pages = published_clause("pages"),
+    )
+}
+
+/// Count the public URLs of the site
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: &`RecordLabel` - The record label to count the pages of
+///
+/// # Returns
+/// Result<usize, `ServerFnError`> - The number of URLs in the sitemap
+///
+/// # Errors
+/// If the artists, releases or pages cannot be counted, return an error
+#[cfg(feature = "ssr")]
+pub async fn sitemap_url_count(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+) -> Result<usize, ServerFnError> {
+    let query = format!("{} SELECT COUNT(*) FROM entries", sitemap_entries());
+    let count = sqlx::query_scalar::<_, i64>(&query)
+        .bind(record_label.id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while counting sitemap URLs: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(STATIC_PATHS.len() + usize::try_from(count).unwrap_or_default())
+}
+
+/// List a window of the public URLs of the site
+///
+/// Only the requested window is read from the database, so each page of a large sitemap is a single query.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: &`RecordLabel` - The record label to list the pages of
+/// `offset`: usize - The number of URLs to skip
+/// `limit`: usize - The most URLs to return
+///
+/// # Returns
+/// Result<Vec<`SitemapUrl`>, `ServerFnError`> - The URLs, in the order they should be listed
+///
+/// # Errors
+/// If the artists, releases or pages cannot be retrieved, return an error
+#[cfg(feature = "ssr")]
+pub async fn sitemap_urls(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<SitemapUrl>, ServerFnError> {
+    let base = base_url();
+    let mut urls = STATIC_PATHS
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .map(|path| SitemapUrl {
+            loc: absolute_url(&base, path),
+            lastmod: None,
+        })
+        .collect::<Vec<_>>();
+    let entry_limit = limit - urls.len();
+    if entry_limit == 0 {
+        return Ok(urls);
+    }
+    let entry_offset = offset.saturating_sub(STATIC_PATHS.len());
+
+    let query = format!(
+        "{} SELECT section, artist_slug, slug, lastmod FROM entries
+         ORDER BY section ASC, position ASC
+         LIMIT $2 OFFSET $3",
+        sitemap_entries()
+    );
+    let entries = sqlx::query_as::<_, SitemapEntry>(&query)
+        .bind(record_label.id)
+        .bind(i64::try_from(entry_limit).unwrap_or(i64::MAX))
+        .bind(i64::try_from(entry_offset).unwrap_or(i64::MAX))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting sitemap URLs: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    urls.extend(entries.into_iter().map(SitemapEntry::into_url));
+
+    Ok(urls)
+}
+
+/// Format a date as a W3C datetime, as used by `lastmod`
+fn format_lastmod(lastmod: chrono::DateTime<chrono::Utc>) -> String {
+    lastmod.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Write a list of URLs as a sitemap document
+#[must_use]
+pub fn render_urlset(urls: &[SitemapUrl]) -> String {
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(r#"<urlset xmlns="{SITEMAP_NAMESPACE}">"#),
+    ];
+    for url in urls {
+        lines.push("  <url>".to_string());
+        lines.push(format!("    <loc>{}</loc>", escape_xml(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            lines.push(format!(
+                "    <lastmod>{}</lastmod>",
+                format_lastmod(lastmod)
+            ));
+        }
+        lines.push("  </url>".to_string());
+    }
+    lines.push("</urlset>".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Write a sitemap index, pointing to each page of a sitemap that is too large for one file
+///
+/// # Arguments
+/// `sitemap_url`: &str - The absolute URL of the sitemap
+/// `pages`: usize - The number of pages in the sitemap
+#[must_use]
+pub fn render_sitemap_index(sitemap_url: &str, pages: usize) -> String {
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(r#"<sitemapindex xmlns="{SITEMAP_NAMESPACE}">"#),
+    ];
+    for page in 1..=pages {
+        lines.push("  <sitemap>".to_string());
+        lines.push(format!(
+            "    <loc>{}</loc>",
+            escape_xml(&format!("{sitemap_url}?page={page}"))
+        ));
+        lines.push("  </sitemap>".to_string());
+    }
+    lines.push("</sitemapindex>".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Build the sitemap for the record label
+///
+/// Up to `MAX_SITEMAP_URLS` URLs are written as a single sitemap. Only the requested page of URLs is read from the database.
+/// Larger sitemaps are split into pages of `MAX_SITEMAP_URLS`, and without a page a sitemap index listing the pages is returned.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// `record_label`: &`RecordLabel` - The record label to build the sitemap for
+/// `sitemap_url`: &str - The absolute URL the sitemap is served from, used in the sitemap index
+/// `page`: Option<usize> - The page of the sitemap, starting from 1
+///
+/// # Returns
+/// Result<Option<String>, `ServerFnError`> - The XML document, or None if the page doesn't exist
+///
+/// # Errors
+/// If the URLs cannot be listed, return an error
+#[cfg(feature = "ssr")]
+pub async fn sitemap(
+    pool: &PgPool,
+    record_label: &RecordLabel,
+    sitemap_url: &str,
+    page: Option<usize>,
+) -> Result<Option<String>, ServerFnError> {
+    let count = sitemap_url_count(pool, record_label).await?;
+    let pages = count.div_ceil(MAX_SITEMAP_URLS).max(1);
+
+    let page = match page {
+        None if pages > 1 => return Ok(Some(render_sitemap_index(sitemap_url, pages))),
+        None => 1,
+        Some(page) if page == 0 || page > pages => return Ok(None),
+        Some(page) => page,
+    };
+    let urls = sitemap_urls(
+        pool,
+        record_label,
+        (page - 1) * MAX_SITEMAP_URLS,
+        MAX_SITEMAP_URLS,
+    )
+    .await?;
+    Ok(Some(render_urlset(&urls)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_page, create_test_record_label, create_test_release,
+        create_test_track,
+    };
+
+    #[test]
+    fn test_render_urlset() {
+        let lastmod = chrono::DateTime::parse_from_rfc3339("2025-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let sitemap = render_urlset(&[
+            SitemapUrl {
+                loc: "https://example.com/pages/a&b".to_string(),
+                lastmod: Some(lastmod),
+            },
+            SitemapUrl {
+                loc: "https://example.com/".to_string(),
+                lastmod: None,
+            },
+        ]);
+
+        assert!(sitemap.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset"));
+        assert!(sitemap.contains("<loc>https://example.com/pages/a&amp;b</loc>"));
+        assert!(sitemap.contains("<lastmod>2025-01-02T03:04:05Z</lastmod>"));
+        assert_eq!(sitemap.matches("<url>").count(), 2);
+        assert_eq!(sitemap.matches("<lastmod>").count(), 1);
+        assert!(sitemap.ends_with("</urlset>\n"));
+    }
+
+    #[test]
+    fn test_render_sitemap_index() {
+        let index = render_sitemap_index("https://example.com/sitemap.xml", 2);
+
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<loc>https://example.com/sitemap.xml?page=1</loc>"));
+        assert!(index.contains("<loc>https://example.com/sitemap.xml?page=2</loc>"));
+        assert_eq!(index.matches("<sitemap>").count(), 2);
+    }
+
+    #[sqlx::test]
+    async fn test_sitemap_urls(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let page = create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let urls = sitemap_urls(&pool, &record_label, 0, MAX_SITEMAP_URLS)
+            .await
+            .unwrap();
+
+        let locs = urls.iter().map(|url| url.loc.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            locs,
+            vec![
+                absolute_url(&base_url(), "/"),
+                absolute_url(&base_url(), "/artists"),
+                artist_url(&artist.slug),
+                release_url(&artist.slug, &release.slug),
+                page_url(&page.slug),
+            ]
+        );
+        assert_eq!(urls[2].lastmod, Some(artist.updated_at));
+        assert_eq!(
+            urls[3].lastmod,
+            Some(release.updated_at.max(track.updated_at))
+        );
+        assert_eq!(urls[4].lastmod, Some(page.updated_at));
+    }
+
+    #[sqlx::test]
+    async fn test_sitemap_urls_window(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let page = create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(sitemap_url_count(&pool, &record_label).await.unwrap(), 5);
+
+        let locs = |urls: Vec<SitemapUrl>| urls.into_iter().map(|url| url.loc).collect::<Vec<_>>();
+        assert_eq!(
+            locs(sitemap_urls(&pool, &record_label, 1, 2).await.unwrap()),
+            vec![
+                absolute_url(&base_url(), "/artists"),
+                artist_url(&artist.slug),
+            ]
+        );
+        assert_eq!(
+            locs(sitemap_urls(&pool, &record_label, 3, 10).await.unwrap()),
+            vec![
+                release_url(&artist.slug, &release.slug),
+                page_url(&page.slug),
+            ]
+        );
+        assert!(
+            sitemap_urls(&pool, &record_label, 5, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_sitemap_urls_hides_unpublished(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        release.published_at = None;
+        release.update(&pool).await.unwrap();
+        let mut hidden_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        hidden_artist.published_at = None;
+        hidden_artist.update(&pool).await.unwrap();
+        let mut page = create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        page.published_at = None;
+        page.update(&pool).await.unwrap();
+
+        let urls = sitemap_urls(&pool, &record_label, 0, MAX_SITEMAP_URLS)
+            .await
+            .unwrap();
+
+        let locs = urls.iter().map(|url| url.loc.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            locs,
+            vec![
+                absolute_url(&base_url(), "/"),
+                absolute_url(&base_url(), "/artists"),
+                artist_url(&artist.slug),
+            ]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_sitemap_lists_shared_release_once(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let other_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO release_artists (release_id, artist_id) VALUES ($1, $2)")
+            .bind(release.id)
+            .bind(other_artist.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let sitemap = sitemap(
+            &pool,
+            &record_label,
+            "https://example.com/sitemap.xml",
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            sitemap
+                .matches(&release_url(&artist.slug, &release.slug))
+                .count(),
+            1
+        );
+        assert!(!sitemap.contains(&release_url(&other_artist.slug, &release.slug)));
+    }
+
+    #[sqlx::test]
+    async fn test_sitemap_pages(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let sitemap_url = "https://example.com/sitemap.xml";
+
+        let first_page = sitemap(&pool, &record_label, sitemap_url, Some(1))
+            .await
+            .unwrap();
+        let missing_page = sitemap(&pool, &record_label, sitemap_url, Some(2))
+            .await
+            .unwrap();
+        let zero_page = sitemap(&pool, &record_label, sitemap_url, Some(0))
+            .await
+            .unwrap();
+
+        assert!(first_page.unwrap().contains("<urlset"));
+        assert!(missing_page.is_none());
+        assert!(zero_page.is_none());
+    }
+}
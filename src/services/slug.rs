@@ -0,0 +1,167 @@
+//! Slug service module
+
+use leptos::prelude::ServerFnError;
+#[cfg(feature = "ssr")]
+use sqlx::PgPool;
+
+#[cfg(feature = "ssr")]
+use crate::models::{
+    artist::Artist, page::Page, record_label::RecordLabel, release::Release, track::Track,
+};
+#[cfg(feature = "ssr")]
+use crate::state::RecordLabelCache;
+#[cfg(feature = "ssr")]
+use crate::utils::slugify::is_reserved_slug;
+
+/// Is a slug available
+///
+/// Applies the same rules as validation, so the UI can warn about a slug before the form is submitted.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// cache: `RecordLabelCache` - The record label cache, as this is checked as the user types
+/// `entity_type`: &str - The type of entity the slug is for, one of "artist", "release", "page" or "track"
+/// `proposed_slug`: String - The slug to check
+/// `label_id`: i64 - The record label the entity belongs to
+///
+/// # Returns
+/// Result<bool, `ServerFnError`> - True if the slug is free to use
+///
+/// # Errors
+/// If the entity type is unknown, return an error
+/// If the record label does not exist, return an error
+#[cfg(feature = "ssr")]
+pub async fn slug_available(
+    pool: &PgPool,
+    cache: &RecordLabelCache,
+    entity_type: &str,
+    proposed_slug: String,
+    label_id: i64,
+) -> Result<bool, ServerFnError> {
+    let slug = proposed_slug.trim().to_string();
+
+    RecordLabel::get_by_id_cached(pool, cache, label_id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting record label: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(format!("Record Label with id {label_id} does not exist."))
+        })?;
+
+    if slug.is_empty() || slug.len() > 255 || is_reserved_slug(&slug) {
+        return Ok(false);
+    }
+
+    let taken = match entity_type {
+        "artist" => Artist::get_by_slug(pool, slug, true).await.is_ok(),
+        "release" => Release::get_by_slug(pool, slug, true).await.is_ok(),
+        // Hidden pages still hold on to their slug
+        "page" => Page::get_by_slug(pool, slug, true).await.is_ok(),
+        "track" => Track::get_by_slug(pool, slug, true).await.is_ok(),
+        _ => return Err(ServerFnError::new("Unknown entity type.")),
+    };
+
+    Ok(!taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_page, create_test_record_label, create_test_release,
+    };
+
+    #[sqlx::test]
+    async fn test_slug_available(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        for entity_type in ["artist", "release", "page", "track"] {
+            let available = slug_available(
+                &pool,
+                &cache,
+                entity_type,
+                "free-slug".to_string(),
+                record_label.id,
+            )
+            .await
+            .unwrap();
+            assert!(available, "{entity_type} slug should be available");
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_slug_available_taken(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let page = create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+
+        let available = slug_available(&pool, &cache, "artist", artist.slug, record_label.id)
+            .await
+            .unwrap();
+        assert!(!available);
+        let available = slug_available(
+            &pool,
+            &cache,
+            "release",
+            release.slug.clone(),
+            record_label.id,
+        )
+        .await
+        .unwrap();
+        assert!(!available);
+        let available = slug_available(&pool, &cache, "page", page.slug, record_label.id)
+            .await
+            .unwrap();
+        assert!(!available);
+
+        // Slugs are only checked against the same type of entity
+        let available = slug_available(&pool, &cache, "artist", release.slug, record_label.id)
+            .await
+            .unwrap();
+        assert!(available);
+    }
+
+    #[sqlx::test]
+    async fn test_slug_available_reserved(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let available = slug_available(&pool, &cache, "page", "admin".to_string(), record_label.id)
+            .await
+            .unwrap();
+        assert!(!available);
+    }
+
+    #[sqlx::test]
+    async fn test_slug_available_unknown_entity_type(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+
+        let result =
+            slug_available(&pool, &cache, "label", "slug".to_string(), record_label.id).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Unknown entity type."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_slug_available_no_record_label(pool: PgPool) {
+        let cache = RecordLabelCache::default();
+        let result = slug_available(&pool, &cache, "artist", "slug".to_string(), 1).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Record Label with id 1 does not exist."
+        );
+    }
+}
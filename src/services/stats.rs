@@ -0,0 +1,181 @@
+//! Services for the dashboard stats
+//!
+//! Counts of the content on a record label. Deleted content is never counted.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::authentication_helpers::user_with_permissions;
+use crate::models::{auth::User, query_helpers::published_clause};
+use crate::routes::dashboard::DashboardStats;
+
+/// Count the artists, releases, tracks and pages on a record label
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `user` - The current user
+/// * `label_id` - The ID of the record label
+///
+/// # Returns
+/// The counts for the record label
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the content cannot be counted, return an error
+pub async fn dashboard_stats(
+    pool: &PgPool,
+    user: Option<&User>,
+    label_id: i64,
+) -> Result<DashboardStats, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let query = format!(
+        "SELECT
+           (SELECT COUNT(*) FROM artists
+            WHERE label_id = $1 AND deleted_at IS NULL) AS total_artists,
+           (SELECT COUNT(*) FROM releases
+            WHERE label_id = $1 AND deleted_at IS NULL) AS total_releases,
+           (SELECT COUNT(*) FROM tracks
+            INNER JOIN releases ON releases.id = tracks.release_id
+            WHERE releases.label_id = $1
+              AND tracks.deleted_at IS NULL
+              AND releases.deleted_at IS NULL) AS total_tracks,
+           (SELECT COUNT(*) FROM releases
+            WHERE label_id = $1 AND {}) AS published_releases,
+           (SELECT COUNT(*) FROM releases
+            WHERE label_id = $1
+              AND deleted_at IS NULL
+              AND release_date > NOW()) AS upcoming_releases,
+           (SELECT COUNT(*) FROM pages
+            WHERE label_id = $1
+              AND deleted_at IS NULL
+              AND published_at IS NULL) AS draft_pages",
+        published_clause("")
+    );
+
+    sqlx::query_as::<_, DashboardStats>(&query)
+        .bind(label_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while counting dashboard stats: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new("Could not count the content on the record label.")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_page, create_test_record_label, create_test_release,
+        create_test_track, create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_dashboard_stats(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let deleted_artist = create_test_artist(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        deleted_artist.delete(&pool).await.unwrap();
+
+        // Published, released in the past
+        let published_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(&pool, 1, Some(published_release.clone()), None)
+            .await
+            .unwrap();
+        let deleted_track = create_test_track(&pool, 2, Some(published_release.clone()), None)
+            .await
+            .unwrap();
+        deleted_track.delete(&pool).await.unwrap();
+        // Published, released in the future
+        let mut upcoming_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        upcoming_release.release_date = Some(chrono::Utc::now() + chrono::Duration::days(7));
+        let upcoming_release = upcoming_release.update(&pool).await.unwrap();
+        create_test_track(&pool, 3, Some(upcoming_release), None)
+            .await
+            .unwrap();
+        // A draft
+        let mut draft_release = create_test_release(&pool, 3, Some(artist.clone()))
+            .await
+            .unwrap();
+        draft_release.published_at = None;
+        draft_release.update(&pool).await.unwrap();
+        let deleted_release = create_test_release(&pool, 4, Some(artist.clone()))
+            .await
+            .unwrap();
+        deleted_release.delete(&pool).await.unwrap();
+
+        create_test_page(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let mut draft_page = create_test_page(&pool, 2, Some(record_label.clone()))
+            .await
+            .unwrap();
+        draft_page.published_at = None;
+        draft_page.update(&pool).await.unwrap();
+        let mut deleted_page = create_test_page(&pool, 3, Some(record_label.clone()))
+            .await
+            .unwrap();
+        deleted_page.published_at = None;
+        deleted_page
+            .update(&pool)
+            .await
+            .unwrap()
+            .delete(&pool)
+            .await
+            .unwrap();
+
+        // Content on another record label is not counted
+        let other_release = create_test_release(&pool, 5, None).await.unwrap();
+        create_test_track(&pool, 4, Some(other_release), None)
+            .await
+            .unwrap();
+
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let stats = dashboard_stats(&pool, Some(&user), record_label.id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            DashboardStats {
+                total_artists: 1,
+                total_releases: 3,
+                total_tracks: 2,
+                published_releases: 2,
+                upcoming_releases: 1,
+                draft_pages: 1,
+            }
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_dashboard_stats_no_permission(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = dashboard_stats(&pool, Some(&user), record_label.id).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+}
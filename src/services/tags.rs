@@ -0,0 +1,198 @@
+//! Tag service module
+//!
+//! Tags organise the releases in the record label's catalogue.
+//! They are only available to label owners.
+
+use leptos::prelude::ServerFnError;
+use sqlx::PgPool;
+
+use super::{
+    authentication_helpers::user_with_permissions, errors::fetch_or_not_found,
+    read_only::ensure_writable,
+};
+use crate::models::{auth::User, record_label::RecordLabel, release::Release, tag::Tag};
+use crate::routes::bulk::BulkResult;
+
+/// Add a tag to, or remove it from, many releases at once
+///
+/// The change is atomic. If any release is missing or belongs to another record label, nothing is changed and that release is reported.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user tagging the releases
+/// `release_ids`: Vec<i64> - The IDs of the releases
+/// `tag_slug`: String - The tag, created if it doesn't exist yet
+/// add: bool - Add the tag if true, remove it if false
+///
+/// # Returns
+/// Result<`BulkResult<i64>`, `ServerFnError`> - The IDs of the changed releases
+///
+/// # Errors
+/// If the user does not have the required permissions, return an error
+/// If the record label cannot be found, return an error
+/// If the tag is empty, or cannot be added or removed, return an error
+pub async fn bulk_tag(
+    pool: &PgPool,
+    user: Option<&User>,
+    release_ids: Vec<i64>,
+    tag_slug: String,
+    add: bool,
+) -> Result<BulkResult<i64>, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let record_label = fetch_or_not_found(
+        RecordLabel::first(pool).await,
+        "Error while getting record label",
+    )?;
+
+    for &release_id in &release_ids {
+        let reason = match Release::get_by_id(pool, release_id).await {
+            Ok(release) if release.label_id != record_label.id => {
+                format!("Release with id {release_id} belongs to another record label.")
+            }
+            Ok(release) if release.deleted_at.is_some() => {
+                format!("Release with id {release_id} has been deleted.")
+            }
+            Ok(_) => continue,
+            Err(e) => e.to_string(),
+        };
+        return Ok(BulkResult::rolled_back(release_id, reason));
+    }
+
+    Tag::bulk_tag_releases(pool, record_label.id, &tag_slug, &release_ids, add)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while tagging releases: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(BulkResult {
+        succeeded: release_ids,
+        failed: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release,
+        create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_bulk_tag_add_and_remove(pool: PgPool) {
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let mut release_ids = vec![];
+        for id in 1..=3 {
+            let release = create_test_release(&pool, id, Some(artist.clone()))
+                .await
+                .unwrap();
+            release_ids.push(release.id);
+        }
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let result = bulk_tag(
+            &pool,
+            Some(&user),
+            release_ids.clone(),
+            "Deep House".to_string(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.succeeded, release_ids);
+        assert!(result.is_complete());
+        for &release_id in &release_ids {
+            let tags = Tag::list_by_release(&pool, release_id).await.unwrap();
+            assert_eq!(tags.len(), 1);
+            assert_eq!(tags[0].slug, "deep-house");
+        }
+
+        let result = bulk_tag(
+            &pool,
+            Some(&user),
+            release_ids.clone(),
+            "deep-house".to_string(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.succeeded, release_ids);
+        for &release_id in &release_ids {
+            assert!(
+                Tag::list_by_release(&pool, release_id)
+                    .await
+                    .unwrap()
+                    .is_empty()
+            );
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_tag_rejects_other_label(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let other_release = create_test_release(&pool, 2, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+
+        let result = bulk_tag(
+            &pool,
+            Some(&user),
+            vec![release.id, other_release.id],
+            "Deep House".to_string(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(result.succeeded.is_empty());
+        assert_eq!(
+            result.failed,
+            vec![(
+                other_release.id,
+                format!(
+                    "Release with id {} belongs to another record label.",
+                    other_release.id
+                )
+            )]
+        );
+        assert!(
+            Tag::list_by_release(&pool, release.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_bulk_tag_no_permission(pool: PgPool) {
+        let release = create_test_release(&pool, 1, None).await.unwrap();
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+
+        let result = bulk_tag(
+            &pool,
+            Some(&user),
+            vec![release.id],
+            "Deep House".to_string(),
+            true,
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Request timeouts
+//!
+//! A slow query shouldn't hold a connection and a request indefinitely, so server functions are aborted after the request timeout.
+use std::time::Duration;
+
+use leptos::prelude::ServerFnError;
+
+/// The error message returned when a request takes longer than the timeout.
+pub const REQUEST_TIMED_OUT: &str = "The request timed out.";
+
+/// Run a service, aborting it if it takes longer than the timeout
+///
+/// # Arguments
+/// timeout: `Duration` - How long the service may run for
+/// future: `Future` - The service call
+///
+/// # Returns
+/// Result<T, `ServerFnError`> - The result of the service
+///
+/// # Errors
+/// If the service takes longer than the timeout, return an error
+/// If the service returns an error, return it
+pub async fn with_timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, ServerFnError>>,
+) -> Result<T, ServerFnError> {
+    tokio::time::timeout(timeout, future).await.map_err(|_| {
+        tracing::error!("Request aborted after {timeout:?}");
+        ServerFnError::new(REQUEST_TIMED_OUT)
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_with_timeout_aborts_slow_query(pool: PgPool) {
+        let result = with_timeout(Duration::from_millis(50), async {
+            sqlx::query("SELECT pg_sleep(5)")
+                .execute(&pool)
+                .await
+                .map_err(ServerFnError::new)
+        })
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: The request timed out."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_with_timeout_completes_fast_query(pool: PgPool) {
+        let result = with_timeout(Duration::from_secs(5), async {
+            sqlx::query_scalar::<_, i32>("SELECT 1")
+                .fetch_one(&pool)
+                .await
+                .map_err(ServerFnError::new)
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 1);
+    }
+}
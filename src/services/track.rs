@@ -2,10 +2,26 @@
 use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
-use super::authentication_helpers::user_with_permissions;
+use super::{
+    artist::get_primary_artist,
+    authentication_helpers::{user_can_manage_artist, user_with_permissions},
+    errors::{ServiceError, fetch_or_not_found},
+    publishing::Republish,
+    read_only::ensure_writable,
+};
 use crate::forms::track::{CreateTrackForm, UpdateTrackForm};
-use crate::models::{artist::Artist, auth::User, release::Release, track::Track};
-use crate::routes::track::{TrackResult, TracksResult};
+use crate::models::{
+    artist::Artist,
+    auth::User,
+    placement::{Placement, PlacementEntity},
+    record_label::RecordLabel,
+    release::{Release, ReleaseError},
+    track::Track,
+    track_relation::TrackRelation,
+};
+use crate::routes::track::{ArtistTracksPage, TrackResult, TrackWithRelease, TracksResult};
+use crate::state::RecordLabelCache;
+use crate::utils::pagination::Pagination;
 
 /// Get an artists tracks
 ///
@@ -29,27 +45,19 @@ pub async fn get_tracks_service(
     user: Option<&User>,
     artist_slug: String,
     release_slug: String,
-) -> Result<TracksResult, ServerFnError> {
-    let artist = match Artist::get_by_slug(pool, artist_slug).await {
-        Ok(artist) => artist,
-        Err(e) => {
-            let err = format!("Error while getting artist: {e:?}");
-            tracing::error!("{err}");
-            return Err(ServerFnError::new(e));
-        }
-    };
-    let release = match Release::get_by_slug(pool, release_slug).await {
-        Ok(release) => release,
-        Err(e) => {
-            let err = format!("Error while getting release: {e:?}");
-            tracing::error!("{err}");
-            return Err(ServerFnError::new(e));
-        }
-    };
-
+) -> Result<TracksResult, ServiceError> {
     let include_hidden =
         user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
 
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, artist_slug, include_hidden).await,
+        "Error while getting artist",
+    )?;
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, release_slug, include_hidden).await,
+        "Error while getting release",
+    )?;
+
     Ok(TracksResult {
         tracks: match Track::list_by_release_and_artist_and_record_label(
             pool,
@@ -64,12 +72,59 @@ pub async fn get_tracks_service(
             Err(e) => {
                 let err = format!("Error while getting tracks: {e:?}");
                 tracing::error!("{err}");
-                return Err(ServerFnError::new(e));
+                return Err(ServiceError::Internal {
+                    message: e.to_string(),
+                });
             }
         },
     })
 }
 
+/// Get the tracks on a release, found by the release's slug alone
+/// The release's primary artist is used to look the tracks up, as in `get_tracks_service`.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+/// * `user` - The current user
+/// * `release_slug` - The slug of the release
+///
+/// # Returns
+/// The tracks on the release
+///
+/// # Errors
+/// * If the release cannot be found, return an error
+/// * If the primary artist cannot be found, return an error
+/// * If the tracks cannot be found, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_release_tracks_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    release_slug: String,
+) -> Result<TracksResult, ServiceError> {
+    let include_hidden =
+        user.is_some_and(|current_user| current_user.permissions.contains("label_owner"));
+
+    // Unpublished releases are hidden from everyone but label owners
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, release_slug.clone(), include_hidden)
+            .await
+            .and_then(|release| {
+                if include_hidden || release.is_visible_at(chrono::Utc::now(), None) {
+                    Ok(release)
+                } else {
+                    Err(ReleaseError::NotFoundBySlug(release_slug))
+                }
+            }),
+        "Error while getting release",
+    )?;
+    let artist = fetch_or_not_found(
+        Artist::get_by_id(pool, release.primary_artist_id).await,
+        "Error while getting artist",
+    )?;
+
+    get_tracks_service(pool, user, artist.slug, release.slug).await
+}
+
 /// Get a specific artists track
 ///
 /// # Arguments
@@ -95,50 +150,86 @@ pub async fn get_track_service(
     release_slug: String,
     track_slug: String,
 ) -> Result<TrackResult, ServerFnError> {
-    let artist = match Artist::get_by_slug(pool, artist_slug).await {
-        Ok(artist) => artist,
-        Err(e) => {
-            let err = format!("Error while getting artist: {e:?}");
-            tracing::error!("{err}");
-            return Err(ServerFnError::new(e));
-        }
-    };
-    let release = match Release::get_by_slug(pool, release_slug).await {
-        Ok(release) => release,
-        Err(e) => {
-            let err = format!("Error while getting release: {e:?}");
-            tracing::error!("{err}");
-            return Err(ServerFnError::new(e));
-        }
-    };
-
     let Some(current_user) = user else {
         return Err(ServerFnError::new("User not found"));
     };
     let include_hidden = current_user.permissions.contains("label_owner");
 
-    let track = Track::get_by_release_and_artist_and_record_label_and_slug(
-        pool,
-        release.id,
-        artist.id,
-        artist.label_id,
-        track_slug.clone(),
-        include_hidden,
-    )
-    .await
-    .map_err(|e| {
-        let err = format!("Error while getting track: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    let artist = fetch_or_not_found(
+        Artist::get_by_slug(pool, artist_slug, include_hidden).await,
+        "Error while getting artist",
+    )?;
+    let release = fetch_or_not_found(
+        Release::get_by_slug(pool, release_slug, include_hidden).await,
+        "Error while getting release",
+    )?;
+
+    let track = fetch_or_not_found(
+        Track::get_by_release_and_artist_and_record_label_and_slug(
+            pool,
+            release.id,
+            artist.id,
+            artist.label_id,
+            track_slug.clone(),
+            include_hidden,
+        )
+        .await,
+        "Error while getting track",
+    )?;
 
     let artists = track.get_artists(pool).await.map_err(|e| {
         let err = format!("Error while getting artists: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
+    let placements = if include_hidden {
+        Placement::list_by_entity(pool, PlacementEntity::Track, track.id)
+            .await
+            .map_err(|e| {
+                let err = format!("Error while getting placements: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?
+    } else {
+        vec![]
+    };
+    let relations = if include_hidden {
+        track.get_relations(pool).await.map_err(|e| {
+            let err = format!("Error while getting relations: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?
+    } else {
+        vec![]
+    };
 
-    Ok(TrackResult { track, artists })
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+
+    Ok(TrackResult {
+        placements,
+        relations,
+        ..TrackResult::new(track, artists, &primary_artist)
+    })
+}
+
+/// Return a user who can manage the tracks on a release
+/// Tracks are managed by whoever can manage the primary artist of their release.
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the user cannot manage the release's primary artist, return an error
+#[cfg(feature = "ssr")]
+async fn user_can_manage_release(
+    pool: &PgPool,
+    user: Option<&User>,
+    release_id: i64,
+) -> Result<User, ServerFnError> {
+    let release = fetch_or_not_found(
+        Release::get_by_id(pool, release_id).await,
+        "Error while getting release",
+    )?;
+
+    user_can_manage_artist(pool, user, release.primary_artist_id).await
 }
 
 /// Create a new track
@@ -161,10 +252,11 @@ pub async fn create_track_service(
     user: Option<&User>,
     form: CreateTrackForm,
 ) -> Result<TrackResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+    match user_can_manage_release(pool, user, form.release_id).await {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
     let track = Track::create(
         pool,
@@ -175,6 +267,9 @@ pub async fn create_track_service(
         form.release_id,
         form.isrc_code,
         form.bpm,
+        form.preview_url,
+        form.preview_start_ms,
+        form.duration_ms,
         form.track_number,
         form.published_at,
     )
@@ -201,7 +296,9 @@ pub async fn create_track_service(
         ServerFnError::new(e)
     })?;
 
-    Ok(TrackResult { track, artists })
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+
+    Ok(TrackResult::new(track, artists, &primary_artist))
 }
 
 /// Update a track
@@ -224,16 +321,18 @@ pub async fn update_track_service(
     user: Option<&User>,
     form: UpdateTrackForm,
 ) -> Result<TrackResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
-        Ok(_) => (),
-        Err(e) => return Err(e),
+    let mut track = fetch_or_not_found(
+        Track::get_by_slug(pool, form.slug, true).await,
+        "Error while getting track by slug",
+    )?;
+    // A manager can't move a track to, or take a track from, a release they don't manage
+    for release_id in [track.release_id, form.release_id] {
+        match user_can_manage_release(pool, user, release_id).await {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
     }
-
-    let mut track = Track::get_by_slug(pool, form.slug).await.map_err(|e| {
-        let err = format!("Error while getting track by slug: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    ensure_writable(pool).await?;
 
     track.name = form.name;
     track.description = form.description;
@@ -242,32 +341,38 @@ pub async fn update_track_service(
     track.release_id = form.release_id;
     track.isrc_code = form.isrc_code;
     track.bpm = form.bpm;
+    track.preview_url = form.preview_url;
+    track.preview_start_ms = form.preview_start_ms;
+    track.duration_ms = form.duration_ms;
+    track.explicit = form.explicit;
+    track.clean_version_of = form.clean_version_of;
+    track.lock_slug = form.lock_slug.or(track.lock_slug);
     track.published_at = form.published_at;
     track.track_number = form.track_number;
 
-    track = track.update(pool).await.map_err(|e| {
-        let err = format!("Error while updating track: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
-
     let artist_ids = form
         .artist_ids
         .split(',')
         .filter_map(|s| s.parse::<i64>().ok())
         .collect::<Vec<i64>>();
-    track.set_artists(pool, artist_ids).await.map_err(|e| {
-        let err = format!("Error while setting artists: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    // Update the track and its artists together, so a failure leaves both untouched
+    track = track
+        .update_with_artists(pool, artist_ids)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while updating track: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
     let artists = track.get_artists(pool).await.map_err(|e| {
         let err = format!("Error while getting artists: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
 
-    Ok(TrackResult { track, artists })
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+
+    Ok(TrackResult::new(track, artists, &primary_artist))
 }
 
 /// Soft delete a track
@@ -289,29 +394,29 @@ pub async fn delete_track_service(
     user: Option<&User>,
     slug: String,
 ) -> Result<TrackResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+    let track = fetch_or_not_found(
+        Track::get_by_slug(pool, slug, true).await,
+        "Error while getting track",
+    )?;
+    match user_can_manage_release(pool, user, track.release_id).await {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
 
-    let track = Track::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting track: {e:?}");
+    let artists = track.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+    let track = track.delete(pool).await.map_err(|e| {
+        let err = format!("Error while deleting track: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
 
-    Ok(TrackResult {
-        track: track.delete(pool).await.map_err(|e| {
-            let err = format!("Error while deleting track: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-        artists: track.get_artists(pool).await.map_err(|e| {
-            let err = format!("Error while getting artists: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    Ok(TrackResult::new(track, artists, &primary_artist))
 }
 
 /// Restore a soft deleted track
@@ -320,6 +425,7 @@ pub async fn delete_track_service(
 /// pool: `PgPool` - The database connection pool
 /// user: Option<&User> - The user deleting the track
 /// slug: String - The slug of the track
+/// republish: `Republish` - Keep the original `published_at`, restore as a draft, or publish at a given date
 ///
 /// # Returns
 /// Result<`TrackResult`, `ServerFnError`> - The restored track
@@ -332,113 +438,373 @@ pub async fn restore_track_service(
     pool: &PgPool,
     user: Option<&User>,
     slug: String,
+    republish: Republish,
 ) -> Result<TrackResult, ServerFnError> {
-    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+    let mut track = fetch_or_not_found(
+        Track::get_by_slug(pool, slug, true).await,
+        "Error while getting track",
+    )?;
+    match user_can_manage_release(pool, user, track.release_id).await {
         Ok(_) => (),
         Err(e) => return Err(e),
     }
-
-    let mut track = Track::get_by_slug(pool, slug).await.map_err(|e| {
-        let err = format!("Error while getting track: {e:?}");
-        tracing::error!("{err}");
-        ServerFnError::new(e)
-    })?;
+    ensure_writable(pool).await?;
     track.deleted_at = None;
+    track.published_at = republish.published_at(track.published_at);
     track.clone().update(pool).await.map_err(|e| {
         let err = format!("Error while restoring track: {e:?}");
         tracing::error!("{err}");
         ServerFnError::new(e)
     })?;
 
-    Ok(TrackResult {
-        track: track.clone(),
-        artists: track.get_artists(pool).await.map_err(|e| {
-            let err = format!("Error while getting artists: {e:?}");
-            tracing::error!("{err}");
-            ServerFnError::new(e)
-        })?,
-    })
+    let artists = track.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+
+    Ok(TrackResult::new(track, artists, &primary_artist))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[cfg(feature = "ssr")]
-    use crate::models::test_helpers::{
-        create_test_artist, create_test_record_label, create_test_release, create_test_track,
-        create_test_user, create_test_user_with_permissions,
-    };
+/// Archive or unarchive a track
+/// Archived tracks are kept out of listings, but can still be reached by their slug.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user archiving the track
+/// slug: String - The slug of the track
+/// archived: bool - Whether the track should be archived
+///
+/// # Returns
+/// Result<`TrackResult`, `ServerFnError`> - The archived or unarchived track
+///
+/// # Errors
+/// If the track cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn archive_track_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    archived: bool,
+) -> Result<TrackResult, ServerFnError> {
+    let track = fetch_or_not_found(
+        Track::get_by_slug(pool, slug, true).await,
+        "Error while getting track",
+    )?;
+    match user_can_manage_release(pool, user, track.release_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
 
-    #[sqlx::test]
-    async fn test_get_tracks_service_admin_user(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
-            .await
-            .unwrap();
+    let artists = track.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
+    let track = track.set_archived(pool, archived).await.map_err(|e| {
+        let err = format!("Error while archiving track: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
 
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
-            .await
-            .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
-            .await
-            .unwrap();
-        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
-            .await
-            .unwrap();
-        let mut unpublished_track =
-            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
-                .await
-                .unwrap();
-        unpublished_track.published_at = None;
-        unpublished_track.clone().update(&pool).await.unwrap();
+    Ok(TrackResult::new(track, artists, &primary_artist))
+}
 
-        let tracks = get_tracks_service(
-            &pool,
-            Some(&user),
-            artist.slug.clone(),
-            release.slug.clone(),
-        )
-        .await
-        .unwrap();
-        assert_eq!(tracks.tracks.len(), 2);
-        assert_eq!(tracks.tracks[0].id, track.id);
-        assert_eq!(tracks.tracks[1].id, unpublished_track.id);
+/// Get the archived tracks on the record label, for the admin archive
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+///
+/// # Returns
+/// Result<`TracksResult`, `ServerFnError`> - The archived tracks, most recently archived first
+///
+/// # Errors
+/// If the record label cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn get_archived_tracks_service(
+    pool: &PgPool,
+    user: Option<&User>,
+) -> Result<TracksResult, ServerFnError> {
+    match user_with_permissions(user, vec!["admin", "label_owner"]) {
+        Ok(_) => (),
+        Err(e) => return Err(e),
     }
 
-    #[sqlx::test]
-    async fn test_get_tracks_service_no_user(pool: PgPool) {
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
-            .await
-            .unwrap();
-        let release = create_test_release(&pool, 1, Some(artist.clone()))
-            .await
-            .unwrap();
-        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
-            .await
-            .unwrap();
-        let mut unpublished_track =
-            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
-                .await
-                .unwrap();
-        unpublished_track.published_at = None;
-        unpublished_track.clone().update(&pool).await.unwrap();
+    let record_label = fetch_or_not_found(
+        RecordLabel::first(pool).await,
+        "Error while getting record label",
+    )?;
 
-        let tracks = get_tracks_service(&pool, None, artist.slug.clone(), release.slug.clone())
+    Ok(TracksResult {
+        tracks: Track::list_archived(pool, record_label.id)
             .await
-            .unwrap();
+            .map_err(|e| {
+                let err = format!("Error while getting archived tracks: {e:?}");
+                tracing::error!("{err}");
+                ServerFnError::new(e)
+            })?,
+    })
+}
 
-        assert_eq!(tracks.tracks.len(), 1);
-        assert_eq!(tracks.tracks[0].id, track.id);
+/// Set the works a track covers, samples or remixes
+/// Any existing relations are replaced.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user setting the relations
+/// slug: String - The slug of the track
+/// relations: Vec<`TrackRelation`> - The relations for the track
+///
+/// # Returns
+/// Result<`TrackResult`, `ServerFnError`> - The track and its relations
+///
+/// # Errors
+/// If the track cannot be found, return an error
+/// If any of the relations are invalid, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn set_track_relations_service(
+    pool: &PgPool,
+    user: Option<&User>,
+    slug: String,
+    relations: Vec<TrackRelation>,
+) -> Result<TrackResult, ServerFnError> {
+    let track = fetch_or_not_found(
+        Track::get_by_slug(pool, slug, true).await,
+        "Error while getting track",
+    )?;
+    match user_can_manage_release(pool, user, track.release_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
     }
+    ensure_writable(pool).await?;
+    let relations = track.set_relations(pool, relations).await.map_err(|e| {
+        let err = format!("Error while setting relations: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+    let artists = track.get_artists(pool).await.map_err(|e| {
+        let err = format!("Error while getting artists: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
 
-    #[sqlx::test]
-    async fn test_get_tracks_service_anonymous_user(pool: PgPool) {
-        let (user, _) = create_test_user(&pool, 1).await.unwrap().into_user(None);
+    let primary_artist = get_primary_artist(pool, track.primary_artist_id, &artists).await?;
 
-        let record_label = create_test_record_label(&pool, 1).await.unwrap();
-        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+    Ok(TrackResult {
+        relations,
+        ..TrackResult::new(track, artists, &primary_artist)
+    })
+}
+
+/// List every track on the artist's releases, for the admin export
+/// Drafts are always included, deleted tracks only when asked for.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The current user
+/// `artist_id`: i64 - The ID of the artist
+/// pagination: `Pagination` - The page to list
+/// `include_deleted`: bool - Whether to include deleted tracks, and tracks on deleted releases
+///
+/// # Returns
+/// Result<`ArtistTracksPage`, `ServerFnError`> - The page of tracks, each with its release
+///
+/// # Errors
+/// If the artist cannot be found, return an error
+/// If the tracks or their releases cannot be found, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn list_all_for_artist_admin(
+    pool: &PgPool,
+    user: Option<&User>,
+    artist_id: i64,
+    pagination: Pagination,
+    include_deleted: bool,
+) -> Result<ArtistTracksPage, ServerFnError> {
+    match user_can_manage_artist(pool, user, artist_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+
+    let artist = fetch_or_not_found(
+        Artist::get_by_id(pool, artist_id).await,
+        "Error while getting artist",
+    )?;
+
+    let total = Track::count_all_for_artist(pool, artist.id, include_deleted)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while counting artist tracks: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let tracks = Track::list_all_for_artist(pool, artist.id, pagination, include_deleted)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting artist tracks: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    // Tracks are grouped by release, so each release only needs fetching once
+    let mut tracks_with_releases: Vec<TrackWithRelease> = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        let release = match tracks_with_releases.last() {
+            Some(previous) if previous.release.id == track.release_id => previous.release.clone(),
+            _ => Release::get_by_id(pool, track.release_id)
+                .await
+                .map_err(|e| {
+                    let err = format!("Error while getting release: {e:?}");
+                    tracing::error!("{err}");
+                    ServerFnError::new(e)
+                })?,
+        };
+        tracks_with_releases.push(TrackWithRelease { track, release });
+    }
+
+    Ok(ArtistTracksPage {
+        tracks: tracks_with_releases,
+        pagination: Pagination::new(pagination.page, pagination.per_page),
+        total,
+    })
+}
+
+/// Assign ISRC codes to all tracks on a release that don't have one
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// cache: `RecordLabelCache` - The record label cache
+/// user: Option<&User> - The user assigning the ISRC codes
+/// `release_id`: i64 - The ID of the release
+/// year: i32 - The year of reference for the ISRC codes
+///
+/// # Returns
+/// Result<`TracksResult`, `ServerFnError`> - The tracks that were assigned an ISRC code
+///
+/// # Errors
+/// If the release cannot be found, return an error
+/// If the record label's ISRC base is invalid, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn assign_isrcs_for_release(
+    pool: &PgPool,
+    cache: &RecordLabelCache,
+    user: Option<&User>,
+    release_id: i64,
+    year: i32,
+) -> Result<TracksResult, ServerFnError> {
+    match user_can_manage_release(pool, user, release_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    ensure_writable(pool).await?;
+
+    let release = fetch_or_not_found(
+        Release::get_by_id(pool, release_id).await,
+        "Error while getting release",
+    )?;
+    let record_label = fetch_or_not_found(
+        RecordLabel::get_by_id_cached(pool, cache, release.label_id).await,
+        "Error while getting record label",
+    )?;
+
+    let tracks = release
+        .assign_isrcs(pool, &record_label.isrc_base, year)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while assigning ISRC codes: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(TracksResult { tracks })
+}
+
+/// Move tracks to another release
+/// The tracks keep their IDs and ISRC codes, so nothing needs deleting and recreating.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// user: Option<&User> - The user moving the tracks
+/// `track_ids`: Vec<i64> - The IDs of the tracks to move
+/// `target_release_id`: i64 - The ID of the release to move the tracks to
+///
+/// # Returns
+/// Result<`TracksResult`, `ServerFnError`> - The moved tracks
+///
+/// # Errors
+/// If no tracks are given, return an error
+/// If the release or a track cannot be found, return an error
+/// If a track number is already used on the release, return an error
+/// If the user does not have the required permissions, return an error
+#[cfg(feature = "ssr")]
+pub async fn move_tracks(
+    pool: &PgPool,
+    user: Option<&User>,
+    track_ids: Vec<i64>,
+    target_release_id: i64,
+) -> Result<TracksResult, ServerFnError> {
+    if track_ids.is_empty() {
+        return Err(ServerFnError::new("No tracks to move."));
+    }
+    match user_can_manage_release(pool, user, target_release_id).await {
+        Ok(_) => (),
+        Err(e) => return Err(e),
+    }
+    // The tracks must also come from releases the user manages
+    for track_id in &track_ids {
+        let track = fetch_or_not_found(
+            Track::get_by_id(pool, *track_id).await,
+            "Error while getting track",
+        )?;
+        match user_can_manage_release(pool, user, track.release_id).await {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+    }
+    ensure_writable(pool).await?;
+
+    let release = fetch_or_not_found(
+        Release::get_by_id(pool, target_release_id).await,
+        "Error while getting release",
+    )?;
+
+    let tracks = Track::move_to_release(pool, &track_ids, release.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while moving tracks: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    Ok(TracksResult { tracks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ssr")]
+    use crate::models::test_helpers::{
+        create_test_artist, create_test_record_label, create_test_release, create_test_track,
+        create_test_user, create_test_user_with_permissions,
+    };
+
+    #[sqlx::test]
+    async fn test_get_tracks_service_admin_user(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
             .await
             .unwrap();
         let release = create_test_release(&pool, 1, Some(artist.clone()))
@@ -462,18 +828,13 @@ mod tests {
         )
         .await
         .unwrap();
-
-        assert_eq!(tracks.tracks.len(), 1);
+        assert_eq!(tracks.tracks.len(), 2);
         assert_eq!(tracks.tracks[0].id, track.id);
+        assert_eq!(tracks.tracks[1].id, unpublished_track.id);
     }
 
     #[sqlx::test]
-    async fn test_get_track_service(pool: PgPool) {
-        let permissions = vec!["admin", "label_owner"];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
-            .await
-            .unwrap();
-
+    async fn test_get_tracks_service_no_user(pool: PgPool) {
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
         let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
             .await
@@ -484,26 +845,24 @@ mod tests {
         let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
             .await
             .unwrap();
+        let mut unpublished_track =
+            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        unpublished_track.published_at = None;
+        unpublished_track.clone().update(&pool).await.unwrap();
 
-        let track_result = get_track_service(
-            &pool,
-            Some(&user),
-            artist.slug.clone(),
-            release.slug.clone(),
-            track.slug.clone(),
-        )
-        .await
-        .unwrap();
+        let tracks = get_tracks_service(&pool, None, artist.slug.clone(), release.slug.clone())
+            .await
+            .unwrap();
 
-        assert_eq!(track_result.track.id, track.id);
+        assert_eq!(tracks.tracks.len(), 1);
+        assert_eq!(tracks.tracks[0].id, track.id);
     }
 
     #[sqlx::test]
-    async fn test_get_track_service_no_permission(pool: PgPool) {
-        let permissions = vec![];
-        let user = create_test_user_with_permissions(&pool, 1, permissions)
-            .await
-            .unwrap();
+    async fn test_get_tracks_service_anonymous_user(pool: PgPool) {
+        let (user, _) = create_test_user(&pool, 1).await.unwrap().into_user(None);
 
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
         let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
@@ -512,33 +871,33 @@ mod tests {
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
         let mut unpublished_track =
-            create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
                 .await
                 .unwrap();
         unpublished_track.published_at = None;
         unpublished_track.clone().update(&pool).await.unwrap();
 
-        let track_result = get_track_service(
+        let tracks = get_tracks_service(
             &pool,
             Some(&user),
             artist.slug.clone(),
             release.slug.clone(),
-            unpublished_track.slug.clone(),
         )
-        .await;
-        assert!(track_result.is_err());
-        assert_eq!(
-            track_result.unwrap_err().to_string(),
-            "error running server function: Could not find track test-track-1 for release with id 1 and artist with id 1 and record label with id 1."
-                .to_string()
-        );
+        .await
+        .unwrap();
+
+        assert_eq!(tracks.tracks.len(), 1);
+        assert_eq!(tracks.tracks[0].id, track.id);
     }
 
     #[sqlx::test]
-    async fn test_create_track_service(pool: PgPool) {
+    async fn test_get_track_service(pool: PgPool) {
         let permissions = vec!["admin", "label_owner"];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
             .await
             .unwrap();
 
@@ -549,45 +908,30 @@ mod tests {
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
-
-        let form = CreateTrackForm {
-            name: "Test Track".to_string(),
-            description: "Test Track Description".to_string(),
-            lyrics: "Test lyrics".to_string(),
-            primary_artist_id: artist.id,
-            release_id: release.id,
-            isrc_code: Some("UKXXX2020123".to_string()),
-            bpm: Some(120),
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-            track_number: 1,
-        };
-
-        let track_result = create_track_service(&pool, Some(&user), form.clone())
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
             .await
             .unwrap();
-        assert_eq!(track_result.track.name, "Test Track");
-        assert_eq!(track_result.track.description, "Test Track Description");
-        assert_eq!(track_result.track.primary_artist_id, artist.id);
-        assert_eq!(track_result.track.release_id, release.id);
-        assert_eq!(
-            track_result.track.isrc_code,
-            Some("UKXXX2020123".to_string())
-        );
-        assert_eq!(track_result.track.bpm, Some(120));
-        assert_eq!(track_result.track.track_number, 1);
-        assert!(track_result.track.published_at.is_some());
-        assert_eq!(track_result.artists.len(), 1);
-        assert_eq!(track_result.artists[0].id, artist.id);
+
+        let track_result = get_track_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            track.slug.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(track_result.track.id, track.id);
+        assert_eq!(track_result.primary_artist_name, artist.name);
+        assert!(!track_result.primary_artist_deleted);
     }
 
     #[sqlx::test]
-    async fn test_create_track_service_no_permision(pool: PgPool) {
-        let permissions = vec![];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_get_track_service_deleted_primary_artist(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
             .await
             .unwrap();
-
         let record_label = create_test_record_label(&pool, 1).await.unwrap();
         let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
             .await
@@ -595,32 +939,33 @@ mod tests {
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        artist.delete(&pool).await.unwrap();
 
-        let form = CreateTrackForm {
-            name: "Test Track".to_string(),
-            description: "Test Track Description".to_string(),
-            lyrics: "Test lyrics".to_string(),
-            primary_artist_id: artist.id,
-            release_id: release.id,
-            isrc_code: Some("UKXXX2020123".to_string()),
-            bpm: Some(120),
-            published_at: Some(chrono::Utc::now()),
-            artist_ids: artist.id.to_string(),
-            track_number: 1,
-        };
+        let track_result = get_track_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            track.slug.clone(),
+        )
+        .await
+        .unwrap();
 
-        let track_result = create_track_service(&pool, Some(&user), form).await;
-        assert!(track_result.is_err());
-        assert_eq!(
-            track_result.unwrap_err().to_string(),
-            "error running server function: You do not have permission.".to_string()
-        );
+        assert_eq!(track_result.track.id, track.id);
+        assert_eq!(track_result.primary_artist_name, artist.name);
+        assert!(track_result.primary_artist_deleted);
     }
 
     #[sqlx::test]
-    async fn test_update_track_service_no_permission(pool: PgPool) {
-        let permissions = vec![];
-        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+    async fn test_get_track_service_relations(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let public_user = create_test_user_with_permissions(&pool, 2, vec![])
             .await
             .unwrap();
 
@@ -631,8 +976,238 @@ mod tests {
         let release = create_test_release(&pool, 1, Some(artist.clone()))
             .await
             .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let set_result = set_track_relations_service(
+            &pool,
+            Some(&user),
+            track.slug.clone(),
+            vec![
+                TrackRelation::new("cover_of", "Original Song", Some("Original Artist"), None),
+                TrackRelation::new("samples", "Break", None, Some("USABC9900001")),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(set_result.relations.len(), 2);
 
-        let form = CreateTrackForm {
+        let track_result = get_track_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            track.slug.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(track_result.relations, set_result.relations);
+
+        // Relations are only shown to label owners
+        let track_result = get_track_service(
+            &pool,
+            Some(&public_user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            track.slug.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(track_result.relations.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_set_track_relations_service_unknown_type(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = set_track_relations_service(
+            &pool,
+            Some(&user),
+            track.slug.clone(),
+            vec![TrackRelation::new(
+                "inspired_by",
+                "Original Song",
+                None,
+                None,
+            )],
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: Relation type must be one of cover_of, samples, remix_of."
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_set_track_relations_service_no_permission(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = set_track_relations_service(
+            &pool,
+            Some(&user),
+            track.slug.clone(),
+            vec![TrackRelation::new("cover_of", "Original Song", None, None)],
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(track.get_relations(&pool).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_get_track_service_no_permission(pool: PgPool) {
+        let permissions = vec![];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut unpublished_track =
+            create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        unpublished_track.published_at = None;
+        unpublished_track.clone().update(&pool).await.unwrap();
+
+        let track_result = get_track_service(
+            &pool,
+            Some(&user),
+            artist.slug.clone(),
+            release.slug.clone(),
+            unpublished_track.slug.clone(),
+        )
+        .await;
+        assert!(track_result.is_err());
+        assert_eq!(
+            track_result.unwrap_err().to_string(),
+            "error running server function: Could not find track test-track-1 for release with id 1 and artist with id 1 and record label with id 1."
+                .to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_create_track_service(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateTrackForm {
+            name: "Test Track".to_string(),
+            description: "Test Track Description".to_string(),
+            lyrics: "Test lyrics".to_string(),
+            primary_artist_id: artist.id,
+            release_id: release.id,
+            isrc_code: Some("UKXXX2020123".to_string()),
+            bpm: Some(120),
+            preview_url: Some("https://example.com/preview.mp3".to_string()),
+            preview_start_ms: Some(15_000),
+            duration_ms: Some(225_000),
+            published_at: Some(chrono::Utc::now()),
+            artist_ids: artist.id.to_string(),
+            track_number: 1,
+        };
+
+        let track_result = create_track_service(&pool, Some(&user), form.clone())
+            .await
+            .unwrap();
+        assert_eq!(track_result.track.name, "Test Track");
+        assert_eq!(track_result.track.description, "Test Track Description");
+        assert_eq!(track_result.track.primary_artist_id, artist.id);
+        assert_eq!(track_result.track.release_id, release.id);
+        assert_eq!(
+            track_result.track.isrc_code,
+            Some("UKXXX2020123".to_string())
+        );
+        assert_eq!(track_result.track.bpm, Some(120));
+        assert_eq!(
+            track_result.track.preview_url,
+            Some("https://example.com/preview.mp3".to_string())
+        );
+        assert_eq!(track_result.track.preview_start_ms, Some(15_000));
+        assert_eq!(track_result.track.duration_ms, Some(225_000));
+        assert_eq!(track_result.duration_display, Some("3:45".to_string()));
+        assert_eq!(track_result.track.track_number, 1);
+        assert!(track_result.track.published_at.is_some());
+        assert_eq!(track_result.artists.len(), 1);
+        assert_eq!(track_result.artists[0].id, artist.id);
+    }
+
+    #[sqlx::test]
+    async fn test_create_track_service_no_permision(pool: PgPool) {
+        let permissions = vec![];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateTrackForm {
+            name: "Test Track".to_string(),
+            description: "Test Track Description".to_string(),
+            lyrics: "Test lyrics".to_string(),
+            primary_artist_id: artist.id,
+            release_id: release.id,
+            isrc_code: Some("UKXXX2020123".to_string()),
+            bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
+            published_at: Some(chrono::Utc::now()),
+            artist_ids: artist.id.to_string(),
+            track_number: 1,
+        };
+
+        let track_result = create_track_service(&pool, Some(&user), form).await;
+        assert!(track_result.is_err());
+        assert_eq!(
+            track_result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_update_track_service_no_permission(pool: PgPool) {
+        let permissions = vec![];
+        let user: User = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let form = CreateTrackForm {
             name: "Test Track".to_string(),
             description: "Test Track Description".to_string(),
             lyrics: "Test lyrics".to_string(),
@@ -640,6 +1215,9 @@ mod tests {
             release_id: release.id,
             isrc_code: Some("UKXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
             published_at: Some(chrono::Utc::now()),
             artist_ids: artist.id.to_string(),
             track_number: 1,
@@ -677,6 +1255,9 @@ mod tests {
             lyrics: "Test lyrics".to_string(),
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
             primary_artist_id: artist.id,
             release_id: release.id,
             published_at: Some(chrono::Utc::now()),
@@ -692,12 +1273,18 @@ mod tests {
         let update_form = UpdateTrackForm {
             name: "Updated Track".to_string(),
             slug: "test-track".to_string(),
+            lock_slug: None,
             description: "Updated Track Description".to_string(),
             lyrics: "Updated lyrics".to_string(),
             primary_artist_id: artist.id,
             release_id: release2.id,
             isrc_code: Some("UKXXX2025321".to_string()),
             bpm: Some(130),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: Some(225_000),
+            explicit: false,
+            clean_version_of: None,
             track_number: 2,
             published_at: Some(chrono::Utc::now()),
             artist_ids: artist.id.to_string(),
@@ -718,6 +1305,8 @@ mod tests {
             Some("UKXXX2025321".to_string())
         );
         assert_eq!(updated_track.track.bpm, Some(130));
+        assert_eq!(updated_track.track.duration_ms, Some(225_000));
+        assert_eq!(updated_track.duration_display, Some("3:45".to_string()));
         assert_eq!(updated_track.track.track_number, 2);
         assert!(updated_track.track.published_at.is_some());
         assert_eq!(updated_track.artists, vec![artist]);
@@ -746,6 +1335,9 @@ mod tests {
             release_id: release.id,
             isrc_code: Some("UKXXX2020123".to_string()),
             bpm: Some(120),
+            preview_url: None,
+            preview_start_ms: None,
+            duration_ms: None,
             track_number: 1,
             published_at: Some(chrono::Utc::now()),
             artist_ids: artist.id.to_string(),
@@ -815,6 +1407,76 @@ mod tests {
         assert!(get_result.is_ok());
     }
 
+    #[sqlx::test]
+    async fn test_archive_track_service(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let deleted_track =
+            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap()
+                .delete(&pool)
+                .await
+                .unwrap();
+
+        let archived_track = archive_track_service(&pool, Some(&user), track.slug.clone(), true)
+            .await
+            .unwrap();
+        assert!(archived_track.track.archived_at.is_some());
+        assert!(archived_track.track.deleted_at.is_none());
+
+        // Hidden from listings
+        let tracks = get_tracks_service(&pool, None, artist.slug.clone(), release.slug.clone())
+            .await
+            .unwrap();
+        assert!(tracks.tracks.is_empty());
+
+        // Still resolvable by slug
+        let (visitor, _) = create_test_user(&pool, 2).await.unwrap().into_user(None);
+        let result = get_track_service(
+            &pool,
+            Some(&visitor),
+            artist.slug.clone(),
+            release.slug.clone(),
+            track.slug.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.track.id, track.id);
+
+        // In the archive, but not the trash
+        let archived = get_archived_tracks_service(&pool, Some(&user))
+            .await
+            .unwrap();
+        assert_eq!(
+            archived.tracks.iter().map(|t| t.id).collect::<Vec<i64>>(),
+            vec![track.id]
+        );
+        assert!(!archived.tracks.iter().any(|t| t.id == deleted_track.id));
+    }
+
+    #[sqlx::test]
+    async fn test_archive_track_service_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = archive_track_service(&pool, Some(&user), track.slug, true).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
     #[sqlx::test]
     async fn test_restore_track_service(pool: PgPool) {
         let permissions = vec!["admin", "label_owner"];
@@ -824,9 +1486,10 @@ mod tests {
 
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
         let track = track.delete(&pool).await.unwrap();
-        let restored_track = restore_track_service(&pool, Some(&user), track.slug.clone())
-            .await
-            .unwrap();
+        let restored_track =
+            restore_track_service(&pool, Some(&user), track.slug.clone(), Republish::Keep)
+                .await
+                .unwrap();
 
         assert!(restored_track.track.deleted_at.is_none());
     }
@@ -838,7 +1501,8 @@ mod tests {
             .await
             .unwrap();
 
-        let restored_track = restore_track_service(&pool, Some(&user), "missing".to_string()).await;
+        let restored_track =
+            restore_track_service(&pool, Some(&user), "missing".to_string(), Republish::Keep).await;
         assert!(restored_track.is_err());
         assert_eq!(
             restored_track.unwrap_err().to_string(),
@@ -850,8 +1514,13 @@ mod tests {
     async fn test_restore_track_service_no_user(pool: PgPool) {
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
         let track = track.delete(&pool).await.unwrap();
-        let restored_track =
-            restore_track_service(&pool, Some(&User::default()), track.slug.clone()).await;
+        let restored_track = restore_track_service(
+            &pool,
+            Some(&User::default()),
+            track.slug.clone(),
+            Republish::Keep,
+        )
+        .await;
         assert!(restored_track.is_err());
         assert_eq!(
             restored_track.unwrap_err().to_string(),
@@ -866,11 +1535,456 @@ mod tests {
             .unwrap();
         let track = create_test_track(&pool, 1, None, None).await.unwrap();
         let track = track.delete(&pool).await.unwrap();
-        let restored_track = restore_track_service(&pool, Some(&user), track.slug.clone()).await;
+        let restored_track =
+            restore_track_service(&pool, Some(&user), track.slug.clone(), Republish::Keep).await;
         assert!(restored_track.is_err());
         assert_eq!(
             restored_track.unwrap_err().to_string(),
             "error running server function: You do not have permission.".to_string()
         );
     }
+
+    #[sqlx::test]
+    async fn test_list_all_for_artist_admin_pagination(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let record_label = create_test_record_label(&pool, 1).await.unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label))
+            .await
+            .unwrap();
+        let first_release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let second_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        // Created out of order, listed by release then track number
+        let track3 = create_test_track(&pool, 3, Some(second_release.clone()), None)
+            .await
+            .unwrap();
+        let track2 = create_test_track(&pool, 2, Some(first_release.clone()), None)
+            .await
+            .unwrap();
+        let track1 = create_test_track(&pool, 1, Some(first_release.clone()), None)
+            .await
+            .unwrap();
+
+        let first_page =
+            list_all_for_artist_admin(&pool, Some(&user), artist.id, Pagination::new(1, 2), false)
+                .await
+                .unwrap();
+        assert_eq!(first_page.total, 3);
+        assert_eq!(
+            first_page
+                .tracks
+                .iter()
+                .map(|row| (row.track.id, row.release.id))
+                .collect::<Vec<_>>(),
+            vec![(track1.id, first_release.id), (track2.id, first_release.id)]
+        );
+        assert_eq!(first_page.tracks[0].track.isrc_code, track1.isrc_code);
+
+        let second_page =
+            list_all_for_artist_admin(&pool, Some(&user), artist.id, Pagination::new(2, 2), false)
+                .await
+                .unwrap();
+        assert_eq!(second_page.total, 3);
+        assert_eq!(second_page.tracks.len(), 1);
+        assert_eq!(second_page.tracks[0].track.id, track3.id);
+        assert_eq!(second_page.tracks[0].release.id, second_release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_list_all_for_artist_admin_drafts_and_deleted(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let mut draft_track = create_test_track(
+            &pool,
+            2,
+            Some(Release::get_by_id(&pool, track.release_id).await.unwrap()),
+            None,
+        )
+        .await
+        .unwrap();
+        draft_track.published_at = None;
+        let draft_track = draft_track.update(&pool).await.unwrap();
+        let deleted_track = create_test_track(
+            &pool,
+            3,
+            Some(Release::get_by_id(&pool, track.release_id).await.unwrap()),
+            None,
+        )
+        .await
+        .unwrap();
+        deleted_track.delete(&pool).await.unwrap();
+
+        let without_deleted = list_all_for_artist_admin(
+            &pool,
+            Some(&user),
+            track.primary_artist_id,
+            Pagination::default(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            without_deleted
+                .tracks
+                .iter()
+                .map(|row| row.track.id)
+                .collect::<Vec<_>>(),
+            vec![track.id, draft_track.id]
+        );
+        assert_eq!(without_deleted.total, 2);
+
+        let with_deleted = list_all_for_artist_admin(
+            &pool,
+            Some(&user),
+            track.primary_artist_id,
+            Pagination::default(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            with_deleted
+                .tracks
+                .iter()
+                .map(|row| row.track.id)
+                .collect::<Vec<_>>(),
+            vec![track.id, draft_track.id, deleted_track.id]
+        );
+        assert_eq!(with_deleted.total, 3);
+    }
+
+    #[sqlx::test]
+    async fn test_list_all_for_artist_admin_scoped_to_artist(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+        let other_track = create_test_track(&pool, 2, None, None).await.unwrap();
+        assert_ne!(track.primary_artist_id, other_track.primary_artist_id);
+
+        let result = list_all_for_artist_admin(
+            &pool,
+            Some(&user),
+            track.primary_artist_id,
+            Pagination::default(),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.total, 1);
+        assert_eq!(result.tracks.len(), 1);
+        assert_eq!(result.tracks[0].track.id, track.id);
+    }
+
+    #[sqlx::test]
+    async fn test_list_all_for_artist_admin_no_permissions(pool: PgPool) {
+        let user = create_test_user(&pool, 1).await.unwrap();
+        let (user, _) = user.into_user(None);
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = list_all_for_artist_admin(
+            &pool,
+            Some(&user),
+            track.primary_artist_id,
+            Pagination::default(),
+            false,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs_for_release(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+
+        let mut record_label = create_test_record_label(&pool, 1).await.unwrap();
+        record_label.isrc_base = "UK-AAA".to_string();
+        let record_label = record_label
+            .update(&pool, &RecordLabelCache::default())
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, Some(record_label.clone()))
+            .await
+            .unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let other_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+
+        // An existing code for the same base and year on another release
+        let mut existing_track =
+            create_test_track(&pool, 1, Some(other_release), Some(artist.clone()))
+                .await
+                .unwrap();
+        existing_track.isrc_code = Some("UKAAA2500007".to_string());
+        existing_track.update(&pool).await.unwrap();
+
+        let track_with_isrc =
+            create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        let mut track_two =
+            create_test_track(&pool, 3, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        track_two.isrc_code = None;
+        let track_two = track_two.update(&pool).await.unwrap();
+        let mut track_three =
+            create_test_track(&pool, 4, Some(release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        track_three.isrc_code = None;
+        let track_three = track_three.update(&pool).await.unwrap();
+
+        let result = assign_isrcs_for_release(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.id,
+            2025,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.tracks.len(), 2);
+        assert_eq!(result.tracks[0].id, track_two.id);
+        assert_eq!(result.tracks[0].isrc_code, Some("UKAAA2500008".to_string()));
+        assert_eq!(result.tracks[1].id, track_three.id);
+        assert_eq!(result.tracks[1].isrc_code, Some("UKAAA2500009".to_string()));
+
+        // Tracks that already had an ISRC code are untouched
+        let unchanged_track = Track::get_by_slug(&pool, track_with_isrc.slug.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(unchanged_track.isrc_code, track_with_isrc.isrc_code);
+
+        // Running it again has nothing left to assign
+        let result = assign_isrcs_for_release(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            release.id,
+            2025,
+        )
+        .await
+        .unwrap();
+        assert!(result.tracks.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs_for_release_invalid_isrc_base(pool: PgPool) {
+        let permissions = vec!["admin", "label_owner"];
+        let user = create_test_user_with_permissions(&pool, 1, permissions)
+            .await
+            .unwrap();
+        let mut track = create_test_track(&pool, 1, None, None).await.unwrap();
+        track.isrc_code = None;
+        let track = track.update(&pool).await.unwrap();
+
+        let result = assign_isrcs_for_release(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            track.release_id,
+            2025,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: ISRC base must be 5 alphanumeric characters."
+                .to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_assign_isrcs_for_release_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = assign_isrcs_for_release(
+            &pool,
+            &RecordLabelCache::default(),
+            Some(&user),
+            track.release_id,
+            2025,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_move_tracks(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let target_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track1 = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let track2 = create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        create_test_track(&pool, 3, Some(target_release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+
+        let result = move_tracks(
+            &pool,
+            Some(&user),
+            vec![track2.id, track1.id],
+            target_release.id,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result
+                .tracks
+                .iter()
+                .map(|track| (track.id, track.release_id, track.isrc_code.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (track1.id, target_release.id, track1.isrc_code),
+                (track2.id, target_release.id, track2.isrc_code),
+            ]
+        );
+        assert!(!release.has_tracks(&pool).await.unwrap());
+        assert_eq!(target_release.get_tracks(&pool).await.unwrap().len(), 3);
+    }
+
+    #[sqlx::test]
+    async fn test_move_tracks_track_number_conflict(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec!["admin", "label_owner"])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let target_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        let track1 = create_test_track(&pool, 1, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let track2 = create_test_track(&pool, 2, Some(release.clone()), Some(artist.clone()))
+            .await
+            .unwrap();
+        let mut target_track =
+            create_test_track(&pool, 3, Some(target_release.clone()), Some(artist.clone()))
+                .await
+                .unwrap();
+        target_track.track_number = 2;
+        target_track.update(&pool).await.unwrap();
+
+        let result = move_tracks(
+            &pool,
+            Some(&user),
+            vec![track1.id, track2.id],
+            target_release.id,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "error running server function: Track number 2 must be unique for release with id {}.",
+                target_release.id
+            )
+        );
+        let track1 = Track::get_by_id(&pool, track1.id).await.unwrap();
+        let track2 = Track::get_by_id(&pool, track2.id).await.unwrap();
+        assert_eq!(track1.release_id, release.id);
+        assert_eq!(track2.release_id, release.id);
+    }
+
+    #[sqlx::test]
+    async fn test_move_tracks_no_permissions(pool: PgPool) {
+        let user = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, None, None).await.unwrap();
+
+        let result = move_tracks(&pool, Some(&user), vec![track.id], track.release_id).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission.".to_string()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_move_tracks_artist_manager(pool: PgPool) {
+        let manager = create_test_user_with_permissions(&pool, 1, vec![])
+            .await
+            .unwrap();
+        let artist = create_test_artist(&pool, 1, None).await.unwrap();
+        let other_artist = create_test_artist(&pool, 2, None).await.unwrap();
+        manager.add_managed_artist(&pool, artist.id).await.unwrap();
+        let release = create_test_release(&pool, 1, Some(artist.clone()))
+            .await
+            .unwrap();
+        let target_release = create_test_release(&pool, 2, Some(artist.clone()))
+            .await
+            .unwrap();
+        let other_release = create_test_release(&pool, 3, Some(other_artist.clone()))
+            .await
+            .unwrap();
+        let track = create_test_track(&pool, 1, Some(release), Some(artist.clone()))
+            .await
+            .unwrap();
+        let other_track =
+            create_test_track(&pool, 2, Some(other_release.clone()), Some(other_artist))
+                .await
+                .unwrap();
+
+        // The manager can move tracks between their artist's releases
+        let result = move_tracks(&pool, Some(&manager), vec![track.id], target_release.id)
+            .await
+            .unwrap();
+        assert_eq!(result.tracks[0].release_id, target_release.id);
+
+        // But not to or from another artist's release
+        let result = move_tracks(&pool, Some(&manager), vec![track.id], other_release.id).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+        let result = move_tracks(
+            &pool,
+            Some(&manager),
+            vec![other_track.id],
+            target_release.id,
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "error running server function: You do not have permission."
+        );
+    }
 }
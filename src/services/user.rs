@@ -5,17 +5,31 @@ use bcrypt::{DEFAULT_COST, hash, verify};
 use leptos::prelude::ServerFnError;
 use sqlx::PgPool;
 
+#[cfg(feature = "ssr")]
+use super::mailer::{Email, Mailer};
+use super::read_only::ensure_writable;
+#[cfg(feature = "ssr")]
+use crate::config::{
+    auth::{EMAIL_VERIFICATION_EXPIRY_HOURS, PASSWORD_RESET_EXPIRY_HOURS},
+    site::base_url,
+};
 use crate::forms::user::{ChangePasswordForm, RegisterUserForm, UpdateUserForm};
 use crate::models::auth::{User, UserPasshash};
 #[cfg(feature = "ssr")]
-use crate::models::traits::Validate;
+use crate::models::{
+    email_verification::EmailVerificationToken, password_reset::PasswordResetToken,
+    traits::Validate,
+};
 
 /// Register User
 ///
 /// This will register a user with the given username, email and matching passwords
 ///
+/// A link to verify the email address is sent to the user. If it can't be sent, the user is still registered.
+///
 /// # Arguments
 /// pool: `PgPool` - The database connection pool
+/// mailer: &dyn `Mailer` - Where to send the verification email
 /// form: `RegisterUserForm` - The form to register the user
 ///
 /// # Returns
@@ -34,8 +48,11 @@ use crate::models::traits::Validate;
 #[cfg(feature = "ssr")]
 pub async fn register_user_service(
     pool: &PgPool,
+    mailer: &dyn Mailer,
     form: RegisterUserForm,
 ) -> Result<User, ServerFnError> {
+    ensure_writable(pool).await?;
+
     if form.username.clone().is_empty() || form.password.is_empty() {
         return Err(ServerFnError::ServerError(
             "Username and password are required.".to_string(),
@@ -63,7 +80,7 @@ pub async fn register_user_service(
         Err(e) => {
             tracing::error!("{:?}", e);
             return Err(ServerFnError::ServerError(
-                "Error hassing password.".to_string(),
+                "Error hashing password.".to_string(),
             ));
         }
     };
@@ -75,7 +92,124 @@ pub async fn register_user_service(
         .execute(pool)
         .await?;
 
-    User::get_from_username(user.username, pool)
+    let user = User::get_from_username(user.username, pool)
+        .await
+        .ok_or_else(|| ServerFnError::new("User does not exist."))?;
+
+    let (_, token) = EmailVerificationToken::create(pool, user.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while creating verification token: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let email = Email {
+        to: user.email.clone(),
+        subject: "Verify your email address".to_string(),
+        body: format!(
+            "Hello {},\n\nVerify your email address at {}/verify-email/{token}\n\nThe link expires in {EMAIL_VERIFICATION_EXPIRY_HOURS} hours.",
+            user.username,
+            base_url()
+        ),
+    };
+    if let Err(e) = mailer.send(&email).await {
+        tracing::error!(
+            "Could not send the verification email to user {}: {e}",
+            user.id
+        );
+    }
+
+    Ok(user)
+}
+
+/// Login User
+///
+/// Check the username and password match a user.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// username: String - The username of the user
+/// password: String - The password of the user
+/// `require_email_verification`: bool - Should users with an unverified email be refused
+///
+/// # Returns
+/// Result<`User`, `ServerFnError`> - The user that has logged in
+///
+/// # Errors
+/// If the username or password is empty, return an error
+/// If the user does not exist, return an error
+/// If the password does not match, return an error
+/// If verification is required and the email is not verified, return an error
+/// If the login cannot be recorded, return an error
+#[cfg(feature = "ssr")]
+pub async fn login_user_service(
+    pool: &PgPool,
+    username: String,
+    password: String,
+    require_email_verification: bool,
+) -> Result<User, ServerFnError> {
+    if username.is_empty() || password.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "Username and password are required.".to_string(),
+        ));
+    }
+
+    let (user, UserPasshash(expected_passhash)) =
+        User::get_from_username_with_passhash(username, pool)
+            .await
+            .ok_or_else(|| ServerFnError::new("User does not exist."))?;
+
+    if !verify(password, &expected_passhash)? {
+        return Err(ServerFnError::ServerError(
+            "Password does not match.".to_string(),
+        ));
+    }
+
+    if require_email_verification && !user.is_verified() {
+        return Err(ServerFnError::ServerError(
+            "Please verify your email address before logging in.".to_string(),
+        ));
+    }
+
+    user.record_login(pool).await.map_err(|e| {
+        let err = format!("Error while recording login: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })
+}
+
+/// Verify Email
+///
+/// Mark the email address of the user the token was sent to as verified.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// token: String - The verification token sent to the user
+///
+/// # Returns
+/// Result<`User`, `ServerFnError`> - The verified user
+///
+/// # Errors
+/// If the token does not exist, return an error
+/// If the token has expired, return an error
+/// If the user cannot be updated, return an error
+#[cfg(feature = "ssr")]
+pub async fn verify_email(pool: &PgPool, token: String) -> Result<User, ServerFnError> {
+    let token = EmailVerificationToken::get_by_token(pool, token)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting verification token: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    token.verify(pool).await.map_err(|e| {
+        let err = format!("Error while verifying email: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    User::get(token.user_id, pool)
         .await
         .ok_or_else(|| ServerFnError::new("User does not exist."))
 }
@@ -102,6 +236,8 @@ pub async fn update_user_service(
     user: Option<&User>,
     user_form: UpdateUserForm,
 ) -> Result<User, ServerFnError> {
+    ensure_writable(pool).await?;
+
     if match user {
         Some(user) => user.username != user_form.original_username,
         None => false,
@@ -150,6 +286,8 @@ pub async fn change_password_service(
     user: Option<&User>,
     form: ChangePasswordForm,
 ) -> Result<User, ServerFnError> {
+    ensure_writable(pool).await?;
+
     let user = match user {
         Some(user) => {
             if !user.is_active() {
@@ -181,7 +319,7 @@ pub async fn change_password_service(
             Err(e) => {
                 tracing::error!("{:?}", e);
                 return Err(ServerFnError::ServerError(
-                    "Error hassing password.".to_string(),
+                    "Error hashing password.".to_string(),
                 ));
             }
         };
@@ -200,15 +338,150 @@ pub async fn change_password_service(
     }
 }
 
+/// Request Password Reset
+///
+/// Send a password reset link to the user with the given email address.
+/// Unknown email addresses are ignored, so the response doesn't reveal who has an account.
+///
+/// The email is also not reported as failed if it can't be sent, for the same reason.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// mailer: &dyn `Mailer` - Where to send the email
+/// email: String - The email address of the user
+///
+/// # Errors
+/// If the site is read only, return an error
+/// If the token cannot be created, return an error
+#[cfg(feature = "ssr")]
+pub async fn request_password_reset(
+    pool: &PgPool,
+    mailer: &dyn Mailer,
+    email: String,
+) -> Result<(), ServerFnError> {
+    ensure_writable(pool).await?;
+
+    let Some(user) = User::get_from_email(email.trim().to_string(), pool).await else {
+        return Ok(());
+    };
+
+    let (_, token) = PasswordResetToken::create(pool, user.id)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while creating password reset token: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+    let email = Email {
+        to: user.email,
+        subject: "Reset your password".to_string(),
+        body: format!(
+            "Hello {},\n\nChoose a new password at {}/reset-password/{token}\n\nThe link can be used once, and expires in {PASSWORD_RESET_EXPIRY_HOURS} hour(s). If you didn't ask to reset your password, you can ignore this email.",
+            user.username,
+            base_url()
+        ),
+    };
+    if let Err(e) = mailer.send(&email).await {
+        tracing::error!(
+            "Could not send the password reset email to user {}: {e}",
+            user.id
+        );
+    }
+
+    Ok(())
+}
+
+/// Reset Password
+///
+/// Set a new password for the user the token was sent to. Each token can only be used once.
+///
+/// # Arguments
+/// pool: `PgPool` - The database connection pool
+/// token: String - The password reset token sent to the user
+/// `new_password`: String - The new password
+///
+/// # Returns
+/// Result<`User`, `ServerFnError`> - The user whose password was reset
+///
+/// # Errors
+/// If the site is read only, return an error
+/// If the password is empty, return an error
+/// If the token cannot be found, return an error
+/// If the token has expired or already been used, return an error
+/// If the password cannot be hashed, return an error
+/// If the password cannot be updated, return an error
+/// If the user does not exist, return an error
+#[cfg(feature = "ssr")]
+pub async fn reset_password(
+    pool: &PgPool,
+    token: String,
+    new_password: String,
+) -> Result<User, ServerFnError> {
+    ensure_writable(pool).await?;
+
+    if new_password.is_empty() {
+        return Err(ServerFnError::ServerError(
+            "Password is required.".to_string(),
+        ));
+    }
+
+    let token = PasswordResetToken::get_by_token(pool, &token)
+        .await
+        .map_err(|e| {
+            let err = format!("Error while getting password reset token: {e:?}");
+            tracing::error!("{err}");
+            ServerFnError::new(e)
+        })?;
+
+    let password_hashed = match hash(new_password, DEFAULT_COST) {
+        Ok(hash) => hash,
+        Err(e) => {
+            tracing::error!("{:?}", e);
+            return Err(ServerFnError::ServerError(
+                "Error hashing password.".to_string(),
+            ));
+        }
+    };
+
+    token.consume(pool, &password_hashed).await.map_err(|e| {
+        let err = format!("Error while resetting password: {e:?}");
+        tracing::error!("{err}");
+        ServerFnError::new(e)
+    })?;
+
+    User::get(token.user_id, pool)
+        .await
+        .ok_or_else(|| ServerFnError::new("User does not exist."))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[cfg(feature = "ssr")]
     mod ssr_tests {
         use crate::models::{auth::UserPasshash, test_helpers::create_test_user};
+        use crate::services::mailer::{DisabledMailer, MemoryMailer};
 
         use super::*;
 
+        /// Get the token from the link in an email
+        fn link_token(email: &Email, path: &str) -> String {
+            email
+                .body
+                .split(path)
+                .nth(1)
+                .and_then(|rest| rest.split_whitespace().next())
+                .unwrap()
+                .to_string()
+        }
+
+        /// Request a password reset, and get the token from the email that was sent
+        async fn requested_reset_token(pool: &PgPool, email: String) -> String {
+            let mailer = MemoryMailer::default();
+            request_password_reset(pool, &mailer, email).await.unwrap();
+            link_token(&mailer.sent()[0], "/reset-password/")
+        }
+
         #[sqlx::test]
         async fn test_register_user_service(pool: PgPool) {
             let form = RegisterUserForm {
@@ -217,9 +490,152 @@ mod tests {
                 password: "password".to_string(),
                 password_confirmation: "password".to_string(),
             };
-            let user = register_user_service(&pool, form.clone()).await.unwrap();
+            let user = register_user_service(&pool, &DisabledMailer, form.clone())
+                .await
+                .unwrap();
+            // The user is registered even though the email couldn't be sent
             assert_eq!(user.username, form.username);
             assert_eq!(user.email, form.email);
+            assert!(!user.is_verified());
+        }
+
+        #[sqlx::test]
+        async fn test_verify_email(pool: PgPool) {
+            let form = RegisterUserForm {
+                username: "username".to_string(),
+                email: "test@example.com".to_string(),
+                password: "password".to_string(),
+                password_confirmation: "password".to_string(),
+            };
+            let mailer = MemoryMailer::default();
+            register_user_service(&pool, &mailer, form.clone())
+                .await
+                .unwrap();
+            let sent = mailer.sent();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, form.email);
+            let token = link_token(&sent[0], "/verify-email/");
+
+            let user = verify_email(&pool, token).await.unwrap();
+            assert!(user.is_verified());
+        }
+
+        #[sqlx::test]
+        async fn test_verify_email_twice(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let (_, token) = EmailVerificationToken::create(&pool, user.id)
+                .await
+                .unwrap();
+            let verified_user = verify_email(&pool, token.clone()).await.unwrap();
+
+            // The token is removed once it has been used
+            let result = verify_email(&pool, token).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Invalid verification token."
+            );
+
+            // Verifying with a new token keeps the original verification time
+            let (_, token) = EmailVerificationToken::create(&pool, user.id)
+                .await
+                .unwrap();
+            let user = verify_email(&pool, token).await.unwrap();
+            assert_eq!(user.email_verified_at, verified_user.email_verified_at);
+        }
+
+        #[sqlx::test]
+        async fn test_verify_email_invalid_token(pool: PgPool) {
+            let result = verify_email(&pool, "invalid".to_string()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Invalid verification token."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_verify_email_expired_token(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let (_, token) = EmailVerificationToken::create(&pool, user.id)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE email_verification_tokens SET expires_at = $1 WHERE user_id = $2")
+                .bind(chrono::Utc::now() - chrono::Duration::hours(1))
+                .bind(user.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let result = verify_email(&pool, token).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Verification token has expired."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_login_user_service(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let logged_in_user =
+                login_user_service(&pool, user.username.clone(), "password".to_string(), false)
+                    .await
+                    .unwrap();
+            assert_eq!(logged_in_user.id, user.id);
+        }
+
+        #[sqlx::test]
+        async fn test_login_user_service_records_login(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            assert_eq!(user.last_login_at, None);
+
+            let before = chrono::Utc::now() - chrono::Duration::seconds(1);
+            let logged_in_user =
+                login_user_service(&pool, user.username.clone(), "password".to_string(), false)
+                    .await
+                    .unwrap();
+            let last_login_at = logged_in_user.last_login_at.unwrap();
+            assert!(last_login_at >= before);
+
+            let stored_user = User::get(user.id, &pool).await.unwrap();
+            assert_eq!(stored_user.last_login_at, Some(last_login_at));
+
+            // A failed login is not recorded
+            login_user_service(&pool, user.username, "wrong".to_string(), false)
+                .await
+                .unwrap_err();
+            let stored_user = User::get(user.id, &pool).await.unwrap();
+            assert_eq!(stored_user.last_login_at, Some(last_login_at));
+        }
+
+        #[sqlx::test]
+        async fn test_login_user_service_wrong_password(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let result = login_user_service(&pool, user.username, "wrong".to_string(), false).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Password does not match."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_login_user_service_unverified_email(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let result =
+                login_user_service(&pool, user.username.clone(), "password".to_string(), true)
+                    .await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Please verify your email address before logging in."
+            );
+
+            let (_, token) = EmailVerificationToken::create(&pool, user.id)
+                .await
+                .unwrap();
+            verify_email(&pool, token).await.unwrap();
+            let logged_in_user =
+                login_user_service(&pool, user.username, "password".to_string(), true)
+                    .await
+                    .unwrap();
+            assert!(logged_in_user.is_verified());
         }
 
         #[sqlx::test]
@@ -230,7 +646,7 @@ mod tests {
                 password: "password".to_string(),
                 password_confirmation: "password".to_string(),
             };
-            let result = register_user_service(&pool, form.clone()).await;
+            let result = register_user_service(&pool, &MemoryMailer::default(), form.clone()).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err().to_string(),
@@ -246,7 +662,7 @@ mod tests {
                 password: String::new(),
                 password_confirmation: String::new(),
             };
-            let result = register_user_service(&pool, form.clone()).await;
+            let result = register_user_service(&pool, &MemoryMailer::default(), form.clone()).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err().to_string(),
@@ -262,7 +678,7 @@ mod tests {
                 password: "password".to_string(),
                 password_confirmation: "password2".to_string(),
             };
-            let result = register_user_service(&pool, form.clone()).await;
+            let result = register_user_service(&pool, &MemoryMailer::default(), form.clone()).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err().to_string(),
@@ -278,7 +694,7 @@ mod tests {
                 password: "password".to_string(),
                 password_confirmation: "password".to_string(),
             };
-            let result = register_user_service(&pool, form.clone()).await;
+            let result = register_user_service(&pool, &MemoryMailer::default(), form.clone()).await;
             assert!(result.is_err());
             assert_eq!(
                 result.unwrap_err().to_string(),
@@ -495,5 +911,126 @@ mod tests {
                 "error running server function: Password does not match."
             );
         }
+
+        #[sqlx::test]
+        async fn test_request_password_reset_unknown_email(pool: PgPool) {
+            let mailer = MemoryMailer::default();
+            request_password_reset(&pool, &mailer, "unknown@example.com".to_string())
+                .await
+                .unwrap();
+            assert!(mailer.sent().is_empty());
+        }
+
+        #[sqlx::test]
+        async fn test_request_password_reset_sends_email(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let mailer = MemoryMailer::default();
+
+            request_password_reset(&pool, &mailer, user.email.clone())
+                .await
+                .unwrap();
+
+            let sent = mailer.sent();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, user.email);
+            assert_eq!(sent[0].subject, "Reset your password");
+            let token = link_token(&sent[0], "/reset-password/");
+            assert!(
+                PasswordResetToken::get_by_token(&pool, &token)
+                    .await
+                    .is_ok()
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_request_password_reset_mailer_fails(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let mailer = MemoryMailer::refusing(vec![user.email.clone()]);
+
+            // The response doesn't reveal that the email couldn't be sent
+            request_password_reset(&pool, &mailer, user.email)
+                .await
+                .unwrap();
+            assert!(mailer.sent().is_empty());
+        }
+
+        #[sqlx::test]
+        async fn test_reset_password(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let token = requested_reset_token(&pool, user.email.clone()).await;
+
+            let reset_user = reset_password(&pool, token, "new_password".to_string())
+                .await
+                .unwrap();
+            assert_eq!(reset_user.id, user.id);
+
+            let logged_in_user = login_user_service(
+                &pool,
+                user.username.clone(),
+                "new_password".to_string(),
+                false,
+            )
+            .await
+            .unwrap();
+            assert_eq!(logged_in_user.id, user.id);
+            let result =
+                login_user_service(&pool, user.username, "password".to_string(), false).await;
+            assert!(result.is_err());
+        }
+
+        #[sqlx::test]
+        async fn test_reset_password_invalid_token(pool: PgPool) {
+            let result =
+                reset_password(&pool, "invalid".to_string(), "new_password".to_string()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Invalid password reset token."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_reset_password_empty_password(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let token = requested_reset_token(&pool, user.email).await;
+
+            let result = reset_password(&pool, token, String::new()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Password is required."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_reset_password_expired_token(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let token = requested_reset_token(&pool, user.email).await;
+            sqlx::query("UPDATE password_reset_tokens SET expires_at = $1 WHERE user_id = $2")
+                .bind(chrono::Utc::now() - chrono::Duration::minutes(1))
+                .bind(user.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let result = reset_password(&pool, token, "new_password".to_string()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Password reset token has expired."
+            );
+        }
+
+        #[sqlx::test]
+        async fn test_reset_password_reused_token(pool: PgPool) {
+            let user = create_test_user(&pool, 1).await.unwrap();
+            let token = requested_reset_token(&pool, user.email).await;
+            reset_password(&pool, token.clone(), "new_password".to_string())
+                .await
+                .unwrap();
+
+            let result = reset_password(&pool, token, "another_password".to_string()).await;
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "error running server function: Password reset token has already been used."
+            );
+        }
     }
 }
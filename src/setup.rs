@@ -12,11 +12,28 @@ use dotenvy::dotenv;
 use leptos::config::get_configuration;
 use leptos_axum::{LeptosRoutes, generate_route_list};
 use sqlx::PgPool;
-use tower_http::services::ServeDir;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+use tower_http::{limit::RequestBodyLimitLayer, services::ServeDir};
 
 use crate::app::{WhiteLabel, shell};
-use crate::database::create_pool;
-use crate::models::auth::User;
+use crate::config::analytics::parse_analytics_snippet;
+use crate::config::database::request_timeout;
+use crate::config::session::{
+    cookie_domain, cookie_same_site, cookie_secure, remember_me_lifetime, session_lifetime,
+    with_cookie_attributes, with_session_lifetimes,
+};
+use crate::config::site::force_https;
+use crate::config::upload::max_request_body_size;
+use crate::database::{create_pool, create_request_pool};
+use crate::middleware::{negotiate_webp_images, redirect_to_https, timeout_server_functions};
+use crate::models::auth::{PERMISSION_TOKENS, User};
+use crate::routes::api;
+use crate::routes::record_label::{
+    RELEASE_CALENDAR_PATH, RELEASE_FEED_PATH, SITEMAP_PATH, release_calendar_feed,
+    release_rss_feed, sitemap_feed,
+};
+use crate::services::digest::schedule_release_digests;
+use crate::services::publishing::schedule_first_published_stamps;
 use crate::state::AppState;
 
 /// Initialise the application.
@@ -24,19 +41,40 @@ use crate::state::AppState;
 /// # Panics
 ///
 /// This function will panic if it can't initialise the logger.
-#[allow(clippy::cognitive_complexity)]
+#[allow(clippy::cognitive_complexity, clippy::too_many_lines)]
 pub async fn init_app() {
     // Initialise the logger
     tracing_subscriber::fmt::init();
 
     // Load environment variables form env file.
     let _ = dotenv().context(".env file not found");
+    if let Ok(snippet) = std::env::var("ANALYTICS_SNIPPET")
+        && let Err(e) = parse_analytics_snippet(&snippet)
+    {
+        tracing::error!("Invalid ANALYTICS_SNIPPET: {:?}", e);
+        return;
+    }
 
     // Set up the database
-    let pool = create_pool().await;
+    // Migrations and background jobs run without the request statement timeout
+    let background_pool = create_pool().await;
+    if let Err(e) = ensure_permissions(&background_pool).await {
+        tracing::error!("Couldn't seed permissions: {:?}", e);
+        return;
+    }
+    let pool = create_request_pool().await;
 
     // Auth section
-    let session_config = SessionConfig::default().with_table_name("axum_sessions");
+    let session_config = with_cookie_attributes(
+        with_session_lifetimes(
+            SessionConfig::default().with_table_name("axum_sessions"),
+            session_lifetime(),
+            remember_me_lifetime(),
+        ),
+        cookie_secure(),
+        cookie_same_site(),
+        cookie_domain(),
+    );
     let auth_config = AuthConfig::<i64>::default();
     let session_store = match SessionStore::<SessionPgPool>::new(
         Some(SessionPgPool::from(pool.clone())),
@@ -67,27 +105,48 @@ pub async fn init_app() {
         leptos_options,
         pool: pool.clone(),
         routes: routes.clone(),
+        record_label_cache: Arc::default(),
     };
 
+    // Send the new release digest in the background
+    tokio::spawn(schedule_release_digests(background_pool.clone()));
+    // Record when scheduled releases and tracks go live
+    tokio::spawn(schedule_first_published_stamps(background_pool));
+
     let Ok(upload_path) = std::env::var("UPLOAD_PATH") else {
         tracing::error!("UPLOAD_PATH not set.");
         return;
     };
 
     // build our application with a route
-    let app = Router::new()
+    let mut app = Router::new()
         .leptos_routes(&app_state, routes, {
             let options = app_state.leptos_options.clone();
             move || shell(options.clone())
         })
-        .nest_service("/uploads", ServeDir::new(upload_path))
+        .merge(api::router())
+        .route(
+            RELEASE_CALENDAR_PATH,
+            axum::routing::get(release_calendar_feed),
+        )
+        .route(RELEASE_FEED_PATH, axum::routing::get(release_rss_feed))
+        .route(SITEMAP_PATH, axum::routing::get(sitemap_feed))
+        .nest_service("/uploads", uploads_service(&upload_path))
         .fallback(leptos_axum::file_and_error_handler::<AppState, _>(shell))
+        .layer(RequestBodyLimitLayer::new(max_request_body_size()))
+        .layer(axum::middleware::from_fn_with_state(
+            request_timeout(),
+            timeout_server_functions,
+        ))
         .layer(
             AuthSessionLayer::<User, i64, SessionPgPool, PgPool>::new(Some(pool.clone()))
                 .with_config(auth_config),
         )
         .layer(SessionLayer::new(session_store))
         .with_state(app_state);
+    if force_https() {
+        app = app.layer(axum::middleware::from_fn(redirect_to_https));
+    }
 
     // run our app with hyper
     // `axum::Server` is a re-export of `hyper::Server`
@@ -101,9 +160,91 @@ pub async fn init_app() {
             return;
         }
     };
-    let serve = axum::serve(listener, app.into_make_service()).await;
+    // The connection's address is used for rate limiting when there is no trusted proxy
+    let serve = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await;
     match serve {
         Ok(()) => tracing::info!("Server stopped."),
         Err(e) => tracing::error!("Server Error: {:?}", e),
     }
 }
+
+/// Seed the permissions reference table with the canonical permission tokens.
+///
+/// This is safe to run on every start, existing tokens are left alone.
+/// Any granted permission that isn't a known token is logged, as it would silently do nothing.
+///
+/// # Arguments
+/// * `pool` - The database connection pool
+///
+/// # Returns
+/// The unknown tokens that have been granted to users
+///
+/// # Errors
+/// If the permissions cannot be seeded or checked, return an error
+pub async fn ensure_permissions(pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    sqlx::query(
+        "INSERT INTO permissions (token) SELECT * FROM UNNEST($1::TEXT[]) ON CONFLICT DO NOTHING",
+    )
+    .bind(PERMISSION_TOKENS)
+    .execute(pool)
+    .await?;
+
+    let unknown_tokens = sqlx::query_scalar::<_, String>(
+        "SELECT DISTINCT token FROM user_permissions
+         WHERE token NOT IN (SELECT token FROM permissions)
+         ORDER BY token ASC",
+    )
+    .fetch_all(pool)
+    .await?;
+    for token in &unknown_tokens {
+        tracing::warn!("Users have been granted the unknown permission {token}.");
+    }
+
+    Ok(unknown_tokens)
+}
+
+/// Serve uploaded files
+/// Clients that accept WebP are sent the WebP variant of an image, if it has one.
+fn uploads_service(upload_path: &str) -> Router {
+    Router::new()
+        .fallback_service(ServeDir::new(upload_path))
+        .layer(axum::middleware::from_fn_with_state(
+            PathBuf::from(upload_path),
+            negotiate_webp_images,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_helpers::create_test_user_with_permissions;
+
+    #[sqlx::test]
+    async fn test_ensure_permissions_is_idempotent(pool: PgPool) {
+        ensure_permissions(&pool).await.unwrap();
+        let unknown_tokens = ensure_permissions(&pool).await.unwrap();
+
+        assert!(unknown_tokens.is_empty());
+        let tokens =
+            sqlx::query_scalar::<_, String>("SELECT token FROM permissions ORDER BY token")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(tokens, PERMISSION_TOKENS.to_vec());
+    }
+
+    #[sqlx::test]
+    async fn test_ensure_permissions_reports_unknown_grants(pool: PgPool) {
+        create_test_user_with_permissions(&pool, 1, vec!["admin", "lable_owner"])
+            .await
+            .unwrap();
+
+        let unknown_tokens = ensure_permissions(&pool).await.unwrap();
+
+        assert_eq!(unknown_tokens, vec!["lable_owner".to_string()]);
+    }
+}
@@ -1,12 +1,19 @@
 //! Global state for the application.
-use axum::extract::FromRef;
+use axum::extract::{ConnectInfo, FromRef};
+use axum::http::request::Parts;
+use dashmap::{DashMap, mapref::entry::Entry};
 use leptos::prelude::LeptosOptions;
 use leptos::prelude::*;
 use leptos_axum::{AxumRouteListing, extract};
 use sqlx::PgPool;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::app::UserContext;
-use crate::models::auth::ssr::AuthSession;
+use crate::config::{cache::record_label_cache_ttl_seconds, site::trust_proxy};
+use crate::middleware::client_ip;
+use crate::models::{auth::ssr::AuthSession, record_label::RecordLabel};
 
 /// The global state for the application.
 ///
@@ -20,6 +27,8 @@ pub struct AppState {
     pub pool: PgPool,
     /// The routes for the application
     pub routes: Vec<AxumRouteListing>,
+    /// The record labels cached between requests
+    pub record_label_cache: Arc<RecordLabelCache>,
 }
 
 /// This gets the database pool from state
@@ -32,6 +41,16 @@ pub fn pool() -> Result<PgPool, ServerFnError> {
         .ok_or_else(|| ServerFnError::ServerError("Pool missing.".into()))
 }
 
+/// This gets the record label cache from state
+///
+/// # Errors
+///
+/// Will return a `ServerError` error if the cache is missing.
+pub fn record_label_cache() -> Result<Arc<RecordLabelCache>, ServerFnError> {
+    with_context::<AppState, _>(|state| state.record_label_cache.clone())
+        .ok_or_else(|| ServerFnError::ServerError("Record label cache missing.".into()))
+}
+
 /// This gets the auth session from state
 ///
 /// # Errors
@@ -42,6 +61,20 @@ pub async fn auth() -> Result<AuthSession, ServerFnError> {
     Ok(auth)
 }
 
+/// This gets the address of the client making the request
+///
+/// The `X-Forwarded-For` header is only used when `TRUST_PROXY` is set, see `client_ip`.
+/// Returns None outside of a request, or if the address is not known.
+pub fn request_client_ip() -> Option<IpAddr> {
+    use_context::<Parts>().and_then(|parts| {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip());
+        client_ip(&parts.headers, peer, trust_proxy())
+    })
+}
+
 /// This gets the user context from state
 ///
 /// # Errors
@@ -51,3 +84,45 @@ pub fn user_context() -> Result<UserContext, ServerFnError> {
     use_context::<UserContext>()
         .ok_or_else(|| ServerFnError::ServerError("User context missing.".into()))
 }
+
+/// An in-memory cache of record labels, keyed by id.
+///
+/// Entries expire after `RECORD_LABEL_CACHE_TTL_SECONDS`, and are replaced with the updated row when the label is
+/// updated. Entries are versioned by `updated_at`, so a lookup that read the label before an update can't overwrite it.
+#[derive(Debug, Default)]
+pub struct RecordLabelCache {
+    entries: DashMap<i64, (Instant, RecordLabel)>,
+}
+
+impl RecordLabelCache {
+    /// Get a record label from the cache, if it hasn't expired
+    #[must_use]
+    pub fn get(&self, id: i64) -> Option<RecordLabel> {
+        let ttl = Duration::from_secs(record_label_cache_ttl_seconds());
+        let cached = self
+            .entries
+            .get(&id)
+            .map(|entry| (entry.0, entry.1.clone()));
+        match cached {
+            Some((cached_at, record_label)) if cached_at.elapsed() < ttl => Some(record_label),
+            Some(_) => {
+                self.entries.remove(&id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Add a record label to the cache, unless a newer version of it is already cached
+    pub fn insert(&self, record_label: RecordLabel) {
+        match self.entries.entry(record_label.id) {
+            Entry::Occupied(entry) if entry.get().1.updated_at > record_label.updated_at => {}
+            Entry::Occupied(mut entry) => {
+                entry.insert((Instant::now(), record_label));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((Instant::now(), record_label));
+            }
+        }
+    }
+}
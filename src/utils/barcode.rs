@@ -0,0 +1,96 @@
+//! Validate release barcodes
+//!
+//! Releases are identified in distribution by a 12 digit UPC-A or 13 digit EAN-13 barcode.
+//! The last digit is a check digit, calculated from the others.
+//! # Example
+//! ```
+//! use white_label::utils::barcode::validate_barcode;
+//! assert!(validate_barcode("4006381333931").is_ok());
+//! assert!(validate_barcode("036000291452").is_ok());
+//! assert!(validate_barcode("4006381333932").is_err());
+//! ```
+
+/// Calculate the check digit for the digits of a barcode, without its check digit
+///
+/// Working back from the digit next to the check digit, digits are weighted 3, 1, 3, 1…
+/// The check digit brings the weighted sum up to a multiple of 10.
+fn check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .zip([3, 1].into_iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+
+    (10 - sum % 10) % 10
+}
+
+/// Check a UPC-A or EAN-13 barcode
+///
+/// # Arguments
+/// * `barcode` - The barcode, as digits only
+///
+/// # Errors
+/// If the barcode is not 12 or 13 digits, return an error
+/// If the check digit does not match, return an error
+pub fn validate_barcode(barcode: &str) -> anyhow::Result<()> {
+    let digits = barcode
+        .chars()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<u32>>>()
+        .filter(|digits| digits.len() == 12 || digits.len() == 13)
+        .ok_or_else(|| anyhow::anyhow!("Barcode must be a 12 digit UPC or 13 digit EAN."))?;
+
+    let (check, digits) = digits
+        .split_last()
+        .ok_or_else(|| anyhow::anyhow!("Barcode must be a 12 digit UPC or 13 digit EAN."))?;
+    if check_digit(digits) != *check {
+        return Err(anyhow::anyhow!("Barcode check digit is invalid."));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_barcode_ean_13() {
+        assert!(validate_barcode("4006381333931").is_ok());
+        assert!(validate_barcode("5012345678900").is_ok());
+    }
+
+    #[test]
+    fn test_validate_barcode_upc_a() {
+        assert!(validate_barcode("036000291452").is_ok());
+    }
+
+    #[test]
+    fn test_validate_barcode_bad_check_digit() {
+        assert_eq!(
+            validate_barcode("4006381333932").unwrap_err().to_string(),
+            "Barcode check digit is invalid."
+        );
+        assert_eq!(
+            validate_barcode("036000291453").unwrap_err().to_string(),
+            "Barcode check digit is invalid."
+        );
+    }
+
+    #[test]
+    fn test_validate_barcode_not_numeric() {
+        for barcode in [
+            "40063813339A1",
+            "4006 381333931",
+            "",
+            "40063813339",
+            "٤٠٠٦٣٨١٣٣٣٩٣",
+        ] {
+            assert_eq!(
+                validate_barcode(barcode).unwrap_err().to_string(),
+                "Barcode must be a 12 digit UPC or 13 digit EAN."
+            );
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Normalise catalogue numbers for comparison and sorting
+//!
+//! Catalogue numbers are written in different formats, "WL-001", "wl 1" and "WL001" are all the same release.
+//! The key keeps only ASCII letters and digits, upper-cases the letters, and zero-pads each run of digits so keys sort numerically.
+//! The `catalogue_sort_key` database function applies the same rule for uniqueness checks and ordering.
+//! # Example
+//! ```
+//! use white_label::utils::catalogue_number::catalogue_sort_key;
+//! assert_eq!(catalogue_sort_key("WL-001"), "WL0000000001");
+//! assert_eq!(catalogue_sort_key("wl 1"), catalogue_sort_key("WL001"));
+//! ```
+
+/// The width each run of digits is zero-padded to. Longer runs are kept whole.
+pub const CATALOGUE_NUMBER_DIGITS: usize = 10;
+
+/// Derive the sort key from a catalogue number
+///
+/// Keep in sync with the `catalogue_sort_key` database function.
+#[must_use]
+pub fn catalogue_sort_key(catalogue_number: &str) -> String {
+    let mut key = String::with_capacity(catalogue_number.len());
+    let mut digits = String::new();
+    for character in catalogue_number.chars().filter(char::is_ascii_alphanumeric) {
+        if character.is_ascii_digit() {
+            digits.push(character);
+            continue;
+        }
+        push_digits(&mut key, &mut digits);
+        key.push(character.to_ascii_uppercase());
+    }
+    push_digits(&mut key, &mut digits);
+    key
+}
+
+/// Append a run of digits to the key, without leading zeros and padded to a fixed width
+fn push_digits(key: &mut String, digits: &mut String) {
+    if digits.is_empty() {
+        return;
+    }
+    let trimmed = digits.trim_start_matches('0');
+    key.extend(std::iter::repeat_n(
+        '0',
+        CATALOGUE_NUMBER_DIGITS.saturating_sub(trimmed.len()),
+    ));
+    key.push_str(trimmed);
+    digits.clear();
+}
+
+/// The width the sequence of a generated catalogue number is zero-padded to, e.g. "CAT-0042".
+pub const CATALOGUE_SEQUENCE_DIGITS: usize = 4;
+
+/// Suggest the catalogue number that follows the existing ones with the prefix
+///
+/// Existing numbers are matched by their sort key, so "cat 7" counts towards the "CAT-" sequence.
+/// Numbers with anything after the sequence, e.g. "CAT-0007-LP", are ignored.
+///
+/// # Arguments
+/// * `prefix` - The record label's catalogue prefix
+/// * `existing` - The catalogue numbers already used on the record label
+///
+/// # Returns
+/// The prefix followed by the next zero-padded sequence number
+#[must_use]
+pub fn next_catalogue_number<'a>(
+    prefix: &str,
+    existing: impl IntoIterator<Item = &'a str>,
+) -> String {
+    let prefix_key = catalogue_sort_key(prefix);
+    let last = existing
+        .into_iter()
+        .filter_map(|catalogue_number| {
+            let key = catalogue_sort_key(catalogue_number);
+            let sequence = key.strip_prefix(&prefix_key)?;
+            if sequence.is_empty() || !sequence.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            sequence.parse::<u64>().ok()
+        })
+        .max()
+        .unwrap_or(0);
+    format!(
+        "{prefix}{:0width$}",
+        last + 1,
+        width = CATALOGUE_SEQUENCE_DIGITS
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogue_sort_key_ignores_format() {
+        assert_eq!(catalogue_sort_key("WL-001"), "WL0000000001");
+        assert_eq!(catalogue_sort_key("wl 1"), "WL0000000001");
+        assert_eq!(catalogue_sort_key("WL.0001"), "WL0000000001");
+        assert_eq!(catalogue_sort_key("wl-001-lp"), "WL0000000001LP");
+        assert_eq!(catalogue_sort_key(" -- "), "");
+    }
+
+    #[test]
+    fn test_catalogue_sort_key_sorts_numerically() {
+        assert!(catalogue_sort_key("WL-2") < catalogue_sort_key("WL-10"));
+        assert_eq!(catalogue_sort_key("000"), "0000000000");
+        assert_eq!(catalogue_sort_key("12345678901"), "12345678901");
+    }
+
+    #[test]
+    fn test_next_catalogue_number() {
+        assert_eq!(next_catalogue_number("WL-", []), "WL-0001");
+        assert_eq!(
+            next_catalogue_number("WL-", ["WL-0041", "wl 42", "WL-0007-LP", "XY-0100", ""]),
+            "WL-0043"
+        );
+        assert_eq!(next_catalogue_number("WL-", ["WL-9999"]), "WL-10000");
+        assert_eq!(next_catalogue_number("", ["0009", "A-0020"]), "0010");
+    }
+}
@@ -0,0 +1,107 @@
+//! Currency helpers
+//!
+//! Prices are stored as an integer number of minor units (e.g. pence or cents) alongside an ISO 4217 currency code.
+//! # Example
+//! ```
+//! use white_label::utils::currency::format_price;
+//! assert_eq!(format_price(999, "GBP"), "£9.99");
+//! assert_eq!(format_price(1500, "JPY"), "¥1500");
+//! ```
+
+/// Active ISO 4217 currency codes
+pub const ISO_4217_CURRENCIES: [&str; 154] = [
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD",
+    "CDF", "CHF", "CLP", "CNY", "COP", "CRC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP", "DZD",
+    "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ",
+    "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD",
+    "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR",
+    "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR",
+    "MWK", "MXN", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN",
+    "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD", "SCR",
+    "SDG", "SEK", "SGD", "SHP", "SLE", "SOS", "SRD", "SSP", "STN", "SYP", "SZL", "THB", "TJS",
+    "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "UYU", "UZS", "VES",
+    "VND", "VUV", "WST", "XAF", "XCD", "XOF", "XPF", "YER", "ZAR", "ZMW", "ZWG",
+];
+
+/// Is the code an active ISO 4217 currency code
+#[must_use]
+pub fn is_valid_currency(code: &str) -> bool {
+    ISO_4217_CURRENCIES.contains(&code)
+}
+
+/// The symbol used to display a currency, if it has a common one
+#[must_use]
+pub const fn currency_symbol(code: &str) -> Option<&'static str> {
+    match code.as_bytes() {
+        b"GBP" => Some("£"),
+        b"EUR" => Some("€"),
+        b"USD" => Some("$"),
+        b"JPY" | b"CNY" => Some("¥"),
+        b"INR" => Some("₹"),
+        b"KRW" => Some("₩"),
+        _ => None,
+    }
+}
+
+/// The number of decimal places used by a currency
+#[must_use]
+pub const fn minor_units(code: &str) -> u32 {
+    match code.as_bytes() {
+        b"BIF" | b"CLP" | b"DJF" | b"GNF" | b"ISK" | b"JPY" | b"KMF" | b"KRW" | b"PYG" | b"RWF"
+        | b"UGX" | b"VND" | b"VUV" | b"XAF" | b"XOF" | b"XPF" => 0,
+        b"BHD" | b"IQD" | b"JOD" | b"KWD" | b"LYD" | b"OMR" | b"TND" => 3,
+        _ => 2,
+    }
+}
+
+/// Format a price for display
+/// Currencies without a common symbol are shown with their code, e.g. "9.99 CHF".
+///
+/// # Arguments
+/// * `minor_amount` - The price in the currency's minor units, e.g. pence
+/// * `code` - The ISO 4217 currency code
+#[must_use]
+pub fn format_price(minor_amount: i32, code: &str) -> String {
+    let decimals = minor_units(code);
+    let amount = if decimals == 0 {
+        minor_amount.to_string()
+    } else {
+        let divisor = 10_i32.pow(decimals);
+        format!(
+            "{}.{:0width$}",
+            minor_amount / divisor,
+            (minor_amount % divisor).abs(),
+            width = decimals as usize
+        )
+    };
+
+    currency_symbol(code).map_or_else(
+        || format!("{amount} {code}"),
+        |symbol| format!("{symbol}{amount}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_currency() {
+        assert!(is_valid_currency("GBP"));
+        assert!(is_valid_currency("USD"));
+        assert!(!is_valid_currency("gbp"));
+        assert!(!is_valid_currency("XYZ"));
+        assert!(!is_valid_currency(""));
+    }
+
+    #[test]
+    fn test_format_price() {
+        assert_eq!(format_price(999, "GBP"), "£9.99");
+        assert_eq!(format_price(1005, "EUR"), "€10.05");
+        assert_eq!(format_price(50, "USD"), "$0.50");
+        assert_eq!(format_price(1500, "JPY"), "¥1500");
+        assert_eq!(format_price(1250, "KWD"), "1.250 KWD");
+        assert_eq!(format_price(1999, "CHF"), "19.99 CHF");
+    }
+}
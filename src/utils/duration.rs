@@ -0,0 +1,43 @@
+//! Helpers for displaying durations
+//!
+//! # Example
+//! ```
+//! use white_label::utils::duration::format_duration;
+//! assert_eq!(format_duration(225), "3:45");
+//! assert_eq!(format_duration(3723), "1:02:03");
+//! ```
+
+/// Format a number of seconds for display
+/// Durations under an hour are shown as `m:ss`, longer durations as `h:mm:ss`.
+#[must_use]
+pub fn format_duration(total_seconds: i32) -> String {
+    let total_seconds = total_seconds.max(0);
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(45), "0:45");
+        assert_eq!(format_duration(754), "12:34");
+        assert_eq!(format_duration(3600), "1:00:00");
+        assert_eq!(format_duration(3723), "1:02:03");
+    }
+
+    #[test]
+    fn test_format_duration_negative() {
+        assert_eq!(format_duration(-5), "0:00");
+    }
+}
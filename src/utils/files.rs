@@ -2,6 +2,166 @@
 
 #[cfg(feature = "ssr")]
 use leptos::prelude::ServerFnError;
+use std::fmt;
+
+use crate::config::image::DEFAULT_IMAGE;
+
+#[cfg(feature = "ssr")]
+use super::slugify::slugify;
+
+/// File extensions that may be stored as uploaded files.
+pub const ALLOWED_FILE_EXTENSIONS: [&str; 5] = ["gif", "jpeg", "jpg", "png", "webp"];
+
+/// A file name that is safe to join onto an upload directory.
+///
+/// Stored file names are only ever joined to a filesystem path or URL through this type,
+/// so a name like `../../etc/passwd` can never escape the upload directory.
+///
+/// # Example
+/// ```
+/// use white_label::utils::files::SafeFileName;
+/// assert!(SafeFileName::new("1700000000-my-artist.jpg").is_ok());
+/// assert!(SafeFileName::new("../secrets.jpg").is_err());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafeFileName(String);
+
+impl SafeFileName {
+    /// Validate a file name
+    ///
+    /// # Errors
+    /// If the file name is empty or longer than 255 characters, return an error
+    /// If the file name contains a path separator or `..`, return an error
+    /// If the file name contains anything other than ASCII letters, numbers, `-`, `_` or `.`, return an error
+    /// If the file extension is not one of `ALLOWED_FILE_EXTENSIONS`, return an error
+    pub fn new(file_name: &str) -> anyhow::Result<Self> {
+        if file_name.is_empty() || file_name.len() > 255 {
+            return Err(anyhow::anyhow!(
+                "File name must be between 1 and 255 characters."
+            ));
+        }
+        if file_name.contains('/') || file_name.contains('\\') || file_name.contains("..") {
+            return Err(anyhow::anyhow!("File name must not contain a path."));
+        }
+        if file_name.starts_with('.')
+            || !file_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(anyhow::anyhow!("File name contains invalid characters."));
+        }
+        match file_extension(file_name) {
+            Some(extension)
+                if ALLOWED_FILE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()) => {}
+            _ => return Err(anyhow::anyhow!("File type is not allowed.")),
+        }
+
+        Ok(Self(file_name.to_string()))
+    }
+
+    /// Get the file name as a string slice
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Join the file name onto a directory
+    #[must_use]
+    pub fn path_in(&self, directory: &str) -> std::path::PathBuf {
+        std::path::Path::new(directory).join(&self.0)
+    }
+}
+
+impl fmt::Display for SafeFileName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Get the public URL for an uploaded file
+/// If there is no file, or the stored name is not safe, return the default image
+///
+/// # Example
+/// ```
+/// use white_label::utils::files::upload_url;
+/// assert_eq!(upload_url("artists", Some("image.jpg")), "/uploads/artists/image.jpg");
+/// assert_eq!(upload_url("artists", Some("../image.jpg")), "/Logo.svg");
+/// assert_eq!(upload_url("artists", None), "/Logo.svg");
+/// ```
+#[must_use]
+pub fn upload_url(directory: &str, file_name: Option<&str>) -> String {
+    upload_url_or(directory, file_name, DEFAULT_IMAGE)
+}
+
+/// Get the public URL for an uploaded file
+/// If there is no file, or the stored name is not safe, return the placeholder
+///
+/// # Example
+/// ```
+/// use white_label::utils::files::upload_url_or;
+/// assert_eq!(upload_url_or("releases", Some("cover.jpg"), "/vinyl.svg"), "/uploads/releases/cover.jpg");
+/// assert_eq!(upload_url_or("releases", None, "/vinyl.svg"), "/vinyl.svg");
+/// ```
+#[must_use]
+pub fn upload_url_or(directory: &str, file_name: Option<&str>, placeholder: &str) -> String {
+    let Some(file_name) = file_name else {
+        return placeholder.to_string();
+    };
+    match SafeFileName::new(file_name) {
+        Ok(file) => format!("/uploads/{directory}/{file}"),
+        Err(e) => {
+            // A stored name should always be safe, so this points at a bad row rather than a missing image
+            tracing::warn!("Showing {placeholder} instead of stored file {file_name:?}: {e}");
+            placeholder.to_string()
+        }
+    }
+}
+
+/// The upload directory that Open Graph share images are cached in
+pub const OG_IMAGE_DIRECTORY: &str = "og";
+
+/// Get the file name of an Open Graph share image
+/// The name includes a fingerprint of the title and artwork drawn on the image, so it only changes when the image would.
+///
+/// # Example
+/// ```
+/// use white_label::utils::files::og_image_file_name;
+/// let file_name = og_image_file_name("release", 1, "A Release", Some("cover.jpg"));
+/// assert!(file_name.starts_with("release-1-"));
+/// assert!(file_name.ends_with(".png"));
+/// assert_eq!(file_name, og_image_file_name("release", 1, "A Release", Some("cover.jpg")));
+/// assert_ne!(file_name, og_image_file_name("release", 1, "A Release", None));
+/// ```
+#[must_use]
+pub fn og_image_file_name(kind: &str, id: i64, title: &str, artwork: Option<&str>) -> String {
+    // FNV-1a, so the fingerprint is the same across builds and restarts
+    let mut fingerprint: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in [title, "\0", artwork.unwrap_or_default()].concat().bytes() {
+        fingerprint ^= u64::from(byte);
+        fingerprint = fingerprint.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{kind}-{id}-{fingerprint:016x}.png")
+}
+
+/// File extensions of uploaded images that get a WebP variant.
+pub const WEBP_VARIANT_EXTENSIONS: [&str; 3] = ["jpeg", "jpg", "png"];
+
+/// Get the file name of the WebP variant of an uploaded image
+/// The variant keeps the original name, so it can never clash with another upload.
+///
+/// # Example
+/// ```
+/// use white_label::utils::files::webp_variant_file_name;
+/// assert_eq!(webp_variant_file_name("1700000000-cover.jpg"), Some("1700000000-cover.jpg.webp".to_string()));
+/// assert_eq!(webp_variant_file_name("1700000000-cover.webp"), None);
+/// ```
+#[must_use]
+pub fn webp_variant_file_name(file_name: &str) -> Option<String> {
+    let extension = file_extension(file_name)?.to_lowercase();
+    WEBP_VARIANT_EXTENSIONS
+        .contains(&extension.as_str())
+        .then(|| format!("{file_name}.webp"))
+}
 
 /// Get the file extension from a file name
 ///
@@ -24,37 +184,37 @@ pub fn file_extension(file_name: &str) -> Option<&str> {
 /// This function will return a filename that is unique if `rename` is true.
 /// If `rename` is false, it will return the original filename.
 ///
+/// A renamed file is slugified so it only contains safe characters.
+///
 /// # Errors
 /// - If the file already exists and `file_exists_ok` is false, it will return an error.
+/// - If the resulting file name is not a `SafeFileName`, it will return an error.
 #[cfg(feature = "ssr")]
 pub fn valid_file_name(
     file_name: &str,
     rename: Option<String>,
     path: &str,
     overwrite_existing_file: bool,
-) -> Result<String, ServerFnError> {
-    let mut name = file_name.to_string();
-
-    match rename {
-        Some(rename_as) => match file_extension(file_name) {
-            Some(file_extension) => {
-                name = format!("{rename_as}.{file_extension}");
-            }
-            None => {
-                name.clone_from(&rename_as);
-            }
+) -> Result<SafeFileName, ServerFnError> {
+    let name = rename.map_or_else(
+        || file_name.to_string(),
+        |rename_as| {
+            file_extension(file_name).map_or_else(
+                || slugify(&rename_as),
+                |file_extension| format!("{}.{file_extension}", slugify(&rename_as)),
+            )
         },
-        None => {
-            name = file_name.to_string();
-        }
-    }
-    name = format!("{}-{name}", chrono::Utc::now().timestamp());
+    );
+    let name = format!("{}-{name}", chrono::Utc::now().timestamp());
+    let name = SafeFileName::new(&name).map_err(|e| {
+        tracing::error!("Invalid file name {name:?}: {e}");
+        ServerFnError::new(e)
+    })?;
 
     if !overwrite_existing_file {
         // Check if the file already exists
         // If it does, return an error
-        let file_path = format!("{path}/{name}");
-        if std::path::Path::new(&file_path).exists() {
+        if name.path_in(path).exists() {
             return Err(ServerFnError::new("File already exists.".to_string()));
         }
     }
@@ -86,4 +246,102 @@ mod tests {
         let extension = file_extension(file_name);
         assert_eq!(extension, Some("txt"));
     }
+
+    #[test]
+    fn test_safe_file_name() {
+        let file_name = SafeFileName::new("1700000000-test-artist.jpg").unwrap();
+        assert_eq!(file_name.as_str(), "1700000000-test-artist.jpg");
+        assert_eq!(
+            file_name.path_in("/uploads/artists"),
+            std::path::PathBuf::from("/uploads/artists/1700000000-test-artist.jpg")
+        );
+        assert!(SafeFileName::new("IMAGE.PNG").is_ok());
+    }
+
+    #[test]
+    fn test_safe_file_name_traversal() {
+        for file_name in [
+            "../image.jpg",
+            "../../etc/passwd",
+            "artists/image.jpg",
+            "..\\image.jpg",
+            "image..jpg",
+        ] {
+            assert_eq!(
+                SafeFileName::new(file_name).unwrap_err().to_string(),
+                "File name must not contain a path.",
+                "{file_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_safe_file_name_invalid() {
+        assert_eq!(
+            SafeFileName::new("").unwrap_err().to_string(),
+            "File name must be between 1 and 255 characters."
+        );
+        assert_eq!(
+            SafeFileName::new(".hidden.jpg").unwrap_err().to_string(),
+            "File name contains invalid characters."
+        );
+        assert_eq!(
+            SafeFileName::new("my image.jpg").unwrap_err().to_string(),
+            "File name contains invalid characters."
+        );
+        assert_eq!(
+            SafeFileName::new("script.sh").unwrap_err().to_string(),
+            "File type is not allowed."
+        );
+        assert_eq!(
+            SafeFileName::new("image").unwrap_err().to_string(),
+            "File type is not allowed."
+        );
+    }
+
+    #[test]
+    fn test_upload_url() {
+        assert_eq!(
+            upload_url("releases", Some("image.png")),
+            "/uploads/releases/image.png"
+        );
+        assert_eq!(upload_url("releases", Some("../image.png")), "/Logo.svg");
+        assert_eq!(upload_url("releases", None), "/Logo.svg");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn test_valid_file_name() {
+        let directory = std::env::temp_dir();
+        let name = valid_file_name(
+            "photo.jpg",
+            Some("Test User".to_string()),
+            directory.to_str().unwrap(),
+            true,
+        )
+        .unwrap();
+        assert!(name.as_str().ends_with("-test-user.jpg"));
+
+        let result = valid_file_name(
+            "photo.jpg",
+            Some("../../etc".to_string()),
+            directory.to_str().unwrap(),
+            true,
+        );
+        assert!(!result.unwrap().as_str().contains('/'));
+    }
+
+    #[test]
+    fn test_webp_variant_file_name() {
+        assert_eq!(
+            webp_variant_file_name("cover.JPEG"),
+            Some("cover.JPEG.webp".to_string())
+        );
+        assert_eq!(
+            webp_variant_file_name("cover.png"),
+            Some("cover.png.webp".to_string())
+        );
+        assert_eq!(webp_variant_file_name("cover.gif"), None);
+        assert_eq!(webp_variant_file_name("cover"), None);
+    }
 }
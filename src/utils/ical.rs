@@ -0,0 +1,86 @@
+//! Helpers for writing iCalendar (RFC 5545) documents
+//!
+//! # Example
+//! ```
+//! use white_label::utils::ical::escape_ical_text;
+//! assert_eq!(escape_ical_text("Live, Loud; Proud"), "Live\\, Loud\\; Proud");
+//! ```
+
+/// The maximum length of a content line in octets, excluding the line break
+pub const MAX_LINE_OCTETS: usize = 75;
+
+/// Escape text so it can be placed in an iCalendar TEXT value
+#[must_use]
+pub fn escape_ical_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => (),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line so no line is longer than `MAX_LINE_OCTETS`
+/// Continuation lines start with a single space, and multi-byte characters are never split.
+#[must_use]
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_octets = 0;
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_ical_text() {
+        assert_eq!(
+            escape_ical_text("Back\\slash, semi;colon\r\nnew line"),
+            "Back\\\\slash\\, semi\\;colon\\nnew line"
+        );
+    }
+
+    #[test]
+    fn test_escape_ical_text_plain_text() {
+        assert_eq!(escape_ical_text("Test Release 1"), "Test Release 1");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("SUMMARY:{}", "a".repeat(100));
+        let folded = fold_line(&line);
+        let lines = folded.split("\r\n").collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_LINE_OCTETS);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn test_fold_line_multi_byte() {
+        let line = format!("SUMMARY:{}", "é".repeat(50));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn test_fold_line_short() {
+        assert_eq!(fold_line("SUMMARY:Short"), "SUMMARY:Short");
+    }
+}
@@ -0,0 +1,120 @@
+//! Generate ISRC codes
+//!
+//! An ISRC is made up of four parts:
+//! - A two character country code (e.g. UK)
+//! - A three character registrant code, issued to the label
+//! - The last two digits of the year of reference
+//! - A five digit designation code, unique within the year
+//!
+//! The country code and registrant code together are stored on the record label as the ISRC base.
+//! # Example
+//! ```
+//! use white_label::utils::isrc::generate_isrc;
+//! let isrc = generate_isrc("UK-AAA", 2025, 1).unwrap();
+//! assert_eq!(isrc, "UKAAA2500001");
+//! ```
+
+/// Normalise an ISRC base
+///
+/// Removes any spaces or hyphens and uppercases the remaining characters.
+#[must_use]
+pub fn normalise_isrc_base(isrc_base: &str) -> String {
+    isrc_base
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Is an ISRC code well formed
+///
+/// Spaces and hyphens are ignored, so "UK-AAA-25-00001" is valid.
+#[must_use]
+pub fn is_valid_isrc(isrc: &str) -> bool {
+    let isrc = normalise_isrc_base(isrc);
+    isrc.is_ascii()
+        && isrc.len() == 12
+        && isrc[..2].chars().all(|c| c.is_ascii_alphabetic())
+        && isrc[2..5].chars().all(|c| c.is_ascii_alphanumeric())
+        && isrc[5..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Generate an ISRC code
+///
+/// # Arguments
+/// * `isrc_base` - The country and registrant code of the label, e.g. "UK-AAA"
+/// * `year` - The year of reference. Only the last two digits are used.
+/// * `designation` - The sequential designation code within the year
+///
+/// # Returns
+/// The 12 character ISRC code
+///
+/// # Errors
+/// If the ISRC base is not five alphanumeric characters, return an error
+/// If the designation is outside of 1 to 99999, return an error
+pub fn generate_isrc(isrc_base: &str, year: i32, designation: i64) -> anyhow::Result<String> {
+    let base = normalise_isrc_base(isrc_base);
+    if base.len() != 5 || !base.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(anyhow::anyhow!(
+            "ISRC base must be 5 alphanumeric characters."
+        ));
+    }
+    if !(1..=99999).contains(&designation) {
+        return Err(anyhow::anyhow!(
+            "ISRC designation code must be between 1 and 99999."
+        ));
+    }
+
+    Ok(format!(
+        "{base}{:02}{designation:0>5}",
+        year.rem_euclid(100)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise_isrc_base() {
+        assert_eq!(normalise_isrc_base("uk-aaa"), "UKAAA");
+        assert_eq!(normalise_isrc_base("UK AAA"), "UKAAA");
+    }
+
+    #[test]
+    fn test_is_valid_isrc() {
+        assert!(is_valid_isrc("UKAAA2500001"));
+        assert!(is_valid_isrc("uk-aaa-25-00001"));
+        assert!(!is_valid_isrc("UKAAA250001"));
+        assert!(!is_valid_isrc("12AAA2500001"));
+        assert!(!is_valid_isrc("UKAAA25ABCDE"));
+        assert!(!is_valid_isrc("UKAAA2500001é"));
+        assert!(!is_valid_isrc("éAAA25000001"));
+    }
+
+    #[test]
+    fn test_generate_isrc() {
+        assert_eq!(generate_isrc("UK-AAA", 2025, 1).unwrap(), "UKAAA2500001");
+        assert_eq!(generate_isrc("UKAAA", 2009, 12345).unwrap(), "UKAAA0912345");
+    }
+
+    #[test]
+    fn test_generate_isrc_invalid_base() {
+        let result = generate_isrc("UK AAA 1", 2025, 1);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ISRC base must be 5 alphanumeric characters."
+        );
+    }
+
+    #[test]
+    fn test_generate_isrc_designation_out_of_range() {
+        let result = generate_isrc("UKAAA", 2025, 100_000);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "ISRC designation code must be between 1 and 99999."
+        );
+        let result = generate_isrc("UKAAA", 2025, 0);
+        assert!(result.is_err());
+    }
+}
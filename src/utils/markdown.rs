@@ -0,0 +1,81 @@
+//! Render markdown to sanitised HTML
+//!
+//! Raw HTML in the markdown is allowed through, then cleaned against an allowlist.
+//! Scripts and event handler attributes are removed, and links are given `rel="noopener"`.
+//! This only runs on the server, so the sanitiser is kept out of the browser bundle.
+//! # Example
+//! ```
+//! use white_label::utils::markdown::render_markdown;
+//! assert_eq!(render_markdown("# Hello"), "<h1>Hello</h1>");
+//! ```
+
+/// Render markdown to HTML that is safe to inject into a page
+///
+/// # Arguments
+/// * `input` - The markdown to render
+///
+/// # Returns
+/// The sanitised HTML, or an empty string if the markdown could not be rendered
+#[must_use]
+pub fn render_markdown(input: &str) -> String {
+    let options = markdown::Options {
+        compile: markdown::CompileOptions {
+            // Safe only because the output is always cleaned by ammonia below
+            allow_dangerous_html: true,
+            ..markdown::CompileOptions::gfm()
+        },
+        ..markdown::Options::gfm()
+    };
+    let html = markdown::to_html_with_options(input, &options).unwrap_or_default();
+
+    ammonia::Builder::default()
+        .link_rel(Some("noopener"))
+        .clean(&html)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_heading() {
+        assert_eq!(
+            render_markdown("# This is an updated page"),
+            "<h1>This is an updated page</h1>"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_removes_script() {
+        let html = render_markdown("Hello\n\n<script>alert('hi')</script>\n\nWorld");
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert"));
+        assert!(html.contains("<p>Hello</p>"));
+        assert!(html.contains("<p>World</p>"));
+    }
+
+    #[test]
+    fn test_render_markdown_removes_event_handlers() {
+        let html = render_markdown("<img src=\"/cover.png\" onerror=\"alert('hi')\">");
+
+        assert!(!html.contains("onerror"));
+        assert!(html.contains("src=\"/cover.png\""));
+    }
+
+    #[test]
+    fn test_render_markdown_links() {
+        assert_eq!(
+            render_markdown("[Our shop](https://example.com/shop)"),
+            "<p><a href=\"https://example.com/shop\" rel=\"noopener\">Our shop</a></p>"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_removes_javascript_links() {
+        let html = render_markdown("<a href=\"javascript:alert('hi')\">Click</a>");
+
+        assert!(!html.contains("javascript"));
+    }
+}
@@ -1,7 +1,23 @@
 //! This module contains utility functions that are used throughout the application.
 
+pub mod barcode;
+pub mod catalogue_number;
+pub mod currency;
+pub mod duration;
 pub mod files;
+pub mod ical;
+pub mod isrc;
+#[cfg(feature = "ssr")]
+pub mod markdown;
+pub mod pagination;
 pub mod redirect;
+pub mod search;
 pub mod shorten_string;
 pub mod slugify;
+pub mod sort_name;
 pub mod split_at_colon;
+pub mod token;
+#[cfg(feature = "ssr")]
+pub mod unicode;
+pub mod urls;
+pub mod xml;
@@ -0,0 +1,101 @@
+//! Page through long admin listings
+//!
+//! Pages are numbered from 1. Out of range values are clamped rather than rejected,
+//! so a stale page number in the URL still returns something sensible.
+//! # Example
+//! ```
+//! use white_label::utils::pagination::Pagination;
+//! let pagination = Pagination::new(3, 25);
+//! assert_eq!(pagination.limit(), 25);
+//! assert_eq!(pagination.offset(), 50);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// The number of items on a page when none is given.
+pub const DEFAULT_PER_PAGE: i64 = 50;
+/// The most items that can be requested on a single page.
+pub const MAX_PER_PAGE: i64 = 500;
+
+/// A page of a listing
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pagination {
+    /// The page number, starting from 1
+    pub page: i64,
+    /// The number of items on each page
+    pub per_page: i64,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+        }
+    }
+}
+
+impl Pagination {
+    /// Create a pagination, clamping the page and page size into range
+    #[must_use]
+    pub fn new(page: i64, per_page: i64) -> Self {
+        Self {
+            page: page.max(1),
+            per_page: per_page.clamp(1, MAX_PER_PAGE),
+        }
+    }
+
+    /// The number of items to fetch
+    #[must_use]
+    pub fn limit(&self) -> i64 {
+        Self::new(self.page, self.per_page).per_page
+    }
+
+    /// The number of items to skip
+    #[must_use]
+    pub fn offset(&self) -> i64 {
+        let pagination = Self::new(self.page, self.per_page);
+        (pagination.page - 1).saturating_mul(pagination.per_page)
+    }
+
+    /// The number of pages needed for `total` items
+    #[must_use]
+    pub fn page_count(&self, total: i64) -> i64 {
+        let per_page = self.limit();
+        (total.max(0) + per_page - 1) / per_page
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination_offset() {
+        assert_eq!(Pagination::default().offset(), 0);
+        assert_eq!(Pagination::new(2, 10).offset(), 10);
+        assert_eq!(Pagination::new(5, 20).offset(), 80);
+    }
+
+    #[test]
+    fn test_pagination_clamps_out_of_range_values() {
+        let pagination = Pagination::new(0, 0);
+        assert_eq!(pagination, Pagination::new(1, 1));
+
+        // Deserialised values skip `new`, but are still clamped when used
+        let pagination = Pagination {
+            page: -3,
+            per_page: 10_000,
+        };
+        assert_eq!(pagination.offset(), 0);
+        assert_eq!(pagination.limit(), MAX_PER_PAGE);
+    }
+
+    #[test]
+    fn test_pagination_page_count() {
+        let pagination = Pagination::new(1, 10);
+        assert_eq!(pagination.page_count(0), 0);
+        assert_eq!(pagination.page_count(10), 1);
+        assert_eq!(pagination.page_count(11), 2);
+    }
+}
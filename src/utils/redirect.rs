@@ -1,4 +1,13 @@
 //! Redirects to a new page using Leptos Router.
+//!
+//! Redirect targets that come from the request, such as a `next` parameter after logging in,
+//! must be checked with `safe_redirect_target` so the site can't be used as an open redirect.
+//! # Example
+//! ```
+//! use white_label::utils::redirect::safe_redirect_target;
+//! assert_eq!(safe_redirect_target(Some("/admin/pages"), "/admin"), "/admin/pages");
+//! assert_eq!(safe_redirect_target(Some("//evil.example"), "/admin"), "/admin");
+//! ```
 
 use leptos_router::{NavigateOptions, hooks::use_navigate};
 
@@ -7,3 +16,79 @@ pub fn redirect(url: &str) {
     let navigate = use_navigate();
     navigate(url, NavigateOptions::default());
 }
+
+/// Is the target a path on this site
+///
+/// Only absolute paths are allowed. Protocol-relative (`//host`) and absolute URLs are rejected,
+/// as are backslashes and control characters, which some browsers treat as `/`.
+#[must_use]
+pub fn is_safe_redirect(target: &str) -> bool {
+    target.starts_with('/')
+        && !target.starts_with("//")
+        && !target.contains('\\')
+        && !target.chars().any(char::is_control)
+}
+
+/// The page to redirect to after logging in
+///
+/// Uses `next` when it is a path on this site, otherwise falls back to `fallback`.
+/// The server passes the configured login redirect as the fallback, the browser can't read it.
+#[must_use]
+pub fn safe_redirect_target(next: Option<&str>, fallback: &str) -> String {
+    next.map(str::trim)
+        .filter(|target| is_safe_redirect(target))
+        .map_or_else(|| fallback.to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_redirect_target_relative_path() {
+        assert_eq!(
+            safe_redirect_target(Some("/admin/pages"), "/admin"),
+            "/admin/pages"
+        );
+        assert_eq!(
+            safe_redirect_target(Some("/artists/test?tab=releases#top"), "/admin"),
+            "/artists/test?tab=releases#top"
+        );
+    }
+
+    #[test]
+    fn test_safe_redirect_target_protocol_relative() {
+        assert_eq!(safe_redirect_target(Some("//evil.com"), "/admin"), "/admin");
+        assert_eq!(
+            safe_redirect_target(Some("/\\evil.com"), "/admin"),
+            "/admin"
+        );
+        assert_eq!(
+            safe_redirect_target(Some("/\t/evil.com"), "/admin"),
+            "/admin"
+        );
+    }
+
+    #[test]
+    fn test_safe_redirect_target_absolute_url() {
+        assert_eq!(
+            safe_redirect_target(Some("https://evil.com/admin"), "/admin"),
+            "/admin"
+        );
+        assert_eq!(
+            safe_redirect_target(Some("javascript:alert(1)"), "/admin"),
+            "/admin"
+        );
+        assert_eq!(safe_redirect_target(Some("admin"), "/admin"), "/admin");
+        assert_eq!(safe_redirect_target(None, "/admin"), "/admin");
+    }
+
+    #[test]
+    fn test_safe_redirect_target_fallback() {
+        assert_eq!(safe_redirect_target(None, "/dashboard"), "/dashboard");
+        assert_eq!(
+            safe_redirect_target(Some("//evil.com"), "/dashboard"),
+            "/dashboard"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Build search patterns
+//!
+//! Search queries are matched with `ILIKE`, so any `%` or `_` typed by the user must be escaped to match literally.
+//! Results are ranked exact matches first, then prefix matches, then substring matches.
+//! # Example
+//! ```
+//! use white_label::utils::search::search_patterns;
+//! let patterns = search_patterns("50%");
+//! assert_eq!(patterns.exact, "50\\%");
+//! assert_eq!(patterns.prefix, "50\\%%");
+//! assert_eq!(patterns.contains, "%50\\%%");
+//! ```
+
+/// The most results of each type that can be requested on a single page of search results.
+/// Search is open to everyone, so this is kept well below `MAX_PER_PAGE`.
+pub const MAX_SEARCH_PER_PAGE: i64 = 50;
+
+/// The `ILIKE` patterns for a search query
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SearchPatterns {
+    /// Matches the whole value, ignoring case
+    pub exact: String,
+    /// Matches the start of the value
+    pub prefix: String,
+    /// Matches anywhere in the value
+    pub contains: String,
+}
+
+/// Escape the `LIKE` wildcards in a search query, so they match literally
+#[must_use]
+pub fn escape_like(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+    for c in query.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Build the `ILIKE` patterns for a search query
+/// The query is trimmed before the patterns are built.
+#[must_use]
+pub fn search_patterns(query: &str) -> SearchPatterns {
+    let escaped = escape_like(query.trim());
+    SearchPatterns {
+        prefix: format!("{escaped}%"),
+        contains: format!("%{escaped}%"),
+        exact: escaped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("plain"), "plain");
+        assert_eq!(escape_like("100%_\\"), "100\\%\\_\\\\");
+    }
+
+    #[test]
+    fn test_search_patterns() {
+        assert_eq!(
+            search_patterns(" WL-001 "),
+            SearchPatterns {
+                exact: "WL-001".to_string(),
+                prefix: "WL-001%".to_string(),
+                contains: "%WL-001%".to_string(),
+            }
+        );
+    }
+}
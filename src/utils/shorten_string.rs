@@ -1,4 +1,17 @@
 //! Shorten a string to the first period or newline, and remove markdown characters.
+//!
+//! `excerpt` makes a plain text preview of a markdown description for listings and cards.
+//! # Example
+//! ```
+//! use white_label::utils::shorten_string::excerpt;
+//! assert_eq!(excerpt("# Debut\n\nA **bold** first record.", 20), "Debut A bold first…");
+//! ```
+
+use markdown::mdast::Node;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Appended to an excerpt when the text was cut short.
+pub const ELLIPSIS: char = '…';
 
 /// Shorten a string. It will either be to the first . or the first new line
 #[must_use]
@@ -22,6 +35,100 @@ pub fn shorten_string(s: String) -> String {
     new_str
 }
 
+/// Shorten a string to at most `max_chars` characters, breaking between words
+///
+/// Characters are counted as graphemes, so an accented letter counts once however it is encoded.
+/// Leading and trailing whitespace is trimmed. Text that is cut short ends at the last whole word that fits,
+/// and "…" is appended, counting towards `max_chars`.
+/// A single word longer than the limit is cut between graphemes.
+///
+/// # Example
+/// ```
+/// use white_label::utils::shorten_string::shorten_string_words;
+/// assert_eq!(shorten_string_words("Live at the Brudenell", 12), "Live at the…");
+/// assert_eq!(shorten_string_words("Live", 12), "Live");
+/// ```
+#[must_use]
+pub fn shorten_string_words(input: &str, max_chars: usize) -> String {
+    let input = input.trim();
+    if input.graphemes(true).count() <= max_chars {
+        return input.to_string();
+    }
+
+    // Leave room for the ellipsis
+    let limit = max_chars.saturating_sub(1);
+    let cut = input
+        .grapheme_indices(true)
+        .nth(limit)
+        .map_or(input.len(), |(index, _)| index);
+    let shortened = if input[cut..].starts_with(char::is_whitespace) {
+        &input[..cut]
+    } else {
+        // Back off to the end of the last whole word
+        input[..cut]
+            .rfind(char::is_whitespace)
+            .map_or("", |index| &input[..index])
+    };
+    let shortened = shortened.trim_end_matches(|c: char| c.is_whitespace() || ",;:-".contains(c));
+    // There is no whole word that fits, so cut the first word between graphemes
+    let shortened = if shortened.is_empty() {
+        &input[..cut]
+    } else {
+        shortened
+    };
+    format!("{shortened}{ELLIPSIS}")
+}
+
+/// Strip markdown to plain text, keeping the words of links, emphasis and headings
+///
+/// Images, HTML and code blocks are dropped. Blocks are separated by a space.
+#[must_use]
+pub fn strip_markdown(markdown: &str) -> String {
+    let Ok(root) = markdown::to_mdast(markdown, &markdown::ParseOptions::gfm()) else {
+        return markdown.to_string();
+    };
+    let mut text = String::with_capacity(markdown.len());
+    push_text(&root, &mut text);
+    text
+}
+
+/// Append the text of a markdown node and its children
+fn push_text(node: &Node, text: &mut String) {
+    match node {
+        Node::Text(node) => text.push_str(&node.value),
+        Node::InlineCode(node) => text.push_str(&node.value),
+        Node::Break(_) => text.push(' '),
+        Node::Code(_) | Node::Html(_) | Node::Image(_) | Node::ImageReference(_) => (),
+        _ => {
+            for child in node.children().into_iter().flatten() {
+                push_text(child, text);
+            }
+            if matches!(
+                node,
+                Node::Paragraph(_)
+                    | Node::Heading(_)
+                    | Node::ListItem(_)
+                    | Node::Blockquote(_)
+                    | Node::TableCell(_)
+            ) {
+                text.push(' ');
+            }
+        }
+    }
+}
+
+/// Make a plain text excerpt of a markdown description
+///
+/// Markdown is stripped and whitespace collapsed, then the text is shortened with `shorten_string_words`.
+#[must_use]
+pub fn excerpt(description: &str, max_len: usize) -> String {
+    let text = strip_markdown(description)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    shorten_string_words(&text, max_len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +164,90 @@ mod tests {
             " This is a test.".to_string()
         );
     }
+
+    #[test]
+    fn test_shorten_string_words_mid_word() {
+        assert_eq!(
+            shorten_string_words("Live at the Brudenell Social Club", 14),
+            "Live at the…"
+        );
+        // The limit lands on the space after a word
+        assert_eq!(
+            shorten_string_words("Live at the Brudenell Social Club", 12),
+            "Live at the…"
+        );
+        assert_eq!(
+            shorten_string_words("Live at the Brudenell Social Club", 11),
+            "Live at…"
+        );
+        assert_eq!(shorten_string_words("Supercalifragilistic", 5), "Supe…");
+    }
+
+    #[test]
+    fn test_shorten_string_words_short_input() {
+        assert_eq!(shorten_string_words("Live at Leeds", 13), "Live at Leeds");
+        assert_eq!(shorten_string_words("Live at Leeds", 50), "Live at Leeds");
+        assert_eq!(shorten_string_words("", 5), "");
+    }
+
+    #[test]
+    fn test_shorten_string_words_leading_whitespace() {
+        assert_eq!(shorten_string_words("   Supercalifragilistic", 6), "Super…");
+        assert_eq!(
+            shorten_string_words("  Live at Leeds  ", 13),
+            "Live at Leeds"
+        );
+        assert_eq!(shorten_string_words("\n Live at Leeds", 10), "Live at…");
+    }
+
+    #[test]
+    fn test_shorten_string_words_counts_the_ellipsis() {
+        for max_chars in 1..30 {
+            let shortened = shorten_string_words("Live at the Brudenell Social Club", max_chars);
+            assert!(shortened.chars().count() <= max_chars, "{shortened:?}");
+        }
+    }
+
+    #[test]
+    fn test_shorten_string_words_multibyte() {
+        assert_eq!(shorten_string_words("Björk Guðmundsdóttir", 8), "Björk…");
+        assert_eq!(shorten_string_words("日本の 音楽です", 5), "日本の…");
+        // "e" followed by a combining acute accent is one grapheme
+        assert_eq!(shorten_string_words("Cafe\u{301}s", 5), "Cafe\u{301}s");
+        assert_eq!(shorten_string_words("Cafe\u{301}s", 4), "Caf…");
+        assert_eq!(shorten_string_words("Cafe\u{301}s!", 5), "Cafe\u{301}…");
+    }
+
+    #[test]
+    fn test_excerpt_long_markdown() {
+        let description = "# The Debut\n\nA **bold**, [first record](https://example.com) \
+            from the band.\n\n![Cover](cover.jpg)\n\n* Recorded live\n* Mixed in mono";
+        assert_eq!(
+            strip_markdown(description)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            "The Debut A bold, first record from the band. Recorded live Mixed in mono"
+        );
+        assert_eq!(excerpt(description, 22), "The Debut A bold…");
+        assert_eq!(excerpt(description, 24), "The Debut A bold, first…");
+        assert_eq!(excerpt(description, 30), "The Debut A bold, first…");
+        assert!(excerpt(description, 30).chars().count() <= 30);
+    }
+
+    #[test]
+    fn test_excerpt_short_description_unchanged() {
+        assert_eq!(excerpt("A short description.", 50), "A short description.");
+        assert_eq!(
+            excerpt("A   short\n\ndescription.", 50),
+            "A short description."
+        );
+        assert_eq!(excerpt("", 50), "");
+    }
+
+    #[test]
+    fn test_excerpt_single_long_word() {
+        assert_eq!(excerpt("Supercalifragilistic", 10), "Supercali…");
+        assert_eq!(excerpt("Ünïcödé wörds everywhere", 12), "Ünïcödé…");
+    }
 }
@@ -2,49 +2,119 @@
 //!
 //! This module provides a function to slugify a string, which means
 //! - Transliterate unicode text to ASCII
-//! - Remove double spaces from the text
-//! - Replace spaces with hyphens
+//! - Treat hyphens and underscores as word separators
+//! - Remove punctuation and collapse runs of whitespace
+//! - Replace spaces with hyphens, or the configured `SLUG_SEPARATOR`
+//! - Fall back to a timestamp if nothing is left
 //! # Example
 //! ```
 //! use white_label::utils::slugify::slugify;
 //! let slug = slugify("The Quick Brown Fox");
 //! assert_eq!(slug, "the-quick-brown-fox");
+//! assert_eq!(slugify("Motörhead"), "motorhead");
 //! ```
 //! # Note
 //! This function is useful for creating slugs for URLs, filenames, etc.
 
 use deunicode::deunicode;
 
+use crate::config::slug::{SLUG_SEPARATORS, slug_lowercase, slug_separator};
+
 /// Slugify a text
 ///
 /// This function takes a text and returns a slugified version of it.
 /// - It transliterates unicode text to ASCII
-/// - It removes double spaces from the text
-/// - It replaces spaces with hyphens
+/// - It treats hyphens and underscores as spaces, so `Knowles-Carter` keeps both words
+/// - It removes punctuation, and leading, trailing and repeated whitespace
+/// - It replaces spaces with the `SLUG_SEPARATOR`, and lowercases unless `SLUG_LOWERCASE` is false
+/// - If nothing is left, e.g. the text was only punctuation, the current timestamp is used
 #[must_use]
 pub fn slugify(text: &str) -> String {
+    let slug = slugify_with(text, slug_separator(), slug_lowercase());
+    if slug.is_empty() {
+        timestamp_slug()
+    } else {
+        slug
+    }
+}
+
+/// Slugify a text with the given separator and casing
+/// Unlike `slugify`, the slug is empty if nothing is left of the text.
+///
+/// # Arguments
+/// * `text` - The text to slugify
+/// * `separator` - The separator between words, e.g. `-` or `_`
+/// * `lowercase` - Should the slug be lowercased
+#[must_use]
+pub fn slugify_with(text: &str, separator: char, lowercase: bool) -> String {
     // Transliterates unicode text to ASCII
     let mut slug = deunicode(text);
-    let binding = slug.to_ascii_lowercase();
-    slug = binding;
+    if lowercase {
+        let binding = slug.to_ascii_lowercase();
+        slug = binding;
+    }
 
-    // Remove all punctuation from the text
+    // Separators split words, so they become spaces before the rest of the punctuation is removed
     let binding = slug
+        .replace(SLUG_SEPARATORS, " ")
         .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .filter(|c| c.is_ascii_alphanumeric() || c.is_ascii_whitespace())
         .collect::<String>();
     slug = binding;
 
-    // Remove double spaces from the text
-    let binding = clean_whitespace(&slug);
-    slug = binding;
+    // Join the words with the separator, dropping leading, trailing and repeated whitespace
+    slug.split_ascii_whitespace()
+        .collect::<Vec<&str>>()
+        .join(&separator.to_string())
+}
 
-    // Replace new lines with hyphens
-    let binding = slug.replace('\n', "-");
-    slug = binding;
+/// A slug for text that has nothing left to slugify, e.g. "!!!"
+#[must_use]
+pub fn timestamp_slug() -> String {
+    chrono::Utc::now().format("%Y%m%d%H%M%S").to_string()
+}
+
+/// Slugs that would clash with the applications own routes.
+pub const RESERVED_SLUGS: [&str; 11] = [
+    "admin",
+    "api",
+    "artists",
+    "login",
+    "logout",
+    "new",
+    "pages",
+    "profile",
+    "register",
+    "uploads",
+    "verify-email",
+];
 
-    // Replace spaces with hyphens
-    slug.to_lowercase().replace(' ', "-")
+/// Is the slug reserved for use by the application
+///
+/// The check ignores casing and the slug separator, so `Verify_Email` is reserved too.
+#[must_use]
+pub fn is_reserved_slug(slug: &str) -> bool {
+    let slug = slug.to_ascii_lowercase().replace('_', "-");
+    RESERVED_SLUGS.contains(&slug.as_str())
+}
+
+/// Is the slug locked, so it shouldn't be regenerated when the name changes
+///
+/// Slugs lock automatically once the entity has been published, so published URLs keep working.
+/// An explicit `lock_slug` always wins over the automatic behaviour.
+///
+/// # Arguments
+/// * `lock_slug` - The explicit lock, if one has been set
+/// * `published_at` - The stored and new published dates of the entity
+#[must_use]
+pub fn is_slug_locked(
+    lock_slug: Option<bool>,
+    published_at: &[Option<chrono::DateTime<chrono::Utc>>],
+) -> bool {
+    lock_slug.unwrap_or_else(|| {
+        let now = chrono::Utc::now();
+        published_at.iter().flatten().any(|date| *date <= now)
+    })
 }
 
 /// Trim whitespace from a string without using regex
@@ -64,6 +134,26 @@ pub fn clean_whitespace(s: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_reserved_slug() {
+        assert!(is_reserved_slug("admin"));
+        assert!(is_reserved_slug(&slugify("New")));
+        assert!(!is_reserved_slug("the-quick-brown-fox"));
+    }
+
+    #[test]
+    fn test_is_slug_locked() {
+        let past = Some(chrono::Utc::now() - chrono::Duration::days(1));
+        let future = Some(chrono::Utc::now() + chrono::Duration::days(1));
+
+        assert!(!is_slug_locked(None, &[None, None]));
+        assert!(!is_slug_locked(None, &[None, future]));
+        assert!(is_slug_locked(None, &[past, None]));
+        assert!(is_slug_locked(None, &[None, past]));
+        assert!(!is_slug_locked(Some(false), &[past, past]));
+        assert!(is_slug_locked(Some(true), &[None, None]));
+    }
+
     #[test]
     fn test_slugify_with_stop_words() {
         assert_eq!(slugify("The Quick Brown Fox"), "the-quick-brown-fox");
@@ -87,6 +177,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_slugify_with_accents() {
+        assert_eq!(slugify("Björk"), "bjork");
+        assert_eq!(slugify("Motörhead"), "motorhead");
+        assert_eq!(slugify("Sigur Rós"), "sigur-ros");
+        assert_eq!(slugify("Beyoncé Knowles-Carter"), "beyonce-knowles-carter");
+    }
+
+    #[test]
+    fn test_slugify_with_separators() {
+        assert_eq!(slugify("Jay-Z"), "jay-z");
+        assert_eq!(slugify("deep_house"), "deep-house");
+        assert_eq!(slugify("AC/DC"), "acdc");
+        assert_eq!(slugify_with("Knowles-Carter", '_', true), "knowles_carter");
+    }
+
+    #[test]
+    fn test_slugify_with_tabs() {
+        assert_eq!(slugify("The\tQuick \t Brown\r\nFox"), "the-quick-brown-fox");
+    }
+
+    #[test]
+    fn test_slugify_empty_falls_back_to_timestamp() {
+        for text in ["", "   ", "!!!", "-!-"] {
+            let slug = slugify(text);
+            assert_eq!(slug.len(), 14, "{text:?} slugified to {slug:?}");
+            assert!(slug.chars().all(|c| c.is_ascii_digit()));
+        }
+        assert_eq!(slugify_with("!!!", '-', true), "");
+    }
+
     #[test]
     fn test_slugify_with_punctuation() {
         assert_eq!(slugify("The Quick, Brown Fox"), "the-quick-brown-fox");
@@ -97,6 +218,13 @@ mod tests {
         assert_eq!(slugify("⏩ 🦊"), "fast-forward-fox-face");
     }
 
+    #[test]
+    fn test_slugify_with_only_emoji() {
+        let slug = slugify("🦊🦊");
+        assert_eq!(slug, "fox-face-fox-face");
+        assert!(slug.is_ascii());
+    }
+
     #[test]
     fn test_trim_whitespace_with_double_spaces() {
         assert_eq!(slugify("The  Quick  Brown  Fox"), "the-quick-brown-fox");
@@ -139,4 +267,39 @@ mod tests {
     fn test_new_line_character() {
         assert_eq!(slugify("The\nQuick\nBrown\nFox\n"), "the-quick-brown-fox");
     }
+
+    #[test]
+    fn test_slugify_with_underscore_separator() {
+        assert_eq!(
+            slugify_with("The Quick Brown Fox", '_', true),
+            "the_quick_brown_fox"
+        );
+    }
+
+    #[test]
+    fn test_slugify_with_preserved_casing() {
+        assert_eq!(
+            slugify_with("The Quick Brown Fox", '-', false),
+            "The-Quick-Brown-Fox"
+        );
+    }
+
+    #[test]
+    fn test_slugify_with_non_default_settings_dedupes() {
+        assert_eq!(
+            slugify_with("  The  Quick,   Brown\nFox-!- ", '_', false),
+            "The_Quick_Brown_Fox"
+        );
+    }
+
+    #[test]
+    fn test_is_reserved_slug_with_non_default_settings() {
+        assert!(is_reserved_slug(&slugify_with("New", '_', false)));
+        assert!(is_reserved_slug(&slugify_with("Verify Email", '_', false)));
+        assert!(!is_reserved_slug(&slugify_with(
+            "Verify Emails",
+            '_',
+            false
+        )));
+    }
 }
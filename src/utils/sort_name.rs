@@ -0,0 +1,54 @@
+//! Derive names for alphabetical sorting
+//!
+//! Leading articles are ignored when sorting, so "The Beatles" sorts under B.
+//! The `sort_name_or_default` database function applies the same rule in ordering queries.
+//! # Example
+//! ```
+//! use white_label::utils::sort_name::default_sort_name;
+//! assert_eq!(default_sort_name("The Beatles"), "Beatles");
+//! assert_eq!(default_sort_name("A Tribe Called Quest"), "Tribe Called Quest");
+//! ```
+
+/// The articles stripped from the start of a name when sorting.
+pub const LEADING_ARTICLES: [&str; 3] = ["the", "an", "a"];
+
+/// Derive the default sort name from a name
+///
+/// Strips a leading "The", "An" or "A", as long as something is left.
+#[must_use]
+pub fn default_sort_name(name: &str) -> String {
+    let name = name.trim();
+    for article in LEADING_ARTICLES {
+        if let Some(prefix) = name.get(..article.len())
+            && prefix.eq_ignore_ascii_case(article)
+            && name[article.len()..].starts_with(char::is_whitespace)
+        {
+            return name[article.len()..].trim_start().to_string();
+        }
+    }
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_sort_name_strips_leading_article() {
+        assert_eq!(default_sort_name("The Beatles"), "Beatles");
+        assert_eq!(default_sort_name("the  beatles"), "beatles");
+        assert_eq!(default_sort_name("An Horse"), "Horse");
+        assert_eq!(
+            default_sort_name("A Tribe Called Quest"),
+            "Tribe Called Quest"
+        );
+    }
+
+    #[test]
+    fn test_default_sort_name_keeps_other_names() {
+        assert_eq!(default_sort_name("Theatre of Hate"), "Theatre of Hate");
+        assert_eq!(default_sort_name("Abba"), "Abba");
+        assert_eq!(default_sort_name("The"), "The");
+        assert_eq!(default_sort_name(" Blur "), "Blur");
+    }
+}
@@ -1,12 +1,30 @@
 //! Split a string at the first occurrence of a colon
 
 /// Split a string at the first occurrence of a colon
+///
 /// Returns a tuple of the string before the colon and the string after the semi-colon
+/// A colon escaped with a backslash (`\:`) is not a separator, and is unescaped in the result
+///
+/// # Example
+/// ```
+/// use white_label::utils::split_at_colon::split_at_colon;
+/// assert_eq!(split_at_colon(r"Remix\: Extended : Club"), ("Remix: Extended".to_string(), "Club".to_string()));
+/// ```
 pub fn split_at_colon(s: &str) -> (String, String) {
-    let mut split = s.splitn(2, ':');
-    let first = split.next().unwrap_or_default().trim().to_string();
-    let second = split.next().unwrap_or_default().trim().to_string();
-    (first, second)
+    let mut first = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with(':') => {
+                first.push(':');
+                chars.next();
+            }
+            ':' => break,
+            _ => first.push(c),
+        }
+    }
+    let second = chars.as_str().replace("\\:", ":");
+    (first.trim().to_string(), second.trim().to_string())
 }
 
 #[cfg(test)]
@@ -43,4 +61,29 @@ mod tests {
             ("A".to_string(), "B:C".to_string())
         );
     }
+
+    #[test]
+    fn test_split_string_with_escaped_colon() {
+        assert_eq!(
+            split_at_colon(r"a\:b:c"),
+            ("a:b".to_string(), "c".to_string())
+        );
+        assert_eq!(
+            split_at_colon(r"a:b\:c"),
+            ("a".to_string(), "b:c".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_string_with_only_escaped_colons() {
+        assert_eq!(split_at_colon(r"a\:b"), ("a:b".to_string(), String::new()));
+    }
+
+    #[test]
+    fn test_split_string_with_backslash() {
+        assert_eq!(
+            split_at_colon(r"a\b:c"),
+            (r"a\b".to_string(), "c".to_string())
+        );
+    }
 }
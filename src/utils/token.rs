@@ -0,0 +1,47 @@
+//! Secret tokens sent to users, e.g. in password reset and email verification links
+//!
+//! Only a hash of each token is stored, so a leaked table can't be used in place of the links.
+
+/// Generate a random alphanumeric token
+#[cfg(feature = "ssr")]
+#[must_use]
+pub fn generate_token(length: usize) -> String {
+    use rand::{Rng, distr::Alphanumeric};
+
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+/// Hash a token, so it can be stored or looked up
+/// The hash is the hex encoded SHA-256 digest, which matches `encode(sha256(...), 'hex')` in Postgres.
+#[cfg(feature = "ssr")]
+#[must_use]
+pub fn hash_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_token() {
+        let token = generate_token(32);
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(token, generate_token(32));
+    }
+
+    #[test]
+    fn test_hash_token() {
+        assert_eq!(
+            hash_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
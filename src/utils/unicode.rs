@@ -0,0 +1,39 @@
+//! Normalise unicode text
+//!
+//! The same accented name can be typed as one precomposed character or as a letter and a combining accent.
+//! Normalising names to NFC means both forms are stored, slugged and compared the same way.
+//! # Example
+//! ```
+//! use white_label::utils::unicode::normalise_name;
+//! assert_eq!(normalise_name("Beyonce\u{301}"), "Beyonc\u{e9}");
+//! ```
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalise a name to Unicode Normalization Form C (NFC)
+#[must_use]
+pub fn normalise_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::slugify::slugify;
+
+    #[test]
+    fn test_normalise_name() {
+        let nfc = "Sigur R\u{f3}s";
+        let nfd = "Sigur Ro\u{301}s";
+        assert_ne!(nfc, nfd);
+
+        assert_eq!(normalise_name(nfd), nfc);
+        assert_eq!(normalise_name(nfc), nfc);
+        assert_eq!(slugify(&normalise_name(nfd)), slugify(&normalise_name(nfc)));
+    }
+
+    #[test]
+    fn test_normalise_name_ascii() {
+        assert_eq!(normalise_name("The Beatles"), "The Beatles");
+    }
+}
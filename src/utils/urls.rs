@@ -0,0 +1,155 @@
+//! Helpers for working with URLs
+//!
+//! The public URL builders are the single source of truth for the site's public routes.
+//! Paths are relative to the site root and used for links within the app.
+//! URLs are absolute, using the `BASE_URL` from config, and used in exports, feeds and emails.
+
+use crate::config::site::base_url;
+
+/// Check that a string is an absolute http or https URL with a host
+///
+/// # Example
+/// ```
+/// use white_label::utils::urls::is_valid_url;
+/// assert!(is_valid_url("https://example.com/preview.mp3"));
+/// assert!(!is_valid_url("not a url"));
+/// ```
+#[must_use]
+pub fn is_valid_url(url: &str) -> bool {
+    url::Url::parse(url).is_ok_and(|parsed| {
+        matches!(parsed.scheme(), "http" | "https") && parsed.host_str().is_some()
+    })
+}
+
+/// Join a base URL and a path into an absolute URL
+#[must_use]
+pub fn absolute_url(base: &str, path: &str) -> String {
+    format!("{}{path}", base.trim_end_matches('/'))
+}
+
+/// The path to an artist's public page
+#[must_use]
+pub fn artist_path(slug: &str) -> String {
+    format!("/artists/{slug}")
+}
+
+/// The path to a release's public page
+#[must_use]
+pub fn release_path(artist_slug: &str, release_slug: &str) -> String {
+    format!("{}/{release_slug}", artist_path(artist_slug))
+}
+
+/// The path to a track, on its release's public page
+#[must_use]
+pub fn track_path(artist_slug: &str, release_slug: &str, track_slug: &str) -> String {
+    format!("{}#{track_slug}", release_path(artist_slug, release_slug))
+}
+
+/// The path to a page
+#[must_use]
+pub fn page_path(slug: &str) -> String {
+    format!("/pages/{slug}")
+}
+
+/// The absolute URL of an artist's public page
+///
+/// # Example
+/// ```
+/// use white_label::utils::urls::artist_url;
+/// assert!(artist_url("test-artist").ends_with("/artists/test-artist"));
+/// ```
+#[must_use]
+pub fn artist_url(slug: &str) -> String {
+    absolute_url(&base_url(), &artist_path(slug))
+}
+
+/// The absolute URL of a release's public page
+#[must_use]
+pub fn release_url(artist_slug: &str, release_slug: &str) -> String {
+    absolute_url(&base_url(), &release_path(artist_slug, release_slug))
+}
+
+/// The absolute URL of a track, on its release's public page
+#[must_use]
+pub fn track_url(artist_slug: &str, release_slug: &str, track_slug: &str) -> String {
+    absolute_url(
+        &base_url(),
+        &track_path(artist_slug, release_slug, track_slug),
+    )
+}
+
+/// The absolute URL of a page
+#[must_use]
+pub fn page_url(slug: &str) -> String {
+    absolute_url(&base_url(), &page_path(slug))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(artist_path("test-artist"), "/artists/test-artist");
+        assert_eq!(
+            release_path("test-artist", "test-release"),
+            "/artists/test-artist/test-release"
+        );
+        assert_eq!(
+            track_path("test-artist", "test-release", "test-track"),
+            "/artists/test-artist/test-release#test-track"
+        );
+        assert_eq!(page_path("about"), "/pages/about");
+    }
+
+    #[test]
+    fn test_urls_use_base_url() {
+        let base = base_url();
+        assert_eq!(
+            artist_url("test-artist"),
+            format!("{base}/artists/test-artist")
+        );
+        assert_eq!(
+            release_url("test-artist", "test-release"),
+            format!("{base}/artists/test-artist/test-release")
+        );
+        assert_eq!(
+            track_url("test-artist", "test-release", "test-track"),
+            format!("{base}/artists/test-artist/test-release#test-track")
+        );
+        assert_eq!(page_url("about"), format!("{base}/pages/about"));
+    }
+
+    #[test]
+    fn test_absolute_url_changing_base() {
+        for base in ["https://label.example.com", "https://other.example.org/"] {
+            let base_without_slash = base.trim_end_matches('/');
+            for path in [
+                artist_path("test-artist"),
+                release_path("test-artist", "test-release"),
+                track_path("test-artist", "test-release", "test-track"),
+                page_path("about"),
+            ] {
+                assert_eq!(
+                    absolute_url(base, &path),
+                    format!("{base_without_slash}{path}")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_valid_url() {
+        assert!(is_valid_url("https://example.com"));
+        assert!(is_valid_url("http://example.com/path?query=1"));
+    }
+
+    #[test]
+    fn test_is_valid_url_invalid() {
+        assert!(!is_valid_url(""));
+        assert!(!is_valid_url("example.com"));
+        assert!(!is_valid_url("https://"));
+        assert!(!is_valid_url("ftp://example.com/file.mp3"));
+        assert!(!is_valid_url("javascript:alert(1)"));
+    }
+}
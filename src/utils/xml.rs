@@ -0,0 +1,42 @@
+//! Helpers for writing XML documents
+//!
+//! # Example
+//! ```
+//! use white_label::utils::xml::escape_xml;
+//! assert_eq!(escape_xml("Drum & Bass"), "Drum &amp; Bass");
+//! ```
+
+/// Escape text so it can be placed inside an XML element or attribute
+#[must_use]
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">Rock & 'Roll'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Rock &amp; &apos;Roll&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_escape_xml_plain_text() {
+        assert_eq!(escape_xml("Test Release 1"), "Test Release 1");
+    }
+}